@@ -4,12 +4,13 @@ use anyhow::Result;
 
 use distribution_filename::WheelFilename;
 use pep508_rs::VerbatimUrl;
+use pypi_types::Provenance;
 use uv_normalize::PackageName;
 
 use crate::direct_url::{DirectUrl, LocalFileUrl};
 use crate::{
-    BuiltDist, Dist, DistributionMetadata, InstalledMetadata, InstalledVersion, Name, SourceDist,
-    VersionOrUrl,
+    BuiltDist, Dist, DistributionMetadata, IndexUrl, InstalledMetadata, InstalledVersion, Name,
+    SourceDist, VersionOrUrl,
 };
 
 /// A built distribution (wheel) that exists in the local cache.
@@ -25,6 +26,10 @@ pub enum CachedDist {
 pub struct CachedRegistryDist {
     pub filename: WheelFilename,
     pub path: PathBuf,
+    /// The index from which the distribution was fetched.
+    pub index: IndexUrl,
+    /// Whether the wheel was built locally from a source distribution.
+    pub built: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -39,9 +44,12 @@ impl CachedDist {
     /// Initialize a [`CachedDist`] from a [`Dist`].
     pub fn from_remote(remote: Dist, filename: WheelFilename, path: PathBuf) -> Self {
         match remote {
-            Dist::Built(BuiltDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
+            Dist::Built(BuiltDist::Registry(dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                index: dist.index,
+                built: false,
+            }),
             Dist::Built(BuiltDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
@@ -54,9 +62,12 @@ impl CachedDist {
                 path,
                 editable: false,
             }),
-            Dist::Source(SourceDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
+            Dist::Source(SourceDist::Registry(dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                index: dist.index,
+                built: true,
+            }),
             Dist::Source(SourceDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
@@ -104,6 +115,17 @@ impl CachedDist {
         }
     }
 
+    /// Return the [`Provenance`] of the distribution, if it was fetched from a registry.
+    pub fn provenance(&self) -> Option<Provenance> {
+        match self {
+            CachedDist::Registry(dist) => Some(Provenance {
+                built: dist.built,
+                index: Some(dist.index.to_string()),
+            }),
+            CachedDist::Url(_) => None,
+        }
+    }
+
     pub fn editable(&self) -> bool {
         match self {
             CachedDist::Registry(_) => false,
@@ -144,8 +144,12 @@ impl TryFrom<&DirectArchiveUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &DirectArchiveUrl) -> Result<Self, Self::Error> {
+        // The subdirectory is recorded in its own field, so strip it (and any other fragments)
+        // from the URL itself, per PEP 610.
+        let mut url = value.url.clone();
+        url.set_fragment(None);
         Ok(pypi_types::DirectUrl::ArchiveUrl {
-            url: value.url.clone(),
+            url,
             archive_info: pypi_types::ArchiveInfo {
                 hash: None,
                 hashes: None,
@@ -210,6 +214,8 @@ impl From<DirectGitUrl> for Url {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use anyhow::Result;
     use url::Url;
 
@@ -246,4 +252,48 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn direct_url_json_subdirectory() -> Result<()> {
+        // The subdirectory should be recorded separately from the URL, and restored when
+        // converting back to a URL.
+        let expected = Url::parse(
+            "https://github.com/foo-labs/foo/archive/master.zip#subdirectory=packages/bar",
+        )?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&expected)?)?;
+        let pypi_types::DirectUrl::ArchiveUrl {
+            url, subdirectory, ..
+        } = &direct_url
+        else {
+            panic!("Expected an archive URL");
+        };
+        assert_eq!(
+            url.as_str(),
+            "https://github.com/foo-labs/foo/archive/master.zip"
+        );
+        assert_eq!(subdirectory.as_deref(), Some(Path::new("packages/bar")));
+        assert_eq!(expected, Url::from(direct_url));
+
+        let expected = Url::parse(
+            "git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_a",
+        )?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&expected)?)?;
+        let pypi_types::DirectUrl::VcsUrl {
+            url, subdirectory, ..
+        } = &direct_url
+        else {
+            panic!("Expected a VCS URL");
+        };
+        assert_eq!(
+            url.as_str(),
+            "https://github.com/pypa/sample-namespace-packages.git"
+        );
+        assert_eq!(
+            subdirectory.as_deref(),
+            Some(Path::new("pkg_resources/pkg_a"))
+        );
+        assert_eq!(expected, Url::from(direct_url));
+
+        Ok(())
+    }
 }
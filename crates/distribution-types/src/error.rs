@@ -1,5 +1,7 @@
 use url::Url;
 
+use uv_normalize::PackageName;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -13,4 +15,7 @@ pub enum Error {
 
     #[error("Distribution not found at: {0}")]
     NotFound(Url),
+
+    #[error("Requested package name `{0}` does not match `{1}` in the distribution filename: {2}")]
+    PackageNameMismatch(PackageName, PackageName, Url),
 }
@@ -7,6 +7,7 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use pep508_rs::split_scheme;
 use uv_fs::normalize_url_path;
 
@@ -66,6 +67,12 @@ impl Deref for IndexUrl {
     }
 }
 
+impl CacheKey for IndexUrl {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        (**self).cache_key(state);
+    }
+}
+
 /// A directory with distributions or a URL to an HTML file with a flat listing of distributions.
 ///
 /// Also known as `--find-links`.
@@ -118,10 +125,114 @@ impl Display for FlatIndexLocation {
     }
 }
 
+impl CacheKey for FlatIndexLocation {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            FlatIndexLocation::Path(path) => {
+                0u8.cache_key(state);
+                path.cache_key(state);
+            }
+            FlatIndexLocation::Url(url) => {
+                1u8.cache_key(state);
+                url.cache_key(state);
+            }
+        }
+    }
+}
+
+/// The strategy to use when a package is available on multiple indexes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum IndexStrategy {
+    /// Only use versions from the first index that contains the package, such that a package on
+    /// an extra index can't shadow the same package on the primary index (i.e., a dependency
+    /// confusion attack).
+    #[default]
+    FirstMatch,
+    /// Pool the versions available across all indexes. If a version is available on multiple
+    /// indexes, the distributions from the first such index are used.
+    BestMatch,
+}
+
+impl CacheKey for IndexStrategy {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            IndexStrategy::FirstMatch => 0u8.cache_key(state),
+            IndexStrategy::BestMatch => 1u8.cache_key(state),
+        }
+    }
+}
+
+/// The format in which an index serves its simple API responses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexFormat {
+    /// The HTML-based simple API (PEP 503).
+    Html,
+    /// The JSON-based simple API (PEP 691).
+    Json,
+}
+
+impl CacheKey for IndexFormat {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            IndexFormat::Html => 0u8.cache_key(state),
+            IndexFormat::Json => 1u8.cache_key(state),
+        }
+    }
+}
+
+/// Overrides for an index that deviates from the simple API specification, as is common among
+/// private index servers (e.g., devpi, Artifactory, or Nexus).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct IndexOverride {
+    /// The URL of the index to which the overrides apply.
+    pub url: Url,
+    /// The format of the index's responses, which takes precedence over the `Content-Type` header
+    /// (e.g., for servers that respond with `application/octet-stream`).
+    #[serde(default)]
+    pub format: Option<IndexFormat>,
+    /// Any additional URLs from which the index serves its artifacts (e.g.,
+    /// `https://files.example.com`), to which the credentials of the index should be sent.
+    #[serde(default)]
+    pub artifact_urls: Vec<Url>,
+    /// The mirrors of the index (e.g., `https://mirror.example.com/simple`), which are tried in
+    /// order when the index is unreachable.
+    #[serde(default)]
+    pub mirrors: Vec<Url>,
+}
+
+impl IndexOverride {
+    /// Returns `true` if the override applies to the given index, ignoring any credentials and
+    /// trailing slash.
+    pub fn matches(&self, index: &Url) -> bool {
+        fn normalize(url: &Url) -> (&str, Option<&str>, Option<u16>, &str) {
+            (
+                url.scheme(),
+                url.host_str(),
+                url.port_or_known_default(),
+                url.path().trim_end_matches('/'),
+            )
+        }
+        normalize(&self.url) == normalize(index)
+    }
+}
+
+impl CacheKey for IndexOverride {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.url.cache_key(state);
+        self.format.cache_key(state);
+        self.artifact_urls.cache_key(state);
+        self.mirrors.cache_key(state);
+    }
+}
+
 /// The index locations to use for fetching packages.
 ///
-/// "pip treats all package sources equally" (<https://github.com/pypa/pip/issues/8606#issuecomment-788754817>),
-/// and so do we, i.e., you can't rely that on any particular order of querying indices.
+/// Indexes are queried in order. By default, only the first index that contains a package is used
+/// for that package; with [`IndexStrategy::BestMatch`], versions are instead pooled across all
+/// indexes, as "pip treats all package sources equally" (<https://github.com/pypa/pip/issues/8606#issuecomment-788754817>).
 ///
 /// If the fields are none and empty, ignore the package index, instead rely on local archives and
 /// caches.
@@ -132,6 +243,10 @@ pub struct IndexLocations {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
+    strategy: IndexStrategy,
+    fallback: bool,
+    lenient: bool,
+    overrides: Vec<IndexOverride>,
 }
 
 impl Default for IndexLocations {
@@ -141,10 +256,26 @@ impl Default for IndexLocations {
             index: Some(IndexUrl::Pypi),
             extra_index: Vec::new(),
             flat_index: Vec::new(),
+            strategy: IndexStrategy::default(),
+            fallback: false,
+            lenient: false,
+            overrides: Vec::new(),
         }
     }
 }
 
+impl CacheKey for IndexLocations {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.index.cache_key(state);
+        self.extra_index.cache_key(state);
+        self.flat_index.cache_key(state);
+        self.strategy.cache_key(state);
+        self.fallback.cache_key(state);
+        self.lenient.cache_key(state);
+        self.overrides.cache_key(state);
+    }
+}
+
 impl IndexLocations {
     /// Determine the index URLs to use for fetching packages.
     pub fn from_args(
@@ -158,16 +289,48 @@ impl IndexLocations {
                 index: None,
                 extra_index: Vec::new(),
                 flat_index,
+                strategy: IndexStrategy::default(),
+                fallback: false,
+                lenient: false,
+                overrides: Vec::new(),
             }
         } else {
             Self {
                 index: Some(index),
                 extra_index,
                 flat_index,
+                strategy: IndexStrategy::default(),
+                fallback: false,
+                lenient: false,
+                overrides: Vec::new(),
             }
         }
     }
 
+    /// Set the strategy to use when a package is available on multiple indexes.
+    #[must_use]
+    pub fn with_strategy(self, strategy: IndexStrategy) -> Self {
+        Self { strategy, ..self }
+    }
+
+    /// Set whether to fall back to the remaining indexes when an index is unreachable.
+    #[must_use]
+    pub fn with_fallback(self, fallback: bool) -> Self {
+        Self { fallback, ..self }
+    }
+
+    /// Set whether to skip (rather than reject) any malformed links on an HTML index page.
+    #[must_use]
+    pub fn with_lenient(self, lenient: bool) -> Self {
+        Self { lenient, ..self }
+    }
+
+    /// Set the overrides to apply to non-compliant indexes.
+    #[must_use]
+    pub fn with_overrides(self, overrides: Vec<IndexOverride>) -> Self {
+        Self { overrides, ..self }
+    }
+
     /// Combine a set of index locations.
     ///
     /// If either the current or the other index locations have `no_index` set, the result will
@@ -187,12 +350,20 @@ impl IndexLocations {
                 index: None,
                 extra_index: Vec::new(),
                 flat_index,
+                strategy: self.strategy,
+                fallback: self.fallback,
+                lenient: self.lenient,
+                overrides: self.overrides,
             }
         } else {
             Self {
                 index: self.index.or(index),
                 extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
                 flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
+                strategy: self.strategy,
+                fallback: self.fallback,
+                lenient: self.lenient,
+                overrides: self.overrides,
             }
         }
     }
@@ -219,11 +390,35 @@ impl<'a> IndexLocations {
         self.flat_index.iter()
     }
 
+    /// Return the strategy to use when a package is available on multiple indexes.
+    pub fn strategy(&self) -> IndexStrategy {
+        self.strategy
+    }
+
+    /// Return `true` if an unreachable index should be skipped in favor of the remaining indexes.
+    pub fn fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Return `true` if malformed links on an HTML index page should be skipped with a warning.
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Return an iterator over the [`IndexOverride`] entries.
+    pub fn overrides(&'a self) -> impl Iterator<Item = &'a IndexOverride> + 'a {
+        self.overrides.iter()
+    }
+
     /// Clone the index locations into a [`IndexUrls`] instance.
     pub fn index_urls(&'a self) -> IndexUrls {
         IndexUrls {
             index: self.index.clone(),
             extra_index: self.extra_index.clone(),
+            strategy: self.strategy,
+            fallback: self.fallback,
+            lenient: self.lenient,
+            overrides: self.overrides.clone(),
         }
     }
 }
@@ -235,6 +430,10 @@ impl<'a> IndexLocations {
 pub struct IndexUrls {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
+    strategy: IndexStrategy,
+    fallback: bool,
+    lenient: bool,
+    overrides: Vec<IndexOverride>,
 }
 
 impl Default for IndexUrls {
@@ -243,6 +442,10 @@ impl Default for IndexUrls {
         Self {
             index: Some(IndexUrl::Pypi),
             extra_index: Vec::new(),
+            strategy: IndexStrategy::default(),
+            fallback: false,
+            lenient: false,
+            overrides: Vec::new(),
         }
     }
 }
@@ -257,6 +460,38 @@ impl<'a> IndexUrls {
     pub fn no_index(&self) -> bool {
         self.index.is_none() && self.extra_index.is_empty()
     }
+
+    /// Return the strategy to use when a package is available on multiple indexes.
+    pub fn strategy(&self) -> IndexStrategy {
+        self.strategy
+    }
+
+    /// Return `true` if an unreachable index should be skipped in favor of the remaining indexes.
+    ///
+    /// An index is unreachable if the request times out, fails to connect, or returns a server
+    /// error (5xx). Disabled by default, since skipping an unreachable primary index would allow
+    /// an extra index to serve a package in its place.
+    pub fn fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Return `true` if malformed links on an HTML index page should be skipped with a warning,
+    /// rather than failing the request for the entire page.
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Return an iterator over the [`IndexOverride`] entries.
+    pub fn overrides(&'a self) -> impl Iterator<Item = &'a IndexOverride> + 'a {
+        self.overrides.iter()
+    }
+
+    /// Return the [`IndexOverride`] that applies to the given index, if any.
+    pub fn override_for(&'a self, index: &IndexUrl) -> Option<&'a IndexOverride> {
+        self.overrides
+            .iter()
+            .find(|index_override| index_override.matches(index))
+    }
 }
 
 impl From<IndexLocations> for IndexUrls {
@@ -264,6 +499,10 @@ impl From<IndexLocations> for IndexUrls {
         Self {
             index: locations.index,
             extra_index: locations.extra_index,
+            strategy: locations.strategy,
+            fallback: locations.fallback,
+            lenient: locations.lenient,
+            overrides: locations.overrides,
         }
     }
 }
@@ -18,6 +18,12 @@ pub enum InstalledDist {
     Registry(InstalledRegistryDist),
     /// The distribution was derived from an arbitrary URL.
     Url(InstalledDirectUrlDist),
+    /// The distribution was installed by a legacy installer, and is represented by an `.egg-info`
+    /// directory (or file) rather than a `.dist-info` directory.
+    EggInfo(InstalledEggInfo),
+    /// The distribution was installed in development mode (i.e., `setup.py develop`), and is
+    /// represented by an `.egg-link` file.
+    LegacyEditable(InstalledLegacyEditable),
 }
 
 #[derive(Debug, Clone)]
@@ -36,9 +42,33 @@ pub struct InstalledDirectUrlDist {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone)]
+pub struct InstalledEggInfo {
+    pub name: PackageName,
+    pub version: Version,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledLegacyEditable {
+    pub name: PackageName,
+    pub version: Version,
+    /// The path to the `.egg-link` file in `site-packages`.
+    pub egg_link: PathBuf,
+    /// The source directory that the `.egg-link` file points to.
+    pub target: PathBuf,
+    /// The source directory, as a `file://` URL.
+    pub target_url: Url,
+    /// The path to the `.egg-info` directory within the source directory.
+    pub egg_info: PathBuf,
+}
+
 impl InstalledDist {
     /// Try to parse a distribution from a `.dist-info` directory name (like `django-5.0a1.dist-info`).
     ///
+    /// Also recognizes distributions installed by legacy installers: `.egg-info` directories or
+    /// files (like `django-5.0a1-py3.12.egg-info`) and `.egg-link` files (like `django.egg-link`).
+    ///
     /// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#recording-installed-packages>
     pub fn try_from_path(path: &Path) -> Result<Option<Self>> {
         if path.extension().is_some_and(|ext| ext == "dist-info") {
@@ -70,14 +100,127 @@ impl InstalledDist {
                 })))
             };
         }
+
+        if path.extension().is_some_and(|ext| ext == "egg-info") {
+            let Some(file_stem) = path.file_stem() else {
+                return Ok(None);
+            };
+            let Some(file_stem) = file_stem.to_str() else {
+                return Ok(None);
+            };
+
+            // The file stem is `{name}-{version}`, optionally followed by `-py{X.Y}` and a
+            // platform tag. Older tools omit the version entirely, in which case we fall back to
+            // `PKG-INFO`.
+            let mut parts = file_stem.split('-');
+            let name = PackageName::from_str(parts.next().unwrap_or_default())?;
+            let version = if let Some(version) = parts.next() {
+                Version::from_str(version).map_err(|err| anyhow!(err))?
+            } else {
+                let pkg_info = if path.is_dir() {
+                    path.join("PKG-INFO")
+                } else {
+                    path.to_path_buf()
+                };
+                Self::read_pkg_info(&pkg_info)?.version
+            };
+
+            return Ok(Some(Self::EggInfo(InstalledEggInfo {
+                name,
+                version,
+                path: path.to_path_buf(),
+            })));
+        }
+
+        if path.extension().is_some_and(|ext| ext == "egg-link") {
+            let Some(file_stem) = path.file_stem() else {
+                return Ok(None);
+            };
+            let Some(file_stem) = file_stem.to_str() else {
+                return Ok(None);
+            };
+            let name = PackageName::from_str(file_stem)?;
+
+            // The first line of the `.egg-link` file is the path to the source directory.
+            let contents = fs::read_to_string(path)?;
+            let Some(target) = contents.lines().next().map(str::trim) else {
+                return Err(anyhow!(
+                    "Invalid `.egg-link` file (expected a target path): {}",
+                    path.normalized_display()
+                ));
+            };
+            let target = PathBuf::from(target);
+
+            // Find the `.egg-info` directory in the source directory.
+            let Some(egg_info) = Self::find_egg_info(&target, &name)? else {
+                return Err(anyhow!(
+                    "Failed to find `.egg-info` directory for `{name}` in: {}",
+                    target.normalized_display()
+                ));
+            };
+            let version = Self::read_pkg_info(&egg_info.join("PKG-INFO"))?.version;
+
+            let target_url = Url::from_directory_path(&target).map_err(|()| {
+                anyhow!(
+                    "Invalid `.egg-link` target: {}",
+                    target.normalized_display()
+                )
+            })?;
+
+            return Ok(Some(Self::LegacyEditable(InstalledLegacyEditable {
+                name,
+                version,
+                egg_link: path.to_path_buf(),
+                target,
+                target_url,
+                egg_info,
+            })));
+        }
+
+        Ok(None)
+    }
+
+    /// Find the `.egg-info` directory for the given package within a source directory.
+    fn find_egg_info(target: &Path, name: &PackageName) -> Result<Option<PathBuf>> {
+        let entries = match fs::read_dir(target) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if !path.extension().is_some_and(|ext| ext == "egg-info") {
+                continue;
+            }
+            let Some(file_stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let file_stem = file_stem.split('-').next().unwrap_or_default();
+            if PackageName::from_str(file_stem).is_ok_and(|stem| stem == *name) {
+                return Ok(Some(path));
+            }
+        }
         Ok(None)
     }
 
+    /// Read a `PKG-INFO` file, as written to `.egg-info` directories by `setuptools`.
+    fn read_pkg_info(path: &Path) -> Result<pypi_types::Metadata21> {
+        let contents = fs::read(path)?;
+        pypi_types::Metadata21::parse(&contents).with_context(|| {
+            format!(
+                "Failed to parse PKG-INFO file at: {}",
+                path.normalized_display()
+            )
+        })
+    }
+
     /// Return the [`Path`] at which the distribution is stored on-disk.
     pub fn path(&self) -> &Path {
         match self {
             Self::Registry(dist) => &dist.path,
             Self::Url(dist) => &dist.path,
+            Self::EggInfo(dist) => &dist.path,
+            Self::LegacyEditable(dist) => &dist.egg_link,
         }
     }
 
@@ -86,6 +229,8 @@ impl InstalledDist {
         match self {
             Self::Registry(dist) => &dist.version,
             Self::Url(dist) => &dist.version,
+            Self::EggInfo(dist) => &dist.version,
+            Self::LegacyEditable(dist) => &dist.version,
         }
     }
 
@@ -99,23 +244,64 @@ impl InstalledDist {
         Ok(Some(direct_url))
     }
 
-    /// Read the `METADATA` file from a `.dist-info` directory.
+    /// Read the `direct_url.json` file of the distribution, if it exists.
+    pub fn read_direct_url(&self) -> Result<Option<pypi_types::DirectUrl>> {
+        Self::direct_url(self.path())
+    }
+
+    /// Read the `uv_provenance.json` file of the distribution, if it exists.
+    ///
+    /// The file is only written by uv, and only for distributions installed from a registry.
+    pub fn provenance(&self) -> Result<Option<pypi_types::Provenance>> {
+        let path = self.path().join("uv_provenance.json");
+        let Ok(file) = fs_err::File::open(path) else {
+            return Ok(None);
+        };
+        let provenance = serde_json::from_reader::<fs_err::File, pypi_types::Provenance>(file)?;
+        Ok(Some(provenance))
+    }
+
+    /// Read the `INSTALLER` file of the distribution, if it exists.
+    pub fn installer(&self) -> Result<Option<String>> {
+        let path = self.path().join("INSTALLER");
+        match fs::read_to_string(path) {
+            Ok(installer) => Ok(Some(installer.trim().to_string())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Read the `METADATA` file from a `.dist-info` directory, or the `PKG-INFO` file for legacy
+    /// distributions.
     pub fn metadata(&self) -> Result<pypi_types::Metadata21> {
-        let path = self.path().join("METADATA");
-        let contents = fs::read(&path)?;
-        pypi_types::Metadata21::parse(&contents).with_context(|| {
-            format!(
-                "Failed to parse METADATA file at: {}",
-                path.normalized_display()
-            )
-        })
+        match self {
+            Self::Registry(_) | Self::Url(_) => {
+                let path = self.path().join("METADATA");
+                let contents = fs::read(&path)?;
+                pypi_types::Metadata21::parse(&contents).with_context(|| {
+                    format!(
+                        "Failed to parse METADATA file at: {}",
+                        path.normalized_display()
+                    )
+                })
+            }
+            Self::EggInfo(dist) => {
+                if dist.path.is_dir() {
+                    Self::read_pkg_info(&dist.path.join("PKG-INFO"))
+                } else {
+                    Self::read_pkg_info(&dist.path)
+                }
+            }
+            Self::LegacyEditable(dist) => Self::read_pkg_info(&dist.egg_info.join("PKG-INFO")),
+        }
     }
 
     /// Return the [`Url`] of the distribution, if it is editable.
     pub fn as_editable(&self) -> Option<&Url> {
         match self {
-            Self::Registry(_) => None,
+            Self::Registry(_) | Self::EggInfo(_) => None,
             Self::Url(dist) => dist.editable.then_some(&dist.url),
+            Self::LegacyEditable(dist) => Some(&dist.target_url),
         }
     }
 }
@@ -132,11 +318,25 @@ impl Name for InstalledDirectUrlDist {
     }
 }
 
+impl Name for InstalledEggInfo {
+    fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
+
+impl Name for InstalledLegacyEditable {
+    fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
+
 impl Name for InstalledDist {
     fn name(&self) -> &PackageName {
         match self {
             Self::Registry(dist) => dist.name(),
             Self::Url(dist) => dist.name(),
+            Self::EggInfo(dist) => dist.name(),
+            Self::LegacyEditable(dist) => dist.name(),
         }
     }
 }
@@ -153,11 +353,25 @@ impl InstalledMetadata for InstalledDirectUrlDist {
     }
 }
 
+impl InstalledMetadata for InstalledEggInfo {
+    fn installed_version(&self) -> InstalledVersion {
+        InstalledVersion::Version(&self.version)
+    }
+}
+
+impl InstalledMetadata for InstalledLegacyEditable {
+    fn installed_version(&self) -> InstalledVersion {
+        InstalledVersion::Url(&self.target_url, &self.version)
+    }
+}
+
 impl InstalledMetadata for InstalledDist {
     fn installed_version(&self) -> InstalledVersion {
         match self {
             Self::Registry(dist) => dist.installed_version(),
             Self::Url(dist) => dist.installed_version(),
+            Self::EggInfo(dist) => dist.installed_version(),
+            Self::LegacyEditable(dist) => dist.installed_version(),
         }
     }
 }
@@ -31,6 +31,10 @@
 //! * [`InstalledRegistryDist`]
 //! * [`InstalledDirectUrlDist`]
 //!
+//! Distributions installed by legacy tools are also tracked, such that they can be uninstalled:
+//! * [`InstalledEggInfo`]
+//! * [`InstalledLegacyEditable`]
+//!
 //! Since we read this information from [`direct_url.json`](https://packaging.python.org/en/latest/specifications/direct-url-data-structure/), it doesn't match the information [`Dist`] exactly.
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
@@ -245,8 +249,18 @@ impl Dist {
                 .extension()
                 .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
             {
+                // Validate that the name in the wheel filename matches that of the requirement.
+                let filename = WheelFilename::from_str(url.filename()?)?;
+                if filename.name != name {
+                    return Err(Error::PackageNameMismatch(
+                        name,
+                        filename.name,
+                        url.to_url(),
+                    ));
+                }
+
                 Ok(Self::Built(BuiltDist::Path(PathBuiltDist {
-                    filename: WheelFilename::from_str(url.filename()?)?,
+                    filename,
                     url,
                     path,
                 })))
@@ -264,8 +278,18 @@ impl Dist {
             .extension()
             .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
         {
+            // Validate that the name in the wheel filename matches that of the requirement.
+            let filename = WheelFilename::from_str(url.filename()?)?;
+            if filename.name != name {
+                return Err(Error::PackageNameMismatch(
+                    name,
+                    filename.name,
+                    url.to_url(),
+                ));
+            }
+
             Ok(Self::Built(BuiltDist::DirectUrl(DirectUrlBuiltDist {
-                filename: WheelFilename::from_str(url.filename()?)?,
+                filename,
                 url,
             })))
         } else {
@@ -295,6 +319,15 @@ impl Dist {
         }
     }
 
+    /// Returns the [`IndexUrl`] from which this dist was fetched, if it is from a registry.
+    pub fn index(&self) -> Option<&IndexUrl> {
+        match self {
+            Dist::Built(BuiltDist::Registry(registry)) => Some(&registry.index),
+            Dist::Source(SourceDist::Registry(registry)) => Some(&registry.index),
+            _ => None,
+        }
+    }
+
     pub fn version(&self) -> Option<&Version> {
         match self {
             Dist::Built(wheel) => Some(wheel.version()),
@@ -9,8 +9,9 @@ use crate::error::Error;
 use crate::{
     BuiltDist, CachedDirectUrlDist, CachedDist, CachedRegistryDist, DirectUrlBuiltDist,
     DirectUrlSourceDist, Dist, DistributionId, GitSourceDist, InstalledDirectUrlDist,
-    InstalledDist, InstalledRegistryDist, InstalledVersion, LocalDist, PackageId, PathBuiltDist,
-    PathSourceDist, RegistryBuiltDist, RegistrySourceDist, ResourceId, SourceDist, VersionOrUrl,
+    InstalledDist, InstalledEggInfo, InstalledLegacyEditable, InstalledRegistryDist,
+    InstalledVersion, LocalDist, PackageId, PathBuiltDist, PathSourceDist, RegistryBuiltDist,
+    RegistrySourceDist, ResourceId, SourceDist, VersionOrUrl,
 };
 
 pub trait Name {
@@ -162,6 +163,18 @@ impl std::fmt::Display for InstalledDirectUrlDist {
     }
 }
 
+impl std::fmt::Display for InstalledEggInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name(), self.installed_version())
+    }
+}
+
+impl std::fmt::Display for InstalledLegacyEditable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name(), self.installed_version())
+    }
+}
+
 impl std::fmt::Display for InstalledRegistryDist {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}{}", self.name(), self.installed_version())
@@ -25,8 +25,30 @@ const ACTIVATE_TEMPLATES: &[(&str, &str)] = &[
 ];
 const VIRTUALENV_PATCH: &str = include_str!("_virtualenv.py");
 
+/// The expressions with which the activate scripts locate the virtualenv relative to themselves,
+/// in place of its absolute path, for relocatable virtualenvs.
+///
+/// The csh script relies on the `source` command line that tcsh records in `$_`, which is only
+/// available in interactive shells. The PowerShell script and `activate_this.py` always locate the
+/// virtualenv relative to themselves, and so need no patching.
+const RELOCATABLE_VIRTUAL_ENV_DIRS: &[(&str, &str)] = &[
+    (
+        "activate",
+        r#""$(cd "$(dirname -- "${BASH_SOURCE[0]:-${(%):-%x}}")"/.. && pwd)""#,
+    ),
+    (
+        "activate.csh",
+        r#""`cd "$_activate_script[2]:h:q/.." && pwd`""#,
+    ),
+    (
+        "activate.fish",
+        "(builtin realpath (dirname (status -f))/..)",
+    ),
+    ("activate.nu", "($env.FILE_PWD | path dirname)"),
+];
+
 /// Very basic `.cfg` file format writer.
-fn write_cfg(f: &mut impl Write, data: &[(&str, String); 8]) -> io::Result<()> {
+fn write_cfg(f: &mut impl Write, data: &[(&str, String)]) -> io::Result<()> {
     for (key, value) in data {
         writeln!(f, "{key} = {value}")?;
     }
@@ -55,7 +77,18 @@ pub struct VenvPaths {
 }
 
 /// Write all the files that belong to a venv without any packages installed.
-pub fn create_bare_venv(location: &Utf8Path, interpreter: &Interpreter) -> io::Result<VenvPaths> {
+///
+/// If a `prompt` is provided, it's used in place of the directory name by the activation scripts.
+/// If `system_site_packages` is set, the venv can import packages from the base interpreter's
+/// `site-packages`. If `relocatable` is set, the activate scripts locate the venv relative to
+/// themselves, such that it can be moved after creation.
+pub fn create_bare_venv(
+    location: &Utf8Path,
+    interpreter: &Interpreter,
+    prompt: Option<&str>,
+    system_site_packages: bool,
+    relocatable: bool,
+) -> io::Result<VenvPaths> {
     // We have to canonicalize the interpreter path, otherwise the home is set to the venv dir instead of the real root.
     // This would make python-build-standalone fail with the encodings module not being found because its home is wrong.
     let base_python: Utf8PathBuf = fs_err::canonicalize(interpreter.sys_executable())?
@@ -154,9 +187,18 @@ pub fn create_bare_venv(location: &Utf8Path, interpreter: &Interpreter) -> io::R
     // TODO(konstin): RELATIVE_SITE_PACKAGES is currently only the unix path. We should ensure that all launchers work
     // cross-platform.
     for (name, template) in ACTIVATE_TEMPLATES {
-        let activator = template
-            .replace("{{ VIRTUAL_ENV_DIR }}", location.as_str())
+        let relocatable_dir = RELOCATABLE_VIRTUAL_ENV_DIRS
+            .iter()
+            .find(|(activator, _)| relocatable && activator == name)
+            .map(|(_, dir)| *dir);
+        let activator = if let Some(relocatable_dir) = relocatable_dir {
+            template.replace("'{{ VIRTUAL_ENV_DIR }}'", relocatable_dir)
+        } else {
+            template.replace("{{ VIRTUAL_ENV_DIR }}", location.as_str())
+        };
+        let activator = activator
             .replace("{{ BIN_NAME }}", bin_name)
+            .replace("{{ VIRTUAL_PROMPT }}", prompt.unwrap_or_default())
             .replace(
                 "{{ RELATIVE_SITE_PACKAGES }}",
                 &format!(
@@ -189,7 +231,7 @@ pub fn create_bare_venv(location: &Utf8Path, interpreter: &Interpreter) -> io::R
     } else {
         unimplemented!("Only Windows and Unix are supported")
     };
-    let pyvenv_cfg_data = &[
+    let mut pyvenv_cfg_data = vec![
         ("home", python_home),
         ("implementation", "CPython".to_string()),
         (
@@ -197,8 +239,10 @@ pub fn create_bare_venv(location: &Utf8Path, interpreter: &Interpreter) -> io::R
             interpreter.markers().python_version.string.clone(),
         ),
         ("gourgeist", env!("CARGO_PKG_VERSION").to_string()),
-        // I wouldn't allow this option anyway
-        ("include-system-site-packages", "false".to_string()),
+        (
+            "include-system-site-packages",
+            system_site_packages.to_string(),
+        ),
         (
             "base-prefix",
             interpreter.base_prefix().to_string_lossy().to_string(),
@@ -209,8 +253,14 @@ pub fn create_bare_venv(location: &Utf8Path, interpreter: &Interpreter) -> io::R
         ),
         ("base-executable", base_python.to_string()),
     ];
+    if let Some(prompt) = prompt {
+        pyvenv_cfg_data.push(("prompt", prompt.to_string()));
+    }
+    if relocatable {
+        pyvenv_cfg_data.push(("relocatable", "true".to_string()));
+    }
     let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
-    write_cfg(&mut pyvenv_cfg, pyvenv_cfg_data)?;
+    write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
     drop(pyvenv_cfg);
 
     let site_packages = if cfg!(unix) {
@@ -24,11 +24,26 @@ pub enum Error {
 }
 
 /// Create a virtualenv.
-pub fn create_venv(location: &Path, interpreter: Interpreter) -> Result<Virtualenv, Error> {
+///
+/// See [`create_bare_venv`] for the meaning of `prompt`, `system_site_packages`, and
+/// `relocatable`.
+pub fn create_venv(
+    location: &Path,
+    interpreter: Interpreter,
+    prompt: Option<&str>,
+    system_site_packages: bool,
+    relocatable: bool,
+) -> Result<Virtualenv, Error> {
     let location: &Utf8Path = location
         .try_into()
         .map_err(|err: FromPathError| err.into_io_error())?;
-    let paths = create_bare_venv(location, &interpreter)?;
+    let paths = create_bare_venv(
+        location,
+        &interpreter,
+        prompt,
+        system_site_packages,
+        relocatable,
+    )?;
     Ok(Virtualenv::from_interpreter(
         interpreter,
         paths.root.as_std_path(),
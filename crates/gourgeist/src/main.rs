@@ -21,6 +21,12 @@ struct Cli {
     path: Option<Utf8PathBuf>,
     #[clap(short, long)]
     python: Option<Utf8PathBuf>,
+    #[clap(long)]
+    prompt: Option<String>,
+    #[clap(long)]
+    system_site_packages: bool,
+    #[clap(long)]
+    relocatable: bool,
 }
 
 fn run() -> Result<(), gourgeist::Error> {
@@ -34,7 +40,13 @@ fn run() -> Result<(), gourgeist::Error> {
         Cache::from_path(".gourgeist_cache")?
     };
     let info = Interpreter::query(python.as_std_path(), &platform, &cache).unwrap();
-    create_bare_venv(&location, &info)?;
+    create_bare_venv(
+        &location,
+        &info,
+        cli.prompt.as_deref(),
+        cli.system_site_packages,
+        cli.relocatable,
+    )?;
     Ok(())
 }
 
@@ -5,6 +5,7 @@ use fs2::FileExt;
 use fs_err::File;
 use tracing::{error, warn};
 
+use pypi_types::Scheme;
 use uv_fs::Normalized;
 
 const INSTALL_LOCKFILE: &str = "install-wheel-rs.lock";
@@ -70,6 +71,29 @@ impl AsRef<Path> for LockedDir {
     }
 }
 
+/// The style of shebang to write to the scripts installed into an environment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ShebangStyle {
+    /// Use the absolute path to the environment's interpreter (e.g., `#!/venv/bin/python`).
+    #[default]
+    Absolute,
+    /// Use a `/bin/sh` preamble that runs the interpreter next to the script, such that the
+    /// environment can be moved after installation.
+    Relative,
+    /// Look up the interpreter on the `PATH` (e.g., `#!/usr/bin/env python`).
+    Env,
+}
+
+/// The shebang to write to the scripts installed into an environment.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Shebang {
+    pub style: ShebangStyle,
+    /// The interpreter to reference in place of the environment's own, for absolute and `env`
+    /// shebangs (e.g., the path at which the environment will live once deployed).
+    pub interpreter: Option<PathBuf>,
+}
+
 /// A virtual environment into which a wheel can be installed.
 ///
 /// We use a lockfile to prevent multiple instance writing stuff on the same time
@@ -79,6 +103,9 @@ pub struct InstallLocation<T> {
     /// absolute path
     venv_root: T,
     python_version: (u8, u8),
+    shebang: Shebang,
+    /// The installation scheme, relative to `venv_root`, if known.
+    scheme: Option<Scheme>,
 }
 
 impl<T: AsRef<Path>> InstallLocation<T> {
@@ -86,9 +113,27 @@ impl<T: AsRef<Path>> InstallLocation<T> {
         Self {
             venv_root: venv_base,
             python_version,
+            shebang: Shebang::default(),
+            scheme: None,
+        }
+    }
+
+    /// Set the installation [`Scheme`] of the environment, with paths relative to its root (e.g.,
+    /// as reported by the target interpreter's `sysconfig`).
+    #[must_use]
+    pub fn with_scheme(self, scheme: Scheme) -> Self {
+        Self {
+            scheme: Some(scheme),
+            ..self
         }
     }
 
+    /// Set the [`Shebang`] to write to installed scripts.
+    #[must_use]
+    pub fn with_shebang(self, shebang: Shebang) -> Self {
+        Self { shebang, ..self }
+    }
+
     /// Returns the location of the `python` interpreter.
     pub fn python(&self) -> PathBuf {
         if cfg!(unix) {
@@ -108,6 +153,19 @@ impl<T: AsRef<Path>> InstallLocation<T> {
     pub fn venv_root(&self) -> &T {
         &self.venv_root
     }
+
+    pub fn shebang(&self) -> &Shebang {
+        &self.shebang
+    }
+
+    /// Returns the absolute paths to which the files in a wheel's `.data` directory are
+    /// installed, falling back to the layout of a standard virtual environment.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+            .clone()
+            .unwrap_or_else(|| Scheme::virtualenv(self.python_version))
+            .join(self.venv_root.as_ref())
+    }
 }
 
 impl InstallLocation<PathBuf> {
@@ -125,6 +183,8 @@ impl InstallLocation<PathBuf> {
         Ok(InstallLocation {
             venv_root: locked_dir,
             python_version: self.python_version,
+            shebang: self.shebang.clone(),
+            scheme: self.scheme.clone(),
         })
     }
 }
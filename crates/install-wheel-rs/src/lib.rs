@@ -11,12 +11,12 @@ use zip::result::ZipError;
 use zip::ZipArchive;
 
 use distribution_filename::WheelFilename;
-pub use install_location::{normalize_name, InstallLocation, LockedDir};
+pub use install_location::{normalize_name, InstallLocation, LockedDir, Shebang, ShebangStyle};
 use pep440_rs::Version;
 use platform_host::{Arch, Os};
 pub use record::RecordEntry;
 pub use script::Script;
-pub use uninstall::{uninstall_wheel, Uninstall};
+pub use uninstall::{uninstall_egg, uninstall_legacy_editable, uninstall_wheel, Uninstall};
 use uv_fs::Normalized;
 use uv_normalize::PackageName;
 pub use wheel::{
@@ -81,6 +81,8 @@ pub enum Error {
     MissingDistInfo,
     #[error("Cannot uninstall package; RECORD file not found at: {}", _0.normalized_display())]
     MissingRecord(PathBuf),
+    #[error("Cannot uninstall package; top_level.txt file not found at: {}", _0.normalized_display())]
+    MissingTopLevel(PathBuf),
     #[error("Multiple .dist-info directories found: {0}")]
     MultipleDistInfo(String),
     #[error("Invalid wheel size")]
@@ -1,7 +1,7 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
 //! reading from a zip file.
 
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 
 use configparser::ini::Ini;
@@ -12,13 +12,13 @@ use tracing::{debug, instrument};
 
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
-use pypi_types::DirectUrl;
+use pypi_types::{DirectUrl, Provenance};
 use uv_normalize::PackageName;
 
 use crate::install_location::InstallLocation;
 use crate::wheel::{
-    extra_dist_info, install_data, parse_metadata, parse_wheel_version, read_scripts_from_section,
-    write_script_entrypoints,
+    copy_and_hash, extra_dist_info, install_data, parse_metadata, parse_wheel_version,
+    read_scripts_from_section, script_file_name, write_file_recorded, write_script_entrypoints,
 };
 use crate::{read_record_file, Error, Script};
 
@@ -35,6 +35,7 @@ pub fn install_wheel(
     wheel: impl AsRef<Path>,
     filename: &WheelFilename,
     direct_url: Option<&DirectUrl>,
+    provenance: Option<&Provenance>,
     installer: Option<&str>,
     link_mode: LinkMode,
 ) -> Result<(), Error> {
@@ -114,7 +115,6 @@ pub fn install_wheel(
     if data_dir.is_dir() {
         debug!(name, "Installing data");
         install_data(
-            root.as_ref(),
             &site_packages,
             &data_dir,
             &name,
@@ -140,6 +140,14 @@ pub fn install_wheel(
         installer,
         &mut record,
     )?;
+    if let Some(provenance) = provenance {
+        write_file_recorded(
+            &site_packages,
+            &PathBuf::from(format!("{dist_info_prefix}.dist-info")).join("uv_provenance.json"),
+            serde_json::to_string(provenance)?.as_bytes(),
+            &mut record,
+        )?;
+    }
 
     debug!(name, "Writing record");
     let mut record_writer = csv::WriterBuilder::new()
@@ -200,7 +208,10 @@ fn dist_info_metadata(dist_info_prefix: &str, wheel: impl AsRef<Path>) -> Result
 /// Returns (`script_name`, module, function)
 ///
 /// Extras are supposed to be ignored, which happens if you pass None for extras
-fn parse_scripts(
+///
+/// Since the `.dist-info` directory layout is preserved on installation, this can also be used to
+/// read the entrypoints of an installed distribution by passing the `site-packages` directory.
+pub fn parse_scripts(
     wheel: impl AsRef<Path>,
     dist_info_prefix: &str,
     extras: Option<&[String]>,
@@ -233,6 +244,95 @@ fn parse_scripts(
     Ok((console_scripts, gui_scripts))
 }
 
+/// Returns the file names that installing the given unzipped wheel would write to the scripts
+/// directory: the launchers for its console and GUI entrypoints, along with any files in its
+/// `.data/scripts` directory.
+pub fn script_names(wheel: impl AsRef<Path>) -> Result<Vec<String>, Error> {
+    let dist_info_prefix = find_dist_info(&wheel)?;
+    let (console_scripts, gui_scripts) = parse_scripts(&wheel, &dist_info_prefix, None)?;
+
+    let mut names = console_scripts
+        .iter()
+        .chain(gui_scripts.iter())
+        .map(script_file_name)
+        .collect::<Vec<_>>();
+
+    let data_scripts = wheel
+        .as_ref()
+        .join(format!("{dist_info_prefix}.data"))
+        .join("scripts");
+    match fs::read_dir(data_scripts) {
+        Ok(entries) => {
+            for entry in entries {
+                let name = entry?.file_name().to_string_lossy().to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(names)
+}
+
+/// Verify the files of an unzipped wheel against the hashes in its `RECORD` file.
+///
+/// This detects wheels that were modified after they were unzipped (e.g., a corrupted cache, or a
+/// file that was edited in place through a hard link). As when installing from a zip file, only
+/// SHA-256 hashes are checked.
+pub fn verify_wheel(wheel: impl AsRef<Path>) -> Result<(), Error> {
+    let dist_info_prefix = find_dist_info(&wheel)?;
+    let mut record_file = File::open(
+        wheel
+            .as_ref()
+            .join(format!("{dist_info_prefix}.dist-info/RECORD")),
+    )?;
+    let record = read_record_file(&mut record_file)?;
+
+    for entry in record {
+        // Files without a hash include the `RECORD` file itself.
+        let Some(recorded_hash) = entry.hash.as_deref() else {
+            continue;
+        };
+        if !recorded_hash.starts_with("sha256=") {
+            continue;
+        }
+
+        // Skip any paths outside of the wheel.
+        let relative = Path::new(&entry.path);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            continue;
+        }
+
+        let mut file = match File::open(wheel.as_ref().join(relative)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::RecordFile(format!("Missing file {}", entry.path)));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let (_size, encoded_hash) = copy_and_hash(&mut file, &mut std::io::sink())?;
+        if recorded_hash != encoded_hash {
+            // See: https://github.com/pytorch/pytorch/issues/47916
+            if entry.path.starts_with("torch-") {
+                debug!("Ignoring hash mismatch for {}", entry.path);
+                continue;
+            }
+            return Err(Error::RecordFile(format!(
+                "Hash mismatch for {}. Recorded: {}, Actual: {}",
+                entry.path, recorded_hash, encoded_hash,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum LinkMode {
@@ -262,6 +362,9 @@ impl LinkMode {
         site_packages: impl AsRef<Path>,
         wheel: impl AsRef<Path>,
     ) -> Result<usize, Error> {
+        // Use extended-length paths on Windows, as deeply nested packages can exceed `MAX_PATH`.
+        let site_packages = uv_fs::long_path(site_packages.as_ref());
+        let wheel = uv_fs::long_path(wheel.as_ref());
         match self {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
@@ -301,6 +404,11 @@ fn clone_recursive(site_packages: &Path, wheel: &Path, entry: &DirEntry) -> Resu
 
     debug!("Cloning {} to {}", from.display(), to.display());
 
+    // Reflinking a symlink would clone its target instead.
+    if entry.file_type()?.is_symlink() {
+        return copy_symlink(&from, &to, site_packages);
+    }
+
     // Attempt to copy the file or directory
     let reflink = reflink_copy::reflink(&from, &to);
 
@@ -318,7 +426,7 @@ fn clone_recursive(site_packages: &Path, wheel: &Path, entry: &DirEntry) -> Resu
             let tempdir = tempdir_in(site_packages)?;
             let tempfile = tempdir.path().join(from.file_name().unwrap());
             reflink_copy::reflink(from, &tempfile)?;
-            fs::rename(&tempfile, to)?;
+            uv_fs::rename_with_retry_sync(&tempfile, to)?;
         }
     } else {
         // Other errors should be tracked
@@ -332,6 +440,23 @@ fn clone_recursive(site_packages: &Path, wheel: &Path, entry: &DirEntry) -> Resu
     Ok(())
 }
 
+/// Recreate the symlink at `from` as `to`, pointing to the same (relative) target, replacing any
+/// existing file.
+fn copy_symlink(from: &Path, to: &Path, site_packages: &Path) -> Result<(), Error> {
+    let target = fs::read_link(from)?;
+
+    // Removing and recreating would lead to race conditions.
+    let tempdir = tempdir_in(site_packages)?;
+    let tempfile = tempdir.path().join(from.file_name().unwrap());
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, &tempfile)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(&target, &tempfile)?;
+    uv_fs::rename_with_retry_sync(&tempfile, to)?;
+
+    Ok(())
+}
+
 /// Extract a wheel by copying all of its files into site packages.
 fn copy_wheel_files(
     site_packages: impl AsRef<Path>,
@@ -352,6 +477,12 @@ fn copy_wheel_files(
             continue;
         }
 
+        if entry.file_type().is_symlink() {
+            copy_symlink(path, &out_path, site_packages.as_ref())?;
+            count += 1;
+            continue;
+        }
+
         // Copy the file, which will also set its permissions.
         fs::copy(path, &out_path)?;
 
@@ -394,6 +525,14 @@ fn hardlink_wheel_files(
             continue;
         }
 
+        // Depending on the platform, hard-linking (or copying) a symlink may follow it, so recreate
+        // the symlink instead.
+        if entry.file_type().is_symlink() {
+            copy_symlink(path, &out_path, site_packages.as_ref())?;
+            count += 1;
+            continue;
+        }
+
         // The `RECORD` file is modified during installation, so we copy it instead of hard-linking.
         if path.ends_with("RECORD") {
             fs::copy(path, &out_path)?;
@@ -420,7 +559,7 @@ fn hardlink_wheel_files(
                             fs::copy(path, &out_path)?;
                             attempt = Attempt::UseCopyFallback;
                         }
-                        fs_err::rename(&tempfile, &out_path)?;
+                        uv_fs::rename_with_retry_sync(&tempfile, &out_path)?;
                     } else {
                         fs::copy(path, &out_path)?;
                         attempt = Attempt::UseCopyFallback;
@@ -439,7 +578,7 @@ fn hardlink_wheel_files(
                         let tempdir = tempdir_in(&site_packages)?;
                         let tempfile = tempdir.path().join(entry.file_name());
                         fs::hard_link(path, &tempfile)?;
-                        fs_err::rename(&tempfile, &out_path)?;
+                        uv_fs::rename_with_retry_sync(&tempfile, &out_path)?;
                     } else {
                         return Err(err.into());
                     }
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 use std::path::{Component, Path, PathBuf};
 
 use fs_err as fs;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{read_record_file, Error};
 
@@ -25,14 +25,181 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     };
     let record = read_record_file(&mut record_file)?;
 
+    let paths = record
+        .iter()
+        .map(|entry| site_packages.join(&entry.path))
+        .collect::<Vec<_>>();
+
+    remove_paths(site_packages, &paths)
+}
+
+/// Uninstall the legacy distribution represented by the given `.egg-info` directory (or file), as
+/// installed by `setup.py install` or `easy_install`.
+///
+/// If the `.egg-info` directory contains an `installed-files.txt`, every file listed is removed.
+/// Otherwise, we fall back to the modules and packages listed in `top_level.txt`, skipping any
+/// namespace packages, which may be shared with other distributions.
+///
+/// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L483>
+pub fn uninstall_egg(egg_info: &Path) -> Result<Uninstall, Error> {
+    let Some(site_packages) = egg_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "egg-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    // A bare `.egg-info` file (as written by `distutils`) doesn't record the installed files, so
+    // all we can do is remove the metadata itself.
+    if egg_info.is_file() {
+        warn!(
+            "Unable to determine the files installed alongside: {}",
+            egg_info.display()
+        );
+        uv_fs::remove_file_with_retry_sync(egg_info)?;
+        debug!("Removed file: {}", egg_info.display());
+        return Ok(Uninstall {
+            file_count: 1,
+            dir_count: 0,
+        });
+    }
+
+    let mut paths = match fs::read_to_string(egg_info.join("installed-files.txt")) {
+        // Paths in `installed-files.txt` are relative to the `.egg-info` directory.
+        Ok(installed_files) => installed_files
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| egg_info.join(line))
+            .collect::<Vec<_>>(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let namespace_packages =
+                match fs::read_to_string(egg_info.join("namespace_packages.txt")) {
+                    Ok(namespace_packages) => namespace_packages
+                        .lines()
+                        .map(|line| line.trim().to_string())
+                        .collect::<Vec<_>>(),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                    Err(err) => return Err(err.into()),
+                };
+
+            let top_level_path = egg_info.join("top_level.txt");
+            let top_level = match fs::read_to_string(&top_level_path) {
+                Ok(top_level) => top_level,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(Error::MissingTopLevel(top_level_path));
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            // Each entry may be a package (directory) or a module (file).
+            let mut paths = Vec::new();
+            for entry in top_level.lines().map(str::trim) {
+                if entry.is_empty() || namespace_packages.iter().any(|ns| ns == entry) {
+                    continue;
+                }
+                let path = site_packages.join(entry);
+                paths.push(path.clone());
+                for extension in ["py", "pyc", "pyo"] {
+                    paths.push(path.with_extension(extension));
+                }
+            }
+            paths
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Remove the `.egg-info` directory itself.
+    paths.push(egg_info.to_path_buf());
+
+    remove_paths(site_packages, &paths)
+}
+
+/// Uninstall the legacy editable represented by the given `.egg-link` file, as installed by
+/// `setup.py develop`.
+///
+/// Removes the `.egg-link` file, along with the corresponding entry for the `target` source
+/// directory in `easy-install.pth`. The source directory itself is left untouched.
+///
+/// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L502>
+pub fn uninstall_legacy_editable(egg_link: &Path, target: &Path) -> Result<Uninstall, Error> {
+    let Some(site_packages) = egg_link.parent() else {
+        return Err(Error::BrokenVenv(
+            "egg-link file is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let mut file_count = 0usize;
+
+    match uv_fs::remove_file_with_retry_sync(egg_link) {
+        Ok(()) => {
+            debug!("Removed file: {}", egg_link.display());
+            file_count += 1;
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    // Remove the source directory from `easy-install.pth`, preserving all other entries.
+    let easy_install = site_packages.join("easy-install.pth");
+    let contents = match fs::read_to_string(&easy_install) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Uninstall {
+                file_count,
+                dir_count: 0,
+            });
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let target = normalize_path(target);
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let retained = contents
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            line.is_empty() || normalize_path(&site_packages.join(line)) != target
+        })
+        .collect::<Vec<_>>();
+    if retained.len() != contents.lines().count() {
+        let mut contents = retained.join(newline);
+        contents.push_str(newline);
+
+        // Write to a temporary file in the same directory, then rename it over the original, such
+        // that an interruption never leaves `easy-install.pth` truncated. Retain the permissions
+        // of the original, since the temporary file is only readable by its owner.
+        let permissions = fs::metadata(&easy_install)?.permissions();
+        let temp_file = tempfile::NamedTempFile::new_in(site_packages)?;
+        fs::write(temp_file.path(), contents)?;
+        fs::set_permissions(temp_file.path(), permissions)?;
+        temp_file.persist(&easy_install).map_err(|err| err.error)?;
+        debug!(
+            "Removed {} from: {}",
+            target.display(),
+            easy_install.display()
+        );
+    }
+
+    Ok(Uninstall {
+        file_count,
+        dir_count: 0,
+    })
+}
+
+/// Remove the given files (or directories), along with any directories within `site-packages`
+/// that are left empty as a result.
+fn remove_paths(site_packages: &Path, paths: &[PathBuf]) -> Result<Uninstall, Error> {
     let mut file_count = 0usize;
     let mut dir_count = 0usize;
 
     // Uninstall the files, keeping track of any directories that are left empty.
     let mut visited = BTreeSet::new();
-    for entry in &record {
-        let path = site_packages.join(&entry.path);
-        match fs::remove_file(&path) {
+    for path in paths {
+        match uv_fs::remove_file_with_retry_sync(path) {
             Ok(()) => {
                 debug!("Removed file: {}", path.display());
                 file_count += 1;
@@ -41,7 +208,7 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
                 }
             }
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-            Err(err) => match fs::remove_dir_all(&path) {
+            Err(err) => match fs::remove_dir_all(path) {
                 Ok(()) => {
                     debug!("Removed directory: {}", path.display());
                     dir_count += 1;
@@ -25,7 +25,7 @@ use pypi_types::DirectUrl;
 use uv_fs::Normalized;
 use uv_normalize::PackageName;
 
-use crate::install_location::{InstallLocation, LockedDir};
+use crate::install_location::{InstallLocation, LockedDir, ShebangStyle};
 use crate::record::RecordEntry;
 use crate::script::Script;
 use crate::{find_dist_info, Error};
@@ -131,7 +131,10 @@ fn parse_scripts<R: Read + Seek>(
 /// <https://github.com/richo/hashing-copy/blob/d8dd2fdb63c6faf198de0c9e5713d6249cbb5323/src/lib.rs#L10-L52>
 /// which in turn got it from std
 /// <https://doc.rust-lang.org/1.58.0/src/std/io/copy.rs.html#128-156>
-fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+pub(crate) fn copy_and_hash(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> io::Result<(u64, String)> {
     // TODO: Do we need to support anything besides sha256?
     let mut hasher = Sha256::new();
     // Same buf size as std. Note that this number is important for performance
@@ -274,8 +277,46 @@ fn unpack_wheel_files<R: Read + Seek>(
     Ok(extracted_paths)
 }
 
+/// Returns the shebang for scripts installed into the given location, per its [`Shebang`].
+///
+/// The shebang is written without a trailing newline, as it may replace a `#!python` placeholder
+/// at the start of an existing first line.
 fn get_shebang(location: &InstallLocation<impl AsRef<Path>>) -> String {
-    let path = location.python().to_string_lossy().to_string();
+    let shebang = location.shebang();
+
+    match shebang.style {
+        // On Windows, the launcher finds the interpreter next to the script regardless, so the
+        // relative preamble isn't needed.
+        ShebangStyle::Relative if cfg!(unix) => {
+            // A polyglot that `sh` executes as an `exec` of the adjacent interpreter, and that
+            // Python reads as a no-op string literal.
+            let python = location.python();
+            let executable = python
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            return format!(
+                r#"#!/bin/sh
+'''exec' "$(dirname -- "$(realpath -- "$0")")"/'{executable}' "$0" "$@"
+' '''"#
+            );
+        }
+        ShebangStyle::Env => {
+            let interpreter = shebang.interpreter.as_ref().map_or_else(
+                || "python".to_string(),
+                |interpreter| interpreter.to_string_lossy().to_string(),
+            );
+            return format!("#!/usr/bin/env {interpreter}");
+        }
+        ShebangStyle::Absolute | ShebangStyle::Relative => {}
+    }
+
+    let path = shebang
+        .interpreter
+        .clone()
+        .unwrap_or_else(|| location.python())
+        .to_string_lossy()
+        .to_string();
     let path = if cfg!(windows) {
         // https://stackoverflow.com/a/50323079
         const VERBATIM_PREFIX: &str = r"\\?\";
@@ -352,6 +393,22 @@ pub(crate) fn windows_script_launcher(
     Ok(launcher)
 }
 
+/// The file name of the launcher written to the scripts directory for an entrypoint.
+pub(crate) fn script_file_name(entrypoint: &Script) -> String {
+    if cfg!(windows) {
+        // On windows we actually build an .exe wrapper
+        entrypoint
+            .script_name
+            // FIXME: What are the in-reality rules here for names?
+            .strip_suffix(".py")
+            .unwrap_or(&entrypoint.script_name)
+            .to_string()
+            + ".exe"
+    } else {
+        entrypoint.script_name.clone()
+    }
+}
+
 /// Create the wrapper scripts in the bin folder of the venv for launching console scripts
 ///
 /// We also pass `venv_base` so we can write the same path as pip does
@@ -364,20 +421,9 @@ pub(crate) fn write_script_entrypoints(
     record: &mut Vec<RecordEntry>,
     is_gui: bool,
 ) -> Result<(), Error> {
+    let scripts = relative_to(&location.scheme().scripts, site_packages)?;
     for entrypoint in entrypoints {
-        let entrypoint_relative = if cfg!(windows) {
-            // On windows we actually build an .exe wrapper
-            let script_name = entrypoint
-                .script_name
-                // FIXME: What are the in-reality rules here for names?
-                .strip_suffix(".py")
-                .unwrap_or(&entrypoint.script_name)
-                .to_string()
-                + ".exe";
-            bin_rel().join(script_name)
-        } else {
-            bin_rel().join(&entrypoint.script_name)
-        };
+        let entrypoint_relative = scripts.join(script_file_name(entrypoint));
 
         // Generate the launcher script.
         let launcher_python_script = get_script_launcher(
@@ -416,16 +462,6 @@ pub(crate) fn write_script_entrypoints(
     Ok(())
 }
 
-fn bin_rel() -> PathBuf {
-    if cfg!(windows) {
-        // windows doesn't have the python part, only Lib/site-packages
-        Path::new("..").join("..").join("Scripts")
-    } else {
-        // linux/mac has lib/python/site-packages
-        Path::new("..").join("..").join("..").join("bin")
-    }
-}
-
 /// Parse WHEEL file
 ///
 /// > {distribution}-{version}.dist-info/WHEEL is metadata about the archive itself in the same
@@ -668,7 +704,7 @@ pub(crate) fn move_folder_recorded(
         if entry.file_type().is_dir() {
             fs::create_dir_all(&target)?;
         } else {
-            fs::rename(src, &target)?;
+            uv_fs::rename_with_retry_sync(src, &target)?;
             let entry = record
                 .iter_mut()
                 .find(|entry| Path::new(&entry.path) == relative_to_site_packages)
@@ -701,7 +737,8 @@ fn install_script(
         )));
     }
 
-    let target_path = bin_rel().join(file.file_name());
+    let target_path =
+        relative_to(&location.scheme().scripts, site_packages)?.join(file.file_name());
 
     let path = file.path();
     let mut script = File::open(&path)?;
@@ -727,7 +764,7 @@ fn install_script(
     } else {
         // reading and writing is slow especially for large binaries, so we move them instead
         drop(script);
-        fs::rename(&path, site_packages.join(&target_path))?;
+        uv_fs::rename_with_retry_sync(&path, site_packages.join(&target_path))?;
         None
     };
     #[cfg(unix)]
@@ -763,11 +800,10 @@ fn install_script(
     Ok(())
 }
 
-/// Move the files from the .data directory to the right location in the venv
-#[allow(clippy::too_many_arguments)]
+/// Move the files from the .data directory to the right location in the venv, per the
+/// installation [`Scheme`][`pypi_types::Scheme`] of the install location.
 #[instrument(skip_all)]
 pub(crate) fn install_data(
-    venv_root: &Path,
     site_packages: &Path,
     data_dir: &Path,
     dist_name: &str,
@@ -776,6 +812,7 @@ pub(crate) fn install_data(
     gui_scripts: &[Script],
     record: &mut [RecordEntry],
 ) -> Result<(), Error> {
+    let scheme = location.scheme();
     for entry in fs::read_dir(data_dir)? {
         let entry = entry?;
         let path = entry.path();
@@ -783,7 +820,7 @@ pub(crate) fn install_data(
         match path.file_name().and_then(|name| name.to_str()) {
             Some("data") => {
                 // Move the content of the folder to the root of the venv
-                move_folder_recorded(&path, venv_root, site_packages, record)?;
+                move_folder_recorded(&path, &scheme.data, site_packages, record)?;
             }
             Some("scripts") => {
                 for file in fs::read_dir(path)? {
@@ -809,21 +846,14 @@ pub(crate) fn install_data(
                 }
             }
             Some("headers") => {
-                let target_path = venv_root
-                    .join("include")
-                    .join("site")
-                    .join(format!(
-                        "python{}.{}",
-                        location.python_version().0,
-                        location.python_version().1
-                    ))
-                    .join(dist_name);
+                let target_path = scheme.include.join(dist_name);
                 move_folder_recorded(&path, &target_path, site_packages, record)?;
             }
-            Some("purelib" | "platlib") => {
-                // purelib and platlib locations are not relevant when using venvs
-                // https://stackoverflow.com/a/27882460/3549270
-                move_folder_recorded(&path, site_packages, site_packages, record)?;
+            Some("purelib") => {
+                move_folder_recorded(&path, &scheme.purelib, site_packages, record)?;
+            }
+            Some("platlib") => {
+                move_folder_recorded(&path, &scheme.platlib, site_packages, record)?;
             }
             _ => {
                 return Err(Error::InvalidWheel(format!(
@@ -1057,7 +1087,6 @@ pub fn install_wheel(
     if data_dir.is_dir() {
         debug!(name = name.as_str(), "Installing data");
         install_data(
-            base_location.as_ref(),
             &site_packages,
             &data_dir,
             &name,
@@ -158,6 +158,32 @@ impl Tags {
         Ok(Self::new(tags))
     }
 
+    /// Returns the tags as `(python, abi, platform)` triples, in order of priority (i.e., from the
+    /// most to the least preferred).
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        let mut tags = self
+            .map
+            .iter()
+            .flat_map(|(py, abis)| {
+                abis.iter().flat_map(move |(abi, platforms)| {
+                    platforms
+                        .iter()
+                        .map(move |(platform, priority)| (*priority, py, abi, platform))
+                })
+            })
+            .collect::<Vec<_>>();
+        tags.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        tags.into_iter()
+            .map(|(_, py, abi, platform)| (py.as_str(), abi.as_str(), platform.as_str()))
+    }
+
+    /// Returns the most-preferred tag (e.g., `cp312-cp312-manylinux_2_17_x86_64`), if any.
+    pub fn best(&self) -> Option<String> {
+        self.iter()
+            .next()
+            .map(|(py, abi, platform)| format!("{py}-{abi}-{platform}"))
+    }
+
     /// Returns true when there exists at least one tag for this platform
     /// whose individual components all appear in each of the slices given.
     ///
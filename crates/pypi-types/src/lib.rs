@@ -2,10 +2,14 @@ pub use base_url::*;
 pub use direct_url::*;
 pub use lenient_requirement::*;
 pub use metadata::*;
+pub use provenance::*;
+pub use scheme::*;
 pub use simple_json::*;
 
 mod base_url;
 mod direct_url;
 mod lenient_requirement;
 mod metadata;
+mod provenance;
+mod scheme;
 mod simple_json;
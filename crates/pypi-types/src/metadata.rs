@@ -1,5 +1,6 @@
 //! Derived from `pypi_types_crate`.
 
+use std::collections::BTreeMap;
 use std::io;
 use std::str::FromStr;
 
@@ -8,7 +9,9 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use pep440_rs::{Version, VersionParseError, VersionSpecifiers, VersionSpecifiersParseError};
-use pep508_rs::{Pep508Error, Requirement};
+use pep508_rs::{
+    MarkerExpression, MarkerOperator, MarkerTree, MarkerValue, Pep508Error, Requirement,
+};
 use uv_normalize::{ExtraName, InvalidNameError, PackageName};
 
 use crate::lenient_requirement::LenientRequirement;
@@ -30,6 +33,32 @@ pub struct Metadata21 {
     pub requires_dist: Vec<Requirement>,
     pub requires_python: Option<VersionSpecifiers>,
     pub provides_extras: Vec<ExtraName>,
+    /// Dependencies on system libraries or tools, which must be provided outside of Python
+    /// packaging (e.g., `libpq (>=9.4)`).
+    ///
+    /// These are informational only, and surfaced to the user when a build fails.
+    #[serde(default)]
+    pub requires_external: Vec<String>,
+    /// The license of the distribution, as an SPDX expression (`License-Expression`).
+    #[serde(default)]
+    pub license_expression: Option<String>,
+    /// The license of the distribution, as free-form text (`License`, e.g., `MIT`).
+    #[serde(default)]
+    pub license: Option<String>,
+    /// The license classifiers of the distribution (e.g., `License :: OSI Approved :: MIT License`).
+    #[serde(default)]
+    pub license_classifiers: Vec<String>,
+}
+
+/// The license of a distribution, as declared in its metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeclaredLicense {
+    /// The SPDX expression (`License-Expression`), if any.
+    pub expression: Option<String>,
+    /// The free-form license text (`License`), if any.
+    pub text: Option<String>,
+    /// The license classifiers, if any.
+    pub classifiers: Vec<String>,
 }
 
 /// <https://github.com/PyO3/python-pkginfo-rs/blob/d719988323a0cfea86d4737116d7917f30e819e2/src/error.rs>
@@ -69,6 +98,18 @@ pub enum Error {
     Pep508Error(#[from] Pep508Error),
     #[error(transparent)]
     InvalidName(#[from] InvalidNameError),
+    /// The metadata version is too old to be trusted without a build (i.e., older than 2.2)
+    #[error("unsupported metadata version: {0}")]
+    UnsupportedMetadataVersion(String),
+    /// A field required for resolution is marked as dynamic
+    #[error("metadata field {0} is dynamic")]
+    DynamicField(&'static str),
+    /// The `pyproject.toml` could not be parsed
+    #[error(transparent)]
+    InvalidPyprojectToml(#[from] toml::de::Error),
+    /// The `pyproject.toml` is missing a `[project]` table
+    #[error("`pyproject.toml` is missing a `[project]` table")]
+    MissingProject,
 }
 
 /// From <https://github.com/PyO3/python-pkginfo-rs/blob/d719988323a0cfea86d4737116d7917f30e819e2/src/metadata.rs#LL78C2-L91C26>
@@ -121,6 +162,12 @@ impl Metadata21 {
         let provides_extras = get_all_values("Provides-Extra")
             .map(ExtraName::new)
             .collect::<Result<Vec<_>, _>>()?;
+        let requires_external = get_all_values("Requires-External").collect::<Vec<_>>();
+        let license_expression = get_first_value("License-Expression");
+        let license = get_first_value("License");
+        let license_classifiers = get_all_values("Classifier")
+            .filter(|classifier| classifier.starts_with("License ::"))
+            .collect::<Vec<_>>();
 
         Ok(Metadata21 {
             metadata_version,
@@ -129,10 +176,154 @@ impl Metadata21 {
             requires_dist,
             requires_python,
             provides_extras,
+            requires_external,
+            license_expression,
+            license,
+            license_classifiers,
         })
     }
 }
 
+impl Metadata21 {
+    /// Read the static metadata from a source distribution's `PKG-INFO` file.
+    ///
+    /// Per PEP 643, the metadata is only reliable if it uses metadata version 2.2 or later, and
+    /// none of the fields required for resolution are marked as `Dynamic`.
+    pub fn parse_pkg_info(content: &[u8]) -> Result<Self, Error> {
+        let (headers, _) = mailparse::parse_headers(content)?;
+
+        let metadata_version = headers
+            .get_first_value("Metadata-Version")
+            .ok_or(Error::FieldNotFound("Metadata-Version"))?;
+        let (major, minor) = metadata_version
+            .split_once('.')
+            .and_then(|(major, minor)| {
+                Some((major.parse::<u64>().ok()?, minor.parse::<u64>().ok()?))
+            })
+            .ok_or_else(|| Error::UnsupportedMetadataVersion(metadata_version.clone()))?;
+        if (major, minor) < (2, 2) || major >= 3 {
+            return Err(Error::UnsupportedMetadataVersion(metadata_version));
+        }
+
+        for dynamic in headers.get_all_values("Dynamic") {
+            for field in ["Requires-Dist", "Requires-Python", "Provides-Extra"] {
+                if dynamic.eq_ignore_ascii_case(field) {
+                    return Err(Error::DynamicField(field));
+                }
+            }
+        }
+
+        Self::parse(content)
+    }
+
+    /// Read the static metadata from a source tree's `pyproject.toml`, per PEP 621.
+    ///
+    /// Fails if the `[project]` table is missing, or if any of the fields required for
+    /// resolution are marked as `dynamic`.
+    pub fn parse_pyproject_toml(contents: &str) -> Result<Self, Error> {
+        let pyproject_toml: PyProjectToml = toml::from_str(contents)?;
+        let project = pyproject_toml.project.ok_or(Error::MissingProject)?;
+
+        let dynamic = project.dynamic.unwrap_or_default();
+        for (key, field) in [
+            ("version", "Version"),
+            ("dependencies", "Requires-Dist"),
+            ("optional-dependencies", "Requires-Dist"),
+            ("requires-python", "Requires-Python"),
+        ] {
+            if dynamic.iter().any(|dynamic| dynamic == key) {
+                return Err(Error::DynamicField(field));
+            }
+        }
+
+        let name = PackageName::new(project.name)?;
+        let version = Version::from_str(&project.version.ok_or(Error::FieldNotFound("version"))?)
+            .map_err(Error::Pep440VersionError)?;
+
+        // Parse the dependencies, including any optional dependencies, which are gated on an
+        // `extra` marker.
+        let mut requires_dist = project
+            .dependencies
+            .unwrap_or_default()
+            .iter()
+            .map(|requirement| LenientRequirement::from_str(requirement).map(Requirement::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut provides_extras = Vec::new();
+        for (extra, requirements) in project.optional_dependencies.unwrap_or_default() {
+            let extra = ExtraName::new(extra)?;
+            for requirement in requirements {
+                let mut requirement =
+                    Requirement::from(LenientRequirement::from_str(&requirement)?);
+                let marker = MarkerTree::Expression(MarkerExpression {
+                    l_value: MarkerValue::Extra,
+                    operator: MarkerOperator::Equal,
+                    r_value: MarkerValue::QuotedString(extra.to_string()),
+                });
+                requirement.marker = Some(match requirement.marker.take() {
+                    Some(existing) => MarkerTree::And(vec![existing, marker]),
+                    None => marker,
+                });
+                requires_dist.push(requirement);
+            }
+            provides_extras.push(extra);
+        }
+
+        let requires_python = project
+            .requires_python
+            .map(|requires_python| {
+                LenientVersionSpecifiers::from_str(&requires_python).map(VersionSpecifiers::from)
+            })
+            .transpose()?;
+
+        Ok(Metadata21 {
+            metadata_version: "2.2".to_string(),
+            name,
+            version,
+            requires_dist,
+            requires_python,
+            provides_extras,
+            requires_external: Vec::new(),
+            license_expression: None,
+            license: None,
+            license_classifiers: Vec::new(),
+        })
+    }
+
+    /// Return the license declared by the distribution, if any.
+    pub fn declared_license(&self) -> Option<DeclaredLicense> {
+        if self.license_expression.is_none()
+            && self.license.is_none()
+            && self.license_classifiers.is_empty()
+        {
+            return None;
+        }
+        Some(DeclaredLicense {
+            expression: self.license_expression.clone(),
+            text: self.license.clone(),
+            classifiers: self.license_classifiers.clone(),
+        })
+    }
+}
+
+/// The subset of a `pyproject.toml` that's relevant to reading static metadata.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    project: Option<Project>,
+}
+
+/// The `[project]` table of a `pyproject.toml`, per PEP 621.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    name: String,
+    version: Option<String>,
+    requires_python: Option<String>,
+    dependencies: Option<Vec<String>>,
+    optional_dependencies: Option<BTreeMap<String, Vec<String>>>,
+    dynamic: Option<Vec<String>>,
+}
+
 impl FromStr for Metadata21 {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -145,7 +336,7 @@ mod tests {
     use std::str::FromStr;
 
     use pep440_rs::Version;
-    use uv_normalize::PackageName;
+    use uv_normalize::{ExtraName, PackageName};
 
     use crate::Error;
 
@@ -182,5 +373,86 @@ mod tests {
         let s = "Metadata-Version: 1.0\nName: =?utf-8?q?=C3=A4_space?= <x@y.org>\nVersion: 1.0";
         let meta = Metadata21::parse(s.as_bytes());
         assert!(matches!(meta, Err(Error::InvalidName(_))));
+
+        let s = "Metadata-Version: 1.2\nName: psycopg2\nVersion: 2.9.9\nRequires-External: libpq (>=9.1)\nRequires-External: C";
+        let meta = Metadata21::parse(s.as_bytes()).unwrap();
+        assert_eq!(meta.requires_external, vec!["libpq (>=9.1)", "C"]);
+
+        let s = "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nLicense: BSD\nLicense-Expression: MIT OR Apache-2.0\nClassifier: License :: OSI Approved :: MIT License\nClassifier: Programming Language :: Python";
+        let meta = Metadata21::parse(s.as_bytes()).unwrap();
+        assert_eq!(
+            meta.license_expression.as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(meta.license.as_deref(), Some("BSD"));
+        assert_eq!(
+            meta.license_classifiers,
+            vec!["License :: OSI Approved :: MIT License"]
+        );
+    }
+
+    #[test]
+    fn test_parse_pkg_info() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0";
+        let meta = Metadata21::parse_pkg_info(s.as_bytes());
+        assert!(matches!(meta, Err(Error::UnsupportedMetadataVersion(_))));
+
+        let s = "Metadata-Version: 2.2\nName: asdf\nVersion: 1.0\nDynamic: Requires-Dist";
+        let meta = Metadata21::parse_pkg_info(s.as_bytes());
+        assert!(matches!(meta, Err(Error::DynamicField("Requires-Dist"))));
+
+        let s = "Metadata-Version: 2.3\nName: asdf\nVersion: 1.0\nDynamic: Description\nRequires-Dist: foo";
+        let meta = Metadata21::parse_pkg_info(s.as_bytes()).unwrap();
+        assert_eq!(meta.name, PackageName::from_str("asdf").unwrap());
+        assert_eq!(meta.version, Version::new([1, 0]));
+        assert_eq!(meta.requires_dist.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_pyproject_toml() {
+        let s = r#"
+            [project]
+            name = "asdf"
+        "#;
+        let meta = Metadata21::parse_pyproject_toml(s);
+        assert!(matches!(meta, Err(Error::FieldNotFound("version"))));
+
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            dynamic = ["dependencies"]
+        "#;
+        let meta = Metadata21::parse_pyproject_toml(s);
+        assert!(matches!(meta, Err(Error::DynamicField("Requires-Dist"))));
+
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            requires-python = ">=3.8"
+            dependencies = ["foo>=1.0"]
+
+            [project.optional-dependencies]
+            dotenv = ["bar; sys_platform == 'linux'"]
+        "#;
+        let meta = Metadata21::parse_pyproject_toml(s).unwrap();
+        assert_eq!(meta.name, PackageName::from_str("asdf").unwrap());
+        assert_eq!(meta.version, Version::new([1, 0]));
+        assert!(meta.requires_python.is_some());
+        assert_eq!(
+            meta.requires_dist
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "foo >=1.0",
+                "bar ; sys_platform == 'linux' and extra == 'dotenv'"
+            ]
+        );
+        assert_eq!(
+            meta.provides_extras,
+            vec![ExtraName::from_str("dotenv").unwrap()]
+        );
     }
 }
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// The provenance of a distribution installed from a registry, as recorded by uv in the
+/// `uv_provenance.json` file of its `.dist-info` directory.
+///
+/// Distributions installed from a direct URL record their origin in `direct_url.json` instead.
+/// For example:
+/// ```json
+/// {"built": true, "index": "https://pypi.org/simple"}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Provenance {
+    /// Whether the wheel was built locally from a source distribution.
+    pub built: bool,
+    /// The index from which the distribution was fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+}
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The directories to which the files of a wheel are installed, per the `sysconfig` installation
+/// scheme of the target interpreter.
+///
+/// Each key matches a subdirectory of the `.data` directory in a wheel, with `include` used for
+/// `headers` (into which each distribution's headers are installed in a subdirectory). The paths
+/// are typically relative to the root of the environment, as reported by the interpreter; see
+/// [`Scheme::join`] to resolve them.
+///
+/// See: <https://docs.python.org/3/library/sysconfig.html#installation-paths>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scheme {
+    pub purelib: PathBuf,
+    pub platlib: PathBuf,
+    pub scripts: PathBuf,
+    pub data: PathBuf,
+    pub include: PathBuf,
+}
+
+impl Scheme {
+    /// The scheme of a standard virtual environment for the given Python version, relative to
+    /// its root, for when the interpreter's own scheme isn't known.
+    pub fn virtualenv(python_version: (u8, u8)) -> Self {
+        let python = format!("python{}.{}", python_version.0, python_version.1);
+        if cfg!(windows) {
+            Self {
+                purelib: Path::new("Lib").join("site-packages"),
+                platlib: Path::new("Lib").join("site-packages"),
+                scripts: PathBuf::from("Scripts"),
+                data: PathBuf::new(),
+                include: Path::new("include").join("site").join(python),
+            }
+        } else {
+            Self {
+                purelib: Path::new("lib").join(&python).join("site-packages"),
+                platlib: Path::new("lib").join(&python).join("site-packages"),
+                scripts: PathBuf::from("bin"),
+                data: PathBuf::new(),
+                include: Path::new("include").join("site").join(python),
+            }
+        }
+    }
+
+    /// Resolve the (relative) paths in the scheme against the given root.
+    #[must_use]
+    pub fn join(&self, root: &Path) -> Self {
+        // Avoid a trailing separator (or `.`) when joining the root itself (e.g., for `data`).
+        let join = |path: &Path| {
+            if path.as_os_str().is_empty() || path == Path::new(".") {
+                root.to_path_buf()
+            } else {
+                root.join(path)
+            }
+        };
+        Self {
+            purelib: join(&self.purelib),
+            platlib: join(&self.platlib),
+            scripts: join(&self.scripts),
+            data: join(&self.data),
+            include: join(&self.include),
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
 
 use pep440_rs::{VersionSpecifiers, VersionSpecifiersParseError};
 
@@ -116,8 +118,8 @@ impl Default for Yanked {
 
 /// A dictionary mapping a hash name to a hex encoded digest of the file.
 ///
-/// PEP 691 says multiple hashes can be included and the interpretation is left to the client, we
-/// only support SHA 256 atm.
+/// PEP 691 says multiple hashes can be included and the interpretation is left to the client; we
+/// support the SHA-2 family (SHA-256, SHA-384, and SHA-512).
 #[derive(
     Debug,
     Clone,
@@ -137,15 +139,103 @@ impl Default for Yanked {
 #[archive_attr(derive(Debug))]
 pub struct Hashes {
     pub sha256: Option<String>,
+    pub sha384: Option<String>,
+    pub sha512: Option<String>,
 }
 
 impl Hashes {
     /// Format as `<algorithm>:<hash>`.
     ///
-    /// Currently limited to SHA256.
+    /// Returns the SHA-256 digest, if present.
     pub fn to_string(&self) -> Option<String> {
-        self.sha256
-            .as_ref()
-            .map(|sha256| format!("sha256:{sha256}"))
+        self.format(HashAlgorithm::Sha256)
+    }
+
+    /// Return the hex-encoded digest for the given algorithm, if present.
+    pub fn get(&self, algorithm: HashAlgorithm) -> Option<&str> {
+        match algorithm {
+            HashAlgorithm::Sha256 => self.sha256.as_deref(),
+            HashAlgorithm::Sha384 => self.sha384.as_deref(),
+            HashAlgorithm::Sha512 => self.sha512.as_deref(),
+        }
+    }
+
+    /// Format the digest for the given algorithm as `<algorithm>:<hash>`, if present.
+    pub fn format(&self, algorithm: HashAlgorithm) -> Option<String> {
+        self.get(algorithm)
+            .map(|digest| format!("{algorithm}:{digest}"))
+    }
+
+    /// Return the strongest algorithm for which a digest is present, along with the digest.
+    pub fn strongest(&self) -> Option<(HashAlgorithm, &str)> {
+        [
+            HashAlgorithm::Sha512,
+            HashAlgorithm::Sha384,
+            HashAlgorithm::Sha256,
+        ]
+        .into_iter()
+        .find_map(|algorithm| Some((algorithm, self.get(algorithm)?)))
+    }
+
+    /// Parse the digests from the fragment of a URL, as in: `sha256=<hash>`.
+    ///
+    /// Any other parameters in the fragment (e.g., `egg=...`), along with any unsupported
+    /// algorithms, are ignored.
+    pub fn from_fragment(fragment: &str) -> Self {
+        let mut hashes = Self::default();
+        for param in fragment.split('&') {
+            let Some((name, value)) = param.split_once('=') else {
+                continue;
+            };
+            match HashAlgorithm::from_str(name) {
+                Ok(HashAlgorithm::Sha256) => hashes.sha256 = Some(value.to_string()),
+                Ok(HashAlgorithm::Sha384) => hashes.sha384 = Some(value.to_string()),
+                Ok(HashAlgorithm::Sha512) => hashes.sha512 = Some(value.to_string()),
+                Err(_) => {}
+            }
+        }
+        hashes
+    }
+
+    /// Returns `true` if no digests are present.
+    pub fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.sha384.is_none() && self.sha512.is_none()
     }
 }
+
+/// A supported hash algorithm.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = HashAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "sha384" => Ok(Self::Sha384),
+            "sha512" => Ok(Self::Sha512),
+            _ => Err(HashAlgorithmError(s.to_string())),
+        }
+    }
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sha256 => write!(f, "sha256"),
+            Self::Sha384 => write!(f, "sha384"),
+            Self::Sha512 => write!(f, "sha512"),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Unsupported hash algorithm: `{0}` (expected one of `sha256`, `sha384`, or `sha512`)")]
+pub struct HashAlgorithmError(String);
@@ -8,11 +8,14 @@
 //!  * `-c`
 //!  * `--hash` (postfix)
 //!  * `-e`
+//!  * `--index-url`, `--extra-index-url`, `--find-links` and `--no-index`
+//!  * `--no-binary` and `--only-binary`
+//!  * `<path>`, for paths to wheels (e.g., `./wheels/foo-1.0-py3-none-any.whl`)
 //!
 //! Unsupported:
-//!  * `<path>`. TBD
+//!  * `<path>`, for paths to source distributions and directories. TBD
 //!  * `<archive_url>`. TBD
-//!  * Options without a requirement, such as `--find-links` or `--index-url`
+//!  * Other global options, such as `--pre` or `--trusted-host`
 //!
 //! Grammar as implemented:
 //!
@@ -26,9 +29,10 @@
 //! # We check whether the line starts with a letter or a number, in that case we assume it's a
 //! # PEP 508 requirement
 //! # https://packaging.python.org/en/latest/specifications/name-normalization/#valid-non-normalized-names
-//! # This does not (yet?) support plain files or urls, we use a letter or a number as first
-//! # character to assume a PEP 508 requirement
-//! requirement = [a-zA-Z0-9] pep508_grammar_tail wrappable_whitespaces hashes
+//! # This does not (yet?) support plain urls, we use a letter or a number as first
+//! # character to assume a PEP 508 requirement, and a `.` or a path separator to assume a path to
+//! # a wheel
+//! requirement = ([a-zA-Z0-9] pep508_grammar_tail | [./\\] wheel_path) wrappable_whitespaces hashes
 //! hashes = ('--hash' ('=' | wrappable_whitespaces) [a-zA-Z0-9-_]+ ':' [a-zA-Z0-9-_] wrappable_whitespaces+)*
 //! # This should indicate a single backslash before a newline
 //! wrappable_whitespaces = whitespace ('\\\n' | whitespace)*
@@ -38,15 +42,20 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 use unscanny::{Pattern, Scanner};
 use url::Url;
 
-use pep508_rs::{split_scheme, Extras, Pep508Error, Pep508ErrorSource, Requirement, VerbatimUrl};
+use distribution_filename::{WheelFilename, WheelFilenameError};
+use pep508_rs::{
+    split_scheme, Extras, MarkerTree, Pep508Error, Pep508ErrorSource, Requirement, VerbatimUrl,
+    VersionOrUrl,
+};
 use uv_fs::{normalize_url_path, Normalized};
-use uv_normalize::ExtraName;
+use uv_normalize::{ExtraName, PackageName, PackageNameSpecifier};
 
 /// We emit one of those for each requirements.txt entry
 enum RequirementsTxtStatement {
@@ -74,6 +83,10 @@ enum RequirementsTxtStatement {
     FindLinks(FindLink),
     /// `--no-index`
     NoIndex,
+    /// `--no-binary`
+    NoBinary(Vec<PackageNameSpecifier>),
+    /// `--only-binary`
+    OnlyBinary(Vec<PackageNameSpecifier>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -124,6 +137,109 @@ impl FindLink {
     }
 }
 
+/// Parse a raw string for a local wheel passed in place of a requirement, which could be a
+/// `file://` URL or a local path.
+///
+/// For example:
+/// - `file:///home/ferris/wheels/foo-1.0-py3-none-any.whl`
+/// - `./wheels/foo-1.0-py3-none-any.whl`
+/// - `./wheels/foo-1.0-py3-none-any.whl ; python_version < '3.12'`
+///
+/// The package name is read from the wheel filename. Returns `None` if the string does not refer
+/// to a wheel, in which case it should be parsed as a PEP 508 requirement.
+pub fn parse_wheel_path(
+    given: &str,
+    working_dir: impl AsRef<Path>,
+) -> Result<Option<Requirement>, RequirementsTxtParserError> {
+    // Named requirements (as in `foo @ ./wheels/foo-1.0-py3-none-any.whl`) are PEP 508
+    // requirements.
+    if is_named_requirement(given) {
+        return Ok(None);
+    }
+
+    // Split off any trailing markers. As in pip, a path may not itself contain a `;`.
+    let (given, marker) = match given.split_once(';') {
+        Some((given, marker)) => (given.trim(), Some(marker.trim())),
+        None => (given.trim(), None),
+    };
+
+    let path = if let Some((scheme, path)) = split_scheme(given) {
+        if scheme != "file" {
+            return Ok(None);
+        }
+
+        // Ex) `file:///home/ferris/wheels/...` or `file:../wheels/...`
+        let path = path.strip_prefix("//").unwrap_or(path);
+
+        // Transform, e.g., `/C:/Users/ferris/wheel-0.42.0.tar.gz` to `C:\Users\ferris\wheel-0.42.0.tar.gz`.
+        normalize_url_path(path)
+    } else {
+        // A wheel filename is also a valid package name, so only treat it as a path if it contains
+        // a path separator or refers to an existing file (as in `foo-1.0-py3-none-any.whl`).
+        if !given.contains('/')
+            && !given.contains('\\')
+            && !working_dir.as_ref().join(given).is_file()
+        {
+            return Ok(None);
+        }
+
+        // Ex) `./wheels/...`
+        Cow::Borrowed(given)
+    };
+
+    let Some(filename) = Path::new(path.as_ref())
+        .file_name()
+        .and_then(|filename| filename.to_str())
+    else {
+        return Ok(None);
+    };
+    if !Path::new(filename)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
+    {
+        return Ok(None);
+    }
+
+    let filename = WheelFilename::from_str(filename).map_err(|err| {
+        RequirementsTxtParserError::WheelFilename {
+            source: err,
+            given: given.to_string(),
+        }
+    })?;
+
+    let marker = marker
+        .map(MarkerTree::from_str)
+        .transpose()
+        .map_err(|err| RequirementsTxtParserError::WheelMarker {
+            source: err,
+            given: given.to_string(),
+        })?;
+
+    let url = VerbatimUrl::from_path(path, working_dir.as_ref()).with_given(given.to_string());
+
+    Ok(Some(Requirement {
+        name: filename.name,
+        extras: vec![],
+        version_or_url: Some(VersionOrUrl::Url(url)),
+        marker,
+    }))
+}
+
+/// Returns `true` if the string is a named requirement with a direct reference (as in
+/// `foo @ ./wheels/foo-1.0-py3-none-any.whl` or `foo[bar]@file:///...`), rather than a path that
+/// happens to contain an `@`.
+fn is_named_requirement(given: &str) -> bool {
+    let Some((name, _)) = given.split_once('@') else {
+        return false;
+    };
+    let name = name.trim_end();
+    let name = name
+        .strip_suffix(']')
+        .and_then(|name| name.split_once('['))
+        .map_or(name, |(name, _)| name.trim_end());
+    PackageName::from_str(name).is_ok()
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EditableRequirement {
     pub url: VerbatimUrl,
@@ -290,6 +406,11 @@ pub struct RequirementsTxt {
     pub find_links: Vec<FindLink>,
     /// Whether to ignore the index, specified with `--no-index`.
     pub no_index: bool,
+    /// The packages for which wheels are disallowed, specified with `--no-binary`.
+    pub no_binary: Vec<PackageNameSpecifier>,
+    /// The packages for which source distributions are disallowed, specified with
+    /// `--only-binary`.
+    pub only_binary: Vec<PackageNameSpecifier>,
 }
 
 impl RequirementsTxt {
@@ -395,15 +516,32 @@ impl RequirementsTxt {
                 RequirementsTxtStatement::NoIndex => {
                     data.no_index = true;
                 }
+                RequirementsTxtStatement::NoBinary(specifiers) => {
+                    data.no_binary.extend(specifiers);
+                }
+                RequirementsTxtStatement::OnlyBinary(specifiers) => {
+                    data.only_binary.extend(specifiers);
+                }
             }
         }
         Ok(data)
     }
 
     /// Merges other into self
+    ///
+    /// Like pip, options in included files (like `--index-url`) apply globally. If both files
+    /// specify an `--index-url`, the first one wins.
     pub fn update_from(&mut self, other: RequirementsTxt) {
         self.requirements.extend(other.requirements);
         self.constraints.extend(other.constraints);
+        if self.index_url.is_none() {
+            self.index_url = other.index_url;
+        }
+        self.extra_index_urls.extend(other.extra_index_urls);
+        self.find_links.extend(other.find_links);
+        self.no_index |= other.no_index;
+        self.no_binary.extend(other.no_binary);
+        self.only_binary.extend(other.only_binary);
     }
 }
 
@@ -468,6 +606,16 @@ fn parse_entry(
         RequirementsTxtStatement::ExtraIndexUrl(url)
     } else if s.eat_if("--no-index") {
         RequirementsTxtStatement::NoIndex
+    } else if s.eat_if("--no-binary") {
+        let specifiers = parse_value(s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        let specifiers = parse_package_name_specifiers(specifiers, start)?;
+        eat_trailing_line(s)?;
+        RequirementsTxtStatement::NoBinary(specifiers)
+    } else if s.eat_if("--only-binary") {
+        let specifiers = parse_value(s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        let specifiers = parse_package_name_specifiers(specifiers, start)?;
+        eat_trailing_line(s)?;
+        RequirementsTxtStatement::OnlyBinary(specifiers)
     } else if s.eat_if("--find-links") || s.eat_if("-f") {
         let path_or_url = parse_value(s, |c: char| !['\n', '\r'].contains(&c))?;
         let path_or_url = FindLink::parse(path_or_url, working_dir).map_err(|err| {
@@ -479,7 +627,7 @@ fn parse_entry(
             }
         })?;
         RequirementsTxtStatement::FindLinks(path_or_url)
-    } else if s.at(char::is_ascii_alphanumeric) {
+    } else if s.at(char::is_ascii_alphanumeric) || s.at(['.', '/', '\\']) {
         let (requirement, hashes) = parse_requirement_and_hashes(s, content, working_dir)?;
         RequirementsTxtStatement::RequirementEntry(RequirementEntry {
             requirement,
@@ -614,7 +762,11 @@ fn parse_requirement_and_hashes(
         }
     }
 
-    let requirement =
+    // If the requirement is a path to a wheel (as in `./wheels/foo-1.0-py3-none-any.whl`), read the
+    // package name from the filename.
+    let requirement = if let Some(requirement) = parse_wheel_path(requirement, working_dir)? {
+        requirement
+    } else {
         Requirement::parse(requirement, working_dir).map_err(|err| match err.message {
             Pep508ErrorSource::String(_) | Pep508ErrorSource::UrlError(_) => {
                 RequirementsTxtParserError::Pep508 {
@@ -630,7 +782,8 @@ fn parse_requirement_and_hashes(
                     end,
                 }
             }
-        })?;
+        })?
+    };
     let hashes = if has_hashes {
         let hashes = parse_hashes(s)?;
         eat_trailing_line(s)?;
@@ -686,6 +839,25 @@ fn parse_value<'a, T>(
     }
 }
 
+/// Parse a comma-separated list of package names (or `:all:` and `:none:`), as accepted by
+/// `--no-binary` and `--only-binary`.
+fn parse_package_name_specifiers(
+    value: &str,
+    location: usize,
+) -> Result<Vec<PackageNameSpecifier>, RequirementsTxtParserError> {
+    value
+        .split(',')
+        .map(|name| {
+            PackageNameSpecifier::from_str(name.trim()).map_err(|err| {
+                RequirementsTxtParserError::Parser {
+                    message: err.to_string(),
+                    location,
+                }
+            })
+        })
+        .collect()
+}
+
 /// Error parsing requirements.txt, wrapper with filename
 #[derive(Debug)]
 pub struct RequirementsTxtFileError {
@@ -707,6 +879,14 @@ pub enum RequirementsTxtParserError {
     UnsupportedUrl(String),
     MissingRequirementPrefix(String),
     MissingEditablePrefix(String),
+    WheelFilename {
+        source: WheelFilenameError,
+        given: String,
+    },
+    WheelMarker {
+        source: Pep508Error,
+        given: String,
+    },
     Parser {
         message: String,
         location: usize,
@@ -757,6 +937,12 @@ impl RequirementsTxtParserError {
             RequirementsTxtParserError::MissingEditablePrefix(given) => {
                 RequirementsTxtParserError::MissingEditablePrefix(given)
             }
+            RequirementsTxtParserError::WheelFilename { source, given } => {
+                RequirementsTxtParserError::WheelFilename { source, given }
+            }
+            RequirementsTxtParserError::WheelMarker { source, given } => {
+                RequirementsTxtParserError::WheelMarker { source, given }
+            }
             RequirementsTxtParserError::Parser { message, location } => {
                 RequirementsTxtParserError::Parser {
                     message,
@@ -810,6 +996,12 @@ impl Display for RequirementsTxtParserError {
                     "Requirement `{given}` looks like a directory but was passed as a package name. Did you mean `-e {given}`?"
                 )
             }
+            RequirementsTxtParserError::WheelFilename { given, .. } => {
+                write!(f, "Invalid wheel filename: `{given}`")
+            }
+            RequirementsTxtParserError::WheelMarker { given, .. } => {
+                write!(f, "Invalid markers for wheel: `{given}`")
+            }
             RequirementsTxtParserError::Parser { message, location } => {
                 write!(f, "{message} at position {location}")
             }
@@ -835,6 +1027,8 @@ impl std::error::Error for RequirementsTxtParserError {
             RequirementsTxtParserError::UnsupportedUrl(_) => None,
             RequirementsTxtParserError::MissingRequirementPrefix(_) => None,
             RequirementsTxtParserError::MissingEditablePrefix(_) => None,
+            RequirementsTxtParserError::WheelFilename { source, .. } => Some(source),
+            RequirementsTxtParserError::WheelMarker { source, .. } => Some(source),
             RequirementsTxtParserError::UnsupportedRequirement { source, .. } => Some(source),
             RequirementsTxtParserError::Pep508 { source, .. } => Some(source),
             RequirementsTxtParserError::Subfile { source, .. } => Some(source.as_ref()),
@@ -882,6 +1076,20 @@ impl Display for RequirementsTxtFileError {
                     self.file.normalized_display(),
                 )
             }
+            RequirementsTxtParserError::WheelFilename { given, .. } => {
+                write!(
+                    f,
+                    "Invalid wheel filename in `{}`: `{given}`",
+                    self.file.normalized_display(),
+                )
+            }
+            RequirementsTxtParserError::WheelMarker { given, .. } => {
+                write!(
+                    f,
+                    "Invalid markers for wheel in `{}`: `{given}`",
+                    self.file.normalized_display(),
+                )
+            }
             RequirementsTxtParserError::Parser { message, location } => {
                 write!(
                     f,
@@ -929,6 +1137,7 @@ impl From<io::Error> for RequirementsTxtParserError {
 #[cfg(test)]
 mod test {
     use std::path::{Path, PathBuf};
+    use std::str::FromStr;
 
     use anyhow::Result;
     use assert_fs::prelude::*;
@@ -937,9 +1146,11 @@ mod test {
     use itertools::Itertools;
     use tempfile::tempdir;
     use test_case::test_case;
+    use url::Url;
     use uv_fs::Normalized;
+    use uv_normalize::{PackageName, PackageNameSpecifier};
 
-    use crate::{EditableRequirement, RequirementsTxt};
+    use crate::{parse_wheel_path, EditableRequirement, RequirementsTxt};
 
     fn workspace_test_data_dir() -> PathBuf {
         PathBuf::from("./test-data")
@@ -1181,6 +1392,56 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn global_options() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            --index-url https://test.pypi.org/simple
+            --no-binary :all:
+            --only-binary=numpy,Pandas # Avoid building from source.
+            -r base.txt
+            flask
+        "})?;
+        let base_txt = temp_dir.child("base.txt");
+        base_txt.write_str(indoc! {"
+            --extra-index-url https://download.pytorch.org/whl/cpu
+            --no-binary black
+            torch
+        "})?;
+
+        let actual = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path())?;
+        assert_eq!(
+            actual.index_url.as_ref().map(Url::as_str),
+            Some("https://test.pypi.org/simple")
+        );
+        assert_eq!(
+            actual
+                .extra_index_urls
+                .iter()
+                .map(Url::as_str)
+                .collect::<Vec<_>>(),
+            vec!["https://download.pytorch.org/whl/cpu"]
+        );
+        assert_eq!(
+            actual.no_binary,
+            vec![
+                PackageNameSpecifier::All,
+                PackageNameSpecifier::Package(PackageName::from_str("black")?),
+            ]
+        );
+        assert_eq!(
+            actual.only_binary,
+            vec![
+                PackageNameSpecifier::Package(PackageName::from_str("numpy")?),
+                PackageNameSpecifier::Package(PackageName::from_str("pandas")?),
+            ]
+        );
+        assert_eq!(actual.requirements.len(), 2);
+
+        Ok(())
+    }
+
     #[test]
     fn editable_extra() {
         assert_eq!(
@@ -1200,4 +1461,47 @@ mod test {
             Some(("../editable[", "[dev]"))
         );
     }
+
+    #[test]
+    fn wheel_path() -> Result<()> {
+        let working_dir = Path::new("/home/ferris");
+
+        // A path to a wheel, optionally with markers.
+        let requirement = parse_wheel_path("./wheels/foo-1.0-py3-none-any.whl", working_dir)?
+            .expect("Expected a wheel");
+        assert_eq!(requirement.name, PackageName::from_str("foo")?);
+        assert!(requirement.marker.is_none());
+
+        let requirement = parse_wheel_path(
+            "./wheels/foo-1.0-py3-none-any.whl ; python_version < '3.12'",
+            working_dir,
+        )?
+        .expect("Expected a wheel");
+        assert_eq!(requirement.name, PackageName::from_str("foo")?);
+        assert_eq!(
+            requirement.marker.map(|marker| marker.to_string()),
+            Some("python_version < '3.12'".to_string())
+        );
+
+        // Paths may contain whitespace or an `@`.
+        let requirement = parse_wheel_path("./my wheels/foo-1.0-py3-none-any.whl", working_dir)?
+            .expect("Expected a wheel");
+        assert_eq!(requirement.name, PackageName::from_str("foo")?);
+        let requirement = parse_wheel_path("/srv/ci@2/foo-1.0-py3-none-any.whl", working_dir)?
+            .expect("Expected a wheel");
+        assert_eq!(requirement.name, PackageName::from_str("foo")?);
+
+        // Named requirements and other PEP 508 requirements are left as-is.
+        assert!(
+            parse_wheel_path("foo @ ./wheels/foo-1.0-py3-none-any.whl", working_dir)?.is_none()
+        );
+        assert!(parse_wheel_path(
+            "foo[bar]@file:///wheels/foo-1.0-py3-none-any.whl",
+            working_dir
+        )?
+        .is_none());
+        assert!(parse_wheel_path("foo>=1.0 ; python_version < '3.12'", working_dir)?.is_none());
+
+        Ok(())
+    }
 }
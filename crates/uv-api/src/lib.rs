@@ -0,0 +1,404 @@
+//! A stable, embeddable interface to the resolver, distribution database, and installer.
+//!
+//! An [`Environment`] wraps a Python virtual environment, along with the client and indexes used
+//! to resolve and install packages into it. For example:
+//!
+//! ```no_run
+//! use std::str::FromStr;
+//!
+//! use uv_api::{Cache, EnvironmentBuilder, Requirement};
+//!
+//! # async fn example() -> Result<(), uv_api::Error> {
+//! let cache = Cache::from_path(".cache")?;
+//! let environment = EnvironmentBuilder::new(cache).venv(".venv").build().await?;
+//!
+//! let requirements = vec![Requirement::from_str("flask>=3").unwrap()];
+//! let resolution = environment.resolve(requirements, vec![], vec![]).await?;
+//! let summary = environment.install(&resolution.into()).await?;
+//! println!("Installed {} packages", summary.installed.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Installation relies on `tokio::task::block_in_place`, and so must be driven by a
+//! multi-threaded runtime.
+
+use std::path::PathBuf;
+
+use tracing::debug;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use platform_host::Platform;
+use platform_tags::Tags;
+use uv_client::{FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_dispatch::BuildDispatch;
+use uv_installer::{Downloader, Installer, Plan, Planner, Reinstall, SitePackages};
+use uv_interpreter::Virtualenv;
+use uv_resolver::{InMemoryIndex, Manifest, Options, OptionsBuilder, Resolver};
+use uv_traits::{InFlight, SetupPyStrategy};
+
+pub use distribution_types::{IndexLocations, Resolution};
+pub use install_wheel_rs::linker::LinkMode;
+pub use pep508_rs::Requirement;
+pub use uv_cache::Cache;
+pub use uv_client::{Connectivity, TrustedHost};
+pub use uv_resolver::{PreReleaseMode, ResolutionGraph, ResolutionMode};
+pub use uv_traits::{Concurrency, NoBinary, NoBuild};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Platform(#[from] platform_host::PlatformError),
+
+    #[error(transparent)]
+    Interpreter(#[from] uv_interpreter::Error),
+
+    #[error(transparent)]
+    Tags(#[from] platform_tags::TagsError),
+
+    #[error(transparent)]
+    FlatIndex(#[from] uv_client::FlatIndexError),
+
+    #[error(transparent)]
+    Resolve(#[from] uv_resolver::ResolveError),
+
+    #[error("Failed to download distributions")]
+    Download(#[source] uv_installer::Error),
+
+    #[error("Failed to read the installed packages")]
+    SitePackages(#[source] anyhow::Error),
+
+    #[error("Failed to determine installation plan")]
+    Plan(#[source] anyhow::Error),
+
+    #[error("Failed to uninstall {0}")]
+    Uninstall(InstalledDist, #[source] anyhow::Error),
+
+    #[error("Failed to install distributions")]
+    Install(#[source] anyhow::Error),
+}
+
+/// A builder for an [`Environment`].
+#[derive(Debug)]
+pub struct EnvironmentBuilder {
+    cache: Cache,
+    venv: Option<PathBuf>,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    no_build: NoBuild,
+    no_binary: NoBinary,
+    link_mode: LinkMode,
+    trusted_hosts: Vec<TrustedHost>,
+}
+
+impl EnvironmentBuilder {
+    pub fn new(cache: Cache) -> Self {
+        Self {
+            cache,
+            venv: None,
+            index_locations: IndexLocations::default(),
+            connectivity: Connectivity::Online,
+            concurrency: Concurrency::default(),
+            resolution_mode: ResolutionMode::default(),
+            prerelease_mode: PreReleaseMode::default(),
+            no_build: NoBuild::None,
+            no_binary: NoBinary::None,
+            link_mode: LinkMode::default(),
+            trusted_hosts: Vec::new(),
+        }
+    }
+
+    /// Use the virtual environment at the given path, rather than detecting the active
+    /// environment (via `VIRTUAL_ENV`, `CONDA_PREFIX`, or a `.venv` in a parent directory).
+    #[must_use]
+    pub fn venv(mut self, venv: impl Into<PathBuf>) -> Self {
+        self.venv = Some(venv.into());
+        self
+    }
+
+    #[must_use]
+    pub fn index_locations(mut self, index_locations: IndexLocations) -> Self {
+        self.index_locations = index_locations;
+        self
+    }
+
+    #[must_use]
+    pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    #[must_use]
+    pub fn resolution_mode(mut self, resolution_mode: ResolutionMode) -> Self {
+        self.resolution_mode = resolution_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn prerelease_mode(mut self, prerelease_mode: PreReleaseMode) -> Self {
+        self.prerelease_mode = prerelease_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn no_build(mut self, no_build: NoBuild) -> Self {
+        self.no_build = no_build;
+        self
+    }
+
+    #[must_use]
+    pub fn no_binary(mut self, no_binary: NoBinary) -> Self {
+        self.no_binary = no_binary;
+        self
+    }
+
+    #[must_use]
+    pub fn link_mode(mut self, link_mode: LinkMode) -> Self {
+        self.link_mode = link_mode;
+        self
+    }
+
+    /// Disable TLS certificate verification, and permit plain HTTP, for the given hosts.
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
+    /// Query the interpreter and fetch any flat indexes, returning the [`Environment`].
+    pub async fn build(self) -> Result<Environment, Error> {
+        let platform = Platform::current()?;
+        let venv = match self.venv {
+            Some(venv) => Virtualenv::from_path(&venv, platform, &self.cache)?,
+            None => Virtualenv::from_env(platform, &self.cache)?,
+        };
+        debug!(
+            "Using Python {} environment at {}",
+            venv.interpreter().python_version(),
+            venv.python_executable().display()
+        );
+
+        let tags = venv.interpreter().tags()?.clone();
+
+        let client = RegistryClientBuilder::new(self.cache.clone())
+            .index_urls(self.index_locations.index_urls())
+            .connectivity(self.connectivity)
+            .trusted_hosts(self.trusted_hosts)
+            .build();
+
+        let flat_index = {
+            let client = FlatIndexClient::new(&client, &self.cache);
+            let entries = client.fetch(self.index_locations.flat_index()).await?;
+            FlatIndex::from_entries(entries, &tags)
+        };
+
+        let options = OptionsBuilder::new()
+            .resolution_mode(self.resolution_mode)
+            .prerelease_mode(self.prerelease_mode)
+            .build();
+
+        Ok(Environment {
+            cache: self.cache,
+            venv,
+            tags,
+            client,
+            flat_index,
+            index_locations: self.index_locations,
+            options,
+            concurrency: self.concurrency,
+            no_build: self.no_build,
+            no_binary: self.no_binary,
+            link_mode: self.link_mode,
+        })
+    }
+}
+
+/// A Python virtual environment, along with the state required to resolve and install packages
+/// into it.
+///
+/// The interpreter is queried, and any flat indexes are fetched, once, when the environment is
+/// built; each call to [`Environment::resolve`] or [`Environment::install`] reuses them.
+#[derive(Debug)]
+pub struct Environment {
+    cache: Cache,
+    venv: Virtualenv,
+    tags: Tags,
+    client: RegistryClient,
+    flat_index: FlatIndex,
+    index_locations: IndexLocations,
+    options: Options,
+    concurrency: Concurrency,
+    no_build: NoBuild,
+    no_binary: NoBinary,
+    link_mode: LinkMode,
+}
+
+/// The changes made to an [`Environment`] by [`Environment::install`].
+#[derive(Debug, Default)]
+pub struct InstallSummary {
+    /// The distributions that were installed (or reinstalled).
+    pub installed: Vec<CachedDist>,
+    /// The distributions that were removed, to be replaced by a different version.
+    pub uninstalled: Vec<InstalledDist>,
+}
+
+impl Environment {
+    /// Return the underlying virtual environment.
+    pub fn venv(&self) -> &Virtualenv {
+        &self.venv
+    }
+
+    /// Return the cache used for downloads, builds, and interpreter queries.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Resolve a set of requirements for this environment's interpreter and platform.
+    pub async fn resolve(
+        &self,
+        requirements: Vec<Requirement>,
+        constraints: Vec<Requirement>,
+        overrides: Vec<Requirement>,
+    ) -> Result<ResolutionGraph, Error> {
+        // Use a fresh in-memory index for each resolution, as a failed resolution may leave it
+        // with incomplete entries.
+        let index = InMemoryIndex::default();
+        let in_flight = InFlight::default();
+        let build_dispatch = self.build_dispatch(&index, &in_flight);
+
+        let manifest = Manifest::new(
+            requirements,
+            constraints,
+            overrides,
+            Vec::new(),
+            None,
+            Vec::new(),
+        );
+
+        let interpreter = self.venv.interpreter();
+        let resolver = Resolver::new(
+            manifest,
+            self.options,
+            interpreter.markers(),
+            interpreter,
+            &self.tags,
+            &self.client,
+            &self.flat_index,
+            &index,
+            &build_dispatch,
+        );
+
+        Ok(resolver.resolve().await?)
+    }
+
+    /// Install a resolution into this environment, downloading and building any distributions
+    /// that are missing from the cache.
+    ///
+    /// Like `pip install`, packages that are already installed at the resolved version are left
+    /// untouched, and packages that are absent from the resolution are not removed.
+    pub async fn install(&self, resolution: &Resolution) -> Result<InstallSummary, Error> {
+        let _lock = self.venv.lock()?;
+
+        let site_packages =
+            SitePackages::from_executable(&self.venv).map_err(Error::SitePackages)?;
+
+        let Plan {
+            local,
+            remote,
+            reinstalls,
+            extraneous: _,
+        } = Planner::with_requirements(&resolution.requirements())
+            .build(
+                site_packages,
+                &Reinstall::None,
+                &self.no_binary,
+                &self.index_locations,
+                &self.cache,
+                &self.venv,
+                &self.tags,
+            )
+            .map_err(Error::Plan)?;
+
+        // Map any registry-based requirements back to those returned by the resolver.
+        let remote = remote
+            .iter()
+            .map(|dist| {
+                resolution
+                    .get(&dist.name)
+                    .cloned()
+                    .expect("Resolution should contain all packages")
+            })
+            .collect::<Vec<_>>();
+
+        // Download, build, and unzip any missing distributions.
+        let wheels = if remote.is_empty() {
+            Vec::new()
+        } else {
+            let index = InMemoryIndex::default();
+            let in_flight = InFlight::default();
+            let build_dispatch = self.build_dispatch(&index, &in_flight);
+            Downloader::new(&self.cache, &self.tags, &self.client, &build_dispatch)
+                .download(remote, &in_flight)
+                .await
+                .map_err(Error::Download)?
+        };
+
+        // Remove any existing installations.
+        for dist_info in &reinstalls {
+            uv_installer::uninstall(dist_info)
+                .await
+                .map_err(|err| Error::Uninstall(dist_info.clone(), err))?;
+            debug!("Uninstalled {}", dist_info.name());
+        }
+
+        // Install the resolved distributions.
+        let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
+        if !wheels.is_empty() {
+            Installer::new(&self.venv)
+                .with_link_mode(self.link_mode)
+                .install(&wheels)
+                .map_err(Error::Install)?;
+        }
+
+        Ok(InstallSummary {
+            installed: wheels,
+            uninstalled: reinstalls,
+        })
+    }
+
+    /// Create a [`BuildDispatch`] for building source distributions into this environment.
+    fn build_dispatch<'a>(
+        &'a self,
+        index: &'a InMemoryIndex,
+        in_flight: &'a InFlight,
+    ) -> BuildDispatch<'a> {
+        let interpreter = self.venv.interpreter();
+        BuildDispatch::new(
+            &self.client,
+            &self.cache,
+            interpreter,
+            &self.index_locations,
+            &self.flat_index,
+            index,
+            in_flight,
+            interpreter.sys_executable().to_path_buf(),
+            SetupPyStrategy::default(),
+            &self.no_build,
+            &self.no_binary,
+        )
+        .with_options(self.options)
+        .with_concurrency(self.concurrency)
+    }
+}
@@ -0,0 +1,429 @@
+//! A minimal, built-in build backend for pure-Python projects.
+//!
+//! Projects opt in by declaring `build-backend = "uv_build_backend"` in their `[build-system]`
+//! table. Since the backend is implemented in Rust, uv can build these projects without creating
+//! a build environment or installing any build requirements.
+//!
+//! The backend is intentionally limited: all metadata must be declared statically in the
+//! `[project]` table, and the project must consist of a single package or module named after the
+//! project, at `src/{module}/`, `{module}/`, or `{module}.py`.
+
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use data_encoding::BASE64URL_NOPAD;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use fs_err as fs;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::debug;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{DateTime, ZipWriter};
+
+use crate::metadata::ProjectMetadata;
+
+mod metadata;
+
+/// The name of the built-in build backend, as used in `build-system.build-backend`.
+pub const BUILD_BACKEND: &str = "uv_build_backend";
+
+/// The tag for wheels built by the built-in backend, which only supports pure-Python projects.
+const WHEEL_TAG: &str = "py3-none-any";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid `pyproject.toml`")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid project metadata in `pyproject.toml`")]
+    Metadata(#[from] pypi_types::Error),
+    #[error("`pyproject.toml` is missing a `[project]` table")]
+    MissingProject,
+    #[error("The built-in build backend doesn't support dynamic metadata, but `project.dynamic` contains: {0}")]
+    Dynamic(String),
+    #[error("Expected a package at `src/{0}/` or `{0}/`, or a module at `{0}.py`")]
+    MissingModule(String),
+    #[error("Unable to infer the content type of the readme (`{}`); set `project.readme.content-type`", _0.display())]
+    ReadmeContentType(PathBuf),
+    #[error("Failed to write archive")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Failed to walk source tree")]
+    WalkDir(#[from] walkdir::Error),
+}
+
+/// Build a wheel from the source tree into `wheel_dir`, returning the filename of the wheel.
+///
+/// See: <https://peps.python.org/pep-0517/#build-wheel>
+pub fn build_wheel(source_tree: &Path, wheel_dir: &Path) -> Result<String, Error> {
+    let project = ProjectMetadata::from_source_tree(source_tree)?;
+    let source = project.source()?;
+
+    let filename = wheel_filename(&project);
+    debug!("Building wheel with the built-in backend: {filename}");
+    let mut wheel = WheelWriter::new(&wheel_dir.join(&filename))?;
+
+    let module = source.root.join(&source.module);
+    if module.is_file() {
+        wheel.write_file(&source.module, &module)?;
+    } else {
+        for path in source_files(&module)? {
+            let relative = path.strip_prefix(&source.root).expect("walked from root");
+            wheel.write_file(relative, &path)?;
+        }
+    }
+
+    write_dist_info(&mut wheel, &project)?;
+    wheel.finish()?;
+    Ok(filename)
+}
+
+/// Build an editable wheel from the source tree into `wheel_dir`, returning the filename of the
+/// wheel.
+///
+/// The wheel contains a `.pth` file that adds the source directory to `sys.path`, rather than the
+/// package itself.
+///
+/// See: <https://peps.python.org/pep-0660/#build-editable>
+pub fn build_editable(source_tree: &Path, wheel_dir: &Path) -> Result<String, Error> {
+    let project = ProjectMetadata::from_source_tree(source_tree)?;
+    let source = project.source()?;
+
+    let filename = wheel_filename(&project);
+    debug!("Building editable with the built-in backend: {filename}");
+    let mut wheel = WheelWriter::new(&wheel_dir.join(&filename))?;
+
+    let root = fs::canonicalize(&source.root)?;
+    wheel.write_bytes(
+        &format!("_{}.pth", project.dist_name()),
+        format!("{}\n", root.display()).as_bytes(),
+    )?;
+
+    write_dist_info(&mut wheel, &project)?;
+    wheel.finish()?;
+    Ok(filename)
+}
+
+/// Write the `.dist-info` directory for the source tree into `metadata_directory`, returning the
+/// name of the directory.
+///
+/// See: <https://peps.python.org/pep-0517/#prepare-metadata-for-build-wheel>
+pub fn prepare_metadata(source_tree: &Path, metadata_directory: &Path) -> Result<String, Error> {
+    let project = ProjectMetadata::from_source_tree(source_tree)?;
+
+    let dist_info_dir = project.dist_info_dir();
+    let dist_info = metadata_directory.join(&dist_info_dir);
+    fs::create_dir_all(&dist_info)?;
+    fs::write(dist_info.join("METADATA"), project.core_metadata()?)?;
+    fs::write(dist_info.join("WHEEL"), wheel_info())?;
+    if let Some(entry_points) = project.entry_points() {
+        fs::write(dist_info.join("entry_points.txt"), entry_points)?;
+    }
+    Ok(dist_info_dir)
+}
+
+/// Build a source distribution from the source tree into `sdist_dir`, returning the filename of
+/// the source distribution.
+///
+/// The source distribution contains the `pyproject.toml`, the package or module, any readme or
+/// license file referenced in the `[project]` table, and a generated `PKG-INFO`.
+///
+/// See: <https://peps.python.org/pep-0517/#build-sdist>
+pub fn build_source_dist(source_tree: &Path, sdist_dir: &Path) -> Result<String, Error> {
+    let project = ProjectMetadata::from_source_tree(source_tree)?;
+    let source = project.source()?;
+
+    let top_level = format!("{}-{}", project.dist_name(), project.metadata.version);
+    let filename = format!("{top_level}.tar.gz");
+    debug!("Building source distribution with the built-in backend: {filename}");
+
+    // Collect the files to include, relative to the source tree.
+    let mut files = vec![PathBuf::from("pyproject.toml")];
+    files.extend(project.extra_source_files());
+    let module = source.root.join(&source.module);
+    if module.is_file() {
+        files.push(module);
+    } else {
+        files.extend(source_files(&module)?);
+    }
+
+    let file = fs::File::create(sdist_dir.join(&filename))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    for path in files {
+        let path = source_tree.join(path);
+        let relative = path
+            .strip_prefix(source_tree)
+            .expect("collected from source tree");
+        tar.append_path_with_name(&path, Path::new(&top_level).join(relative))?;
+    }
+
+    let pkg_info = project.core_metadata()?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(pkg_info.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(
+        &mut header,
+        Path::new(&top_level).join("PKG-INFO"),
+        pkg_info.as_bytes(),
+    )?;
+
+    tar.into_inner()?.finish()?;
+    Ok(filename)
+}
+
+/// The filename of the wheel for a project (e.g., `foo_bar-1.0.0-py3-none-any.whl`).
+fn wheel_filename(project: &ProjectMetadata) -> String {
+    format!(
+        "{}-{}-{WHEEL_TAG}.whl",
+        project.dist_name(),
+        project.metadata.version
+    )
+}
+
+/// The contents of the `WHEEL` file.
+fn wheel_info() -> String {
+    format!(
+        "Wheel-Version: 1.0\nGenerator: uv {}\nRoot-Is-Purelib: true\nTag: {WHEEL_TAG}\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Write the `.dist-info` directory for a project into the wheel, excluding the `RECORD`.
+fn write_dist_info(wheel: &mut WheelWriter, project: &ProjectMetadata) -> Result<(), Error> {
+    let dist_info_dir = project.dist_info_dir();
+    wheel.write_bytes(
+        &format!("{dist_info_dir}/METADATA"),
+        project.core_metadata()?.as_bytes(),
+    )?;
+    wheel.write_bytes(&format!("{dist_info_dir}/WHEEL"), wheel_info().as_bytes())?;
+    if let Some(entry_points) = project.entry_points() {
+        wheel.write_bytes(
+            &format!("{dist_info_dir}/entry_points.txt"),
+            entry_points.as_bytes(),
+        )?;
+    }
+    wheel.record_path = Some(format!("{dist_info_dir}/RECORD"));
+    Ok(())
+}
+
+/// Returns the files in a package directory, in a stable order, excluding bytecode.
+fn source_files(package: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(package)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != "__pycache__");
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if entry
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "pyc" || extension == "pyo")
+        {
+            continue;
+        }
+        files.push(entry.into_path());
+    }
+    Ok(files)
+}
+
+/// Writes a wheel archive, tracking the hash and size of each file for the `RECORD`.
+struct WheelWriter {
+    zip: ZipWriter<BufWriter<fs::File>>,
+    /// The `RECORD` entries, as `(path, hash, size)`.
+    record: Vec<(String, String, usize)>,
+    /// The path to the `RECORD` within the wheel.
+    record_path: Option<String>,
+}
+
+impl WheelWriter {
+    fn new(path: &Path) -> Result<Self, Error> {
+        let file = fs::File::create(path)?;
+        Ok(Self {
+            zip: ZipWriter::new(BufWriter::new(file)),
+            record: Vec::new(),
+            record_path: None,
+        })
+    }
+
+    /// Use a fixed timestamp, such that builds are reproducible.
+    fn options() -> FileOptions {
+        FileOptions::default().last_modified_time(DateTime::default())
+    }
+
+    /// Write a file from the source tree into the wheel at the given relative path.
+    fn write_file(&mut self, relative: &Path, path: &Path) -> Result<(), Error> {
+        let contents = fs::read(path)?;
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.write_bytes(&relative, &contents)
+    }
+
+    /// Write the given contents into the wheel at the given path.
+    fn write_bytes(&mut self, path: &str, contents: &[u8]) -> Result<(), Error> {
+        self.zip.start_file(path, Self::options())?;
+        self.zip.write_all(contents)?;
+        let hash = Sha256::new().chain_update(contents).finalize();
+        self.record.push((
+            path.to_string(),
+            format!("sha256={}", BASE64URL_NOPAD.encode(&hash)),
+            contents.len(),
+        ));
+        Ok(())
+    }
+
+    /// Write the `RECORD` and finalize the archive.
+    fn finish(mut self) -> Result<(), Error> {
+        let record_path = self
+            .record_path
+            .take()
+            .expect("`.dist-info` to be written before finishing the wheel");
+        let mut record = String::new();
+        for (path, hash, size) in &self.record {
+            record.push_str(&format!("{path},{hash},{size}\n"));
+        }
+        record.push_str(&format!("{record_path},,\n"));
+        self.zip.start_file(record_path, Self::options())?;
+        self.zip.write_all(record.as_bytes())?;
+        self.zip.finish()?.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use fs_err as fs;
+    use zip::ZipArchive;
+
+    use super::{build_editable, build_wheel, prepare_metadata};
+
+    const PYPROJECT_TOML: &str = r#"
+        [project]
+        name = "Foo-Bar"
+        version = "1.0.0"
+        description = "A test project"
+        readme = "README.md"
+        requires-python = ">=3.8"
+        dependencies = ["anyio>=4"]
+        authors = [{ name = "Ferris", email = "ferris@example.com" }]
+
+        [project.optional-dependencies]
+        cli = ["click"]
+
+        [project.scripts]
+        foo = "foo_bar.cli:main"
+
+        [build-system]
+        requires = []
+        build-backend = "uv_build_backend"
+    "#;
+
+    fn create_project(root: &std::path::Path) {
+        fs::write(root.join("pyproject.toml"), PYPROJECT_TOML).unwrap();
+        fs::write(root.join("README.md"), "# Foo Bar\n").unwrap();
+        let package = root.join("src").join("foo_bar");
+        fs::create_dir_all(package.join("__pycache__")).unwrap();
+        fs::write(package.join("__init__.py"), "").unwrap();
+        fs::write(package.join("cli.py"), "def main(): pass\n").unwrap();
+        fs::write(package.join("__pycache__").join("cli.cpython-312.pyc"), "").unwrap();
+    }
+
+    #[test]
+    fn wheel() {
+        let source_tree = tempfile::tempdir().unwrap();
+        let wheel_dir = tempfile::tempdir().unwrap();
+        create_project(source_tree.path());
+
+        let filename = build_wheel(source_tree.path(), wheel_dir.path()).unwrap();
+        assert_eq!(filename, "foo_bar-1.0.0-py3-none-any.whl");
+
+        let mut archive =
+            ZipArchive::new(fs::File::open(wheel_dir.path().join(filename)).unwrap()).unwrap();
+        let files = archive.file_names().collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                "foo_bar/__init__.py",
+                "foo_bar/cli.py",
+                "foo_bar-1.0.0.dist-info/METADATA",
+                "foo_bar-1.0.0.dist-info/WHEEL",
+                "foo_bar-1.0.0.dist-info/entry_points.txt",
+                "foo_bar-1.0.0.dist-info/RECORD",
+            ]
+        );
+
+        let mut metadata = String::new();
+        archive
+            .by_name("foo_bar-1.0.0.dist-info/METADATA")
+            .unwrap()
+            .read_to_string(&mut metadata)
+            .unwrap();
+        insta::assert_snapshot!(metadata, @r###"
+        Metadata-Version: 2.2
+        Name: foo-bar
+        Version: 1.0.0
+        Summary: A test project
+        Author-email: Ferris <ferris@example.com>
+        Requires-Python: >=3.8
+        Requires-Dist: anyio >=4
+        Requires-Dist: click ; extra == 'cli'
+        Provides-Extra: cli
+        Description-Content-Type: text/markdown
+
+        # Foo Bar
+        "###);
+    }
+
+    #[test]
+    fn editable() {
+        let source_tree = tempfile::tempdir().unwrap();
+        let wheel_dir = tempfile::tempdir().unwrap();
+        create_project(source_tree.path());
+
+        let filename = build_editable(source_tree.path(), wheel_dir.path()).unwrap();
+        let mut archive =
+            ZipArchive::new(fs::File::open(wheel_dir.path().join(filename)).unwrap()).unwrap();
+        let mut pth = String::new();
+        archive
+            .by_name("_foo_bar.pth")
+            .unwrap()
+            .read_to_string(&mut pth)
+            .unwrap();
+        let src = fs::canonicalize(source_tree.path().join("src")).unwrap();
+        assert_eq!(pth.trim_end(), src.display().to_string());
+    }
+
+    #[test]
+    fn dynamic_metadata() {
+        let source_tree = tempfile::tempdir().unwrap();
+        let metadata_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            source_tree.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "foo"
+            version = "1.0.0"
+            dynamic = ["readme"]
+            "#,
+        )
+        .unwrap();
+
+        let err = prepare_metadata(source_tree.path(), metadata_dir.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The built-in build backend doesn't support dynamic metadata, but `project.dynamic` contains: readme"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use serde::Deserialize;
+
+use pypi_types::Metadata21;
+
+use crate::Error;
+
+/// A `pyproject.toml` with a `[project]` table, per PEP 621.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    project: Option<Project>,
+}
+
+/// The fields of the `[project]` table that aren't covered by [`Metadata21`].
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    description: Option<String>,
+    readme: Option<Readme>,
+    license: Option<License>,
+    #[serde(default)]
+    authors: Vec<Contact>,
+    #[serde(default)]
+    maintainers: Vec<Contact>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    classifiers: Vec<String>,
+    #[serde(default)]
+    urls: BTreeMap<String, String>,
+    #[serde(default)]
+    scripts: BTreeMap<String, String>,
+    #[serde(default)]
+    gui_scripts: BTreeMap<String, String>,
+    #[serde(default)]
+    entry_points: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(default)]
+    dynamic: Vec<String>,
+}
+
+/// The `project.readme` field, which is either a path or a table.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Readme {
+    Path(PathBuf),
+    File {
+        file: PathBuf,
+        #[serde(rename = "content-type")]
+        content_type: Option<String>,
+    },
+    Text {
+        text: String,
+        #[serde(rename = "content-type")]
+        content_type: String,
+    },
+}
+
+/// The `project.license` field.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum License {
+    Text { text: String },
+    File { file: PathBuf },
+}
+
+/// An entry in `project.authors` or `project.maintainers`.
+#[derive(Deserialize, Debug)]
+struct Contact {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+/// The metadata for a project built with the built-in backend.
+#[derive(Debug)]
+pub(crate) struct ProjectMetadata {
+    /// The core metadata, as used during resolution.
+    pub(crate) metadata: Metadata21,
+    /// The remaining `[project]` fields.
+    project: Project,
+    /// The root of the source tree.
+    source_tree: PathBuf,
+}
+
+impl ProjectMetadata {
+    /// Read the project metadata from the `pyproject.toml` in the given source tree.
+    pub(crate) fn from_source_tree(source_tree: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(source_tree.join("pyproject.toml"))?;
+        let metadata = Metadata21::parse_pyproject_toml(&contents)?;
+        let project = toml::from_str::<PyProjectToml>(&contents)?
+            .project
+            .ok_or(Error::MissingProject)?;
+
+        // The built-in backend doesn't compute any metadata, so everything must be static.
+        if !project.dynamic.is_empty() {
+            return Err(Error::Dynamic(project.dynamic.join(", ")));
+        }
+
+        Ok(Self {
+            metadata,
+            project,
+            source_tree: source_tree.to_path_buf(),
+        })
+    }
+
+    /// The name of the distribution, as used in file names (e.g., `foo_bar`).
+    pub(crate) fn dist_name(&self) -> String {
+        self.metadata.name.as_dist_info_name().to_string()
+    }
+
+    /// The name of the `.dist-info` directory (e.g., `foo_bar-1.0.0.dist-info`).
+    pub(crate) fn dist_info_dir(&self) -> String {
+        format!("{}-{}.dist-info", self.dist_name(), self.metadata.version)
+    }
+
+    /// The files outside the package that should be included in a source distribution.
+    pub(crate) fn extra_source_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        match &self.project.readme {
+            Some(Readme::Path(file) | Readme::File { file, .. }) => files.push(file.clone()),
+            Some(Readme::Text { .. }) | None => {}
+        }
+        if let Some(License::File { file }) = &self.project.license {
+            files.push(file.clone());
+        }
+        files
+    }
+
+    /// Render the core metadata file (`METADATA` or `PKG-INFO`).
+    ///
+    /// See: <https://packaging.python.org/en/latest/specifications/core-metadata/>
+    pub(crate) fn core_metadata(&self) -> Result<String, Error> {
+        let project = &self.project;
+        // Dynamic fields are rejected, so every field is static, as Metadata 2.2 expresses by the
+        // absence of `Dynamic`.
+        let mut fields: Vec<(&str, String)> = vec![
+            ("Metadata-Version", "2.2".to_string()),
+            ("Name", self.metadata.name.to_string()),
+            ("Version", self.metadata.version.to_string()),
+        ];
+        if let Some(description) = &project.description {
+            fields.push(("Summary", description.clone()));
+        }
+        if !project.keywords.is_empty() {
+            fields.push(("Keywords", project.keywords.join(",")));
+        }
+        for (field, email_field, contacts) in [
+            ("Author", "Author-email", &project.authors),
+            ("Maintainer", "Maintainer-email", &project.maintainers),
+        ] {
+            let (names, emails) = split_contacts(contacts);
+            if !names.is_empty() {
+                fields.push((field, names.join(", ")));
+            }
+            if !emails.is_empty() {
+                fields.push((email_field, emails.join(", ")));
+            }
+        }
+        match &project.license {
+            Some(License::Text { text }) => fields.push(("License", text.clone())),
+            Some(License::File { file }) => {
+                fields.push(("License", fs::read_to_string(self.source_tree.join(file))?));
+            }
+            None => {}
+        }
+        for classifier in &project.classifiers {
+            fields.push(("Classifier", classifier.clone()));
+        }
+        for (label, url) in &project.urls {
+            fields.push(("Project-URL", format!("{label}, {url}")));
+        }
+        if let Some(requires_python) = &self.metadata.requires_python {
+            fields.push(("Requires-Python", requires_python.to_string()));
+        }
+        for requirement in &self.metadata.requires_dist {
+            fields.push(("Requires-Dist", requirement.to_string()));
+        }
+        for extra in &self.metadata.provides_extras {
+            fields.push(("Provides-Extra", extra.to_string()));
+        }
+
+        // The readme forms the body of the message.
+        let readme = match &project.readme {
+            Some(Readme::Path(file)) => {
+                Some((fs::read_to_string(self.source_tree.join(file))?, None))
+            }
+            Some(Readme::File { file, content_type }) => Some((
+                fs::read_to_string(self.source_tree.join(file))?,
+                content_type.clone(),
+            )),
+            Some(Readme::Text { text, content_type }) => {
+                Some((text.clone(), Some(content_type.clone())))
+            }
+            None => None,
+        };
+        let body = if let Some((body, content_type)) = readme {
+            let content_type = match content_type {
+                Some(content_type) => content_type,
+                None => readme_content_type(project.readme.as_ref())?,
+            };
+            fields.push(("Description-Content-Type", content_type));
+            Some(body)
+        } else {
+            None
+        };
+
+        let mut metadata = String::new();
+        for (field, value) in fields {
+            // Multi-line values (like a license) continue on indented lines.
+            let value = value.trim_end().replace('\n', "\n        ");
+            writeln!(metadata, "{field}: {value}").unwrap();
+        }
+        if let Some(body) = body {
+            writeln!(metadata).unwrap();
+            metadata.push_str(&body);
+        }
+        Ok(metadata)
+    }
+
+    /// Render the `entry_points.txt` file, if the project declares any entry points.
+    pub(crate) fn entry_points(&self) -> Option<String> {
+        let project = &self.project;
+        let groups = [
+            ("console_scripts", &project.scripts),
+            ("gui_scripts", &project.gui_scripts),
+        ]
+        .into_iter()
+        .chain(
+            project
+                .entry_points
+                .iter()
+                .map(|(group, entries)| (group.as_str(), entries)),
+        )
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect::<Vec<_>>();
+        if groups.is_empty() {
+            return None;
+        }
+
+        let mut entry_points = String::new();
+        for (group, entries) in groups {
+            writeln!(entry_points, "[{group}]").unwrap();
+            for (name, target) in entries {
+                writeln!(entry_points, "{name} = {target}").unwrap();
+            }
+            writeln!(entry_points).unwrap();
+        }
+        Some(entry_points)
+    }
+
+    /// Locate the importable package or module for the project.
+    ///
+    /// The built-in backend doesn't support any configuration, so the module name is derived from
+    /// the project name, and must be at `src/{module}/`, `{module}/`, or `{module}.py`.
+    pub(crate) fn source(&self) -> Result<Source, Error> {
+        let module = self.dist_name().to_lowercase();
+        for root in [self.source_tree.join("src"), self.source_tree.clone()] {
+            if root.join(&module).join("__init__.py").is_file() {
+                return Ok(Source {
+                    root,
+                    module: PathBuf::from(module),
+                });
+            }
+        }
+        let file = format!("{module}.py");
+        if self.source_tree.join(&file).is_file() {
+            return Ok(Source {
+                root: self.source_tree.clone(),
+                module: PathBuf::from(file),
+            });
+        }
+        Err(Error::MissingModule(module))
+    }
+}
+
+/// The location of a project's importable package or module.
+#[derive(Debug)]
+pub(crate) struct Source {
+    /// The directory containing the package or module (i.e., the directory that's added to
+    /// `sys.path`).
+    pub(crate) root: PathBuf,
+    /// The package directory or module file, relative to the [`Source::root`].
+    pub(crate) module: PathBuf,
+}
+
+/// Split contacts into the `Author` and `Author-email` fields, per PEP 621.
+fn split_contacts(contacts: &[Contact]) -> (Vec<String>, Vec<String>) {
+    let mut names = Vec::new();
+    let mut emails = Vec::new();
+    for contact in contacts {
+        match (&contact.name, &contact.email) {
+            (Some(name), Some(email)) => emails.push(format!("{name} <{email}>")),
+            (None, Some(email)) => emails.push(email.clone()),
+            (Some(name), None) => names.push(name.clone()),
+            (None, None) => {}
+        }
+    }
+    (names, emails)
+}
+
+/// Infer the content type of a readme file from its extension, per PEP 621.
+fn readme_content_type(readme: Option<&Readme>) -> Result<String, Error> {
+    let Some(Readme::Path(file) | Readme::File { file, .. }) = readme else {
+        return Ok("text/plain".to_string());
+    };
+    match file
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("md") => Ok("text/markdown".to_string()),
+        Some("rst") => Ok("text/x-rst".to_string()),
+        Some("txt") => Ok("text/plain".to_string()),
+        _ => Err(Error::ReadmeContentType(file.clone())),
+    }
+}
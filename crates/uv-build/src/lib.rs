@@ -44,6 +44,31 @@ static LD_NOT_FOUND_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"/usr/bin/ld: cannot find -l([a-zA-Z10-9]+): No such file or directory").unwrap()
 });
 
+/// Well-known system dependencies, as `(header, library, debian, fedora)`, used to suggest the
+/// system package that provides a missing header or shared library.
+///
+/// A header matches if it's equal to the given name, or nested within a directory of that name
+/// (e.g., `openssl/ssl.h`).
+static SYSTEM_DEPENDENCIES: &[(&str, &str, &str, &str)] = &[
+    ("libpq-fe.h", "pq", "libpq-dev", "postgresql-devel"),
+    (
+        "mysql.h",
+        "mysqlclient",
+        "default-libmysqlclient-dev",
+        "mysql-devel",
+    ),
+    ("ffi.h", "ffi", "libffi-dev", "libffi-devel"),
+    ("openssl", "ssl", "libssl-dev", "openssl-devel"),
+    ("libxml", "xml2", "libxml2-dev", "libxml2-devel"),
+    ("libxslt", "xslt", "libxslt1-dev", "libxslt-devel"),
+    ("zlib.h", "z", "zlib1g-dev", "zlib-devel"),
+    ("jpeglib.h", "jpeg", "libjpeg-dev", "libjpeg-turbo-devel"),
+    ("sqlite3.h", "sqlite3", "libsqlite3-dev", "sqlite-devel"),
+    ("yaml.h", "yaml", "libyaml-dev", "libyaml-devel"),
+    ("krb5.h", "krb5", "libkrb5-dev", "krb5-devel"),
+    ("Python.h", "python3", "python3-dev", "python3-devel"),
+];
+
 /// The default backend to use when PEP 517 is used without a `build-system` section.
 static DEFAULT_BACKEND: Lazy<Pep517Backend> = Lazy::new(|| Pep517Backend {
     backend: "setuptools.build_meta:__legacy__".to_string(),
@@ -93,6 +118,8 @@ pub enum Error {
     },
     #[error("Failed to build PATH for build script")]
     BuildScriptPath(#[source] env::JoinPathsError),
+    #[error("Failed to build with the built-in build backend")]
+    BuiltinBackend(#[from] uv_build_backend::Error),
 }
 
 #[derive(Debug)]
@@ -101,10 +128,31 @@ pub enum MissingLibrary {
     Linker(String),
 }
 
+impl MissingLibrary {
+    /// Return the system packages that are known to provide the missing library, as
+    /// `(debian, fedora)`.
+    fn system_packages(&self) -> Option<(&'static str, &'static str)> {
+        SYSTEM_DEPENDENCIES
+            .iter()
+            .find(|(header, library, ..)| match self {
+                MissingLibrary::Header(missing) => {
+                    missing == header
+                        || missing
+                            .strip_prefix(header)
+                            .is_some_and(|rest| rest.starts_with('/'))
+                }
+                MissingLibrary::Linker(missing) => missing == library,
+            })
+            .map(|(.., debian, fedora)| (*debian, *fedora))
+    }
+}
+
 #[derive(Debug, Error)]
 pub struct MissingHeaderCause {
     missing_library: MissingLibrary,
     package_id: String,
+    /// The `Requires-External` entries declared by the package, if any.
+    requires_external: Vec<String>,
 }
 
 impl Display for MissingHeaderCause {
@@ -115,17 +163,38 @@ impl Display for MissingHeaderCause {
                     f,
                     "This error likely indicates that you need to install a library that provides \"{}\" for {}",
                     header, self.package_id
-                )
+                )?;
+                if let Some((debian, fedora)) = self.missing_library.system_packages() {
+                    write!(f, " (e.g. {debian} or {fedora})")?;
+                }
             }
             MissingLibrary::Linker(library) => {
-                write!(
-                    f,
-                    "This error likely indicates that you need to install the library that provides a shared library \
-                    for {library} for {package_id} (e.g. lib{library}-dev)",
-                    library = library, package_id = self.package_id
-                )
+                if let Some((debian, fedora)) = self.missing_library.system_packages() {
+                    write!(
+                        f,
+                        "This error likely indicates that you need to install the library that provides a shared library \
+                        for {library} for {package_id} (e.g. {debian} or {fedora})",
+                        library = library, package_id = self.package_id
+                    )?;
+                } else {
+                    write!(
+                        f,
+                        "This error likely indicates that you need to install the library that provides a shared library \
+                        for {library} for {package_id} (e.g. lib{library}-dev)",
+                        library = library, package_id = self.package_id
+                    )?;
+                }
             }
         }
+        if !self.requires_external.is_empty() {
+            write!(
+                f,
+                ". {} declares the following external requirements: {}",
+                self.package_id,
+                self.requires_external.join(", ")
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -134,6 +203,7 @@ impl Error {
         message: String,
         output: &Output,
         package_id: impl Into<String>,
+        requires_external: &[String],
     ) -> Self {
         let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -161,6 +231,7 @@ impl Error {
                 missing_header_cause: MissingHeaderCause {
                     missing_library,
                     package_id: package_id.into(),
+                    requires_external: requires_external.to_vec(),
                 },
             };
         }
@@ -198,6 +269,12 @@ struct Pep517Backend {
 }
 
 impl Pep517Backend {
+    /// Returns `true` if the project uses uv's built-in build backend, which runs in-process
+    /// rather than in the build environment.
+    fn is_builtin(&self) -> bool {
+        self.backend == uv_build_backend::BUILD_BACKEND
+    }
+
     fn backend_import(&self) -> String {
         let import = if let Some((path, object)) = self.backend.split_once(':') {
             format!("from {path} import {object} as backend")
@@ -249,8 +326,9 @@ pub struct SourceBuild {
     source_tree: PathBuf,
     /// If performing a PEP 517 build, the backend to use.
     pep517_backend: Option<Pep517Backend>,
-    /// The virtual environment in which to build the source distribution.
-    venv: Virtualenv,
+    /// The virtual environment in which to build the source distribution, unless the built-in
+    /// backend is used.
+    venv: Option<Virtualenv>,
     /// Populated if `prepare_metadata_for_build_wheel` was called.
     ///
     /// > If the build frontend has previously called prepare_metadata_for_build_wheel and depends
@@ -263,6 +341,9 @@ pub struct SourceBuild {
     metadata_directory: Option<PathBuf>,
     /// Package id such as `foo-1.2.3`, for error reporting
     package_id: String,
+    /// The `Requires-External` entries from the source distribution's `PKG-INFO`, for error
+    /// reporting
+    requires_external: Vec<String>,
     /// Whether we do a regular PEP 517 build or an PEP 660 editable build
     build_kind: BuildKind,
 }
@@ -314,43 +395,70 @@ impl SourceBuild {
             source_root
         };
 
+        // Read any system dependencies declared in `PKG-INFO`, to improve error messages.
+        let requires_external = fs::read(source_tree.join("PKG-INFO"))
+            .ok()
+            .and_then(|content| pypi_types::Metadata21::parse(&content).ok())
+            .map(|metadata| metadata.requires_external)
+            .unwrap_or_default();
+
         let default_backend: Pep517Backend = DEFAULT_BACKEND.clone();
 
         // Check if we have a PEP 517 build backend.
         let pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
             .map_err(|err| *err)?;
 
-        let venv = gourgeist::create_venv(&temp_dir.path().join(".venv"), interpreter.clone())?;
-
-        // Setup the build environment.
-        let resolved_requirements = Self::get_resolved_requirements(
-            build_context,
-            source_build_context,
-            &default_backend,
-            pep517_backend.as_ref(),
-        )
-        .await?;
-
-        build_context
-            .install(&resolved_requirements, &venv)
-            .await
-            .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+        // The built-in backend runs in-process, so it needs neither a build environment nor any
+        // build requirements.
+        let venv = if pep517_backend
+            .as_ref()
+            .is_some_and(Pep517Backend::is_builtin)
+        {
+            debug!("Using the built-in build backend for: {package_id}");
+            None
+        } else {
+            let venv = gourgeist::create_venv(
+                &temp_dir.path().join(".venv"),
+                interpreter.clone(),
+                None,
+                false,
+                false,
+            )?;
+
+            // Setup the build environment.
+            let resolved_requirements = Self::get_resolved_requirements(
+                build_context,
+                source_build_context,
+                &default_backend,
+                pep517_backend.as_ref(),
+            )
+            .await?;
 
-        // If we're using the default backend configuration, skip `get_requires_for_build_*`, since
-        // we already installed the requirements above.
-        if let Some(pep517_backend) = &pep517_backend {
-            if pep517_backend != &default_backend {
-                create_pep517_build_environment(
-                    &source_tree,
-                    &venv,
-                    pep517_backend,
-                    build_context,
-                    &package_id,
-                    build_kind,
-                )
-                .await?;
+            build_context
+                .install(&resolved_requirements, &venv)
+                .await
+                .map_err(|err| {
+                    Error::RequirementsInstall("build-system.requires (install)", err)
+                })?;
+
+            // If we're using the default backend configuration, skip `get_requires_for_build_*`,
+            // since we already installed the requirements above.
+            if let Some(pep517_backend) = &pep517_backend {
+                if pep517_backend != &default_backend {
+                    create_pep517_build_environment(
+                        &source_tree,
+                        &venv,
+                        pep517_backend,
+                        build_context,
+                        &package_id,
+                        build_kind,
+                    )
+                    .await?;
+                }
             }
-        }
+
+            Some(venv)
+        };
 
         Ok(Self {
             temp_dir,
@@ -360,9 +468,19 @@ impl SourceBuild {
             build_kind,
             metadata_directory: None,
             package_id,
+            requires_external,
         })
     }
 
+    /// The virtual environment in which to build the source distribution.
+    ///
+    /// Only the built-in backend builds without one, in-process.
+    fn venv(&self) -> &Virtualenv {
+        self.venv
+            .as_ref()
+            .expect("a build environment for all but the built-in backend")
+    }
+
     async fn get_resolved_requirements(
         build_context: &impl BuildContext,
         source_build_context: SourceBuildContext,
@@ -453,7 +571,13 @@ impl SourceBuild {
                 // 517 builds the default in the future.
                 // See: https://github.com/pypa/pip/issues/9175.
                 match setup_py {
-                    SetupPyStrategy::Pep517 => Ok(Some(default_backend.clone())),
+                    SetupPyStrategy::Pep517 => {
+                        debug!(
+                            "No `pyproject.toml` found; building with the default backend: `{}`",
+                            default_backend.backend
+                        );
+                        Ok(Some(default_backend.clone()))
+                    }
                     SetupPyStrategy::Setuptools => Ok(None),
                 }
             }
@@ -476,6 +600,13 @@ impl SourceBuild {
         let metadata_directory = self.temp_dir.path().join("metadata_directory");
         fs::create_dir(&metadata_directory)?;
 
+        if pep517_backend.is_builtin() {
+            let dist_info =
+                uv_build_backend::prepare_metadata(&self.source_tree, &metadata_directory)?;
+            self.metadata_directory = Some(metadata_directory.join(dist_info));
+            return Ok(self.metadata_directory.clone());
+        }
+
         debug!(
             "Calling `{}.prepare_metadata_for_build_wheel()`",
             pep517_backend.backend
@@ -494,9 +625,9 @@ impl SourceBuild {
         let span = info_span!(
             "run_python_script",
             script="prepare_metadata_for_build_wheel",
-            python_version = %self.venv.interpreter().python_version()
+            python_version = %self.venv().interpreter().python_version()
         );
-        let output = run_python_script(&self.venv, &script, &self.source_tree)
+        let output = run_python_script(self.venv(), &script, &self.source_tree)
             .instrument(span)
             .await?;
         if !output.status.success() {
@@ -504,6 +635,7 @@ impl SourceBuild {
                 "Build backend failed to determine metadata through `prepare_metadata_for_build_wheel`".to_string(),
                 &output,
                 &self.package_id,
+                &self.requires_external,
             ));
         }
         let message = output
@@ -520,6 +652,7 @@ impl SourceBuild {
                     ),
                     &output,
                     &self.package_id,
+                    &self.requires_external,
                 )
             })?;
         if message.is_empty() {
@@ -555,11 +688,11 @@ impl SourceBuild {
                 return Err(Error::EditableSetupPy);
             }
             // We checked earlier that setup.py exists.
-            let python_interpreter = self.venv.python_executable();
+            let python_interpreter = self.venv().python_executable();
             let span = info_span!(
                 "run_python_script",
                 script="setup.py bdist_wheel",
-                python_version = %self.venv.interpreter().python_version()
+                python_version = %self.venv().interpreter().python_version()
             );
             let output = Command::new(&python_interpreter)
                 .args(["setup.py", "bdist_wheel"])
@@ -573,6 +706,7 @@ impl SourceBuild {
                     "Failed building wheel through setup.py".to_string(),
                     &output,
                     &self.package_id,
+                    &self.requires_external,
                 ));
             }
             let dist = fs::read_dir(self.source_tree.join("dist"))?;
@@ -583,8 +717,9 @@ impl SourceBuild {
                         "Expected exactly wheel in `dist/` after invoking setup.py, found {dist_dir:?}"
                     ),
                     &output,
-                    &self.package_id)
-                );
+                    &self.package_id,
+                    &self.requires_external,
+                ));
             };
 
             let from = dist_wheel.path();
@@ -600,6 +735,15 @@ impl SourceBuild {
         wheel_dir: &Path,
         pep517_backend: &Pep517Backend,
     ) -> Result<String, Error> {
+        if pep517_backend.is_builtin() {
+            return Ok(match self.build_kind {
+                BuildKind::Wheel => uv_build_backend::build_wheel(&self.source_tree, wheel_dir)?,
+                BuildKind::Editable => {
+                    uv_build_backend::build_editable(&self.source_tree, wheel_dir)?
+                }
+            });
+        }
+
         let metadata_directory = self
             .metadata_directory
             .as_deref()
@@ -619,9 +763,9 @@ impl SourceBuild {
         let span = info_span!(
             "run_python_script",
             script=format!("build_{}", self.build_kind),
-            python_version = %self.venv.interpreter().python_version()
+            python_version = %self.venv().interpreter().python_version()
         );
-        let output = run_python_script(&self.venv, &script, &self.source_tree)
+        let output = run_python_script(self.venv(), &script, &self.source_tree)
             .instrument(span)
             .await?;
         if !output.status.success() {
@@ -632,6 +776,7 @@ impl SourceBuild {
                 ),
                 &output,
                 &self.package_id,
+                &self.requires_external,
             ));
         }
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -646,6 +791,7 @@ impl SourceBuild {
                 ),
                 &output,
                 &self.package_id,
+                &self.requires_external,
             ));
         };
         Ok(distribution_filename.to_string())
@@ -707,6 +853,7 @@ async fn create_pep517_build_environment(
             format!("Build backend failed to determine extra requires with `build_{build_kind}()`"),
             &output,
             package_id,
+            &[],
         ));
     }
     let extra_requires = output
@@ -725,6 +872,7 @@ async fn create_pep517_build_environment(
             ),
             &output,
             package_id,
+            &[],
         )
     })?;
 
@@ -819,6 +967,7 @@ mod test {
             "Failed building wheel through setup.py".to_string(),
             &output,
             "pygraphviz-1.11",
+            &[],
         );
         assert!(matches!(err, Error::MissingHeader { .. }));
         insta::assert_display_snapshot!(err, @r###"
@@ -869,6 +1018,7 @@ mod test {
             "Failed building wheel through setup.py".to_string(),
             &output,
             "pygraphviz-1.11",
+            &[],
         );
         assert!(matches!(err, Error::MissingHeader { .. }));
         insta::assert_display_snapshot!(err, @r###"
@@ -888,4 +1038,36 @@ mod test {
             @"This error likely indicates that you need to install the library that provides a shared library for ncurses for pygraphviz-1.11 (e.g. libncurses-dev)"
         );
     }
+
+    #[test]
+    fn missing_header_system_package() {
+        let output = Output {
+            status: ExitStatus::default(), // This is wrong but `from_raw` is platform-gated.
+            stdout: Vec::new(),
+            stderr: indoc!(
+                r"
+                In file included from psycopg/adapter_binary.c:28:
+                ./psycopg/psycopg.h:35:10: fatal error: libpq-fe.h: No such file or directory
+                   35 | #include <libpq-fe.h>
+                      |          ^~~~~~~~~~~~
+                compilation terminated.
+                error: command '/usr/bin/x86_64-linux-gnu-gcc' failed with exit code 1
+                "
+            )
+            .as_bytes()
+            .to_vec(),
+        };
+
+        let err = Error::from_command_output(
+            "Failed building wheel through setup.py".to_string(),
+            &output,
+            "psycopg2-2.9.9",
+            &["libpq (>=9.1)".to_string()],
+        );
+        assert!(matches!(err, Error::MissingHeader { .. }));
+        insta::assert_display_snapshot!(
+            std::error::Error::source(&err).unwrap(),
+            @r###"This error likely indicates that you need to install a library that provides "libpq-fe.h" for psycopg2-2.9.9 (e.g. libpq-dev or postgresql-devel). psycopg2-2.9.9 declares the following external requirements: libpq (>=9.1)"###
+        );
+    }
 }
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use directories::ProjectDirs;
+use url::Url;
 
 use crate::Cache;
 
@@ -23,6 +24,68 @@ pub struct CacheArgs {
     /// Path to the cache directory.
     #[arg(global = true, long, env = "UV_CACHE_DIR")]
     cache_dir: Option<PathBuf>,
+
+    /// Treat the cache directory as a pre-populated, read-only cache.
+    ///
+    /// Entries are read from the cache directory without attempting any writes or locks; any
+    /// missing entries are written to a temporary scratch cache instead. Useful for sharing a warm
+    /// cache that's baked into an immutable location, like a container image.
+    #[arg(
+        global = true,
+        long,
+        conflicts_with = "no_cache",
+        env = "UV_CACHE_READONLY"
+    )]
+    cache_readonly: bool,
+
+    /// Path to a pre-populated, read-only cache directory to overlay on the cache directory.
+    ///
+    /// The read-only cache is consulted in the same way as with `--cache-readonly`, and is never
+    /// written to or locked. The difference is where missing entries are written: with
+    /// `--cache-readonly`, the cache directory itself is the read-only cache, and new entries go
+    /// to a temporary scratch cache that's discarded on exit; with `--cache-readonly-dir`, new
+    /// entries go to the (writable) cache directory as usual, and persist across invocations.
+    #[arg(
+        global = true,
+        long,
+        conflicts_with = "cache_readonly",
+        env = "UV_CACHE_READONLY_DIR"
+    )]
+    cache_readonly_dir: Option<PathBuf>,
+
+    /// The base URL of a remote cache for wheels built from source distributions.
+    ///
+    /// Before building a source distribution, uv checks the remote cache for a wheel built from
+    /// identical sources with the same build settings, for a compatible interpreter and platform.
+    /// Downloaded wheels are verified against the digest recorded alongside them. The remote
+    /// cache can be any HTTP server (or S3-compatible bucket) that supports `GET` requests (and
+    /// `PUT` requests, with `--remote-build-cache-upload`).
+    #[arg(global = true, long, env = "UV_REMOTE_BUILD_CACHE")]
+    remote_build_cache: Option<Url>,
+
+    /// Upload wheels built from source distributions to the remote build cache.
+    ///
+    /// By default, the remote build cache is only read from, such that only trusted machines
+    /// (e.g., CI on the default branch) populate it.
+    #[arg(
+        global = true,
+        long,
+        requires = "remote_build_cache",
+        env = "UV_REMOTE_BUILD_CACHE_UPLOAD"
+    )]
+    remote_build_cache_upload: bool,
+}
+
+impl CacheArgs {
+    /// Use the given cache directory, unless one was provided via `--cache-dir` or
+    /// `UV_CACHE_DIR`.
+    #[must_use]
+    pub fn with_default_cache_dir(self, cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            cache_dir: self.cache_dir.or(cache_dir),
+            ..self
+        }
+    }
 }
 
 impl TryFrom<CacheArgs> for Cache {
@@ -34,9 +97,24 @@ impl TryFrom<CacheArgs> for Cache {
     /// 3. The system-appropriate cache directory.
     /// 4. A `.uv_cache` directory in the current working directory.
     ///
+    /// If the user requested `--cache-readonly`, the selected cache directory is instead overlaid
+    /// on a temporary cache directory, and never written to. If the user provided
+    /// `--cache-readonly-dir`, that directory is overlaid on the selected cache directory. If the
+    /// user provided `--remote-build-cache`, built wheels are fetched from the remote cache (and
+    /// uploaded to it, with `--remote-build-cache-upload`).
+    ///
     /// Returns an absolute cache dir.
     fn try_from(value: CacheArgs) -> Result<Self, Self::Error> {
-        if value.no_cache {
+        let cache = if value.cache_readonly {
+            let readonly = if let Some(cache_dir) = value.cache_dir {
+                cache_dir
+            } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
+                project_dirs.cache_dir().to_path_buf()
+            } else {
+                PathBuf::from(".uv_cache")
+            };
+            Cache::temp()?.with_readonly(readonly)
+        } else if value.no_cache {
             Cache::temp()
         } else if let Some(cache_dir) = value.cache_dir {
             Cache::from_path(cache_dir)
@@ -44,6 +122,18 @@ impl TryFrom<CacheArgs> for Cache {
             Cache::from_path(project_dirs.cache_dir())
         } else {
             Cache::from_path(".uv_cache")
+        }?;
+
+        let cache = if let Some(readonly) = value.cache_readonly_dir {
+            cache.with_readonly(readonly)?
+        } else {
+            cache
+        };
+
+        if let Some(url) = value.remote_build_cache {
+            Ok(cache.with_remote_build_cache(url, value.remote_build_cache_upload))
+        } else {
+            Ok(cache)
         }
     }
 }
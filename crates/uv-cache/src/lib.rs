@@ -1,14 +1,15 @@
 use std::fmt::{Display, Formatter};
 use std::io;
-use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use fs_err as fs;
 use tempfile::{tempdir, TempDir};
+use tracing::{debug, warn};
+use url::Url;
 
-use uv_fs::directories;
+use uv_fs::{directories, write_atomic_sync, LockedFile, Normalized};
 use uv_normalize::PackageName;
 
 pub use crate::by_timestamp::CachedByTimestamp;
@@ -21,70 +22,135 @@ use crate::wheel::WheelCacheKind;
 
 mod by_timestamp;
 mod cli;
+mod objects;
 mod removal;
 mod timestamp;
 mod wheel;
 
 /// A [`CacheEntry`] which may or may not exist yet.
+///
+/// Entries always point into the writable cache. If a read-only cache is overlaid, the entry also
+/// tracks its counterpart in the read-only cache, which is consulted by [`CacheEntry::read_path`]
+/// but is never written to.
 #[derive(Debug, Clone)]
-pub struct CacheEntry(PathBuf);
+pub struct CacheEntry {
+    /// The path to the entry in the writable cache.
+    path: PathBuf,
+    /// The path to the same entry in the read-only cache, if any.
+    readonly: Option<PathBuf>,
+}
 
 impl CacheEntry {
     /// Create a new [`CacheEntry`] from a directory and a file name.
     pub fn new(dir: impl Into<PathBuf>, file: impl AsRef<Path>) -> Self {
-        Self(dir.into().join(file))
+        Self::from_path(dir.into().join(file))
     }
 
     /// Create a new [`CacheEntry`] from a path.
     pub fn from_path(path: impl Into<PathBuf>) -> Self {
-        Self(path.into())
+        Self {
+            path: path.into(),
+            readonly: None,
+        }
     }
 
     /// Convert the [`CacheEntry`] into a [`PathBuf`].
     #[inline]
     pub fn into_path_buf(self) -> PathBuf {
-        self.0
+        self.path
     }
 
-    /// Return the path to the [`CacheEntry`].
+    /// Return the path to the [`CacheEntry`] in the writable cache.
+    ///
+    /// This is the path to use for writes and locks.
     #[inline]
     pub fn path(&self) -> &Path {
-        &self.0
+        &self.path
+    }
+
+    /// Return the path from which to read the [`CacheEntry`].
+    ///
+    /// Prefers the writable cache; if the entry only exists in the read-only cache, returns the
+    /// path into the read-only cache instead.
+    pub fn read_path(&self) -> &Path {
+        match &self.readonly {
+            Some(readonly) if !self.path.exists() && readonly.exists() => readonly,
+            _ => &self.path,
+        }
+    }
+
+    /// Returns `true` if the [`CacheEntry`] is read from the read-only cache.
+    pub fn is_readonly(&self) -> bool {
+        self.read_path() != self.path
     }
 
     /// Return the cache entry's parent directory.
     #[inline]
     pub fn dir(&self) -> &Path {
-        self.0.parent().expect("Cache entry has no parent")
+        self.path.parent().expect("Cache entry has no parent")
     }
 
     /// Create a new [`CacheEntry`] with the given file name.
     #[must_use]
     pub fn with_file(&self, file: impl AsRef<Path>) -> Self {
-        Self(self.dir().join(file))
+        Self {
+            path: self.dir().join(file.as_ref()),
+            readonly: self.readonly.as_ref().map(|readonly| {
+                readonly
+                    .parent()
+                    .expect("Cache entry has no parent")
+                    .join(file.as_ref())
+            }),
+        }
     }
 }
 
 /// A subdirectory within the cache.
+///
+/// Like [`CacheEntry`], shards always point into the writable cache, and track their counterpart
+/// in the read-only cache, if any.
 #[derive(Debug, Clone)]
-pub struct CacheShard(PathBuf);
+pub struct CacheShard {
+    /// The path to the shard in the writable cache.
+    path: PathBuf,
+    /// The path to the same shard in the read-only cache, if any.
+    readonly: Option<PathBuf>,
+}
 
 impl CacheShard {
     /// Return a [`CacheEntry`] within this shard.
     pub fn entry(&self, file: impl AsRef<Path>) -> CacheEntry {
-        CacheEntry::new(&self.0, file)
+        CacheEntry {
+            path: self.path.join(file.as_ref()),
+            readonly: self
+                .readonly
+                .as_ref()
+                .map(|readonly| readonly.join(file.as_ref())),
+        }
     }
 
     /// Return a [`CacheShard`] within this shard.
     #[must_use]
     pub fn shard(&self, dir: impl AsRef<Path>) -> Self {
-        Self(self.0.join(dir.as_ref()))
+        Self {
+            path: self.path.join(dir.as_ref()),
+            readonly: self
+                .readonly
+                .as_ref()
+                .map(|readonly| readonly.join(dir.as_ref())),
+        }
+    }
+
+    /// Return the directories from which to read the shard's contents: the shard in the writable
+    /// cache, followed by the shard in the read-only cache, if any.
+    pub fn read_dirs(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.path.as_path()).chain(self.readonly.as_deref())
     }
 }
 
 impl AsRef<Path> for CacheShard {
     fn as_ref(&self) -> &Path {
-        &self.0
+        &self.path
     }
 }
 
@@ -92,7 +158,7 @@ impl Deref for CacheShard {
     type Target = Path;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.path
     }
 }
 
@@ -103,6 +169,15 @@ pub struct Cache {
     root: PathBuf,
     /// The refresh strategy to use when reading from the cache.
     refresh: Refresh,
+    /// A pre-populated, read-only cache directory, if the user requested `--cache-readonly`.
+    ///
+    /// Entries that exist in the read-only cache are read from it directly and are never
+    /// revalidated; all writes go to `root`, which acts as a writable scratch cache.
+    readonly: Option<PathBuf>,
+    /// The base URL of a remote cache for wheels built from source distributions, if any.
+    remote_build_cache: Option<Url>,
+    /// Whether wheels built from source distributions should be uploaded to the remote cache.
+    remote_build_cache_upload: bool,
     /// A temporary cache directory, if the user requested `--no-cache`.
     ///
     /// Included to ensure that the temporary directory exists for the length of the operation, but
@@ -116,6 +191,9 @@ impl Cache {
         Ok(Self {
             root: Self::init(root)?,
             refresh: Refresh::None,
+            readonly: None,
+            remote_build_cache: None,
+            remote_build_cache_upload: false,
             _temp_dir_drop: None,
         })
     }
@@ -126,6 +204,9 @@ impl Cache {
         Ok(Self {
             root: Self::init(temp_dir.path())?,
             refresh: Refresh::None,
+            readonly: None,
+            remote_build_cache: None,
+            remote_build_cache_upload: false,
             _temp_dir_drop: Some(Arc::new(temp_dir)),
         })
     }
@@ -136,29 +217,110 @@ impl Cache {
         Self { refresh, ..self }
     }
 
+    /// Overlay the cache on top of a pre-populated, read-only cache directory.
+    ///
+    /// Entries are read from the read-only cache when present, and are otherwise read from and
+    /// written to this cache, which acts as a writable scratch space. The read-only cache is never
+    /// modified (nor locked).
+    pub fn with_readonly(self, readonly: impl AsRef<Path>) -> Result<Self, io::Error> {
+        Ok(Self {
+            readonly: Some(fs::canonicalize(readonly.as_ref())?),
+            ..self
+        })
+    }
+
+    /// Share wheels built from source distributions via a remote cache at the given base URL.
+    ///
+    /// Wheels are fetched from the remote cache (via `GET`) before building a source distribution.
+    /// If `upload` is set, they're also uploaded to it (via `PUT`) after a successful build.
+    #[must_use]
+    pub fn with_remote_build_cache(self, url: Url, upload: bool) -> Self {
+        Self {
+            remote_build_cache: Some(url),
+            remote_build_cache_upload: upload,
+            ..self
+        }
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
     }
 
+    /// Return the root of the read-only cache, if any.
+    pub fn readonly(&self) -> Option<&Path> {
+        self.readonly.as_deref()
+    }
+
+    /// Return the base URL of the remote build cache, if any.
+    pub fn remote_build_cache(&self) -> Option<&Url> {
+        self.remote_build_cache.as_ref()
+    }
+
+    /// Returns `true` if wheels built from source distributions should be uploaded to the remote
+    /// build cache.
+    pub fn remote_build_cache_upload(&self) -> bool {
+        self.remote_build_cache_upload
+    }
+
+    /// Returns `true` if the given path is part of the read-only cache.
+    pub fn is_readonly(&self, path: impl AsRef<Path>) -> bool {
+        self.readonly
+            .as_ref()
+            .is_some_and(|readonly| path.as_ref().starts_with(readonly))
+    }
+
     /// The folder for a specific cache bucket
     pub fn bucket(&self, cache_bucket: CacheBucket) -> PathBuf {
         self.root.join(cache_bucket.to_str())
     }
 
-    /// Compute an entry in the cache.
+    /// Compute a shard in the cache.
+    ///
+    /// The shard always points into the writable cache; if a read-only cache is overlaid, the
+    /// corresponding shard in the read-only cache is available via [`CacheShard::read_dirs`].
     pub fn shard(&self, cache_bucket: CacheBucket, dir: impl AsRef<Path>) -> CacheShard {
-        CacheShard(self.bucket(cache_bucket).join(dir.as_ref()))
+        CacheShard {
+            path: self.bucket(cache_bucket).join(dir.as_ref()),
+            readonly: self
+                .readonly
+                .as_ref()
+                .map(|readonly| readonly.join(cache_bucket.to_str()).join(dir.as_ref())),
+        }
     }
 
     /// Compute an entry in the cache.
+    ///
+    /// The entry always points into the writable cache; if a read-only cache is overlaid, reads
+    /// fall back to the read-only cache via [`CacheEntry::read_path`].
     pub fn entry(
         &self,
         cache_bucket: CacheBucket,
         dir: impl AsRef<Path>,
         file: impl AsRef<Path>,
     ) -> CacheEntry {
-        CacheEntry::new(self.bucket(cache_bucket).join(dir), file)
+        self.shard(cache_bucket, dir).entry(file)
+    }
+
+    /// Acquire an exclusive, cross-process lock on a [`CacheShard`], to guard against concurrent
+    /// writes from other processes.
+    ///
+    /// The lock is held until the returned [`LockedFile`] is dropped. The lock is always taken in
+    /// the writable cache, as the read-only cache is never written to.
+    pub fn lock_shard(&self, shard: &CacheShard) -> Result<LockedFile, io::Error> {
+        fs::create_dir_all(shard)?;
+        LockedFile::acquire(shard.join(".lock"), shard.normalized_display())
+    }
+
+    /// Acquire an exclusive, cross-process lock on a [`CacheEntry`].
+    ///
+    /// The lock is stored alongside the entry (as `{entry}.lock`), such that it can be acquired
+    /// before the entry itself exists.
+    pub fn lock_entry(&self, entry: &CacheEntry) -> Result<LockedFile, io::Error> {
+        fs::create_dir_all(entry.dir())?;
+        let mut path = entry.path().as_os_str().to_owned();
+        path.push(".lock");
+        LockedFile::acquire(PathBuf::from(path), entry.path().normalized_display())
     }
 
     /// Returns `true` if a cache entry must be revalidated given the [`Refresh`] policy.
@@ -176,6 +338,11 @@ impl Cache {
         entry: &CacheEntry,
         package: Option<&PackageName>,
     ) -> io::Result<Freshness> {
+        // Entries in the read-only cache can't be refreshed.
+        if entry.is_readonly() {
+            return Ok(Freshness::Readonly);
+        }
+
         // Grab the cutoff timestamp, if it's relevant.
         let timestamp = match &self.refresh {
             Refresh::None => return Ok(Freshness::Fresh),
@@ -202,19 +369,32 @@ impl Cache {
         }
     }
 
+    /// Return the path to a directory in the artifact store, given its ID.
+    ///
+    /// Absolute paths (as stored by older versions of uv) are returned as-is.
+    pub fn archive(&self, id: &Path) -> PathBuf {
+        self.entry(CacheBucket::Archive, "", id)
+            .read_path()
+            .to_path_buf()
+    }
+
     /// Persist a temporary directory to the artifact store.
     pub fn persist(
         &self,
         temp_dir: impl AsRef<Path>,
         path: impl AsRef<Path>,
     ) -> io::Result<PathBuf> {
+        // Deduplicate the files against those already in the cache.
+        let keys = objects::deduplicate(temp_dir.as_ref(), &self.bucket(CacheBucket::Objects))?;
+
         // Create a unique ID for the artifact.
-        // TODO(charlie): Support content-addressed persistence via SHAs.
         let id = nanoid::nanoid!();
 
-        // Move the temporary directory into the directory store.
+        // Record the objects that the artifact links to, then move the temporary directory into
+        // the directory store.
         let archive_entry = self.entry(CacheBucket::Archive, "", id);
         fs_err::create_dir_all(archive_entry.dir())?;
+        objects::write_manifest(archive_entry.path(), &keys)?;
         fs_err::rename(temp_dir.as_ref(), archive_entry.path())?;
 
         // Create a symlink to the directory store.
@@ -224,6 +404,24 @@ impl Cache {
         Ok(archive_entry.into_path_buf())
     }
 
+    /// Remove an unzipped wheel from the artifact store, given its path, along with any objects
+    /// that its files are linked to.
+    ///
+    /// Used to evict a corrupted archive, such that neither it nor its objects are reused by later
+    /// downloads.
+    pub fn remove_archive(&self, archive: &Path) -> io::Result<Removal> {
+        objects::unlink(archive, &self.bucket(CacheBucket::Objects))?;
+        rm_rf(archive)
+    }
+
+    /// Remove any objects from the object store that are no longer linked from any archive or
+    /// environment.
+    ///
+    /// Link counts are only available on Unix, so this is a no-op on other platforms.
+    pub fn prune_objects(&self) -> io::Result<Removal> {
+        objects::prune(&self.bucket(CacheBucket::Objects))
+    }
+
     /// Initialize a directory for use as a cache.
     fn init(root: impl Into<PathBuf>) -> Result<PathBuf, io::Error> {
         let root = root.into();
@@ -237,21 +435,80 @@ impl Cache {
         // Add the .gitignore.
         let gitignore_path = root.join(".gitignore");
         if !gitignore_path.exists() {
-            let mut file = fs::File::create(gitignore_path)?;
-            file.write_all(b"*")?;
+            write_atomic_sync(gitignore_path, b"*")?;
         }
 
         fs::canonicalize(root)
     }
 
+    /// Remove any buckets in an outdated format, along with any objects that are no longer linked
+    /// from any archive or environment.
+    ///
+    /// Each bucket embeds its format version in its directory name (e.g., `wheels-v0`), which is
+    /// bumped whenever the format of its entries changes, such that entries in an outdated format
+    /// are never read. Buckets with an older version are only left behind by older versions of uv,
+    /// which may still be running, so they're removed on request rather than on every invocation.
+    /// Buckets with a newer version are left untouched, as they may be in use by a newer version of
+    /// uv.
+    ///
+    /// Removal is best-effort: buckets that can't be removed (e.g., because a file is in use) are
+    /// skipped with a warning. The read-only cache, if any, is left untouched.
+    pub fn prune(&self) -> Result<Removal, io::Error> {
+        // Avoid racing with another process that's pruning the same cache.
+        let _lock = LockedFile::acquire(self.root.join(".lock"), self.root.normalized_display())?;
+
+        let mut summary = Removal::default();
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let Some((name, version)) = file_name
+                .to_str()
+                .and_then(|file_name| parse_bucket(file_name))
+            else {
+                continue;
+            };
+            let Some(bucket) = CacheBucket::ALL
+                .iter()
+                .find(|bucket| parse_bucket(bucket.to_str()).is_some_and(|(n, _)| n == name))
+            else {
+                continue;
+            };
+            let (_, current) = parse_bucket(bucket.to_str()).expect("Bucket to be versioned");
+            if version < current {
+                debug!(
+                    "Removing outdated cache bucket: {} (expected {bucket})",
+                    entry.path().display()
+                );
+                match rm_rf(entry.path()) {
+                    Ok(removal) => summary += removal,
+                    Err(err) => warn!(
+                        "Failed to remove outdated cache bucket {}: {err}",
+                        entry.path().display()
+                    ),
+                }
+            }
+        }
+
+        // Walking the object store is expensive, so objects are only pruned on request.
+        match self.prune_objects() {
+            Ok(removal) => summary += removal,
+            Err(err) => warn!("Failed to prune the object store: {err}"),
+        }
+
+        Ok(summary)
+    }
+
     /// Clear the cache, removing all entries.
+    ///
+    /// The read-only cache, if any, is left untouched.
     pub fn clear(&self) -> Result<Removal, io::Error> {
         rm_rf(&self.root)
     }
 
     /// Remove a package from the cache.
     ///
-    /// Returns the number of entries removed from the cache.
+    /// Returns the number of entries removed from the cache. The read-only cache, if any, is left
+    /// untouched.
     pub fn remove(&self, name: &PackageName) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
         for bucket in [
@@ -265,6 +522,92 @@ impl Cache {
         }
         Ok(summary)
     }
+
+    /// Return the entries for a package in the cache, across buckets.
+    ///
+    /// Each entry is a file or directory that's specific to the package, such as the index
+    /// response for the package from a given index, or the directory of wheels for the package
+    /// from a given index or URL. The read-only cache, if any, is not included.
+    pub fn package_entries(
+        &self,
+        name: &PackageName,
+    ) -> Result<Vec<(CacheBucket, PathBuf)>, io::Error> {
+        let mut entries = Vec::new();
+        for bucket in [
+            CacheBucket::Simple,
+            CacheBucket::Wheels,
+            CacheBucket::BuiltWheels,
+        ] {
+            for path in bucket.package_entries(self, name) {
+                match fs::symlink_metadata(&path) {
+                    Ok(_) => entries.push((bucket, path)),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Remove the wheels built from superseded commits of a Git repository.
+    ///
+    /// Built wheels are stored per commit, so every new commit that's resolved for a given
+    /// repository URL leaves the builds for previous commits behind. This retains the builds for
+    /// the commit at `sha` and for the most recently built commits, up to `retain` commits in
+    /// total, and removes the rest. Builds that are locked by another process are skipped.
+    pub fn prune_git_builds(
+        &self,
+        url: &Url,
+        sha: &str,
+        retain: usize,
+    ) -> Result<Removal, io::Error> {
+        let shard = self
+            .bucket(CacheBucket::BuiltWheels)
+            .join(WheelCache::Git(url, sha).root());
+        let Some(repository) = shard.parent() else {
+            return Ok(Removal::default());
+        };
+
+        // Collect the builds for every other commit, ordered from most to least recently modified.
+        let mut builds = Vec::new();
+        for entry in directories(repository) {
+            if entry.file_name().is_some_and(|name| name == sha) {
+                continue;
+            }
+            let modified = entry.metadata()?.modified()?;
+            builds.push((modified, entry));
+        }
+        builds.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let mut summary = Removal::default();
+        for (_, build) in builds.into_iter().skip(retain.saturating_sub(1)) {
+            // Avoid removing a build that's in use by another process. The locks are released
+            // before the removal, since locked files can't be removed on all platforms.
+            let mut locks = Vec::new();
+            let mut in_use = false;
+            for package in directories(&build) {
+                match LockedFile::try_acquire(package.join(".lock"))? {
+                    Some(lock) => locks.push(lock),
+                    None => {
+                        in_use = true;
+                        break;
+                    }
+                }
+            }
+            drop(locks);
+            if in_use {
+                debug!("Skipping in-use Git build: {}", build.normalized_display());
+                continue;
+            }
+
+            debug!(
+                "Removing superseded Git build: {}",
+                build.normalized_display()
+            );
+            summary += rm_rf(&build)?;
+        }
+        Ok(summary)
+    }
 }
 
 /// The different kinds of data in the cache are stored in different bucket, which in our case
@@ -282,6 +625,8 @@ pub enum CacheBucket {
     ///  * `wheel-metadata-v0/pypi/foo/{foo-1.0.0-py3-none-any.msgpack, foo-1.0.0-py3-none-any.whl}`
     ///  * `wheel-metadata-v0/<digest(index-url)>/foo/{foo-1.0.0-py3-none-any.msgpack, foo-1.0.0-py3-none-any.whl}`
     ///  * `wheel-metadata-v0/url/<digest(url)>/foo/{foo-1.0.0-py3-none-any.msgpack, foo-1.0.0-py3-none-any.whl}`
+    ///  * `wheel-metadata-v0/url/<digest(url)>/<digest(hash)>/foo/{foo-1.0.0-py3-none-any.msgpack, foo-1.0.0-py3-none-any.whl}`,
+    ///    for URLs that are pinned to a hash (e.g., `#sha256=...`)
     ///
     /// See `uv_client::RegistryClient::wheel_metadata` for information on how wheel metadata
     /// is fetched.
@@ -440,7 +785,7 @@ pub enum CacheBucket {
     /// Flat index responses, a format very similar to the simple metadata API.
     ///
     /// Cache structure:
-    ///  * `flat-index-v0/index/<digest(flat_index_url)>.msgpack`
+    ///  * `flat-index-v1/index/<digest(flat_index_url)>.msgpack`
     ///
     /// The response is stored as `Vec<File>`.
     FlatIndex,
@@ -488,8 +833,8 @@ pub enum CacheBucket {
     /// Index responses through the simple metadata API.
     ///
     /// Cache structure:
-    ///  * `simple-v0/pypi/<package_name>.rkyv`
-    ///  * `simple-v0/<digest(index_url)>/<package_name>.rkyv`
+    ///  * `simple-v1/pypi/<package_name>.rkyv`
+    ///  * `simple-v1/<digest(index_url)>/<package_name>.rkyv`
     ///
     /// The response is parsed into `uv_client::SimpleMetadata` before storage.
     Simple,
@@ -499,19 +844,127 @@ pub enum CacheBucket {
     /// that cache entries can be atomically replaced and removed, as storing directories in the
     /// other buckets directly would make atomic operations impossible.
     Archive,
+    /// A content-addressed store for the files within unzipped wheels, keyed by the SHA-256 of
+    /// their contents (and, on Unix, their permissions).
+    ///
+    /// When a directory is persisted to [`CacheBucket::Archive`], each of its files is replaced
+    /// with a hard link to the corresponding object, such that files that are shared across
+    /// wheels (or across versions of the same wheel) are only stored once. As environments are
+    /// in turn linked from the archive (e.g., with `--link-mode hardlink`), the same applies
+    /// across environments.
+    ///
+    /// The keys of the objects that each archive links to are recorded alongside the archive, in
+    /// `archive-v0/<id>.objects`. Objects that are no longer linked from anywhere are removed by
+    /// `uv cache prune`.
+    ///
+    /// As all links to an object share its contents, modifying an installed file in place (rather
+    /// than replacing it) modifies the object, and every other file linked to it.
+    ///
+    /// Cache structure: `objects-v0/<sha256[..2]>/<sha256[2..]>`
+    Objects,
+    /// Ephemeral virtual environments used to run command-line tools (e.g., `uv tool run`).
+    ///
+    /// Each environment is keyed by a digest of the requested tool requirement and the base
+    /// interpreter, such that repeated invocations of the same tool can reuse the environment.
+    ///
+    /// Cache structure:
+    ///  * `environments-v0/<digest(requirement, interpreter)>/{bin,lib,pyvenv.cfg,...}`
+    ///
+    /// The bucket also holds the locks that serialize `uv venv` invocations for a given path,
+    /// which can't live within the virtual environment, as it's removed and recreated:
+    ///  * `environments-v0/venvs/<digest(path)>.lock`
+    Environments,
+    /// The output of `pip compile`, keyed by a digest of all of the inputs to the resolution
+    /// (requirements, constraints, preferences, indexes, markers, and options), such that
+    /// re-running `pip compile` with unchanged inputs can skip the resolution entirely.
+    ///
+    /// Cache structure: `resolutions-v0/<digest(inputs)>.json`
+    Resolutions,
 }
 
 impl CacheBucket {
+    /// All buckets in the cache.
+    const ALL: [CacheBucket; 10] = [
+        CacheBucket::Wheels,
+        CacheBucket::BuiltWheels,
+        CacheBucket::FlatIndex,
+        CacheBucket::Git,
+        CacheBucket::Interpreter,
+        CacheBucket::Simple,
+        CacheBucket::Archive,
+        CacheBucket::Objects,
+        CacheBucket::Environments,
+        CacheBucket::Resolutions,
+    ];
+
+    /// The name of the bucket's directory, including its format version (e.g., `wheels-v0`).
+    ///
+    /// The version must be bumped whenever the format of the bucket's entries changes in an
+    /// incompatible way.
     fn to_str(self) -> &'static str {
         match self {
             CacheBucket::BuiltWheels => "built-wheels-v0",
-            CacheBucket::FlatIndex => "flat-index-v0",
+            CacheBucket::FlatIndex => "flat-index-v1",
             CacheBucket::Git => "git-v0",
             CacheBucket::Interpreter => "interpreter-v0",
-            CacheBucket::Simple => "simple-v0",
+            CacheBucket::Simple => "simple-v1",
             CacheBucket::Wheels => "wheels-v0",
             CacheBucket::Archive => "archive-v0",
+            CacheBucket::Objects => "objects-v0",
+            CacheBucket::Environments => "environments-v0",
+            CacheBucket::Resolutions => "resolutions-v0",
+        }
+    }
+
+    /// Return the candidate paths for a package's entries in the cache bucket, which may or may
+    /// not exist.
+    fn package_entries(self, cache: &Cache, name: &PackageName) -> Vec<PathBuf> {
+        let mut entries = Vec::new();
+        match self {
+            CacheBucket::Simple => {
+                // We expect a directory for every index (including `pypi`), followed by a rkyv
+                // file per package, indexed by name.
+                for directory in directories(cache.bucket(self)) {
+                    entries.push(directory.join(format!("{name}.rkyv")));
+                }
+            }
+            CacheBucket::Wheels | CacheBucket::BuiltWheels => {
+                // For `pypi` wheels, we expect a directory per package (indexed by name).
+                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
+                entries.push(root.join(name.to_string()));
+
+                // For alternate indices, direct URLs, and local dependencies, we expect a
+                // directory for every index, URL, or path, followed by a directory per package
+                // (indexed by name).
+                for kind in [
+                    WheelCacheKind::Index,
+                    WheelCacheKind::Url,
+                    WheelCacheKind::Path,
+                ] {
+                    for directory in directories(cache.bucket(self).join(kind)) {
+                        entries.push(directory.join(name.to_string()));
+                    }
+                }
+
+                // For Git dependencies, we expect a directory for every repository, followed by a
+                // directory for every SHA, followed by a directory per package (indexed by name).
+                for directory in directories(cache.bucket(self).join(WheelCacheKind::Git)) {
+                    for directory in directories(directory) {
+                        entries.push(directory.join(name.to_string()));
+                    }
+                }
+            }
+            CacheBucket::FlatIndex
+            | CacheBucket::Git
+            | CacheBucket::Interpreter
+            | CacheBucket::Archive
+            | CacheBucket::Objects
+            | CacheBucket::Environments
+            | CacheBucket::Resolutions => {
+                // These buckets aren't indexed by package.
+            }
         }
+        entries
     }
 
     /// Remove a package from the cache bucket.
@@ -601,11 +1054,30 @@ impl CacheBucket {
             CacheBucket::Archive => {
                 // Nothing to do.
             }
+            CacheBucket::Objects => {
+                // Nothing to do.
+            }
+            CacheBucket::Environments => {
+                // Nothing to do.
+            }
+            CacheBucket::Resolutions => {
+                // We can't know if a resolution includes a package, so we just remove all of
+                // the cached resolutions.
+                let root = cache.bucket(self);
+                summary += rm_rf(root)?;
+            }
         }
         Ok(summary)
     }
 }
 
+/// Split a bucket directory name into its name and format version (e.g., `wheels-v0` into
+/// `wheels` and `0`).
+fn parse_bucket(dir: &str) -> Option<(&str, u32)> {
+    let (name, version) = dir.rsplit_once("-v")?;
+    Some((name, version.parse().ok()?))
+}
+
 impl Display for CacheBucket {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.to_str())
@@ -617,7 +1089,7 @@ pub enum ArchiveTimestamp {
     /// The archive consists of a single file with the given modification time.
     Exact(Timestamp),
     /// The archive consists of a directory. The modification time is the latest modification time
-    /// of the `pyproject.toml` or `setup.py` file in the directory.
+    /// of any source file in the directory (see [`ArchiveTimestamp::from_path`]).
     Approximate(Timestamp),
 }
 
@@ -625,33 +1097,44 @@ impl ArchiveTimestamp {
     /// Return the modification timestamp for an archive, which could be a file (like a wheel or a zip
     /// archive) or a directory containing a Python package.
     ///
+    /// For directories, the timestamp is the latest modification time across all files in the
+    /// source tree, such that editing any module (not just `pyproject.toml` or `setup.py`)
+    /// invalidates wheels built from the directory. Version control metadata, virtual environments,
+    /// and the artifacts that build backends write into the source tree (like `build/` and
+    /// `*.egg-info`) are excluded, as they'd otherwise invalidate the cache on every build.
+    ///
     /// If the path is to a directory with no entrypoint (i.e., no `pyproject.toml` or `setup.py`),
     /// returns `None`.
     pub fn from_path(path: impl AsRef<Path>) -> Result<Option<Self>, io::Error> {
-        let metadata = fs_err::metadata(path.as_ref())?;
+        let path = path.as_ref();
+        let metadata = fs_err::metadata(path)?;
         if metadata.is_file() {
-            Ok(Some(Self::Exact(Timestamp::from_metadata(&metadata))))
-        } else {
-            if let Some(metadata) = path
-                .as_ref()
-                .join("pyproject.toml")
-                .metadata()
-                .ok()
-                .filter(std::fs::Metadata::is_file)
-            {
-                Ok(Some(Self::Approximate(Timestamp::from_metadata(&metadata))))
-            } else if let Some(metadata) = path
-                .as_ref()
-                .join("setup.py")
+            return Ok(Some(Self::Exact(Timestamp::from_metadata(&metadata))));
+        }
+
+        let has_entrypoint = ["pyproject.toml", "setup.py"].iter().any(|entrypoint| {
+            path.join(entrypoint)
                 .metadata()
-                .ok()
-                .filter(std::fs::Metadata::is_file)
-            {
-                Ok(Some(Self::Approximate(Timestamp::from_metadata(&metadata))))
-            } else {
-                Ok(None)
+                .is_ok_and(|metadata| metadata.is_file())
+        });
+        if !has_entrypoint {
+            return Ok(None);
+        }
+
+        let mut latest = None;
+        let walker = walkdir::WalkDir::new(path)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_build_artifact(entry));
+        for entry in walker {
+            let entry = entry.map_err(io::Error::from)?;
+            if !entry.file_type().is_file() {
+                continue;
             }
+            let timestamp = Timestamp::from_metadata(&entry.metadata().map_err(io::Error::from)?);
+            latest = latest.max(Some(timestamp));
         }
+
+        Ok(latest.map(Self::Approximate))
     }
 
     /// Return the modification timestamp for an archive.
@@ -663,6 +1146,29 @@ impl ArchiveTimestamp {
     }
 }
 
+/// Returns `true` if the directory entry should be excluded when computing the modification time
+/// of a source tree.
+fn is_build_artifact(entry: &walkdir::DirEntry) -> bool {
+    let Some(name) = entry.file_name().to_str() else {
+        return false;
+    };
+    if entry.file_type().is_dir() {
+        // Build and distribution directories are only excluded at the root of the source tree,
+        // since packages may contain modules named (e.g.) `build`.
+        name.starts_with('.')
+            || name == "__pycache__"
+            || name.ends_with(".egg-info")
+            || (entry.depth() == 1 && matches!(name, "build" | "dist" | "target"))
+    } else {
+        matches!(
+            Path::new(name)
+                .extension()
+                .and_then(std::ffi::OsStr::to_str),
+            Some("pyc" | "pyo" | "so" | "pyd")
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Freshness {
     /// The cache entry is fresh according to the [`Refresh`] policy.
@@ -671,11 +1177,13 @@ pub enum Freshness {
     Stale,
     /// The cache entry does not exist.
     Missing,
+    /// The cache entry exists in the read-only cache, and so can't be revalidated or replaced.
+    Readonly,
 }
 
 impl Freshness {
     pub const fn is_fresh(self) -> bool {
-        matches!(self, Self::Fresh)
+        matches!(self, Self::Fresh | Self::Readonly)
     }
 
     pub const fn is_stale(self) -> bool {
@@ -711,3 +1219,255 @@ impl Refresh {
         matches!(self, Self::None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use std::path::Path;
+
+    use url::Url;
+
+    use super::{parse_bucket, Cache, CacheBucket, WheelCache};
+
+    #[test]
+    fn readonly_overlay() {
+        let readonly = Cache::temp().unwrap();
+        let cache = Cache::temp()
+            .unwrap()
+            .with_readonly(readonly.root())
+            .unwrap();
+
+        // Populate an entry in the read-only cache.
+        let entry = readonly.entry(CacheBucket::Simple, "pypi", "flask.rkyv");
+        fs_err::create_dir_all(entry.dir()).unwrap();
+        fs_err::write(entry.path(), b"").unwrap();
+
+        // Entries in the read-only cache are read from the read-only cache, but written to the
+        // writable cache.
+        let entry = cache.entry(CacheBucket::Simple, "pypi", "flask.rkyv");
+        assert!(entry.path().starts_with(cache.root()));
+        assert!(entry.read_path().starts_with(readonly.root()));
+        assert!(entry.is_readonly());
+        assert!(cache.is_readonly(entry.read_path()));
+
+        // Shards always point into the writable cache, even if they exist in the read-only cache.
+        let shard = cache.shard(CacheBucket::Simple, "pypi");
+        assert!(shard.starts_with(cache.root()));
+        assert_eq!(
+            shard.read_dirs().collect::<Vec<_>>(),
+            [
+                cache.bucket(CacheBucket::Simple).join("pypi").as_path(),
+                readonly.bucket(CacheBucket::Simple).join("pypi").as_path(),
+            ]
+        );
+        assert!(shard.entry("flask.rkyv").is_readonly());
+
+        // Missing entries fall back to the writable cache.
+        let entry = cache.entry(CacheBucket::Simple, "pypi", "django.rkyv");
+        assert!(entry.read_path().starts_with(cache.root()));
+        assert!(!entry.is_readonly());
+
+        // Once written to the writable cache, entries are read from the writable cache.
+        let entry = cache.entry(CacheBucket::Simple, "pypi", "flask.rkyv");
+        fs_err::create_dir_all(entry.dir()).unwrap();
+        fs_err::write(entry.path(), b"").unwrap();
+        assert_eq!(entry.read_path(), entry.path());
+        assert!(!entry.is_readonly());
+    }
+
+    #[test]
+    fn prune_git_builds() {
+        let cache = Cache::temp().unwrap();
+        let url = Url::parse("https://github.com/pallets/flask.git").unwrap();
+
+        // Populate builds for five commits, from oldest to newest.
+        let shas = ["0000000", "1111111", "2222222", "3333333", "4444444"];
+        for sha in shas {
+            let shard = cache.shard(
+                CacheBucket::BuiltWheels,
+                WheelCache::Git(&url, sha).remote_wheel_dir("flask"),
+            );
+            drop(cache.lock_shard(&shard).unwrap());
+        }
+        let build = |sha: &str| {
+            cache
+                .bucket(CacheBucket::BuiltWheels)
+                .join(WheelCache::Git(&url, sha).root())
+        };
+        for (seconds, sha) in (0..).zip(shas) {
+            let mtime = filetime::FileTime::from_unix_time(1_700_000_000 + seconds, 0);
+            filetime::set_file_mtime(build(sha), mtime).unwrap();
+        }
+
+        // Hold the lock for one of the superseded builds, as if it were in use.
+        let in_use = cache
+            .lock_shard(&cache.shard(
+                CacheBucket::BuiltWheels,
+                WheelCache::Git(&url, shas[1]).remote_wheel_dir("flask"),
+            ))
+            .unwrap();
+
+        // The current commit is always retained, along with the most recent commits.
+        cache.prune_git_builds(&url, shas[0], 3).unwrap();
+        assert!(build(shas[0]).is_dir());
+        assert!(build(shas[1]).is_dir());
+        assert!(!build(shas[2]).exists());
+        assert!(build(shas[3]).is_dir());
+        assert!(build(shas[4]).is_dir());
+
+        // Once released, the superseded build is removed.
+        drop(in_use);
+        cache.prune_git_builds(&url, shas[0], 3).unwrap();
+        assert!(!build(shas[1]).exists());
+        assert!(build(shas[3]).is_dir());
+    }
+
+    #[test]
+    fn lock_entries() {
+        let readonly = Cache::temp().unwrap();
+        let cache = Cache::temp()
+            .unwrap()
+            .with_readonly(readonly.root())
+            .unwrap();
+
+        // Locks are created alongside the entry, before the entry itself exists.
+        let entry = cache.entry(
+            CacheBucket::Wheels,
+            "pypi/flask",
+            "flask-3.0.0-py3-none-any",
+        );
+        let _lock = cache.lock_entry(&entry).unwrap();
+        assert!(entry.dir().join("flask-3.0.0-py3-none-any.lock").is_file());
+        assert!(!entry.path().exists());
+
+        let shard = cache.shard(CacheBucket::BuiltWheels, "pypi/flask");
+        let _lock = cache.lock_shard(&shard).unwrap();
+        assert!(shard.join(".lock").is_file());
+
+        // Shards that exist in the read-only cache are locked in the writable cache.
+        let readonly_shard = readonly.shard(CacheBucket::BuiltWheels, "pypi/django");
+        fs_err::create_dir_all(&readonly_shard).unwrap();
+        let shard = cache.shard(CacheBucket::BuiltWheels, "pypi/django");
+        let _lock = cache.lock_shard(&shard).unwrap();
+        assert!(shard.join(".lock").is_file());
+        assert!(!readonly_shard.join(".lock").exists());
+    }
+
+    #[test]
+    fn bucket_versions() {
+        assert_eq!(parse_bucket("wheels-v0"), Some(("wheels", 0)));
+        assert_eq!(parse_bucket("built-wheels-v12"), Some(("built-wheels", 12)));
+        assert_eq!(parse_bucket("wheels"), None);
+        assert_eq!(parse_bucket(".gitignore"), None);
+
+        // Every bucket is versioned, with a unique name.
+        let names = CacheBucket::ALL
+            .iter()
+            .map(|bucket| parse_bucket(bucket.to_str()).unwrap().0)
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(names.len(), CacheBucket::ALL.len());
+
+        // Buckets from older versions of uv are left in place until the cache is pruned.
+        let root = tempfile::tempdir().unwrap();
+        let cache = Cache::from_path(root.path()).unwrap();
+        assert_eq!(CacheBucket::Simple.to_str(), "simple-v1");
+        let stale = cache.root().join("simple-v0");
+        let newer = cache.root().join("simple-v2");
+        fs_err::create_dir_all(&stale).unwrap();
+        fs_err::create_dir_all(&newer).unwrap();
+        Cache::from_path(root.path()).unwrap();
+        assert!(stale.is_dir());
+
+        // Pruning removes older buckets, but leaves buckets from newer versions of uv untouched.
+        cache.prune().unwrap();
+        assert!(!stale.exists());
+        assert!(newer.is_dir());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn persist_deduplicates() {
+        use std::os::unix::fs::MetadataExt;
+
+        let cache = Cache::temp().unwrap();
+
+        // Persist two archives that share a file.
+        let mut archives = Vec::new();
+        for (version, contents) in [("1.0.0", "a"), ("2.0.0", "b")] {
+            let temp_dir = tempfile::tempdir_in(cache.root()).unwrap();
+            fs_err::write(temp_dir.path().join("shared.py"), "print('shared')").unwrap();
+            fs_err::write(temp_dir.path().join("version.py"), contents).unwrap();
+            let entry = cache.entry(CacheBucket::Wheels, "pypi", version);
+            archives.push(cache.persist(temp_dir.into_path(), entry.path()).unwrap());
+        }
+
+        // Identical files are linked to the same object; distinct files are not.
+        let inode = |archive: &std::path::PathBuf, file: &str| {
+            fs_err::metadata(archive.join(file)).unwrap().ino()
+        };
+        assert_eq!(
+            inode(&archives[0], "shared.py"),
+            inode(&archives[1], "shared.py")
+        );
+        assert_ne!(
+            inode(&archives[0], "version.py"),
+            inode(&archives[1], "version.py")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn remove_archive_unlinks_objects() {
+        let cache = Cache::temp().unwrap();
+
+        let temp_dir = tempfile::tempdir_in(cache.root()).unwrap();
+        fs_err::write(temp_dir.path().join("module.py"), "print('hello')").unwrap();
+        let entry = cache.entry(CacheBucket::Wheels, "pypi", "1.0.0");
+        let archive = cache.persist(temp_dir.into_path(), entry.path()).unwrap();
+        assert_eq!(count_files(&cache.bucket(CacheBucket::Objects)), 1);
+
+        // Removing the archive removes its objects, and its manifest, without scanning the store.
+        cache.remove_archive(&archive).unwrap();
+        assert!(!archive.exists());
+        assert_eq!(count_files(&cache.bucket(CacheBucket::Objects)), 0);
+        assert_eq!(count_files(&cache.bucket(CacheBucket::Archive)), 0);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn prune_objects() {
+        let cache = Cache::temp().unwrap();
+
+        // Persist two archives with distinct files.
+        let mut archives = Vec::new();
+        for (version, contents) in [("1.0.0", "a"), ("2.0.0", "b")] {
+            let temp_dir = tempfile::tempdir_in(cache.root()).unwrap();
+            fs_err::write(temp_dir.path().join("version.py"), contents).unwrap();
+            let entry = cache.entry(CacheBucket::Wheels, "pypi", version);
+            archives.push(cache.persist(temp_dir.into_path(), entry.path()).unwrap());
+        }
+
+        // Nothing is pruned while every object is linked.
+        assert_eq!(cache.prune_objects().unwrap().num_files, 0);
+
+        // Once an archive is gone, its objects are pruned along with the rest of the cache, but the
+        // other archive's are retained.
+        fs_err::remove_dir_all(&archives[0]).unwrap();
+        assert_eq!(cache.prune().unwrap().num_files, 1);
+        assert_eq!(count_files(&cache.bucket(CacheBucket::Objects)), 1);
+        assert_eq!(
+            fs_err::read_to_string(archives[1].join("version.py")).unwrap(),
+            "b"
+        );
+    }
+
+    /// Count the files within the given directory, recursively.
+    #[cfg(unix)]
+    fn count_files(dir: &Path) -> usize {
+        walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .count()
+    }
+}
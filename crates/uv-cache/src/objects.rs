@@ -0,0 +1,188 @@
+//! A content-addressed store for the files within unzipped wheels.
+//!
+//! Many wheels (and many versions of the same wheel) contain identical files. Before an unzipped
+//! wheel is persisted to the cache, each of its files is replaced with a hard link to a single
+//! copy in the object store, such that identical files only occupy disk space once. The keys of
+//! the objects that an archive links to are recorded in a manifest alongside the archive, such
+//! that the objects can be found again without scanning the store.
+//!
+//! All links to an object share its contents. If an installed file that was hard-linked from the
+//! cache (e.g., with `--link-mode hardlink`) is modified in place, the modification is visible in
+//! the object, and thus in every archive and environment that links to it. Objects are not made
+//! read-only, as installed files must remain writable (e.g., to be removed on Windows), so tools
+//! that patch installed files should use `--link-mode copy`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use crate::removal::Removal;
+
+/// Replace each file in `dir` with a hard link to an identical file in the object store at
+/// `objects`, adding any files that aren't yet present in the store.
+///
+/// Returns the keys of the objects that the files in `dir` are now linked to.
+///
+/// Hard links are only possible within a single file system. If a link can't be created (e.g.,
+/// because the platform doesn't support hard links), the remaining files are left as-is.
+pub(crate) fn deduplicate(dir: &Path, objects: &Path) -> io::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut linked = 0usize;
+    let mut added = 0usize;
+
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let key = object_key(path)?;
+        let object = objects.join(object_path(&key));
+
+        if object.exists() {
+            // Replace the file with a link to the existing object.
+            let temp = path.with_file_name(format!(".{}.tmp", nanoid::nanoid!()));
+            if let Err(err) = fs::hard_link(&object, &temp) {
+                debug!("Failed to link to cached object; skipping deduplication: {err}");
+                break;
+            }
+            fs::rename(&temp, path)?;
+            linked += 1;
+        } else {
+            // Add the file to the store. Link to a temporary path first, such that concurrent
+            // writers never observe a partial object.
+            fs::create_dir_all(object.parent().expect("Object to have parent"))?;
+            let temp = object.with_extension(nanoid::nanoid!());
+            if let Err(err) = fs::hard_link(path, &temp) {
+                debug!("Failed to add file to object store; skipping deduplication: {err}");
+                break;
+            }
+            fs::rename(&temp, &object)?;
+            added += 1;
+        }
+        keys.push(key);
+    }
+
+    debug!(
+        "Deduplicated {} against the object store ({linked} linked, {added} added)",
+        dir.display()
+    );
+    Ok(keys)
+}
+
+/// Write the manifest of the objects linked from the archive at `archive`.
+pub(crate) fn write_manifest(archive: &Path, keys: &[String]) -> io::Result<()> {
+    uv_fs::write_atomic_sync(manifest_path(archive), keys.join("\n"))
+}
+
+/// Remove the objects in the object store at `objects` that are linked from the archive at
+/// `archive`, as recorded in its manifest, along with the manifest itself.
+///
+/// Objects are removed by key, rather than by their contents, since a file that was modified in
+/// place corrupts its object too, such that the object's contents may no longer match its key.
+///
+/// Returns the number of objects removed.
+pub(crate) fn unlink(archive: &Path, objects: &Path) -> io::Result<usize> {
+    let manifest = manifest_path(archive);
+    let keys = match fs::read_to_string(&manifest) {
+        Ok(keys) => keys,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err),
+    };
+
+    let mut removed = 0usize;
+    for key in keys.lines().filter(|key| is_object_key(key)) {
+        match fs::remove_file(objects.join(object_path(key))) {
+            Ok(()) => removed += 1,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    fs::remove_file(&manifest)?;
+
+    debug!(
+        "Removed {removed} objects linked from {} from the object store",
+        archive.display()
+    );
+    Ok(removed)
+}
+
+/// Remove any objects in the object store at `objects` that are no longer linked from anywhere
+/// else, i.e., that aren't used by any archive or environment.
+#[cfg(unix)]
+pub(crate) fn prune(objects: &Path) -> io::Result<Removal> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut summary = Removal::default();
+    if !objects.is_dir() {
+        return Ok(summary);
+    }
+
+    for entry in WalkDir::new(objects) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if metadata.nlink() == 1 {
+            fs::remove_file(entry.path())?;
+            summary.num_files += 1;
+            summary.total_bytes += metadata.len();
+        }
+    }
+
+    debug!(
+        "Removed {} unused objects from the object store",
+        summary.num_files
+    );
+    Ok(summary)
+}
+
+/// Remove any objects in the object store at `objects` that are no longer linked from anywhere
+/// else.
+///
+/// Link counts can't be determined on this platform, so no objects are removed.
+#[cfg(not(unix))]
+pub(crate) fn prune(_objects: &Path) -> io::Result<Removal> {
+    Ok(Removal::default())
+}
+
+/// Return the path to the manifest of the objects linked from the archive at `archive`.
+fn manifest_path(archive: &Path) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(".objects");
+    PathBuf::from(path)
+}
+
+/// Return the key of the object for the given file.
+///
+/// Objects are keyed by the SHA-256 of their contents, along with their permissions on Unix, as
+/// all links to an object necessarily share the same permissions.
+fn object_key(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)?.permissions().mode();
+        hasher.update(mode.to_le_bytes());
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Returns `true` if the given string is a valid object key (i.e., a hex-encoded SHA-256 digest).
+fn is_object_key(key: &str) -> bool {
+    key.len() == 64 && key.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// Return the path to the object with the given key, relative to the store.
+fn object_path(key: &str) -> PathBuf {
+    Path::new(&key[..2]).join(&key[2..])
+}
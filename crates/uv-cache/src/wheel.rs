@@ -34,9 +34,17 @@ impl<'a> WheelCache<'a> {
             WheelCache::Index(url) => WheelCacheKind::Index
                 .root()
                 .join(digest(&CanonicalUrl::new(url))),
-            WheelCache::Url(url) => WheelCacheKind::Url
-                .root()
-                .join(digest(&CanonicalUrl::new(url))),
+            WheelCache::Url(url) => {
+                let root = WheelCacheKind::Url
+                    .root()
+                    .join(digest(&CanonicalUrl::new(url)));
+                // Artifacts pinned to a hash (e.g., `#sha256=...`) are cached separately for each
+                // hash, such that changing the pin never reuses an artifact cached for another.
+                match fragment_hash(url) {
+                    Some(hash) => root.join(digest(&hash)),
+                    None => root,
+                }
+            }
             WheelCache::Path(url) => WheelCacheKind::Path
                 .root()
                 .join(digest(&CanonicalUrl::new(url))),
@@ -47,6 +55,11 @@ impl<'a> WheelCache<'a> {
         }
     }
 
+    /// The root directory for the cache entries, relative to the bucket.
+    pub fn root(&self) -> PathBuf {
+        self.bucket()
+    }
+
     /// Metadata of a remote wheel. See [`CacheBucket::Wheels`]
     pub fn remote_wheel_dir(&self, package_name: impl AsRef<Path>) -> PathBuf {
         self.bucket().join(package_name)
@@ -58,6 +71,15 @@ impl<'a> WheelCache<'a> {
     }
 }
 
+/// Return the hash pinned in the fragment of a URL (e.g., `sha256=...`), if any.
+fn fragment_hash(url: &Url) -> Option<&str> {
+    url.fragment()?.split('&').find(|param| {
+        ["sha256=", "sha384=", "sha512="]
+            .iter()
+            .any(|prefix| param.starts_with(prefix))
+    })
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum WheelCacheKind {
     /// A cache of data from PyPI.
@@ -138,6 +138,8 @@ impl From<Freshness> for CacheControl {
             Freshness::Fresh => CacheControl::None,
             Freshness::Stale => CacheControl::MustRevalidate,
             Freshness::Missing => CacheControl::None,
+            // Entries in the read-only cache are used as-is, since they can't be updated.
+            Freshness::Readonly => CacheControl::AllowStale,
         }
     }
 }
@@ -280,18 +282,20 @@ impl CachedClient {
     }
 
     async fn read_cache(cache_entry: &CacheEntry) -> Option<DataWithCachePolicy> {
-        let span = info_span!("read_and_parse_cache", file = %cache_entry.path().display());
+        let path = cache_entry.read_path();
+        let span = info_span!("read_and_parse_cache", file = %path.display());
         match span
-            .in_scope(|| DataWithCachePolicy::from_path_async(cache_entry.path()))
+            .in_scope(|| DataWithCachePolicy::from_path_async(path))
             .await
         {
             Ok(data) => Some(data),
+            Err(err) if cache_entry.is_readonly() => {
+                warn!("Broken cache entry at {}: {err}", path.display());
+                None
+            }
             Err(err) => {
-                warn!(
-                    "Broken cache entry at {}, removing: {err}",
-                    cache_entry.path().display()
-                );
-                let _ = fs_err::tokio::remove_file(&cache_entry.path()).await;
+                warn!("Broken cache entry at {}, removing: {err}", path.display());
+                let _ = fs_err::tokio::remove_file(path).await;
                 None
             }
         }
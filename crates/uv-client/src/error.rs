@@ -64,6 +64,17 @@ pub enum ErrorKind {
     #[error("Package `{0}` was not found in the registry.")]
     PackageNotFound(String),
 
+    /// The package was not found in any of the reachable indexes, but at least one index was
+    /// unreachable, and so may contain the package.
+    #[error(
+        "Package `{package}` was not found in any reachable index, and the following indexes were unreachable: {}",
+        indexes.join(", ")
+    )]
+    IndexesUnreachable {
+        package: String,
+        indexes: Vec<String>,
+    },
+
     /// The metadata file could not be parsed.
     #[error("Couldn't parse metadata of {0} from {1}")]
     MetadataParseError(WheelFilename, String, #[source] Box<pypi_types::Error>),
@@ -142,6 +153,31 @@ pub enum ErrorKind {
 }
 
 impl ErrorKind {
+    /// Returns `true` if the error was caused by a failure to reach the network (or by network
+    /// access being disabled), as opposed to, e.g., an invalid response.
+    pub fn is_network_error(&self) -> bool {
+        matches!(
+            self,
+            Self::RequestError(_)
+                | Self::RequestMiddlewareError(_)
+                | Self::FileNotFound(..)
+                | Self::IndexesUnreachable { .. }
+                | Self::Offline(_)
+        )
+    }
+
+    /// Returns `true` if the error indicates that the server was temporarily unreachable (i.e.,
+    /// the request timed out, failed to connect, or returned a server error), as opposed to the
+    /// server responding that the resource doesn't exist.
+    pub(crate) fn is_unreachable(&self) -> bool {
+        let Self::RequestError(err) = self else {
+            return false;
+        };
+        err.is_timeout()
+            || err.is_connect()
+            || err.status().is_some_and(|status| status.is_server_error())
+    }
+
     pub(crate) fn from_middleware(err: reqwest_middleware::Error) -> Self {
         if let reqwest_middleware::Error::Middleware(ref underlying) = err {
             if let Some(err) = underlying.downcast_ref::<OfflineError>() {
@@ -1,6 +1,6 @@
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
@@ -28,6 +28,9 @@ pub enum FlatIndexError {
     #[error("Failed to read `--find-links` directory: {0}")]
     FindLinksDirectory(PathBuf, #[source] std::io::Error),
 
+    #[error("Failed to read `--find-links` HTML file: {0}")]
+    FindLinksHtml(PathBuf, #[source] Error),
+
     #[error("Failed to read `--find-links` URL: {0}")]
     FindLinksUrl(Url, #[source] Error),
 }
@@ -98,6 +101,10 @@ impl<'a> FlatIndexClient<'a> {
         let mut fetches = futures::stream::iter(indexes)
             .map(|index| async move {
                 let entries = match index {
+                    FlatIndexLocation::Path(path) if path.is_file() => {
+                        Self::read_from_html_file(path, self.client.lenient())
+                            .map_err(|err| FlatIndexError::FindLinksHtml(path.clone(), err))?
+                    }
                     FlatIndexLocation::Path(path) => Self::read_from_directory(path)
                         .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?,
                     FlatIndexLocation::Url(url) => self
@@ -151,10 +158,18 @@ impl<'a> FlatIndexClient<'a> {
             .header("Accept", "text/html")
             .build()
             .map_err(ErrorKind::RequestError)?;
+        let lenient = self.client.lenient();
         let parse_simple_response = |response: Response| {
             async {
+                // Resolve any relative links against the final URL, if the request was redirected,
+                // retaining any credentials from the original URL.
+                let mut base = response.url().clone();
+                if base.origin() == url.origin() {
+                    let _ = base.set_username(url.username());
+                    let _ = base.set_password(url.password());
+                }
                 let text = response.text().await.map_err(ErrorKind::RequestError)?;
-                let SimpleHtml { base, files } = SimpleHtml::parse(&text, url)
+                let SimpleHtml { base, files } = SimpleHtml::parse(&text, &base, lenient)
                     .map_err(|err| Error::from_html_err(err, url.clone()))?;
 
                 let files: Vec<File> = files
@@ -204,10 +219,65 @@ impl<'a> FlatIndexClient<'a> {
         }
     }
 
+    /// Read a flat index from a local `--find-links` HTML file (e.g., the `index.html` of a
+    /// wheelhouse).
+    ///
+    /// Relative links are resolved against the directory containing the file, such that links to
+    /// local files are read from disk, like the entries of a `--find-links` directory.
+    ///
+    /// If `lenient` is set, malformed links are skipped with a warning.
+    fn read_from_html_file(path: &Path, lenient: bool) -> Result<FlatIndexEntries, Error> {
+        // Absolute paths are required for the URL conversion.
+        let path = fs_err::canonicalize(path).map_err(ErrorKind::Io)?;
+        let url = Url::from_file_path(&path).expect("path is absolute");
+
+        let text = fs_err::read_to_string(&path).map_err(ErrorKind::Io)?;
+        let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url, lenient)
+            .map_err(|err| Error::from_html_err(err, url.clone()))?;
+
+        let mut dists = Vec::new();
+        for file in files {
+            let mut file = match File::try_from(file, base.as_url().as_str()) {
+                Ok(file) => file,
+                Err(err) => {
+                    // Ignore files with unparseable version specifiers.
+                    warn!("Skipping file in {url}: {err}");
+                    continue;
+                }
+            };
+
+            // Read any links to local files from disk.
+            let location = match &file.url {
+                FileLocation::RelativeUrl(base, relative) => {
+                    pypi_types::base_url_join_relative(base, relative).ok()
+                }
+                FileLocation::AbsoluteUrl(absolute) => Url::parse(absolute).ok(),
+                FileLocation::Path(_) => None,
+            };
+            if let Some(path) = location
+                .filter(|location| location.scheme() == "file")
+                .and_then(|location| location.to_file_path().ok())
+            {
+                file.url = FileLocation::Path(path);
+            }
+
+            let Some(filename) = DistFilename::try_from_normalized_filename(&file.filename) else {
+                debug!(
+                    "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
+                    file.filename
+                );
+                continue;
+            };
+            dists.push((filename, file, IndexUrl::Url(url.clone())));
+        }
+        Ok(FlatIndexEntries::from_entries(dists))
+    }
+
     /// Read a flat remote index from a `--find-links` directory.
     fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, std::io::Error> {
         // Absolute paths are required for the URL conversion.
         let path = fs_err::canonicalize(path)?;
+        let url = Url::from_directory_path(&path).expect("path is absolute");
 
         let mut dists = Vec::new();
         for entry in fs_err::read_dir(path)? {
@@ -228,7 +298,7 @@ impl<'a> FlatIndexClient<'a> {
             let file = File {
                 dist_info_metadata: None,
                 filename: filename.to_string(),
-                hashes: Hashes { sha256: None },
+                hashes: Hashes::default(),
                 requires_python: None,
                 size: None,
                 upload_time_utc_ms: None,
@@ -243,7 +313,7 @@ impl<'a> FlatIndexClient<'a> {
                 );
                 continue;
             };
-            dists.push((filename, file, IndexUrl::Pypi));
+            dists.push((filename, file, IndexUrl::Url(url.clone())));
         }
         Ok(FlatIndexEntries::from_entries(dists))
     }
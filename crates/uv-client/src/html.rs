@@ -6,6 +6,7 @@ use url::Url;
 
 use pep440_rs::VersionSpecifiers;
 use pypi_types::{BaseUrl, DistInfoMetadata, File, Hashes, Yanked};
+use uv_warnings::warn_user;
 
 /// A parsed structure from PyPI "HTML" index format for a single package.
 #[derive(Debug, Clone)]
@@ -18,8 +19,11 @@ pub(crate) struct SimpleHtml {
 
 impl SimpleHtml {
     /// Parse the list of [`File`]s from the simple HTML page returned by the given URL.
+    ///
+    /// If `lenient` is set, malformed links are skipped (and unparseable hashes are dropped) with
+    /// a warning naming the page, rather than failing the page as a whole.
     #[instrument(skip(text))]
-    pub(crate) fn parse(text: &str, url: &Url) -> Result<Self, Error> {
+    pub(crate) fn parse(text: &str, url: &Url, lenient: bool) -> Result<Self, Error> {
         let dom = tl::parse(text, tl::ParserOptions::default())?;
 
         // Parse the first `<base>` tag, if any, to determine the base URL to which all
@@ -31,20 +35,34 @@ impl SimpleHtml {
                 .filter_map(|node| node.as_tag())
                 .take_while(|tag| !matches!(tag.name().as_bytes(), b"a" | b"link"))
                 .find(|tag| tag.name().as_bytes() == b"base")
-                .map(|base| Self::parse_base(base))
+                .map(|base| match Self::parse_base(base) {
+                    Err(err) if lenient => {
+                        warn_user!("Ignoring invalid `<base>` tag on {url}: {err}");
+                        Ok(None)
+                    }
+                    result => result,
+                })
                 .transpose()?
                 .flatten()
                 .unwrap_or_else(|| url.clone()),
         );
 
         // Parse each `<a>` tag, to extract the filename, hash, and URL.
-        let mut files: Vec<File> = dom
+        let mut files: Vec<File> = Vec::new();
+        for link in dom
             .nodes()
             .iter()
             .filter_map(|node| node.as_tag())
             .filter(|link| link.name().as_bytes() == b"a")
-            .map(|link| Self::parse_anchor(link))
-            .collect::<Result<Vec<_>, _>>()?;
+        {
+            match Self::parse_anchor(link, url, lenient) {
+                Ok(file) => files.push(file),
+                Err(err) if lenient => {
+                    warn_user!("Skipping malformed link on {url}: {err}");
+                }
+                Err(err) => return Err(err),
+            }
+        }
         // While it has not been positively observed, we sort the files
         // to ensure we have a defined ordering. Otherwise, if we rely on
         // the API to provide a stable ordering and doesn't, it can lead
@@ -85,20 +103,21 @@ impl SimpleHtml {
             return Err(Error::FragmentParse(fragment.to_string()));
         }
 
-        // TODO(charlie): Support all hash algorithms.
-        if name != "sha256" {
-            return Err(Error::UnsupportedHashAlgorithm(fragment.to_string()));
+        let value = std::str::from_utf8(value.as_bytes())?.to_string();
+        let mut hashes = Hashes::default();
+        match name {
+            "sha256" => hashes.sha256 = Some(value),
+            "sha384" => hashes.sha384 = Some(value),
+            "sha512" => hashes.sha512 = Some(value),
+            _ => return Err(Error::UnsupportedHashAlgorithm(fragment.to_string())),
         }
-
-        let sha256 = std::str::from_utf8(value.as_bytes())?;
-        let sha256 = sha256.to_string();
-        Ok(Hashes {
-            sha256: Some(sha256),
-        })
+        Ok(hashes)
     }
 
     /// Parse a [`File`] from an `<a>` tag.
-    fn parse_anchor(link: &HTMLTag) -> Result<File, Error> {
+    ///
+    /// If `lenient` is set, unparseable hashes are dropped with a warning, rather than rejected.
+    fn parse_anchor(link: &HTMLTag, url: &Url, lenient: bool) -> Result<File, Error> {
         // Extract the href.
         let href = link
             .attributes()
@@ -110,7 +129,15 @@ impl SimpleHtml {
 
         let (path, hashes) = if let Some((path, fragment)) = href.split_once('#') {
             // Extract the hash, which should be in the fragment.
-            (path, Self::parse_hash(fragment)?)
+            let hashes = match Self::parse_hash(fragment) {
+                Ok(hashes) => hashes,
+                Err(err) if lenient => {
+                    warn_user!("Ignoring hash for `{path}` on {url}: {err}");
+                    Hashes::default()
+                }
+                Err(err) => return Err(err),
+            };
+            (path, hashes)
         } else {
             (href, Hashes::default())
         };
@@ -144,7 +171,12 @@ impl SimpleHtml {
             match dist_info_metadata.as_ref() {
                 "true" => Some(DistInfoMetadata::Bool(true)),
                 "false" => Some(DistInfoMetadata::Bool(false)),
-                fragment => Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?)),
+                fragment => match Self::parse_hash(fragment) {
+                    Ok(hashes) => Some(DistInfoMetadata::Hashes(hashes)),
+                    // The metadata is still available; only its hash is unknown.
+                    Err(_) if lenient => Some(DistInfoMetadata::Bool(true)),
+                    Err(err) => return Err(err),
+                },
             }
         } else {
             None
@@ -196,7 +228,7 @@ pub enum Error {
     #[error("Unexpected fragment (expected `#sha256=...`) on URL: {0}")]
     FragmentParse(String),
 
-    #[error("Unsupported hash algorithm (expected `sha256`) on: {0}")]
+    #[error("Unsupported hash algorithm (expected `sha256`, `sha384`, or `sha512`) on: {0}")]
     UnsupportedHashAlgorithm(String),
 
     #[error("Invalid `requires-python` specifier: {0}")]
@@ -220,7 +252,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap();
+        let result = SimpleHtml::parse(text, &base, false).unwrap();
         insta::assert_debug_snapshot!(result, @r###"
         SimpleHtml {
             base: BaseUrl(
@@ -248,6 +280,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -276,7 +310,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap();
+        let result = SimpleHtml::parse(text, &base, false).unwrap();
         insta::assert_debug_snapshot!(result, @r###"
         SimpleHtml {
             base: BaseUrl(
@@ -304,6 +338,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -316,6 +352,31 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn parse_sha512() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha512=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+<!--TIMESTAMP 1703347410-->
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base, false).unwrap();
+        insta::assert_debug_snapshot!(result.files[0].hashes, @r###"
+        Hashes {
+            sha256: None,
+            sha384: None,
+            sha512: Some(
+                "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
+            ),
+        }
+        "###);
+    }
+
     #[test]
     fn parse_missing_hash() {
         let text = r#"
@@ -329,7 +390,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap();
+        let result = SimpleHtml::parse(text, &base, false).unwrap();
         insta::assert_debug_snapshot!(result, @r###"
         SimpleHtml {
             base: BaseUrl(
@@ -355,6 +416,8 @@ mod tests {
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -380,7 +443,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap_err();
+        let result = SimpleHtml::parse(text, &base, false).unwrap_err();
         insta::assert_display_snapshot!(result, @"Missing href attribute on anchor link");
     }
 
@@ -397,7 +460,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap_err();
+        let result = SimpleHtml::parse(text, &base, false).unwrap_err();
         insta::assert_display_snapshot!(result, @"Missing href attribute on anchor link");
     }
 
@@ -414,7 +477,7 @@ mod tests {
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap_err();
+        let result = SimpleHtml::parse(text, &base, false).unwrap_err();
         insta::assert_display_snapshot!(result, @"Unexpected fragment (expected `#sha256=...`) on URL: sha256");
     }
 
@@ -425,14 +488,49 @@ mod tests {
 <html>
   <body>
     <h1>Links for jinja2</h1>
-    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha512=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#blake2b=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61">Jinja2-3.1.2-py3-none-any.whl</a><br/>
   </body>
 </html>
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap_err();
-        insta::assert_display_snapshot!(result, @"Unsupported hash algorithm (expected `sha256`) on: sha512=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61");
+        let result = SimpleHtml::parse(text, &base, false).unwrap_err();
+        insta::assert_display_snapshot!(result, @"Unsupported hash algorithm (expected `sha256`, `sha384`, or `sha512`) on: blake2b=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61");
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="">Jinja2-3.1.1-py3-none-any.whl</a><br/>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#md5=6088930bfe239f0e6710546ab9c19c9e">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+<!--TIMESTAMP 1703347410-->
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base, true).unwrap();
+        insta::assert_debug_snapshot!(result.files, @r###"
+        [
+            File {
+                dist_info_metadata: None,
+                filename: "Jinja2-3.1.2-py3-none-any.whl",
+                hashes: Hashes {
+                    sha256: None,
+                    sha384: None,
+                    sha512: None,
+                },
+                requires_python: None,
+                size: None,
+                upload_time: None,
+                url: "/whl/Jinja2-3.1.2-py3-none-any.whl#md5=6088930bfe239f0e6710546ab9c19c9e",
+                yanked: None,
+            },
+        ]
+        "###);
     }
 
     #[test]
@@ -449,7 +547,7 @@ mod tests {
         "#;
         let base = Url::parse("https://storage.googleapis.com/jax-releases/jax_cuda_releases.html")
             .unwrap();
-        let result = SimpleHtml::parse(text, &base).unwrap();
+        let result = SimpleHtml::parse(text, &base, false).unwrap();
         insta::assert_debug_snapshot!(result, @r###"
         SimpleHtml {
             base: BaseUrl(
@@ -475,6 +573,8 @@ mod tests {
                     filename: "jaxlib-0.1.52+cuda100-cp36-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -487,6 +587,8 @@ mod tests {
                     filename: "jaxlib-0.1.52+cuda100-cp37-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
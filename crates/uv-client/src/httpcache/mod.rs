@@ -425,14 +425,15 @@ impl ArchivedCachePolicy {
             return true;
         }
         // As per [RFC 9111 S4.3.4], we need to confirm that our validators match. Here,
-        // we check `ETag`.
+        // we check `ETag`. A weak validator is sufficient to select the stored
+        // response for update, so we use the weak comparison function. (Many
+        // servers, e.g., `nginx`, weaken any strong entity tags when
+        // compressing a response.)
         //
         // [RFC 9111 S4.3.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.4
         if let Some(old_etag) = self.response.headers.etag.as_ref() {
             if let Some(new_etag) = new_policy.response.headers.etag.as_ref() {
-                // We don't support weak validators, so only match if they're
-                // both strong.
-                if !old_etag.weak && !new_etag.weak && old_etag.value == new_etag.value {
+                if old_etag.value == new_etag.value {
                     return false;
                 }
             }
@@ -480,9 +481,9 @@ impl ArchivedCachePolicy {
     /// NOT MODIFIED status, which avoids the need to re-transmit the response
     /// body. That is, it indicates that our cached response is still fresh.
     ///
-    /// This will always use a strong etag validator if it's present on the
-    /// cached response. If the given request already has an etag validator
-    /// on it, this routine will add to it and not replace it.
+    /// This will always use the etag validator (strong or weak) if it's present
+    /// on the cached response. If the given request already has an etag
+    /// validator on it, this routine will add to it and not replace it.
     ///
     /// In contrast, if the request already has the `If-Modified-Since` header
     /// set, then this will not change or replace it. If it's not set, then one
@@ -498,16 +499,12 @@ impl ArchivedCachePolicy {
         //
         // [RFC 9110 S13.1.2]: https://www.rfc-editor.org/rfc/rfc9110#section-13.1.2
         // [RFC 9111 S4.3.1]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.1
+        //
+        // `If-None-Match` uses the weak comparison function, so weak validators
+        // are sent as-is, with their `W/` prefix, per [RFC 9110 S13.1.2].
         if let Some(etag) = self.response.headers.etag.as_ref() {
-            // We don't support weak validation principally because we want to
-            // be notified if there was a change in the content. Namely, from
-            // RFC 9110 S13.1.2: "... weak entity tags can be used for cache
-            // validation even if there have been changes to the representation
-            // data."
-            if !etag.weak {
-                if let Ok(header) = HeaderValue::from_bytes(&etag.value) {
-                    request.headers_mut().append("if-none-match", header);
-                }
+            if let Ok(header) = HeaderValue::from_bytes(&etag.to_header_value()) {
+                request.headers_mut().append("if-none-match", header);
             }
         }
         // We also set `If-Modified-Since` as per [RFC 9110 S13.1.3] and [RFC
@@ -1197,7 +1194,8 @@ struct ETag {
     /// group representations by some self-determined set of equivalency rather
     /// than unique sequences of data."
     ///
-    /// We don't currently support weak validation.
+    /// Weak validators are only ever compared with the weak comparison
+    /// function, i.e., when revalidating a cached response.
     ///
     /// [RFC 9110 S8.8.1]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.1-6
     weak: bool,
@@ -1223,6 +1221,18 @@ impl ETag {
     }
 }
 
+impl ArchivedETag {
+    /// Returns the ETag as a header value, restoring the `W/` prefix for weak
+    /// validators.
+    fn to_header_value(&self) -> Vec<u8> {
+        if self.weak {
+            [b"W/".as_slice(), &self.value[..]].concat()
+        } else {
+            self.value.to_vec()
+        }
+    }
+}
+
 /// Represents the `Vary` header on a cached response, as per [RFC 9110
 /// S12.5.5] and [RFC 9111 S4.1].
 ///
@@ -1356,3 +1366,76 @@ fn parse_seconds(value: &[u8]) -> Option<u64> {
     }
     std::str::from_utf8(value).ok()?.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> reqwest::Request {
+        reqwest::Request::new(
+            http::Method::GET,
+            "https://pypi.org/simple/flask/".parse().unwrap(),
+        )
+    }
+
+    fn response(status: u16, etag: &str) -> reqwest::Response {
+        reqwest::Response::from(
+            http::Response::builder()
+                .status(status)
+                .header("cache-control", "max-age=0")
+                .header("etag", etag)
+                .body("")
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn revalidate_strong_etag() {
+        let policy = CachePolicyBuilder::new(&request())
+            .build(&response(200, "\"abc\""))
+            .to_archived();
+
+        let mut revalidation = request();
+        let BeforeRequest::Stale(builder) = policy.before_request(&mut revalidation) else {
+            panic!("Expected a stale response");
+        };
+        assert_eq!(revalidation.headers()["if-none-match"], "\"abc\"");
+        assert!(matches!(
+            policy.after_response(builder, &response(304, "\"abc\"")),
+            AfterResponse::NotModified(_)
+        ));
+    }
+
+    #[test]
+    fn revalidate_weak_etag() {
+        let policy = CachePolicyBuilder::new(&request())
+            .build(&response(200, "W/\"abc\""))
+            .to_archived();
+
+        let mut revalidation = request();
+        let BeforeRequest::Stale(builder) = policy.before_request(&mut revalidation) else {
+            panic!("Expected a stale response");
+        };
+        assert_eq!(revalidation.headers()["if-none-match"], "W/\"abc\"");
+        assert!(matches!(
+            policy.after_response(builder, &response(304, "W/\"abc\"")),
+            AfterResponse::NotModified(_)
+        ));
+    }
+
+    #[test]
+    fn revalidate_changed_etag() {
+        let policy = CachePolicyBuilder::new(&request())
+            .build(&response(200, "W/\"abc\""))
+            .to_archived();
+
+        let mut revalidation = request();
+        let BeforeRequest::Stale(builder) = policy.before_request(&mut revalidation) else {
+            panic!("Expected a stale response");
+        };
+        assert!(matches!(
+            policy.after_response(builder, &response(200, "W/\"def\"")),
+            AfterResponse::Modified(_)
+        ));
+    }
+}
@@ -1,9 +1,16 @@
 use std::fmt::Debug;
 
-use reqwest::{Request, Response};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Request, Response};
 use reqwest_middleware::{Middleware, Next};
 use task_local_extensions::Extensions;
-use url::Url;
+use tracing::{debug, info};
+use url::{Host, Url};
+
+use distribution_types::IndexUrls;
+use uv_warnings::warn_user_once;
+
+use crate::TrustedHost;
 
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,3 +52,319 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that relaxes the TLS requirements for `--trusted-host` entries, and warns on
+/// plain-HTTP requests to any other (non-local) host.
+pub(crate) struct TrustedHostMiddleware {
+    /// The hosts for which TLS certificate verification is disabled.
+    trusted_hosts: Vec<TrustedHost>,
+    /// A client that accepts invalid certificates, used for requests to trusted hosts.
+    insecure_client: Client,
+}
+
+impl TrustedHostMiddleware {
+    pub(crate) fn new(trusted_hosts: Vec<TrustedHost>, insecure_client: Client) -> Self {
+        Self {
+            trusted_hosts,
+            insecure_client,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TrustedHostMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let url = req.url();
+        if let Some(trusted_host) = self.trusted_hosts.iter().find(|host| host.matches(url)) {
+            warn_user_once!(
+                "TLS certificate verification is disabled for trusted host: `{trusted_host}`"
+            );
+            return self
+                .insecure_client
+                .execute(req)
+                .await
+                .map_err(reqwest_middleware::Error::from);
+        }
+
+        if url.scheme() == "http" && !is_local(url) {
+            if let Some(host) = url.host_str() {
+                warn_user_once!(
+                    "Using an insecure connection (HTTP) for `{host}`; pass `--trusted-host {host}` to silence this warning"
+                );
+            }
+        }
+
+        next.run(req, extensions).await
+    }
+}
+
+/// The scheme, host, and port of a URL, which together determine the set of URLs that share
+/// credentials (akin to an HTTP authentication realm).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Realm {
+    scheme: String,
+    host: Option<String>,
+    port: Option<u16>,
+}
+
+impl From<&Url> for Realm {
+    fn from(url: &Url) -> Self {
+        Self {
+            scheme: url.scheme().to_string(),
+            host: url.host_str().map(str::to_ascii_lowercase),
+            port: url.port_or_known_default(),
+        }
+    }
+}
+
+/// A middleware that attaches the credentials embedded in an index URL to requests for the
+/// artifacts it serves.
+///
+/// The links on an index page rarely carry the credentials of the index itself, and private index
+/// servers often serve artifacts from a different path or host than the index (e.g., Artifactory
+/// and Nexus behind a CDN), in which case the requests would otherwise be unauthenticated.
+/// Credentials are only attached to requests in the same realm as the index, or in the realm of
+/// one of its configured `artifact-urls`.
+pub(crate) struct AuthenticationMiddleware {
+    realms: Vec<(Realm, HeaderValue)>,
+}
+
+impl AuthenticationMiddleware {
+    /// Collect the credentials from the given index URLs (and their mirrors), if any.
+    pub(crate) fn from_index_urls(index_urls: &IndexUrls, client: &Client) -> Option<Self> {
+        let mut realms = Vec::new();
+        for index in index_urls.indexes() {
+            let index_override = index_urls.override_for(index);
+            if let Some(header) = authorization(client, index) {
+                realms.push((Realm::from(&**index), header.clone()));
+                for url in index_override.iter().flat_map(|o| &o.artifact_urls) {
+                    realms.push((Realm::from(url), header.clone()));
+                }
+            }
+            // Mirrors are authenticated with their own credentials, if any.
+            for mirror in index_override.iter().flat_map(|o| &o.mirrors) {
+                if let Some(header) = authorization(client, mirror) {
+                    realms.push((Realm::from(mirror), header));
+                }
+            }
+        }
+        if realms.is_empty() {
+            None
+        } else {
+            Some(Self { realms })
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for AuthenticationMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        // Respect any credentials that were provided explicitly.
+        if !req.headers().contains_key(AUTHORIZATION) && req.url().username().is_empty() {
+            let realm = Realm::from(req.url());
+            if let Some((_, header)) = self
+                .realms
+                .iter()
+                .find(|(candidate, _)| *candidate == realm)
+            {
+                req.headers_mut().insert(AUTHORIZATION, header.clone());
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that retries requests against the mirrors of an index when the index is
+/// unreachable (i.e., the request times out, fails to connect, or returns a server error).
+///
+/// Requests under the index URL are relocated to each mirror in turn, preserving the path layout:
+/// paths under the index URL are rebased onto the mirror URL (e.g., `/simple/flask/` on
+/// `https://pypi.org/simple` to `/pypi/simple/flask/` on `https://mirror.example.com/pypi/simple`).
+/// Requests to any other paths on the index host (e.g., `/simple-private/flask/`, which may belong
+/// to a different index) are never relocated.
+pub(crate) struct MirrorMiddleware {
+    /// The indexes with mirrors, along with their mirrors, in order of priority.
+    indexes: Vec<(Url, Vec<Url>)>,
+}
+
+impl MirrorMiddleware {
+    /// Collect the mirrors of the given index URLs, if any.
+    pub(crate) fn from_index_urls(index_urls: &IndexUrls) -> Option<Self> {
+        let indexes: Vec<_> = index_urls
+            .indexes()
+            .filter_map(|index| {
+                let index_override = index_urls.override_for(index)?;
+                if index_override.mirrors.is_empty() {
+                    return None;
+                }
+                Some(((**index).clone(), index_override.mirrors.clone()))
+            })
+            .collect();
+        if indexes.is_empty() {
+            None
+        } else {
+            Some(Self { indexes })
+        }
+    }
+
+    /// Return the index that serves the given URL, along with its mirrors, if any.
+    ///
+    /// The URL must be the index URL itself, or a path beneath it (i.e., `/simple` contains
+    /// `/simple/flask/`, but not `/simple-private/flask/`).
+    fn find(&self, url: &Url) -> Option<&(Url, Vec<Url>)> {
+        self.indexes.iter().find(|(index, _)| {
+            if index.origin() != url.origin() {
+                return false;
+            }
+            let index_path = index.path().trim_end_matches('/');
+            url.path()
+                .strip_prefix(index_path)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for MirrorMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some((index, mirrors)) = self.find(req.url()) else {
+            return next.run(req, extensions).await;
+        };
+        // Requests with a streaming body can't be replayed.
+        let Some(original) = req.try_clone() else {
+            return next.run(req, extensions).await;
+        };
+
+        let result = next.clone().run(req, extensions).await;
+        if !is_unreachable(&result) {
+            return result;
+        }
+
+        for mirror in mirrors {
+            let url = relocate(original.url(), index, mirror);
+            let Some(mut request) = original.try_clone() else {
+                break;
+            };
+            *request.url_mut() = url.clone();
+            // Never forward the credentials of the index to the mirror.
+            if mirror.origin() != index.origin() {
+                request.headers_mut().remove(AUTHORIZATION);
+            }
+
+            let mirrored = next.clone().run(request, extensions).await;
+            if is_unreachable(&mirrored) {
+                debug!("Mirror is unreachable: {}", redacted(mirror));
+                continue;
+            }
+            warn_user_once!(
+                "Index `{}` is unreachable; falling back to mirror: `{}`",
+                redacted(index),
+                redacted(mirror)
+            );
+            info!(
+                url = %redacted(original.url()),
+                mirror = %redacted(&url),
+                "Served request from mirror"
+            );
+            return mirrored;
+        }
+
+        result
+    }
+}
+
+/// Relocate a URL under the index URL to the equivalent URL on the given mirror.
+fn relocate(url: &Url, index: &Url, mirror: &Url) -> Url {
+    let index_path = index.path().trim_end_matches('/');
+    let mirror_path = mirror.path().trim_end_matches('/');
+    let path = match url.path().strip_prefix(index_path) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("{mirror_path}{rest}"),
+        _ => url.path().to_string(),
+    };
+    let mut relocated = redacted(mirror);
+    relocated.set_path(&path);
+    relocated.set_query(url.query());
+    relocated
+}
+
+/// Returns `true` if the request failed to reach the server, such that it should be retried
+/// against a mirror.
+fn is_unreachable(result: &reqwest_middleware::Result<Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(reqwest_middleware::Error::Reqwest(err)) => err.is_timeout() || err.is_connect(),
+        Err(reqwest_middleware::Error::Middleware(_)) => false,
+    }
+}
+
+/// Strip any credentials from a URL, for display or to relocate a request.
+fn redacted(url: &Url) -> Url {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url
+}
+
+/// Return the `Authorization` header that is sent to the given URL, as derived from the
+/// credentials embedded in the URL (if any).
+fn authorization(client: &Client, url: &Url) -> Option<HeaderValue> {
+    // `reqwest` converts (and percent-decodes) any embedded credentials into a Basic
+    // `Authorization` header when building the request.
+    let request = client.get(url.clone()).build().ok()?;
+    request.headers().get(AUTHORIZATION).cloned()
+}
+
+/// Returns `true` if the [`Url`] refers to the local machine (e.g., `localhost` or `127.0.0.1`).
+fn is_local(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::MirrorMiddleware;
+
+    #[test]
+    fn find_mirror() {
+        let index = Url::parse("https://example.com/simple").unwrap();
+        let mirror = Url::parse("https://mirror.example.com/pypi/simple").unwrap();
+        let middleware = MirrorMiddleware {
+            indexes: vec![(index.clone(), vec![mirror])],
+        };
+
+        let find = |url: &str| {
+            middleware
+                .find(&Url::parse(url).unwrap())
+                .map(|(index, _)| index.as_str().to_string())
+        };
+        assert_eq!(find("https://example.com/simple"), Some(index.to_string()));
+        assert_eq!(
+            find("https://example.com/simple/flask/"),
+            Some(index.to_string())
+        );
+        assert_eq!(find("https://example.com/simple-private/flask/"), None);
+        assert_eq!(find("https://example.com/packages/flask.whl"), None);
+        assert_eq!(find("https://other.example.com/simple/flask/"), None);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use async_http_range_reader::{AsyncHttpRangeReader, AsyncHttpRangeReaderError};
 use async_zip::tokio::read::seek::ZipFileReader;
@@ -17,26 +18,33 @@ use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use url::Url;
 
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
-use distribution_types::{BuiltDist, File, FileLocation, IndexUrl, IndexUrls, Name};
+use distribution_types::{
+    BuiltDist, File, FileLocation, IndexFormat, IndexStrategy, IndexUrl, IndexUrls, Name,
+};
 use install_wheel_rs::find_dist_info;
 use pep440_rs::Version;
 use pypi_types::{Metadata21, SimpleJson};
 use uv_cache::{Cache, CacheBucket, WheelCache};
 use uv_normalize::PackageName;
+use uv_warnings::warn_user;
 
 use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
-use crate::middleware::OfflineMiddleware;
+use crate::middleware::{
+    AuthenticationMiddleware, MirrorMiddleware, OfflineMiddleware, TrustedHostMiddleware,
+};
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
 use crate::rkyvutil::OwnedArchive;
-use crate::{CachedClient, CachedClientError, Error, ErrorKind};
+use crate::{CachedClient, CachedClientError, Error, ErrorKind, TrustedHost};
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
 pub struct RegistryClientBuilder {
     index_urls: IndexUrls,
+    trusted_hosts: Vec<TrustedHost>,
     retries: u32,
     connectivity: Connectivity,
+    timeouts: HttpTimeouts,
     cache: Cache,
 }
 
@@ -44,8 +52,10 @@ impl RegistryClientBuilder {
     pub fn new(cache: Cache) -> Self {
         Self {
             index_urls: IndexUrls::default(),
+            trusted_hosts: Vec::new(),
             cache,
             connectivity: Connectivity::Online,
+            timeouts: HttpTimeouts::default(),
             retries: 3,
         }
     }
@@ -58,12 +68,24 @@ impl RegistryClientBuilder {
         self
     }
 
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
         self.connectivity = connectivity;
         self
     }
 
+    #[must_use]
+    pub fn timeouts(mut self, timeouts: HttpTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
         self.retries = retries;
@@ -77,24 +99,51 @@ impl RegistryClientBuilder {
     }
 
     pub fn build(self) -> RegistryClient {
-        let client_raw = {
-            // Disallow any connections.
-            let client_core = ClientBuilder::new()
+        // Disallow any connections.
+        let client_core = || {
+            let builder = ClientBuilder::new()
                 .user_agent("uv")
                 .pool_max_idle_per_host(20)
-                .timeout(std::time::Duration::from_secs(60 * 5));
-
-            client_core.build().expect("Failed to build HTTP client.")
+                .timeout(self.timeouts.request);
+            if let Some(connect) = self.timeouts.connect {
+                builder.connect_timeout(connect)
+            } else {
+                builder
+            }
         };
 
+        let client_raw = client_core().build().expect("Failed to build HTTP client.");
+
+        // For any `--trusted-host` entries, use a separate client that accepts invalid
+        // certificates (e.g., self-signed certificates on an internal index).
+        let insecure_client_raw = (!self.trusted_hosts.is_empty()).then(|| {
+            client_core()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .expect("Failed to build HTTP client.")
+        });
+
         let uncached_client = match self.connectivity {
             Connectivity::Online => {
                 let retry_policy =
                     ExponentialBackoff::builder().build_with_max_retries(self.retries);
                 let retry_strategy = RetryTransientMiddleware::new_with_policy(retry_policy);
-                reqwest_middleware::ClientBuilder::new(client_raw.clone())
-                    .with(retry_strategy)
-                    .build()
+                let trusted_hosts = TrustedHostMiddleware::new(
+                    self.trusted_hosts.clone(),
+                    insecure_client_raw
+                        .clone()
+                        .unwrap_or_else(|| client_raw.clone()),
+                );
+                let mut builder = reqwest_middleware::ClientBuilder::new(client_raw.clone());
+                if let Some(mirrors) = MirrorMiddleware::from_index_urls(&self.index_urls) {
+                    builder = builder.with(mirrors);
+                }
+                if let Some(authentication) =
+                    AuthenticationMiddleware::from_index_urls(&self.index_urls, &client_raw)
+                {
+                    builder = builder.with(authentication);
+                }
+                builder.with(retry_strategy).with(trusted_hosts).build()
             }
             Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client_raw.clone())
                 .with(OfflineMiddleware)
@@ -103,9 +152,11 @@ impl RegistryClientBuilder {
 
         RegistryClient {
             index_urls: self.index_urls,
+            trusted_hosts: self.trusted_hosts,
             cache: self.cache,
             connectivity: self.connectivity,
             client_raw: client_raw.clone(),
+            insecure_client_raw,
             client: CachedClient::new(uncached_client.clone()),
         }
     }
@@ -121,6 +172,10 @@ pub struct RegistryClient {
     /// Don't use this client, it only exists because `async_http_range_reader` needs
     /// [`reqwest::Client] instead of [`reqwest_middleware::Client`]
     client_raw: Client,
+    /// Like `client_raw`, but accepts invalid certificates, for use with `trusted_hosts`.
+    insecure_client_raw: Option<Client>,
+    /// The hosts for which TLS certificate verification is disabled.
+    trusted_hosts: Vec<TrustedHost>,
     /// Used for the remote wheel METADATA cache
     cache: Cache,
     /// The connectivity mode to use.
@@ -138,37 +193,72 @@ impl RegistryClient {
         self.connectivity
     }
 
+    /// Return `true` if malformed links on HTML index pages should be skipped with a warning.
+    pub(crate) fn lenient(&self) -> bool {
+        self.index_urls.lenient()
+    }
+
     /// Fetch a package from the `PyPI` simple API.
     ///
     /// "simple" here refers to [PEP 503 – Simple Repository API](https://peps.python.org/pep-0503/)
     /// and [PEP 691 – JSON-based Simple API for Python Package Indexes](https://peps.python.org/pep-0691/),
     /// which the pypi json api approximately implements.
+    ///
+    /// Returns the metadata from each index that contains the package, in order of priority. Under
+    /// [`IndexStrategy::FirstMatch`], this includes only the first such index.
     #[instrument("simple_api", skip_all, fields(package = % package_name))]
     pub async fn simple(
         &self,
         package_name: &PackageName,
-    ) -> Result<(IndexUrl, OwnedArchive<SimpleMetadata>), Error> {
+    ) -> Result<Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>, Error> {
         if self.index_urls.no_index() {
             return Err(ErrorKind::NoIndex(package_name.as_ref().to_string()).into());
         }
 
+        let mut results = Vec::new();
+        let mut unreachable = Vec::new();
         for index in self.index_urls.indexes() {
             let result = self.simple_single_index(package_name, index).await?;
 
-            return match result {
-                Ok(metadata) => Ok((index.clone(), metadata)),
+            let metadata = match result {
+                Ok(metadata) => metadata,
                 Err(CachedClientError::Client(err)) => match err.into_kind() {
                     ErrorKind::Offline(_) => continue,
-                    ErrorKind::RequestError(err) => {
-                        if err.status() == Some(StatusCode::NOT_FOUND) {
-                            continue;
-                        }
-                        Err(ErrorKind::RequestError(err).into())
+                    ErrorKind::RequestError(err) if err.status() == Some(StatusCode::NOT_FOUND) => {
+                        continue;
+                    }
+                    kind if self.index_urls.fallback() && kind.is_unreachable() => {
+                        warn!("Skipping unreachable index {index} for {package_name}: {kind}");
+                        unreachable.push(index.to_string());
+                        continue;
                     }
-                    other => Err(other.into()),
+                    other => return Err(other.into()),
                 },
-                Err(CachedClientError::Callback(err)) => Err(err),
+                Err(CachedClientError::Callback(err)) => return Err(err),
             };
+
+            results.push((index.clone(), metadata));
+            if self.index_urls.strategy() == IndexStrategy::FirstMatch {
+                break;
+            }
+        }
+
+        if !results.is_empty() {
+            if !unreachable.is_empty() {
+                warn_user!(
+                    "Found `{package_name}` on a fallback index, as the following indexes were unreachable: {}",
+                    unreachable.join(", ")
+                );
+            }
+            return Ok(results);
+        }
+
+        if !unreachable.is_empty() {
+            return Err(ErrorKind::IndexesUnreachable {
+                package: package_name.to_string(),
+                indexes: unreachable,
+            }
+            .into());
         }
 
         match self.connectivity {
@@ -218,36 +308,85 @@ impl RegistryClient {
             .header("Accept", MediaType::accepts())
             .build()
             .map_err(ErrorKind::RequestError)?;
+        let lenient = self.lenient();
+        let index_override = self.index_urls.override_for(index);
+        let format = index_override.and_then(|index_override| index_override.format);
+        let mirrors = index_override
+            .map(|index_override| index_override.mirrors.as_slice())
+            .unwrap_or_default();
         let parse_simple_response = |response: Response| {
             async {
-                let content_type = response
-                    .headers()
-                    .get("content-type")
-                    .ok_or_else(|| Error::from(ErrorKind::MissingContentType(url.clone())))?;
-                let content_type = content_type.to_str().map_err(|err| {
-                    Error::from(ErrorKind::InvalidContentTypeHeader(url.clone(), err))
-                })?;
-                let media_type = content_type.split(';').next().unwrap_or(content_type);
-                let media_type = MediaType::from_str(media_type).ok_or_else(|| {
-                    Error::from(ErrorKind::UnsupportedMediaType(
-                        url.clone(),
-                        media_type.to_string(),
-                    ))
-                })?;
+                // If the index redirected the request (e.g., to add a trailing slash, as devpi and
+                // Nexus do), resolve any relative links against the final URL, retaining any
+                // credentials from the original URL.
+                //
+                // If the page was instead served by a mirror, resolve links against the index
+                // itself (as a directory, per PEP 503), such that artifacts are fetched from the
+                // index, falling back to its mirrors.
+                let mirrored = response.url().origin() != url.origin()
+                    && mirrors
+                        .iter()
+                        .any(|mirror| mirror.origin() == response.url().origin());
+                let mut base = if mirrored {
+                    let mut base = url.clone();
+                    if !base.path().ends_with('/') {
+                        base.set_path(&format!("{}/", base.path()));
+                    }
+                    base
+                } else {
+                    response.url().clone()
+                };
+                if base.origin() == url.origin() {
+                    let _ = base.set_username(url.username());
+                    let _ = base.set_password(url.password());
+                }
+                if base.path() != url.path() {
+                    debug!("Resolving links on {url} relative to: {}", base.path());
+                }
+
+                let media_type = if let Some(format) = format {
+                    Some(MediaType::from(format))
+                } else {
+                    let content_type = response
+                        .headers()
+                        .get("content-type")
+                        .ok_or_else(|| Error::from(ErrorKind::MissingContentType(url.clone())))?;
+                    let content_type = content_type.to_str().map_err(|err| {
+                        Error::from(ErrorKind::InvalidContentTypeHeader(url.clone(), err))
+                    })?;
+                    let media_type = content_type.split(';').next().unwrap_or(content_type);
+                    match MediaType::from_str(media_type) {
+                        Some(media_type) => Some(media_type),
+                        // Some servers respond with a generic media type; inspect the body instead.
+                        None if MediaType::is_generic(media_type) => None,
+                        None => {
+                            return Err(Error::from(ErrorKind::UnsupportedMediaType(
+                                url.clone(),
+                                media_type.to_string(),
+                            )))
+                        }
+                    }
+                };
+
+                let text = response.text().await.map_err(ErrorKind::RequestError)?;
+                let media_type = media_type.unwrap_or_else(|| {
+                    let media_type = MediaType::sniff(&text);
+                    debug!("Inferred {media_type:?} response from the body of: {url}");
+                    media_type
+                });
 
                 let unarchived = match media_type {
                     MediaType::Json => {
-                        let bytes = response.bytes().await.map_err(ErrorKind::RequestError)?;
-                        let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
+                        let data: SimpleJson = serde_json::from_str(&text)
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
                         let metadata =
-                            SimpleMetadata::from_files(data.files, package_name, url.as_str());
+                            SimpleMetadata::from_files(data.files, package_name, base.as_str());
                         metadata
                     }
                     MediaType::Html => {
-                        let text = response.text().await.map_err(ErrorKind::RequestError)?;
-                        let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url)
-                            .map_err(|err| Error::from_html_err(err, url.clone()))?;
+                        let SimpleHtml { base, files } =
+                            SimpleHtml::parse(&text, &base, lenient)
+                                .map_err(|err| Error::from_html_err(err, url.clone()))?;
                         let metadata =
                             SimpleMetadata::from_files(files, package_name, base.as_url().as_str());
                         metadata
@@ -411,7 +550,12 @@ impl RegistryClient {
 
         // This response callback is special, we actually make a number of subsequent requests to
         // fetch the file from the remote zip.
-        let client = self.client_raw.clone();
+        let client = match &self.insecure_client_raw {
+            Some(insecure) if self.trusted_hosts.iter().any(|host| host.matches(url)) => {
+                insecure.clone()
+            }
+            _ => self.client_raw.clone(),
+        };
         let read_metadata_range_request = |response: Response| {
             async {
                 let mut reader = AsyncHttpRangeReader::from_head_response(client, response)
@@ -679,11 +823,37 @@ impl MediaType {
     fn from_str(s: &str) -> Option<Self> {
         match s {
             "application/vnd.pypi.simple.v1+json" => Some(Self::Json),
-            "application/vnd.pypi.simple.v1+html" | "text/html" => Some(Self::Html),
+            "application/vnd.pypi.simple.v1+html" | "text/html" | "application/xhtml+xml" => {
+                Some(Self::Html)
+            }
             _ => None,
         }
     }
 
+    /// Returns `true` if the media type doesn't identify the format of the response, as served by
+    /// some private indexes (e.g., `application/octet-stream`), such that the format must be
+    /// inferred from the body.
+    fn is_generic(s: &str) -> bool {
+        matches!(
+            s,
+            "application/octet-stream"
+                | "binary/octet-stream"
+                | "application/json"
+                | "text/plain"
+                | "text/xml"
+                | "application/xml"
+        )
+    }
+
+    /// Infer the media type of a response from its body.
+    fn sniff(text: &str) -> Self {
+        if text.trim_start().starts_with('{') {
+            Self::Json
+        } else {
+            Self::Html
+        }
+    }
+
     /// Return the `Accept` header value for all supported media types.
     #[inline]
     const fn accepts() -> &'static str {
@@ -692,6 +862,39 @@ impl MediaType {
     }
 }
 
+impl From<IndexFormat> for MediaType {
+    fn from(format: IndexFormat) -> Self {
+        match format {
+            IndexFormat::Html => Self::Html,
+            IndexFormat::Json => Self::Json,
+        }
+    }
+}
+
+/// The timeouts to apply to HTTP requests.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct HttpTimeouts {
+    /// The maximum time to wait for a connection to be established, if any.
+    pub connect: Option<Duration>,
+    /// The maximum time for a request to complete, from connecting until the response body has
+    /// been read in full.
+    pub request: Duration,
+}
+
+impl HttpTimeouts {
+    /// The default request timeout, which is generous enough for large wheels on slow links.
+    pub const DEFAULT_REQUEST: Duration = Duration::from_secs(60 * 5);
+}
+
+impl Default for HttpTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: None,
+            request: Self::DEFAULT_REQUEST,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Connectivity {
     /// Allow access to the network.
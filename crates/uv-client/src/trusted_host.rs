@@ -0,0 +1,120 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use url::Url;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+
+/// A host for which TLS certificate verification is disabled, and plain HTTP is permitted without
+/// warning, as in `--trusted-host`.
+///
+/// For example: `internal.example.com`, or `internal.example.com:8080` to trust a single port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedHost {
+    host: String,
+    port: Option<u16>,
+}
+
+impl TrustedHost {
+    /// Returns `true` if the [`Url`] is served by this host.
+    pub fn matches(&self, url: &Url) -> bool {
+        if !url
+            .host_str()
+            .is_some_and(|host| host.eq_ignore_ascii_case(&self.host))
+        {
+            return false;
+        }
+        self.port
+            .map_or(true, |port| url.port_or_known_default() == Some(port))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedHostError {
+    #[error("Expected a host, optionally followed by a port (e.g., `example.com:8080`), but found: `{0}`")]
+    InvalidHost(String),
+}
+
+impl FromStr for TrustedHost {
+    type Err = TrustedHostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Accept a scheme, like pip, though it has no effect.
+        let host = s
+            .strip_prefix("https://")
+            .or_else(|| s.strip_prefix("http://"))
+            .unwrap_or(s)
+            .trim_end_matches('/');
+
+        let (host, port) = match host.rsplit_once(':') {
+            // Ex) `[::1]`, without a port.
+            Some((_, port)) if port.ends_with(']') => (host, None),
+            Some((host, port)) => {
+                let port = u16::from_str(port)
+                    .map_err(|_| TrustedHostError::InvalidHost(s.to_string()))?;
+                (host, Some(port))
+            }
+            None => (host, None),
+        };
+
+        if host.is_empty() || host.contains(['/', '@', ' ']) {
+            return Err(TrustedHostError::InvalidHost(s.to_string()));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl Display for TrustedHost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(port) = self.port {
+            write!(f, "{}:{port}", self.host)
+        } else {
+            write!(f, "{}", self.host)
+        }
+    }
+}
+
+impl CacheKey for TrustedHost {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.host.cache_key(state);
+        self.port.cache_key(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use url::Url;
+
+    use super::TrustedHost;
+
+    #[test]
+    fn matches() {
+        let host = TrustedHost::from_str("example.com").unwrap();
+        assert!(host.matches(&Url::parse("https://example.com/simple/").unwrap()));
+        assert!(host.matches(&Url::parse("http://EXAMPLE.com:8080/simple/").unwrap()));
+        assert!(!host.matches(&Url::parse("https://pypi.org/simple/").unwrap()));
+
+        let host = TrustedHost::from_str("example.com:8080").unwrap();
+        assert!(host.matches(&Url::parse("http://example.com:8080/simple/").unwrap()));
+        assert!(!host.matches(&Url::parse("https://example.com/simple/").unwrap()));
+
+        let host = TrustedHost::from_str("https://example.com:443/").unwrap();
+        assert!(host.matches(&Url::parse("https://example.com/simple/").unwrap()));
+
+        let host = TrustedHost::from_str("[::1]:8080").unwrap();
+        assert!(host.matches(&Url::parse("http://[::1]:8080/simple/").unwrap()));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(TrustedHost::from_str("").is_err());
+        assert!(TrustedHost::from_str("example.com:http").is_err());
+        assert!(TrustedHost::from_str("example.com/simple").is_err());
+    }
+}
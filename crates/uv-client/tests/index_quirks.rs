@@ -0,0 +1,272 @@
+//! Tests for the quirks of common private index servers (e.g., devpi, Artifactory, and Nexus),
+//! served from a minimal local HTTP server.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use anyhow::Result;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use url::Url;
+
+use distribution_types::{FileLocation, IndexLocations, IndexOverride, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{OwnedArchive, RegistryClientBuilder};
+use uv_normalize::PackageName;
+
+const TQDM_WHEEL: &str = "tqdm-4.66.1-py3-none-any.whl";
+const TQDM_SHA256: &str = "d302b3c5b53d47bce91fea46679d9c3c6508cf6332229aa1e7d8653723793386";
+
+/// A request, as received by the test server.
+struct Request {
+    path: String,
+    authorization: Option<String>,
+}
+
+/// A response, as sent by the test server.
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn ok(content_type: &str, body: String) -> Self {
+        Self {
+            status: "200 OK",
+            headers: vec![("Content-Type", content_type.to_string())],
+            body,
+        }
+    }
+
+    fn redirect(location: &str) -> Self {
+        Self {
+            status: "301 Moved Permanently",
+            headers: vec![("Location", location.to_string())],
+            body: String::new(),
+        }
+    }
+
+    fn status(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+}
+
+/// Serve each request with the given handler, returning the address of the server.
+async fn serve<F, Fut>(handler: F) -> Result<SocketAddr>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let handler = std::sync::Arc::new(handler);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                // Read the request head; the test requests never have a body.
+                let mut head = Vec::new();
+                let mut buf = [0; 1024];
+                while !head.ends_with(b"\r\n\r\n") {
+                    let Ok(n) = stream.read(&mut buf).await else {
+                        return;
+                    };
+                    if n == 0 {
+                        return;
+                    }
+                    head.extend_from_slice(&buf[..n]);
+                }
+                let head = String::from_utf8_lossy(&head);
+                let mut lines = head.lines();
+                let path = lines
+                    .next()
+                    .and_then(|line| line.split(' ').nth(1))
+                    .unwrap_or_default()
+                    .to_string();
+                let authorization = lines.find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("authorization")
+                        .then(|| value.trim().to_string())
+                });
+
+                let response = handler(Request {
+                    path,
+                    authorization,
+                })
+                .await;
+                let mut raw = format!("HTTP/1.1 {}\r\n", response.status);
+                for (name, value) in &response.headers {
+                    raw.push_str(&format!("{name}: {value}\r\n"));
+                }
+                raw.push_str(&format!(
+                    "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.body.len(),
+                    response.body
+                ));
+                let _ = stream.write_all(raw.as_bytes()).await;
+            });
+        }
+    });
+    Ok(addr)
+}
+
+/// devpi and Nexus redirect `/simple/<package>` to `/simple/<package>/`, and serve relative links
+/// that must be resolved against the redirected URL. Some servers also respond with a generic
+/// `Content-Type`.
+#[tokio::test]
+async fn trailing_slash_redirect() -> Result<()> {
+    let addr = serve(|request: Request| async move {
+        match request.path.as_str() {
+            "/simple/tqdm" => Response::redirect("/simple/tqdm/"),
+            "/simple/tqdm/" => Response::ok(
+                "application/octet-stream",
+                format!(
+                    r#"<html><body><a href="../../+f/{TQDM_WHEEL}#sha256={TQDM_SHA256}">{TQDM_WHEEL}</a></body></html>"#
+                ),
+            ),
+            _ => Response::status("404 Not Found"),
+        }
+    })
+    .await?;
+
+    let index = IndexUrl::from_str(&format!("http://{addr}/simple"))?;
+    let index_urls = IndexLocations::from_args(index, Vec::new(), Vec::new(), false).index_urls();
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .index_urls(index_urls)
+        .build();
+
+    let results = client.simple(&PackageName::from_str("tqdm")?).await?;
+    let (_, archive) = results.into_iter().next().unwrap();
+    let metadata = OwnedArchive::deserialize(&archive);
+    let datum = metadata.iter().next().unwrap();
+    assert_eq!(datum.version.to_string(), "4.66.1");
+
+    let wheel = &datum.files.wheels[0];
+    let FileLocation::RelativeUrl(base, url) = &wheel.file.url else {
+        panic!("Expected a relative URL, found: {:?}", wheel.file.url);
+    };
+    let mut url = Url::parse(base)?.join(url)?;
+    url.set_fragment(None);
+    assert_eq!(url.as_str(), format!("http://{addr}/+f/{TQDM_WHEEL}"));
+
+    Ok(())
+}
+
+/// Artifactory and Nexus may serve artifacts from a different host than the index (e.g., behind a
+/// CDN), in which case the credentials of the index must be forwarded to the artifact host, as
+/// configured by an index override.
+#[tokio::test]
+async fn artifact_credentials() -> Result<()> {
+    // `Basic` credentials for `user:pass`.
+    let artifacts = serve(|request: Request| async move {
+        if request.authorization.as_deref() == Some("Basic dXNlcjpwYXNz") {
+            Response::ok("application/octet-stream", "wheel".to_string())
+        } else {
+            Response::status("401 Unauthorized")
+        }
+    })
+    .await?;
+    let index = serve(|_| async move { Response::status("404 Not Found") }).await?;
+
+    let index_url = IndexUrl::from_str(&format!("http://user:pass@{index}/simple"))?;
+    let artifact_url = Url::parse(&format!("http://{artifacts}/files/{TQDM_WHEEL}"))?;
+
+    // Without an override, the credentials are scoped to the index host.
+    let index_urls =
+        IndexLocations::from_args(index_url.clone(), Vec::new(), Vec::new(), false).index_urls();
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .index_urls(index_urls)
+        .retries(0)
+        .build();
+    let response = client
+        .cached_client()
+        .uncached()
+        .get(artifact_url.clone())
+        .send()
+        .await?;
+    assert_eq!(response.status(), 401);
+
+    // With an override, the credentials are forwarded to the artifact host.
+    let index_urls = IndexLocations::from_args(index_url, Vec::new(), Vec::new(), false)
+        .with_overrides(vec![IndexOverride {
+            url: Url::parse(&format!("http://{index}/simple/"))?,
+            format: None,
+            artifact_urls: vec![Url::parse(&format!("http://{artifacts}"))?],
+            mirrors: Vec::new(),
+        }])
+        .index_urls();
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .index_urls(index_urls)
+        .retries(0)
+        .build();
+    let response = client
+        .cached_client()
+        .uncached()
+        .get(artifact_url)
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await?, "wheel");
+
+    Ok(())
+}
+
+/// When an index is unreachable, requests are retried against its mirrors, preserving the path
+/// layout of the index.
+#[tokio::test]
+async fn mirror_failover() -> Result<()> {
+    let primary = serve(|_| async move { Response::status("503 Service Unavailable") }).await?;
+    let mirror = serve(|request: Request| async move {
+        match request.path.as_str() {
+            "/pypi/simple/tqdm" => Response::ok(
+                "text/html",
+                format!(
+                    r#"<html><body><a href="{TQDM_WHEEL}#sha256={TQDM_SHA256}">{TQDM_WHEEL}</a></body></html>"#
+                ),
+            ),
+            _ => Response::status("404 Not Found"),
+        }
+    })
+    .await?;
+
+    let index = IndexUrl::from_str(&format!("http://{primary}/simple"))?;
+    let index_urls = IndexLocations::from_args(index, Vec::new(), Vec::new(), false)
+        .with_overrides(vec![IndexOverride {
+            url: Url::parse(&format!("http://{primary}/simple"))?,
+            format: None,
+            artifact_urls: Vec::new(),
+            mirrors: vec![Url::parse(&format!("http://{mirror}/pypi/simple"))?],
+        }])
+        .index_urls();
+    let client = RegistryClientBuilder::new(Cache::temp()?)
+        .index_urls(index_urls)
+        .retries(0)
+        .build();
+
+    let results = client.simple(&PackageName::from_str("tqdm")?).await?;
+    let (_, archive) = results.into_iter().next().unwrap();
+    let metadata = OwnedArchive::deserialize(&archive);
+    let datum = metadata.iter().next().unwrap();
+    assert_eq!(datum.version.to_string(), "4.66.1");
+
+    // Links on the mirrored page are resolved against the index, such that artifacts are fetched
+    // from the index (falling back to the mirror).
+    let wheel = &datum.files.wheels[0];
+    let FileLocation::RelativeUrl(base, url) = &wheel.file.url else {
+        panic!("Expected a relative URL, found: {:?}", wheel.file.url);
+    };
+    let mut url = Url::parse(base)?.join(url)?;
+    url.set_fragment(None);
+    assert_eq!(
+        url.as_str(),
+        format!("http://{primary}/simple/tqdm/{TQDM_WHEEL}")
+    );
+
+    Ok(())
+}
@@ -48,7 +48,7 @@ async fn find_latest_version(
     client: &RegistryClient,
     package_name: &PackageName,
 ) -> Option<Version> {
-    let (_, raw_simple_metadata) = client.simple(package_name).await.ok()?;
+    let (_, raw_simple_metadata) = client.simple(package_name).await.ok()?.into_iter().next()?;
     let simple_metadata = OwnedArchive::deserialize(&raw_simple_metadata);
     let version = simple_metadata.into_iter().next()?.version;
     Some(version.clone())
@@ -7,6 +7,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use itertools::Itertools;
+use tokio::sync::Semaphore;
 use tracing::{debug, instrument};
 
 use distribution_types::{IndexLocations, Name, Resolution, SourceDist};
@@ -18,7 +19,7 @@ use uv_client::{FlatIndex, RegistryClient};
 use uv_installer::{Downloader, Installer, NoBinary, Plan, Planner, Reinstall, SitePackages};
 use uv_interpreter::{Interpreter, Virtualenv};
 use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
-use uv_traits::{BuildContext, BuildKind, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, BuildKind, Concurrency, InFlight, NoBuild, SetupPyStrategy};
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
@@ -36,6 +37,8 @@ pub struct BuildDispatch<'a> {
     no_binary: &'a NoBinary,
     source_build_context: SourceBuildContext,
     options: Options,
+    concurrency: Concurrency,
+    build_semaphore: Semaphore,
 }
 
 impl<'a> BuildDispatch<'a> {
@@ -67,6 +70,8 @@ impl<'a> BuildDispatch<'a> {
             no_binary,
             source_build_context: SourceBuildContext::default(),
             options: Options::default(),
+            concurrency: Concurrency::default(),
+            build_semaphore: Semaphore::new(Concurrency::default().builds),
         }
     }
 
@@ -75,6 +80,13 @@ impl<'a> BuildDispatch<'a> {
         self.options = options;
         self
     }
+
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.build_semaphore = Semaphore::new(concurrency.builds);
+        self.concurrency = concurrency;
+        self
+    }
 }
 
 impl<'a> BuildContext for BuildDispatch<'a> {
@@ -108,6 +120,14 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.index_locations
     }
 
+    fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
+    fn build_semaphore(&self) -> &Semaphore {
+        &self.build_semaphore
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
         let markers = self.interpreter.markers();
         let tags = self.interpreter.tags()?;
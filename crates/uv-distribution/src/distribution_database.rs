@@ -1,26 +1,32 @@
 use std::borrow::Cow;
+use std::future::Future;
 use std::io;
-use std::path::Path;
+use std::io::Seek;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
+use tokio::io::AsyncWriteExt;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{info_span, instrument, Instrument};
+use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
 
+use distribution_filename::WheelFilename;
 use distribution_types::{
     BuiltDist, DirectGitUrl, Dist, FileLocation, IndexLocations, LocalEditable, Name, SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::Metadata21;
-use uv_cache::{Cache, CacheBucket, Timestamp, WheelCache};
+use pypi_types::{Hashes, Metadata21};
+use uv_cache::{Cache, CacheBucket, CacheEntry, Timestamp, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
 use uv_fs::metadata_if_exists;
 use uv_git::GitSource;
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, NoBinary, NoBuild};
 
 use crate::download::{BuiltWheel, UnzippedWheel};
-use crate::locks::Locks;
+use crate::hash::HashValidator;
+use crate::locks::{lock_entry, Locks};
 use crate::reporter::Facade;
 use crate::{DiskWheel, Error, LocalWheel, Reporter, SourceDistCachedBuilder};
 
@@ -77,8 +83,22 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     ///
     /// If `no_remote_wheel` is set, the wheel will be built from a source distribution
     /// even if compatible pre-built wheels are available.
+    ///
+    /// If a download doesn't match the hash advertised by the index, or pinned in the URL of a
+    /// direct URL wheel (e.g., due to a truncated download or a misbehaving mirror), it's retried
+    /// once before failing.
     #[instrument(skip(self))]
     pub async fn get_or_build_wheel(&self, dist: Dist) -> Result<LocalWheel, Error> {
+        match self.fetch_or_build_wheel(dist.clone()).await {
+            Err(err @ Error::HashMismatch { .. }) => {
+                debug!("{err}\n\nRetrying download...");
+                self.fetch_or_build_wheel(dist).await
+            }
+            result => result,
+        }
+    }
+
+    async fn fetch_or_build_wheel(&self, dist: Dist) -> Result<LocalWheel, Error> {
         let no_binary = match self.build_context.no_binary() {
             NoBinary::None => false,
             NoBinary::All => true,
@@ -107,7 +127,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                         // If the file is already unzipped, and the unzipped directory is fresh,
                         // return it.
-                        match cache_entry.path().canonicalize() {
+                        match cache_entry.read_path().canonicalize() {
                             Ok(archive) => {
                                 if let (Some(cache_metadata), Some(path_metadata)) = (
                                     metadata_if_exists(&archive).map_err(Error::CacheRead)?,
@@ -144,49 +164,40 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     WheelCache::Index(&wheel.index).remote_wheel_dir(wheel.name().as_ref()),
                     wheel.filename.stem(),
                 );
-                let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
-
-                let download = |response: reqwest::Response| {
-                    async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-                            .into_async_read();
-
-                        // Download and unzip the wheel to a temporary directory.
-                        let temp_dir =
-                            tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
-
-                        // Persist the temporary directory to the directory store.
-                        let archive = self
-                            .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
-                            .map_err(Error::CacheRead)?;
-                        Ok(archive)
-                    }
-                    .instrument(info_span!("download", wheel = %wheel))
-                };
-
-                let req = self.client.cached_client().uncached().get(url).build()?;
-                let cache_control = match self.client.connectivity() {
-                    Connectivity::Online => CacheControl::from(
-                        self.cache
-                            .freshness(&http_entry, Some(wheel.name()))
-                            .map_err(Error::CacheRead)?,
-                    ),
-                    Connectivity::Offline => CacheControl::AllowStale,
-                };
 
-                let archive = self
-                    .client
-                    .cached_client()
-                    .get_serde(req, &http_entry, cache_control, download)
+                // Avoid races with other processes downloading the same wheel.
+                let _lock = lock_entry(&self.cache, &wheel_entry).await?;
+
+                // Unzip the wheel as it's downloaded. If the archive layout doesn't allow for
+                // streaming (e.g., entries with data descriptors), download it to disk first.
+                let archive = match self
+                    .stream_wheel(
+                        url.clone(),
+                        &wheel.filename,
+                        wheel.file.size,
+                        Some(&wheel.file.hashes),
+                        &wheel_entry,
+                        &dist,
+                    )
                     .await
-                    .map_err(|err| match err {
-                        CachedClientError::Callback(err) => err,
-                        CachedClientError::Client(err) => Error::Client(err),
-                    })?;
+                {
+                    Ok(archive) => archive,
+                    Err(Error::Extract(err)) if err.is_http_streaming_unsupported() => {
+                        warn!(
+                            "Streaming unsupported for {dist}; downloading wheel to disk ({err})"
+                        );
+                        self.download_wheel(
+                            url,
+                            &wheel.filename,
+                            wheel.file.size,
+                            Some(&wheel.file.hashes),
+                            &wheel_entry,
+                            &dist,
+                        )
+                        .await?
+                    }
+                    Err(err) => return Err(err),
+                };
 
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
@@ -200,59 +211,56 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     return Err(Error::NoBinary);
                 }
 
-                // Create an entry for the wheel itself alongside its HTTP cache.
+                // Create an entry for the wheel itself alongside its HTTP cache. If the URL is
+                // pinned to a hash, the entry is keyed by the hash, too.
                 let wheel_entry = self.cache.entry(
                     CacheBucket::Wheels,
                     WheelCache::Url(&wheel.url).remote_wheel_dir(wheel.name().as_ref()),
                     wheel.filename.stem(),
                 );
-                let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
-
-                let download = |response: reqwest::Response| {
-                    async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-                            .into_async_read();
-
-                        // Download and unzip the wheel to a temporary directory.
-                        let temp_dir =
-                            tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
-                        uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
-
-                        // Persist the temporary directory to the directory store.
-                        let archive = self
-                            .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
-                            .map_err(Error::CacheRead)?;
-                        Ok(archive)
-                    }
-                    .instrument(info_span!("download", wheel = %wheel))
-                };
 
-                let req = self
-                    .client
-                    .cached_client()
-                    .uncached()
-                    .get(wheel.url.raw().clone())
-                    .build()?;
-                let cache_control = match self.client.connectivity() {
-                    Connectivity::Online => CacheControl::from(
-                        self.cache
-                            .freshness(&http_entry, Some(wheel.name()))
-                            .map_err(Error::CacheRead)?,
-                    ),
-                    Connectivity::Offline => CacheControl::AllowStale,
-                };
-                let archive = self
-                    .client
-                    .cached_client()
-                    .get_serde(req, &http_entry, cache_control, download)
+                // Validate the download against the hash pinned in the URL (e.g., `#sha256=...`),
+                // if any.
+                let hashes = wheel
+                    .url
+                    .raw()
+                    .fragment()
+                    .map(Hashes::from_fragment)
+                    .filter(|hashes| !hashes.is_empty());
+
+                // Avoid races with other processes downloading the same wheel.
+                let _lock = lock_entry(&self.cache, &wheel_entry).await?;
+
+                // Unzip the wheel as it's downloaded. If the archive layout doesn't allow for
+                // streaming (e.g., entries with data descriptors), download it to disk first.
+                let archive = match self
+                    .stream_wheel(
+                        wheel.url.raw().clone(),
+                        &wheel.filename,
+                        None,
+                        hashes.as_ref(),
+                        &wheel_entry,
+                        &dist,
+                    )
                     .await
-                    .map_err(|err| match err {
-                        CachedClientError::Callback(err) => err,
-                        CachedClientError::Client(err) => Error::Client(err),
-                    })?;
+                {
+                    Ok(archive) => archive,
+                    Err(Error::Extract(err)) if err.is_http_streaming_unsupported() => {
+                        warn!(
+                            "Streaming unsupported for {dist}; downloading wheel to disk ({err})"
+                        );
+                        self.download_wheel(
+                            wheel.url.raw().clone(),
+                            &wheel.filename,
+                            None,
+                            hashes.as_ref(),
+                            &wheel_entry,
+                            &dist,
+                        )
+                        .await?
+                    }
+                    Err(err) => return Err(err),
+                };
 
                 Ok(LocalWheel::Unzipped(UnzippedWheel {
                     dist: dist.clone(),
@@ -274,7 +282,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 // If the file is already unzipped, and the unzipped directory is fresh,
                 // return it.
-                match cache_entry.path().canonicalize() {
+                match cache_entry.read_path().canonicalize() {
                     Ok(archive) => {
                         if let (Some(cache_metadata), Some(path_metadata)) = (
                             metadata_if_exists(&archive).map_err(Error::CacheRead)?,
@@ -341,6 +349,19 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
     pub async fn get_or_build_wheel_metadata(
         &self,
         dist: &Dist,
+    ) -> Result<(Metadata21, Option<Url>), Error> {
+        match self.fetch_or_build_wheel_metadata(dist).await {
+            Err(err @ Error::HashMismatch { .. }) => {
+                debug!("{err}\n\nRetrying download...");
+                self.fetch_or_build_wheel_metadata(dist).await
+            }
+            result => result,
+        }
+    }
+
+    async fn fetch_or_build_wheel_metadata(
+        &self,
+        dist: &Dist,
     ) -> Result<(Metadata21, Option<Url>), Error> {
         match dist {
             Dist::Built(built_dist) => {
@@ -398,6 +419,150 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         Ok((LocalWheel::Built(built_wheel), metadata))
     }
 
+    /// Download a wheel from a URL, unzipping it into the cache as the bytes arrive.
+    async fn stream_wheel(
+        &self,
+        url: Url,
+        filename: &WheelFilename,
+        size: Option<u64>,
+        hashes: Option<&Hashes>,
+        wheel_entry: &CacheEntry,
+        dist: &Dist,
+    ) -> Result<PathBuf, Error> {
+        let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
+
+        let download = |response: reqwest::Response| {
+            async {
+                let download = Download::start(
+                    self.reporter.as_ref(),
+                    dist.name(),
+                    response.content_length().or(size),
+                    hashes,
+                );
+
+                // Download and unzip the wheel to a temporary directory.
+                let temp_dir =
+                    tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
+                let mut reader = download.reader(response).compat();
+                uv_extract::stream::unzip(&mut reader, temp_dir.path()).await?;
+
+                // Validate the download against the hash advertised by the index, before it's
+                // persisted to the cache.
+                if download.validator.is_some() {
+                    tokio::io::copy(&mut reader, &mut tokio::io::sink())
+                        .await
+                        .map_err(Error::Download)?;
+                    download.validate(dist)?;
+                }
+
+                // Persist the temporary directory to the directory store.
+                let archive = self
+                    .cache
+                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .map_err(Error::CacheRead)?;
+                Ok(archive_id(&archive))
+            }
+            .instrument(info_span!("download", wheel = %dist))
+        };
+
+        self.fetch_wheel(url, dist, &http_entry, download).await
+    }
+
+    /// Download a wheel from a URL to a temporary file, then unzip it into the cache.
+    ///
+    /// Slower than [`DistributionDatabase::stream_wheel`], but supports any valid zip archive.
+    async fn download_wheel(
+        &self,
+        url: Url,
+        filename: &WheelFilename,
+        size: Option<u64>,
+        hashes: Option<&Hashes>,
+        wheel_entry: &CacheEntry,
+        dist: &Dist,
+    ) -> Result<PathBuf, Error> {
+        let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
+
+        let download = |response: reqwest::Response| {
+            async {
+                let download = Download::start(
+                    self.reporter.as_ref(),
+                    dist.name(),
+                    response.content_length().or(size),
+                    hashes,
+                );
+
+                // Download the wheel to a temporary file.
+                let temp_file =
+                    tempfile::tempfile_in(self.cache.root()).map_err(Error::CacheWrite)?;
+                let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(temp_file));
+                tokio::io::copy(&mut download.reader(response).compat(), &mut writer)
+                    .await
+                    .map_err(Error::CacheWrite)?;
+                writer.flush().await.map_err(Error::CacheWrite)?;
+                download.validate(dist)?;
+
+                // Close out the progress bar before unzipping.
+                drop(download);
+
+                // Unzip the wheel to a temporary directory.
+                let mut file = writer.into_inner().into_std().await;
+                file.seek(io::SeekFrom::Start(0))
+                    .map_err(Error::CacheWrite)?;
+                let temp_dir =
+                    tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
+                let target = temp_dir.path().to_path_buf();
+                tokio::task::spawn_blocking(move || uv_extract::unzip(file, &target)).await??;
+
+                // Persist the temporary directory to the directory store.
+                let archive = self
+                    .cache
+                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .map_err(Error::CacheRead)?;
+                Ok(archive_id(&archive))
+            }
+            .instrument(info_span!("download", wheel = %dist))
+        };
+
+        self.fetch_wheel(url, dist, &http_entry, download).await
+    }
+
+    /// Request a wheel through the HTTP cache, passing any new or updated response to `download`.
+    ///
+    /// The cached payload is the ID of the unzipped archive, rather than its absolute path, such
+    /// that the cache remains valid when moved (e.g., via `uv cache export` and `uv cache import`).
+    async fn fetch_wheel<Callback, CallbackReturn>(
+        &self,
+        url: Url,
+        dist: &Dist,
+        http_entry: &CacheEntry,
+        download: Callback,
+    ) -> Result<PathBuf, Error>
+    where
+        Callback: FnOnce(reqwest::Response) -> CallbackReturn + Send,
+        CallbackReturn: Future<Output = Result<PathBuf, Error>> + Send,
+    {
+        let req = self.client.cached_client().uncached().get(url).build()?;
+        let cache_control = match self.client.connectivity() {
+            Connectivity::Online => CacheControl::from(
+                self.cache
+                    .freshness(http_entry, Some(dist.name()))
+                    .map_err(Error::CacheRead)?,
+            ),
+            Connectivity::Offline => CacheControl::AllowStale,
+        };
+
+        let id: PathBuf = self
+            .client
+            .cached_client()
+            .get_serde(req, http_entry, cache_control, download)
+            .await
+            .map_err(|err| match err {
+                CachedClientError::Callback(err) => err,
+                CachedClientError::Client(err) => Error::Client(err),
+            })?;
+        Ok(self.cache.archive(&id))
+    }
+
     /// Given a remote source distribution, return a precise variant, if possible.
     ///
     /// For example, given a Git dependency with a reference to a branch or tag, return a URL
@@ -440,3 +605,70 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         self.build_context.index_locations()
     }
 }
+
+/// A wheel download in progress, which reports its progress to the [`Reporter`] and validates the
+/// bytes it receives against the hash advertised by the index, if any.
+///
+/// The download is reported as complete when dropped, such that the progress bar is closed out on
+/// every path, including failures (e.g., if the caller falls back to downloading the wheel to
+/// disk, or the hash doesn't match).
+struct Download<'a> {
+    name: &'a PackageName,
+    progress: Option<(&'a Arc<dyn Reporter>, usize)>,
+    validator: Option<HashValidator>,
+}
+
+impl<'a> Download<'a> {
+    /// Report the start of a download of the given size, if known.
+    fn start(
+        reporter: Option<&'a Arc<dyn Reporter>>,
+        name: &'a PackageName,
+        size: Option<u64>,
+        hashes: Option<&Hashes>,
+    ) -> Self {
+        Self {
+            name,
+            progress: reporter.map(|reporter| (reporter, reporter.on_download_start(name, size))),
+            validator: hashes.and_then(HashValidator::new),
+        }
+    }
+
+    /// Read the response body, reporting progress and feeding the validator as chunks arrive.
+    fn reader(&self, response: reqwest::Response) -> impl futures::AsyncRead + Unpin + '_ {
+        let progress = self.progress;
+        let validator = self.validator.as_ref();
+        response
+            .bytes_stream()
+            .inspect_ok(move |chunk| {
+                if let Some((reporter, id)) = progress {
+                    reporter.on_download_progress(id, chunk.len() as u64);
+                }
+                if let Some(validator) = validator {
+                    validator.update(chunk);
+                }
+            })
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+            .into_async_read()
+    }
+
+    /// Validate the bytes read so far against the expected hash, if any.
+    fn validate(&self, dist: &Dist) -> Result<(), Error> {
+        match &self.validator {
+            Some(validator) => validator.validate(dist),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Download<'_> {
+    fn drop(&mut self) {
+        if let Some((reporter, id)) = self.progress {
+            reporter.on_download_complete(self.name, id);
+        }
+    }
+}
+
+/// Return the ID of an archive persisted to the cache.
+fn archive_id(archive: &Path) -> PathBuf {
+    PathBuf::from(archive.file_name().expect("Archive to have a file name"))
+}
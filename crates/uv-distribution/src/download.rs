@@ -118,6 +118,16 @@ impl BuiltWheel {
     pub fn remote(&self) -> &Dist {
         &self.dist
     }
+
+    /// Return the [`WheelFilename`] of the built wheel.
+    pub fn filename(&self) -> &WheelFilename {
+        &self.filename
+    }
+
+    /// Return the path to the built wheel.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 impl std::fmt::Display for LocalWheel {
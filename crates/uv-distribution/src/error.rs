@@ -22,6 +22,15 @@ pub enum Error {
     Request(#[from] reqwest::Error),
     #[error(transparent)]
     Client(#[from] uv_client::Error),
+    #[error("Failed to read the response body")]
+    Download(#[source] std::io::Error),
+    #[error("Hash mismatch for {distribution}\n\nExpected:\n  {algorithm}:{expected}\n\nComputed:\n  {algorithm}:{actual}")]
+    HashMismatch {
+        distribution: String,
+        algorithm: pypi_types::HashAlgorithm,
+        expected: String,
+        actual: String,
+    },
 
     // Cache writing error
     #[error("Failed to read from the distribution cache")]
@@ -56,6 +65,12 @@ pub enum Error {
     #[error("Failed to extract source distribution")]
     Extract(#[from] uv_extract::Error),
 
+    // Remote build cache error
+    #[error("Malformed remote build cache entry at: {0}")]
+    RemoteBuildEntry(String),
+    #[error("Remote build `{0}` is not compatible with the current interpreter and platform")]
+    IncompatibleRemoteBuild(String),
+
     /// Should not occur; only seen when another task panicked.
     #[error("The task executor is broken, did some other task panic?")]
     Join(#[from] JoinError),
@@ -0,0 +1,91 @@
+use std::fmt::Display;
+use std::sync::Mutex;
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use pypi_types::{HashAlgorithm, Hashes};
+
+use crate::Error;
+
+/// Computes the digest of a distribution as it's downloaded, to validate it against the hash
+/// advertised by the index.
+///
+/// If the index advertises multiple hashes, the strongest supported algorithm is used.
+///
+/// The digest must cover the entire response body, so any bytes that the archive reader leaves
+/// unread (e.g., trailing data after the last entry) must be consumed before calling
+/// [`HashValidator::validate`].
+pub struct HashValidator {
+    algorithm: HashAlgorithm,
+    expected: String,
+    hasher: Mutex<Hasher>,
+}
+
+impl HashValidator {
+    /// Create a validator for the given hashes, or `None` if the index didn't provide a supported
+    /// hash.
+    pub fn new(hashes: &Hashes) -> Option<Self> {
+        let (algorithm, expected) = hashes.strongest()?;
+        Some(Self {
+            algorithm,
+            expected: expected.to_ascii_lowercase(),
+            hasher: Mutex::new(Hasher::from(algorithm)),
+        })
+    }
+
+    /// Feed a chunk of the response body into the digest.
+    pub fn update(&self, chunk: &[u8]) {
+        self.hasher.lock().unwrap().update(chunk);
+    }
+
+    /// Compare the digest of the bytes seen so far against the expected hash.
+    pub fn validate(&self, distribution: &impl Display) -> Result<(), Error> {
+        let actual = self.hasher.lock().unwrap().clone().finalize();
+        if actual == self.expected {
+            Ok(())
+        } else {
+            Err(Error::HashMismatch {
+                distribution: distribution.to_string(),
+                algorithm: self.algorithm,
+                expected: self.expected.clone(),
+                actual,
+            })
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha384(hasher) => hasher.update(data),
+            Self::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Return the hex-encoded digest.
+    fn finalize(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha384(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha512(hasher) => format!("{:x}", hasher.finalize()),
+        }
+    }
+}
+
+impl From<HashAlgorithm> for Hasher {
+    fn from(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => Self::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+        }
+    }
+}
@@ -98,7 +98,7 @@ impl BuiltWheelIndex {
         let mut candidate: Option<CachedWheel> = None;
 
         // Unzipped wheels are stored as symlinks into the archive directory.
-        for subdir in symlinks(shard) {
+        for subdir in shard.read_dirs().flat_map(symlinks) {
             match CachedWheel::from_path(&subdir) {
                 None => {}
                 Some(dist_info) => {
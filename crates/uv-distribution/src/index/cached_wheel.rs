@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use distribution_filename::WheelFilename;
-use distribution_types::{CachedDirectUrlDist, CachedRegistryDist};
+use distribution_types::{CachedDirectUrlDist, CachedRegistryDist, IndexUrl};
 use pep508_rs::VerbatimUrl;
 use uv_cache::CacheEntry;
 
@@ -24,10 +24,14 @@ impl CachedWheel {
     }
 
     /// Convert a [`CachedWheel`] into a [`CachedRegistryDist`].
-    pub fn into_registry_dist(self) -> CachedRegistryDist {
+    ///
+    /// The `built` flag indicates whether the wheel was built locally from a source distribution.
+    pub fn into_registry_dist(self, index: IndexUrl, built: bool) -> CachedRegistryDist {
         CachedRegistryDist {
             filename: self.filename,
             path: self.entry.into_path_buf(),
+            index,
+            built,
         }
     }
 
@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use rustc_hash::FxHashMap;
@@ -94,7 +94,9 @@ impl<'a> RegistryWheelIndex<'a> {
                 WheelCache::Index(index_url).remote_wheel_dir(package.to_string()),
             );
 
-            Self::add_directory(&wheel_dir, tags, &mut versions);
+            for dir in wheel_dir.read_dirs() {
+                Self::add_directory(dir, tags, index_url, false, &mut versions);
+            }
 
             // Index all the built wheels, created by downloading and building source distributions
             // from the registry.
@@ -104,12 +106,19 @@ impl<'a> RegistryWheelIndex<'a> {
             );
 
             // For registry wheels, the cache structure is: `<index>/<package-name>/<version>/`.
-            for shard in directories(&cache_shard) {
+            let shards = cache_shard
+                .read_dirs()
+                .flat_map(directories)
+                .filter_map(|shard| shard.file_name().map(ToOwned::to_owned))
+                .collect::<BTreeSet<_>>();
+            for shard in shards {
                 // Read the existing metadata from the cache, if it exists.
                 let cache_shard = cache_shard.shard(shard);
                 let manifest_entry = cache_shard.entry(MANIFEST);
                 if let Ok(Some(manifest)) = read_http_manifest(&manifest_entry) {
-                    Self::add_directory(cache_shard.join(manifest.id()), tags, &mut versions);
+                    for dir in cache_shard.shard(manifest.id()).read_dirs() {
+                        Self::add_directory(dir, tags, index_url, true, &mut versions);
+                    }
                 };
             }
         }
@@ -119,10 +128,13 @@ impl<'a> RegistryWheelIndex<'a> {
 
     /// Add the wheels in a given directory to the index.
     ///
-    /// Each subdirectory in the given path is expected to be that of an unzipped wheel.
+    /// Each subdirectory in the given path is expected to be that of an unzipped wheel. The `built`
+    /// flag indicates whether the wheels were built locally from source distributions.
     fn add_directory(
         path: impl AsRef<Path>,
         tags: &Tags,
+        index_url: &IndexUrl,
+        built: bool,
         versions: &mut BTreeMap<Version, CachedRegistryDist>,
     ) {
         // Unzipped wheels are stored as symlinks into the archive directory.
@@ -130,7 +142,7 @@ impl<'a> RegistryWheelIndex<'a> {
             match CachedWheel::from_path(&wheel_dir) {
                 None => {}
                 Some(dist_info) => {
-                    let dist_info = dist_info.into_registry_dist();
+                    let dist_info = dist_info.into_registry_dist(index_url.clone(), built);
 
                     // Pick the wheel with the highest priority
                     let compatibility = dist_info.filename.compatibility(tags);
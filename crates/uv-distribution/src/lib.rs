@@ -1,6 +1,7 @@
 pub use distribution_database::DistributionDatabase;
 pub use download::{BuiltWheel, DiskWheel, LocalWheel};
 pub use error::Error;
+pub use hash::HashValidator;
 pub use index::{BuiltWheelIndex, RegistryWheelIndex};
 pub use reporter::Reporter;
 pub use source::SourceDistCachedBuilder;
@@ -9,6 +10,7 @@ pub use unzip::Unzip;
 mod distribution_database;
 mod download;
 mod error;
+mod hash;
 mod index;
 mod locks;
 mod reporter;
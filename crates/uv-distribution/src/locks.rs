@@ -4,6 +4,10 @@ use rustc_hash::FxHashMap;
 use tokio::sync::Mutex;
 
 use distribution_types::{Identifier, ResourceId};
+use uv_cache::{Cache, CacheEntry, CacheShard};
+use uv_fs::LockedFile;
+
+use crate::Error;
 
 /// A set of locks used to prevent concurrent access to the same resource.
 #[derive(Debug, Default)]
@@ -18,3 +22,28 @@ impl Locks {
             .clone()
     }
 }
+
+/// Acquire a cross-process lock on a cache shard, to prevent concurrent access from other
+/// processes.
+///
+/// Waiting on the lock blocks, so it's acquired on a blocking thread to avoid stalling the runtime.
+/// Callers are expected to hold the corresponding in-process lock (via [`Locks`]) first.
+pub(crate) async fn lock_shard(cache: &Cache, shard: &CacheShard) -> Result<LockedFile, Error> {
+    let cache = cache.clone();
+    let shard = shard.clone();
+    tokio::task::spawn_blocking(move || cache.lock_shard(&shard))
+        .await?
+        .map_err(Error::CacheWrite)
+}
+
+/// Acquire a cross-process lock on a cache entry, to prevent concurrent access from other
+/// processes.
+///
+/// See [`lock_shard`].
+pub(crate) async fn lock_entry(cache: &Cache, entry: &CacheEntry) -> Result<LockedFile, Error> {
+    let cache = cache.clone();
+    let entry = entry.clone();
+    tokio::task::spawn_blocking(move || cache.lock_entry(&entry))
+        .await?
+        .map_err(Error::CacheWrite)
+}
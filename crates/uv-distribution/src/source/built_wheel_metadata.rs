@@ -19,8 +19,11 @@ pub struct BuiltWheelMetadata {
 
 impl BuiltWheelMetadata {
     /// Find a compatible wheel in the cache based on the given manifest.
+    ///
+    /// Wheels may be read from the read-only cache, but are always unzipped into the writable
+    /// cache.
     pub(crate) fn find_in_cache(tags: &Tags, cache_shard: &CacheShard) -> Option<Self> {
-        for directory in files(cache_shard) {
+        for directory in cache_shard.read_dirs().flat_map(files) {
             if let Some(metadata) = Self::from_path(directory, cache_shard) {
                 // Validate that the wheel is compatible with the target platform.
                 if metadata.filename.is_compatible(tags) {
@@ -8,8 +8,9 @@ use anyhow::Result;
 use fs_err::tokio as fs;
 use futures::{FutureExt, TryStreamExt};
 use reqwest::Response;
+use serde::de::DeserializeOwned;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{debug, info_span, instrument, Instrument};
+use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
 use zip::ZipArchive;
 
@@ -33,13 +34,17 @@ use uv_git::{Fetch, GitSource};
 use uv_traits::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
 
 use crate::error::Error;
+use crate::hash::HashValidator;
+use crate::locks::lock_shard;
 use crate::reporter::Facade;
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
 use crate::source::manifest::Manifest;
+use crate::source::remote::RemoteBuildCache;
 use crate::Reporter;
 
 mod built_wheel_metadata;
 mod manifest;
+mod remote;
 
 /// Fetch and build a source distribution from a remote source, or from a local cache.
 pub struct SourceDistCachedBuilder<'a, T: BuildContext> {
@@ -55,6 +60,9 @@ pub(crate) const MANIFEST: &str = "manifest.msgpack";
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
+/// The number of commits for which to retain built wheels, per Git repository URL.
+const GIT_BUILD_RETENTION: usize = 3;
+
 impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     /// Initialize a [`SourceDistCachedBuilder`] from a [`BuildContext`].
     pub fn new(build_context: &'a T, client: &'a RegistryClient, tags: &'a Tags) -> Self {
@@ -252,6 +260,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         cache_shard: &CacheShard,
         subdirectory: Option<&'data Path>,
     ) -> Result<BuiltWheelMetadata, Error> {
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), cache_shard).await?;
+
         let cache_entry = cache_shard.entry(MANIFEST);
         let cache_control = match self.client.connectivity() {
             Connectivity::Online => CacheControl::from(
@@ -298,8 +309,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
-        // manifest itself. There's also no need to lock, since we never replace entries within the
-        // shard.
+        // manifest itself. The lock on the parent shard also covers the manifest shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
         // If the cache contains a compatible wheel, return it.
@@ -314,13 +324,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // Build the source distribution.
         let source_dist_entry = cache_shard.entry(filename);
+        let source_dist_path = self.writable_source_dist(&source_dist_entry).await?;
         let (disk_filename, wheel_filename, metadata) = self
-            .build_source_dist(
-                source_dist,
-                source_dist_entry.path(),
-                subdirectory,
-                &cache_shard,
-            )
+            .build_source_dist(source_dist, source_dist_path, subdirectory, &cache_shard)
             .await?;
 
         if let Some(task) = task {
@@ -355,6 +361,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         cache_shard: &CacheShard,
         subdirectory: Option<&'data Path>,
     ) -> Result<Metadata21, Error> {
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), cache_shard).await?;
+
         let cache_entry = cache_shard.entry(MANIFEST);
         let cache_control = match self.client.connectivity() {
             Connectivity::Online => CacheControl::from(
@@ -401,8 +410,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
-        // manifest itself. There's also no need to lock, since we never replace entries within the
-        // shard.
+        // manifest itself. The lock on the parent shard also covers the manifest shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
         // If the cache contains compatible metadata, return it.
@@ -414,10 +422,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // Otherwise, we either need to build the metadata or the wheel.
         let source_dist_entry = cache_shard.entry(filename);
+        let source_dist_path = self.writable_source_dist(&source_dist_entry).await?;
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
-            .build_source_dist_metadata(source_dist, source_dist_entry.path(), subdirectory)
+            .build_source_dist_metadata(source_dist, source_dist_path, subdirectory)
             .boxed()
             .await?
         {
@@ -440,12 +449,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // Build the source distribution.
         let (_disk_filename, _wheel_filename, metadata) = self
-            .build_source_dist(
-                source_dist,
-                source_dist_entry.path(),
-                subdirectory,
-                &cache_shard,
-            )
+            .build_source_dist(source_dist, source_dist_path, subdirectory, &cache_shard)
             .await?;
 
         // Store the metadata.
@@ -475,6 +479,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), &cache_shard).await?;
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
             ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
@@ -494,8 +501,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
-        // manifest itself. There's also no need to lock, since we never replace entries within the
-        // shard.
+        // manifest itself. The lock on the parent shard also covers the manifest shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
         // If the cache contains a compatible wheel, return it.
@@ -547,6 +553,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), &cache_shard).await?;
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
             ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
@@ -566,8 +575,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
 
         // From here on, scope all operations to the current build. Within the manifest shard,
         // there's no need to check for freshness, since entries have to be fresher than the
-        // manifest itself. There's also no need to lock, since we never replace entries within the
-        // shard.
+        // manifest itself. The lock on the parent shard also covers the manifest shard.
         let cache_shard = cache_shard.shard(manifest.id());
 
         // If the cache contains compatible metadata, return it.
@@ -642,6 +650,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
 
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), &cache_shard).await?;
+
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
             return Ok(built_wheel);
@@ -673,6 +684,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .await
             .map_err(Error::CacheWrite)?;
 
+        self.prune_git_builds(&git_source_dist.url, &git_sha.to_short_string())
+            .await;
+
         Ok(BuiltWheelMetadata {
             path: cache_shard.join(&disk_filename),
             target: cache_shard.join(filename.stem()),
@@ -698,6 +712,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
 
+        // Avoid races with other processes building the same source distribution.
+        let _lock = lock_shard(self.build_context.cache(), &cache_shard).await?;
+
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
         if self
@@ -727,6 +744,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .await
                 .map_err(Error::CacheWrite)?;
 
+            self.prune_git_builds(&git_source_dist.url, &git_sha.to_short_string())
+                .await;
+
             return Ok(metadata);
         }
 
@@ -757,9 +777,38 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .await
             .map_err(Error::CacheWrite)?;
 
+        self.prune_git_builds(&git_source_dist.url, &git_sha.to_short_string())
+            .await;
+
         Ok(metadata)
     }
 
+    /// Remove the builds for superseded commits of a Git repository, retaining the builds for up to
+    /// [`GIT_BUILD_RETENTION`] commits (including the current commit).
+    ///
+    /// Failures are logged rather than propagated, since they don't affect the current build.
+    async fn prune_git_builds(&self, url: &Url, git_sha: &str) {
+        let cache = self.build_context.cache().clone();
+        let url = url.clone();
+        let git_sha = git_sha.to_string();
+        match tokio::task::spawn_blocking(move || {
+            cache.prune_git_builds(&url, &git_sha, GIT_BUILD_RETENTION)
+        })
+        .await
+        {
+            Ok(Ok(removal)) => {
+                if removal.num_dirs > 0 {
+                    debug!(
+                        "Removed {} files from superseded Git builds",
+                        removal.num_files
+                    );
+                }
+            }
+            Ok(Err(err)) => warn!("Failed to prune superseded Git builds: {err}"),
+            Err(err) => warn!("Failed to prune superseded Git builds: {err}"),
+        }
+    }
+
     /// Download and unzip a source distribution into the cache from an HTTP response.
     async fn persist_source_dist_url<'data>(
         &self,
@@ -779,11 +828,31 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             info_span!("download_source_dist", filename = filename, source_dist = %source_dist);
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
+        let validator = match source_dist {
+            SourceDist::Registry(registry_source_dist) => {
+                HashValidator::new(&registry_source_dist.file.hashes)
+            }
+            _ => None,
+        };
         let reader = response
             .bytes_stream()
+            .inspect_ok(|chunk| {
+                if let Some(validator) = &validator {
+                    validator.update(chunk);
+                }
+            })
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .into_async_read();
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+        let mut reader = reader.compat();
+        uv_extract::stream::archive(&mut reader, filename, temp_dir.path()).await?;
+
+        // Validate the download against the hash advertised by the index.
+        if let Some(validator) = &validator {
+            tokio::io::copy(&mut reader, &mut tokio::io::sink())
+                .await
+                .map_err(Error::Download)?;
+            validator.validate(source_dist)?;
+        }
         drop(span);
 
         // Extract the top-level directory.
@@ -800,6 +869,42 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         Ok(cache_path)
     }
 
+    /// Return the path to an unzipped source distribution in the writable cache, copying it out of
+    /// the read-only cache if necessary.
+    ///
+    /// Builds can write to the source tree (e.g., to generate an `.egg-info` directory), so source
+    /// distributions are never built in place in the read-only cache.
+    async fn writable_source_dist<'data>(
+        &self,
+        source_dist_entry: &'data CacheEntry,
+    ) -> Result<&'data Path, Error> {
+        if !source_dist_entry.is_readonly() {
+            return Ok(source_dist_entry.path());
+        }
+
+        debug!(
+            "Copying source distribution out of the read-only cache: {}",
+            source_dist_entry.read_path().display()
+        );
+        let temp_dir =
+            tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
+        let source = source_dist_entry.read_path().to_path_buf();
+        let target = temp_dir.path().to_path_buf();
+        tokio::task::spawn_blocking(move || copy_dir(&source, &target))
+            .await?
+            .map_err(Error::CacheWrite)?;
+
+        // Persist it to the cache.
+        fs_err::tokio::create_dir_all(source_dist_entry.dir())
+            .await
+            .map_err(Error::CacheWrite)?;
+        fs_err::tokio::rename(temp_dir.into_path(), source_dist_entry.path())
+            .await
+            .map_err(Error::CacheWrite)?;
+
+        Ok(source_dist_entry.path())
+    }
+
     /// Download a source distribution from a Git repository.
     async fn download_source_dist_git(&self, url: &Url) -> Result<(Fetch, Option<PathBuf>), Error> {
         debug!("Fetching source distribution from Git: {url}");
@@ -853,11 +958,30 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             return Err(Error::NoBuild);
         }
 
-        // Build the wheel.
         fs::create_dir_all(&cache_shard)
             .await
             .map_err(Error::CacheWrite)?;
-        let disk_filename = self
+
+        // If an identical build exists in the remote build cache, use it.
+        let remote = self
+            .remote_build_cache(dist, source_dist, subdirectory)
+            .await;
+        if let Some((remote, key)) = &remote {
+            match remote.get(dist, key, cache_shard).await {
+                Ok(Some(disk_filename)) => {
+                    debug!("Using remote build for: {dist}");
+                    let filename = WheelFilename::from_str(&disk_filename)?;
+                    let metadata =
+                        read_wheel_metadata(&filename, cache_shard.join(&disk_filename))?;
+                    return Ok((disk_filename, filename, metadata));
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Failed to read from the remote build cache: {err}"),
+            }
+        }
+
+        // Build the wheel.
+        let builder = self
             .build_context
             .setup_build(
                 source_dist,
@@ -867,7 +991,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 BuildKind::Wheel,
             )
             .await
-            .map_err(|err| Error::Build(dist.to_string(), err))?
+            .map_err(|err| Error::Build(dist.to_string(), err))?;
+        let _permit = self.build_context.build_semaphore().acquire().await;
+        let disk_filename = builder
             .wheel(cache_shard)
             .await
             .map_err(|err| Error::Build(dist.to_string(), err))?;
@@ -885,9 +1011,58 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         }
 
         debug!("Finished building: {dist}");
+
+        // Share the build via the remote build cache, if uploads are enabled.
+        if let Some((remote, key)) = &remote {
+            if self.build_context.cache().remote_build_cache_upload() {
+                if let Err(err) = remote.put(dist, key, cache_shard, &disk_filename).await {
+                    warn!("Failed to upload to the remote build cache: {err}");
+                }
+            }
+        }
+
         Ok((disk_filename, filename, metadata))
     }
 
+    /// Return the remote build cache, along with the key for building the given source
+    /// distribution, if a remote build cache is enabled.
+    async fn remote_build_cache(
+        &self,
+        dist: &SourceDist,
+        source_dist: &Path,
+        subdirectory: Option<&Path>,
+    ) -> Option<(RemoteBuildCache<'_>, String)> {
+        let url = self.build_context.cache().remote_build_cache()?;
+        if matches!(self.client.connectivity(), Connectivity::Offline) {
+            return None;
+        }
+
+        // Local source trees change frequently and often contain stray build artifacts, so they're
+        // never shared.
+        if let SourceDist::Path(path_source_dist) = dist {
+            if path_source_dist.path.is_dir() {
+                return None;
+            }
+        }
+
+        match RemoteBuildCache::key(
+            source_dist,
+            subdirectory,
+            self.tags,
+            self.build_context.setup_py_strategy(),
+            self.build_context.no_build(),
+            self.build_context.no_binary(),
+        )
+        .await
+        {
+            Ok(key) => Some((RemoteBuildCache::new(self.client, url, self.tags), key)),
+            Err(err) => {
+                warn!("Failed to compute the remote build cache key for {dist}: {err}");
+                None
+            }
+        }
+    }
+
     /// Build the metadata for a source distribution.
     #[instrument(skip_all, fields(dist))]
     async fn build_source_dist_metadata(
@@ -896,6 +1071,20 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         source_dist: &Path,
         subdirectory: Option<&Path>,
     ) -> Result<Option<Metadata21>, Error> {
+        // Attempt to read static metadata from the source distribution, to avoid invoking the
+        // build backend entirely.
+        if let Some(metadata) = read_static_metadata(dist, source_dist, subdirectory).await? {
+            // Validate the metadata.
+            if &metadata.name != dist.name() {
+                return Err(Error::NameMismatch {
+                    metadata: metadata.name,
+                    given: dist.name().clone(),
+                });
+            }
+
+            return Ok(Some(metadata));
+        }
+
         debug!("Preparing metadata for: {dist}");
 
         // Setup the builder.
@@ -912,11 +1101,14 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .map_err(|err| Error::Build(dist.to_string(), err))?;
 
         // Build the metadata.
+        let permit = self.build_context.build_semaphore().acquire().await;
         let dist_info = builder
             .metadata()
             .await
             .map_err(|err| Error::Build(dist.to_string(), err))?;
+        drop(permit);
         let Some(dist_info) = dist_info else {
+            debug!("Build backend does not support `prepare_metadata_for_build_wheel`; building the wheel to determine metadata for: {dist}");
             return Ok(None);
         };
 
@@ -945,7 +1137,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         editable_wheel_dir: &Path,
     ) -> Result<(Dist, String, WheelFilename, Metadata21), Error> {
         debug!("Building (editable) {editable}");
-        let disk_filename = self
+        let builder = self
             .build_context
             .setup_build(
                 &editable.path,
@@ -955,7 +1147,9 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 BuildKind::Editable,
             )
             .await
-            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?
+            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
+        let _permit = self.build_context.build_semaphore().acquire().await;
+        let disk_filename = builder
             .wheel(editable_wheel_dir)
             .await
             .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
@@ -974,12 +1168,64 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     }
 }
 
+/// Read the static metadata for a source distribution, if it can be determined without a build.
+///
+/// Uses the `PKG-INFO` file, if its metadata version is 2.2 or later and the dependencies aren't
+/// marked as dynamic (PEP 643). Otherwise, uses the `[project]` table in `pyproject.toml`, if the
+/// version and dependencies aren't marked as dynamic (PEP 621).
+async fn read_static_metadata(
+    dist: &SourceDist,
+    source_dist: &Path,
+    subdirectory: Option<&Path>,
+) -> Result<Option<Metadata21>, Error> {
+    let source_tree = if let Some(subdirectory) = subdirectory {
+        source_dist.join(subdirectory)
+    } else {
+        source_dist.to_path_buf()
+    };
+
+    // Local source distributions may be archives, which need to be unpacked by the builder.
+    if !source_tree.is_dir() {
+        return Ok(None);
+    }
+
+    match fs::read(source_tree.join("PKG-INFO")).await {
+        Ok(content) => match Metadata21::parse_pkg_info(&content) {
+            Ok(metadata) => {
+                debug!("Found static `PKG-INFO` for: {dist}");
+                return Ok(Some(metadata));
+            }
+            Err(err) => {
+                debug!("Unable to use static `PKG-INFO` for {dist}: {err}");
+            }
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(Error::CacheRead(err)),
+    }
+
+    match fs::read_to_string(source_tree.join("pyproject.toml")).await {
+        Ok(content) => match Metadata21::parse_pyproject_toml(&content) {
+            Ok(metadata) => {
+                debug!("Found static `pyproject.toml` for: {dist}");
+                return Ok(Some(metadata));
+            }
+            Err(err) => {
+                debug!("Unable to use static `pyproject.toml` for {dist}: {err}");
+            }
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(Error::CacheRead(err)),
+    }
+
+    Ok(None)
+}
+
 /// Read an existing HTTP-cached [`Manifest`], if it exists.
 pub(crate) fn read_http_manifest(cache_entry: &CacheEntry) -> Result<Option<Manifest>, Error> {
-    match std::fs::File::open(cache_entry.path()) {
+    match std::fs::File::open(cache_entry.read_path()) {
         Ok(file) => {
             let data = DataWithCachePolicy::from_reader(file)?.data;
-            Ok(Some(rmp_serde::from_slice::<Manifest>(&data)?))
+            Ok(decode_or_warn(cache_entry, &data))
         }
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
         Err(err) => Err(Error::CacheRead(err)),
@@ -994,11 +1240,14 @@ pub(crate) fn read_timestamp_manifest(
     modified: ArchiveTimestamp,
 ) -> Result<Option<Manifest>, Error> {
     // If the cache entry is up-to-date, return it.
-    match std::fs::read(cache_entry.path()) {
+    match std::fs::read(cache_entry.read_path()) {
         Ok(cached) => {
-            let cached = rmp_serde::from_slice::<CachedByTimestamp<Manifest>>(&cached)?;
-            if cached.timestamp == modified.timestamp() {
-                return Ok(Some(cached.data));
+            if let Some(cached) =
+                decode_or_warn::<CachedByTimestamp<Manifest>>(cache_entry, &cached)
+            {
+                if cached.timestamp == modified.timestamp() {
+                    return Ok(Some(cached.data));
+                }
             }
         }
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
@@ -1043,13 +1292,28 @@ pub(crate) async fn refresh_timestamp_manifest(
 pub(crate) async fn read_cached_metadata(
     cache_entry: &CacheEntry,
 ) -> Result<Option<Metadata21>, Error> {
-    match fs::read(&cache_entry.path()).await {
-        Ok(cached) => Ok(Some(rmp_serde::from_slice::<Metadata21>(&cached)?)),
+    match fs::read(cache_entry.read_path()).await {
+        Ok(cached) => Ok(decode_or_warn(cache_entry, &cached)),
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
         Err(err) => Err(Error::CacheRead(err)),
     }
 }
 
+/// Deserialize a cache entry, treating an entry that can't be deserialized (e.g., one written in an
+/// incompatible format by a different version of uv) as missing, such that it's overwritten.
+fn decode_or_warn<T: DeserializeOwned>(cache_entry: &CacheEntry, data: &[u8]) -> Option<T> {
+    match rmp_serde::from_slice::<T>(data) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            warn!(
+                "Broken cache entry at {}, ignoring: {err}",
+                cache_entry.read_path().display()
+            );
+            None
+        }
+    }
+}
+
 /// Read the [`Metadata21`] from a built wheel.
 fn read_wheel_metadata(
     filename: &WheelFilename,
@@ -1061,3 +1325,23 @@ fn read_wheel_metadata(
     let dist_info = read_dist_info(filename, &mut archive)?;
     Ok(Metadata21::parse(&dist_info)?)
 }
+
+/// Recursively copy the contents of the `source` directory into the `target` directory.
+fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir starts with root");
+        let path = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs_err::create_dir_all(&path)?;
+        } else if entry.file_type().is_symlink() {
+            uv_fs::replace_symlink(fs_err::read_link(entry.path())?, &path)?;
+        } else {
+            fs_err::copy(entry.path(), &path)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+//! A remote build cache, to share wheels built from source distributions across machines.
+//!
+//! The remote cache is a plain HTTP server (or S3-compatible bucket) that supports `GET` and `PUT`.
+//! Each build is stored under a key derived from the contents of the source distribution, the
+//! compatible tags of the target interpreter, and the build settings:
+//!
+//! * `{base}/{key}.txt`: the filename of the built wheel, followed by its SHA-256 digest on the
+//!   next line.
+//! * `{base}/{key}/{filename}`: the built wheel itself.
+//!
+//! The filename is uploaded last, such that readers never observe a partial entry. Before a
+//! downloaded wheel is used, its tags are checked against the target interpreter, and its contents
+//! are checked against the recorded digest.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use fs_err::tokio as fs;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use url::Url;
+use walkdir::WalkDir;
+
+use distribution_filename::WheelFilename;
+use distribution_types::{Name, SourceDist};
+use platform_tags::Tags;
+use pypi_types::HashAlgorithm;
+use uv_client::RegistryClient;
+use uv_fs::write_atomic;
+use uv_traits::{NoBinary, NoBuild, SetupPyStrategy};
+
+use crate::error::Error;
+
+/// The version of the remote cache key format, to be bumped when the key inputs change.
+const KEY_VERSION: &str = "v2";
+
+/// A remote cache of wheels built from source distributions.
+pub(crate) struct RemoteBuildCache<'a> {
+    client: &'a RegistryClient,
+    url: &'a Url,
+    tags: &'a Tags,
+}
+
+impl<'a> RemoteBuildCache<'a> {
+    pub(crate) fn new(client: &'a RegistryClient, url: &'a Url, tags: &'a Tags) -> Self {
+        Self { client, url, tags }
+    }
+
+    /// Compute the cache key for a build of the given source tree.
+    ///
+    /// The key covers the contents of every file in the source tree (rather than, e.g., its URL),
+    /// such that identical source distributions share a key regardless of where they were
+    /// downloaded from, along with every input to the build: the compatible tags of the target
+    /// interpreter (which capture its implementation, version, ABI, and platform, including the
+    /// glibc or musl version), and the build settings.
+    pub(crate) async fn key(
+        source_tree: &Path,
+        subdirectory: Option<&Path>,
+        tags: &Tags,
+        setup_py: SetupPyStrategy,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
+    ) -> Result<String, Error> {
+        let source_tree = source_tree.to_path_buf();
+        let settings = cache_key::digest(&(
+            KEY_VERSION,
+            tags.iter()
+                .map(|(python, abi, platform)| format!("{python}-{abi}-{platform}"))
+                .collect::<Vec<_>>(),
+            setup_py,
+            no_build,
+            no_binary,
+            subdirectory,
+        ));
+        tokio::task::spawn_blocking(move || {
+            let mut hasher = Sha256::new();
+            hasher.update(settings.as_bytes());
+            hasher.update([0]);
+
+            // Hash the source tree, in a stable order.
+            for entry in WalkDir::new(&source_tree).sort_by_file_name() {
+                let entry = entry.map_err(|err| Error::CacheRead(err.into()))?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let relative = entry
+                    .path()
+                    .strip_prefix(&source_tree)
+                    .expect("walked from source tree");
+                hasher.update(relative.to_string_lossy().as_bytes());
+                hasher.update([0]);
+                hasher.update(fs_err::read(entry.path()).map_err(Error::CacheRead)?);
+                hasher.update([0]);
+            }
+
+            Ok(format!("{:x}", hasher.finalize()))
+        })
+        .await?
+    }
+
+    /// Download the wheel for the given key into `wheel_dir`, returning its filename, or `None` if
+    /// the remote cache doesn't contain a build for the key.
+    ///
+    /// The wheel is only written to `wheel_dir` if it's compatible with the target interpreter and
+    /// matches the digest recorded alongside it.
+    pub(crate) async fn get(
+        &self,
+        dist: &SourceDist,
+        key: &str,
+        wheel_dir: &Path,
+    ) -> Result<Option<String>, Error> {
+        let client = self.client.cached_client().uncached();
+
+        let url = self.join(&format!("{key}.txt"))?;
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(request_error)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            debug!("No remote build for {dist} at: {url}");
+            return Ok(None);
+        }
+        let entry = response.error_for_status()?.text().await?;
+        let mut lines = entry.lines().map(str::trim);
+        let (Some(filename), Some(expected)) = (lines.next(), lines.next()) else {
+            return Err(Error::RemoteBuildEntry(url.to_string()));
+        };
+
+        // Validate the filename before downloading the wheel.
+        let parsed = WheelFilename::from_str(filename)?;
+        if &parsed.name != dist.name() {
+            return Err(Error::NameMismatch {
+                metadata: parsed.name,
+                given: dist.name().clone(),
+            });
+        }
+        if !parsed.is_compatible(self.tags) {
+            return Err(Error::IncompatibleRemoteBuild(filename.to_string()));
+        }
+
+        let url = self.join(&format!("{key}/{filename}"))?;
+        debug!("Downloading remote build for {dist} from: {url}");
+        let wheel = client
+            .get(url)
+            .send()
+            .await
+            .map_err(request_error)?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        // Validate the contents before writing to the cache.
+        let actual = format!("{:x}", Sha256::digest(&wheel));
+        if actual != expected {
+            return Err(Error::HashMismatch {
+                distribution: filename.to_string(),
+                algorithm: HashAlgorithm::Sha256,
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+
+        write_atomic(wheel_dir.join(filename), wheel)
+            .await
+            .map_err(Error::CacheWrite)?;
+
+        Ok(Some(filename.to_string()))
+    }
+
+    /// Upload the built wheel at `wheel_dir/filename` to the remote cache under the given key.
+    pub(crate) async fn put(
+        &self,
+        dist: &SourceDist,
+        key: &str,
+        wheel_dir: &Path,
+        filename: &str,
+    ) -> Result<(), Error> {
+        let client = self.client.cached_client().uncached();
+
+        let wheel = fs::read(wheel_dir.join(filename))
+            .await
+            .map_err(Error::CacheRead)?;
+        let digest = format!("{:x}", Sha256::digest(&wheel));
+        let url = self.join(&format!("{key}/{filename}"))?;
+        debug!("Uploading build for {dist} to: {url}");
+        client
+            .put(url)
+            .body(wheel)
+            .send()
+            .await
+            .map_err(request_error)?
+            .error_for_status()?;
+
+        client
+            .put(self.join(&format!("{key}.txt"))?)
+            .body(format!("{filename}\n{digest}\n"))
+            .send()
+            .await
+            .map_err(request_error)?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Join a path onto the base URL of the remote cache.
+    fn join(&self, path: &str) -> Result<Url, Error> {
+        let url = format!("{}/{path}", self.url.as_str().trim_end_matches('/'));
+        Url::parse(&url).map_err(|err| Error::Url(url, err))
+    }
+}
+
+/// Convert a failed request to the remote cache into an [`Error`].
+fn request_error(err: impl Into<uv_client::ErrorKind>) -> Error {
+    Error::Client(err.into().into())
+}
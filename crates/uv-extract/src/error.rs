@@ -16,4 +16,38 @@ pub enum Error {
         "The top level of the archive must only contain a list directory, but it contains: {0:?}"
     )]
     InvalidArchive(Vec<OsString>),
+    #[error(
+        "The archive contains a symlink, which is not supported on this platform: {}",
+        .0.display()
+    )]
+    UnsupportedSymlink(PathBuf),
+    #[error(
+        "The archive contains a symlink that points outside of the archive: {} -> {}",
+        link.display(),
+        target.display()
+    )]
+    ExternalSymlink { link: PathBuf, target: PathBuf },
+    #[error(
+        "The archive contains a hard link that points outside of the archive: {} -> {}",
+        link.display(),
+        target.display()
+    )]
+    ExternalHardLink { link: PathBuf, target: PathBuf },
+    #[error("The archive contains an entry with a path outside of the archive: `{0}`")]
+    UnsafePath(String),
+    #[error("The archive contains more than {limit} entries, at: `{entry}` (set `UV_EXTRACT_MAX_ENTRIES` to raise the limit)")]
+    TooManyEntries { entry: String, limit: u64 },
+    #[error("The archive expands to more than {limit} bytes, at: `{entry}` (set `UV_EXTRACT_MAX_SIZE` to raise the limit)")]
+    TooLarge { entry: String, limit: u64 },
+}
+
+impl Error {
+    /// Returns `true` if the error is due to the archive being incompatible with streaming
+    /// extraction (e.g., a stored entry with a data descriptor, whose size isn't known up-front).
+    pub fn is_http_streaming_unsupported(&self) -> bool {
+        matches!(
+            self,
+            Self::AsyncZip(async_zip::error::ZipError::FeatureNotSupported(_))
+        )
+    }
 }
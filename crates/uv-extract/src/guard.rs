@@ -0,0 +1,151 @@
+//! Guards against malicious archives (e.g., path traversal or decompression bombs), as we
+//! extract third-party source distributions and wheels.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use crate::Error;
+
+/// The default maximum number of entries in an archive.
+const DEFAULT_MAX_ENTRIES: u64 = 500_000;
+
+/// The default maximum total size of the files extracted from an archive, in bytes (16 GiB).
+const DEFAULT_MAX_SIZE: u64 = 16 * 1024 * 1024 * 1024;
+
+/// Resolve the name of an archive entry to a relative path within the extraction directory.
+///
+/// Returns `None` if the name is absolute, contains a NUL byte, or would resolve outside of the
+/// extraction directory (e.g., `../../etc/passwd`).
+pub(crate) fn enclosed_name(name: &str) -> Option<PathBuf> {
+    if name.contains('\0') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    let mut depth = 0usize;
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(component) => {
+                depth += 1;
+                path.push(component);
+            }
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => {
+                depth -= 1;
+                path.pop();
+            }
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
+
+/// The limits on the number of entries and the total size of an archive.
+#[derive(Debug, Copy, Clone)]
+pub struct Limits {
+    /// The maximum number of entries.
+    pub max_entries: u64,
+    /// The maximum total size of the extracted files, in bytes.
+    pub max_size: u64,
+}
+
+impl Limits {
+    /// Read the limits from the environment (`UV_EXTRACT_MAX_ENTRIES` and `UV_EXTRACT_MAX_SIZE`),
+    /// falling back to the defaults.
+    ///
+    /// The environment is only read once per process.
+    pub fn from_env() -> Self {
+        static LIMITS: OnceLock<Limits> = OnceLock::new();
+        *LIMITS.get_or_init(|| {
+            let var = |name: &str| {
+                std::env::var(name)
+                    .ok()
+                    .and_then(|value| value.parse::<u64>().ok())
+            };
+            Self {
+                max_entries: var("UV_EXTRACT_MAX_ENTRIES").unwrap_or(DEFAULT_MAX_ENTRIES),
+                max_size: var("UV_EXTRACT_MAX_SIZE").unwrap_or(DEFAULT_MAX_SIZE),
+            }
+        })
+    }
+}
+
+/// Tracks the number of entries and bytes extracted from an archive, failing once either exceeds
+/// its limit.
+///
+/// The budget is shared across threads when extracting entries in parallel.
+#[derive(Debug)]
+pub(crate) struct Budget {
+    limits: Limits,
+    entries: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            entries: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Record an entry, failing if the archive has too many entries.
+    pub(crate) fn entry(&self, name: &str) -> Result<(), Error> {
+        let entries = self.entries.fetch_add(1, Ordering::Relaxed) + 1;
+        if entries > self.limits.max_entries {
+            return Err(Error::TooManyEntries {
+                entry: name.to_string(),
+                limit: self.limits.max_entries,
+            });
+        }
+        Ok(())
+    }
+
+    /// Return the number of bytes to read (at most) from the next entry.
+    ///
+    /// This is one byte more than may still be extracted, such that reading the entry and passing
+    /// the number of bytes read to [`Budget::bytes`] catches an entry whose declared size
+    /// understates its contents, without reading the entry in full.
+    pub(crate) fn read_limit(&self) -> u64 {
+        self.limits
+            .max_size
+            .saturating_sub(self.bytes.load(Ordering::Relaxed))
+            .saturating_add(1)
+    }
+
+    /// Record the bytes extracted for an entry, failing if the archive is too large.
+    pub(crate) fn bytes(&self, name: &str, bytes: u64) -> Result<(), Error> {
+        let total = self.bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if total > self.limits.max_size {
+            return Err(Error::TooLarge {
+                entry: name.to_string(),
+                limit: self.limits.max_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::enclosed_name;
+
+    #[test]
+    fn enclosed() {
+        assert_eq!(
+            enclosed_name("foo-1.0/foo/__init__.py"),
+            Some(PathBuf::from("foo-1.0/foo/__init__.py"))
+        );
+        assert_eq!(
+            enclosed_name("./foo-1.0/../bar"),
+            Some(PathBuf::from("bar"))
+        );
+        assert_eq!(enclosed_name("../evil.py"), None);
+        assert_eq!(enclosed_name("foo-1.0/../../evil.py"), None);
+        assert_eq!(enclosed_name("/etc/passwd"), None);
+        assert_eq!(enclosed_name("foo\0bar"), None);
+    }
+}
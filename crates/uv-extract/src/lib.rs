@@ -1,7 +1,10 @@
 pub use error::Error;
+pub use guard::Limits;
 pub use sync::*;
 
 mod error;
+mod guard;
 pub mod stream;
+mod symlink;
 mod sync;
 mod vendor;
@@ -1,8 +1,12 @@
 use std::path::Path;
 
+use futures::StreamExt;
 use rustc_hash::FxHashSet;
+use tokio::io::AsyncReadExt;
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 
+use crate::guard::{enclosed_name, Budget, Limits};
+use crate::symlink::{create_symlink, is_symlink, Links};
 use crate::Error;
 
 /// Unzip a `.zip` archive into the target directory, without requiring `Seek`.
@@ -13,15 +17,30 @@ use crate::Error;
 pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
     reader: R,
     target: impl AsRef<Path>,
+) -> Result<(), Error> {
+    unzip_with_limits(reader, target, Limits::from_env()).await
+}
+
+/// Unzip a `.zip` archive into the target directory, without requiring `Seek`, with the given
+/// [`Limits`] rather than those configured in the environment.
+pub async fn unzip_with_limits<R: tokio::io::AsyncRead + Unpin>(
+    reader: R,
+    target: impl AsRef<Path>,
+    limits: Limits,
 ) -> Result<(), Error> {
     let mut reader = reader.compat();
     let mut zip = async_zip::base::read::stream::ZipFileReader::new(&mut reader);
 
     let mut directories = FxHashSet::default();
+    let budget = Budget::new(limits);
 
     while let Some(mut entry) = zip.next_with_entry().await? {
         // Construct the (expected) path to the file on-disk.
-        let path = entry.reader().entry().filename().as_str()?;
+        let name = entry.reader().entry().filename().as_str()?.to_string();
+        budget.entry(&name)?;
+        let Some(path) = enclosed_name(&name) else {
+            return Err(Error::UnsafePath(name));
+        };
         let path = target.as_ref().join(path);
         let is_dir = entry.reader().entry().dir()?;
 
@@ -44,8 +63,11 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
                 } else {
                     tokio::io::BufWriter::new(file)
                 };
-            let mut reader = entry.reader_mut().compat();
-            tokio::io::copy(&mut reader, &mut writer).await?;
+            // Copy the file contents, stopping once the archive exceeds the size limit (regardless
+            // of the size declared in the archive).
+            let mut reader = entry.reader_mut().compat().take(budget.read_limit());
+            let bytes = tokio::io::copy(&mut reader, &mut writer).await?;
+            budget.bytes(&name, bytes)?;
         }
 
         // Close current file to get access to the next one. See docs:
@@ -53,48 +75,109 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
         zip = entry.skip().await?;
     }
 
-    // On Unix, we need to set file permissions, which are stored in the central directory, at the
-    // end of the archive. The `ZipFileReader` reads until it sees a central directory signature,
-    // which indicates the first entry in the central directory. So we continue reading from there.
-    #[cfg(unix)]
-    {
-        use std::fs::Permissions;
-        use std::os::unix::fs::PermissionsExt;
-
-        // To avoid lots of small reads to `reader` when parsing the central directory, wrap it in
-        // a buffer.
-        let mut buf = futures::io::BufReader::new(reader);
-        let mut directory = async_zip::base::read::cd::CentralDirectoryReader::new(&mut buf);
-        while let Some(entry) = directory.next().await? {
-            if entry.dir()? {
-                continue;
-            }
+    // Symlinks and (on Unix) file permissions are stored in the central directory, at the end of
+    // the archive. The `ZipFileReader` reads until it sees a central directory signature, which
+    // indicates the first entry in the central directory. So we continue reading from there.
+    //
+    // To avoid lots of small reads to `reader` when parsing the central directory, wrap it in a
+    // buffer.
+    let mut buf = futures::io::BufReader::new(reader);
+    let mut directory = async_zip::base::read::cd::CentralDirectoryReader::new(&mut buf);
+    let mut symlinks = Vec::new();
+    while let Some(entry) = directory.next().await? {
+        if entry.dir()? {
+            continue;
+        }
+
+        // Construct the (expected) path to the file on-disk.
+        let name = entry.filename().as_str()?;
+        let Some(path) = enclosed_name(name) else {
+            return Err(Error::UnsafePath(name.to_string()));
+        };
+        let path = target.as_ref().join(path);
 
-            // Construct the (expected) path to the file on-disk.
-            let path = entry.filename().as_str()?;
-            let path = target.as_ref().join(path);
+        let Some(mode) = entry.unix_permissions().map(u32::from) else {
+            continue;
+        };
+
+        // The entry was written out as a file containing the path to the link target; replace it
+        // with the symlink itself, once every other entry is in place.
+        if is_symlink(mode) {
+            let link = fs_err::tokio::read_to_string(&path).await?;
+            fs_err::tokio::remove_file(&path).await?;
+            symlinks.push((path, link));
+            continue;
+        }
 
-            if let Some(mode) = entry.unix_permissions() {
-                fs_err::set_permissions(&path, Permissions::from_mode(mode))?;
-            }
+        #[cfg(unix)]
+        {
+            use std::fs::Permissions;
+            use std::os::unix::fs::PermissionsExt;
+
+            fs_err::set_permissions(&path, Permissions::from_mode(mode))?;
         }
     }
 
+    let mut links = Links::default();
+    for (path, link) in symlinks {
+        create_symlink(&mut links, target.as_ref(), &path, Path::new(&link))?;
+    }
+
     Ok(())
 }
 
 /// Unzip a `.tar.gz` archive into the target directory, without requiring `Seek`.
 ///
-/// This is useful for unpacking files as they're being downloaded.
+/// This is useful for unpacking files as they're being downloaded. Every entry is validated before
+/// it's unpacked: paths and link targets must resolve within the target directory, and the archive
+/// must respect the entry-count and size limits.
 pub async fn untar<R: tokio::io::AsyncBufRead + Unpin>(
     reader: R,
     target: impl AsRef<Path>,
 ) -> Result<(), Error> {
+    let target = target.as_ref();
     let decompressed_bytes = async_compression::tokio::bufread::GzipDecoder::new(reader);
     let mut archive = tokio_tar::ArchiveBuilder::new(decompressed_bytes)
         .set_preserve_permissions(false)
         .build();
-    Ok(archive.unpack(target.as_ref()).await?)
+
+    fs_err::tokio::create_dir_all(target).await?;
+
+    // As in `tokio_tar::Archive::unpack`, unpack directories last, such that restrictive directory
+    // permissions don't prevent us from writing their contents.
+    let budget = Budget::new(Limits::from_env());
+    let mut links = Links::default();
+    let mut directories = Vec::new();
+    let mut entries = archive.entries()?;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        budget.entry(&name)?;
+
+        let Some(path) = enclosed_name(&name) else {
+            return Err(Error::UnsafePath(name));
+        };
+        if let Some(link) = entry.link_name()? {
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_hard_link() {
+                links.hard_link(&path, &link)?;
+            } else if entry_type.is_symlink() {
+                links.symlink(target, &target.join(&path), &link)?;
+            }
+        }
+        budget.bytes(&name, entry.header().size()?)?;
+
+        if entry.header().entry_type().is_dir() {
+            directories.push(entry);
+        } else {
+            entry.unpack_in(target).await?;
+        }
+    }
+    for mut directory in directories {
+        directory.unpack_in(target).await?;
+    }
+
+    Ok(())
 }
 
 /// Unzip a `.zip` or `.tar.gz` archive into the target directory, without requiring `Seek`.
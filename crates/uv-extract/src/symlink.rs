@@ -0,0 +1,235 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::guard::enclosed_name;
+use crate::Error;
+
+/// Returns `true` if the Unix mode of an archive entry marks it as a symlink.
+///
+/// In a `.zip` archive, a symlink is stored as a regular entry whose contents are the path to the
+/// link target, with `S_IFLNK` set in the upper bits of its external attributes.
+pub(crate) fn is_symlink(mode: u32) -> bool {
+    mode & 0o170_000 == 0o120_000
+}
+
+/// Create a symlink at `path` (within the extraction directory `root`) that points to `target`.
+///
+/// Symlinks that are absolute, that would resolve outside of `root`, or that pass through another
+/// of the archive's `links` are rejected, as are all symlinks on platforms without Unix-style
+/// symlinks.
+pub(crate) fn create_symlink(
+    links: &mut Links,
+    root: &Path,
+    path: &Path,
+    target: &Path,
+) -> Result<(), Error> {
+    links.symlink(root, path, target)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, path)?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        Err(Error::UnsupportedSymlink(path.to_path_buf()))
+    }
+}
+
+/// The links in an archive, used to reject chains of links that escape the extraction directory.
+///
+/// Each link target is validated on paper, without touching the filesystem, so a link that
+/// resolves within the root on its own could still escape by passing through another symlink
+/// (e.g., `a -> .` and `b -> a/..`). Targets that pass through a symlink are rejected outright. As
+/// symlinks are resolved when they're used, rather than when they're created, every symlink is
+/// re-validated whenever another is added, such that the order of the entries doesn't matter.
+#[derive(Debug, Default)]
+pub(crate) struct Links {
+    /// The symlinks, relative to the extraction directory, along with their targets.
+    symlinks: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Links {
+    /// Validate a symlink at `path` (within the extraction directory `root`) that points to
+    /// `target`, and record it.
+    pub(crate) fn symlink(&mut self, root: &Path, path: &Path, target: &Path) -> Result<(), Error> {
+        check_symlink(root, path, target)?;
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|_| Error::ExternalSymlink {
+                link: path.to_path_buf(),
+                target: target.to_path_buf(),
+            })?
+            .to_path_buf();
+        self.symlinks.push((relative, target.to_path_buf()));
+
+        for (link, target) in &self.symlinks {
+            let parent = link.parent().unwrap_or(Path::new(""));
+            if self.traverses_symlink(parent, target) {
+                return Err(Error::ExternalSymlink {
+                    link: root.join(link),
+                    target: target.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate that a hard link at `path` that points to `target` resolves within the archive,
+    /// without passing through any of the symlinks extracted so far.
+    ///
+    /// Unlike symlinks, hard links are resolved when they're created, so only the symlinks that
+    /// precede them in the archive are relevant.
+    pub(crate) fn hard_link(&self, path: &Path, target: &Path) -> Result<(), Error> {
+        check_hard_link(path, target)?;
+        if self.traverses_symlink(Path::new(""), target) {
+            return Err(Error::ExternalHardLink {
+                link: path.to_path_buf(),
+                target: target.to_path_buf(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if resolving `target` relative to the directory `start` (both relative to the
+    /// extraction directory) passes through any of the symlinks.
+    fn traverses_symlink(&self, start: &Path, target: &Path) -> bool {
+        let mut current = PathBuf::new();
+        for component in start.components().chain(target.components()) {
+            if self.symlinks.iter().any(|(link, _)| *link == current) {
+                return true;
+            }
+            match component {
+                Component::Normal(component) => current.push(component),
+                Component::ParentDir => {
+                    current.pop();
+                }
+                Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            }
+        }
+        false
+    }
+}
+
+/// Validate that a hard link at `path` that points to `target` resolves within the archive.
+///
+/// Unlike symlinks, hard link targets are relative to the root of the archive.
+fn check_hard_link(path: &Path, target: &Path) -> Result<(), Error> {
+    if enclosed_name(&target.to_string_lossy()).is_none() {
+        return Err(Error::ExternalHardLink {
+            link: path.to_path_buf(),
+            target: target.to_path_buf(),
+        });
+    }
+    Ok(())
+}
+
+/// Validate that a symlink at `path` (within the extraction directory `root`) that points to
+/// `target` resolves within `root`.
+fn check_symlink(root: &Path, path: &Path, target: &Path) -> Result<(), Error> {
+    // Resolve the target relative to the directory containing the link, without touching the
+    // filesystem, as the target may not have been extracted yet.
+    let parent = path
+        .parent()
+        .and_then(|parent| parent.strip_prefix(root).ok())
+        .ok_or_else(|| Error::ExternalSymlink {
+            link: path.to_path_buf(),
+            target: target.to_path_buf(),
+        })?;
+    let mut depth = parent.components().count();
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::ExternalSymlink {
+                    link: path.to_path_buf(),
+                    target: target.to_path_buf(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn symlink_mode() {
+        assert!(is_symlink(0o120_777));
+        assert!(!is_symlink(0o100_755));
+        assert!(!is_symlink(0o040_755));
+    }
+
+    #[test]
+    fn chained_symlinks() {
+        let root = Path::new("/wheel");
+
+        // `b` resolves to the parent of the root through `a`, regardless of the order of the
+        // entries.
+        let mut links = Links::default();
+        links
+            .symlink(root, &root.join("a"), Path::new("."))
+            .unwrap();
+        assert!(matches!(
+            links.symlink(root, &root.join("b"), Path::new("a/..")),
+            Err(Error::ExternalSymlink { .. })
+        ));
+
+        let mut links = Links::default();
+        links
+            .symlink(root, &root.join("b"), Path::new("a/.."))
+            .unwrap();
+        assert!(matches!(
+            links.symlink(root, &root.join("a"), Path::new(".")),
+            Err(Error::ExternalSymlink { .. })
+        ));
+
+        // Links within a symlinked directory are rejected, too.
+        let mut links = Links::default();
+        links
+            .symlink(root, &root.join("a"), Path::new("."))
+            .unwrap();
+        assert!(matches!(
+            links.symlink(root, &root.join("a").join("b"), Path::new("c")),
+            Err(Error::ExternalSymlink { .. })
+        ));
+
+        // As are hard links that pass through a symlink.
+        assert!(matches!(
+            links.hard_link(Path::new("c"), Path::new("a/../x")),
+            Err(Error::ExternalHardLink { .. })
+        ));
+
+        // Links to links are fine.
+        let mut links = Links::default();
+        links
+            .symlink(root, &root.join("a"), Path::new("lib"))
+            .unwrap();
+        links
+            .symlink(root, &root.join("b"), Path::new("a"))
+            .unwrap();
+        links.hard_link(Path::new("c"), Path::new("lib/x")).unwrap();
+    }
+
+    #[test]
+    fn external_symlink() {
+        let root = Path::new("/wheel");
+        let path = root.join("package").join("lib").join("libfoo.so");
+        for target in [
+            "/usr/lib/libfoo.so",
+            "../../../libfoo.so",
+            "../../../wheel/x",
+        ] {
+            assert!(matches!(
+                create_symlink(&mut Links::default(), root, &path, Path::new(target)),
+                Err(Error::ExternalSymlink { .. })
+            ));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::fs::OpenOptions;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
@@ -6,30 +7,48 @@ use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use zip::ZipArchive;
 
+use crate::guard::{enclosed_name, Budget, Limits};
+use crate::symlink::{create_symlink, is_symlink, Links};
 use crate::vendor::{CloneableSeekableReader, HasLength};
 use crate::Error;
 
 /// Unzip a `.zip` archive into the target directory.
+///
+/// Symlinks are created once every other entry has been written, such that no entry is written
+/// through a symlink.
 pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
     reader: R,
     target: &Path,
+) -> Result<(), Error> {
+    unzip_with_limits(reader, target, Limits::from_env())
+}
+
+/// Unzip a `.zip` archive into the target directory, with the given [`Limits`] rather than those
+/// configured in the environment.
+pub fn unzip_with_limits<R: Send + std::io::Read + std::io::Seek + HasLength>(
+    reader: R,
+    target: &Path,
+    limits: Limits,
 ) -> Result<(), Error> {
     // Unzip in parallel.
     let archive = ZipArchive::new(CloneableSeekableReader::new(reader))?;
     let directories = Mutex::new(FxHashSet::default());
+    let symlinks = Mutex::new(Vec::new());
+    let budget = Budget::new(limits);
     (0..archive.len())
         .par_bridge()
         .map(|file_number| {
             let mut archive = archive.clone();
             let mut file = archive.by_index(file_number)?;
+            budget.entry(file.name())?;
 
             // Determine the path of the file within the wheel.
-            let Some(enclosed_name) = file.enclosed_name() else {
-                return Ok(());
+            let Some(relative) = enclosed_name(file.name()) else {
+                return Err(Error::UnsafePath(file.name().to_string()));
             };
 
             // Create necessary parent directories.
-            let path = target.join(enclosed_name);
+            let path = target.join(relative);
             if file.is_dir() {
                 let mut directories = directories.lock().unwrap();
                 if directories.insert(path.clone()) {
@@ -45,6 +64,16 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
                 }
             }
 
+            // Materialize symlinks, rather than writing out the path to the target as a file.
+            if file.unix_mode().is_some_and(is_symlink) {
+                let mut link = String::new();
+                (&mut file)
+                    .take(budget.read_limit())
+                    .read_to_string(&mut link)?;
+                symlinks.lock().unwrap().push((path, PathBuf::from(link)));
+                return Ok(());
+            }
+
             // Create the file, with the correct permissions (on Unix).
             let mut options = OpenOptions::new();
             options.write(true);
@@ -59,24 +88,47 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
                 }
             }
 
-            // Copy the file contents.
+            // Copy the file contents, stopping once the archive exceeds the size limit (regardless
+            // of the size declared in the archive).
             let mut outfile = options.open(&path)?;
-            std::io::copy(&mut file, &mut outfile)?;
+            let bytes = std::io::copy(&mut (&mut file).take(budget.read_limit()), &mut outfile)?;
+            budget.bytes(file.name(), bytes)?;
 
             Ok(())
         })
-        .collect::<Result<_, Error>>()
+        .collect::<Result<(), Error>>()?;
+
+    let mut links = Links::default();
+    for (path, link) in symlinks.into_inner().unwrap() {
+        create_symlink(&mut links, target, &path, &link)?;
+    }
+
+    Ok(())
 }
 
 /// Extract a `.zip` or `.tar.gz` archive into the target directory.
 pub fn archive(source: impl AsRef<Path>, target: impl AsRef<Path>) -> Result<(), Error> {
+    archive_with_limits(source, target, Limits::from_env())
+}
+
+/// Extract a `.zip` or `.tar.gz` archive into the target directory, with the given [`Limits`]
+/// rather than those configured in the environment.
+pub fn archive_with_limits(
+    source: impl AsRef<Path>,
+    target: impl AsRef<Path>,
+    limits: Limits,
+) -> Result<(), Error> {
     // `.zip`
     if source
         .as_ref()
         .extension()
         .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
     {
-        unzip(fs_err::File::open(source.as_ref())?, target.as_ref())?;
+        unzip_with_limits(
+            fs_err::File::open(source.as_ref())?,
+            target.as_ref(),
+            limits,
+        )?;
         return Ok(());
     }
 
@@ -91,12 +143,11 @@ pub fn archive(source: impl AsRef<Path>, target: impl AsRef<Path>) -> Result<(),
                 .extension()
                 .is_some_and(|ext| ext.eq_ignore_ascii_case("tar"))
         }) {
-            let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(fs_err::File::open(
-                source.as_ref(),
-            )?));
-            // https://github.com/alexcrichton/tar-rs/issues/349
-            archive.set_preserve_mtime(false);
-            archive.unpack(target)?;
+            untar(
+                flate2::read::GzDecoder::new(fs_err::File::open(source.as_ref())?),
+                target.as_ref(),
+                limits,
+            )?;
             return Ok(());
         }
     }
@@ -104,6 +155,53 @@ pub fn archive(source: impl AsRef<Path>, target: impl AsRef<Path>) -> Result<(),
     Err(Error::UnsupportedArchive(source.as_ref().to_path_buf()))
 }
 
+/// Unpack a `.tar` archive into the target directory.
+///
+/// Every entry is validated before it's unpacked: paths and link targets must resolve within the
+/// target directory, and the archive must respect the entry-count and size limits.
+fn untar(reader: impl Read, target: &Path, limits: Limits) -> Result<(), Error> {
+    let mut archive = tar::Archive::new(reader);
+    // https://github.com/alexcrichton/tar-rs/issues/349
+    archive.set_preserve_mtime(false);
+
+    fs_err::create_dir_all(target)?;
+
+    // As in `tar::Archive::unpack`, unpack directories last, such that restrictive directory
+    // permissions don't prevent us from writing their contents.
+    let budget = Budget::new(limits);
+    let mut links = Links::default();
+    let mut directories = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        budget.entry(&name)?;
+
+        let Some(path) = enclosed_name(&name) else {
+            return Err(Error::UnsafePath(name));
+        };
+        if let Some(link) = entry.link_name()? {
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_hard_link() {
+                links.hard_link(&path, &link)?;
+            } else if entry_type.is_symlink() {
+                links.symlink(target, &target.join(&path), &link)?;
+            }
+        }
+        budget.bytes(&name, entry.header().size()?)?;
+
+        if entry.header().entry_type().is_dir() {
+            directories.push(entry);
+        } else {
+            entry.unpack_in(target)?;
+        }
+    }
+    for mut directory in directories {
+        directory.unpack_in(target)?;
+    }
+
+    Ok(())
+}
+
 /// Extract the top-level directory from an unpacked archive.
 ///
 /// The specification says:
@@ -0,0 +1,15 @@
+use std::io::{Cursor, Write};
+
+use anyhow::Result;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Build a `.zip` archive with the given entries.
+pub fn zip_archive(entries: &[(&str, &[u8])]) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in entries {
+        writer.start_file(*name, FileOptions::default())?;
+        writer.write_all(contents)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
@@ -0,0 +1,228 @@
+//! Tests for the rejection of malicious archives, i.e., entries and links that would escape the
+//! extraction directory.
+
+use std::io::{Cursor, Write};
+
+use anyhow::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use uv_extract::Error;
+
+use crate::common::zip_archive;
+
+mod common;
+
+/// Build a `.tar.gz` archive with a regular file and a link of the given type.
+fn tar_gz_archive(entry_type: tar::EntryType, link: &str, target: &str) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    let contents = b"print('hello')\n";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    builder.append_data(&mut header, "foo-1.0/foo.py", &contents[..])?;
+
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_size(0);
+    header.set_mode(0o777);
+    header.set_link_name(target)?;
+    builder.append_data(&mut header, link, std::io::empty())?;
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+#[test]
+fn zip_parent_dir() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("target");
+
+    let archive = zip_archive(&[
+        ("foo-1.0/foo.py", b"print('hello')\n".as_slice()),
+        ("foo-1.0/../../evil.py", b"print('evil')\n".as_slice()),
+    ])?;
+    let err = uv_extract::unzip(Cursor::new(archive), &target).unwrap_err();
+    assert!(
+        matches!(&err, Error::UnsafePath(name) if name == "foo-1.0/../../evil.py"),
+        "{err}"
+    );
+    assert!(!temp_dir.path().join("evil.py").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zip_parent_dir_stream() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("target");
+
+    let archive = zip_archive(&[("../evil.py", b"print('evil')\n".as_slice())])?;
+    let err = uv_extract::stream::unzip(archive.as_slice(), &target)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::UnsafePath(name) if name == "../evil.py"),
+        "{err}"
+    );
+    assert!(!temp_dir.path().join("evil.py").exists());
+
+    Ok(())
+}
+
+#[test]
+fn tar_external_symlink() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("foo-1.0.tar.gz");
+    let target = temp_dir.path().join("target");
+
+    fs_err::write(
+        &source,
+        tar_gz_archive(tar::EntryType::Symlink, "foo-1.0/link", "../../outside")?,
+    )?;
+    let err = uv_extract::archive(&source, &target).unwrap_err();
+    assert!(matches!(err, Error::ExternalSymlink { .. }), "{err}");
+    assert!(!target.join("foo-1.0").join("link").exists());
+
+    Ok(())
+}
+
+#[test]
+fn tar_external_hard_link() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("foo-1.0.tar.gz");
+    let target = temp_dir.path().join("target");
+
+    fs_err::write(
+        &source,
+        tar_gz_archive(tar::EntryType::Link, "foo-1.0/link", "../outside")?,
+    )?;
+    let err = uv_extract::archive(&source, &target).unwrap_err();
+    assert!(matches!(err, Error::ExternalHardLink { .. }), "{err}");
+    assert!(!target.join("foo-1.0").join("link").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tar_external_symlink_stream() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("target");
+
+    let archive = tar_gz_archive(tar::EntryType::Symlink, "foo-1.0/link", "/etc/passwd")?;
+    let err = uv_extract::stream::untar(archive.as_slice(), &target)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ExternalSymlink { .. }), "{err}");
+    assert!(!target.join("foo-1.0").join("link").exists());
+
+    Ok(())
+}
+
+/// Links that resolve within the archive are extracted.
+#[test]
+#[cfg(unix)]
+fn tar_internal_symlink() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("foo-1.0.tar.gz");
+    let target = temp_dir.path().join("target");
+
+    fs_err::write(
+        &source,
+        tar_gz_archive(tar::EntryType::Symlink, "foo-1.0/link.py", "foo.py")?,
+    )?;
+    uv_extract::archive(&source, &target)?;
+    assert_eq!(
+        fs_err::read_to_string(target.join("foo-1.0").join("link.py"))?,
+        "print('hello')\n"
+    );
+
+    Ok(())
+}
+
+/// Build a `.zip` archive with a chain of symlinks, `a -> .` and `b -> a/..`, which together point
+/// to the parent of the extraction directory, followed by the given entries.
+#[cfg(unix)]
+fn zip_chained_symlinks(entries: &[(&str, &[u8])]) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer.add_symlink("a", ".", FileOptions::default())?;
+    writer.add_symlink("b", "a/..", FileOptions::default())?;
+    for (name, contents) in entries {
+        writer.start_file(*name, FileOptions::default())?;
+        writer.write_all(contents)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[test]
+#[cfg(unix)]
+fn zip_chained_symlinks_rejected() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("target");
+
+    let archive = zip_chained_symlinks(&[])?;
+    let err = uv_extract::unzip(Cursor::new(archive), &target).unwrap_err();
+    assert!(matches!(err, Error::ExternalSymlink { .. }), "{err}");
+
+    // An entry written through the chain fails, rather than escaping the extraction directory.
+    let target = temp_dir.path().join("target-evil");
+    let archive = zip_chained_symlinks(&[("b/evil.py", b"print('evil')\n".as_slice())])?;
+    uv_extract::unzip(Cursor::new(archive), &target).unwrap_err();
+    assert!(!temp_dir.path().join("evil.py").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn zip_chained_symlinks_rejected_stream() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("target");
+
+    let archive = zip_chained_symlinks(&[])?;
+    let err = uv_extract::stream::unzip(archive.as_slice(), &target)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ExternalSymlink { .. }), "{err}");
+
+    let target = temp_dir.path().join("target-evil");
+    let archive = zip_chained_symlinks(&[("b/evil.py", b"print('evil')\n".as_slice())])?;
+    uv_extract::stream::unzip(archive.as_slice(), &target)
+        .await
+        .unwrap_err();
+    assert!(!temp_dir.path().join("evil.py").exists());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn tar_chained_symlinks_rejected() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = temp_dir.path().join("foo-1.0.tar.gz");
+    let target = temp_dir.path().join("target");
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (link, link_target) in [("foo-1.0/a", "."), ("foo-1.0/b", "a/../..")] {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        header.set_link_name(link_target)?;
+        builder.append_data(&mut header, link, std::io::empty())?;
+    }
+    let contents = b"print('evil')\n";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    builder.append_data(&mut header, "foo-1.0/b/evil.py", &contents[..])?;
+    fs_err::write(&source, builder.into_inner()?.finish()?)?;
+
+    let err = uv_extract::archive(&source, &target).unwrap_err();
+    assert!(matches!(err, Error::ExternalSymlink { .. }), "{err}");
+    assert!(!temp_dir.path().join("evil.py").exists());
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Tests for the limits on the number of entries and total size of an archive.
+
+use std::io::Cursor;
+
+use anyhow::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use uv_extract::{Error, Limits};
+
+use crate::common::zip_archive;
+
+mod common;
+
+/// Build a `.tar.gz` archive with the given files.
+fn tar_gz_archive(entries: &[(&str, &[u8])]) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (name, contents) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, name, *contents)?;
+    }
+    Ok(builder.into_inner()?.finish()?)
+}
+
+#[tokio::test]
+async fn limits() -> Result<()> {
+    let limits = Limits {
+        max_entries: 2,
+        max_size: 16,
+    };
+    let temp_dir = tempfile::tempdir()?;
+
+    // An archive within both limits is extracted.
+    let archive = zip_archive(&[
+        ("foo-1.0/a.txt", b"12345678".as_slice()),
+        ("foo-1.0/b.txt", b"12345678".as_slice()),
+    ])?;
+    uv_extract::unzip_with_limits(Cursor::new(archive), &temp_dir.path().join("ok"), limits)?;
+
+    // Too many entries.
+    let archive = zip_archive(&[
+        ("foo-1.0/a.txt", b"a".as_slice()),
+        ("foo-1.0/b.txt", b"b".as_slice()),
+        ("foo-1.0/c.txt", b"c".as_slice()),
+    ])?;
+    let err = uv_extract::unzip_with_limits(
+        Cursor::new(archive),
+        &temp_dir.path().join("entries"),
+        limits,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::TooManyEntries { limit: 2, .. }),
+        "{err}"
+    );
+
+    // Too many bytes, as counted while reading the entries of a `.zip` archive.
+    let archive = zip_archive(&[("foo-1.0/a.txt", [0u8; 17].as_slice())])?;
+    let err =
+        uv_extract::unzip_with_limits(Cursor::new(archive), &temp_dir.path().join("size"), limits)
+            .unwrap_err();
+    assert!(matches!(err, Error::TooLarge { limit: 16, .. }), "{err}");
+
+    let archive = zip_archive(&[("foo-1.0/a.txt", [0u8; 17].as_slice())])?;
+    let err = uv_extract::stream::unzip_with_limits(
+        archive.as_slice(),
+        temp_dir.path().join("size-stream"),
+        limits,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, Error::TooLarge { limit: 16, .. }), "{err}");
+
+    // Too many bytes, as declared in the headers of a `.tar.gz` archive.
+    let source = temp_dir.path().join("foo-1.0.tar.gz");
+    fs_err::write(
+        &source,
+        tar_gz_archive(&[("foo-1.0/a.txt", [0u8; 17].as_slice())])?,
+    )?;
+    let err = uv_extract::archive_with_limits(&source, temp_dir.path().join("size-tar"), limits)
+        .unwrap_err();
+    assert!(matches!(err, Error::TooLarge { limit: 16, .. }), "{err}");
+
+    Ok(())
+}
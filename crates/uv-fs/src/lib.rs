@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use fs2::FileExt;
 use fs_err as fs;
 use tempfile::NamedTempFile;
-use tracing::{error, warn};
+use tracing::{debug, error, warn};
 
 use uv_warnings::warn_user;
 
@@ -83,6 +83,9 @@ pub fn replace_symlink(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> std::io:
 }
 
 /// Write `data` to `path` atomically using a temporary file and atomic rename.
+///
+/// If the process is interrupted, `path` is never left partially written, so all writes into the
+/// cache should go through this function.
 #[cfg(feature = "tokio")]
 pub async fn write_atomic(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> std::io::Result<()> {
     let temp_file = NamedTempFile::new_in(
@@ -105,6 +108,9 @@ pub async fn write_atomic(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> std
 }
 
 /// Write `data` to `path` atomically using a temporary file and atomic rename.
+///
+/// If the process is interrupted, `path` is never left partially written, so all writes into the
+/// cache should go through this function.
 pub fn write_atomic_sync(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> std::io::Result<()> {
     let temp_file = NamedTempFile::new_in(
         path.as_ref()
@@ -125,6 +131,52 @@ pub fn write_atomic_sync(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> std:
     Ok(())
 }
 
+/// The number of times to retry a file operation that fails transiently, e.g., on Windows, when
+/// another process (like a virus scanner or search indexer) briefly holds the file open.
+const RETRIES: u32 = 5;
+
+/// Returns `true` if the error may be caused by another process holding the file open, such that
+/// the operation is worth retrying.
+fn is_transient(err: &std::io::Error) -> bool {
+    if !cfg!(windows) {
+        return false;
+    }
+    // `ERROR_ACCESS_DENIED` (5) and `ERROR_SHARING_VIOLATION` (32).
+    err.kind() == std::io::ErrorKind::PermissionDenied || err.raw_os_error() == Some(32)
+}
+
+/// Run a file operation, retrying with exponential backoff if it fails transiently.
+fn with_retries<T>(mut operation: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+    let mut backoff = std::time::Duration::from_millis(10);
+    let mut attempt = 0;
+    loop {
+        match operation() {
+            Err(err) if attempt < RETRIES && is_transient(&err) => {
+                debug!("Retrying file operation after transient error: {err}");
+                std::thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Rename `from` to `to`, retrying if the rename fails transiently.
+///
+/// On Windows, renames regularly fail when a virus scanner or search indexer has just opened one
+/// of the files, so the rename is retried a few times before giving up.
+pub fn rename_with_retry_sync(from: impl AsRef<Path>, to: impl AsRef<Path>) -> std::io::Result<()> {
+    with_retries(|| fs_err::rename(from.as_ref(), to.as_ref()))
+}
+
+/// Remove the file at `path`, retrying if the removal fails transiently.
+///
+/// See [`rename_with_retry_sync`].
+pub fn remove_file_with_retry_sync(path: impl AsRef<Path>) -> std::io::Result<()> {
+    with_retries(|| fs_err::remove_file(path.as_ref()))
+}
+
 /// Remove the file or directory at `path`, if it exists.
 ///
 /// Returns `true` if the file or directory was removed, and `false` if the path did not exist.
@@ -237,6 +289,16 @@ impl LockedFile {
             Err(err) => Err(err),
         }
     }
+
+    /// Acquire the lock without blocking, returning `None` if it's held by another process.
+    pub fn try_acquire(path: impl AsRef<Path>) -> Result<Option<Self>, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        match file.file().try_lock_exclusive() {
+            Ok(()) => Ok(Some(Self(file))),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 impl Drop for LockedFile {
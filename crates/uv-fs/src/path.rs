@@ -1,5 +1,6 @@
 use std::borrow::Cow;
-use std::path::Path;
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf, Prefix};
 
 pub trait Normalized {
     /// Normalize a [`Path`].
@@ -42,6 +43,67 @@ pub fn normalize_url_path(path: &str) -> Cow<'_, str> {
     }
 }
 
+/// Convert an absolute path to its extended-length form, such that it can exceed `MAX_PATH`
+/// (260 characters).
+///
+/// For example, on Windows, transforms `C:\Users\ferris\site-packages` to
+/// `\\?\C:\Users\ferris\site-packages`, and `\\server\share\venv` to
+/// `\\?\UNC\server\share\venv`.
+///
+/// Paths that are relative, already in extended-length form, or contain `..` components (which
+/// aren't resolved in extended-length paths) are returned unchanged, as are all paths on other
+/// platforms.
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    if !cfg!(windows) {
+        return Cow::Borrowed(path);
+    }
+
+    let mut components = path.components();
+    let Some(Component::Prefix(prefix)) = components.next() else {
+        return Cow::Borrowed(path);
+    };
+    let mut long = match prefix.kind() {
+        Prefix::Disk(_) => {
+            let mut long = OsString::from(r"\\?\");
+            long.push(prefix.as_os_str());
+            long
+        }
+        Prefix::UNC(server, share) => {
+            let mut long = OsString::from(r"\\?\UNC\");
+            long.push(server);
+            long.push(r"\");
+            long.push(share);
+            long
+        }
+        _ => return Cow::Borrowed(path),
+    };
+
+    // Drive-relative paths (like `C:foo`) can't be converted without the current directory.
+    if components.next() != Some(Component::RootDir) {
+        return Cow::Borrowed(path);
+    }
+
+    let mut empty = true;
+    for component in components {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(name) => {
+                long.push(r"\");
+                long.push(name);
+                empty = false;
+            }
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Cow::Borrowed(path);
+            }
+        }
+    }
+    if empty {
+        long.push(r"\");
+    }
+
+    Cow::Owned(PathBuf::from(long))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +134,30 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn long() {
+        if cfg!(windows) {
+            assert_eq!(
+                long_path(Path::new(r"C:\Users\ferris\site-packages")),
+                Path::new(r"\\?\C:\Users\ferris\site-packages")
+            );
+            assert_eq!(
+                long_path(Path::new(r"\\server\share\venv")),
+                Path::new(r"\\?\UNC\server\share\venv")
+            );
+            assert_eq!(
+                long_path(Path::new(r"\\?\C:\Users\ferris")),
+                Path::new(r"\\?\C:\Users\ferris")
+            );
+            assert_eq!(
+                long_path(Path::new(r"C:\Users\..\ferris")),
+                Path::new(r"C:\Users\..\ferris")
+            );
+        }
+        assert_eq!(
+            long_path(Path::new("ferris/site-packages")),
+            Path::new("ferris/site-packages")
+        );
+    }
 }
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use fs_err as fs;
+use rustc_hash::FxHashSet;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use uv_interpreter::Virtualenv;
+use uv_normalize::PackageName;
+
+use crate::SitePackages;
+
+/// A script that would be written to the environment's scripts directory by more than one
+/// distribution.
+#[derive(Debug, Clone)]
+pub struct ScriptConflict {
+    /// The file name of the script (e.g., `black`, or `black.exe` on Windows).
+    pub script: String,
+    /// The distributions that provide the script, in sorted order.
+    pub packages: Vec<PackageName>,
+}
+
+impl ScriptConflict {
+    /// Convert the conflict into a user-facing message.
+    pub fn message(&self) -> String {
+        let packages = self
+            .packages
+            .iter()
+            .map(|package| format!("`{package}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "The script `{}` is provided by multiple packages ({packages}), and would be overwritten",
+            self.script
+        )
+    }
+}
+
+/// Detect scripts that would be overwritten by the given installation.
+///
+/// A conflict arises when two of the `wheels` provide the same script, or when one of the `wheels`
+/// provides a script owned by a distribution that will remain in the environment. Distributions
+/// in `removals` are uninstalled before any wheels are installed, and so are never in conflict.
+pub fn script_conflicts<'a>(
+    venv: &Virtualenv,
+    wheels: &[CachedDist],
+    removals: impl IntoIterator<Item = &'a InstalledDist>,
+) -> Result<Vec<ScriptConflict>> {
+    let mut owners: BTreeMap<String, Vec<PackageName>> = BTreeMap::new();
+
+    // Index the scripts provided by each wheel.
+    for wheel in wheels {
+        let scripts = install_wheel_rs::linker::script_names(wheel.path())
+            .with_context(|| format!("Failed to read scripts for: {wheel}"))?;
+        for script in scripts {
+            owners.entry(script).or_default().push(wheel.name().clone());
+        }
+    }
+
+    // Nothing can conflict if no scripts are being written.
+    if owners.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Index the scripts owned by any distributions that will remain installed.
+    let removals = removals
+        .into_iter()
+        .map(InstalledDist::path)
+        .collect::<FxHashSet<_>>();
+    let site_packages = SitePackages::from_executable(venv)?;
+    let scripts_dir = install_wheel_rs::relative_to(
+        &venv.interpreter().scheme().join(venv.root()).scripts,
+        &venv.site_packages(),
+    )?;
+    for dist in site_packages.iter() {
+        if removals.contains(dist.path()) {
+            continue;
+        }
+        if wheels.iter().any(|wheel| wheel.name() == dist.name()) {
+            continue;
+        }
+        for script in installed_scripts(dist, &scripts_dir)? {
+            if let Some(packages) = owners.get_mut(&script) {
+                packages.push(dist.name().clone());
+            }
+        }
+    }
+
+    Ok(owners
+        .into_iter()
+        .filter(|(_, packages)| packages.len() > 1)
+        .map(|(script, mut packages)| {
+            packages.sort_unstable();
+            packages.dedup();
+            ScriptConflict { script, packages }
+        })
+        .collect())
+}
+
+/// Returns the file names of the scripts recorded for an installed distribution, given the path
+/// to the scripts directory relative to `site-packages`.
+fn installed_scripts(dist: &InstalledDist, scripts_dir: &Path) -> Result<Vec<String>> {
+    // Only `.dist-info` installs have a `RECORD` of the files they own.
+    if !matches!(dist, InstalledDist::Registry(_) | InstalledDist::Url(_)) {
+        return Ok(Vec::new());
+    }
+
+    let mut record = match fs::File::open(dist.path().join("RECORD")) {
+        Ok(record) => record,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let record = install_wheel_rs::read_record_file(&mut record)?;
+
+    Ok(record
+        .iter()
+        .filter_map(|entry| {
+            let path = Path::new(&entry.path);
+            if path.parent() == Some(scripts_dir) {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            } else {
+                None
+            }
+        })
+        .collect())
+}
@@ -1,6 +1,6 @@
 use std::cmp::Reverse;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
 use tokio::task::JoinError;
@@ -11,7 +11,8 @@ use distribution_types::{CachedDist, Dist, Identifier, LocalEditable, RemoteSour
 use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::RegistryClient;
-use uv_distribution::{DistributionDatabase, LocalWheel, Unzip};
+use uv_distribution::{BuiltWheel, DistributionDatabase, LocalWheel, Unzip};
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, InFlight};
 
 use crate::editable::BuiltEditable;
@@ -31,11 +32,26 @@ pub enum Error {
     Thread(String),
 }
 
+impl Error {
+    /// Return the distribution that failed to download or unzip, if the error is specific to a
+    /// single distribution.
+    pub fn dist(&self) -> Option<&Dist> {
+        match self {
+            Self::Unzip(dist, _) | Self::Fetch(dist, _) => Some(dist),
+            Self::Join(_) | Self::Editable(_) | Self::Thread(_) => None,
+        }
+    }
+}
+
 /// Download, build, and unzip a set of distributions.
 pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     database: DistributionDatabase<'a, Context>,
     cache: &'a Cache,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The wheels that were built from source distributions.
+    built: Mutex<Vec<BuiltWheel>>,
+    /// The maximum number of concurrent downloads.
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -49,6 +65,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             database: DistributionDatabase::new(cache, tags, client, build_context),
             reporter: None,
             cache,
+            built: Mutex::default(),
+            concurrency: build_context.concurrency().downloads,
         }
     }
 
@@ -60,6 +78,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             reporter: Some(reporter.clone()),
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             cache: self.cache,
+            built: self.built,
+            concurrency: self.concurrency,
         }
     }
 
@@ -77,9 +97,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // Concurrent builds are further limited by the build context.
+            .buffer_unordered(self.concurrency)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
@@ -105,6 +124,46 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
         Ok(wheels)
     }
 
+    /// Download, build, and unzip a set of distributions, continuing past any distributions that
+    /// fail to download or build.
+    ///
+    /// Returns the distributions that were fetched successfully, along with the errors for those
+    /// that were not. Errors that aren't specific to a single distribution are returned
+    /// immediately.
+    #[instrument(skip_all, fields(total = distributions.len()))]
+    pub async fn download_partial(
+        &self,
+        mut distributions: Vec<Dist>,
+        in_flight: &InFlight,
+    ) -> Result<(Vec<CachedDist>, Vec<Error>), Error> {
+        // Sort the distributions by size.
+        distributions
+            .sort_unstable_by_key(|distribution| Reverse(distribution.size().unwrap_or(u64::MAX)));
+
+        let mut wheels = Vec::with_capacity(distributions.len());
+        let mut failures = Vec::new();
+        let mut fetches = self.download_stream(distributions, in_flight);
+        while let Some(result) = fetches.next().await {
+            match result {
+                Ok(wheel) => wheels.push(wheel),
+                Err(err) if err.dist().is_some() => failures.push(err),
+                Err(err) => return Err(err),
+            }
+        }
+
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.on_complete();
+        }
+
+        Ok((wheels, failures))
+    }
+
+    /// Return the wheels that were built from source distributions (as opposed to downloaded) by
+    /// this downloader, in the order in which they were built.
+    pub fn built_wheels(&self) -> Vec<BuiltWheel> {
+        self.built.lock().unwrap().clone()
+    }
+
     /// Build a set of editables
     #[instrument(skip_all)]
     pub async fn build_editables(
@@ -133,7 +192,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<_, Error>((editable, cached_dist, metadata))
             })
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
@@ -164,6 +223,9 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 .boxed()
                 .map_err(|err| Error::Fetch(dist.clone(), err))
                 .await?;
+            if let LocalWheel::Built(wheel) = &download {
+                self.built.lock().unwrap().push(wheel.clone());
+            }
             let result = self.unzip_wheel(download).await;
             match result {
                 Ok(cached) => {
@@ -241,6 +303,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+
+    /// Callback to invoke when a wheel download begins, with its size in bytes, if known.
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a wheel download is received.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when a wheel download completes.
+    fn on_download_complete(&self, name: &PackageName, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
@@ -270,4 +341,16 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        self.reporter.on_download_start(name, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.reporter.on_download_progress(id, bytes);
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        self.reporter.on_download_complete(name, id);
+    }
 }
@@ -8,6 +8,7 @@ use uv_interpreter::Virtualenv;
 pub struct Installer<'a> {
     venv: &'a Virtualenv,
     link_mode: install_wheel_rs::linker::LinkMode,
+    shebang: install_wheel_rs::Shebang,
     reporter: Option<Box<dyn Reporter>>,
 }
 
@@ -17,6 +18,7 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: install_wheel_rs::linker::LinkMode::default(),
+            shebang: install_wheel_rs::Shebang::default(),
             reporter: None,
         }
     }
@@ -27,6 +29,12 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Set the [`Shebang`][`install_wheel_rs::Shebang`] to write to installed scripts.
+    #[must_use]
+    pub fn with_shebang(self, shebang: install_wheel_rs::Shebang) -> Self {
+        Self { shebang, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -37,14 +45,30 @@ impl<'a> Installer<'a> {
     }
 
     /// Install a set of wheels into a Python virtual environment.
+    ///
+    /// Scripts installed into a relocatable environment default to relative shebangs, such that
+    /// the environment remains relocatable.
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
     pub fn install(self, wheels: &[CachedDist]) -> Result<()> {
+        let shebang = if self.shebang == install_wheel_rs::Shebang::default()
+            && self.venv.cfg().is_ok_and(|cfg| cfg.is_relocatable())
+        {
+            install_wheel_rs::Shebang {
+                style: install_wheel_rs::ShebangStyle::Relative,
+                interpreter: None,
+            }
+        } else {
+            self.shebang.clone()
+        };
+
         tokio::task::block_in_place(|| {
             wheels.par_iter().try_for_each(|wheel| {
                 let location = install_wheel_rs::InstallLocation::new(
                     self.venv.root(),
                     self.venv.interpreter().python_tuple(),
-                );
+                )
+                .with_scheme(self.venv.interpreter().scheme().clone())
+                .with_shebang(shebang.clone());
 
                 install_wheel_rs::linker::install_wheel(
                     &location,
@@ -56,6 +80,7 @@ impl<'a> Installer<'a> {
                         .map(pypi_types::DirectUrl::try_from)
                         .transpose()?
                         .as_ref(),
+                    wheel.provenance().as_ref(),
                     Some("uv"),
                     self.link_mode,
                 )
@@ -1,4 +1,5 @@
-pub use downloader::{Downloader, Reporter as DownloadReporter};
+pub use conflicts::{script_conflicts, ScriptConflict};
+pub use downloader::{Downloader, Error, Reporter as DownloadReporter};
 pub use editable::{BuiltEditable, ResolvedEditable};
 pub use installer::{Installer, Reporter as InstallReporter};
 pub use plan::{Plan, Planner, Reinstall};
@@ -6,6 +7,7 @@ pub use plan::{Plan, Planner, Reinstall};
 pub use site_packages::SitePackages;
 pub use uninstall::uninstall;
 pub use uv_traits::NoBinary;
+mod conflicts;
 mod downloader;
 mod editable;
 mod installer;
@@ -18,6 +18,7 @@ use uv_fs::Normalized;
 use uv_interpreter::Virtualenv;
 use uv_normalize::PackageName;
 use uv_traits::NoBinary;
+use uv_warnings::warn_user;
 
 use crate::{ResolvedEditable, SitePackages};
 
@@ -26,6 +27,7 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: Vec<ResolvedEditable>,
+    verify_hashes: bool,
 }
 
 impl<'a> Planner<'a> {
@@ -35,6 +37,7 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: Vec::new(),
+            verify_hashes: false,
         }
     }
 
@@ -47,6 +50,18 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Verify the files of any cached distributions against their `RECORD` before using them.
+    ///
+    /// Cached distributions that fail verification are evicted from the cache and planned for
+    /// download (or build), as if they'd never been cached.
+    #[must_use]
+    pub fn with_verify_hashes(self, verify_hashes: bool) -> Self {
+        Self {
+            verify_hashes,
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -65,6 +80,8 @@ impl<'a> Planner<'a> {
         venv: &Virtualenv,
         tags: &Tags,
     ) -> Result<Plan> {
+        let verify_hashes = self.verify_hashes;
+
         // Index all the already-downloaded wheels in the cache.
         let mut registry_index = RegistryWheelIndex::new(cache, tags, index_locations);
 
@@ -197,9 +214,12 @@ impl<'a> Planner<'a> {
                     if let Some((_version, distribution)) =
                         registry_index.get(&requirement.name).next()
                     {
-                        debug!("Requirement already cached: {distribution}");
-                        local.push(CachedDist::Registry(distribution.clone()));
-                        continue;
+                        let cached_dist = CachedDist::Registry(distribution.clone());
+                        if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                            debug!("Requirement already cached: {distribution}");
+                            local.push(cached_dist);
+                            continue;
+                        }
                     }
                 }
                 Some(VersionOrUrl::VersionSpecifier(specifier)) => {
@@ -214,9 +234,12 @@ impl<'a> Planner<'a> {
                                 }
                             })
                     {
-                        debug!("Requirement already cached: {distribution}");
-                        local.push(CachedDist::Registry(distribution.clone()));
-                        continue;
+                        let cached_dist = CachedDist::Registry(distribution.clone());
+                        if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                            debug!("Requirement already cached: {distribution}");
+                            local.push(cached_dist);
+                            continue;
+                        }
                     }
                 }
                 Some(VersionOrUrl::Url(url)) => {
@@ -254,15 +277,19 @@ impl<'a> Planner<'a> {
 
                             match cache_entry.path().canonicalize() {
                                 Ok(archive) => {
-                                    let cached_dist = CachedDirectUrlDist::from_url(
-                                        wheel.filename,
-                                        wheel.url,
-                                        archive,
-                                    );
-
-                                    debug!("URL wheel requirement already cached: {cached_dist}");
-                                    local.push(CachedDist::Url(cached_dist));
-                                    continue;
+                                    let cached_dist =
+                                        CachedDist::Url(CachedDirectUrlDist::from_url(
+                                            wheel.filename,
+                                            wheel.url,
+                                            archive,
+                                        ));
+                                    if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                                        debug!(
+                                            "URL wheel requirement already cached: {cached_dist}"
+                                        );
+                                        local.push(cached_dist);
+                                        continue;
+                                    }
                                 }
                                 Err(err) if err.kind() == io::ErrorKind::NotFound => {
                                     // The cache entry doesn't exist, so it's not fresh.
@@ -298,17 +325,19 @@ impl<'a> Planner<'a> {
                             if not_modified_cache(&cache_entry, &wheel.path)? {
                                 match cache_entry.path().canonicalize() {
                                     Ok(archive) => {
-                                        let cached_dist = CachedDirectUrlDist::from_url(
-                                            wheel.filename,
-                                            wheel.url,
-                                            archive,
-                                        );
-
-                                        debug!(
-                                            "URL wheel requirement already cached: {cached_dist}"
-                                        );
-                                        local.push(CachedDist::Url(cached_dist));
-                                        continue;
+                                        let cached_dist =
+                                            CachedDist::Url(CachedDirectUrlDist::from_url(
+                                                wheel.filename,
+                                                wheel.url,
+                                                archive,
+                                            ));
+                                        if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                                            debug!(
+                                                "URL wheel requirement already cached: {cached_dist}"
+                                            );
+                                            local.push(cached_dist);
+                                            continue;
+                                        }
                                     }
                                     Err(err) if err.kind() == io::ErrorKind::NotFound => {
                                         // The cache entry doesn't exist, so it's not fresh.
@@ -321,30 +350,36 @@ impl<'a> Planner<'a> {
                             // Find the most-compatible wheel from the cache, since we don't know
                             // the filename in advance.
                             if let Some(wheel) = BuiltWheelIndex::url(&sdist, cache, tags)? {
-                                let cached_dist = wheel.into_url_dist(url.clone());
-                                debug!("URL source requirement already cached: {cached_dist}");
-                                local.push(CachedDist::Url(cached_dist));
-                                continue;
+                                let cached_dist = CachedDist::Url(wheel.into_url_dist(url.clone()));
+                                if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                                    debug!("URL source requirement already cached: {cached_dist}");
+                                    local.push(cached_dist);
+                                    continue;
+                                }
                             }
                         }
                         Dist::Source(SourceDist::Path(sdist)) => {
                             // Find the most-compatible wheel from the cache, since we don't know
                             // the filename in advance.
                             if let Some(wheel) = BuiltWheelIndex::path(&sdist, cache, tags)? {
-                                let cached_dist = wheel.into_url_dist(url.clone());
-                                debug!("Path source requirement already cached: {cached_dist}");
-                                local.push(CachedDist::Url(cached_dist));
-                                continue;
+                                let cached_dist = CachedDist::Url(wheel.into_url_dist(url.clone()));
+                                if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                                    debug!("Path source requirement already cached: {cached_dist}");
+                                    local.push(cached_dist);
+                                    continue;
+                                }
                             }
                         }
                         Dist::Source(SourceDist::Git(sdist)) => {
                             // Find the most-compatible wheel from the cache, since we don't know
                             // the filename in advance.
                             if let Some(wheel) = BuiltWheelIndex::git(&sdist, cache, tags) {
-                                let cached_dist = wheel.into_url_dist(url.clone());
-                                debug!("Git source requirement already cached: {cached_dist}");
-                                local.push(CachedDist::Url(cached_dist));
-                                continue;
+                                let cached_dist = CachedDist::Url(wheel.into_url_dist(url.clone()));
+                                if !verify_hashes || verify_cached(&cached_dist, cache)? {
+                                    debug!("Git source requirement already cached: {cached_dist}");
+                                    local.push(cached_dist);
+                                    continue;
+                                }
                             }
                         }
                     }
@@ -382,6 +417,52 @@ impl<'a> Planner<'a> {
     }
 }
 
+/// Returns `true` if the files of the cached distribution match its `RECORD`.
+///
+/// Otherwise, the distribution is evicted from the cache, along with the HTTP cache entry for the
+/// wheel (if any), such that it's downloaded (or built) again.
+fn verify_cached(dist: &CachedDist, cache: &Cache) -> Result<bool> {
+    let Err(err) = install_wheel_rs::linker::verify_wheel(dist.path()) else {
+        return Ok(true);
+    };
+
+    // Entries in the read-only cache can't be repaired.
+    if cache.is_readonly(dist.path()) {
+        return Err(anyhow::Error::new(err).context(format!(
+            "Cached distribution is corrupt, but the cache is read-only: {dist}"
+        )));
+    }
+
+    warn_user!("Cached distribution is corrupt, downloading it again: {dist} ({err})");
+
+    // Remove the link to the unzipped wheel, and the HTTP cache entry for the wheel, such that the
+    // wheel isn't served from the HTTP cache.
+    let shard = match dist {
+        CachedDist::Registry(dist) => cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Index(&dist.index).remote_wheel_dir(dist.name().as_ref()),
+        ),
+        CachedDist::Url(dist) => cache.shard(
+            CacheBucket::Wheels,
+            WheelCache::Url(&dist.url).remote_wheel_dir(dist.name().as_ref()),
+        ),
+    };
+    let stem = dist.filename().stem();
+    for entry in [shard.entry(&stem), shard.entry(format!("{stem}.http"))] {
+        match fs_err::remove_file(entry.path()) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    // Remove the unzipped wheel itself, along with any objects that it shares with other wheels.
+    // Built wheels are unzipped again from the built archive.
+    cache.remove_archive(dist.path())?;
+
+    Ok(false)
+}
+
 /// Returns `true` if the cache entry linked to the file at the given [`Path`] is not-modified.
 ///
 /// A cache entry is not modified if it exists and is newer than the file at the given path.
@@ -34,47 +34,45 @@ impl<'a> SitePackages<'a> {
         let mut by_name = FxHashMap::default();
         let mut by_url = FxHashMap::default();
 
-        // Index all installed packages by name.
+        // Index all installed packages by name. Legacy installs (`.egg-info` and `.egg-link`) may be
+        // files rather than directories.
         for entry in fs::read_dir(venv.site_packages())? {
-            let entry = entry?;
-            if entry.file_type()?.is_dir() {
-                let path = entry.path();
+            let path = entry?.path();
 
-                let Some(dist_info) = InstalledDist::try_from_path(&path)
-                    .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
-                else {
-                    continue;
-                };
+            let Some(dist_info) = InstalledDist::try_from_path(&path)
+                .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
+            else {
+                continue;
+            };
 
-                let idx = distributions.len();
+            let idx = distributions.len();
+
+            // Index the distribution by name.
+            if let Some(existing) = by_name.insert(dist_info.name().clone(), idx) {
+                let existing = &distributions[existing];
+                anyhow::bail!(
+                    "Found duplicate package in environment: {} ({} vs. {})",
+                    existing.name(),
+                    existing.path().display(),
+                    path.display()
+                );
+            }
 
-                // Index the distribution by name.
-                if let Some(existing) = by_name.insert(dist_info.name().clone(), idx) {
+            // Index the distribution by URL.
+            if let Some(url) = dist_info.as_editable() {
+                if let Some(existing) = by_url.insert(url.clone(), idx) {
                     let existing = &distributions[existing];
                     anyhow::bail!(
-                        "Found duplicate package in environment: {} ({} vs. {})",
+                        "Found duplicate editable in environment: {} ({} vs. {})",
                         existing.name(),
                         existing.path().display(),
                         path.display()
                     );
                 }
-
-                // Index the distribution by URL.
-                if let Some(url) = dist_info.as_editable() {
-                    if let Some(existing) = by_url.insert(url.clone(), idx) {
-                        let existing = &distributions[existing];
-                        anyhow::bail!(
-                            "Found duplicate editable in environment: {} ({} vs. {})",
-                            existing.name(),
-                            existing.path().display(),
-                            path.display()
-                        );
-                    }
-                }
-
-                // Add the distribution to the database.
-                distributions.push(dist_info);
             }
+
+            // Add the distribution to the database.
+            distributions.push(dist_info);
         }
 
         Ok(Self {
@@ -310,6 +308,53 @@ impl<'a> SitePackages<'a> {
 
         Ok(true)
     }
+
+    /// Returns `true` if the installed packages exactly match the given pinned requirements, such
+    /// that syncing them would be a no-op.
+    ///
+    /// Unlike [`SitePackages::satisfies`], dependencies aren't traversed, and URL requirements must
+    /// match the URL from which the package was installed. Requirements on local paths are never
+    /// considered satisfied, since their contents may have changed since installation. If `exact`
+    /// is `true`, any installed package that isn't required is treated as a mismatch.
+    pub fn satisfies_exactly(&self, requirements: &[Requirement], exact: bool) -> bool {
+        let mut required =
+            FxHashSet::with_capacity_and_hasher(requirements.len(), BuildHasherDefault::default());
+
+        for requirement in requirements {
+            if !requirement.evaluate_markers(self.venv.interpreter().markers(), &[]) {
+                continue;
+            }
+
+            let Some(distribution) = self.get(&requirement.name) else {
+                // The package isn't installed.
+                return false;
+            };
+
+            match &requirement.version_or_url {
+                None | Some(pep508_rs::VersionOrUrl::VersionSpecifier(_)) => {
+                    if !requirement.is_satisfied_by(distribution.version()) {
+                        return false;
+                    }
+                }
+                Some(pep508_rs::VersionOrUrl::Url(url)) => {
+                    let InstalledDist::Url(distribution) = distribution else {
+                        return false;
+                    };
+                    if &distribution.url != url.raw() || url.to_file_path().is_ok() {
+                        return false;
+                    }
+                }
+            }
+
+            required.insert(&requirement.name);
+        }
+
+        !exact
+            || self
+                .distributions
+                .iter()
+                .all(|distribution| required.contains(distribution.name()))
+    }
 }
 
 impl IntoIterator for SitePackages<'_> {
@@ -5,8 +5,16 @@ use distribution_types::InstalledDist;
 /// Uninstall a package from the specified Python environment.
 pub async fn uninstall(dist: &InstalledDist) -> Result<install_wheel_rs::Uninstall> {
     let uninstall = tokio::task::spawn_blocking({
-        let path = dist.path().to_owned();
-        move || install_wheel_rs::uninstall_wheel(&path)
+        let dist = dist.clone();
+        move || match dist {
+            InstalledDist::Registry(_) | InstalledDist::Url(_) => {
+                install_wheel_rs::uninstall_wheel(dist.path())
+            }
+            InstalledDist::EggInfo(_) => install_wheel_rs::uninstall_egg(dist.path()),
+            InstalledDist::LegacyEditable(dist) => {
+                install_wheel_rs::uninstall_legacy_editable(&dist.egg_link, &dist.target)
+            }
+        }
     })
     .await??;
 
@@ -9,6 +9,8 @@ pub struct Configuration {
     pub(crate) virtualenv: bool,
     /// The version of the `gourgeist` package used to create the virtual environment, if any.
     pub(crate) gourgeist: bool,
+    /// Whether the virtual environment was created as relocatable.
+    pub(crate) relocatable: bool,
 }
 
 impl Configuration {
@@ -16,13 +18,14 @@ impl Configuration {
     pub fn parse(cfg: impl AsRef<Path>) -> Result<Self, Error> {
         let mut virtualenv = false;
         let mut gourgeist = false;
+        let mut relocatable = false;
 
         // Per https://snarky.ca/how-virtual-environments-work/, the `pyvenv.cfg` file is not a
         // valid INI file, and is instead expected to be parsed by partitioning each line on the
         // first equals sign.
         let content = fs::read_to_string(&cfg)?;
         for line in content.lines() {
-            let Some((key, _value)) = line.split_once('=') else {
+            let Some((key, value)) = line.split_once('=') else {
                 continue;
             };
             match key.trim() {
@@ -32,6 +35,9 @@ impl Configuration {
                 "gourgeist" => {
                     gourgeist = true;
                 }
+                "relocatable" => {
+                    relocatable = value.trim().eq_ignore_ascii_case("true");
+                }
                 _ => {}
             }
         }
@@ -39,6 +45,7 @@ impl Configuration {
         Ok(Self {
             virtualenv,
             gourgeist,
+            relocatable,
         })
     }
 
@@ -51,6 +58,12 @@ impl Configuration {
     pub fn is_gourgeist(&self) -> bool {
         self.gourgeist
     }
+
+    /// Returns true if the virtual environment was created as relocatable (e.g., via
+    /// `uv venv --relocatable`).
+    pub fn is_relocatable(&self) -> bool {
+        self.relocatable
+    }
 }
 
 #[derive(Debug, Error)]
@@ -32,11 +32,52 @@ markers = {
     "python_version": ".".join(platform.python_version_tuple()[:2]),
     "sys_platform": sys.platform,
 }
+
+
+def get_virtualenv_scheme():
+    """Return the installation scheme of a virtual environment, relative to its root.
+
+    Uses the `venv` scheme where available (Python 3.11+), which is what the interpreter itself
+    uses within a virtual environment, and falls back to the default scheme for the platform.
+    Like pip, headers are installed to `include/site/pythonX.Y` within the environment, rather
+    than to the `include` directory of the base interpreter.
+    """
+    names = sysconfig.get_scheme_names()
+    if "venv" in names:
+        name = "venv"
+    elif os.name == "nt":
+        name = "nt"
+    elif implementation_name == "pypy" and "pypy" in names:
+        name = "pypy"
+    else:
+        name = "posix_prefix"
+
+    prefix = sys.prefix
+    paths = sysconfig.get_paths(
+        scheme=name,
+        vars={
+            "base": prefix,
+            "platbase": prefix,
+            "installed_base": prefix,
+            "installed_platbase": prefix,
+        },
+    )
+    scheme = {
+        key: os.path.relpath(paths[key], prefix)
+        for key in ("purelib", "platlib", "scripts", "data")
+    }
+    scheme["include"] = os.path.join(
+        "include", "site", "python{0}.{1}".format(*sys.version_info[:2])
+    )
+    return scheme
+
+
 interpreter_info = {
     "markers": markers,
     "base_prefix": sys.base_prefix,
     "base_exec_prefix": sys.base_exec_prefix,
     "stdlib": sysconfig.get_path("stdlib"),
     "sys_executable": sys.executable,
+    "scheme": get_virtualenv_scheme(),
 }
 print(json.dumps(interpreter_info))
@@ -12,6 +12,7 @@ use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_host::Platform;
 use platform_tags::{Tags, TagsError};
+use pypi_types::Scheme;
 use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
 use uv_fs::write_atomic_sync;
 
@@ -19,6 +20,9 @@ use crate::python_platform::PythonPlatform;
 use crate::virtual_env::detect_virtual_env;
 use crate::{find_requested_python, Error, PythonVersion};
 
+/// The script used to query an interpreter for its markers, paths, and scheme.
+const QUERY_SCRIPT: &str = include_str!("get_interpreter_info.py");
+
 /// A Python executable and its associated platform markers.
 #[derive(Debug, Clone)]
 pub struct Interpreter {
@@ -28,6 +32,7 @@ pub struct Interpreter {
     pub(crate) base_prefix: PathBuf,
     pub(crate) stdlib: PathBuf,
     pub(crate) sys_executable: PathBuf,
+    pub(crate) scheme: Scheme,
     tags: OnceCell<Tags>,
 }
 
@@ -54,6 +59,7 @@ impl Interpreter {
             base_prefix: info.base_prefix,
             stdlib: info.stdlib,
             sys_executable: info.sys_executable,
+            scheme: info.scheme,
             tags: OnceCell::new(),
         })
     }
@@ -66,6 +72,7 @@ impl Interpreter {
         base_prefix: PathBuf,
         sys_executable: PathBuf,
         stdlib: PathBuf,
+        scheme: Scheme,
     ) -> Self {
         Self {
             platform: PythonPlatform(platform),
@@ -74,6 +81,7 @@ impl Interpreter {
             base_prefix,
             stdlib,
             sys_executable,
+            scheme,
             tags: OnceCell::new(),
         }
     }
@@ -310,6 +318,12 @@ impl Interpreter {
     pub fn sys_executable(&self) -> &Path {
         &self.sys_executable
     }
+
+    /// The installation scheme of a virtual environment created from this interpreter, with
+    /// paths relative to the root of the environment.
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -319,13 +333,14 @@ pub(crate) struct InterpreterQueryResult {
     pub(crate) base_prefix: PathBuf,
     pub(crate) stdlib: PathBuf,
     pub(crate) sys_executable: PathBuf,
+    pub(crate) scheme: Scheme,
 }
 
 impl InterpreterQueryResult {
     /// Return the resolved [`InterpreterQueryResult`] for the given Python executable.
     pub(crate) fn query(interpreter: &Path) -> Result<Self, Error> {
         let output = Command::new(interpreter)
-            .args(["-c", include_str!("get_interpreter_info.py")])
+            .args(["-c", QUERY_SCRIPT])
             .output()
             .map_err(|err| Error::PythonSubcommandLaunch {
                 interpreter: interpreter.to_path_buf(),
@@ -363,14 +378,15 @@ impl InterpreterQueryResult {
     ///
     /// Running a Python script is (relatively) expensive, and the markers won't change
     /// unless the Python executable changes, so we use the executable's last modified
-    /// time as a cache key.
+    /// time as a cache key. The query script itself is also part of the key, such that results
+    /// cached by a version of uv with a different script are never reused.
     pub(crate) fn query_cached(executable: &Path, cache: &Cache) -> Result<Self, Error> {
         let executable_bytes = executable.as_os_str().as_encoded_bytes();
 
         let cache_entry = cache.entry(
             CacheBucket::Interpreter,
             "",
-            format!("{}.msgpack", digest(&executable_bytes)),
+            format!("{}.msgpack", digest(&(executable_bytes, QUERY_SCRIPT))),
         );
 
         let modified = Timestamp::from_path(fs_err::canonicalize(executable)?)?;
@@ -380,7 +396,7 @@ impl InterpreterQueryResult {
             .freshness(&cache_entry, None)
             .is_ok_and(Freshness::is_fresh)
         {
-            if let Ok(data) = fs::read(cache_entry.path()) {
+            if let Ok(data) = fs::read(cache_entry.read_path()) {
                 match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
                     Ok(cached) => {
                         if cached.timestamp == modified {
@@ -393,6 +409,12 @@ impl InterpreterQueryResult {
                             executable.display()
                         );
                     }
+                    Err(err) if cache_entry.is_readonly() => {
+                        warn!(
+                            "Broken cache entry at {}: {err}",
+                            cache_entry.read_path().display()
+                        );
+                    }
                     Err(err) => {
                         warn!(
                             "Broken cache entry at {}, removing: {err}",
@@ -462,7 +484,14 @@ mod tests {
                 "base_exec_prefix": "/home/ferris/.pyenv/versions/3.12.0",
                 "base_prefix": "/home/ferris/.pyenv/versions/3.12.0",
                 "stdlib": "/usr/lib/python3.12",
-                "sys_executable": "/home/ferris/projects/uv/.venv/bin/python"
+                "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
+                "scheme": {
+                    "purelib": "lib/python3.12/site-packages",
+                    "platlib": "lib/python3.12/site-packages",
+                    "scripts": "bin",
+                    "data": ".",
+                    "include": "include/site/python3.12"
+                }
             }
         "##};
 
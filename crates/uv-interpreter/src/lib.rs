@@ -11,6 +11,7 @@ pub use crate::cfg::Configuration;
 pub use crate::interpreter::Interpreter;
 pub use crate::python_query::{find_default_python, find_requested_python};
 pub use crate::python_version::PythonVersion;
+pub use crate::toolchain::{Toolchain, ToolchainRequest, ToolchainVersion, Toolchains};
 pub use crate::virtual_env::Virtualenv;
 
 mod cfg;
@@ -18,6 +19,7 @@ mod interpreter;
 mod python_platform;
 mod python_query;
 mod python_version;
+mod toolchain;
 mod virtual_env;
 
 #[derive(Debug, Error)]
@@ -32,6 +34,8 @@ pub enum Error {
     PythonNotFound,
     #[error("Failed to locate a virtualenv or Conda environment (checked: `VIRTUAL_ENV`, `CONDA_PREFIX`, and `.venv`). Run `uv venv` to create a virtualenv.")]
     NotFound,
+    #[error("The Python interpreter at `{}` is not in a virtualenv. Refusing to modify a system interpreter; run `uv venv` to create a virtualenv.", _0.normalized_display())]
+    SystemInterpreter(PathBuf),
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error("Failed to query python interpreter `{interpreter}`")]
@@ -3,13 +3,16 @@
 use std::env;
 use std::path::PathBuf;
 use std::process::Command;
+use std::str::FromStr;
 
 use once_cell::sync::Lazy;
 use platform_host::Platform;
 use regex::Regex;
-use tracing::{info_span, instrument};
+use tracing::{debug, info_span, instrument};
 use uv_cache::Cache;
+use uv_fs::Normalized;
 
+use crate::toolchain::{ToolchainRequest, Toolchains};
 use crate::{Error, Interpreter};
 
 /// ```text
@@ -33,11 +36,55 @@ static PY_LIST_PATHS: Lazy<Regex> = Lazy::new(|| {
 /// version (e.g. `python3.12` on unix) and error when the version mismatches, as a binary with the
 /// patch version (e.g. `python3.12.1`) is often not in `PATH` and we make the simplifying
 /// assumption that the user has only this one patch version installed.
+///
+/// If no matching system interpreter is found for a version request, we fall back to the managed
+/// toolchains installed via `uv python install`.
 #[instrument]
 pub fn find_requested_python(
     request: &str,
     platform: &Platform,
     cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    let result = find_requested_system_python(request, platform, cache);
+    if matches!(result, Ok(None) | Err(Error::PatchVersionMismatch(..))) {
+        if let Some(interpreter) = find_managed_python(request, platform, cache)? {
+            return Ok(Some(interpreter));
+        }
+    }
+    result
+}
+
+/// Find a managed toolchain matching a version request (e.g., `3.12`), if any.
+fn find_managed_python(
+    request: &str,
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    let Ok(request) = ToolchainRequest::from_str(request) else {
+        return Ok(None);
+    };
+    let Some(toolchains) = Toolchains::from_env() else {
+        return Ok(None);
+    };
+    let Some(toolchain) = toolchains.find(&request)? else {
+        return Ok(None);
+    };
+    debug!(
+        "Using managed toolchain for Python {request}: {}",
+        toolchain.path().normalized_display()
+    );
+    Ok(Some(Interpreter::query(
+        &toolchain.executable(),
+        platform,
+        cache,
+    )?))
+}
+
+/// Find a system Python interpreter of a specific version; see [`find_requested_python`].
+fn find_requested_system_python(
+    request: &str,
+    platform: &Platform,
+    cache: &Cache,
 ) -> Result<Option<Interpreter>, Error> {
     let versions = request
         .splitn(3, '.')
@@ -0,0 +1,283 @@
+//! Discovery of managed Python toolchains, i.e., standalone CPython builds installed via
+//! `uv python install`.
+//!
+//! Each toolchain is unpacked into its own directory under the toolchain root, named after its
+//! implementation and version (e.g., `cpython-3.12.1`).
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use directories::ProjectDirs;
+
+use crate::Error;
+
+/// The prefix of each managed toolchain directory.
+const CPYTHON_PREFIX: &str = "cpython-";
+
+/// The directory in which managed toolchains are installed.
+#[derive(Debug, Clone)]
+pub struct Toolchains {
+    root: PathBuf,
+}
+
+impl Toolchains {
+    /// Use the toolchain directory provided by `UV_TOOLCHAIN_DIR`, falling back to the `toolchains`
+    /// directory of the user data directory (e.g., `~/.local/share/uv/toolchains` on Linux).
+    ///
+    /// Returns `None` if neither is available.
+    pub fn from_env() -> Option<Self> {
+        if let Some(root) = env::var_os("UV_TOOLCHAIN_DIR") {
+            return Some(Self::from_path(root));
+        }
+        let project_dirs = ProjectDirs::from("", "", "uv")?;
+        Some(Self::from_path(project_dirs.data_dir().join("toolchains")))
+    }
+
+    /// Use the given directory as the toolchain root.
+    pub fn from_path(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The root of the toolchain directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The directory into which the toolchain for the given version is (or would be) installed.
+    pub fn install_dir(&self, version: &ToolchainVersion) -> PathBuf {
+        self.root.join(format!("{CPYTHON_PREFIX}{version}"))
+    }
+
+    /// Return all installed toolchains, sorted by version, with the newest first.
+    pub fn find_all(&self) -> Result<Vec<Toolchain>, Error> {
+        let entries = match fs_err::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut toolchains = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let Some(version) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix(CPYTHON_PREFIX))
+                .and_then(|version| ToolchainVersion::from_str(version).ok())
+            else {
+                continue;
+            };
+            let toolchain = Toolchain {
+                path: entry.path(),
+                version,
+            };
+            // Skip any partial or broken installations.
+            if toolchain.executable().is_file() {
+                toolchains.push(toolchain);
+            }
+        }
+        toolchains.sort_by(|a, b| b.version.cmp(&a.version));
+        Ok(toolchains)
+    }
+
+    /// Return the newest installed toolchain that satisfies the given request, if any.
+    pub fn find(&self, request: &ToolchainRequest) -> Result<Option<Toolchain>, Error> {
+        Ok(self
+            .find_all()?
+            .into_iter()
+            .find(|toolchain| request.matches(&toolchain.version)))
+    }
+}
+
+/// An installed, managed toolchain.
+#[derive(Debug, Clone)]
+pub struct Toolchain {
+    path: PathBuf,
+    version: ToolchainVersion,
+}
+
+impl Toolchain {
+    /// The root directory of the toolchain.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The version of the toolchain.
+    pub fn version(&self) -> &ToolchainVersion {
+        &self.version
+    }
+
+    /// The path to the toolchain's Python executable.
+    pub fn executable(&self) -> PathBuf {
+        if cfg!(windows) {
+            self.path.join("python.exe")
+        } else {
+            self.path.join("bin").join("python3")
+        }
+    }
+}
+
+/// The complete version of a managed toolchain (e.g., `3.12.1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ToolchainVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+impl FromStr for ToolchainVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split('.')
+            .map(str::parse::<u8>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("Invalid Python version `{s}`: {err}"))?;
+        let [major, minor, patch] = parts.as_slice() else {
+            return Err(format!(
+                "Invalid Python version `{s}`: expected a major, minor, and patch version"
+            ));
+        };
+        Ok(Self {
+            major: *major,
+            minor: *minor,
+            patch: *patch,
+        })
+    }
+}
+
+impl Display for ToolchainVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A request for a toolchain, with an optional minor and patch version (e.g., `3`, `3.12`, or
+/// `3.12.1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolchainRequest {
+    major: u8,
+    minor: Option<u8>,
+    patch: Option<u8>,
+}
+
+impl ToolchainRequest {
+    /// Returns `true` if the given version satisfies the request.
+    pub fn matches(&self, version: &ToolchainVersion) -> bool {
+        self.major == version.major
+            && self.minor.map_or(true, |minor| minor == version.minor)
+            && self.patch.map_or(true, |patch| patch == version.patch)
+    }
+}
+
+impl FromStr for ToolchainRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split('.')
+            .map(str::parse::<u8>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("Invalid Python version `{s}`: {err}"))?;
+        match parts.as_slice() {
+            [major] => Ok(Self {
+                major: *major,
+                minor: None,
+                patch: None,
+            }),
+            [major, minor] => Ok(Self {
+                major: *major,
+                minor: Some(*minor),
+                patch: None,
+            }),
+            [major, minor, patch] => Ok(Self {
+                major: *major,
+                minor: Some(*minor),
+                patch: Some(*patch),
+            }),
+            _ => Err(format!(
+                "Invalid Python version `{s}`: expected at most a major, minor, and patch version"
+            )),
+        }
+    }
+}
+
+impl Display for ToolchainRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+
+    use super::{ToolchainRequest, ToolchainVersion, Toolchains};
+
+    #[test]
+    fn find_toolchain() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        for version in ["3.11.7", "3.12.0", "3.12.1"] {
+            let bin = if cfg!(windows) {
+                temp_dir.path().join(format!("cpython-{version}"))
+            } else {
+                temp_dir
+                    .path()
+                    .join(format!("cpython-{version}"))
+                    .join("bin")
+            };
+            fs_err::create_dir_all(&bin)?;
+            fs_err::write(
+                bin.join(if cfg!(windows) {
+                    "python.exe"
+                } else {
+                    "python3"
+                }),
+                "",
+            )?;
+        }
+        // A partial installation, without an executable, should be ignored.
+        fs_err::create_dir_all(temp_dir.path().join("cpython-3.13.0"))?;
+
+        let toolchains = Toolchains::from_path(temp_dir.path());
+        let versions = toolchains
+            .find_all()?
+            .into_iter()
+            .map(|toolchain| toolchain.version().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, ["3.12.1", "3.12.0", "3.11.7"]);
+
+        let find = |request: &str| -> Result<Option<ToolchainVersion>> {
+            Ok(toolchains
+                .find(&ToolchainRequest::from_str(request).unwrap())?
+                .map(|toolchain| *toolchain.version()))
+        };
+        assert_eq!(
+            find("3")?,
+            Some(ToolchainVersion::from_str("3.12.1").unwrap())
+        );
+        assert_eq!(
+            find("3.11")?,
+            Some(ToolchainVersion::from_str("3.11.7").unwrap())
+        );
+        assert_eq!(
+            find("3.12.0")?,
+            Some(ToolchainVersion::from_str("3.12.0").unwrap())
+        );
+        assert_eq!(find("3.13")?, None);
+
+        Ok(())
+    }
+}
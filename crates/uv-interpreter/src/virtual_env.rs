@@ -10,7 +10,7 @@ use uv_fs::{LockedFile, Normalized};
 
 use crate::cfg::Configuration;
 use crate::python_platform::PythonPlatform;
-use crate::{Error, Interpreter};
+use crate::{find_requested_python, Error, Interpreter};
 
 /// A Python executable and its associated platform markers.
 #[derive(Debug, Clone)]
@@ -21,11 +21,20 @@ pub struct Virtualenv {
 
 impl Virtualenv {
     /// Venv the current Python executable from the host environment.
+    ///
+    /// In order of precedence, uses the environment at `VIRTUAL_ENV`, then the environment at
+    /// `CONDA_PREFIX`, then the first `.venv` directory in the current directory or its ancestors.
     pub fn from_env(platform: Platform, cache: &Cache) -> Result<Self, Error> {
         let platform = PythonPlatform::from(platform);
         let Some(venv) = detect_virtual_env(&platform)? else {
             return Err(Error::NotFound);
         };
+        Self::from_path(&venv, platform.0, cache)
+    }
+
+    /// Venv the Python executable of an existing virtual environment at the given path.
+    pub fn from_path(venv: &Path, platform: Platform, cache: &Cache) -> Result<Self, Error> {
+        let platform = PythonPlatform::from(platform);
         let venv = fs_err::canonicalize(venv)?;
         let executable = platform.venv_python(&venv);
         let interpreter = Interpreter::query(&executable, &platform.0, cache)?;
@@ -36,6 +45,41 @@ impl Virtualenv {
         })
     }
 
+    /// Venv the virtual environment for a `--python` request, in place of the one detected from
+    /// the host environment.
+    ///
+    /// The request may be the path to a virtual environment, or any request supported by
+    /// [`find_requested_python`] (e.g., `3.12` or `.venv/bin/python`), in which case the
+    /// interpreter must belong to a virtual environment: we refuse to modify a system interpreter.
+    pub fn from_python(request: &str, platform: Platform, cache: &Cache) -> Result<Self, Error> {
+        let path = Path::new(request);
+        if path.join("pyvenv.cfg").is_file() {
+            debug!("Using virtualenv at: {}", path.normalized_display());
+            return Self::from_path(path, platform, cache);
+        }
+
+        let Some(interpreter) = find_requested_python(request, &platform, cache)? else {
+            return Err(Error::NoSuchPython(request.to_string()));
+        };
+
+        // The interpreter of a virtual environment lives in its `bin` (or `Scripts`) directory.
+        let Some(root) = interpreter
+            .sys_executable()
+            .parent()
+            .and_then(Path::parent)
+            .filter(|root| root.join("pyvenv.cfg").is_file())
+        else {
+            return Err(Error::SystemInterpreter(
+                interpreter.sys_executable().to_path_buf(),
+            ));
+        };
+        debug!(
+            "Using virtualenv for `{request}` at: {}",
+            root.normalized_display()
+        );
+        Self::from_path(root, platform, cache)
+    }
+
     /// Creating a new venv from a Python interpreter changes this.
     pub fn from_interpreter(interpreter: Interpreter, venv: &Path) -> Self {
         Self {
@@ -82,6 +126,11 @@ impl Virtualenv {
     }
 
     /// Lock the virtual environment to prevent concurrent writes.
+    ///
+    /// The lock is an advisory lock on a `.lock` file at the root of the environment, held by
+    /// every command that modifies the environment (e.g., `pip sync`, `pip install`, and
+    /// `pip uninstall`), such that concurrent invocations against the same environment are
+    /// serialized rather than interleaved.
     pub fn lock(&self) -> Result<LockedFile, std::io::Error> {
         LockedFile::acquire(self.root.join(".lock"), self.root.normalized_display())
     }
@@ -2,10 +2,14 @@ use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 pub use extra_name::ExtraName;
+pub use group_name::GroupName;
 pub use package_name::PackageName;
+pub use package_name_specifier::PackageNameSpecifier;
 
 mod extra_name;
+mod group_name;
 mod package_name;
+mod package_name_specifier;
 
 /// Validate and normalize an owned package or extra name.
 pub(crate) fn validate_and_normalize_owned(name: String) -> Result<String, InvalidNameError> {
@@ -3,6 +3,8 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use cache_key::{CacheKey, CacheKeyHasher};
+
 use crate::{validate_and_normalize_owned, validate_and_normalize_ref, InvalidNameError};
 
 /// The normalized name of a package.
@@ -96,3 +98,9 @@ impl AsRef<str> for PackageName {
         &self.0
     }
 }
+
+impl CacheKey for PackageName {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.0.cache_key(state);
+    }
+}
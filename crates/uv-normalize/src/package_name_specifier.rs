@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+use crate::{InvalidNameError, PackageName};
+
+/// A package name specifier, as accepted by `--no-binary` and `--only-binary`.
+///
+/// In addition to package names, accepts the special values `:all:` and `:none:`, as in pip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageNameSpecifier {
+    All,
+    None,
+    Package(PackageName),
+}
+
+impl FromStr for PackageNameSpecifier {
+    type Err = InvalidNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            ":all:" => Ok(Self::All),
+            ":none:" => Ok(Self::None),
+            _ => Ok(Self::Package(PackageName::from_str(name)?)),
+        }
+    }
+}
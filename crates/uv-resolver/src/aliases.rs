@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+use std::hash::BuildHasherDefault;
+use std::str::FromStr;
+
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+use pep508_rs::Requirement;
+use uv_normalize::{InvalidNameError, PackageName};
+
+/// A rule declaring that requirements on one package are satisfied by another package, e.g., that
+/// `opencv-python` is provided by `opencv-python-headless`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alias {
+    /// The package that's requested, e.g., `opencv-python`.
+    pub requested: PackageName,
+    /// The package that provides the requested package, e.g., `opencv-python-headless`.
+    pub provider: PackageName,
+}
+
+impl CacheKey for Alias {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.requested.cache_key(state);
+        self.provider.cache_key(state);
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AliasError {
+    #[error("Expected an alias of the form `<requested>=<provider>`, but found: `{0}`")]
+    MissingSeparator(String),
+    #[error(transparent)]
+    InvalidName(#[from] InvalidNameError),
+}
+
+impl FromStr for Alias {
+    type Err = AliasError;
+
+    /// Parse an alias of the form `<requested>=<provider>`, e.g., `requests=acme-requests`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (requested, provider) = s
+            .split_once('=')
+            .ok_or_else(|| AliasError::MissingSeparator(s.to_string()))?;
+        Ok(Self {
+            requested: PackageName::from_str(requested.trim())?,
+            provider: PackageName::from_str(provider.trim())?,
+        })
+    }
+}
+
+/// A set of aliases, mapping requested packages to the packages that provide them.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Aliases(FxHashMap<PackageName, PackageName>);
+
+impl Aliases {
+    /// Create a new set of aliases from a set of rules.
+    pub(crate) fn from_aliases(aliases: Vec<Alias>) -> Self {
+        let mut map =
+            FxHashMap::with_capacity_and_hasher(aliases.len(), BuildHasherDefault::default());
+        for alias in aliases {
+            map.insert(alias.requested, alias.provider);
+        }
+        Self(map)
+    }
+
+    /// Get the provider for a requested package, if it's aliased.
+    pub(crate) fn get(&self, name: &PackageName) -> Option<&PackageName> {
+        self.0.get(name)
+    }
+
+    /// Return the requested packages that are provided by the given package, in sorted order.
+    pub(crate) fn provided_by(&self, provider: &PackageName) -> Vec<&PackageName> {
+        let mut requested = self
+            .0
+            .iter()
+            .filter(|(_, candidate)| *candidate == provider)
+            .map(|(requested, _)| requested)
+            .collect::<Vec<_>>();
+        requested.sort_unstable();
+        requested
+    }
+
+    /// Apply the aliases to a requirement, replacing the requested package with its provider while
+    /// retaining any extras, version specifiers, and markers.
+    pub(crate) fn apply<'a>(&self, requirement: &'a Requirement) -> Cow<'a, Requirement> {
+        if let Some(provider) = self.get(&requirement.name) {
+            Cow::Owned(Requirement {
+                name: provider.clone(),
+                ..requirement.clone()
+            })
+        } else {
+            Cow::Borrowed(requirement)
+        }
+    }
+}
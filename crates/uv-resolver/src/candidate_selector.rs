@@ -19,6 +19,7 @@ pub(crate) struct CandidateSelector {
     resolution_strategy: ResolutionStrategy,
     prerelease_strategy: PreReleaseStrategy,
     preferences: Preferences,
+    prefer_binary: bool,
 }
 
 impl CandidateSelector {
@@ -34,6 +35,7 @@ impl CandidateSelector {
                 manifest.requirements.as_slice(),
             ),
             preferences: Preferences::from(manifest.preferences.as_slice()),
+            prefer_binary: options.prefer_binary,
         }
     }
 
@@ -136,16 +138,45 @@ impl CandidateSelector {
             range,
             version_map.len()
         );
+
+        // If binaries are preferred, select the best version with a compatible wheel, falling
+        // back to the best version overall (e.g., a source-only release) if there is none.
+        if self.prefer_binary {
+            if let Some(candidate) =
+                self.select_with_strategy(package_name, range, version_map, allow_prerelease, true)
+            {
+                return Some(candidate);
+            }
+        }
+
+        self.select_with_strategy(package_name, range, version_map, allow_prerelease, false)
+    }
+
+    /// Select a [`Candidate`] according to the resolution strategy, optionally considering only
+    /// versions with a compatible wheel.
+    fn select_with_strategy<'a>(
+        &'a self,
+        package_name: &'a PackageName,
+        range: &'a Range<Version>,
+        version_map: &'a VersionMap,
+        allow_prerelease: AllowPreRelease,
+        binary_only: bool,
+    ) -> Option<Candidate<'a>> {
         match &self.resolution_strategy {
             ResolutionStrategy::Highest => Self::select_candidate(
                 version_map.iter().rev(),
                 package_name,
                 range,
                 allow_prerelease,
+                binary_only,
+            ),
+            ResolutionStrategy::Lowest => Self::select_candidate(
+                version_map.iter(),
+                package_name,
+                range,
+                allow_prerelease,
+                binary_only,
             ),
-            ResolutionStrategy::Lowest => {
-                Self::select_candidate(version_map.iter(), package_name, range, allow_prerelease)
-            }
             ResolutionStrategy::LowestDirect(direct_dependencies) => {
                 if direct_dependencies.contains(package_name) {
                     Self::select_candidate(
@@ -153,6 +184,7 @@ impl CandidateSelector {
                         package_name,
                         range,
                         allow_prerelease,
+                        binary_only,
                     )
                 } else {
                     Self::select_candidate(
@@ -160,6 +192,7 @@ impl CandidateSelector {
                         package_name,
                         range,
                         allow_prerelease,
+                        binary_only,
                     )
                 }
             }
@@ -168,11 +201,14 @@ impl CandidateSelector {
 
     /// Select the first-matching [`Candidate`] from a set of candidate versions and files,
     /// preferring wheels over source distributions.
+    ///
+    /// If `binary_only` is set, versions without a compatible wheel are skipped.
     fn select_candidate<'a>(
         versions: impl Iterator<Item = (&'a Version, VersionMapDistHandle<'a>)>,
         package_name: &'a PackageName,
         range: &Range<Version>,
         allow_prerelease: AllowPreRelease,
+        binary_only: bool,
     ) -> Option<Candidate<'a>> {
         #[derive(Debug)]
         enum PreReleaseCandidate<'a> {
@@ -185,6 +221,14 @@ impl CandidateSelector {
         for (version, maybe_dist) in versions {
             steps += 1;
 
+            if binary_only
+                && !maybe_dist
+                    .prioritized_dist()
+                    .is_some_and(|dist| dist.compatible_wheel().is_some())
+            {
+                continue;
+            }
+
             let dist = if version.any_prerelease() {
                 if range.contains(version) {
                     match allow_prerelease {
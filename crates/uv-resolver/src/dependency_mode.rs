@@ -1,3 +1,5 @@
+use cache_key::{CacheKey, CacheKeyHasher};
+
 #[derive(Debug, Default, Clone, Copy)]
 pub enum DependencyMode {
     /// Include all dependencies, whether direct or transitive.
@@ -7,6 +9,15 @@ pub enum DependencyMode {
     Direct,
 }
 
+impl CacheKey for DependencyMode {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Transitive => 0u8.cache_key(state),
+            Self::Direct => 1u8.cache_key(state),
+        }
+    }
+}
+
 impl DependencyMode {
     /// Returns `true` if transitive dependencies should be included.
     pub fn is_transitive(self) -> bool {
@@ -4,6 +4,7 @@ use std::fmt::Formatter;
 
 use dashmap::{DashMap, DashSet};
 use indexmap::IndexMap;
+use itertools::Itertools;
 use pubgrub::range::Range;
 use pubgrub::report::{DefaultStringReporter, DerivationTree, Reporter};
 use rustc_hash::FxHashMap;
@@ -81,11 +82,48 @@ pub enum ResolveError {
         version: Box<Version>,
     },
 
+    #[error(
+        "The resolution doesn't fork on Python-version markers, so it can't satisfy every version in the requires-python range:\n{}",
+        .0.iter().map(|omitted| format!("  - {omitted}")).join("\n")
+    )]
+    OmittedRequirements(Vec<OmittedRequirement>),
+
     /// Something unexpected happened.
     #[error("{0}")]
     Failure(String),
 }
 
+/// A requirement whose markers don't apply to the target Python version, but do apply to another
+/// version in the `requires-python` range.
+#[derive(Debug)]
+pub struct OmittedRequirement {
+    /// The package that declared the requirement, or `None` for a top-level requirement.
+    /// For example, `black==23.10.0`.
+    pub(crate) dependent: Option<(PackageName, Version)>,
+    /// The requirement that was omitted. For example, `tomli; python_version >= "3.11"`.
+    pub(crate) requirement: Requirement,
+    /// The lowest Python version in the `requires-python` range to which the requirement
+    /// applies. For example, `3.11`.
+    pub(crate) python_version: Version,
+}
+
+impl std::fmt::Display for OmittedRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.dependent {
+            Some((name, version)) => write!(
+                f,
+                "The dependency `{}` of `{name}=={version}` applies to Python {}",
+                self.requirement, self.python_version
+            ),
+            None => write!(
+                f,
+                "The requirement `{}` applies to Python {}",
+                self.requirement, self.python_version
+            ),
+        }
+    }
+}
+
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for ResolveError {
     /// Drop the value we want to send to not leak the private type we're sending.
     /// The tokio error only says "channel closed", so we don't lose information.
@@ -252,6 +290,14 @@ impl NoSolutionError {
         self
     }
 
+    /// Returns `true` if any of the packages involved in the error were unavailable because the
+    /// network was disabled.
+    pub fn is_offline(&self) -> bool {
+        self.unavailable_packages
+            .values()
+            .any(|reason| matches!(reason, UnavailablePackage::Offline))
+    }
+
     /// Update the Python requirements attached to the error.
     #[must_use]
     pub(crate) fn with_python_requirement(
@@ -67,11 +67,14 @@ impl<'a> DistFinder<'a> {
         match requirement.version_or_url.as_ref() {
             None | Some(VersionOrUrl::VersionSpecifier(_)) => {
                 // Query the index(es) (cached) to get the URLs for the available files.
-                let (index, raw_metadata) = self.client.simple(&requirement.name).await?;
-                let metadata = OwnedArchive::deserialize(&raw_metadata);
-
-                // Pick a version that satisfies the requirement.
-                let Some(dist) = self.select(requirement, metadata, &index, flat_index) else {
+                let results = self.client.simple(&requirement.name).await?;
+
+                // Pick a version that satisfies the requirement, from the first index (in order
+                // of priority) that contains one.
+                let Some(dist) = results.iter().find_map(|(index, raw_metadata)| {
+                    let metadata = OwnedArchive::deserialize(raw_metadata);
+                    self.select(requirement, metadata, index, flat_index)
+                }) else {
                     return Err(ResolveError::NotFound(requirement.clone()));
                 };
 
@@ -1,15 +1,18 @@
+pub use aliases::{Alias, AliasError};
 pub use dependency_mode::DependencyMode;
-pub use error::ResolveError;
+pub use error::{NoSolutionError, OmittedRequirement, ResolveError};
 pub use finder::{DistFinder, Reporter as FinderReporter};
 pub use manifest::Manifest;
 pub use options::{Options, OptionsBuilder};
 pub use prerelease_mode::PreReleaseMode;
-pub use resolution::{Diagnostic, DisplayResolutionGraph, ResolutionGraph};
+pub use python_requirement::requires_python_versions;
+pub use resolution::{Dependency, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, InMemoryIndex, Reporter as ResolverReporter, Resolver, ResolverProvider,
 };
 
+mod aliases;
 mod candidate_selector;
 mod dependency_mode;
 mod error;
@@ -3,6 +3,8 @@ use pep508_rs::Requirement;
 use pypi_types::Metadata21;
 use uv_normalize::PackageName;
 
+use crate::aliases::Alias;
+
 /// A manifest of requirements, constraints, and preferences.
 #[derive(Debug)]
 pub struct Manifest {
@@ -12,6 +14,7 @@ pub struct Manifest {
     pub(crate) preferences: Vec<Requirement>,
     pub(crate) project: Option<PackageName>,
     pub(crate) editables: Vec<(LocalEditable, Metadata21)>,
+    pub(crate) aliases: Vec<Alias>,
 }
 
 impl Manifest {
@@ -30,6 +33,7 @@ impl Manifest {
             preferences,
             project,
             editables,
+            aliases: Vec::new(),
         }
     }
 
@@ -41,6 +45,14 @@ impl Manifest {
             preferences: Vec::new(),
             project: None,
             editables: Vec::new(),
+            aliases: Vec::new(),
         }
     }
+
+    /// Set the aliases to apply during resolution, such that requirements on the requested package
+    /// are satisfied by the provider instead.
+    #[must_use]
+    pub fn with_aliases(self, aliases: Vec<Alias>) -> Self {
+        Self { aliases, ..self }
+    }
 }
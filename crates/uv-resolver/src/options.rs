@@ -8,6 +8,7 @@ pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
+    pub prefer_binary: bool,
     pub exclude_newer: Option<DateTime<Utc>>,
 }
 
@@ -17,6 +18,7 @@ pub struct OptionsBuilder {
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    prefer_binary: bool,
     exclude_newer: Option<DateTime<Utc>>,
 }
 
@@ -47,6 +49,13 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets whether to prefer versions with a compatible wheel over newer source-only versions.
+    #[must_use]
+    pub fn prefer_binary(mut self, prefer_binary: bool) -> Self {
+        self.prefer_binary = prefer_binary;
+        self
+    }
+
     /// Sets the exclusion date.
     #[must_use]
     pub fn exclude_newer(mut self, exclude_newer: Option<DateTime<Utc>>) -> Self {
@@ -60,6 +69,7 @@ impl OptionsBuilder {
             resolution_mode: self.resolution_mode,
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
+            prefer_binary: self.prefer_binary,
             exclude_newer: self.exclude_newer,
         }
     }
@@ -1,5 +1,6 @@
 use rustc_hash::FxHashSet;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use pep508_rs::{Requirement, VersionOrUrl};
 use uv_normalize::PackageName;
 
@@ -25,6 +26,18 @@ pub enum PreReleaseMode {
     IfNecessaryOrExplicit,
 }
 
+impl CacheKey for PreReleaseMode {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Disallow => 0u8.cache_key(state),
+            Self::Allow => 1u8.cache_key(state),
+            Self::IfNecessary => 2u8.cache_key(state),
+            Self::Explicit => 3u8.cache_key(state),
+            Self::IfNecessaryOrExplicit => 4u8.cache_key(state),
+        }
+    }
+}
+
 /// Like [`PreReleaseMode`], but with any additional information required to select a candidate,
 /// like the set of direct dependencies.
 #[derive(Debug, Clone)]
@@ -7,6 +7,7 @@ use tracing::warn;
 use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
 use uv_normalize::{ExtraName, PackageName};
 
+use crate::aliases::Aliases;
 use crate::overrides::Overrides;
 use crate::pubgrub::specifier::PubGrubSpecifier;
 use crate::pubgrub::PubGrubPackage;
@@ -21,6 +22,7 @@ impl PubGrubDependencies {
         requirements: &[Requirement],
         constraints: &[Requirement],
         overrides: &Overrides,
+        aliases: &Aliases,
         extra: Option<&ExtraName>,
         source: Option<&PackageName>,
         env: &MarkerEnvironment,
@@ -29,6 +31,10 @@ impl PubGrubDependencies {
 
         // Iterate over all declared requirements.
         for requirement in overrides.apply(requirements) {
+            // If the requirement is satisfied by another package, depend on that package instead.
+            let requirement = aliases.apply(requirement);
+            let requirement = requirement.as_ref();
+
             // Avoid self-dependencies.
             if source.is_some_and(|source| source == &requirement.name) {
                 warn!("{} has a dependency on itself", requirement.name);
@@ -80,6 +86,10 @@ impl PubGrubDependencies {
                 continue;
             }
 
+            // If the constraint is satisfied by another package, constrain that package instead.
+            let constraint = aliases.apply(constraint);
+            let constraint = constraint.as_ref();
+
             // Avoid self-dependencies.
             if source.is_some_and(|source| source == &constraint.name) {
                 warn!("{} has a dependency on itself", constraint.name);
@@ -133,6 +143,11 @@ impl PubGrubDependencies {
         self.0.insert(package, version)
     }
 
+    /// Remove a [`PubGrubPackage`] from the set of dependencies, returning its version range.
+    pub(crate) fn remove(&mut self, package: &PubGrubPackage) -> Option<Range<Version>> {
+        self.0.remove(package)
+    }
+
     /// Iterate over the dependencies.
     pub(crate) fn iter(&self) -> impl Iterator<Item = (&PubGrubPackage, &Range<Version>)> {
         self.0.iter()
@@ -24,7 +24,14 @@ impl TryFrom<&VersionSpecifier> for PubGrubSpecifier {
         let ranges = match specifier.operator() {
             Operator::Equal => {
                 let version = specifier.version().clone();
-                Range::singleton(version)
+                if version.local().is_empty() {
+                    // Per PEP 440, a specifier without a local version ignores the local version
+                    // of the candidate, such that `==1.0` is satisfied by `1.0+cu118`.
+                    let upper = local_upper_bound(&version);
+                    Range::from_range_bounds(version..upper)
+                } else {
+                    Range::singleton(version)
+                }
             }
             Operator::ExactEqual => {
                 let version = specifier.version().clone();
@@ -32,7 +39,12 @@ impl TryFrom<&VersionSpecifier> for PubGrubSpecifier {
             }
             Operator::NotEqual => {
                 let version = specifier.version().clone();
-                Range::singleton(version).complement()
+                if version.local().is_empty() {
+                    let upper = local_upper_bound(&version);
+                    Range::from_range_bounds(version..upper).complement()
+                } else {
+                    Range::singleton(version).complement()
+                }
             }
             Operator::TildeEqual => {
                 let [rest @ .., last, _] = specifier.version().release() else {
@@ -49,14 +61,18 @@ impl TryFrom<&VersionSpecifier> for PubGrubSpecifier {
                 Range::strictly_lower_than(version)
             }
             Operator::LessThanEqual => {
+                // Include any local versions of the given version (e.g., `<=1.0` allows
+                // `1.0+cu118`).
                 let version = specifier.version().clone();
-                Range::lower_than(version)
+                Range::strictly_lower_than(local_upper_bound(&version))
             }
             Operator::GreaterThan => {
                 // Per PEP 440: "The exclusive ordered comparison >V MUST NOT allow a post-release of
                 // the given version unless V itself is a post release."
+                //
+                // Similarly, local versions are ignored, so `>1.0` doesn't allow `1.0+cu118`.
                 let version = specifier.version().clone();
-                Range::strictly_higher_than(version)
+                Range::higher_than(local_upper_bound(&version))
             }
             Operator::GreaterThanEqual => {
                 let version = specifier.version().clone();
@@ -101,3 +117,73 @@ impl TryFrom<&VersionSpecifier> for PubGrubSpecifier {
         Ok(Self(ranges))
     }
 }
+
+/// Return the smallest version that's greater than the given version and all of its local
+/// versions, i.e., such that `version..upper` contains `1.0`, `1.0+cu118`, `1.0+cpu`, etc.
+///
+/// Local versions sort immediately after their public version, and before the next development
+/// release (`1.0.dev1+local < 1.0.dev2`) or the first post-release (`1.0+local < 1.0.post0.dev0`).
+fn local_upper_bound(version: &Version) -> Version {
+    let version = version.clone().without_local();
+    if let Some(dev) = version.dev() {
+        version.with_dev(Some(dev + 1))
+    } else {
+        let post = version.post().map_or(0, |post| post + 1);
+        version.with_post(Some(post)).with_dev(Some(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pubgrub::range::Range;
+
+    use pep440_rs::{Version, VersionSpecifier};
+
+    use super::PubGrubSpecifier;
+
+    fn range(specifier: &str) -> Range<Version> {
+        let specifier = VersionSpecifier::from_str(specifier).unwrap();
+        PubGrubSpecifier::try_from(&specifier).unwrap().into()
+    }
+
+    fn version(version: &str) -> Version {
+        Version::from_str(version).unwrap()
+    }
+
+    #[test]
+    fn local_versions() {
+        // A specifier without a local version matches any local version.
+        assert!(range("==1.0").contains(&version("1.0")));
+        assert!(range("==1.0").contains(&version("1.0+cu118")));
+        assert!(!range("==1.0").contains(&version("1.0.post0")));
+        assert!(!range("==1.0").contains(&version("1.0.1")));
+        assert!(range("==1.0a1").contains(&version("1.0a1+cpu")));
+        assert!(!range("==1.0a1").contains(&version("1.0a1.post0")));
+        assert!(range("==1.0.post1").contains(&version("1.0.post1+cpu")));
+        assert!(range("==1.0.dev1").contains(&version("1.0.dev1+cpu")));
+        assert!(!range("==1.0.dev1").contains(&version("1.0.dev2")));
+        assert!(!range("!=1.0").contains(&version("1.0+cu118")));
+        assert!(range("<=1.0").contains(&version("1.0+cu118")));
+        assert!(!range(">1.0").contains(&version("1.0+cu118")));
+        assert!(range(">1.0").contains(&version("1.0.1")));
+
+        // A specifier with a local version only matches that exact version.
+        assert!(range("==1.0+cu118").contains(&version("1.0+cu118")));
+        assert!(!range("==1.0+cu118").contains(&version("1.0")));
+        assert!(!range("==1.0+cu118").contains(&version("1.0+cpu")));
+    }
+
+    #[test]
+    fn epochs() {
+        assert!(range("==1!2.0").contains(&version("1!2.0+cu118")));
+        assert!(!range("==1!2.0").contains(&version("2.0")));
+        assert!(range(">=1.0").contains(&version("1!0.5")));
+        assert!(!range("<3.0").contains(&version("1!2.0")));
+        assert!(range("~=1!2.0").contains(&version("1!2.5")));
+        assert!(!range("~=1!2.0").contains(&version("1!3.0")));
+        assert!(range("==1!2.*").contains(&version("1!2.1+cpu")));
+        assert!(!range("==1!2.*").contains(&version("2.1")));
+    }
+}
@@ -1,9 +1,12 @@
+use std::str::FromStr;
+
 use distribution_types::{CompatibleDist, Dist};
-use pep440_rs::{Version, VersionSpecifiers};
-use pep508_rs::MarkerEnvironment;
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{MarkerEnvironment, MarkerTree, MarkerValue, Requirement, StringVersion};
 use uv_interpreter::Interpreter;
+use uv_normalize::ExtraName;
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PythonRequirement {
     /// The installed version of Python.
     installed: Version,
@@ -11,6 +14,8 @@ pub struct PythonRequirement {
     /// dependencies. This is typically the same as the installed version, but may be different
     /// when specifying an alternate Python version for the resolution.
     target: Version,
+    /// The `requires-python` range for which the resolution must be valid, if any.
+    range: Option<VersionSpecifiers>,
 }
 
 impl PythonRequirement {
@@ -18,6 +23,17 @@ impl PythonRequirement {
         Self {
             installed: interpreter.python_version().clone(),
             target: markers.python_full_version.version.clone(),
+            range: None,
+        }
+    }
+
+    /// Require that the resolution is valid for every Python version in the given
+    /// `requires-python` range, rather than only the target version.
+    #[must_use]
+    pub fn with_range(self, range: VersionSpecifiers) -> Self {
+        Self {
+            range: Some(range),
+            ..self
         }
     }
 
@@ -31,6 +47,53 @@ impl PythonRequirement {
         &self.target
     }
 
+    /// Return the `requires-python` range for which we are resolving dependencies, if any.
+    pub(crate) fn range(&self) -> Option<&VersionSpecifiers> {
+        self.range.as_ref()
+    }
+
+    /// Returns `true` if the given version specifiers cover the entire `requires-python` range.
+    fn covers_range(&self, requires_python: &VersionSpecifiers) -> bool {
+        self.range.as_ref().map_or(true, |range| {
+            let bounds = range.iter().chain(requires_python.iter());
+            candidate_versions(bounds.map(VersionSpecifier::version))
+                .into_iter()
+                .filter(|version| range.contains(version))
+                .all(|version| requires_python.contains(&version))
+        })
+    }
+
+    /// If the requirement doesn't apply to the target Python version, but does apply to another
+    /// version in the `requires-python` range, return the lowest such version.
+    ///
+    /// Markers are only evaluated for the target version, so such requirements are omitted from
+    /// the resolution, even though they're required elsewhere in the range.
+    pub(crate) fn omits(
+        &self,
+        requirement: &Requirement,
+        extras: &[ExtraName],
+        markers: &MarkerEnvironment,
+    ) -> Option<Version> {
+        let range = self.range.as_ref()?;
+        let marker = requirement.marker.as_ref()?;
+        if requirement.evaluate_markers(markers, extras) {
+            return None;
+        }
+
+        // Consider the bounds of the range, along with any Python versions named in the markers
+        // (e.g., `python_version >= "3.11"`).
+        let mut bounds = range
+            .iter()
+            .map(|specifier| specifier.version().clone())
+            .collect::<Vec<_>>();
+        marker_versions(marker, &mut bounds);
+
+        candidate_versions(&bounds)
+            .into_iter()
+            .filter(|version| range.contains(version))
+            .find(|version| requirement.evaluate_markers(&python_markers(markers, version), extras))
+    }
+
     /// If the dist doesn't match the given Python requirement, return the version specifiers.
     pub(crate) fn validate_dist<'a>(
         &self,
@@ -45,6 +108,12 @@ impl PythonRequirement {
             return Some(requires_python);
         }
 
+        // If the dist doesn't support the entire `requires-python` range, return the failing
+        // version specifiers.
+        if !self.covers_range(requires_python) {
+            return Some(requires_python);
+        }
+
         // If the dist is a source distribution, and doesn't support the installed Python
         // version, return the failing version specifiers, since we won't be able to build it.
         if matches!(dist.for_installation().dist, Dist::Source(_)) {
@@ -69,3 +138,138 @@ impl PythonRequirement {
         None
     }
 }
+
+/// Return the marker environment for the given Python version, based on the given environment.
+fn python_markers(base: &MarkerEnvironment, version: &Version) -> MarkerEnvironment {
+    let mut markers = base.clone();
+
+    // Ex) `python_full_version == "3.12.0"`
+    let full_version = StringVersion {
+        string: version.to_string(),
+        version: version.clone(),
+    };
+    if markers.implementation_name == "cpython" {
+        markers.implementation_version = full_version.clone();
+    }
+    markers.python_full_version = full_version;
+
+    // Ex) `python_version == "3.12"`
+    let minor = Version::new(version.release().iter().take(2));
+    markers.python_version = StringVersion {
+        string: minor.to_string(),
+        version: minor,
+    };
+
+    markers
+}
+
+/// Collect the Python versions compared against in the given markers (e.g., `3.11` for
+/// `python_version >= "3.11"`).
+fn marker_versions(marker: &MarkerTree, versions: &mut Vec<Version>) {
+    match marker {
+        MarkerTree::Expression(expression) => {
+            let value = match (&expression.l_value, &expression.r_value) {
+                (MarkerValue::MarkerEnvVersion(_), MarkerValue::QuotedString(value))
+                | (MarkerValue::QuotedString(value), MarkerValue::MarkerEnvVersion(_)) => value,
+                _ => return,
+            };
+            // Ex) `python_version in "3.8 3.9"`
+            versions.extend(
+                value
+                    .split_whitespace()
+                    .filter_map(|version| Version::from_str(version).ok()),
+            );
+        }
+        MarkerTree::And(trees) | MarkerTree::Or(trees) => {
+            for tree in trees {
+                marker_versions(tree, versions);
+            }
+        }
+    }
+}
+
+/// Return the versions of Python 3 at which a range bounded by the given versions can start or
+/// stop matching, in ascending order.
+///
+/// Within each minor release, a range can only change at `3.{minor}`, at a bound, or at the patch
+/// release following a bound (e.g., `3.8.11` for `>3.8.10`). The minor release following the
+/// highest bound is included too, such that ranges without an upper bound (e.g., `>=3.13`) are
+/// represented.
+fn candidate_versions<'a>(bounds: impl IntoIterator<Item = &'a Version>) -> Vec<Version> {
+    let bounds = bounds
+        .into_iter()
+        .filter_map(|version| match version.release() {
+            [3] => Some((0, 0)),
+            [3, minor] => Some((*minor, 0)),
+            [3, minor, patch, ..] => Some((*minor, *patch)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let latest_minor = bounds.iter().map(|(minor, _)| *minor).max().unwrap_or(0) + 1;
+
+    (0..=latest_minor)
+        .flat_map(|minor| {
+            let mut patches = bounds
+                .iter()
+                .filter(|(bound, _)| *bound == minor)
+                .flat_map(|(_, patch)| [*patch, patch + 1])
+                .chain([0])
+                .collect::<Vec<_>>();
+            patches.sort_unstable();
+            patches.dedup();
+            patches.into_iter().map(move |patch| {
+                if patch == 0 {
+                    Version::new([3, minor])
+                } else {
+                    Version::new([3, minor, patch])
+                }
+            })
+        })
+        .collect()
+}
+
+/// Return the lowest version of each minor release of Python 3 covered by the given
+/// `requires-python` specifiers, in ascending order (e.g., `3.8`, `3.9`, for `>=3.8`).
+pub fn requires_python_versions(requires_python: &VersionSpecifiers) -> Vec<Version> {
+    let mut versions: Vec<Version> = Vec::new();
+    for version in candidate_versions(requires_python.iter().map(VersionSpecifier::version)) {
+        if !requires_python.contains(&version) {
+            continue;
+        }
+        if versions
+            .last()
+            .is_some_and(|last| last.release()[1] == version.release()[1])
+        {
+            continue;
+        }
+        versions.push(version);
+    }
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep440_rs::{Version, VersionSpecifiers};
+
+    use super::requires_python_versions;
+
+    fn versions(requires_python: &str) -> Vec<String> {
+        requires_python_versions(&VersionSpecifiers::from_str(requires_python).unwrap())
+            .iter()
+            .map(Version::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn lowest_versions() {
+        assert_eq!(versions(">=3.8, <3.10"), ["3.8", "3.9"]);
+        assert_eq!(versions(">=3.8.10, <3.10"), ["3.8.10", "3.9"]);
+        assert_eq!(versions(">3.8.10, !=3.8.11, <3.10"), ["3.8.12", "3.9"]);
+        assert_eq!(versions("~=3.11.2"), ["3.11.2"]);
+        assert_eq!(versions(">=3.13"), ["3.13", "3.14"]);
+        assert_eq!(versions(">=3.31"), ["3.31", "3.32"]);
+        assert!(versions("<3").is_empty());
+    }
+}
@@ -16,10 +16,11 @@ use url::Url;
 use distribution_types::{Dist, DistributionMetadata, LocalEditable, Name, PackageId, Verbatim};
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pep508_rs::VerbatimUrl;
-use pypi_types::{Hashes, Metadata21};
+use pep508_rs::{MarkerTree, VerbatimUrl};
+use pypi_types::{DeclaredLicense, HashAlgorithm, Hashes, Metadata21};
 use uv_normalize::{ExtraName, PackageName};
 
+use crate::aliases::Aliases;
 use crate::pins::FilePins;
 use crate::pubgrub::{PubGrubDistribution, PubGrubPackage, PubGrubPriority};
 use crate::resolver::VersionsResponse;
@@ -31,13 +32,17 @@ use crate::ResolveError;
 #[derive(Debug)]
 pub struct ResolutionGraph {
     /// The underlying graph.
-    petgraph: petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed>,
+    petgraph: petgraph::graph::Graph<Dist, Dependency, petgraph::Directed>,
     /// The metadata for every distribution in this resolution.
     hashes: FxHashMap<PackageName, Vec<Hashes>>,
     /// The set of editable requirements in this resolution.
     editables: FxHashMap<PackageName, (LocalEditable, Metadata21)>,
+    /// The declared license of every distribution in this resolution, if known.
+    licenses: FxHashMap<PackageName, DeclaredLicense>,
     /// Any diagnostics that were encountered while building the graph.
     diagnostics: Vec<Diagnostic>,
+    /// The aliases that were applied during resolution.
+    aliases: Aliases,
 }
 
 impl ResolutionGraph {
@@ -50,12 +55,14 @@ impl ResolutionGraph {
         redirects: &DashMap<Url, Url>,
         state: &State<PubGrubPackage, Range<Version>, PubGrubPriority>,
         editables: FxHashMap<PackageName, (LocalEditable, Metadata21)>,
+        aliases: Aliases,
     ) -> Result<Self, ResolveError> {
         // TODO(charlie): petgraph is a really heavy and unnecessary dependency here. We should
         // write our own graph, given that our requirements are so simple.
         let mut petgraph = petgraph::graph::Graph::with_capacity(selection.len(), selection.len());
         let mut hashes =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
+        let mut licenses = FxHashMap::default();
         let mut diagnostics = Vec::new();
 
         // Add every package to the graph.
@@ -81,6 +88,15 @@ impl ResolutionGraph {
                         }
                     }
 
+                    // Record its license, if declared.
+                    let dist = PubGrubDistribution::from_registry(package_name, version);
+                    if let Some(license) = distributions
+                        .get(&dist.package_id())
+                        .and_then(|metadata| metadata.declared_license())
+                    {
+                        licenses.insert(package_name.clone(), license);
+                    }
+
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
                     inverse.insert(package_name, index);
@@ -108,6 +124,19 @@ impl ResolutionGraph {
                         }
                     }
 
+                    // Record its license, if declared.
+                    let license = if let Some((_, metadata)) = editables.get(package_name) {
+                        metadata.declared_license()
+                    } else {
+                        let dist = PubGrubDistribution::from_url(package_name, url);
+                        distributions
+                            .get(&dist.package_id())
+                            .and_then(|metadata| metadata.declared_license())
+                    };
+                    if let Some(license) = license {
+                        licenses.insert(package_name.clone(), license);
+                    }
+
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
                     inverse.insert(package_name, index);
@@ -168,7 +197,8 @@ impl ResolutionGraph {
                     let PubGrubPackage::Package(self_package, _, _) = self_package else {
                         continue;
                     };
-                    let PubGrubPackage::Package(dependency_package, _, _) = dependency_package
+                    let PubGrubPackage::Package(dependency_package, dependency_extra, _) =
+                        dependency_package
                     else {
                         continue;
                     };
@@ -179,13 +209,69 @@ impl ResolutionGraph {
                     }
 
                     if self_version.contains(version) {
-                        let self_index = &inverse[self_package];
-                        let dependency_index = &inverse[dependency_package];
-                        petgraph.update_edge(
-                            *self_index,
-                            *dependency_index,
-                            dependency_range.clone(),
-                        );
+                        let self_index = inverse[self_package];
+                        let dependency_index = inverse[dependency_package];
+
+                        // Recover the markers from the dependent's declared requirements.
+                        let markers = match package {
+                            PubGrubPackage::Package(package_name, _, url)
+                                if package_name == self_package =>
+                            {
+                                if let Some((_, metadata)) = editables.get(package_name) {
+                                    dependency_markers(
+                                        metadata,
+                                        dependency_package,
+                                        dependency_extra.as_ref(),
+                                    )
+                                } else {
+                                    let package_id = match url {
+                                        Some(url) => {
+                                            PubGrubDistribution::from_url(package_name, url)
+                                                .package_id()
+                                        }
+                                        None => PubGrubDistribution::from_registry(
+                                            package_name,
+                                            version,
+                                        )
+                                        .package_id(),
+                                    };
+                                    distributions
+                                        .get(&package_id)
+                                        .map(|metadata| {
+                                            dependency_markers(
+                                                &metadata,
+                                                dependency_package,
+                                                dependency_extra.as_ref(),
+                                            )
+                                        })
+                                        .unwrap_or_default()
+                                }
+                            }
+                            _ => Vec::new(),
+                        };
+
+                        if let Some(edge) = petgraph.find_edge(self_index, dependency_index) {
+                            let dependency = &mut petgraph[edge];
+                            dependency.extras.extend(dependency_extra.clone());
+                            dependency.extras.sort_unstable();
+                            dependency.extras.dedup();
+                            for marker in markers {
+                                if !dependency.markers.contains(&marker) {
+                                    dependency.markers.push(marker);
+                                }
+                            }
+                            sort_markers(&mut dependency.markers);
+                        } else {
+                            petgraph.add_edge(
+                                self_index,
+                                dependency_index,
+                                Dependency {
+                                    range: dependency_range.clone(),
+                                    extras: dependency_extra.iter().cloned().collect(),
+                                    markers,
+                                },
+                            );
+                        }
                     }
                 }
             }
@@ -195,7 +281,9 @@ impl ResolutionGraph {
             petgraph,
             hashes,
             editables,
+            licenses,
             diagnostics,
+            aliases,
         })
     }
 
@@ -209,13 +297,33 @@ impl ResolutionGraph {
         self.petgraph.node_count() == 0
     }
 
+    /// Return the hashes of the distributions for the given package, if any.
+    pub fn hashes(&self, package_name: &PackageName) -> &[Hashes] {
+        self.hashes.get(package_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the declared license of the given package, if any.
+    pub fn license(&self, package_name: &PackageName) -> Option<&DeclaredLicense> {
+        self.licenses.get(package_name)
+    }
+
     /// Return the [`Diagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[Diagnostic] {
         &self.diagnostics
     }
 
+    /// Return the pinned version (or URL) of each package in the graph, excluding editables.
+    pub fn pins(
+        &self,
+    ) -> impl Iterator<Item = (&PackageName, distribution_types::VersionOrUrl<'_>)> {
+        self.petgraph
+            .node_weights()
+            .filter(|dist| !self.editables.contains_key(dist.name()))
+            .map(|dist| (dist.name(), dist.version_or_url()))
+    }
+
     /// Return the underlying graph.
-    pub fn petgraph(&self) -> &petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed> {
+    pub fn petgraph(&self) -> &petgraph::graph::Graph<Dist, Dependency, petgraph::Directed> {
         &self.petgraph
     }
 }
@@ -227,9 +335,14 @@ pub struct DisplayResolutionGraph<'a> {
     resolution: &'a ResolutionGraph,
     /// Whether to include hashes in the output.
     show_hashes: bool,
+    /// The algorithms for which to include hashes, if hashes are included.
+    hash_algorithms: &'a [HashAlgorithm],
     /// Whether to include annotations in the output, to indicate which dependency or dependencies
     /// requested each package.
     include_annotations: bool,
+    /// The packages to omit from the output as unsafe (e.g., `pip` and `setuptools`), listing
+    /// them in a trailing comment instead.
+    unsafe_packages: &'a [PackageName],
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
@@ -248,9 +361,84 @@ impl<'a> DisplayResolutionGraph<'a> {
         Self {
             resolution: underlying,
             show_hashes,
+            hash_algorithms: &[HashAlgorithm::Sha256],
             include_annotations,
+            unsafe_packages: &[],
         }
     }
+
+    /// Set the algorithms for which to include hashes in the output.
+    ///
+    /// Defaults to SHA-256. Digests are included for each algorithm that the index provided for a
+    /// given distribution.
+    #[must_use]
+    pub fn with_hash_algorithms(mut self, hash_algorithms: &'a [HashAlgorithm]) -> Self {
+        self.hash_algorithms = hash_algorithms;
+        self
+    }
+
+    /// Set the packages to omit from the output as unsafe, as in pip-compile without
+    /// `--allow-unsafe`.
+    ///
+    /// Omitted packages are listed, unpinned, in a comment at the end of the output. Defaults to
+    /// omitting no packages.
+    #[must_use]
+    pub fn with_unsafe_packages(mut self, unsafe_packages: &'a [PackageName]) -> Self {
+        self.unsafe_packages = unsafe_packages;
+        self
+    }
+}
+
+/// A dependency between two pinned packages in a [`ResolutionGraph`].
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    /// The versions of the dependency that are allowed by the dependent.
+    pub range: Range<Version>,
+    /// The extras of the dependency that are requested by the dependent.
+    pub extras: Vec<ExtraName>,
+    /// The markers under which the dependent requires the dependency, if any; the dependency
+    /// applies if any of the markers is satisfied.
+    pub markers: Vec<MarkerTree>,
+}
+
+impl std::fmt::Display for Dependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.range)
+    }
+}
+
+/// Return the markers on the requirements that the dependent (with the given metadata) declares
+/// on the dependency.
+fn dependency_markers(
+    metadata: &Metadata21,
+    dependency: &PackageName,
+    extra: Option<&ExtraName>,
+) -> Vec<MarkerTree> {
+    let mut markers = Vec::new();
+    for requirement in &metadata.requires_dist {
+        if &requirement.name != dependency {
+            continue;
+        }
+        if extra.is_some_and(|extra| !requirement.extras.contains(extra)) {
+            continue;
+        }
+        // An unconditional requirement subsumes any conditional ones.
+        let Some(marker) = &requirement.marker else {
+            return Vec::new();
+        };
+        if !markers.contains(marker) {
+            markers.push(marker.clone());
+        }
+    }
+    sort_markers(&mut markers);
+    markers
+}
+
+/// Sort markers by their normalized string representation, such that the order in which they
+/// were discovered (which depends on the order in which the resolver visited each package) doesn't
+/// leak into the output.
+fn sort_markers(markers: &mut [MarkerTree]) {
+    markers.sort_by_cached_key(ToString::to_string);
 }
 
 /// Write the graph in the `{name}=={version}` format of requirements.txt that pip uses.
@@ -266,10 +454,12 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
 
         #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
         enum NodeKey<'a> {
-            /// A node linked to an editable distribution, sorted by verbatim representation.
-            Editable(Cow<'a, str>),
-            /// A node linked to a non-editable distribution, sorted by package name.
-            Distribution(&'a PackageName),
+            /// A node linked to an editable distribution, sorted case-insensitively by verbatim
+            /// representation.
+            Editable(String, Cow<'a, str>),
+            /// A node linked to a non-editable distribution, sorted by package name, then by its
+            /// rendered requirement.
+            Distribution(&'a PackageName, String),
         }
 
         impl<'a> Node<'a> {
@@ -284,8 +474,13 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             /// Return a comparable key for the node.
             fn key(&self) -> NodeKey<'a> {
                 match self {
-                    Node::Editable(_, editable) => NodeKey::Editable(editable.verbatim()),
-                    Node::Distribution(name, _) => NodeKey::Distribution(name),
+                    Node::Editable(_, editable) => {
+                        let verbatim = editable.verbatim();
+                        NodeKey::Editable(verbatim.to_lowercase(), verbatim)
+                    }
+                    Node::Distribution(name, dist) => {
+                        NodeKey::Distribution(name, dist.verbatim().to_string())
+                    }
                 }
             }
         }
@@ -307,8 +502,15 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             })
             .collect::<Vec<_>>();
 
-        // Sort the nodes by name, but with editable packages first.
-        nodes.sort_unstable_by_key(|(index, node)| (node.key(), *index));
+        // Sort the nodes by name, but with editable packages first. The sort is independent of the
+        // order in which the nodes were added to the graph, which varies with the order in which
+        // the resolver fetched each package.
+        nodes.sort_by_cached_key(|(_, node)| node.key());
+
+        // Omit any unsafe packages, to be listed at the end of the output.
+        let (unsafe_nodes, nodes): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|(_, node)| {
+            matches!(node, Node::Distribution(..)) && self.unsafe_packages.contains(node.name())
+        });
 
         // Print out the dependency graph.
         for (index, node) in nodes {
@@ -331,9 +533,11 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                     .filter(|hashes| !hashes.is_empty())
                 {
                     for hash in hashes {
-                        if let Some(hash) = hash.to_string() {
-                            writeln!(f, " \\")?;
-                            write!(f, "    --hash={hash}")?;
+                        for algorithm in self.hash_algorithms {
+                            if let Some(hash) = hash.format(*algorithm) {
+                                writeln!(f, " \\")?;
+                                write!(f, "    --hash={hash}")?;
+                            }
                         }
                     }
                 }
@@ -341,6 +545,11 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             writeln!(f)?;
 
             if self.include_annotations {
+                // Display any packages that this package provides in lieu of the original.
+                for requested in self.resolution.aliases.provided_by(node.name()) {
+                    writeln!(f, "{}", format!("    # provides {requested}").green())?;
+                }
+
                 // Display all dependencies.
                 let mut edges = self
                     .resolution
@@ -349,6 +558,7 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                     .map(|edge| &self.resolution.petgraph[edge.source()])
                     .collect::<Vec<_>>();
                 edges.sort_unstable_by_key(|package| package.name());
+                edges.dedup_by_key(|package| package.name());
 
                 match edges.len() {
                     0 => {}
@@ -367,6 +577,19 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             }
         }
 
+        if !unsafe_nodes.is_empty() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{}",
+                "# The following packages are considered to be unsafe in a requirements file:"
+                    .green()
+            )?;
+            for (_, node) in unsafe_nodes {
+                writeln!(f, "{}", format!("# {}", node.name()).green())?;
+            }
+        }
+
         Ok(())
     }
 }
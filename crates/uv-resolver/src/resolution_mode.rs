@@ -1,5 +1,6 @@
 use rustc_hash::FxHashSet;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use pep508_rs::Requirement;
 use uv_normalize::PackageName;
 
@@ -16,6 +17,16 @@ pub enum ResolutionMode {
     LowestDirect,
 }
 
+impl CacheKey for ResolutionMode {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Highest => 0u8.cache_key(state),
+            Self::Lowest => 1u8.cache_key(state),
+            Self::LowestDirect => 2u8.cache_key(state),
+        }
+    }
+}
+
 /// Like [`ResolutionMode`], but with any additional information required to select a candidate,
 /// like the set of direct dependencies.
 #[derive(Debug, Clone)]
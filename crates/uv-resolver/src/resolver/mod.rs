@@ -10,7 +10,7 @@ use itertools::Itertools;
 use pubgrub::error::PubGrubError;
 use pubgrub::range::Range;
 use pubgrub::solver::{Incompatibility, State};
-use pubgrub::type_aliases::DependencyConstraints;
+use pubgrub::type_aliases::{DependencyConstraints, SelectedDependencies};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use tokio::select;
@@ -18,8 +18,9 @@ use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use url::Url;
 
+use crate::aliases::Aliases;
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
-use crate::error::ResolveError;
+use crate::error::{OmittedRequirement, ResolveError};
 use crate::manifest::Manifest;
 use crate::overrides::Overrides;
 use crate::pins::FilePins;
@@ -64,6 +65,9 @@ mod reporter;
 pub(crate) enum UnavailableVersion {
     /// Version is incompatible due to the `Requires-Python` version specifiers for that package.
     RequiresPython(VersionSpecifiers),
+    /// Version is incompatible because its `Requires-Python` version specifiers don't cover the
+    /// entire `requires-python` range of the resolution.
+    RequiresPythonRange(VersionSpecifiers),
     /// Version is incompatible because it is yanked
     Yanked(Yanked),
     /// Version is incompatible because it has no usable distributions
@@ -93,6 +97,7 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     requirements: Vec<Requirement>,
     constraints: Vec<Requirement>,
     overrides: Overrides,
+    aliases: Aliases,
     allowed_yanks: AllowedYanks,
     allowed_urls: AllowedUrls,
     dependency_mode: DependencyMode,
@@ -214,6 +219,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             requirements: manifest.requirements,
             constraints: manifest.constraints,
             overrides: Overrides::from_requirements(manifest.overrides),
+            aliases: Aliases::from_aliases(manifest.aliases),
             markers,
             python_requirement,
             editables,
@@ -222,6 +228,16 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    /// Require that the resolution is valid for every Python version in the given
+    /// `requires-python` range, rather than only the target version.
+    #[must_use]
+    pub fn with_requires_python(self, requires_python: VersionSpecifiers) -> Self {
+        Self {
+            python_requirement: self.python_requirement.with_range(requires_python),
+            ..self
+        }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -314,6 +330,10 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     })
             else {
                 let selection = state.partial_solution.extract_solution();
+                let omitted = self.omitted_requirements(&selection);
+                if !omitted.is_empty() {
+                    return Err(ResolveError::OmittedRequirements(omitted));
+                }
                 return ResolutionGraph::from_state(
                     &selection,
                     &pins,
@@ -322,6 +342,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &self.index.redirects,
                     &state,
                     self.editables.clone(),
+                    self.aliases.clone(),
                 );
             };
             next = highest_priority_pkg;
@@ -407,6 +428,13 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                             state.partial_solution.add_decision(next.clone(), version);
                             continue;
                         }
+                        UnavailableVersion::RequiresPythonRange(requires_python) => format!(
+                            "it requires Python {requires_python}, which does not cover the requires-python range ({})",
+                            self.python_requirement
+                                .range()
+                                .map(ToString::to_string)
+                                .unwrap_or_default()
+                        ),
                         UnavailableVersion::Yanked(yanked) => match yanked {
                             Yanked::Bool(_) => "it was yanked".to_string(),
                             Yanked::Reason(reason) => format!(
@@ -712,9 +740,18 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
 
                 // If the version is incompatible because of its Python requirement
                 if let Some(requires_python) = self.python_requirement.validate_dist(dist) {
+                    // If the dist supports both the installed and target Python versions, then
+                    // it's only incompatible with the `requires-python` range.
+                    let unavailable = if requires_python.contains(self.python_requirement.target())
+                        && requires_python.contains(self.python_requirement.installed())
+                    {
+                        UnavailableVersion::RequiresPythonRange(requires_python.clone())
+                    } else {
+                        UnavailableVersion::RequiresPython(requires_python.clone())
+                    };
                     return Ok(Some(ResolverVersion::Unavailable(
                         candidate.version().clone(),
-                        UnavailableVersion::RequiresPython(requires_python.clone()),
+                        unavailable,
                     )));
                 }
 
@@ -741,6 +778,16 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     );
                 }
 
+                // Track the index from which the candidate was selected, as, when versions are
+                // pooled across indexes, it may not be the first index that contains the package.
+                if let Some(index) = dist.for_resolution().dist.index() {
+                    debug!(
+                        "Selected {}=={} from index: {index}",
+                        candidate.name(),
+                        candidate.version()
+                    );
+                }
+
                 // We want to return a package pinned to a specific version; but we _also_ want to
                 // store the exact file that we selected to satisfy that version.
                 pins.insert(&candidate, dist);
@@ -774,6 +821,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &self.requirements,
                     &self.constraints,
                     &self.overrides,
+                    &self.aliases,
                     None,
                     None,
                     self.markers,
@@ -786,6 +834,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     return Ok(Dependencies::Unavailable(uncapitalize(err.to_string())));
                 }
                 let mut constraints = constraints?;
+                self.apply_editables(&mut constraints);
 
                 for (package, version) in constraints.iter() {
                     debug!("Adding direct dependency: {package}{version}");
@@ -795,16 +844,20 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         .await?;
                 }
 
-                // Add a dependency on each editable.
+                // Add a dependency on each editable, respecting any requirements on the editable
+                // itself (e.g., if a workspace member is also required by name).
                 for (editable, metadata) in self.editables.values() {
-                    constraints.insert(
-                        PubGrubPackage::Package(
-                            metadata.name.clone(),
-                            None,
-                            Some(editable.url().clone()),
-                        ),
-                        Range::singleton(metadata.version.clone()),
+                    let package = PubGrubPackage::Package(
+                        metadata.name.clone(),
+                        None,
+                        Some(editable.url().clone()),
                     );
+                    let version = Range::singleton(metadata.version.clone());
+                    let version = match constraints.remove(&package) {
+                        Some(existing) => existing.intersection(&version),
+                        None => version,
+                    };
+                    constraints.insert(package, version);
                     for extra in &editable.extras {
                         constraints.insert(
                             PubGrubPackage::Package(
@@ -861,10 +914,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     &metadata.requires_dist,
                     &self.constraints,
                     &self.overrides,
+                    &self.aliases,
                     extra.as_ref(),
                     Some(package_name),
                     self.markers,
                 )?;
+                self.apply_editables(&mut constraints);
 
                 for (package, version) in constraints.iter() {
                     debug!("Adding transitive dependency: {package}{version}");
@@ -887,6 +942,109 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    /// When resolving for a `requires-python` range, find the requirements of the selected
+    /// packages that were omitted because their markers don't apply to the target Python version,
+    /// even though they apply to another version in the range.
+    ///
+    /// The resolution doesn't fork on Python-version markers, so such requirements would be missing
+    /// from the output for part of the range.
+    fn omitted_requirements(
+        &self,
+        selection: &SelectedDependencies<PubGrubPackage, Version>,
+    ) -> Vec<OmittedRequirement> {
+        if self.python_requirement.range().is_none() {
+            return Vec::new();
+        }
+
+        let mut seen = FxHashSet::default();
+        let mut omitted = Vec::new();
+        for (package, version) in selection {
+            let (dependent, extra, url) = match package {
+                PubGrubPackage::Root(_) => {
+                    for requirement in self.overrides.apply(&self.requirements) {
+                        if let Some(python_version) =
+                            self.python_requirement
+                                .omits(requirement, &[], self.markers)
+                        {
+                            if seen.insert((None, requirement.to_string())) {
+                                omitted.push(OmittedRequirement {
+                                    dependent: None,
+                                    requirement: requirement.clone(),
+                                    python_version,
+                                });
+                            }
+                        }
+                    }
+                    continue;
+                }
+                PubGrubPackage::Python(_) => continue,
+                PubGrubPackage::Package(package_name, extra, url) => (package_name, extra, url),
+            };
+
+            let registry_metadata;
+            let metadata = if let Some((_, metadata)) = self.editables.get(dependent) {
+                metadata
+            } else {
+                let package_id = match url {
+                    Some(url) => PubGrubDistribution::from_url(dependent, url),
+                    None => PubGrubDistribution::from_registry(dependent, version),
+                }
+                .package_id();
+                let Some(metadata) = self.index.distributions.get(&package_id) else {
+                    continue;
+                };
+                registry_metadata = metadata;
+                &*registry_metadata
+            };
+
+            let extras = extra.as_slice();
+            for requirement in self.overrides.apply(&metadata.requires_dist) {
+                if let Some(python_version) =
+                    self.python_requirement
+                        .omits(requirement, extras, self.markers)
+                {
+                    if seen.insert((Some(dependent.clone()), requirement.to_string())) {
+                        omitted.push(OmittedRequirement {
+                            dependent: Some((dependent.clone(), version.clone())),
+                            requirement: requirement.clone(),
+                            python_version,
+                        });
+                    }
+                }
+            }
+        }
+        omitted
+    }
+
+    /// Resolve any registry dependencies on an editable package to the editable itself.
+    ///
+    /// For example, if a workspace member requires another member by name, the requirement should
+    /// be satisfied by the local, editable member rather than a distribution from the registry.
+    fn apply_editables(&self, constraints: &mut PubGrubDependencies) {
+        let packages = constraints
+            .iter()
+            .filter_map(|(package, _)| match package {
+                PubGrubPackage::Package(package_name, extra, None) => {
+                    self.editables.get(package_name).map(|(editable, _)| {
+                        PubGrubPackage::Package(
+                            package_name.clone(),
+                            extra.clone(),
+                            Some(editable.url().clone()),
+                        )
+                    })
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Since the URL is ignored when hashing, removing the package removes the registry variant.
+        for package in packages {
+            if let Some(version) = constraints.remove(&package) {
+                constraints.insert(package, version);
+            }
+        }
+    }
+
     /// Fetch the metadata for a stream of packages and versions.
     async fn fetch(
         &self,
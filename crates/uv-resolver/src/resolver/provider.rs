@@ -124,14 +124,13 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
         // If the "Simple API" request was successful, convert to `VersionMap` on the Tokio
         // threadpool, since it can be slow.
         match result {
-            Ok((index, metadata)) => {
+            Ok(metadata) => {
                 let self_send = self.inner.clone();
                 let package_name_owned = package_name.clone();
                 Ok(tokio::task::spawn_blocking(move || {
                     VersionsResponse::Found(VersionMap::from_metadata(
                         metadata,
                         &package_name_owned,
-                        &index,
                         &self_send.tags,
                         &self_send.python_requirement,
                         self_send.exclude_newer.as_ref(),
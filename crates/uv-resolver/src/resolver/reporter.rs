@@ -48,4 +48,14 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+
+    // Wheels are rarely downloaded in full during resolution (only when their metadata can't be
+    // fetched directly), so the resolver's progress spinner doesn't track individual downloads.
+    fn on_download_start(&self, _name: &PackageName, _size: Option<u64>) -> usize {
+        0
+    }
+
+    fn on_download_progress(&self, _id: usize, _bytes: u64) {}
+
+    fn on_download_complete(&self, _name: &PackageName, _id: usize) {}
 }
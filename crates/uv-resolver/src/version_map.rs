@@ -24,13 +24,16 @@ pub struct VersionMap {
 }
 
 impl VersionMap {
-    /// Initialize a [`VersionMap`] from the given metadata.
+    /// Initialize a [`VersionMap`] from the given metadata, fetched from each index in order of
+    /// priority.
+    ///
+    /// If a version is available on multiple indexes, the distributions from the first such index
+    /// are used.
     #[instrument(skip_all, fields(package_name))]
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_metadata(
-        simple_metadata: OwnedArchive<SimpleMetadata>,
+        simple_metadata: Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>,
         package_name: &PackageName,
-        index: &IndexUrl,
         tags: &Tags,
         python_requirement: &PythonRequirement,
         exclude_newer: Option<&DateTime<Utc>>,
@@ -41,18 +44,20 @@ impl VersionMap {
         // Create stubs for each entry in simple metadata. The full conversion
         // from a `VersionFiles` to a PrioritizedDist for each version
         // isn't done until that specific version is requested.
-        for (datum_index, datum) in simple_metadata.iter().enumerate() {
-            let version: Version = datum
-                .version
-                .deserialize(&mut SharedDeserializeMap::new())
-                .expect("archived version always deserializes");
-            map.insert(
-                version,
-                LazyPrioritizedDist::OnlySimple(SimplePrioritizedDist {
-                    datum_index,
-                    dist: OnceLock::new(),
-                }),
-            );
+        for (source_index, (_, metadata)) in simple_metadata.iter().enumerate() {
+            for (datum_index, datum) in metadata.iter().enumerate() {
+                let version: Version = datum
+                    .version
+                    .deserialize(&mut SharedDeserializeMap::new())
+                    .expect("archived version always deserializes");
+                map.entry(version).or_insert_with(|| {
+                    LazyPrioritizedDist::OnlySimple(SimplePrioritizedDist {
+                        source_index,
+                        datum_index,
+                        dist: OnceLock::new(),
+                    })
+                });
+            }
         }
         // If a set of flat distributions have been given, we need to add those
         // to our map of entries as well.
@@ -90,7 +95,6 @@ impl VersionMap {
                 map,
                 simple_metadata,
                 no_binary,
-                index: index.clone(),
                 tags: tags.clone(),
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
@@ -244,12 +248,11 @@ struct VersionMapLazy {
     /// A map from version to possibly-initialized distribution.
     map: BTreeMap<Version, LazyPrioritizedDist>,
     /// The raw simple metadata from which `PrioritizedDist`s should
-    /// be constructed.
-    simple_metadata: OwnedArchive<SimpleMetadata>,
+    /// be constructed, along with the URL of the index from which it was
+    /// fetched, in order of priority.
+    simple_metadata: Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>,
     /// When true, wheels aren't allowed.
     no_binary: bool,
-    /// The URL of the index where this package came from.
-    index: IndexUrl,
     /// The set of compatibility tags that determines whether a wheel is usable
     /// in the current environment.
     tags: Tags,
@@ -302,8 +305,8 @@ impl VersionMapLazy {
         simple: &'p SimplePrioritizedDist,
     ) -> Option<&'p PrioritizedDist> {
         let get_or_init = || {
-            let files: VersionFiles = self
-                .simple_metadata
+            let (index, metadata) = &self.simple_metadata[simple.source_index];
+            let files: VersionFiles = metadata
                 .datum(simple.datum_index)
                 .expect("index to lazy dist is correct")
                 .files
@@ -357,7 +360,7 @@ impl VersionMapLazy {
                         let dist = Dist::from_registry(
                             DistFilename::WheelFilename(filename),
                             file,
-                            self.index.clone(),
+                            index.clone(),
                         );
                         priority_dist.insert_built(
                             dist,
@@ -371,7 +374,7 @@ impl VersionMapLazy {
                         let dist = Dist::from_registry(
                             DistFilename::SourceDistFilename(filename),
                             file,
-                            self.index.clone(),
+                            index.clone(),
                         );
                         priority_dist.insert_source(dist, requires_python, yanked, Some(hash));
                     }
@@ -408,6 +411,9 @@ enum LazyPrioritizedDist {
 /// Represents a lazily initialized `PrioritizedDist`.
 #[derive(Debug)]
 struct SimplePrioritizedDist {
+    /// An offset into the `simple_metadata` of the [`VersionMapLazy`],
+    /// identifying the index that provided this version.
+    source_index: usize,
     /// An offset into `SimpleMetadata` corresponding to a `SimpleMetadatum`.
     /// This provides access to a `VersionFiles` that is used to construct a
     /// `PrioritizedDist`.
@@ -9,11 +9,13 @@ use std::str::FromStr;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
+use tokio::sync::Semaphore;
 
 use distribution_types::{IndexLocations, Resolution, SourceDist};
 use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
 use platform_host::{Arch, Os, Platform};
 use platform_tags::Tags;
+use pypi_types::Scheme;
 use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClientBuilder};
 use uv_interpreter::{Interpreter, Virtualenv};
@@ -21,7 +23,9 @@ use uv_resolver::{
     DisplayResolutionGraph, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
     ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{BuildContext, BuildKind, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait};
+use uv_traits::{
+    BuildContext, BuildKind, Concurrency, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait,
+};
 
 // Exclude any packages uploaded after this date.
 static EXCLUDE_NEWER: Lazy<DateTime<Utc>> = Lazy::new(|| {
@@ -34,6 +38,7 @@ struct DummyContext {
     cache: Cache,
     interpreter: Interpreter,
     index_locations: IndexLocations,
+    build_semaphore: Semaphore,
 }
 
 impl DummyContext {
@@ -42,6 +47,7 @@ impl DummyContext {
             cache,
             interpreter,
             index_locations: IndexLocations::default(),
+            build_semaphore: Semaphore::new(1),
         }
     }
 }
@@ -77,6 +83,14 @@ impl BuildContext for DummyContext {
         &self.index_locations
     }
 
+    fn concurrency(&self) -> Concurrency {
+        Concurrency::default()
+    }
+
+    fn build_semaphore(&self) -> &Semaphore {
+        &self.build_semaphore
+    }
+
     async fn resolve<'a>(&'a self, _requirements: &'a [Requirement]) -> Result<Resolution> {
         panic!("The test should not need to build source distributions")
     }
@@ -129,6 +143,7 @@ async fn resolve(
         PathBuf::from("/dev/null"),
         PathBuf::from("/dev/null"),
         PathBuf::from("/dev/null"),
+        Scheme::virtualenv((3, 12)),
     );
     let build_context = DummyContext::new(Cache::temp()?, interpreter.clone());
     let resolver = Resolver::new(
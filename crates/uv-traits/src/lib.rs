@@ -2,18 +2,22 @@
 
 use std::fmt::{Display, Formatter};
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
-use std::str::FromStr;
 
 use anyhow::Result;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use distribution_types::{CachedDist, DistributionId, IndexLocations, Resolution, SourceDist};
 use once_map::OnceMap;
 use pep508_rs::Requirement;
+use tokio::sync::Semaphore;
 use uv_cache::Cache;
 use uv_interpreter::{Interpreter, Virtualenv};
 use uv_normalize::PackageName;
 
+pub use uv_normalize::PackageNameSpecifier;
+
 /// Avoid cyclic crate dependencies between resolver, installer and builder.
 ///
 /// To resolve the dependencies of a packages, we may need to build one or more source
@@ -79,6 +83,17 @@ pub trait BuildContext: Sync {
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// The limits on the number of concurrent downloads, builds, and installs.
+    fn concurrency(&self) -> Concurrency;
+
+    /// The semaphore bounding the number of source distribution builds that run concurrently,
+    /// sized by [`Concurrency::builds`].
+    ///
+    /// Permits should only be held while running the build backend (i.e., after
+    /// [`BuildContext::setup_build`]), since setting up a build may itself require building the
+    /// build requirements.
+    fn build_semaphore(&self) -> &Semaphore;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -148,6 +163,15 @@ pub enum SetupPyStrategy {
     Setuptools,
 }
 
+impl CacheKey for SetupPyStrategy {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Pep517 => 0u8.cache_key(state),
+            Self::Setuptools => 1u8.cache_key(state),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum BuildKind {
     /// A regular PEP 517 wheel build
@@ -166,25 +190,6 @@ impl Display for BuildKind {
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum PackageNameSpecifier {
-    All,
-    None,
-    Package(PackageName),
-}
-
-impl FromStr for PackageNameSpecifier {
-    type Err = uv_normalize::InvalidNameError;
-
-    fn from_str(name: &str) -> Result<Self, Self::Err> {
-        match name {
-            ":all:" => Ok(Self::All),
-            ":none:" => Ok(Self::None),
-            _ => Ok(Self::Package(PackageName::from_str(name)?)),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 pub enum PackageNameSpecifiers {
     All,
@@ -251,6 +256,33 @@ impl NoBinary {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Combine with the selections from another source (e.g., a `requirements.txt` file), such
+    /// that a wheel is disallowed if either source disallows it.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => Self::All,
+            (Self::None, other) | (other, Self::None) => other,
+            (Self::Packages(mut packages), Self::Packages(other)) => {
+                packages.extend(other);
+                Self::Packages(packages)
+            }
+        }
+    }
+}
+
+impl CacheKey for NoBinary {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::None => 0u8.cache_key(state),
+            Self::All => 1u8.cache_key(state),
+            Self::Packages(packages) => {
+                2u8.cache_key(state);
+                packages.cache_key(state);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -286,6 +318,66 @@ impl NoBuild {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Combine with the selections from another source (e.g., a `requirements.txt` file), such
+    /// that a build is disallowed if either source disallows it.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => Self::All,
+            (Self::None, other) | (other, Self::None) => other,
+            (Self::Packages(mut packages), Self::Packages(other)) => {
+                packages.extend(other);
+                Self::Packages(packages)
+            }
+        }
+    }
+}
+
+impl CacheKey for NoBuild {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::None => 0u8.cache_key(state),
+            Self::All => 1u8.cache_key(state),
+            Self::Packages(packages) => {
+                2u8.cache_key(state);
+                packages.cache_key(state);
+            }
+        }
+    }
+}
+
+/// The limits on the number of concurrent operations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of concurrent downloads.
+    pub downloads: usize,
+    /// The maximum number of source distributions to build concurrently.
+    pub builds: usize,
+    /// The maximum number of threads to use when unzipping and installing wheels.
+    pub installs: usize,
+}
+
+impl Concurrency {
+    /// The default number of concurrent downloads.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// The number of threads available to the process, falling back to one if unknown.
+    pub fn threads() -> usize {
+        std::thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            builds: Self::threads(),
+            installs: Self::threads(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,178 @@
+//! Support for `--trace-output`, which writes the `tracing` spans of a command to a file in the
+//! Chrome trace event format, for viewing in `chrome://tracing` or <https://ui.perfetto.dev>.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+use std::time::Instant;
+
+use anyhow::{Context as _, Result};
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use uv_fs::Normalized;
+
+use crate::logging::JsonFields;
+
+/// Collects the spans in a command as Chrome trace events, for `--trace-output`.
+#[derive(Debug, Clone)]
+pub(crate) struct ChromeTrace(Arc<Mutex<TraceState>>);
+
+#[derive(Debug)]
+struct TraceState {
+    /// The time at which tracing started, from which all timestamps are measured.
+    start: Instant,
+    /// The small, stable identifiers assigned to each thread, in order of first use.
+    threads: FxHashMap<ThreadId, usize>,
+    /// The completed events.
+    events: Vec<TraceEvent>,
+}
+
+/// A "complete" (`"ph": "X"`) event in the Chrome trace event format.
+///
+/// See: <https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU>
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    cat: &'static str,
+    ph: &'static str,
+    /// The start of the event, in microseconds.
+    ts: f64,
+    /// The duration of the event, in microseconds.
+    dur: f64,
+    pid: u32,
+    tid: usize,
+    args: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+impl Default for ChromeTrace {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(TraceState {
+            start: Instant::now(),
+            threads: FxHashMap::default(),
+            events: Vec::new(),
+        })))
+    }
+}
+
+impl ChromeTrace {
+    /// Return a [`Layer`] that records spans into this trace.
+    pub(crate) fn layer(&self) -> ChromeTraceLayer {
+        ChromeTraceLayer(self.clone())
+    }
+
+    /// Write the recorded trace to the given file.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let state = self.0.lock().unwrap();
+        let file = fs_err::File::create(path)?;
+        serde_json::to_writer(
+            std::io::BufWriter::new(file),
+            &TraceFile {
+                trace_events: &state.events,
+                display_time_unit: "ms",
+            },
+        )
+        .with_context(|| format!("Failed to write trace to: {}", path.normalized_display()))?;
+        Ok(())
+    }
+}
+
+/// The fields recorded on a span, as the `args` of its events.
+///
+/// (Span extensions are keyed by type and shared across layers, so this wraps the fields rather
+/// than storing [`JsonFields`] directly.)
+#[derive(Debug, Default)]
+struct SpanArgs(JsonFields);
+
+/// The times at which a span was entered, and not yet exited.
+#[derive(Debug, Default)]
+struct SpanEntries(Vec<Instant>);
+
+/// A [`Layer`] that records each time a span is entered (e.g., each poll of an instrumented
+/// future) as a Chrome trace event on the current thread.
+#[derive(Debug)]
+pub(crate) struct ChromeTraceLayer(ChromeTrace);
+
+impl<S> Layer<S> for ChromeTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanArgs::default();
+        attrs.record(&mut fields.0);
+        let mut extensions = span.extensions_mut();
+        extensions.insert(fields);
+        extensions.insert(SpanEntries::default());
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(fields) = span.extensions_mut().get_mut::<SpanArgs>() {
+            values.record(&mut fields.0);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(entries) = span.extensions_mut().get_mut::<SpanEntries>() {
+            entries.0.push(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let end = Instant::now();
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(start) = extensions
+            .get_mut::<SpanEntries>()
+            .and_then(|entries| entries.0.pop())
+        else {
+            return;
+        };
+        let args = extensions
+            .get_mut::<SpanArgs>()
+            .map(|fields| fields.0 .0.clone())
+            .unwrap_or_default();
+
+        let mut state = self.0 .0.lock().unwrap();
+        let threads = state.threads.len();
+        let tid = *state
+            .threads
+            .entry(std::thread::current().id())
+            .or_insert(threads + 1);
+        let ts = start.duration_since(state.start).as_secs_f64() * 1_000_000.0;
+        let dur = end.duration_since(start).as_secs_f64() * 1_000_000.0;
+        state.events.push(TraceEvent {
+            name: span.metadata().name(),
+            cat: span.metadata().target(),
+            ph: "X",
+            ts,
+            dur,
+            pid: std::process::id(),
+            tid,
+            args,
+        });
+    }
+}
@@ -0,0 +1,427 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anstream::println;
+use anyhow::{bail, Context, Result};
+use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use tracing::debug;
+
+use distribution_types::Name;
+use pep440_rs::{Operator, Version};
+use pep508_rs::VersionOrUrl;
+use platform_host::Platform;
+use reqwest::header::CONTENT_TYPE;
+use reqwest_middleware::ClientWithMiddleware;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
+use uv_fs::Normalized;
+use uv_installer::SitePackages;
+use uv_interpreter::Virtualenv;
+use uv_normalize::PackageName;
+use uv_traits::Concurrency;
+use uv_warnings::warn_user;
+
+use crate::commands::python::client;
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{RequirementsSource, RequirementsSpecification};
+
+/// The endpoint used to query the OSV database for the vulnerabilities affecting a package.
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+/// Audit a set of pinned requirements (or, if none are provided, the packages installed in the
+/// current environment) for known vulnerabilities.
+pub(crate) async fn audit(
+    sources: &[RequirementsSource],
+    advisory_db: Option<&Path>,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let packages = if sources.is_empty() {
+        installed_packages(cache)?
+    } else {
+        pinned_packages(sources)?
+    };
+
+    if packages.is_empty() {
+        writeln!(printer, "No packages found")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Find the vulnerabilities affecting each package.
+    let findings = if let Some(advisory_db) = advisory_db {
+        let database = AdvisoryDatabase::from_dir(advisory_db)?;
+        packages
+            .into_iter()
+            .map(|(name, version)| {
+                let vulnerabilities = database.query(&name, &version);
+                (name, version, vulnerabilities)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        if matches!(connectivity, Connectivity::Offline) {
+            bail!(
+                "Querying OSV requires network access, but `--offline` was provided (hint: use `--advisory-db`)"
+            );
+        }
+        let client = client(cache, trusted_hosts, connectivity, timeouts);
+        futures::stream::iter(packages)
+            .map(|(name, version)| {
+                let client = &client;
+                async move {
+                    let vulnerabilities = query_osv(client, &name, &version)
+                        .await
+                        .with_context(|| format!("Failed to query OSV for: {name}=={version}"))?;
+                    Ok::<_, anyhow::Error>((name, version, vulnerabilities))
+                }
+            })
+            .buffer_unordered(concurrency.downloads)
+            .try_collect()
+            .await?
+    };
+
+    let num_packages = findings.len();
+    let mut num_vulnerable = 0;
+    let mut num_vulnerabilities = 0;
+    for (name, version, vulnerabilities) in findings
+        .iter()
+        .sorted_unstable_by(|(a, ..), (b, ..)| a.cmp(b))
+    {
+        if vulnerabilities.is_empty() {
+            continue;
+        }
+        num_vulnerable += 1;
+        num_vulnerabilities += vulnerabilities.len();
+
+        println!("{}", format!("{name}=={version}").bold());
+        for vulnerability in vulnerabilities
+            .iter()
+            .sorted_unstable_by(|a, b| a.id.cmp(&b.id))
+        {
+            let mut line = format!("  {}", vulnerability.id.red());
+            if !vulnerability.aliases.is_empty() {
+                write!(line, " ({})", vulnerability.aliases.iter().join(", "))?;
+            }
+            if let Some(summary) = &vulnerability.summary {
+                write!(line, ": {summary}")?;
+            }
+            println!("{line}");
+            if let Some(fixed) = vulnerability.fixed_version(name, version) {
+                println!("    {} {name}=={fixed}", "Fixed in:".green());
+            }
+        }
+    }
+
+    let s = if num_packages == 1 { "" } else { "s" };
+    if num_vulnerabilities == 0 {
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Audited {} in {}",
+                format!("{num_packages} package{s}").bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer,
+        "Found {} in {} (audited {} in {})",
+        format!(
+            "{num_vulnerabilities} vulnerabilit{}",
+            if num_vulnerabilities == 1 { "y" } else { "ies" }
+        )
+        .bold(),
+        format!(
+            "{num_vulnerable} package{}",
+            if num_vulnerable == 1 { "" } else { "s" }
+        )
+        .bold(),
+        format!("{num_packages} package{s}"),
+        elapsed(start.elapsed())
+    )?;
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Read the packages installed in the current environment.
+fn installed_packages(cache: &Cache) -> Result<Vec<(PackageName, Version)>> {
+    let platform = Platform::current()?;
+    let venv = Virtualenv::from_env(platform, cache)?;
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    let site_packages = SitePackages::from_executable(&venv)?;
+    Ok(site_packages
+        .iter()
+        .map(|dist| (dist.name().clone(), dist.version().clone()))
+        .collect())
+}
+
+/// Read the pinned packages from a set of requirements files, skipping any requirements that
+/// aren't pinned to an exact version.
+fn pinned_packages(sources: &[RequirementsSource]) -> Result<Vec<(PackageName, Version)>> {
+    let spec = RequirementsSpecification::from_simple_sources(sources)?;
+    if !spec.editables.is_empty() {
+        debug!("Skipping editable requirements, which can't be audited");
+    }
+
+    let mut packages = Vec::with_capacity(spec.requirements.len());
+    for requirement in spec.requirements {
+        let version = match &requirement.version_or_url {
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) => match specifiers.as_slice() {
+                [specifier] if *specifier.operator() == Operator::Equal => {
+                    Some(specifier.version().clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(version) = version {
+            packages.push((requirement.name, version));
+        } else {
+            warn_user!("Skipping requirement that isn't pinned to an exact version: {requirement}");
+        }
+    }
+    Ok(packages)
+}
+
+/// A vulnerability record, in the OSV schema.
+///
+/// See: <https://ossf.github.io/osv-schema/>
+#[derive(Debug, Clone, Deserialize)]
+struct Vulnerability {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Affected {
+    #[serde(default)]
+    package: Option<AffectedPackage>,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AffectedPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RangeEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+impl Vulnerability {
+    /// Return the entries that describe the given package on PyPI.
+    fn affected_entries<'a>(&'a self, name: &'a PackageName) -> impl Iterator<Item = &'a Affected> {
+        self.affected.iter().filter(move |affected| {
+            affected.package.as_ref().is_some_and(|package| {
+                package.ecosystem == "PyPI"
+                    && PackageName::from_str(&package.name)
+                        .is_ok_and(|candidate| candidate == *name)
+            })
+        })
+    }
+
+    /// Returns `true` if the vulnerability affects the given version of the package.
+    fn affects(&self, name: &PackageName, version: &Version) -> bool {
+        if self.withdrawn.is_some() {
+            return false;
+        }
+        self.affected_entries(name).any(|affected| {
+            affected
+                .versions
+                .iter()
+                .any(|candidate| Version::from_str(candidate).is_ok_and(|v| v == *version))
+                || affected
+                    .ranges
+                    .iter()
+                    .filter(|range| range.kind == "ECOSYSTEM")
+                    .any(|range| range.contains(version))
+        })
+    }
+
+    /// Return the lowest version that fixes the vulnerability, above the given version.
+    fn fixed_version(&self, name: &PackageName, version: &Version) -> Option<Version> {
+        self.affected_entries(name)
+            .flat_map(|affected| &affected.ranges)
+            .flat_map(|range| &range.events)
+            .filter_map(|event| event.fixed.as_deref())
+            .filter_map(|fixed| Version::from_str(fixed).ok())
+            .filter(|fixed| fixed > version)
+            .min()
+    }
+}
+
+impl AffectedRange {
+    /// Returns `true` if the version falls within the range, evaluating its events in version
+    /// order.
+    fn contains(&self, version: &Version) -> bool {
+        let mut events = self
+            .events
+            .iter()
+            .filter_map(|event| {
+                if let Some(introduced) = &event.introduced {
+                    Some((Version::from_str(introduced).ok()?, Event::Introduced))
+                } else if let Some(fixed) = &event.fixed {
+                    Some((Version::from_str(fixed).ok()?, Event::Fixed))
+                } else if let Some(last_affected) = &event.last_affected {
+                    Some((Version::from_str(last_affected).ok()?, Event::LastAffected))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        events.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut affected = false;
+        for (boundary, event) in events {
+            match event {
+                Event::Introduced if boundary <= *version => affected = true,
+                Event::Fixed if boundary <= *version => affected = false,
+                Event::LastAffected if boundary < *version => affected = false,
+                _ => {}
+            }
+        }
+        affected
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Event {
+    Introduced,
+    Fixed,
+    LastAffected,
+}
+
+/// A local database of OSV records, e.g., an extracted copy of the PyPI advisories published by
+/// OSV.
+#[derive(Debug, Default)]
+struct AdvisoryDatabase(FxHashMap<PackageName, Vec<Vulnerability>>);
+
+impl AdvisoryDatabase {
+    /// Read every `.json` record in the given directory (recursively).
+    fn from_dir(dir: &Path) -> Result<Self> {
+        let mut database = Self::default();
+        for entry in walkdir::WalkDir::new(dir) {
+            let entry = entry.with_context(|| {
+                format!(
+                    "Failed to read advisory database: {}",
+                    dir.normalized_display()
+                )
+            })?;
+            if entry.path().extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let contents = fs_err::read(entry.path())?;
+            let vulnerability = match serde_json::from_slice::<Vulnerability>(&contents) {
+                Ok(vulnerability) => vulnerability,
+                Err(err) => {
+                    debug!(
+                        "Skipping invalid advisory at {}: {err}",
+                        entry.path().normalized_display()
+                    );
+                    continue;
+                }
+            };
+            let names = vulnerability
+                .affected
+                .iter()
+                .filter_map(|affected| affected.package.as_ref())
+                .filter(|package| package.ecosystem == "PyPI")
+                .filter_map(|package| PackageName::from_str(&package.name).ok())
+                .unique()
+                .collect::<Vec<_>>();
+            for name in names {
+                database
+                    .0
+                    .entry(name)
+                    .or_default()
+                    .push(vulnerability.clone());
+            }
+        }
+        Ok(database)
+    }
+
+    /// Return the vulnerabilities affecting the given version of a package.
+    fn query(&self, name: &PackageName, version: &Version) -> Vec<Vulnerability> {
+        self.0
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter(|vulnerability| vulnerability.affects(name, version))
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvResponse {
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+/// Query the OSV API for the vulnerabilities affecting the given version of a package.
+async fn query_osv(
+    client: &ClientWithMiddleware,
+    name: &PackageName,
+    version: &Version,
+) -> Result<Vec<Vulnerability>> {
+    let query = serde_json::json!({
+        "package": { "name": name.as_ref(), "ecosystem": "PyPI" },
+        "version": version.to_string(),
+    });
+    let response: OsvResponse = client
+        .post(OSV_QUERY_URL)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&query)?)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response
+        .vulns
+        .into_iter()
+        .filter(|vulnerability| vulnerability.withdrawn.is_none())
+        .collect())
+}
@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use distribution_types::{
+    Dist, DistributionMetadata, FileLocation, Name, SourceDist, VersionOrUrl,
+};
+use uv_distribution::BuiltWheel;
+use uv_fs::Normalized;
+
+/// A manifest of the wheels that were built from source distributions, for consumption by
+/// pipelines that need to attest to exactly which binaries were produced.
+///
+/// For example:
+/// ```json
+/// {
+///   "wheels": [
+///     {
+///       "filename": "example-1.0.0-py3-none-any.whl",
+///       "sha256": "…",
+///       "size": 1234,
+///       "source": {
+///         "name": "example",
+///         "version": "1.0.0",
+///         "url": "https://files.pythonhosted.org/…/example-1.0.0.tar.gz",
+///         "sha256": "…"
+///       }
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+struct BuildManifest {
+    wheels: Vec<BuildManifestWheel>,
+}
+
+#[derive(Debug, Serialize)]
+struct BuildManifestWheel {
+    /// The filename of the built wheel.
+    filename: String,
+    /// The SHA-256 digest of the built wheel.
+    sha256: String,
+    /// The size of the built wheel, in bytes.
+    size: u64,
+    /// The source distribution from which the wheel was built.
+    source: BuildManifestSource,
+}
+
+#[derive(Debug, Serialize)]
+struct BuildManifestSource {
+    /// The name of the source distribution.
+    name: String,
+    /// The version of the source distribution, if it was fetched from a registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The URL or path from which the source distribution was fetched.
+    url: String,
+    /// The SHA-256 digest of the source distribution, if provided by the registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+}
+
+/// Write a [`BuildManifest`] describing the given built wheels to the given path.
+pub(crate) fn write_build_manifest(path: &Path, built_wheels: &[BuiltWheel]) -> Result<()> {
+    let mut wheels = built_wheels
+        .iter()
+        .map(|wheel| {
+            let (sha256, size) = digest(wheel.path()).with_context(|| {
+                format!(
+                    "Failed to hash built wheel: {}",
+                    wheel.path().normalized_display()
+                )
+            })?;
+            Ok(BuildManifestWheel {
+                filename: wheel.filename().to_string(),
+                sha256,
+                size,
+                source: BuildManifestSource::from(wheel.remote()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    wheels.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    let mut contents = serde_json::to_string_pretty(&BuildManifest { wheels })?;
+    contents.push('\n');
+    fs_err::write(path, contents)?;
+    Ok(())
+}
+
+impl From<&Dist> for BuildManifestSource {
+    fn from(dist: &Dist) -> Self {
+        match dist {
+            Dist::Source(SourceDist::Registry(sdist)) => Self {
+                name: sdist.filename.name.to_string(),
+                version: Some(sdist.filename.version.to_string()),
+                url: match &sdist.file.url {
+                    FileLocation::RelativeUrl(base, url) => {
+                        pypi_types::base_url_join_relative(base, url)
+                            .map_or_else(|_| url.clone(), |url| url.to_string())
+                    }
+                    location => location.to_string(),
+                },
+                sha256: sdist.file.hashes.sha256.clone(),
+            },
+            dist => Self {
+                name: dist.name().to_string(),
+                version: dist.version().map(ToString::to_string),
+                url: match dist.version_or_url() {
+                    VersionOrUrl::Url(url) => url.to_string(),
+                    VersionOrUrl::Version(version) => version.to_string(),
+                },
+                sha256: None,
+            },
+        }
+    }
+}
+
+/// Compute the SHA-256 digest and size of the file at the given path.
+fn digest(path: &Path) -> Result<(String, u64)> {
+    let mut file = fs_err::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut file, &mut hasher)?;
+    Ok((format!("{:x}", hasher.finalize()), size))
+}
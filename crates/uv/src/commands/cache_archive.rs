@@ -0,0 +1,187 @@
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use owo_colors::OwoColorize;
+use tar::{EntryType, Header};
+use tracing::debug;
+
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::Normalized;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// The buckets to include in an exported cache.
+///
+/// Buckets that contain machine-specific state (like interpreter metadata or virtual
+/// environments) are excluded. The artifact store is included (first, such that links are always
+/// created after their targets), as the wheel buckets link into it.
+const BUCKETS: [CacheBucket; 4] = [
+    CacheBucket::Archive,
+    CacheBucket::Wheels,
+    CacheBucket::BuiltWheels,
+    CacheBucket::Simple,
+];
+
+/// Export the cache to a `.tar.gz` archive.
+pub(crate) fn cache_export(cache: &Cache, path: &Path, mut printer: Printer) -> Result<ExitStatus> {
+    let file = fs_err::File::create(path)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut num_entries = 0usize;
+    for bucket in BUCKETS {
+        let root = cache.bucket(bucket);
+        if !root.exists() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&root).follow_links(false) {
+            let entry = entry?;
+            let relative = entry
+                .path()
+                .strip_prefix(cache.root())
+                .expect("Cache entry to be within the cache root");
+
+            if entry.path_is_symlink() {
+                // Store links relative to the cache root, such that they can be relocated.
+                let target = fs_err::read_link(entry.path())?;
+                let Ok(target) = target.strip_prefix(cache.root()) else {
+                    debug!(
+                        "Skipping link outside of the cache: {}",
+                        entry.path().display()
+                    );
+                    continue;
+                };
+                let mut header = Header::new_gnu();
+                header.set_entry_type(EntryType::Symlink);
+                header.set_size(0);
+                builder.append_link(&mut header, relative, relative_to(target, relative))?;
+            } else if entry.file_type().is_dir() {
+                builder.append_dir(relative, entry.path())?;
+            } else {
+                builder.append_path_with_name(entry.path(), relative)?;
+            }
+            num_entries += 1;
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+
+    writeln!(
+        printer,
+        "Exported {} cache entries to: {}",
+        num_entries,
+        path.normalized_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Import a `.tar.gz` archive, as created by [`cache_export`], into the cache.
+///
+/// Existing entries are overwritten.
+pub(crate) fn cache_import(cache: &Cache, path: &Path, mut printer: Printer) -> Result<ExitStatus> {
+    let file = fs_err::File::open(path)?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    archive.set_preserve_mtime(true);
+
+    let mut num_entries = 0usize;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let relative = entry.path()?.into_owned();
+
+        if !is_bucket_path(&relative) {
+            bail!(
+                "Invalid cache archive entry: `{}`",
+                relative.normalized_display()
+            );
+        }
+
+        if entry.header().entry_type() == EntryType::Symlink {
+            let Some(target) = entry.link_name()? else {
+                bail!("Invalid link in cache archive: `{}`", relative.display());
+            };
+
+            // Resolve the link against the new cache root.
+            let Some(target) = resolve(&relative, &target).filter(|path| is_bucket_path(path))
+            else {
+                bail!("Invalid link in cache archive: `{}`", relative.display());
+            };
+            let link = cache.root().join(&relative);
+            fs_err::create_dir_all(link.parent().expect("Cache entry to have parent"))?;
+            uv_fs::replace_symlink(cache.root().join(target), &link)?;
+        } else if entry.header().entry_type().is_file() {
+            // Unpack into a temporary file and rename it into place, such that an interrupted
+            // import can't leave a partially-written file in the cache.
+            let target = cache.root().join(&relative);
+            let parent = target.parent().expect("Cache entry to have parent");
+            fs_err::create_dir_all(parent)?;
+            let temp_path = tempfile::NamedTempFile::new_in(parent)?.into_temp_path();
+            entry
+                .unpack(&temp_path)
+                .with_context(|| format!("Failed to unpack: `{}`", relative.display()))?;
+            temp_path
+                .persist(&target)
+                .with_context(|| format!("Failed to unpack: `{}`", relative.display()))?;
+        } else {
+            entry
+                .unpack_in(cache.root())
+                .with_context(|| format!("Failed to unpack: `{}`", relative.display()))?;
+        }
+        num_entries += 1;
+    }
+
+    writeln!(
+        printer,
+        "Imported {} cache entries into: {}",
+        num_entries,
+        cache.root().normalized_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Returns `true` if the path is a relative path within one of the exported buckets.
+fn is_bucket_path(path: &Path) -> bool {
+    let mut components = path.components();
+    let Some(Component::Normal(bucket)) = components.next() else {
+        return false;
+    };
+    BUCKETS.iter().any(|b| bucket == b.to_string().as_str())
+        && components.all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Express `target` (relative to the cache root) relative to the parent directory of `link` (also
+/// relative to the cache root).
+fn relative_to(target: &Path, link: &Path) -> PathBuf {
+    let depth = link
+        .parent()
+        .map_or(0, |parent| parent.components().count());
+    let mut path = PathBuf::new();
+    for _ in 0..depth {
+        path.push("..");
+    }
+    path.join(target)
+}
+
+/// Resolve a relative link `target` against the parent directory of `link`, returning a path
+/// relative to the cache root, or `None` if the target escapes the cache root.
+fn resolve(link: &Path, target: &Path) -> Option<PathBuf> {
+    let mut path = link.parent()?.to_path_buf();
+    for component in target.components() {
+        match component {
+            Component::ParentDir => {
+                if !path.pop() {
+                    return None;
+                }
+            }
+            Component::Normal(name) => path.push(name),
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
@@ -0,0 +1,9 @@
+use anstream::println;
+
+use uv_cache::Cache;
+use uv_fs::Normalized;
+
+/// Show the cache directory.
+pub(crate) fn cache_dir(cache: &Cache) {
+    println!("{}", cache.root().normalized_display());
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use anstream::println;
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use distribution_filename::WheelFilename;
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::Normalized;
+use uv_normalize::PackageName;
+
+use crate::commands::clean::format_bytes;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Show the cached index metadata, wheels, and built wheels for a package.
+pub(crate) fn cache_info(
+    cache: &Cache,
+    package: &PackageName,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let entries = cache.package_entries(package).with_context(|| {
+        format!(
+            "Failed to read cache at: {}",
+            cache.root().normalized_display()
+        )
+    })?;
+
+    if entries.is_empty() {
+        writeln!(
+            printer,
+            "No cache entries found for {} at: {}",
+            package.cyan(),
+            cache.root().normalized_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer,
+        "Cache entries for {} at: {}",
+        package.cyan(),
+        cache.root().normalized_display().cyan()
+    )?;
+
+    let mut total_bytes = 0;
+    for bucket in [
+        CacheBucket::Simple,
+        CacheBucket::Wheels,
+        CacheBucket::BuiltWheels,
+    ] {
+        let paths = entries
+            .iter()
+            .filter(|(entry_bucket, _)| *entry_bucket == bucket)
+            .map(|(_, path)| path)
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            continue;
+        }
+
+        println!(
+            "{}",
+            match bucket {
+                CacheBucket::Simple => "Index metadata:",
+                CacheBucket::Wheels => "Wheels:",
+                _ => "Built wheels:",
+            }
+            .bold()
+        );
+        for path in paths {
+            let bytes = disk_usage(path);
+            total_bytes += bytes;
+            println!(
+                "  {} ({}, updated {})",
+                path.strip_prefix(cache.root())
+                    .unwrap_or(path)
+                    .normalized_display(),
+                format_bytes(bytes),
+                age(path)
+            );
+            match bucket {
+                CacheBucket::Wheels => {
+                    for (stem, kinds) in wheels(path)? {
+                        println!("    {stem}: {}", kinds.join(", ").dimmed());
+                    }
+                }
+                CacheBucket::BuiltWheels => {
+                    for (build, tags) in builds(path) {
+                        if tags.is_empty() {
+                            println!("    {build}: {}", "metadata only".dimmed());
+                        } else {
+                            println!("    {build}: {}", tags.join(", ").dimmed());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Found {} cache {} ({})",
+            entries.len(),
+            if entries.len() == 1 {
+                "entry"
+            } else {
+                "entries"
+            },
+            format_bytes(total_bytes)
+        )
+        .dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Summarize the cached wheels in a package directory of the wheels bucket, by wheel.
+///
+/// Each wheel may be cached as its metadata (and cache policy), its archive, and its unzipped
+/// contents (as a link into the archive bucket).
+fn wheels(path: &Path) -> Result<BTreeMap<String, Vec<&'static str>>> {
+    let mut wheels = BTreeMap::<String, Vec<&'static str>>::new();
+    for entry in fs_err::read_dir(path)? {
+        let entry = entry?;
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let (stem, kind) = if let Some(stem) = file_name.strip_suffix(".msgpack") {
+            (stem, "metadata")
+        } else if let Some(stem) = file_name.strip_suffix(".whl") {
+            (stem, "archive")
+        } else if path.is_dir() {
+            (file_name, "unzipped")
+        } else {
+            continue;
+        };
+        wheels.entry(stem.to_string()).or_default().push(kind);
+    }
+    for kinds in wheels.values_mut() {
+        kinds.sort_unstable();
+    }
+    Ok(wheels)
+}
+
+/// Summarize the builds in a package directory of the built wheels bucket, returning the tags of
+/// the wheels built for each source distribution.
+fn builds(path: &Path) -> BTreeMap<String, Vec<String>> {
+    let mut builds = BTreeMap::<String, Vec<String>>::new();
+    for entry in walkdir::WalkDir::new(path).min_depth(1) {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(file_name) = entry.file_name().to_str() else {
+            continue;
+        };
+        let Ok(relative) = entry.path().strip_prefix(path) else {
+            continue;
+        };
+
+        // Every directory that holds metadata or wheels is a build of a source distribution.
+        if file_name == "metadata.msgpack" || file_name == "manifest.msgpack" {
+            if let Some(build) = relative.parent() {
+                builds
+                    .entry(build.normalized_display().to_string())
+                    .or_default();
+            }
+            continue;
+        }
+
+        // Built wheels are stored as archives, and unzipped alongside them (without the `.whl`
+        // extension).
+        let wheel = file_name.strip_suffix(".whl").unwrap_or(file_name);
+        let Ok(filename) = WheelFilename::from_str(&format!("{wheel}.whl")) else {
+            continue;
+        };
+        if let Some(build) = relative.parent() {
+            let tags = builds
+                .entry(build.normalized_display().to_string())
+                .or_default();
+            let tag = filename.get_tag();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    for tags in builds.values_mut() {
+        tags.sort();
+    }
+    builds
+}
+
+/// Return the total size of the files at the given path, following links into the archive bucket.
+fn disk_usage(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Format the time since the path was last modified, e.g., `3h ago`.
+fn age(path: &Path) -> String {
+    let Some(elapsed) = fs_err::symlink_metadata(path)
+        .ok()
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+    else {
+        return "at an unknown time".to_string();
+    };
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 60 * 60 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 24 * 60 * 60 {
+        format!("{}h ago", secs / (60 * 60))
+    } else {
+        format!("{}d ago", secs / (24 * 60 * 60))
+    }
+}
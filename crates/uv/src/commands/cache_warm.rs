@@ -0,0 +1,204 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use platform_host::Platform;
+use uv_cache::{Cache, Refresh, Timestamp};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClientBuilder, TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Normalized;
+use uv_installer::{Downloader, NoBinary};
+use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_resolver::{DistFinder, InMemoryIndex};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::warn_user;
+
+use crate::commands::reporters::{DownloadReporter, FinderReporter};
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{RequirementsSource, RequirementsSpecification};
+
+/// Revalidate the cached index metadata for a set of pinned requirements, and prefetch their
+/// distributions into the cache.
+///
+/// If an interval is provided, the cache is re-warmed on that interval until the process is
+/// terminated.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn cache_warm(
+    sources: &[RequirementsSource],
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    interval: Option<Duration>,
+    time_limit: Option<Duration>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    loop {
+        let pass = warm(
+            sources,
+            python,
+            &index_locations,
+            &trusted_hosts,
+            setup_py,
+            no_build,
+            no_binary,
+            timeouts,
+            concurrency,
+            &cache,
+            printer,
+        );
+
+        let result = if let Some(time_limit) = time_limit {
+            tokio::time::timeout(time_limit, pass)
+                .await
+                .unwrap_or_else(|_| {
+                    warn_user!(
+                        "Stopped warming the cache after exceeding the time limit of {}",
+                        elapsed(time_limit)
+                    );
+                    Ok(())
+                })
+        } else {
+            pass.await
+        };
+
+        // In a one-shot invocation, propagate any errors. When running on an interval, report the
+        // error and try again on the next pass.
+        let Some(interval) = interval else {
+            result?;
+            return Ok(ExitStatus::Success);
+        };
+        if let Err(err) = result {
+            writeln!(printer, "{}{} {err:#}", "error".red().bold(), ":".bold())?;
+        }
+
+        debug!("Warming the cache again in {}", elapsed(interval));
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Perform a single pass over the requirements, revalidating and prefetching each distribution.
+#[allow(clippy::too_many_arguments)]
+async fn warm(
+    sources: &[RequirementsSource],
+    python: Option<&str>,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<()> {
+    let start = std::time::Instant::now();
+
+    // Revalidate any entries that were created before this pass.
+    let cache = cache.clone().with_refresh(Refresh::All(Timestamp::now()));
+
+    // Read the requirements on every pass, to pick up any changes to the lockfile.
+    let spec = RequirementsSpecification::from_simple_sources(sources)?;
+    if !spec.editables.is_empty() {
+        debug!("Skipping editable requirements, which aren't cached");
+    }
+    let index_locations = index_locations.clone().combine(
+        spec.index_url,
+        spec.extra_index_urls,
+        spec.find_links,
+        spec.no_index,
+    );
+
+    if spec.requirements.is_empty() {
+        writeln!(printer, "No requirements found")?;
+        return Ok(());
+    }
+
+    // Locate the interpreter for which distributions should be fetched.
+    let platform = Platform::current()?;
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, &platform, &cache)?
+            .ok_or(Error::NoSuchPython(python.to_string()))?
+    } else {
+        find_default_python(&platform, &cache)?
+    };
+    debug!(
+        "Using Python {} interpreter at {}",
+        interpreter.python_version(),
+        interpreter.sys_executable().normalized_display().cyan()
+    );
+    let tags = interpreter.tags()?;
+
+    // Prep the registry client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(Connectivity::Online)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts.to_vec())
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags)
+    };
+
+    // Prep the build context, in case any source distributions need to be built.
+    let index = InMemoryIndex::default();
+    let in_flight = InFlight::default();
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        interpreter.sys_executable().to_path_buf(),
+        setup_py,
+        no_build,
+        no_binary,
+    )
+    .with_concurrency(concurrency);
+
+    // Find the distribution for each requirement, revalidating the index metadata.
+    let finder = DistFinder::new(tags, &client, &interpreter, &flat_index, no_binary)
+        .with_reporter(FinderReporter::from(printer).with_length(spec.requirements.len() as u64));
+    let resolution = finder.resolve(&spec.requirements).await?;
+    let distributions = resolution.into_distributions().collect::<Vec<_>>();
+
+    // Download (and, if necessary, build) each distribution into the cache.
+    let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
+        .with_reporter(DownloadReporter::from(printer).with_length(distributions.len() as u64));
+    let wheels = downloader
+        .download(distributions, &in_flight)
+        .await
+        .context("Failed to download distributions")?;
+
+    let s = if wheels.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Warmed {} in {}",
+            format!("{} package{}", wheels.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    Ok(())
+}
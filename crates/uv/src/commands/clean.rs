@@ -60,12 +60,7 @@ pub(crate) fn clean(
 
         // If any, write a summary of the total byte count removed.
         if summary.total_bytes > 0 {
-            let bytes = if summary.total_bytes < 1024 {
-                format!("{}B", summary.total_bytes)
-            } else {
-                let (bytes, unit) = human_readable_bytes(summary.total_bytes);
-                format!("{bytes:.1}{unit}")
-            };
+            let bytes = format_bytes(summary.total_bytes);
             write!(printer, " ({})", bytes.green())?;
         }
 
@@ -103,12 +98,7 @@ pub(crate) fn clean(
 
             // If any, write a summary of the total byte count removed.
             if summary.total_bytes > 0 {
-                let bytes = if summary.total_bytes < 1024 {
-                    format!("{}B", summary.total_bytes)
-                } else {
-                    let (bytes, unit) = human_readable_bytes(summary.total_bytes);
-                    format!("{bytes:.1}{unit}")
-                };
+                let bytes = format_bytes(summary.total_bytes);
                 write!(printer, " ({})", bytes.green())?;
             }
 
@@ -119,6 +109,16 @@ pub(crate) fn clean(
     Ok(ExitStatus::Success)
 }
 
+/// Formats a number of bytes as a human-readable size (e.g., `1.2MiB`).
+pub(super) fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes}B")
+    } else {
+        let (bytes, unit) = human_readable_bytes(bytes);
+        format!("{bytes:.1}{unit}")
+    }
+}
+
 /// Formats a number of bytes into a human readable SI-prefixed size.
 ///
 /// Returns a tuple of `(quantity, units)`.
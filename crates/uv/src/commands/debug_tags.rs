@@ -0,0 +1,134 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anstream::println;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+
+use distribution_filename::WheelFilename;
+use platform_host::Platform;
+use platform_tags::{IncompatibleTag, TagCompatibility, Tags};
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_interpreter::{find_default_python, find_requested_python, Error};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Print the wheel tags supported by the target interpreter, in order of priority, or, if any
+/// wheels are provided, explain whether (and why) each is compatible.
+pub(crate) fn debug_tags(
+    python: Option<&str>,
+    wheels: &[String],
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let platform = Platform::current()?;
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, &platform, cache)?
+            .ok_or(Error::NoSuchPython(python.to_string()))?
+    } else {
+        find_default_python(&platform, cache)?
+    };
+    let tags = interpreter.tags()?;
+    let num_tags = tags.iter().count();
+
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Using {} {} at {} ({num_tags} tags)",
+            interpreter.implementation_name(),
+            interpreter.python_version(),
+            interpreter.sys_executable().normalized_display()
+        )
+        .dimmed()
+    )?;
+
+    if wheels.is_empty() {
+        for (py, abi, platform) in tags.iter() {
+            println!("{py}-{abi}-{platform}");
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    let mut compatible = true;
+    for wheel in wheels {
+        // Accept a path or URL, in addition to a bare filename.
+        let filename = wheel.rsplit(['/', '\\']).next().unwrap_or(wheel);
+        let filename = WheelFilename::from_str(filename)
+            .with_context(|| format!("Invalid wheel filename: `{wheel}`"))?;
+        let explanation = explain(&filename, tags, num_tags);
+        compatible &= explanation.is_ok();
+        match explanation {
+            Ok(reason) => println!("{filename}: {} ({reason})", "compatible".green()),
+            Err(reason) => println!("{filename}: {} ({reason})", "incompatible".red()),
+        }
+    }
+
+    if compatible {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Explain whether the wheel is compatible with the given tags, returning the reason.
+fn explain(wheel: &WheelFilename, tags: &Tags, num_tags: usize) -> Result<String, String> {
+    match wheel.compatibility(tags) {
+        TagCompatibility::Compatible(_) => {
+            // Find the most-preferred tag that the wheel matches.
+            let (rank, (py, abi, platform)) = tags
+                .iter()
+                .find_position(|(py, abi, platform)| {
+                    wheel.python_tag.iter().any(|tag| tag == py)
+                        && wheel.abi_tag.iter().any(|tag| tag == abi)
+                        && wheel.platform_tag.iter().any(|tag| tag == platform)
+                })
+                .expect("compatible wheel matches a tag");
+            Ok(format!(
+                "matches `{py}-{abi}-{platform}`, tag {} of {num_tags}",
+                rank + 1
+            ))
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Invalid) => {
+            Err("the wheel has no valid tags".to_string())
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Python) => {
+            let supported = tags.iter().map(|(py, ..)| py).unique().join(", ");
+            Err(format!(
+                "the Python tag `{}` is not supported; expected one of: {supported}",
+                wheel.python_tag.join(".")
+            ))
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Abi) => {
+            let supported = tags
+                .iter()
+                .filter(|(py, ..)| wheel.python_tag.iter().any(|tag| tag == py))
+                .map(|(_, abi, _)| abi)
+                .unique()
+                .join(", ");
+            Err(format!(
+                "the ABI tag `{}` is not supported for Python tag `{}`; expected one of: {supported}",
+                wheel.abi_tag.join("."),
+                wheel.python_tag.join(".")
+            ))
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Platform) => {
+            let supported = tags
+                .iter()
+                .filter(|(py, abi, _)| {
+                    wheel.python_tag.iter().any(|tag| tag == py)
+                        && wheel.abi_tag.iter().any(|tag| tag == abi)
+                })
+                .map(|(.., platform)| platform)
+                .unique()
+                .join(", ");
+            Err(format!(
+                "the platform tag `{}` is not supported; expected one of: {supported}",
+                wheel.platform_tag.join(".")
+            ))
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use miette::{Diagnostic, IntoDiagnostic};
+use owo_colors::OwoColorize;
+use thiserror::Error;
+use tracing::debug;
+
+use distribution_types::{DistributionMetadata, IndexLocations, Name};
+use pep508_rs::Requirement;
+use uv_cache::{Cache, CacheBucket};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClientBuilder, TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, SitePackages};
+use uv_interpreter::{Interpreter, Virtualenv};
+use uv_resolver::{InMemoryIndex, OptionsBuilder};
+use uv_traits::{BuildContext, Concurrency, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::printer::Printer;
+
+#[derive(Error, Debug, Diagnostic)]
+enum EphemeralError {
+    #[error("Failed to create environment")]
+    #[diagnostic(code(uv::ephemeral::creation))]
+    Creation(#[source] gourgeist::Error),
+
+    #[error("Failed to install requirements: {0}")]
+    #[diagnostic(code(uv::ephemeral::install))]
+    Install(String, #[source] anyhow::Error),
+
+    #[error("Failed to extract interpreter tags")]
+    #[diagnostic(code(uv::ephemeral::tags))]
+    Tags(#[source] platform_tags::TagsError),
+
+    #[error("Failed to resolve `--find-links` entry")]
+    #[diagnostic(code(uv::ephemeral::flat_index))]
+    FlatIndex(#[source] uv_client::FlatIndexError),
+
+    #[error("Failed to read installed packages")]
+    #[diagnostic(code(uv::ephemeral::site_packages))]
+    SitePackages(#[source] anyhow::Error),
+}
+
+/// Create (or reuse) a cached, ephemeral virtual environment in which the given requirements are
+/// installed.
+///
+/// Each environment is keyed by the requirements, the base interpreter, and the sources from which
+/// the requirements are resolved, such that repeated invocations can reuse the same environment.
+/// An environment that already satisfies the requirements is reused as-is, unless `upgrade` is set,
+/// in which case the requirements are re-resolved and the environment is upgraded to match.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn ephemeral_environment(
+    requirements: &[Requirement],
+    interpreter: Interpreter,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    connectivity: Connectivity,
+    upgrade: bool,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    mut printer: Printer,
+) -> miette::Result<Virtualenv> {
+    let key = requirements
+        .iter()
+        .map(ToString::to_string)
+        .sorted()
+        .collect::<Vec<_>>();
+    let digest = cache_key::digest(&(
+        key,
+        interpreter.sys_executable(),
+        index_locations,
+        trusted_hosts,
+        setup_py,
+        no_build,
+        no_binary,
+        exclude_newer.map(|exclude_newer| exclude_newer.to_rfc3339()),
+    ));
+    let root = cache.shard(CacheBucket::Environments, &digest);
+
+    // Prevent concurrent invocations from creating or modifying the environment. The lock lives
+    // alongside the environment, rather than within it, since it must be held before the
+    // environment exists.
+    let _lock = cache
+        .lock_entry(&cache.entry(CacheBucket::Environments, "", &digest))
+        .into_diagnostic()?;
+
+    // Reuse the environment, if it exists; otherwise, create it.
+    let venv = if root.join("pyvenv.cfg").is_file() {
+        debug!("Reusing environment at: {}", root.normalized_display());
+        Virtualenv::from_interpreter(interpreter, &root)
+    } else {
+        debug!("Creating environment at: {}", root.normalized_display());
+        gourgeist::create_venv(&root, interpreter, None, false, false)
+            .map_err(EphemeralError::Creation)?
+    };
+
+    // Prevent other writers (e.g., `uv pip install`) from modifying the environment.
+    let _venv_lock = venv.lock().into_diagnostic()?;
+
+    // If the environment already satisfies the requirements, we're done, unless an upgrade was
+    // requested.
+    if !upgrade {
+        let site_packages =
+            SitePackages::from_executable(&venv).map_err(EphemeralError::SitePackages)?;
+        if site_packages
+            .satisfies(requirements, &[], &[])
+            .map_err(EphemeralError::SitePackages)?
+        {
+            return Ok(venv);
+        }
+    }
+
+    let interpreter = venv.interpreter();
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts.to_vec())
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let tags = interpreter.tags().map_err(EphemeralError::Tags)?;
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client
+            .fetch(index_locations.flat_index())
+            .await
+            .map_err(EphemeralError::FlatIndex)?;
+        FlatIndex::from_entries(entries, tags)
+    };
+
+    // Create a shared in-memory index.
+    let index = InMemoryIndex::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    // Prep the build context.
+    let options = OptionsBuilder::new().exclude_newer(exclude_newer).build();
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        interpreter,
+        index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        venv.python_executable(),
+        setup_py,
+        no_build,
+        no_binary,
+    )
+    .with_options(options)
+    .with_concurrency(concurrency);
+
+    // Resolve the requirements.
+    let resolution = build_dispatch
+        .resolve(requirements)
+        .await
+        .map_err(|err| EphemeralError::Install(requirements.iter().join(", "), err))?;
+
+    // Install into the environment, sizing the install pool to match the cache directory's disk.
+    crate::concurrency::size_install_pool(cache.root());
+    build_dispatch
+        .install(&resolution, &venv)
+        .await
+        .map_err(|err| EphemeralError::Install(requirements.iter().join(", "), err))?;
+
+    for distribution in resolution
+        .distributions()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+    {
+        writeln!(
+            printer,
+            " {} {}{}",
+            "+".green(),
+            distribution.name().as_ref().white().bold(),
+            distribution.version_or_url().dimmed()
+        )
+        .into_diagnostic()?;
+    }
+
+    Ok(venv)
+}
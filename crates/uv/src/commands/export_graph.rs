@@ -0,0 +1,192 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+use distribution_types::{Name, VersionOrUrl};
+use uv_resolver::ResolutionGraph;
+
+/// The format in which to export a resolution graph.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum GraphFormat {
+    /// A Graphviz DOT file, for visualization (e.g., with `dot -Tsvg`).
+    #[default]
+    Dot,
+    /// A JSON object of nodes and edges, for consumption by other tools.
+    Json,
+}
+
+impl CacheKey for GraphFormat {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Dot => 0u8.cache_key(state),
+            Self::Json => 1u8.cache_key(state),
+        }
+    }
+}
+
+/// Render the resolution graph in the given format.
+///
+/// Nodes and edges are sorted by package name, such that the output is deterministic.
+pub(crate) fn render_graph(resolution: &ResolutionGraph, format: GraphFormat) -> String {
+    let graph = ExportGraph::from(resolution);
+    match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Json => {
+            let mut output =
+                serde_json::to_string_pretty(&graph).expect("Graph to be serializable");
+            output.push('\n');
+            output
+        }
+    }
+}
+
+/// A resolution graph, in exportable form.
+///
+/// For example:
+/// ```json
+/// {
+///   "nodes": [
+///     { "name": "flask", "version": "3.0.0" },
+///     { "name": "importlib-metadata", "version": "7.0.0" }
+///   ],
+///   "edges": [
+///     {
+///       "from": "flask",
+///       "to": "importlib-metadata",
+///       "specifier": ">=3.6.0",
+///       "extras": [],
+///       "markers": ["python_version < '3.10'"]
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+struct ExportGraph {
+    /// The pinned packages, sorted by name.
+    nodes: Vec<ExportNode>,
+    /// The dependencies between the pinned packages, sorted by dependent and then dependency.
+    edges: Vec<ExportEdge>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportNode {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportEdge {
+    /// The name of the dependent package.
+    from: String,
+    /// The name of the dependency.
+    to: String,
+    /// The versions of the dependency that are allowed by the dependent.
+    specifier: String,
+    /// The extras of the dependency that are requested by the dependent.
+    extras: Vec<String>,
+    /// The markers under which the dependency applies; empty if the dependency is unconditional.
+    markers: Vec<String>,
+}
+
+impl From<&ResolutionGraph> for ExportGraph {
+    fn from(resolution: &ResolutionGraph) -> Self {
+        let petgraph = resolution.petgraph();
+
+        let nodes = petgraph
+            .node_weights()
+            .map(|dist| {
+                let (version, url) = match dist.version_or_url() {
+                    VersionOrUrl::Version(version) => (Some(version.to_string()), None),
+                    VersionOrUrl::Url(url) => (None, Some(url.to_string())),
+                };
+                ExportNode {
+                    name: dist.name().to_string(),
+                    version,
+                    url,
+                }
+            })
+            .sorted_unstable_by(|a, b| a.name.cmp(&b.name))
+            .collect();
+
+        let edges = petgraph
+            .raw_edges()
+            .iter()
+            .map(|edge| {
+                let dependency = &edge.weight;
+                ExportEdge {
+                    from: petgraph[edge.source()].name().to_string(),
+                    to: petgraph[edge.target()].name().to_string(),
+                    specifier: dependency.range.to_string(),
+                    extras: dependency.extras.iter().map(ToString::to_string).collect(),
+                    markers: dependency.markers.iter().map(ToString::to_string).collect(),
+                }
+            })
+            .sorted_unstable_by(|a, b| a.from.cmp(&b.from).then_with(|| a.to.cmp(&b.to)))
+            .collect();
+
+        Self { nodes, edges }
+    }
+}
+
+impl ExportNode {
+    /// The label for the node, e.g., `flask==3.0.0`.
+    fn label(&self) -> String {
+        match (&self.version, &self.url) {
+            (Some(version), _) => format!("{}=={version}", self.name),
+            (None, Some(url)) => format!("{} @ {url}", self.name),
+            (None, None) => self.name.clone(),
+        }
+    }
+}
+
+impl ExportEdge {
+    /// The label for the edge, e.g., `[socks] >=2.0; python_version < '3.10'`.
+    fn label(&self) -> String {
+        let mut label = String::new();
+        if !self.extras.is_empty() {
+            write!(label, "[{}] ", self.extras.join(", ")).unwrap();
+        }
+        label.push_str(&self.specifier);
+        match self.markers.as_slice() {
+            [] => {}
+            [marker] => write!(label, "; {marker}").unwrap(),
+            markers => write!(
+                label,
+                "; {}",
+                markers
+                    .iter()
+                    .map(|marker| format!("({marker})"))
+                    .join(" or ")
+            )
+            .unwrap(),
+        }
+        label
+    }
+}
+
+impl ExportGraph {
+    /// Render the graph as a Graphviz DOT file.
+    fn to_dot(&self) -> String {
+        let mut output = String::from("digraph {\n");
+        for node in &self.nodes {
+            writeln!(output, "    {:?} [label={:?}]", node.name, node.label()).unwrap();
+        }
+        for edge in &self.edges {
+            writeln!(
+                output,
+                "    {:?} -> {:?} [label={:?}]",
+                edge.from,
+                edge.to,
+                edge.label()
+            )
+            .unwrap();
+        }
+        output.push_str("}\n");
+        output
+    }
+}
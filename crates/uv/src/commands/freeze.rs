@@ -1,23 +1,47 @@
 use std::fmt::Write;
 
 use anstream::println;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use futures::{StreamExt, TryStreamExt};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
-use distribution_types::Name;
+use distribution_types::{IndexLocations, InstalledDist, Name};
+use pep440_rs::Version;
 use platform_host::Platform;
+use pypi_types::{HashAlgorithm, Hashes};
 use uv_cache::Cache;
+use uv_client::{
+    Connectivity, HttpTimeouts, OwnedArchive, RegistryClient, RegistryClientBuilder, TrustedHost,
+};
 use uv_fs::Normalized;
 use uv_installer::SitePackages;
 use uv_interpreter::Virtualenv;
+use uv_normalize::PackageName;
+use uv_traits::Concurrency;
+use uv_warnings::warn_user;
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
 
 /// Enumerate the installed packages in the current environment.
-pub(crate) fn freeze(cache: &Cache, strict: bool, mut printer: Printer) -> Result<ExitStatus> {
+///
+/// If `lock` is set, the packages are instead written as a fully pinned requirements file, with
+/// the hashes of the matching distributions on the index.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn freeze(
+    strict: bool,
+    lock: bool,
+    hash_algorithms: &[HashAlgorithm],
+    index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
     let venv = Virtualenv::from_env(platform, cache)?;
@@ -30,11 +54,33 @@ pub(crate) fn freeze(cache: &Cache, strict: bool, mut printer: Printer) -> Resul
 
     // Build the installed index.
     let site_packages = SitePackages::from_executable(&venv)?;
-    for dist in site_packages
-        .iter()
-        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
-    {
-        println!("{dist}");
+    if lock {
+        let client = RegistryClientBuilder::new(cache.clone())
+            .index_urls(index_locations.index_urls())
+            .connectivity(connectivity)
+            .timeouts(timeouts)
+            .trusted_hosts(trusted_hosts)
+            .build();
+
+        let dists = site_packages
+            .iter()
+            .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+            .collect::<Vec<_>>();
+        let lines = futures::stream::iter(dists)
+            .map(|dist| locked(dist, hash_algorithms, &client))
+            .buffered(concurrency.downloads)
+            .try_collect::<Vec<_>>()
+            .await?;
+        for line in lines {
+            println!("{line}");
+        }
+    } else {
+        for dist in site_packages
+            .iter()
+            .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+        {
+            println!("{dist}");
+        }
     }
 
     // Validate that the environment is consistent.
@@ -52,3 +98,81 @@ pub(crate) fn freeze(cache: &Cache, strict: bool, mut printer: Printer) -> Resul
 
     Ok(ExitStatus::Success)
 }
+
+/// Format an installed distribution as a pinned requirement, annotated with the hashes of the
+/// distributions published to the index for the installed version.
+///
+/// Distributions installed from a URL are pinned to that URL, as they can't be matched to the
+/// index.
+async fn locked(
+    dist: &InstalledDist,
+    hash_algorithms: &[HashAlgorithm],
+    client: &RegistryClient,
+) -> Result<String> {
+    match dist {
+        InstalledDist::Url(dist) if dist.editable => Ok(format!("-e {}", dist.url)),
+        InstalledDist::Url(dist) => Ok(format!("{} @ {}", dist.name, dist.url)),
+        InstalledDist::LegacyEditable(dist) => Ok(format!("-e {}", dist.target_url)),
+        InstalledDist::Registry(_) | InstalledDist::EggInfo(_) => {
+            let mut line = format!("{}=={}", dist.name(), dist.version());
+            let hashes = index_hashes(dist.name(), dist.version(), client)
+                .await
+                .with_context(|| format!("Failed to fetch hashes for: {dist}"))?;
+            let Some(hashes) = hashes else {
+                warn_user!(
+                    "{dist} was not found on the index, so it will be pinned without hashes"
+                );
+                return Ok(line);
+            };
+            for hash in &hashes {
+                for algorithm in hash_algorithms {
+                    if let Some(hash) = hash.format(*algorithm) {
+                        write!(line, " \\\n    --hash={hash}")?;
+                    }
+                }
+            }
+            Ok(line)
+        }
+    }
+}
+
+/// Return the hashes of the distributions published for the given version of a package, or `None`
+/// if the version isn't published to the index.
+async fn index_hashes(
+    name: &PackageName,
+    version: &Version,
+    client: &RegistryClient,
+) -> Result<Option<Vec<Hashes>>> {
+    let results = match client.simple(name).await {
+        Ok(results) => results,
+        Err(err) if matches!(err.kind(), uv_client::ErrorKind::PackageNotFound(_)) => {
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let Some(metadatum) = results
+        .iter()
+        .flat_map(|(_, archive)| OwnedArchive::deserialize(archive))
+        .find(|metadatum| metadatum.version == *version)
+    else {
+        return Ok(None);
+    };
+
+    let mut hashes = metadatum
+        .files
+        .wheels
+        .into_iter()
+        .map(|wheel| wheel.file.hashes)
+        .chain(
+            metadatum
+                .files
+                .source_dists
+                .into_iter()
+                .map(|sdist| sdist.file.hashes),
+        )
+        .collect::<Vec<_>>();
+    hashes.sort_unstable();
+    hashes.dedup();
+    Ok(Some(hashes))
+}
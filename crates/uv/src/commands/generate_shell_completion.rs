@@ -0,0 +1,74 @@
+use std::io::Write;
+
+use anyhow::Result;
+use clap_complete::Shell;
+
+use crate::commands::ExitStatus;
+
+/// Complete the names of the packages installed in the current environment for
+/// `uv pip uninstall`, in Bash.
+const BASH_DYNAMIC: &str = r#"
+_uv_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ "${COMP_WORDS[1]}" == "pip" && "${COMP_WORDS[2]}" == "uninstall" && "${cur}" != -* ]]; then
+        local packages
+        packages="$(uv pip freeze 2>/dev/null | sed -e 's/[ =@].*//')"
+        COMPREPLY=( $(compgen -W "${packages}" -- "${cur}") )
+        return 0
+    fi
+    _uv "$@"
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _uv_dynamic -o nosort -o bashdefault -o default uv
+else
+    complete -F _uv_dynamic -o bashdefault -o default uv
+fi
+"#;
+
+/// Complete the names of the packages installed in the current environment for
+/// `uv pip uninstall`, in Zsh.
+const ZSH_DYNAMIC: &str = r#"
+_uv_dynamic() {
+    if [[ "${words[2]}" == "pip" && "${words[3]}" == "uninstall" && "${PREFIX}" != -* ]]; then
+        local -a packages
+        packages=(${${(f)"$(uv pip freeze 2>/dev/null)"}%%[ =@]*})
+        compadd -a packages
+        return
+    fi
+    _uv "$@"
+}
+
+compdef _uv_dynamic uv
+"#;
+
+/// Complete the names of the packages installed in the current environment for
+/// `uv pip uninstall`, in Fish.
+const FISH_DYNAMIC: &str = r#"
+complete -c uv -n "__fish_seen_subcommand_from pip; and __fish_seen_subcommand_from uninstall" -f -a "(uv pip freeze 2>/dev/null | string replace -r '[ =@].*' '')"
+"#;
+
+/// Write the completion script for the given shell to stdout.
+///
+/// In Bash, Zsh, and Fish, the script also completes the names of installed packages for
+/// `uv pip uninstall`, by querying the current environment at completion time.
+pub(crate) fn generate_shell_completion(
+    shell: Shell,
+    command: &mut clap::Command,
+) -> Result<ExitStatus> {
+    let mut stdout = std::io::stdout().lock();
+    clap_complete::generate(shell, command, "uv", &mut stdout);
+
+    let dynamic = match shell {
+        Shell::Bash => Some(BASH_DYNAMIC),
+        Shell::Zsh => Some(ZSH_DYNAMIC),
+        Shell::Fish => Some(FISH_DYNAMIC),
+        _ => None,
+    };
+    if let Some(dynamic) = dynamic {
+        stdout.write_all(dynamic.as_bytes())?;
+    }
+    stdout.flush()?;
+
+    Ok(ExitStatus::Success)
+}
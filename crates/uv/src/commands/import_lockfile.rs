@@ -0,0 +1,502 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::io::stdout;
+use std::path::Path;
+use std::str::FromStr;
+
+use anstream::AutoStream;
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use tracing::debug;
+use url::Url;
+
+use pep508_rs::MarkerTree;
+use uv_fs::Normalized;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The index URL for PyPI, which is omitted from the output.
+const PYPI_URL: &str = "https://pypi.org/simple";
+
+/// The format of a lockfile to import.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LockfileFormat {
+    /// A `poetry.lock` file, as generated by Poetry.
+    Poetry,
+    /// A `Pipfile.lock` file, as generated by Pipenv.
+    Pipenv,
+}
+
+impl LockfileFormat {
+    /// Infer the format of a lockfile from its filename.
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "poetry.lock" => Some(Self::Poetry),
+            "Pipfile.lock" => Some(Self::Pipenv),
+            _ => None,
+        }
+    }
+}
+
+/// A pinned requirement read from a lockfile, to be written in `requirements.txt` format.
+#[derive(Debug)]
+struct PinnedRequirement {
+    /// The normalized name of the package, used for sorting.
+    name: PackageName,
+    /// The requirement itself, e.g., `flask==3.0.0` or `-e ./path/to/project`.
+    requirement: String,
+    /// The environment markers under which the requirement applies, if any.
+    markers: Option<MarkerTree>,
+    /// The hashes of the package's distributions, e.g., `sha256:...`.
+    hashes: Vec<String>,
+}
+
+/// The pinned requirements read from a lockfile, along with any additional package indexes.
+#[derive(Debug, Default)]
+struct ImportedLockfile {
+    requirements: Vec<PinnedRequirement>,
+    extra_index_urls: Vec<String>,
+}
+
+/// Convert a `poetry.lock` or `Pipfile.lock` into a `requirements.txt` file, preserving the pinned
+/// versions, hashes, and markers, without re-resolving.
+pub(crate) fn import_lockfile(
+    lockfile: &Path,
+    format: Option<LockfileFormat>,
+    dev: bool,
+    all_extras: bool,
+    output_file: Option<&Path>,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let Some(format) = format.or_else(|| LockfileFormat::from_path(lockfile)) else {
+        bail!(
+            "Unable to determine the format of `{}`; pass `--format poetry` or `--format pipenv`",
+            lockfile.normalized_display()
+        );
+    };
+
+    let contents = fs_err::read_to_string(lockfile)?;
+    let root = lockfile
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve: {}", root.normalized_display()))?;
+
+    let mut imported = match format {
+        LockfileFormat::Poetry => {
+            let lock = toml::from_str::<PoetryLock>(&contents)
+                .with_context(|| format!("Failed to parse: {}", lockfile.normalized_display()))?;
+            lock.into_requirements(&root, dev, all_extras)?
+        }
+        LockfileFormat::Pipenv => {
+            let lock = serde_json::from_str::<PipfileLock>(&contents)
+                .with_context(|| format!("Failed to parse: {}", lockfile.normalized_display()))?;
+            lock.into_requirements(&root, dev)?
+        }
+    };
+    imported.requirements.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.requirement.cmp(&b.requirement))
+    });
+
+    // Write the requirements to the output channel.
+    let mut writer: Box<dyn std::io::Write> = if let Some(output_file) = output_file {
+        Box::new(AutoStream::<std::fs::File>::auto(
+            fs_err::File::create(output_file)?.into(),
+        ))
+    } else {
+        Box::new(AutoStream::auto(stdout()))
+    };
+
+    writeln!(
+        writer,
+        "{}",
+        format!("# This file was autogenerated by uv v{VERSION} via the following command:")
+            .green()
+    )?;
+    writeln!(
+        writer,
+        "{}",
+        format!(
+            "#    uv {}",
+            env::args_os()
+                .skip(1)
+                .map(|arg| arg.normalized_display().to_string())
+                .join(" ")
+        )
+        .green()
+    )?;
+
+    if !imported.extra_index_urls.is_empty() {
+        for extra_index_url in &imported.extra_index_urls {
+            writeln!(writer, "--extra-index-url {extra_index_url}")?;
+        }
+        writeln!(writer)?;
+    }
+
+    for requirement in &imported.requirements {
+        write!(writer, "{}", requirement.requirement)?;
+        if let Some(markers) = &requirement.markers {
+            write!(writer, " ; {markers}")?;
+        }
+        for hash in &requirement.hashes {
+            writeln!(writer, " \\")?;
+            write!(writer, "    --hash={hash}")?;
+        }
+        writeln!(writer)?;
+    }
+
+    let s = if imported.requirements.len() == 1 {
+        ""
+    } else {
+        "s"
+    };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Imported {} in {}",
+            format!("{} package{s}", imported.requirements.len()).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Parse a marker expression, warning (and discarding it) if it isn't valid PEP 508.
+fn parse_markers(name: &PackageName, markers: &str) -> Option<MarkerTree> {
+    let markers = markers.trim();
+    if markers.is_empty() || markers == "*" {
+        return None;
+    }
+    match MarkerTree::from_str(markers) {
+        Ok(markers) => Some(markers),
+        Err(err) => {
+            warn_user!("Ignoring invalid markers for `{name}` ({markers}): {err}");
+            None
+        }
+    }
+}
+
+/// Return a `file://` URL for the given path, relative to the directory containing the lockfile.
+fn file_url(root: &Path, path: &str) -> Result<Url> {
+    let path = root.join(path);
+    Url::from_file_path(&path)
+        .map_err(|()| anyhow::anyhow!("Invalid path: {}", path.normalized_display()))
+}
+
+/// A `poetry.lock` file.
+///
+/// See: <https://python-poetry.org/docs/basic-usage/#committing-your-poetrylock-file-to-version-control>
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+    #[serde(default)]
+    metadata: PoetryMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PoetryMetadata {
+    /// The files for each package, as written by lockfile versions prior to 1.2.
+    #[serde(default)]
+    files: FxHashMap<PackageName, Vec<PoetryFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryPackage {
+    name: PackageName,
+    version: String,
+    /// Whether the package is only required by an extra.
+    #[serde(default)]
+    optional: bool,
+    /// The category of the package (`main` or `dev`), as written by lockfile versions prior to 2.0.
+    category: Option<String>,
+    /// The dependency groups that require the package, as written by Poetry 2.0 and later.
+    groups: Option<Vec<String>>,
+    /// The markers under which the package is required, either as a single expression or per
+    /// dependency group.
+    markers: Option<PoetryMarkers>,
+    #[serde(default)]
+    files: Vec<PoetryFile>,
+    source: Option<PoetrySource>,
+    /// Whether a directory dependency is installed in editable mode.
+    #[serde(default)]
+    develop: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PoetryMarkers {
+    Expression(String),
+    Groups(BTreeMap<String, String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryFile {
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetrySource {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    reference: Option<String>,
+    resolved_reference: Option<String>,
+    subdirectory: Option<String>,
+}
+
+impl PoetryLock {
+    fn into_requirements(
+        mut self,
+        root: &Path,
+        dev: bool,
+        all_extras: bool,
+    ) -> Result<ImportedLockfile> {
+        let mut imported = ImportedLockfile::default();
+        for package in self.package {
+            let is_main = match (&package.groups, &package.category) {
+                (Some(groups), _) => groups.iter().any(|group| group == "main"),
+                (None, Some(category)) => category == "main",
+                (None, None) => true,
+            };
+            if !is_main && !dev {
+                debug!("Skipping development dependency: {}", package.name);
+                continue;
+            }
+            if package.optional && !all_extras {
+                debug!("Skipping optional dependency: {}", package.name);
+                continue;
+            }
+
+            let markers = match &package.markers {
+                Some(PoetryMarkers::Expression(markers)) => parse_markers(&package.name, markers),
+                Some(PoetryMarkers::Groups(groups)) => {
+                    // Combine the markers for each included group; if any included group
+                    // requires the package unconditionally, so do we.
+                    let included = package
+                        .groups
+                        .iter()
+                        .flatten()
+                        .filter(|group| dev || *group == "main")
+                        .map(|group| groups.get(group))
+                        .collect::<Vec<_>>();
+                    if included.iter().any(Option::is_none) {
+                        None
+                    } else {
+                        let markers = included
+                            .into_iter()
+                            .flatten()
+                            .unique()
+                            .map(|markers| format!("({markers})"))
+                            .join(" or ");
+                        parse_markers(&package.name, &markers)
+                    }
+                }
+                None => None,
+            };
+
+            let files = if package.files.is_empty() {
+                self.metadata
+                    .files
+                    .remove(&package.name)
+                    .unwrap_or_default()
+            } else {
+                package.files
+            };
+
+            let (requirement, hashes) = match package.source {
+                None => (
+                    format!("{}=={}", package.name, package.version),
+                    files.into_iter().map(|file| file.hash).collect(),
+                ),
+                Some(source) => match source.kind.as_str() {
+                    "legacy" => {
+                        let url = source.url.trim_end_matches('/');
+                        if url != PYPI_URL
+                            && !imported.extra_index_urls.iter().any(|index| index == url)
+                        {
+                            imported.extra_index_urls.push(url.to_string());
+                        }
+                        (
+                            format!("{}=={}", package.name, package.version),
+                            files.into_iter().map(|file| file.hash).collect(),
+                        )
+                    }
+                    "git" => {
+                        let reference = source.resolved_reference.or(source.reference);
+                        let mut url = format!("git+{}", source.url);
+                        if let Some(reference) = reference {
+                            write!(url, "@{reference}")?;
+                        }
+                        if let Some(subdirectory) = source.subdirectory {
+                            write!(url, "#subdirectory={subdirectory}")?;
+                        }
+                        (format!("{} @ {url}", package.name), Vec::new())
+                    }
+                    "url" => {
+                        let mut url = source.url;
+                        if let Some(subdirectory) = source.subdirectory {
+                            write!(url, "#subdirectory={subdirectory}")?;
+                        }
+                        (
+                            format!("{} @ {url}", package.name),
+                            files.into_iter().map(|file| file.hash).collect(),
+                        )
+                    }
+                    "directory" if package.develop => {
+                        (format!("-e {}", file_url(root, &source.url)?), Vec::new())
+                    }
+                    "directory" | "file" => (
+                        format!("{} @ {}", package.name, file_url(root, &source.url)?),
+                        Vec::new(),
+                    ),
+                    kind => bail!("Unsupported source type for `{}`: {kind}", package.name),
+                },
+            };
+
+            imported.requirements.push(PinnedRequirement {
+                name: package.name,
+                requirement,
+                markers,
+                hashes,
+            });
+        }
+        Ok(imported)
+    }
+}
+
+/// A `Pipfile.lock` file.
+///
+/// See: <https://pipenv.pypa.io/en/latest/pipfile.html#pipfile-lock-security-features>
+#[derive(Debug, Deserialize)]
+struct PipfileLock {
+    #[serde(rename = "_meta", default)]
+    meta: PipfileMeta,
+    #[serde(default)]
+    default: BTreeMap<PackageName, PipfilePackage>,
+    #[serde(default)]
+    develop: BTreeMap<PackageName, PipfilePackage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PipfileMeta {
+    #[serde(default)]
+    sources: Vec<PipfileSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileSource {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfilePackage {
+    /// The pinned version, e.g., `==1.0.0`.
+    version: Option<String>,
+    #[serde(default)]
+    hashes: Vec<String>,
+    markers: Option<String>,
+    #[serde(default)]
+    extras: Vec<String>,
+    git: Option<String>,
+    #[serde(rename = "ref")]
+    reference: Option<String>,
+    subdirectory: Option<String>,
+    path: Option<String>,
+    file: Option<String>,
+    #[serde(default)]
+    editable: bool,
+}
+
+impl PipfileLock {
+    fn into_requirements(self, root: &Path, dev: bool) -> Result<ImportedLockfile> {
+        let mut imported = ImportedLockfile::default();
+        for source in self.meta.sources {
+            let url = source.url.trim_end_matches('/');
+            if url != PYPI_URL && !imported.extra_index_urls.iter().any(|index| index == url) {
+                imported.extra_index_urls.push(url.to_string());
+            }
+        }
+
+        // Packages in both sections are pinned to the same version, so prefer the default entry.
+        let mut packages = self.default;
+        if dev {
+            for (name, package) in self.develop {
+                packages.entry(name).or_insert(package);
+            }
+        }
+
+        for (name, package) in packages {
+            let extras = if package.extras.is_empty() {
+                String::new()
+            } else {
+                format!("[{}]", package.extras.join(","))
+            };
+
+            let (requirement, hashes) = if let Some(git) = package.git {
+                let mut url = if git.starts_with("git+") {
+                    git
+                } else {
+                    format!("git+{git}")
+                };
+                if let Some(reference) = package.reference {
+                    write!(url, "@{reference}")?;
+                }
+                if let Some(subdirectory) = package.subdirectory {
+                    write!(url, "#subdirectory={subdirectory}")?;
+                }
+                if package.editable {
+                    (format!("-e {url}#egg={name}"), Vec::new())
+                } else {
+                    (format!("{name}{extras} @ {url}"), Vec::new())
+                }
+            } else if let Some(path) = package.path {
+                if package.editable {
+                    (format!("-e {}", file_url(root, &path)?), Vec::new())
+                } else {
+                    (
+                        format!("{name}{extras} @ {}", file_url(root, &path)?),
+                        package.hashes,
+                    )
+                }
+            } else if let Some(file) = package.file {
+                (format!("{name}{extras} @ {file}"), package.hashes)
+            } else if let Some(version) = package.version {
+                (format!("{name}{extras}{version}"), package.hashes)
+            } else {
+                bail!("Missing version for `{name}`");
+            };
+
+            let markers = package
+                .markers
+                .as_deref()
+                .and_then(|markers| parse_markers(&name, markers));
+
+            imported.requirements.push(PinnedRequirement {
+                name,
+                requirement,
+                markers,
+                hashes,
+            });
+        }
+        Ok(imported)
+    }
+}
@@ -1,37 +1,202 @@
 use std::process::ExitCode;
 use std::time::Duration;
 
+pub(crate) use audit::audit;
+pub(crate) use build_manifest::write_build_manifest;
+pub(crate) use cache_archive::{cache_export, cache_import};
+pub(crate) use cache_dir::cache_dir;
+pub(crate) use cache_info::cache_info;
+pub(crate) use cache_prune::cache_prune;
+pub(crate) use cache_warm::cache_warm;
 pub(crate) use clean::clean;
+pub(crate) use debug_tags::debug_tags;
 use distribution_types::InstalledMetadata;
+pub(crate) use export_graph::GraphFormat;
 pub(crate) use freeze::freeze;
-pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, Upgrade};
+pub(crate) use generate_shell_completion::generate_shell_completion;
+pub(crate) use import_lockfile::{import_lockfile, LockfileFormat};
+pub(crate) use pip_compile::{
+    extra_name_with_clap_error, group_name_with_clap_error, pip_compile, Upgrade,
+};
+pub(crate) use pip_download::{pip_download, TargetPlatform};
 pub(crate) use pip_install::pip_install;
+pub(crate) use pip_list::pip_list;
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
+pub(crate) use publish::{publish, PublishCredentials};
+pub(crate) use python::{python_install, python_list};
+pub(crate) use report::OutputFormat;
+pub(crate) use run::run;
+pub(crate) use self_update::self_update;
+pub(crate) use serve_api::serve_api;
+pub(crate) use sync_all::sync_all;
+pub(crate) use tool_run::tool_run;
 pub(crate) use venv::venv;
+pub(crate) use why::why;
 
+mod audit;
+mod build_manifest;
+mod cache_archive;
+mod cache_dir;
+mod cache_info;
+mod cache_prune;
+mod cache_warm;
 mod clean;
+mod debug_tags;
+mod ephemeral;
+mod export_graph;
 mod freeze;
+mod generate_shell_completion;
+mod import_lockfile;
 mod pip_compile;
+mod pip_download;
 mod pip_install;
+mod pip_list;
 mod pip_sync;
 mod pip_uninstall;
+mod publish;
+mod python;
+mod report;
 mod reporters;
+mod run;
+mod self_update;
+mod serve_api;
+mod sync_all;
+mod tool_run;
 mod venv;
+mod why;
 
+/// The status with which a command exits.
+///
+/// Each class of failure maps to a distinct exit code, such that automation can branch on the
+/// failure without parsing stderr:
+///
+/// | Code | Status             |
+/// |------|--------------------|
+/// | 0    | [`Success`]        |
+/// | 1    | [`Failure`]        |
+/// | 2    | [`Error`]          |
+/// | 3    | [`NoSolution`]     |
+/// | 4    | [`BuildFailure`]   |
+/// | 5    | [`NetworkFailure`] |
+/// | 6    | [`Usage`]          |
+/// | 7    | [`External`]       |
+///
+/// Codes of `8` and above are reserved for the exit codes of external processes (see
+/// [`External`]).
+///
+/// [`Success`]: ExitStatus::Success
+/// [`Failure`]: ExitStatus::Failure
+/// [`Error`]: ExitStatus::Error
+/// [`NoSolution`]: ExitStatus::NoSolution
+/// [`BuildFailure`]: ExitStatus::BuildFailure
+/// [`NetworkFailure`]: ExitStatus::NetworkFailure
+/// [`Usage`]: ExitStatus::Usage
+/// [`External`]: ExitStatus::External
 #[derive(Copy, Clone)]
 pub(crate) enum ExitStatus {
     /// The command succeeded.
-    #[allow(unused)]
     Success,
 
     /// The command failed due to an error in the user input.
-    #[allow(unused)]
     Failure,
 
     /// The command failed with an unexpected error.
-    #[allow(unused)]
     Error,
+
+    /// The command failed because the requirements could not be resolved.
+    NoSolution,
+
+    /// The command failed because a source distribution could not be built.
+    BuildFailure,
+
+    /// The command failed because of a network error (or because the network was disabled).
+    NetworkFailure,
+
+    /// The command failed because the command-line arguments (or the requirements they refer to)
+    /// were invalid.
+    Usage,
+
+    /// The command failed because an external process failed.
+    ///
+    /// The exit code of the process is passed through if it's `1`, or between `8` and `255`;
+    /// a process terminated by a signal exits with `128` plus the signal number, as in a shell.
+    /// Any other failure (e.g., a code that coincides with one of uv's own codes, or a Windows
+    /// exit code that doesn't fit in a byte) exits with `7`, such that it's never mistaken for
+    /// a failure in uv itself.
+    External(Option<u8>),
+}
+
+impl ExitStatus {
+    /// Determine the [`ExitStatus`] for an error that was propagated out of a command, based on
+    /// the class of failure it was caused by.
+    pub(crate) fn from_error(err: &anyhow::Error) -> Self {
+        if err.chain().any(|cause| cause.is::<UsageError>()) {
+            return Self::Usage;
+        }
+
+        // A source tree that doesn't exist (e.g., an editable requirement at a missing path) is an
+        // error in the user input, even though it's only detected when building.
+        if err.chain().any(|cause| {
+            matches!(
+                cause.downcast_ref::<uv_build::Error>(),
+                Some(uv_build::Error::NotFound(_))
+            )
+        }) {
+            return Self::Usage;
+        }
+
+        for cause in err.chain() {
+            if let Some(err) = cause.downcast_ref::<uv_resolver::ResolveError>() {
+                match err {
+                    uv_resolver::ResolveError::NoSolution(err) => {
+                        return Self::from_no_solution(err);
+                    }
+                    uv_resolver::ResolveError::OmittedRequirements(_) => {
+                        return Self::NoSolution;
+                    }
+                    uv_resolver::ResolveError::Client(err) if err.kind().is_network_error() => {
+                        return Self::NetworkFailure;
+                    }
+                    _ => {}
+                }
+            } else if let Some(err) = cause.downcast_ref::<uv_distribution::Error>() {
+                match err {
+                    uv_distribution::Error::Build(..)
+                    | uv_distribution::Error::BuildEditable(..) => return Self::BuildFailure,
+                    uv_distribution::Error::Request(_) | uv_distribution::Error::Download(_) => {
+                        return Self::NetworkFailure;
+                    }
+                    uv_distribution::Error::Client(err) if err.kind().is_network_error() => {
+                        return Self::NetworkFailure;
+                    }
+                    _ => {}
+                }
+            } else if let Some(err) = cause.downcast_ref::<uv_client::Error>() {
+                if err.kind().is_network_error() {
+                    return Self::NetworkFailure;
+                }
+            } else if cause.is::<uv_build::Error>() {
+                return Self::BuildFailure;
+            } else if cause.is::<reqwest::Error>() {
+                return Self::NetworkFailure;
+            }
+        }
+        Self::Error
+    }
+
+    /// Determine the [`ExitStatus`] for a resolution that failed without a solution.
+    ///
+    /// If any of the packages involved were unavailable because the network was disabled, the
+    /// failure is reported as a network failure, consistent with commands that fail to fetch a
+    /// distribution with `--offline`.
+    pub(crate) fn from_no_solution(err: &uv_resolver::NoSolutionError) -> Self {
+        if err.is_offline() {
+            Self::NetworkFailure
+        } else {
+            Self::NoSolution
+        }
+    }
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -40,10 +205,42 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => ExitCode::from(0),
             ExitStatus::Failure => ExitCode::from(1),
             ExitStatus::Error => ExitCode::from(2),
+            ExitStatus::NoSolution => ExitCode::from(3),
+            ExitStatus::BuildFailure => ExitCode::from(4),
+            ExitStatus::NetworkFailure => ExitCode::from(5),
+            ExitStatus::Usage => ExitCode::from(6),
+            ExitStatus::External(code) => ExitCode::from(code.unwrap_or(7)),
         }
     }
 }
 
+impl From<std::process::ExitStatus> for ExitStatus {
+    /// Propagate the exit status of an external process.
+    fn from(status: std::process::ExitStatus) -> Self {
+        let code = match status.code() {
+            Some(0) => return ExitStatus::Success,
+            Some(code) => Some(code),
+            #[cfg(unix)]
+            None => {
+                use std::os::unix::process::ExitStatusExt;
+                status.signal().map(|signal| 128 + signal)
+            }
+            #[cfg(not(unix))]
+            None => None,
+        };
+        ExitStatus::External(
+            code.and_then(|code| u8::try_from(code).ok())
+                .filter(|code| *code == 1 || *code >= 8),
+        )
+    }
+}
+
+/// An error in the user input that's only detected while running a command (e.g., missing
+/// credentials), which exits with [`ExitStatus::Usage`].
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub(crate) struct UsageError(pub(crate) String);
+
 /// Format a duration as a human-readable string, Cargo-style.
 pub(super) fn elapsed(duration: Duration) -> String {
     let secs = duration.as_secs();
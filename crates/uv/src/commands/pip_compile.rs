@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fmt::Write;
 use std::io::stdout;
@@ -12,30 +13,38 @@ use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
 use tempfile::tempdir_in;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, LocalEditable};
-use pep508_rs::Requirement;
+use distribution_types::{FlatIndexLocation, IndexLocations, LocalEditable};
+use pep440_rs::{Operator, VersionSpecifiers};
+use pep508_rs::{Requirement, VersionOrUrl};
 use platform_host::Platform;
 use platform_tags::Tags;
+use pypi_types::HashAlgorithm;
 use requirements_txt::EditableRequirement;
-use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_cache::{Cache, CacheBucket, CacheEntry};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClientBuilder, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{Downloader, NoBinary};
 use uv_interpreter::{Interpreter, PythonVersion};
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName};
 use uv_resolver::{
-    DependencyMode, DisplayResolutionGraph, InMemoryIndex, Manifest, OptionsBuilder,
-    PreReleaseMode, ResolutionMode, Resolver,
+    requires_python_versions, Alias, DependencyMode, DisplayResolutionGraph, InMemoryIndex,
+    Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver,
 };
-use uv_traits::{InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
 use uv_warnings::warn_user;
 
+use crate::commands::export_graph::render_graph;
+use crate::commands::report::CompileReport;
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{elapsed, ExitStatus, GraphFormat, OutputFormat};
+use crate::policy::{report_violations, Policy};
 use crate::printer::Printer;
 use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 
@@ -47,23 +56,37 @@ pub(crate) async fn pip_compile(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    aliases: Vec<Alias>,
     extras: ExtrasSpecification<'_>,
+    groups: &[GroupName],
     output_file: Option<&Path>,
+    check: bool,
+    changes_file: Option<&Path>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    prefer_binary: bool,
     upgrade: Upgrade,
     generate_hashes: bool,
+    hash_algorithms: &[HashAlgorithm],
     include_annotations: bool,
     include_header: bool,
     include_index_url: bool,
     include_find_links: bool,
+    unsafe_packages: &[PackageName],
+    format: OutputFormat,
+    graph_format: Option<GraphFormat>,
     index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     no_build: &NoBuild,
+    policy: &Policy,
     python_version: Option<PythonVersion>,
+    requires_python: Option<VersionSpecifiers>,
     exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -80,6 +103,17 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // If the user requests dependency groups but does not provide a pyproject toml source
+    if !groups.is_empty()
+        && !requirements
+            .iter()
+            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+    {
+        return Err(anyhow!(
+            "Requesting dependency groups requires a pyproject.toml input file."
+        ));
+    }
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
@@ -91,13 +125,27 @@ pub(crate) async fn pip_compile(
         extra_index_urls,
         no_index,
         find_links,
+        no_binary: _no_binary,
+        only_binary: requirements_only_binary,
         extras: used_extras,
-    } = RequirementsSpecification::from_sources(requirements, constraints, overrides, &extras)?;
+        groups: used_groups,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &extras,
+        groups,
+    )?;
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
+    // Incorporate any `--only-binary` options from the provided sources.
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(requirements_only_binary, false));
+
     // Check that all provided extras are used
     if let ExtrasSpecification::Some(extras) = extras {
         let mut unused_extras = extras
@@ -115,16 +163,37 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    let preferences: Vec<Requirement> = output_file
-        // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
-        .filter(|_| !upgrade.is_all())
+    // Check that all provided dependency groups are used
+    let mut unused_groups = groups
+        .iter()
+        .filter(|group| !used_groups.contains(group))
+        .collect::<Vec<_>>();
+    if !unused_groups.is_empty() {
+        unused_groups.sort_unstable();
+        unused_groups.dedup();
+        let s = if unused_groups.len() == 1 { "" } else { "s" };
+        return Err(anyhow!(
+            "Requested dependency group{s} not found: {}",
+            unused_groups.iter().join(", ")
+        ));
+    }
+
+    // Upgrades are explicitly requests for the latest versions, so never reuse a cached resolution.
+    let is_upgrade = !matches!(upgrade, Upgrade::None);
+
+    // Read the existing lockfile, if any. Its pins are used both to seed the resolution, such that
+    // unchanged inputs lead to as few version changes as possible, and to report the changes.
+    let previous: Option<Vec<Requirement>> = output_file
         .filter(|output_file| output_file.exists())
         .map(Path::to_path_buf)
         .map(RequirementsSource::from_path)
         .as_ref()
-        .map(|source| RequirementsSpecification::from_source(source, &extras))
+        .map(|source| RequirementsSpecification::from_source(source, &extras, &[]))
         .transpose()?
-        .map(|spec| spec.requirements)
+        .map(|spec| spec.requirements);
+
+    let preferences: Vec<Requirement> = previous
+        .clone()
         .map(|requirements| match upgrade {
             // Respect all pinned versions from the existing lockfile.
             Upgrade::None => requirements,
@@ -138,6 +207,18 @@ pub(crate) async fn pip_compile(
         })
         .unwrap_or_default();
 
+    // When resolving for a `requires-python` range, target the lowest version in the range.
+    let python_version = if let Some(requires_python) = requires_python.as_ref() {
+        let Some(lowest) = requires_python_versions(requires_python).into_iter().next() else {
+            return Err(anyhow!(
+                "The requires-python range `{requires_python}` does not include any supported Python version"
+            ));
+        };
+        Some(PythonVersion::from_str(&lowest.to_string()).map_err(|err| anyhow!(err))?)
+    } else {
+        python_version
+    };
+
     // Find an interpreter to use for building distributions
     let platform = Platform::current()?;
     let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
@@ -194,10 +275,93 @@ pub(crate) async fn pip_compile(
         |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
     );
 
+    // Reuse the cached resolution, if all of its inputs are unchanged. Resolutions are only cached
+    // with an `--exclude-newer` date in the past, since otherwise, new uploads to the index could
+    // change the result. Similarly, resolutions that include editables, direct URLs, or remote
+    // `--find-links` entries aren't cached, since their contents can change without any of the
+    // inputs changing.
+    let resolution_entry = if is_upgrade
+        || !exclude_newer.is_some_and(|exclude_newer| exclude_newer <= Utc::now())
+        || !editables.is_empty()
+        || requirements
+            .iter()
+            .chain(&constraints)
+            .chain(&overrides)
+            .any(|requirement| matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))))
+    {
+        None
+    } else {
+        find_links_fingerprint(&index_locations).map(|find_links| {
+            let to_strings = |requirements: &[Requirement]| {
+                requirements
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            };
+            let inputs = (
+                VERSION,
+                to_strings(&requirements),
+                to_strings(&constraints),
+                to_strings(&overrides),
+                to_strings(&preferences),
+                &index_locations,
+                find_links,
+                serde_json::to_string(&markers).unwrap_or_default(),
+                interpreter.sys_executable(),
+            );
+            let options = (
+                resolution_mode,
+                prerelease_mode,
+                dependency_mode,
+                prefer_binary,
+                &aliases,
+                requires_python.as_ref().map(ToString::to_string),
+                setup_py,
+                no_build,
+                exclude_newer.map(|exclude_newer| exclude_newer.to_rfc3339()),
+                policy,
+            );
+            let output = (
+                generate_hashes,
+                hash_algorithms
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                include_annotations,
+                include_index_url,
+                include_find_links,
+                unsafe_packages,
+                format,
+                graph_format,
+            );
+            cache.entry(
+                CacheBucket::Resolutions,
+                "",
+                format!("{}.json", cache_key::digest(&(inputs, options, output))),
+            )
+        })
+    };
+    if let Some(entry) = resolution_entry.as_ref() {
+        if cache.freshness(entry, None)?.is_fresh() {
+            if let Some(cached) = CachedResolution::read(entry) {
+                debug!(
+                    "Using cached resolution from: {}",
+                    entry.read_path().normalized_display()
+                );
+                cached.write_summary(start, printer)?;
+                report_changes(previous.as_deref(), &cached.pins, changes_file, printer)?;
+                let output = with_header(&cached.output, include_header, format, graph_format)?;
+                return write_output(&output, output_file, check, printer);
+            }
+        }
+    }
+
     // Instantiate a client.
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts)
         .build();
 
     // Resolve the flat indexes from `--find-links`.
@@ -214,6 +378,7 @@ pub(crate) async fn pip_compile(
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
+        .prefer_binary(prefer_binary)
         .exclude_newer(exclude_newer)
         .build();
 
@@ -230,7 +395,8 @@ pub(crate) async fn pip_compile(
         no_build,
         &NoBinary::None,
     )
-    .with_options(options);
+    .with_options(options)
+    .with_concurrency(concurrency);
 
     // Build the editables and add their requirements
     let editable_metadata = if editables.is_empty() {
@@ -284,7 +450,8 @@ pub(crate) async fn pip_compile(
         preferences,
         project,
         editable_metadata,
-    );
+    )
+    .with_aliases(aliases);
 
     // Resolve the dependencies.
     let resolver = Resolver::new(
@@ -299,81 +466,155 @@ pub(crate) async fn pip_compile(
         &build_dispatch,
     )
     .with_reporter(ResolverReporter::from(printer));
+    let resolver = if let Some(requires_python) = requires_python {
+        resolver.with_requires_python(requires_python)
+    } else {
+        resolver
+    };
 
     let resolution = match resolver.resolve().await {
         Err(uv_resolver::ResolveError::NoSolution(err)) => {
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::from_no_solution(&err));
         }
         result => result,
     }?;
 
-    let s = if resolution.len() == 1 { "" } else { "s" };
+    let mut cached = CachedResolution {
+        packages: resolution.len(),
+        diagnostics: resolution
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect(),
+        pins: Pins::from_resolution(&resolution),
+        output: String::new(),
+    };
+    cached.write_summary(start, printer)?;
+
+    // Enforce the package policy. Resolutions that violate the policy are never cached, so cached
+    // resolutions (which are keyed by the policy) needn't be re-evaluated.
+    let violations = policy.evaluate(&resolution);
+    if !violations.is_empty() {
+        report_violations(&violations, printer)?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    report_changes(previous.as_deref(), &cached.pins, changes_file, printer)?;
+
+    cached.output = render_output(
+        &resolution,
+        &index_locations,
+        start,
+        generate_hashes,
+        hash_algorithms,
+        include_annotations,
+        include_index_url,
+        include_find_links,
+        unsafe_packages,
+        format,
+        graph_format,
+    )?;
+
+    // Cache the resolution, such that it can be reused if the inputs are unchanged.
+    if let Some(entry) = resolution_entry.as_ref() {
+        if let Err(err) = cached.write(entry) {
+            debug!(
+                "Failed to cache resolution at {}: {err}",
+                entry.path().normalized_display()
+            );
+        }
+    }
+
+    let output = with_header(&cached.output, include_header, format, graph_format)?;
+    write_output(&output, output_file, check, printer)
+}
+
+/// Prepend the header to the rendered requirements, if requested.
+///
+/// The header is rendered separately from the rest of the output, since it includes the
+/// command-line arguments, some of which (like `--check`) have no effect on the resolution.
+fn with_header(
+    output: &str,
+    include_header: bool,
+    format: OutputFormat,
+    graph_format: Option<GraphFormat>,
+) -> Result<String> {
+    if !include_header || format == OutputFormat::Json || graph_format.is_some() {
+        return Ok(output.to_string());
+    }
+
+    let mut header = String::new();
+    writeln!(
+        header,
+        "{}",
+        format!("# This file was autogenerated by uv v{VERSION} via the following command:")
+            .green()
+    )?;
     writeln!(
-        printer,
+        header,
         "{}",
         format!(
-            "Resolved {} in {}",
-            format!("{} package{}", resolution.len(), s).bold(),
-            elapsed(start.elapsed())
+            "#    uv {}",
+            env::args_os()
+                .skip(1)
+                .filter(|arg| arg != "--check")
+                .map(|arg| arg.normalized_display().to_string())
+                .join(" ")
         )
-        .dimmed()
+        .green()
     )?;
+    Ok(header + output)
+}
 
-    // Notify the user of any diagnostics.
-    for diagnostic in resolution.diagnostics() {
-        writeln!(
-            printer,
-            "{}{} {}",
-            "warning".yellow().bold(),
-            ":".bold(),
-            diagnostic.message().bold()
-        )?;
+/// Render the resolution in the requested output format, excluding the header.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+fn render_output(
+    resolution: &ResolutionGraph,
+    index_locations: &IndexLocations,
+    start: std::time::Instant,
+    generate_hashes: bool,
+    hash_algorithms: &[HashAlgorithm],
+    include_annotations: bool,
+    include_index_url: bool,
+    include_find_links: bool,
+    unsafe_packages: &[PackageName],
+    format: OutputFormat,
+    graph_format: Option<GraphFormat>,
+) -> Result<String> {
+    // Render the resolution graph, if requested.
+    if let Some(graph_format) = graph_format {
+        return Ok(render_graph(resolution, graph_format));
     }
 
-    // Write the resolved dependencies to the output channel.
-    let mut writer: Box<dyn std::io::Write> = if let Some(output_file) = output_file {
-        Box::new(AutoStream::<std::fs::File>::auto(
-            fs_err::File::create(output_file)?.into(),
-        ))
-    } else {
-        Box::new(AutoStream::auto(stdout()))
-    };
-
-    if include_header {
-        writeln!(
-            writer,
-            "{}",
-            format!("# This file was autogenerated by uv v{VERSION} via the following command:")
-                .green()
-        )?;
-        writeln!(
-            writer,
-            "{}",
-            format!(
-                "#    uv {}",
-                env::args_os()
-                    .skip(1)
-                    .map(|arg| arg.normalized_display().to_string())
-                    .join(" ")
-            )
-            .green()
-        )?;
+    // Render the resolved dependencies.
+    if format == OutputFormat::Json {
+        let report = CompileReport::new(resolution, hash_algorithms, start.elapsed());
+        let mut output = serde_json::to_string_pretty(&report)?;
+        output.push('\n');
+        return Ok(output);
     }
 
-    // Write the index locations to the output channel.
+    let mut output = String::new();
+
+    // Write the index locations to the output.
     let mut wrote_index = false;
 
-    // If necessary, include the `--index-url` and `--extra-index-url` locations.
+    // If necessary, include the `--index-url` and `--extra-index-url` locations. If the index is
+    // disabled, include `--no-index`, such that installers don't fall back to PyPI.
     if include_index_url {
+        if index_locations.no_index() {
+            writeln!(output, "--no-index")?;
+            wrote_index = true;
+        }
         if let Some(index) = index_locations.index() {
-            writeln!(writer, "--index-url {index}")?;
+            writeln!(output, "--index-url {index}")?;
             wrote_index = true;
         }
         for extra_index in index_locations.extra_index() {
-            writeln!(writer, "--extra-index-url {extra_index}")?;
+            writeln!(output, "--extra-index-url {extra_index}")?;
             wrote_index = true;
         }
     }
@@ -381,25 +622,328 @@ pub(crate) async fn pip_compile(
     // If necessary, include the `--find-links` locations.
     if include_find_links {
         for flat_index in index_locations.flat_index() {
-            writeln!(writer, "--find-links {flat_index}")?;
+            writeln!(output, "--find-links {flat_index}")?;
             wrote_index = true;
         }
     }
 
     // If we wrote an index, add a newline to separate it from the requirements
     if wrote_index {
-        writeln!(writer)?;
+        writeln!(output)?;
     }
 
     write!(
-        writer,
+        output,
         "{}",
-        DisplayResolutionGraph::new(&resolution, generate_hashes, include_annotations)
+        DisplayResolutionGraph::new(resolution, generate_hashes, include_annotations)
+            .with_hash_algorithms(hash_algorithms)
+            .with_unsafe_packages(unsafe_packages)
     )?;
 
+    Ok(output)
+}
+
+/// A resolution, as rendered by `pip compile`, that's cached for reuse when all of the inputs to
+/// the resolution are unchanged.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResolution {
+    /// The number of resolved packages.
+    packages: usize,
+    /// The diagnostics emitted during resolution.
+    diagnostics: Vec<String>,
+    /// The pinned version (or URL) of each resolved package.
+    pins: Pins,
+    /// The rendered output, excluding the header.
+    output: String,
+}
+
+impl CachedResolution {
+    /// Read a cached resolution from the given cache entry, if it exists and is valid.
+    fn read(entry: &CacheEntry) -> Option<Self> {
+        let data = fs_err::read(entry.read_path()).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Write the resolution to the given cache entry.
+    fn write(&self, entry: &CacheEntry) -> Result<()> {
+        fs_err::create_dir_all(entry.dir())?;
+        uv_fs::write_atomic_sync(entry.path(), serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Write the resolution summary and any diagnostics to the printer.
+    fn write_summary(&self, start: std::time::Instant, mut printer: Printer) -> Result<()> {
+        let s = if self.packages == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Resolved {} in {}",
+                format!("{} package{}", self.packages, s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+
+        // Notify the user of any diagnostics.
+        for diagnostic in &self.diagnostics {
+            writeln!(
+                printer,
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                diagnostic.bold()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Return a fingerprint of the local `--find-links` directories, such that a cached resolution is
+/// invalidated when distributions are added, removed, or modified.
+///
+/// Returns `None` if any `--find-links` entry can't be fingerprinted, e.g., because it's a URL.
+fn find_links_fingerprint(index_locations: &IndexLocations) -> Option<String> {
+    let mut fingerprint = Vec::new();
+    for location in index_locations.flat_index() {
+        let FlatIndexLocation::Path(path) = location else {
+            return None;
+        };
+        let mut entries = fs_err::read_dir(path)
+            .ok()?
+            .map(|entry| {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                Ok(format!(
+                    "{} {} {:?}",
+                    entry.path().normalized_display(),
+                    metadata.len(),
+                    metadata.modified()?
+                ))
+            })
+            .collect::<std::io::Result<Vec<_>>>()
+            .ok()?;
+        entries.sort_unstable();
+        fingerprint.extend(entries);
+    }
+    Some(fingerprint.join("\n"))
+}
+
+/// Write the rendered output to the output file (or stdout).
+///
+/// In `--check` mode, compare the output to the existing output file instead, failing if it's
+/// missing or out-of-date.
+fn write_output(
+    output: &str,
+    output_file: Option<&Path>,
+    check: bool,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    if check {
+        let output_file = output_file.context("`--check` requires an `--output-file`")?;
+        let expected = anstream::adapter::strip_str(output).to_string();
+        let existing = match fs_err::read_to_string(output_file) {
+            Ok(existing) => Some(existing),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        return if existing.as_deref() == Some(expected.as_str()) {
+            writeln!(
+                printer,
+                "{} is up-to-date",
+                output_file.normalized_display().cyan()
+            )?;
+            Ok(ExitStatus::Success)
+        } else if existing.is_some_and(|existing| is_reordering(&existing, &expected)) {
+            writeln!(
+                printer,
+                "{} is not in canonical order; re-run without `--check` to update it",
+                output_file.normalized_display().cyan()
+            )?;
+            Ok(ExitStatus::Failure)
+        } else {
+            writeln!(
+                printer,
+                "{} is out-of-date; re-run without `--check` to update it",
+                output_file.normalized_display().cyan()
+            )?;
+            Ok(ExitStatus::Failure)
+        };
+    }
+
+    if let Some(output_file) = output_file {
+        let mut stream =
+            AutoStream::<std::fs::File>::auto(fs_err::File::create(output_file)?.into());
+        std::io::Write::write_all(&mut stream, output.as_bytes())?;
+    } else {
+        let mut stream = AutoStream::auto(stdout());
+        std::io::Write::write_all(&mut stream, output.as_bytes())?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Returns `true` if the existing output contains the same lines as the expected output, but in a
+/// different order or with different trailing whitespace (e.g., if it was edited by hand, or
+/// generated by a version of uv whose output was ordered differently).
+fn is_reordering(existing: &str, expected: &str) -> bool {
+    let normalize = |output: &str| {
+        output
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .sorted_unstable()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    normalize(existing) == normalize(expected)
+}
+
+/// The pinned version (e.g., `==1.0.0`) or URL (e.g., ` @ https://...`) of each package in a
+/// resolution, as rendered in a requirements file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Pins(BTreeMap<PackageName, String>);
+
+impl Pins {
+    /// Collect the pins from a resolution.
+    fn from_resolution(resolution: &ResolutionGraph) -> Self {
+        Self(
+            resolution
+                .pins()
+                .map(|(name, version_or_url)| (name.clone(), version_or_url.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Collect the pins from the requirements in an existing lockfile, ignoring any that aren't
+    /// pinned to an exact version or URL.
+    fn from_requirements(requirements: &[Requirement]) -> Self {
+        Self(
+            requirements
+                .iter()
+                .filter_map(|requirement| {
+                    let pin = match requirement.version_or_url.as_ref()? {
+                        VersionOrUrl::VersionSpecifier(specifiers) => {
+                            let [specifier] = specifiers.as_ref() else {
+                                return None;
+                            };
+                            if *specifier.operator() != Operator::Equal {
+                                return None;
+                            }
+                            format!("=={}", specifier.version())
+                        }
+                        VersionOrUrl::Url(url) => format!(" @ {url}"),
+                    };
+                    Some((requirement.name.clone(), pin))
+                })
+                .collect(),
+        )
+    }
+
+    /// Return the changes from the `previous` pins to these pins, sorted by package name.
+    fn changes<'a>(&'a self, previous: &'a Pins) -> Vec<(&'a PackageName, PinChange<'a>)> {
+        previous
+            .0
+            .keys()
+            .chain(self.0.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|name| {
+                let change = match (previous.0.get(name), self.0.get(name)) {
+                    (Some(previous), Some(current)) if previous == current => return None,
+                    (Some(previous), Some(current)) => PinChange::Changed(previous, current),
+                    (Some(previous), None) => PinChange::Removed(previous),
+                    (None, Some(current)) => PinChange::Added(current),
+                    (None, None) => return None,
+                };
+                Some((name, change))
+            })
+            .collect()
+    }
+}
+
+/// A change to a pinned package, relative to the existing lockfile.
+#[derive(Debug)]
+enum PinChange<'a> {
+    Added(&'a str),
+    Removed(&'a str),
+    Changed(&'a str, &'a str),
+}
+
+/// Strip the `==` or ` @ ` prefix from a pin, leaving the bare version or URL.
+fn bare(pin: &str) -> &str {
+    pin.strip_prefix("==")
+        .or_else(|| pin.strip_prefix(" @ "))
+        .unwrap_or(pin)
+}
+
+/// Report the packages that were added, removed, or changed relative to the existing lockfile,
+/// and write a summary of the changes to the `changes_file`, if requested.
+///
+/// If there's no existing lockfile, the changes are only written to the `changes_file`, in which
+/// case every package is reported as added.
+fn report_changes(
+    previous: Option<&[Requirement]>,
+    current: &Pins,
+    changes_file: Option<&Path>,
+    mut printer: Printer,
+) -> Result<()> {
+    let previous_pins = previous.map(Pins::from_requirements).unwrap_or_default();
+    let changes = current.changes(&previous_pins);
+
+    if previous.is_some() {
+        for (name, change) in &changes {
+            match change {
+                PinChange::Added(current) => writeln!(
+                    printer,
+                    " {} {}{}",
+                    "+".green(),
+                    name.as_ref().white().bold(),
+                    current.dimmed()
+                )?,
+                PinChange::Removed(previous) => writeln!(
+                    printer,
+                    " {} {}{}",
+                    "-".red(),
+                    name.as_ref().white().bold(),
+                    previous.dimmed()
+                )?,
+                PinChange::Changed(previous, current) => writeln!(
+                    printer,
+                    " {} {} {} {} {}",
+                    "~".yellow(),
+                    name.as_ref().white().bold(),
+                    bare(previous).dimmed(),
+                    "→".dimmed(),
+                    bare(current).dimmed()
+                )?,
+            }
+        }
+    }
+
+    if let Some(changes_file) = changes_file {
+        let mut summary = String::new();
+        if changes.is_empty() {
+            writeln!(summary, "No changes to the pinned packages.")?;
+        } else {
+            writeln!(summary, "| Package | Old | New |")?;
+            writeln!(summary, "| --- | --- | --- |")?;
+            for (name, change) in &changes {
+                let (previous, current) = match change {
+                    PinChange::Added(current) => ("", bare(current)),
+                    PinChange::Removed(previous) => (bare(previous), ""),
+                    PinChange::Changed(previous, current) => (bare(previous), bare(current)),
+                };
+                writeln!(summary, "| {name} | {previous} | {current} |")?;
+            }
+        }
+        fs_err::write(changes_file, summary)?;
+    }
+
+    Ok(())
+}
+
 /// Whether to allow package upgrades.
 #[derive(Debug)]
 pub(crate) enum Upgrade {
@@ -445,6 +989,15 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
     })
 }
 
+pub(crate) fn group_name_with_clap_error(arg: &str) -> Result<GroupName> {
+    GroupName::from_str(arg).map_err(|_err| {
+        anyhow!(
+            "Group names must start and end with a letter or digit and may only \
+            contain -, _, ., and alphanumeric characters"
+        )
+    })
+}
+
 /// An owned or unowned [`InMemoryIndex`].
 enum InMemoryIndexRef<'a> {
     Owned(InMemoryIndex),
@@ -0,0 +1,635 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use anstream::eprint;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::{AsyncReadExt, StreamExt, TryStreamExt};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{
+    BuiltDist, Dist, FileLocation, IndexLocations, Name, Resolution, SourceDist,
+};
+use pep508_rs::MarkerEnvironment;
+use platform_host::{Arch, Os, Platform};
+use platform_tags::Tags;
+use pypi_types::Hashes;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClient, RegistryClientBuilder,
+    TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_distribution::HashValidator;
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::{Interpreter, PythonVersion};
+use uv_resolver::{
+    DependencyMode, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode,
+    Resolver,
+};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::warn_user;
+
+use crate::commands::reporters::ResolverReporter;
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+
+/// Resolve a set of requirements, and download the resulting distributions into a directory.
+///
+/// If multiple Python versions or platforms are provided, the requirements are resolved for every
+/// combination thereof, and the union of the resulting distributions is downloaded.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_download(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
+    dest: &Path,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    dependency_mode: DependencyMode,
+    index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    no_build: &NoBuild,
+    python_versions: Vec<PythonVersion>,
+    target_platforms: Vec<TargetPlatform>,
+    manifest_path: Option<&Path>,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Read all requirements from the provided sources.
+    let RequirementsSpecification {
+        project,
+        requirements,
+        constraints,
+        overrides,
+        editables,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        no_binary: _,
+        only_binary: requirements_only_binary,
+        extras: _,
+        groups: _,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &ExtrasSpecification::None,
+        &[],
+    )?;
+
+    if !editables.is_empty() {
+        bail!("Editable requirements can't be downloaded");
+    }
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Incorporate any `--only-binary` options from the provided sources.
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(requirements_only_binary, false));
+
+    // Find an interpreter to use for building distributions.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_versions.first(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().normalized_display().cyan()
+    );
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts)
+        .build();
+
+    // Fetch the flat indexes from `--find-links`, which are filtered separately for the builds
+    // and for each target.
+    let flat_index_entries = {
+        let client = FlatIndexClient::new(&client, &cache);
+        client.fetch(index_locations.flat_index()).await?
+    };
+    let build_flat_index = FlatIndex::from_entries(flat_index_entries.clone(), interpreter.tags()?);
+
+    // Create an in-memory index for builds, which is shared across targets.
+    let source_index = InMemoryIndex::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .build();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &build_flat_index,
+        &source_index,
+        &in_flight,
+        interpreter.sys_executable().to_path_buf(),
+        setup_py,
+        no_build,
+        &NoBinary::None,
+    )
+    .with_options(options)
+    .with_concurrency(concurrency);
+
+    // Resolve each target in turn, collecting the union of the archives to download.
+    let targets = Target::product(&python_versions, &target_platforms);
+    let mut archives = BTreeMap::new();
+    let mut download_manifest = DownloadManifest::default();
+    for target in &targets {
+        let start = std::time::Instant::now();
+
+        // Determine the tags and markers to use for resolution. If the user requested a different
+        // Python version or platform, resolve against that target instead of the interpreter.
+        let tags = if target.python_version.is_some() || target.platform.is_some() {
+            let python_tuple = target.python_version.as_ref().map_or(
+                (interpreter.python_major(), interpreter.python_minor()),
+                |python_version| (python_version.major(), python_version.minor()),
+            );
+            let platform = target.platform.map_or_else(
+                || interpreter.platform().clone(),
+                |target_platform| target_platform.platform(),
+            );
+            Cow::Owned(Tags::from_env(
+                &platform,
+                python_tuple,
+                interpreter.implementation_name(),
+                interpreter.implementation_tuple(),
+            )?)
+        } else {
+            Cow::Borrowed(interpreter.tags()?)
+        };
+        let markers = target.python_version.as_ref().map_or_else(
+            || interpreter.markers().clone(),
+            |python_version| python_version.markers(interpreter.markers()),
+        );
+        let markers = match target.platform {
+            Some(target_platform) => target_platform.markers(&markers),
+            None => markers,
+        };
+
+        // The available distributions depend on the tags, so each target needs its own index.
+        let flat_index = FlatIndex::from_entries(flat_index_entries.clone(), &tags);
+        let top_level_index = InMemoryIndex::default();
+
+        // Resolve the dependencies.
+        let manifest = Manifest::new(
+            requirements.clone(),
+            constraints.clone(),
+            overrides.clone(),
+            vec![],
+            project.clone(),
+            vec![],
+        );
+        let resolver = Resolver::new(
+            manifest,
+            options,
+            &markers,
+            &interpreter,
+            &tags,
+            &client,
+            &flat_index,
+            &top_level_index,
+            &build_dispatch,
+        )
+        .with_reporter(ResolverReporter::from(printer));
+
+        let resolution = match resolver.resolve().await {
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                let report = miette::Report::msg(format!("{err}")).context(if targets.len() == 1 {
+                    "No solution found when resolving dependencies:".to_string()
+                } else {
+                    format!("No solution found when resolving dependencies for {target}:")
+                });
+                eprint!("{report:?}");
+                return Ok(ExitStatus::from_no_solution(&err));
+            }
+            result => result,
+        }?;
+        let resolution = Resolution::from(resolution);
+
+        let s = if resolution.len() == 1 { "" } else { "s" };
+        let suffix = if targets.len() == 1 {
+            String::new()
+        } else {
+            format!(" for {target}")
+        };
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Resolved {}{suffix} in {}",
+                format!("{} package{}", resolution.len(), s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+
+        // Locate the archive for each distribution, deduplicating across targets.
+        let mut files = Vec::new();
+        for dist in resolution.into_distributions() {
+            let Some(archive) = Archive::from_dist(dist)? else {
+                continue;
+            };
+            files.push(archive.filename.clone());
+            archives.entry(archive.filename.clone()).or_insert(archive);
+        }
+        files.sort_unstable();
+        download_manifest.targets.push(ManifestTarget {
+            python_version: target.python_version.as_ref().map_or_else(
+                || interpreter.python_version().to_string(),
+                |python_version| python_version.string.clone(),
+            ),
+            platform: target.platform.map(TargetPlatform::name),
+            files,
+        });
+    }
+
+    // Download each distribution into the destination directory.
+    let start = std::time::Instant::now();
+    fs_err::create_dir_all(dest)?;
+
+    let mut downloads = futures::stream::iter(archives.into_values())
+        .map(|archive| archive.download(dest, &client))
+        .buffer_unordered(concurrency.downloads)
+        .try_collect::<Vec<_>>()
+        .await?;
+    downloads.sort_unstable();
+
+    let s = if downloads.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Downloaded {} to {} in {}",
+            format!("{} file{}", downloads.len(), s).bold(),
+            dest.normalized_display(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    for filename in downloads {
+        writeln!(printer, " {} {}", "+".green(), filename.bold())?;
+    }
+
+    // Record the files required by each target, if requested.
+    if let Some(manifest_path) = manifest_path {
+        let mut contents = serde_json::to_string_pretty(&download_manifest)?;
+        contents.push('\n');
+        fs_err::write(manifest_path, contents)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// A combination of Python version and platform for which distributions should be downloaded.
+///
+/// If either is omitted, that of the current interpreter is used.
+#[derive(Debug, Clone)]
+struct Target {
+    python_version: Option<PythonVersion>,
+    platform: Option<TargetPlatform>,
+}
+
+impl Target {
+    /// Return every combination of the given Python versions and platforms.
+    fn product(python_versions: &[PythonVersion], platforms: &[TargetPlatform]) -> Vec<Self> {
+        let python_versions = if python_versions.is_empty() {
+            vec![None]
+        } else {
+            python_versions.iter().cloned().map(Some).collect()
+        };
+        let platforms = if platforms.is_empty() {
+            vec![None]
+        } else {
+            platforms.iter().copied().map(Some).collect()
+        };
+        python_versions
+            .iter()
+            .cartesian_product(&platforms)
+            .map(|(python_version, platform)| Self {
+                python_version: python_version.clone(),
+                platform: *platform,
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.python_version, self.platform) {
+            (Some(python_version), Some(platform)) => {
+                write!(f, "Python {} on {}", python_version.string, platform.name())
+            }
+            (Some(python_version), None) => write!(f, "Python {}", python_version.string),
+            (None, Some(platform)) => write!(f, "{}", platform.name()),
+            (None, None) => write!(f, "the current interpreter"),
+        }
+    }
+}
+
+/// A record of the files downloaded for each target, written by `--manifest`.
+#[derive(Debug, Default, Serialize)]
+struct DownloadManifest {
+    targets: Vec<ManifestTarget>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestTarget {
+    /// The Python version of the target (e.g., `3.12`).
+    python_version: String,
+    /// The platform of the target, or `None` for the current platform.
+    platform: Option<&'static str>,
+    /// The filenames of the distributions required by the target, sorted.
+    files: Vec<String>,
+}
+
+/// A distribution archive to download.
+struct Archive {
+    dist: Dist,
+    location: Location,
+    filename: String,
+    /// The hashes advertised by the index, or pinned in the URL (e.g., `#sha256=...`).
+    hashes: Hashes,
+}
+
+impl Archive {
+    /// Locate the archive for a distribution.
+    ///
+    /// Distributions that aren't backed by an archive (e.g., Git repositories and local
+    /// directories) are skipped with a warning.
+    fn from_dist(dist: Dist) -> Result<Option<Self>> {
+        let location = match &dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => Location::from_file(&wheel.file.url)?,
+            Dist::Source(SourceDist::Registry(sdist)) => Location::from_file(&sdist.file.url)?,
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => Location::Url(wheel.url.to_url()),
+            Dist::Source(SourceDist::DirectUrl(sdist)) => Location::Url(sdist.url.to_url()),
+            Dist::Built(BuiltDist::Path(wheel)) => Location::Path(wheel.path.clone()),
+            Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => {
+                Location::Path(sdist.path.clone())
+            }
+            Dist::Source(SourceDist::Path(_) | SourceDist::Git(_)) => {
+                warn_user!("Skipping {dist}, which isn't backed by a distribution archive");
+                return Ok(None);
+            }
+        };
+
+        let filename = match &dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => wheel.file.filename.clone(),
+            Dist::Source(SourceDist::Registry(sdist)) => sdist.file.filename.clone(),
+            _ => location
+                .filename()
+                .ok_or_else(|| anyhow!("Unable to determine the filename for {dist}"))?,
+        };
+
+        let hashes = match &dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => wheel.file.hashes.clone(),
+            Dist::Source(SourceDist::Registry(sdist)) => sdist.file.hashes.clone(),
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => wheel
+                .url
+                .raw()
+                .fragment()
+                .map(Hashes::from_fragment)
+                .unwrap_or_default(),
+            Dist::Source(SourceDist::DirectUrl(sdist)) => sdist
+                .url
+                .raw()
+                .fragment()
+                .map(Hashes::from_fragment)
+                .unwrap_or_default(),
+            _ => Hashes::default(),
+        };
+
+        Ok(Some(Self {
+            dist,
+            location,
+            filename,
+            hashes,
+        }))
+    }
+
+    /// Download the archive into the destination directory, returning its filename.
+    ///
+    /// The archive is validated against the hash advertised by the index (or pinned in the URL),
+    /// if any, before it's moved into the destination.
+    async fn download(self, dest: &Path, client: &RegistryClient) -> Result<String> {
+        let Self {
+            dist,
+            location,
+            filename,
+            hashes,
+        } = self;
+        let validator = HashValidator::new(&hashes);
+
+        // Avoid re-downloading any files that are already present in the destination, unless
+        // they don't match the expected hash.
+        let target = dest.join(&filename);
+        if target.is_file() {
+            let matches = match HashValidator::new(&hashes) {
+                Some(existing) => {
+                    existing.update(&fs_err::tokio::read(&target).await?);
+                    existing.validate(&dist).is_ok()
+                }
+                None => true,
+            };
+            if matches {
+                debug!("Already downloaded: {}", target.normalized_display());
+                return Ok(filename);
+            }
+            debug!(
+                "Re-downloading {}, which doesn't match the expected hash",
+                target.normalized_display()
+            );
+        }
+
+        match location {
+            Location::Url(url) => {
+                debug!("Downloading: {url}");
+                let mut reader = client
+                    .stream_external(&url)
+                    .await
+                    .with_context(|| format!("Failed to download {dist}"))?;
+                let mut temp_file = NamedTempFile::new_in(dest)?;
+                let mut buffer = vec![0; 64 * 1024];
+                loop {
+                    let n = reader
+                        .read(&mut buffer)
+                        .await
+                        .with_context(|| format!("Failed to download {dist}"))?;
+                    if n == 0 {
+                        break;
+                    }
+                    if let Some(validator) = &validator {
+                        validator.update(&buffer[..n]);
+                    }
+                    temp_file.write_all(&buffer[..n])?;
+                }
+                if let Some(validator) = &validator {
+                    validator.validate(&dist)?;
+                }
+                temp_file.persist(&target)?;
+            }
+            Location::Path(path) => {
+                debug!("Copying: {}", path.normalized_display());
+                if let Some(validator) = &validator {
+                    validator.update(&fs_err::tokio::read(&path).await?);
+                    validator.validate(&dist)?;
+                }
+                fs_err::tokio::copy(&path, &target).await?;
+            }
+        }
+
+        Ok(filename)
+    }
+}
+
+/// The location of a distribution archive.
+enum Location {
+    Url(Url),
+    Path(PathBuf),
+}
+
+impl Location {
+    fn from_file(location: &FileLocation) -> Result<Self> {
+        Ok(match location {
+            FileLocation::RelativeUrl(base, url) => {
+                Self::Url(pypi_types::base_url_join_relative(base, url)?)
+            }
+            FileLocation::AbsoluteUrl(url) => Self::Url(Url::parse(url)?),
+            FileLocation::Path(path) => Self::Path(path.clone()),
+        })
+    }
+
+    /// Return the filename of the archive, i.e., the last segment of its path.
+    fn filename(&self) -> Option<String> {
+        match self {
+            Self::Url(url) => url
+                .path_segments()
+                .and_then(Iterator::last)
+                .filter(|segment| !segment.is_empty())
+                .map(ToString::to_string),
+            Self::Path(path) => path
+                .file_name()
+                .map(|filename| filename.to_string_lossy().to_string()),
+        }
+    }
+}
+
+/// A platform for which distributions can be downloaded, other than the current platform.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum TargetPlatform {
+    /// An x86-64 Linux target, with glibc 2.17 or later (i.e., `manylinux2014`).
+    #[value(name = "x86_64-unknown-linux-gnu")]
+    LinuxX86_64,
+    /// An ARM64 Linux target, with glibc 2.17 or later (i.e., `manylinux2014`).
+    #[value(name = "aarch64-unknown-linux-gnu")]
+    LinuxAarch64,
+    /// An x86-64 macOS target, running macOS 10.12 or later.
+    #[value(name = "x86_64-apple-darwin")]
+    MacosX86_64,
+    /// An ARM64 macOS target, running macOS 11.0 or later.
+    #[value(name = "aarch64-apple-darwin")]
+    MacosAarch64,
+    /// An x86-64 Windows target.
+    #[value(name = "x86_64-pc-windows-msvc")]
+    WindowsX86_64,
+}
+
+impl TargetPlatform {
+    /// Return the name of the target, as provided on the command line.
+    fn name(self) -> &'static str {
+        match self {
+            Self::LinuxX86_64 => "x86_64-unknown-linux-gnu",
+            Self::LinuxAarch64 => "aarch64-unknown-linux-gnu",
+            Self::MacosX86_64 => "x86_64-apple-darwin",
+            Self::MacosAarch64 => "aarch64-apple-darwin",
+            Self::WindowsX86_64 => "x86_64-pc-windows-msvc",
+        }
+    }
+
+    /// Return the [`Platform`] used to determine compatible wheel tags for the target.
+    fn platform(self) -> Platform {
+        match self {
+            Self::LinuxX86_64 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            Self::LinuxAarch64 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::Aarch64,
+            ),
+            Self::MacosX86_64 => Platform::new(
+                Os::Macos {
+                    major: 10,
+                    minor: 12,
+                },
+                Arch::X86_64,
+            ),
+            Self::MacosAarch64 => Platform::new(
+                Os::Macos {
+                    major: 11,
+                    minor: 0,
+                },
+                Arch::Aarch64,
+            ),
+            Self::WindowsX86_64 => Platform::new(Os::Windows, Arch::X86_64),
+        }
+    }
+
+    /// Apply the platform-specific markers for the target to the given [`MarkerEnvironment`].
+    fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let mut markers = base.clone();
+        let (os_name, sys_platform, platform_system, platform_machine) = match self {
+            Self::LinuxX86_64 => ("posix", "linux", "Linux", "x86_64"),
+            Self::LinuxAarch64 => ("posix", "linux", "Linux", "aarch64"),
+            Self::MacosX86_64 => ("posix", "darwin", "Darwin", "x86_64"),
+            Self::MacosAarch64 => ("posix", "darwin", "Darwin", "arm64"),
+            Self::WindowsX86_64 => ("nt", "win32", "Windows", "AMD64"),
+        };
+        markers.os_name = os_name.to_string();
+        markers.sys_platform = sys_platform.to_string();
+        markers.platform_system = platform_system.to_string();
+        markers.platform_machine = platform_machine.to_string();
+        // The release and version of the target can't be known in advance.
+        markers.platform_release = String::new();
+        markers.platform_version = String::new();
+        markers
+    }
+}
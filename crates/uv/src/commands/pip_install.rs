@@ -2,6 +2,7 @@ use std::collections::HashSet;
 use std::fmt::Write;
 
 use std::path::Path;
+use std::str::FromStr;
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
@@ -11,32 +12,39 @@ use owo_colors::OwoColorize;
 use tempfile::tempdir_in;
 use tracing::debug;
 
+use distribution_filename::WheelFilename;
 use distribution_types::{
     IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name, Resolution,
 };
 use install_wheel_rs::linker::LinkMode;
-use pep508_rs::{MarkerEnvironment, Requirement};
+use install_wheel_rs::Shebang;
+use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
 use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClient, RegistryClientBuilder,
+    TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{
     BuiltEditable, Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable, SitePackages,
 };
 use uv_interpreter::{Interpreter, Virtualenv};
-use uv_normalize::PackageName;
+use uv_normalize::{GroupName, PackageName};
 use uv_resolver::{
-    DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
+    Alias, DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
     ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
 
+use crate::commands::pip_sync::check_script_conflicts;
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{elapsed, write_build_manifest, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::policy::{report_violations, Policy};
 use crate::printer::Printer;
 use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 
@@ -48,20 +56,31 @@ pub(crate) async fn pip_install(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    aliases: Vec<Alias>,
     extras: &ExtrasSpecification<'_>,
+    groups: &[GroupName],
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    prefer_binary: bool,
     upgrade: Upgrade,
     index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    shebang: &Shebang,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     no_build: &NoBuild,
     no_binary: &NoBinary,
+    policy: &Policy,
     strict: bool,
+    verify_hashes: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    build_manifest: Option<&Path>,
+    python: Option<&str>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -78,13 +97,24 @@ pub(crate) async fn pip_install(
         extra_index_urls,
         no_index,
         find_links,
+        no_binary: requirements_no_binary,
+        only_binary: requirements_only_binary,
         extras: used_extras,
-    } = specification(requirements, constraints, overrides, extras)?;
+        groups: used_groups,
+    } = specification(requirements, constraints, overrides, extras, groups)?;
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
+    // Incorporate any `--no-binary` and `--only-binary` options from the provided sources.
+    let no_binary = &no_binary
+        .clone()
+        .combine(NoBinary::from_args(requirements_no_binary));
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(requirements_only_binary, false));
+
     // Check that all provided extras are used
     if let ExtrasSpecification::Some(extras) = extras {
         let mut unused_extras = extras
@@ -102,9 +132,28 @@ pub(crate) async fn pip_install(
         }
     }
 
+    // Check that all provided dependency groups are used
+    let mut unused_groups = groups
+        .iter()
+        .filter(|group| !used_groups.contains(group))
+        .collect::<Vec<_>>();
+    if !unused_groups.is_empty() {
+        unused_groups.sort_unstable();
+        unused_groups.dedup();
+        let s = if unused_groups.len() == 1 { "" } else { "s" };
+        return Err(anyhow!(
+            "Requested dependency group{s} not found: {}",
+            unused_groups.iter().join(", ")
+        ));
+    }
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, &cache)?;
+    let venv = if let Some(python) = python {
+        Virtualenv::from_python(python, platform, &cache)?
+    } else {
+        Virtualenv::from_env(platform, &cache)?
+    };
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
@@ -144,10 +193,15 @@ pub(crate) async fn pip_install(
     let tags = venv.interpreter().tags()?;
     let markers = venv.interpreter().markers();
 
+    // Validate that any local wheels are compatible with the environment, prior to resolution.
+    validate_wheels(&requirements, tags, markers)?;
+
     // Instantiate a client.
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts)
         .build();
 
     // Resolve the flat indexes from `--find-links`.
@@ -167,6 +221,7 @@ pub(crate) async fn pip_install(
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
+        .prefer_binary(prefer_binary)
         .exclude_newer(exclude_newer)
         .build();
 
@@ -183,7 +238,8 @@ pub(crate) async fn pip_install(
         no_build,
         no_binary,
     )
-    .with_options(options);
+    .with_options(options)
+    .with_concurrency(concurrency);
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command. If an editable is already
@@ -210,6 +266,7 @@ pub(crate) async fn pip_install(
         requirements,
         constraints,
         overrides,
+        aliases,
         project,
         &editables,
         &site_packages,
@@ -227,14 +284,22 @@ pub(crate) async fn pip_install(
     )
     .await
     {
-        Ok(resolution) => Resolution::from(resolution),
+        Ok(resolution) => {
+            // Enforce the package policy before installing anything.
+            let violations = policy.evaluate(&resolution);
+            if !violations.is_empty() {
+                report_violations(&violations, printer)?;
+                return Ok(ExitStatus::Failure);
+            }
+            Resolution::from(resolution)
+        }
         Err(Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::from_no_solution(&err));
         }
-        Err(err) => return Err(err.into()),
+        Err(err) => return Err(err.into_anyhow()),
     };
 
     // Re-initialize the in-flight map.
@@ -258,6 +323,7 @@ pub(crate) async fn pip_install(
             no_build,
             no_binary,
         )
+        .with_concurrency(concurrency)
     };
 
     // Sync the environment.
@@ -267,17 +333,22 @@ pub(crate) async fn pip_install(
         site_packages,
         reinstall,
         no_binary,
+        strict,
+        verify_hashes,
         link_mode,
+        shebang,
         &index_locations,
         tags,
         &client,
         &in_flight,
         &install_dispatch,
+        build_manifest,
         &cache,
         &venv,
         printer,
     )
-    .await?;
+    .await
+    .map_err(Error::into_anyhow)?;
 
     // Validate the environment.
     if strict {
@@ -293,6 +364,7 @@ fn specification(
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
+    groups: &[GroupName],
 ) -> Result<RequirementsSpecification, Error> {
     // If the user requests `extras` but does not provide a pyproject toml source
     if !matches!(extras, ExtrasSpecification::None)
@@ -303,9 +375,25 @@ fn specification(
         return Err(anyhow!("Requesting extras requires a pyproject.toml input file.").into());
     }
 
+    // If the user requests dependency groups but does not provide a pyproject toml source
+    if !groups.is_empty()
+        && !requirements
+            .iter()
+            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+    {
+        return Err(
+            anyhow!("Requesting dependency groups requires a pyproject.toml input file.").into(),
+        );
+    }
+
     // Read all requirements from the provided sources.
-    let spec =
-        RequirementsSpecification::from_sources(requirements, constraints, overrides, extras)?;
+    let spec = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        extras,
+        groups,
+    )?;
 
     // Check that all provided extras are used
     if let ExtrasSpecification::Some(extras) = extras {
@@ -383,6 +471,7 @@ async fn resolve(
     requirements: Vec<Requirement>,
     constraints: Vec<Requirement>,
     overrides: Vec<Requirement>,
+    aliases: Vec<Alias>,
     project: Option<PackageName>,
     editables: &[BuiltEditable],
     site_packages: &SitePackages<'_>,
@@ -440,7 +529,8 @@ async fn resolve(
         preferences,
         project,
         editables,
-    );
+    )
+    .with_aliases(aliases);
 
     // Resolve the dependencies.
     let resolver = Resolver::new(
@@ -480,12 +570,16 @@ async fn install(
     site_packages: SitePackages<'_>,
     reinstall: &Reinstall,
     no_binary: &NoBinary,
+    strict: bool,
+    verify_hashes: bool,
     link_mode: LinkMode,
+    shebang: &Shebang,
     index_urls: &IndexLocations,
     tags: &Tags,
     client: &RegistryClient,
     in_flight: &InFlight,
     build_dispatch: &BuildDispatch<'_>,
+    build_manifest: Option<&Path>,
     cache: &Cache,
     venv: &Virtualenv,
     mut printer: Printer,
@@ -507,6 +601,7 @@ async fn install(
         extraneous: _,
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(editables)
+        .with_verify_hashes(verify_hashes)
         .build(
             site_packages,
             reinstall,
@@ -535,6 +630,10 @@ async fn install(
         return Ok(());
     }
 
+    // Now that wheels are about to be unzipped and installed, size the install pool to match the
+    // cache directory's disk.
+    crate::concurrency::size_install_pool(cache.root());
+
     // Map any registry-based requirements back to those returned by the resolver.
     let remote = remote
         .iter()
@@ -574,8 +673,8 @@ async fn install(
     }
 
     // Download, build, and unzip any missing distributions.
-    let wheels = if remote.is_empty() {
-        vec![]
+    let (wheels, built_wheels) = if remote.is_empty() {
+        (vec![], vec![])
     } else {
         let start = std::time::Instant::now();
 
@@ -599,9 +698,18 @@ async fn install(
             .dimmed()
         )?;
 
-        wheels
+        (wheels, downloader.built_wheels())
     };
 
+    // Record the wheels that were built from source distributions, if requested.
+    if let Some(build_manifest) = build_manifest {
+        write_build_manifest(build_manifest, &built_wheels)?;
+    }
+
+    // Check for any scripts that would be overwritten by the installation.
+    let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
+    check_script_conflicts(venv, &wheels, &reinstalls, strict, printer)?;
+
     // Remove any existing installations.
     if !reinstalls.is_empty() {
         for dist_info in &reinstalls {
@@ -618,11 +726,11 @@ async fn install(
     }
 
     // Install the resolved distributions.
-    let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
         uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang.clone())
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -681,6 +789,45 @@ async fn install(
     Ok(())
 }
 
+/// Validate that the local wheels in the requirements (e.g., `./foo-1.0-py3-none-any.whl`) are
+/// compatible with the given tags.
+///
+/// Wheels whose markers don't apply to the environment are skipped, as they won't be installed.
+fn validate_wheels(
+    requirements: &[Requirement],
+    tags: &Tags,
+    markers: &MarkerEnvironment,
+) -> Result<()> {
+    for requirement in requirements {
+        if !requirement.evaluate_markers(markers, &[]) {
+            continue;
+        }
+        let Some(VersionOrUrl::Url(url)) = &requirement.version_or_url else {
+            continue;
+        };
+        if url.scheme() != "file" {
+            continue;
+        }
+        let Ok(path) = url.to_file_path() else {
+            continue;
+        };
+        let Some(filename) = path
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .and_then(|filename| WheelFilename::from_str(filename).ok())
+        else {
+            continue;
+        };
+        if !filename.is_compatible(tags) {
+            return Err(anyhow!(
+                "A path dependency is incompatible with the current platform: {}",
+                path.normalized_display()
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Validate the installed packages in the virtual environment.
 fn validate(resolution: &Resolution, venv: &Virtualenv, mut printer: Printer) -> Result<(), Error> {
     let site_packages = SitePackages::from_executable(venv)?;
@@ -723,3 +870,16 @@ enum Error {
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
+
+impl Error {
+    /// Convert the error into an [`anyhow::Error`], unwrapping the transparent variants such that
+    /// the underlying error remains available to [`ExitStatus::from_error`].
+    fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            Self::Resolve(err) => err.into(),
+            Self::Client(err) => err.into(),
+            Self::Anyhow(err) => err,
+            err => err.into(),
+        }
+    }
+}
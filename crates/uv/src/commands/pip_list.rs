@@ -0,0 +1,202 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{InstalledDist, Name};
+use platform_host::Platform;
+use pypi_types::DirectUrl;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_installer::SitePackages;
+use uv_interpreter::Virtualenv;
+
+use crate::commands::{ExitStatus, OutputFormat};
+use crate::printer::Printer;
+
+/// Enumerate the installed packages in the current environment, along with their provenance.
+pub(crate) fn pip_list(
+    format: OutputFormat,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = Virtualenv::from_env(platform, cache)?;
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    let rows = site_packages
+        .iter()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+        .map(|dist| {
+            let source = match Source::from_dist(dist) {
+                Ok(source) => source.to_string(),
+                Err(err) => {
+                    debug!("Failed to read provenance for {}: {err}", dist.name());
+                    "unknown".to_string()
+                }
+            };
+            (dist.name().to_string(), dist.version().to_string(), source)
+        })
+        .collect::<Vec<_>>();
+
+    if format == OutputFormat::Json {
+        let rows = rows
+            .into_iter()
+            .map(|(name, version, source)| ListEntry {
+                name,
+                version,
+                source,
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(ExitStatus::Success);
+    }
+
+    if rows.is_empty() {
+        writeln!(printer, "No packages installed")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Align the columns.
+    let name_width = rows
+        .iter()
+        .map(|(name, ..)| name.len())
+        .chain(std::iter::once("Package".len()))
+        .max()
+        .unwrap_or_default();
+    let version_width = rows
+        .iter()
+        .map(|(_, version, _)| version.len())
+        .chain(std::iter::once("Version".len()))
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:name_width$} {:version_width$} Source",
+        "Package", "Version"
+    );
+    println!(
+        "{} {} {}",
+        "-".repeat(name_width),
+        "-".repeat(version_width),
+        "-".repeat("Source".len())
+    );
+    for (name, version, source) in rows {
+        println!("{name:name_width$} {version:version_width$} {source}");
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// An installed distribution, as listed with `--format json`.
+#[derive(Debug, Serialize)]
+struct ListEntry {
+    name: String,
+    version: String,
+    source: String,
+}
+
+/// The best-effort provenance of an installed distribution.
+#[derive(Debug)]
+enum Source {
+    /// A pre-built wheel, downloaded from the given host.
+    Wheel(Option<String>),
+    /// A wheel that was built locally from a source distribution downloaded from the given host.
+    Sdist(Option<String>),
+    /// A wheel that was built locally from a local directory.
+    Directory,
+    /// A wheel that was built locally from a VCS repository hosted at the given host.
+    Vcs(pypi_types::VcsKind, Option<String>),
+    /// An editable installation of a local directory.
+    Editable(Url),
+    /// The provenance is unknown, e.g., because the distribution was installed by another tool.
+    Unknown(Option<String>),
+}
+
+impl Source {
+    /// Determine the [`Source`] of an installed distribution, based on its `direct_url.json` and
+    /// the provenance recorded at install time.
+    fn from_dist(dist: &InstalledDist) -> Result<Self> {
+        // Legacy editables (`setup.py develop`) don't have a `direct_url.json`.
+        if let InstalledDist::LegacyEditable(dist) = dist {
+            return Ok(Self::Editable(dist.target_url.clone()));
+        }
+
+        if let Some(direct_url) = dist.read_direct_url()? {
+            return Ok(match direct_url {
+                DirectUrl::LocalDirectory { url, dir_info } => {
+                    if dir_info.editable == Some(true) {
+                        Self::Editable(url)
+                    } else {
+                        Self::Directory
+                    }
+                }
+                DirectUrl::ArchiveUrl { url, .. } => {
+                    if url.path().ends_with(".whl") {
+                        Self::Wheel(host(&url))
+                    } else {
+                        Self::Sdist(host(&url))
+                    }
+                }
+                DirectUrl::VcsUrl { url, vcs_info, .. } => Self::Vcs(vcs_info.vcs, host(&url)),
+            });
+        }
+
+        if let Some(provenance) = dist.provenance()? {
+            let host = provenance
+                .index
+                .as_deref()
+                .and_then(|index| Url::parse(index).ok())
+                .and_then(|url| host(&url));
+            return Ok(if provenance.built {
+                Self::Sdist(host)
+            } else {
+                Self::Wheel(host)
+            });
+        }
+
+        Ok(Self::Unknown(dist.installer()?.filter(|installer| {
+            !installer.is_empty() && installer != "uv"
+        })))
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wheel(Some(host)) => write!(f, "wheel ({host})"),
+            Self::Wheel(None) => write!(f, "wheel (local)"),
+            Self::Sdist(Some(host)) => write!(f, "built from sdist ({host})"),
+            Self::Sdist(None) => write!(f, "built from sdist (local)"),
+            Self::Directory => write!(f, "built from directory"),
+            Self::Vcs(vcs, Some(host)) => write!(f, "built from {vcs} ({host})"),
+            Self::Vcs(vcs, None) => write!(f, "built from {vcs}"),
+            Self::Editable(url) => write!(f, "editable ({url})"),
+            Self::Unknown(Some(installer)) => write!(f, "unknown (installed by {installer})"),
+            Self::Unknown(None) => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Return the host of a URL, if it isn't a local file.
+fn host(url: &Url) -> Option<String> {
+    if url.scheme() == "file" {
+        None
+    } else {
+        url.host_str().map(ToString::to_string)
+    }
+}
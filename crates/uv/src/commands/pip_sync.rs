@@ -1,18 +1,28 @@
 use std::fmt::Write;
+use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name};
+use distribution_types::{
+    CachedDist, Dist, IndexLocations, InstalledDist, InstalledMetadata, LocalDist, LocalEditable,
+    Name, SourceDist,
+};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::Shebang;
+use pep440_rs::Version;
+use pep508_rs::{MarkerEnvironment, Requirement};
 use platform_host::Platform;
-use platform_tags::Tags;
+use platform_tags::{IncompatibleTag, TagCompatibility, Tags};
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, OwnedArchive, RegistryClient,
+    RegistryClientBuilder, TrustedHost, VersionWheel,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{
@@ -20,10 +30,14 @@ use uv_installer::{
 };
 use uv_interpreter::Virtualenv;
 use uv_resolver::InMemoryIndex;
-use uv_traits::{InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
 
+use crate::commands::report::{PlanReport, SyncReport};
 use crate::commands::reporters::{DownloadReporter, FinderReporter, InstallReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    elapsed, write_build_manifest, ChangeEvent, ChangeEventKind, ExitStatus, OutputFormat,
+    UsageError,
+};
 use crate::printer::Printer;
 use crate::requirements::{RequirementsSource, RequirementsSpecification};
 
@@ -32,13 +46,25 @@ use crate::requirements::{RequirementsSource, RequirementsSpecification};
 pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
     reinstall: &Reinstall,
+    exact: bool,
     link_mode: LinkMode,
+    shebang: &Shebang,
     index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
+    keep_going: bool,
+    verify_hashes: bool,
+    format: OutputFormat,
+    build_manifest: Option<&Path>,
+    report: bool,
+    report_json: Option<&Path>,
+    python: Option<&str>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -55,7 +81,10 @@ pub(crate) async fn pip_sync(
         extra_index_urls,
         no_index,
         find_links,
+        no_binary: requirements_no_binary,
+        only_binary: requirements_only_binary,
         extras: _extras,
+        groups: _groups,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
     let num_requirements = requirements.len() + editables.len();
@@ -68,17 +97,205 @@ pub(crate) async fn pip_sync(
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
+    // Incorporate any `--no-binary` and `--only-binary` options from the provided sources.
+    let no_binary = &no_binary
+        .clone()
+        .combine(NoBinary::from_args(requirements_no_binary));
+    let no_build = &no_build
+        .clone()
+        .combine(NoBuild::from_args(requirements_only_binary, false));
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, &cache)?;
+    let venv = if let Some(python) = python {
+        Virtualenv::from_python(python, platform, &cache)?
+    } else {
+        Virtualenv::from_env(platform, &cache)?
+    };
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().normalized_display().cyan()
     );
 
+    // Merge the requirements across all sources, rejecting any conflicts.
+    let requirements = merge_requirements(requirements, venv.interpreter().markers())?;
+
     let _lock = venv.lock()?;
 
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let SyncOutcome { changes, failures } = sync_environment(
+        &venv,
+        &requirements,
+        editables,
+        reinstall,
+        exact,
+        strict,
+        link_mode,
+        shebang,
+        &index_locations,
+        &trusted_hosts,
+        setup_py,
+        connectivity,
+        no_build,
+        no_binary,
+        keep_going,
+        verify_hashes,
+        &in_flight,
+        build_manifest,
+        report,
+        report_json,
+        timeouts,
+        concurrency,
+        &cache,
+        printer,
+    )
+    .await?;
+
+    // If requested, write a machine-readable report of the changes to stdout.
+    if format == OutputFormat::Json {
+        let report = SyncReport::new(&changes, &failures, start.elapsed());
+        let mut stdout = std::io::stdout().lock();
+        serde_json::to_writer_pretty(&mut stdout, &report)?;
+        std::io::Write::write_all(&mut stdout, b"\n")?;
+    }
+
+    // Report on any changes in the environment.
+    report_changes(changes, printer)?;
+
+    // Report on any packages that failed to download or build.
+    if !failures.is_empty() {
+        report_failures(&failures, printer)?;
+    }
+
+    // Validate that the environment is consistent.
+    if strict {
+        let site_packages = SitePackages::from_executable(&venv)?;
+        for diagnostic in site_packages.diagnostics()? {
+            writeln!(
+                printer,
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                diagnostic.message().bold()
+            )?;
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Merge the requirements read from multiple sources (e.g., a `requirements.txt` and a
+/// `dev-requirements.txt`), dropping any exact duplicates.
+///
+/// Returns an error if two requirements that apply to the current environment refer to the same
+/// package, but differ in their version specifiers, URLs, or extras.
+fn merge_requirements(
+    requirements: Vec<Requirement>,
+    markers: &MarkerEnvironment,
+) -> Result<Vec<Requirement>> {
+    let mut merged: Vec<Requirement> = Vec::with_capacity(requirements.len());
+    for requirement in requirements {
+        if merged.contains(&requirement) {
+            debug!("Ignoring duplicate requirement: {requirement}");
+            continue;
+        }
+        if requirement.evaluate_markers(markers, &[]) {
+            if let Some(existing) = merged.iter().find(|existing| {
+                existing.name == requirement.name && existing.evaluate_markers(markers, &[])
+            }) {
+                bail!(
+                    "Conflicting requirements for `{}`: `{existing}` and `{requirement}`",
+                    requirement.name
+                );
+            }
+        }
+        merged.push(requirement);
+    }
+    Ok(merged)
+}
+
+/// The outcome of syncing an environment.
+#[derive(Debug)]
+pub(super) struct SyncOutcome {
+    /// The changes to the environment, sorted by package name.
+    pub(super) changes: Vec<ChangeEvent<LocalDist>>,
+    /// The distributions that failed to download or build, if the sync was allowed to continue
+    /// past them.
+    pub(super) failures: Vec<uv_installer::Error>,
+}
+
+/// Install a set of locked requirements into the given virtual environment, returning the set of
+/// changes, sorted by package name.
+///
+/// If `exact` is set, any installed packages that aren't part of the requirements are uninstalled;
+/// otherwise, they're left in place.
+///
+/// If `keep_going` is set, distributions that fail to download or build are skipped (leaving any
+/// existing installation in place) and returned as failures, rather than aborting the sync.
+///
+/// If `verify_hashes` is set, cached distributions are verified against their `RECORD` before
+/// they're installed, and downloaded (or built) again if they've been modified.
+///
+/// The caller is responsible for locking the environment. Downloads and builds are deduplicated
+/// through the given [`InFlight`], which may be shared across environments with the same
+/// interpreter.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn sync_environment(
+    venv: &Virtualenv,
+    requirements: &[Requirement],
+    editables: Vec<EditableRequirement>,
+    reinstall: &Reinstall,
+    exact: bool,
+    strict: bool,
+    link_mode: LinkMode,
+    shebang: &Shebang,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    keep_going: bool,
+    verify_hashes: bool,
+    in_flight: &InFlight,
+    build_manifest: Option<&Path>,
+    report: bool,
+    report_json: Option<&Path>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<SyncOutcome> {
+    let start = std::time::Instant::now();
+
+    let num_requirements = requirements.len() + editables.len();
+
+    // Determine the set of installed packages.
+    let site_packages =
+        SitePackages::from_executable(venv).context("Failed to list installed packages")?;
+
+    // If the environment already matches the requirements, skip the installation plan (and any
+    // network access) entirely.
+    if reinstall.is_none()
+        && editables.is_empty()
+        && site_packages.satisfies_exactly(requirements, exact)
+    {
+        debug!("Environment already satisfies all requirements");
+        report_plan(&PlanReport::default(), report, report_json, printer)?;
+        audited(num_requirements, start, printer)?;
+        return Ok(SyncOutcome {
+            changes: Vec::new(),
+            failures: Vec::new(),
+        });
+    }
+
     // Determine the current environment markers.
     let tags = venv.interpreter().tags()?;
 
@@ -86,11 +303,13 @@ pub(crate) async fn pip_sync(
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts.to_vec())
         .build();
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
+        let client = FlatIndexClient::new(&client, cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
         FlatIndex::from_entries(entries, tags)
     };
@@ -98,36 +317,30 @@ pub(crate) async fn pip_sync(
     // Create a shared in-memory index.
     let index = InMemoryIndex::default();
 
-    // Track in-flight downloads, builds, etc., across resolutions.
-    let in_flight = InFlight::default();
-
     // Prep the build context.
     let build_dispatch = BuildDispatch::new(
         &client,
-        &cache,
+        cache,
         venv.interpreter(),
-        &index_locations,
+        index_locations,
         &flat_index,
         &index,
-        &in_flight,
+        in_flight,
         venv.python_executable(),
         setup_py,
         no_build,
         no_binary,
-    );
-
-    // Determine the set of installed packages.
-    let site_packages =
-        SitePackages::from_executable(&venv).context("Failed to list installed packages")?;
+    )
+    .with_concurrency(concurrency);
 
     // Resolve any editables.
     let resolved_editables = resolve_editables(
         editables,
         &site_packages,
         reinstall,
-        &venv,
+        venv,
         tags,
-        &cache,
+        cache,
         &client,
         &build_dispatch,
         printer,
@@ -141,36 +354,44 @@ pub(crate) async fn pip_sync(
         remote,
         reinstalls,
         extraneous,
-    } = Planner::with_requirements(&requirements)
+    } = Planner::with_requirements(requirements)
         .with_editable_requirements(resolved_editables.editables)
+        .with_verify_hashes(verify_hashes)
         .build(
             site_packages,
             reinstall,
             no_binary,
-            &index_locations,
-            &cache,
-            &venv,
+            index_locations,
+            cache,
+            venv,
             tags,
         )
         .context("Failed to determine installation plan")?;
 
+    // Unless the sync is exact, leave any unnecessary packages in place.
+    let extraneous = if exact {
+        extraneous
+    } else {
+        for dist_info in &extraneous {
+            debug!("Preserving unnecessary package: {dist_info}");
+        }
+        Vec::new()
+    };
+
     // Nothing to do.
     if remote.is_empty() && local.is_empty() && reinstalls.is_empty() && extraneous.is_empty() {
-        let s = if num_requirements == 1 { "" } else { "s" };
-        writeln!(
-            printer,
-            "{}",
-            format!(
-                "Audited {} in {}",
-                format!("{num_requirements} package{s}").bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
-        )?;
-
-        return Ok(ExitStatus::Success);
+        report_plan(&PlanReport::default(), report, report_json, printer)?;
+        audited(num_requirements, start, printer)?;
+        return Ok(SyncOutcome {
+            changes: Vec::new(),
+            failures: Vec::new(),
+        });
     }
 
+    // Now that wheels are about to be unzipped and installed, size the install pool to match the
+    // cache directory's disk.
+    crate::concurrency::size_install_pool(cache.root());
+
     // Resolve any registry-based requirements.
     let remote = if remote.is_empty() {
         Vec::new()
@@ -224,19 +445,48 @@ pub(crate) async fn pip_sync(
         }
     }
 
+    // If builds are disabled, report every distribution that would need to be built up-front,
+    // rather than failing on the first.
+    if !keep_going {
+        check_no_build(
+            &remote,
+            no_build,
+            tags,
+            venv.interpreter().python_version(),
+            &client,
+        )
+        .await?;
+    }
+
+    // If requested, report the installation plan before executing it.
+    report_plan(
+        &PlanReport::new(&local, &remote, extraneous.iter().chain(reinstalls.iter())),
+        report,
+        report_json,
+        printer,
+    )?;
+
     // Download, build, and unzip any missing distributions.
-    let wheels = if remote.is_empty() {
-        Vec::new()
+    let (wheels, built_wheels, failures) = if remote.is_empty() {
+        (Vec::new(), Vec::new(), Vec::new())
     } else {
         let start = std::time::Instant::now();
 
-        let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
+        let downloader = Downloader::new(cache, tags, &client, &build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
-        let wheels = downloader
-            .download(remote, &in_flight)
-            .await
-            .context("Failed to download distributions")?;
+        let (wheels, failures) = if keep_going {
+            downloader
+                .download_partial(remote, in_flight)
+                .await
+                .context("Failed to download distributions")?
+        } else {
+            let wheels = downloader
+                .download(remote, in_flight)
+                .await
+                .context("Failed to download distributions")?;
+            (wheels, Vec::new())
+        };
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -250,9 +500,35 @@ pub(crate) async fn pip_sync(
             .dimmed()
         )?;
 
-        wheels
+        (wheels, downloader.built_wheels(), failures)
     };
 
+    // Record the wheels that were built from source distributions, if requested.
+    if let Some(build_manifest) = build_manifest {
+        write_build_manifest(build_manifest, &built_wheels)?;
+    }
+
+    // Leave any existing installation in place for packages that failed to download or build.
+    let reinstalls = reinstalls
+        .into_iter()
+        .filter(|dist_info| {
+            !failures
+                .iter()
+                .filter_map(uv_installer::Error::dist)
+                .any(|dist| dist.name() == dist_info.name())
+        })
+        .collect::<Vec<_>>();
+
+    // Check for any scripts that would be overwritten by the installation.
+    let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
+    check_script_conflicts(
+        venv,
+        &wheels,
+        extraneous.iter().chain(reinstalls.iter()),
+        strict,
+        printer,
+    )?;
+
     // Remove any unnecessary packages.
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
@@ -287,11 +563,11 @@ pub(crate) async fn pip_sync(
     }
 
     // Install the resolved distributions.
-    let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
-        uv_installer::Installer::new(&venv)
+        uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang.clone())
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -308,8 +584,8 @@ pub(crate) async fn pip_sync(
         )?;
     }
 
-    // Report on any changes in the environment.
-    for event in extraneous
+    // Collect the changes to the environment.
+    let changes = extraneous
         .into_iter()
         .chain(reinstalls.into_iter())
         .map(|distribution| ChangeEvent {
@@ -326,7 +602,200 @@ pub(crate) async fn pip_sync(
                 .cmp(b.dist.name())
                 .then_with(|| a.kind.cmp(&b.kind))
         })
-    {
+        .collect();
+
+    Ok(SyncOutcome { changes, failures })
+}
+
+/// Report the installation plan as text (if `report` is set) and as JSON (if a path is provided).
+fn report_plan(
+    plan: &PlanReport,
+    report: bool,
+    report_json: Option<&Path>,
+    mut printer: Printer,
+) -> Result<()> {
+    if report {
+        writeln!(printer, "{plan}")?;
+    }
+    if let Some(report_json) = report_json {
+        let mut contents = serde_json::to_string_pretty(plan)?;
+        contents.push('\n');
+        fs_err::write(report_json, contents).with_context(|| {
+            format!(
+                "Failed to write installation plan to: {}",
+                report_json.normalized_display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Report that an environment already satisfies its requirements.
+fn audited(num_requirements: usize, start: std::time::Instant, mut printer: Printer) -> Result<()> {
+    let s = if num_requirements == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Audited {} in {}",
+            format!("{num_requirements} package{s}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+    Ok(())
+}
+
+/// Error if any of the given distributions would need to be built from source, despite builds
+/// being disabled (e.g., via `--no-build` or `--only-binary`).
+///
+/// Every such distribution is listed, along with the wheels that were considered for it (and why
+/// each was rejected), such that the requirements can be fixed in one pass.
+async fn check_no_build(
+    remote: &[Dist],
+    no_build: &NoBuild,
+    tags: &Tags,
+    python_version: &Version,
+    client: &RegistryClient,
+) -> Result<()> {
+    let unbuildable = remote
+        .iter()
+        .filter_map(|dist| match dist {
+            Dist::Source(source) => Some(source),
+            Dist::Built(_) => None,
+        })
+        .filter(|source| match no_build {
+            NoBuild::All => true,
+            NoBuild::None => false,
+            NoBuild::Packages(packages) => packages.contains(source.name()),
+        })
+        .collect::<Vec<_>>();
+    if unbuildable.is_empty() {
+        return Ok(());
+    }
+
+    let mut reasons = Vec::with_capacity(unbuildable.len());
+    for source in unbuildable {
+        let SourceDist::Registry(sdist) = source else {
+            reasons.push(format!("{source} must be built from source"));
+            continue;
+        };
+
+        // Collect the wheels published for the same version, if any.
+        let wheels = client
+            .simple(&sdist.filename.name)
+            .await
+            .map(|results| {
+                results
+                    .iter()
+                    .flat_map(|(_, archive)| OwnedArchive::deserialize(archive))
+                    .filter(|metadatum| metadatum.version == sdist.filename.version)
+                    .flat_map(|metadatum| metadatum.files.wheels)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if wheels.is_empty() {
+            reasons.push(format!("{source} has no wheels"));
+        } else {
+            let considered = wheels
+                .iter()
+                .map(|wheel| {
+                    let reason = rejection_reason(wheel, tags, python_version);
+                    format!("{} ({reason})", wheel.name.get_tag())
+                })
+                .unique()
+                .join(", ");
+            reasons.push(format!(
+                "{source} has no compatible wheels (found: {considered})"
+            ));
+        }
+    }
+
+    reasons.sort();
+
+    let s = if reasons.len() == 1 { "" } else { "s" };
+    let mut message = format!(
+        "Building source distributions is disabled, but {} package{s} would need to be built:",
+        reasons.len()
+    );
+    for reason in &reasons {
+        message.push_str(&format!("\n  {reason}"));
+    }
+    if let Some(best) = tags.best() {
+        message.push_str(&format!(
+            "\n\nThe most specific wheel tag supported by the current environment is `{best}`."
+        ));
+    }
+    Err(UsageError(message).into())
+}
+
+/// Explain why a wheel was not selected for installation in the current environment.
+fn rejection_reason(wheel: &VersionWheel, tags: &Tags, python_version: &Version) -> &'static str {
+    match wheel.name.compatibility(tags) {
+        TagCompatibility::Compatible(_) => {
+            if wheel
+                .file
+                .requires_python
+                .as_ref()
+                .is_some_and(|requires_python| !requires_python.contains(python_version))
+            {
+                "incompatible `requires-python`"
+            } else if wheel.file.yanked.as_ref().is_some_and(Yanked::is_yanked) {
+                "yanked"
+            } else {
+                "not selected"
+            }
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Invalid) => "invalid tags",
+        TagCompatibility::Incompatible(IncompatibleTag::Python) => {
+            "incompatible Python implementation"
+        }
+        TagCompatibility::Incompatible(IncompatibleTag::Abi) => "incompatible Python ABI",
+        TagCompatibility::Incompatible(IncompatibleTag::Platform) => "incompatible platform",
+    }
+}
+
+/// Warn about any scripts that would be overwritten by installing the given wheels. In `--strict`
+/// mode, refuse to install them instead.
+pub(super) fn check_script_conflicts<'a>(
+    venv: &Virtualenv,
+    wheels: &[CachedDist],
+    removals: impl IntoIterator<Item = &'a InstalledDist>,
+    strict: bool,
+    mut printer: Printer,
+) -> Result<()> {
+    let conflicts = uv_installer::script_conflicts(venv, wheels, removals)?;
+    if strict && !conflicts.is_empty() {
+        bail!(
+            "{}",
+            conflicts
+                .iter()
+                .map(|conflict| format!(
+                    "{} (remove `--strict` to install anyway)",
+                    conflict.message()
+                ))
+                .join("\n")
+        );
+    }
+    for conflict in conflicts {
+        writeln!(
+            printer,
+            "{}{} {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            conflict.message().bold()
+        )?;
+    }
+    Ok(())
+}
+
+/// Report on a set of changes to an environment.
+pub(super) fn report_changes(
+    changes: Vec<ChangeEvent<LocalDist>>,
+    mut printer: Printer,
+) -> Result<()> {
+    for event in changes {
         match event.kind {
             ChangeEventKind::Added => {
                 writeln!(
@@ -348,22 +817,37 @@ pub(crate) async fn pip_sync(
             }
         }
     }
+    Ok(())
+}
 
-    // Validate that the environment is consistent.
-    if strict {
-        let site_packages = SitePackages::from_executable(&venv)?;
-        for diagnostic in site_packages.diagnostics()? {
+/// Report on a set of distributions that failed to download or build.
+fn report_failures(failures: &[uv_installer::Error], mut printer: Printer) -> Result<()> {
+    let s = if failures.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}{} Failed to install {}",
+        "error".red().bold(),
+        ":".bold(),
+        format!("{} package{s}", failures.len()).bold()
+    )?;
+    for err in failures {
+        if let Some(dist) = err.dist() {
             writeln!(
                 printer,
-                "{}{} {}",
-                "warning".yellow().bold(),
-                ":".bold(),
-                diagnostic.message().bold()
+                " {} {}",
+                "x".red(),
+                dist.to_string().white().bold()
             )?;
+        } else {
+            writeln!(printer, " {} {}", "x".red(), err.to_string().white().bold())?;
+        }
+        let mut source = std::error::Error::source(err);
+        while let Some(err) = source {
+            writeln!(printer, "   {}: {}", "Caused by".red().bold(), err)?;
+            source = err.source();
         }
     }
-
-    Ok(ExitStatus::Success)
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -33,7 +33,10 @@ pub(crate) async fn pip_uninstall(
         extra_index_urls: _extra_index_urls,
         no_index: _no_index,
         find_links: _find_links,
+        no_binary: _no_binary,
+        only_binary: _only_binary,
         extras: _extras,
+        groups: _groups,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
     // Detect the current Python interpreter.
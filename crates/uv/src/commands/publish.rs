@@ -0,0 +1,455 @@
+use std::fmt::Write;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use reqwest::header::CONTENT_TYPE;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use url::Url;
+
+use distribution_filename::{DistFilename, SourceDistExtension};
+use install_wheel_rs::read_dist_info;
+use pypi_types::Metadata21;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, RegistryClientBuilder, TrustedHost};
+use uv_fs::Normalized;
+
+use crate::commands::python::client;
+use crate::commands::{elapsed, ExitStatus, UsageError};
+use crate::printer::Printer;
+
+/// The username used when authenticating with an API token.
+const TOKEN_USERNAME: &str = "__token__";
+
+/// The credentials with which to authenticate against the upload endpoint.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PublishCredentials {
+    /// The username, e.g., as provided via `--username`.
+    pub(crate) username: Option<String>,
+    /// The password, e.g., as provided via `--password`.
+    pub(crate) password: Option<String>,
+    /// An API token, e.g., as provided via `--token`.
+    pub(crate) token: Option<String>,
+    /// Whether to query the `keyring` CLI for a password, if none was provided.
+    pub(crate) keyring: bool,
+    /// Whether to mint a short-lived API token via trusted publishing.
+    pub(crate) trusted_publishing: bool,
+}
+
+/// Upload distributions to a package index via the legacy upload API.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn publish(
+    paths: &[PathBuf],
+    publish_url: &Url,
+    credentials: PublishCredentials,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Collect the distributions to upload, defaulting to the contents of `dist`.
+    let files = if paths.is_empty() {
+        files_in_dir(Path::new("dist"))?
+    } else {
+        paths.to_vec()
+    };
+    let files = files
+        .into_iter()
+        .map(|path| {
+            let filename = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(DistFilename::try_from_normalized_filename)
+                .ok_or_else(|| {
+                    UsageError(format!(
+                        "Expected a wheel (`.whl`) or source distribution (`.tar.gz` or `.zip`), but found: {}",
+                        path.normalized_display()
+                    ))
+                })?;
+            Ok((path, filename))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if files.is_empty() {
+        writeln!(printer, "No files found to upload")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if matches!(connectivity, Connectivity::Offline) {
+        bail!("Publishing requires network access, but `--offline` was provided");
+    }
+
+    let client = client(cache, trusted_hosts.clone(), connectivity, timeouts);
+
+    // Uploads aren't idempotent: if the server accepted a file but the response was lost, a retry
+    // would be rejected as a duplicate, masking the successful upload.
+    let upload_client = RegistryClientBuilder::new(cache.clone())
+        .trusted_hosts(trusted_hosts)
+        .connectivity(connectivity)
+        .timeouts(timeouts)
+        .retries(0)
+        .build()
+        .cached_client()
+        .uncached();
+
+    let (username, password) = resolve_credentials(&client, publish_url, credentials).await?;
+
+    for (path, filename) in &files {
+        writeln!(printer, "Uploading {}", filename.to_string().bold())?;
+        upload(
+            &upload_client,
+            publish_url,
+            &username,
+            &password,
+            path,
+            filename,
+        )
+        .await
+        .with_context(|| format!("Failed to upload: {}", path.normalized_display()))?;
+    }
+
+    let s = if files.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Uploaded {} in {}",
+            format!("{} file{}", files.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the distributions in the given directory, in sorted order.
+fn files_in_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = fs_err::read_dir(dir)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(DistFilename::try_from_normalized_filename)
+                .is_some()
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Resolve the username and password with which to authenticate.
+async fn resolve_credentials(
+    client: &ClientWithMiddleware,
+    publish_url: &Url,
+    credentials: PublishCredentials,
+) -> Result<(String, String)> {
+    if credentials.trusted_publishing {
+        let token = trusted_publishing_token(client, publish_url).await?;
+        return Ok((TOKEN_USERNAME.to_string(), token));
+    }
+
+    if let Some(token) = credentials.token {
+        return Ok((TOKEN_USERNAME.to_string(), token));
+    }
+
+    let Some(username) = credentials.username else {
+        return Err(UsageError(
+            "No credentials provided; pass `--token`, `--username` and `--password`, or `--trusted-publishing`".to_string(),
+        )
+        .into());
+    };
+
+    if let Some(password) = credentials.password {
+        return Ok((username, password));
+    }
+
+    if credentials.keyring {
+        let password = keyring_password(publish_url, &username)?;
+        return Ok((username, password));
+    }
+
+    Err(UsageError(format!(
+        "No password provided for `{username}`; pass `--password` or `--keyring`"
+    ))
+    .into())
+}
+
+/// Query the `keyring` CLI for the password associated with the given URL and username.
+fn keyring_password(publish_url: &Url, username: &str) -> Result<String> {
+    let keyring = which::which("keyring")
+        .context("Failed to find the `keyring` executable; is it installed and on the `PATH`?")?;
+    debug!("Querying {} for credentials", keyring.normalized_display());
+
+    let output = std::process::Command::new(keyring)
+        .arg("get")
+        .arg(publish_url.as_str())
+        .arg(username)
+        .output()
+        .context("Failed to run `keyring`")?;
+    if !output.status.success() {
+        bail!(
+            "No password found in the keyring for `{username}` at: {publish_url}\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let password = String::from_utf8(output.stdout)?.trim_end().to_string();
+    if password.is_empty() {
+        bail!("No password found in the keyring for `{username}` at: {publish_url}");
+    }
+    Ok(password)
+}
+
+#[derive(Deserialize)]
+struct Audience {
+    audience: String,
+}
+
+#[derive(Deserialize)]
+struct OidcToken {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct MintedToken {
+    token: String,
+}
+
+/// Exchange an OpenID Connect token from GitHub Actions for a short-lived API token, via the
+/// index's trusted publishing endpoints.
+///
+/// See: <https://docs.pypi.org/trusted-publishers/using-a-publisher/>
+async fn trusted_publishing_token(
+    client: &ClientWithMiddleware,
+    publish_url: &Url,
+) -> Result<String> {
+    let (Ok(request_url), Ok(request_token)) = (
+        std::env::var("ACTIONS_ID_TOKEN_REQUEST_URL"),
+        std::env::var("ACTIONS_ID_TOKEN_REQUEST_TOKEN"),
+    ) else {
+        bail!(
+            "Trusted publishing is only supported in GitHub Actions, with the `id-token: write` permission"
+        );
+    };
+
+    // Uploads to `upload.pypi.org` are authorized by `pypi.org`.
+    let mut index_url = publish_url.clone();
+    index_url.set_path("");
+    if index_url.host_str() == Some("upload.pypi.org") {
+        index_url.set_host(Some("pypi.org"))?;
+    }
+
+    let Audience { audience } = client
+        .get(index_url.join("_/oidc/audience")?)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to determine the trusted publishing audience")?;
+    debug!("Requesting an OIDC token for audience: {audience}");
+
+    let mut request_url = Url::parse(&request_url)?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("audience", &audience);
+    let OidcToken { value } = client
+        .get(request_url)
+        .bearer_auth(request_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to request an OIDC token from GitHub Actions")?;
+
+    let response = client
+        .post(index_url.join("_/oidc/mint-token")?)
+        .header(CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&serde_json::json!({ "token": value }))?)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("Failed to mint a trusted publishing token ({status}): {body}");
+    }
+    let MintedToken { token } = response.json().await?;
+
+    Ok(token)
+}
+
+/// Upload a single distribution.
+async fn upload(
+    client: &ClientWithMiddleware,
+    publish_url: &Url,
+    username: &str,
+    password: &str,
+    path: &Path,
+    filename: &DistFilename,
+) -> Result<()> {
+    let contents = fs_err::tokio::read(path).await?;
+    let metadata = read_metadata(&contents, filename)?;
+    let sha256_digest = format!("{:x}", Sha256::digest(&contents));
+
+    let (filetype, pyversion) = match filename {
+        DistFilename::WheelFilename(wheel) => ("bdist_wheel", wheel.python_tag.join(".")),
+        DistFilename::SourceDistFilename(_) => ("sdist", "source".to_string()),
+    };
+
+    // The digest of the file can't appear in the file itself, so it makes for a safe boundary.
+    let mut form = MultipartForm::new(format!("uv-{sha256_digest}"));
+    form.text(":action", "file_upload");
+    form.text("protocol_version", "1");
+    form.text("metadata_version", &metadata.metadata_version);
+    form.text("name", metadata.name.as_ref());
+    form.text("version", &metadata.version.to_string());
+    form.text("filetype", filetype);
+    form.text("pyversion", &pyversion);
+    form.text("sha256_digest", &sha256_digest);
+    if let Some(requires_python) = metadata.requires_python {
+        form.text("requires_python", &requires_python.to_string());
+    }
+    for requirement in metadata.requires_dist {
+        form.text("requires_dist", &requirement.to_string());
+    }
+    for extra in metadata.provides_extras {
+        form.text("provides_extra", extra.as_ref());
+    }
+    form.file("content", &filename.to_string(), &contents);
+
+    debug!("Uploading {filename} to: {publish_url}");
+    let response = client
+        .post(publish_url.clone())
+        .basic_auth(username, Some(password))
+        .header(CONTENT_TYPE, form.content_type())
+        .body(form.finish())
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!(
+            "Upload was rejected by the server ({status}): {}",
+            body.trim()
+        );
+    }
+
+    Ok(())
+}
+
+/// A `multipart/form-data` body, buffered in memory.
+///
+/// Unlike a streaming body, a buffered body can be cloned, which the client's retry middleware
+/// requires, even with retries disabled.
+struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl MultipartForm {
+    fn new(boundary: String) -> Self {
+        Self {
+            boundary,
+            body: Vec::new(),
+        }
+    }
+
+    /// Add a text field.
+    fn text(&mut self, name: &str, value: &str) {
+        self.part(
+            &format!("Content-Disposition: form-data; name=\"{name}\""),
+            value.as_bytes(),
+        );
+    }
+
+    /// Add a file field.
+    fn file(&mut self, name: &str, filename: &str, contents: &[u8]) {
+        self.part(
+            &format!(
+                "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\"\r\nContent-Type: application/octet-stream"
+            ),
+            contents,
+        );
+    }
+
+    fn part(&mut self, headers: &str, contents: &[u8]) {
+        self.body
+            .extend_from_slice(format!("--{}\r\n{headers}\r\n\r\n", self.boundary).as_bytes());
+        self.body.extend_from_slice(contents);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    /// The value of the `Content-Type` header for the form.
+    fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Return the encoded body, terminated by the closing boundary.
+    fn finish(mut self) -> Vec<u8> {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+}
+
+/// Read the core metadata from a wheel's `METADATA` file, or a source distribution's `PKG-INFO`.
+fn read_metadata(contents: &[u8], filename: &DistFilename) -> Result<Metadata21> {
+    let metadata = match filename {
+        DistFilename::WheelFilename(wheel) => {
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(contents))?;
+            read_dist_info(wheel, &mut archive)?
+        }
+        DistFilename::SourceDistFilename(sdist) => match sdist.extension {
+            SourceDistExtension::TarGz => {
+                let mut archive =
+                    tar::Archive::new(flate2::read::GzDecoder::new(std::io::Cursor::new(contents)));
+                let mut buffer = Vec::new();
+                for entry in archive.entries()? {
+                    let mut entry = entry?;
+                    if is_pkg_info(&entry.path()?.to_string_lossy()) {
+                        entry.read_to_end(&mut buffer)?;
+                        break;
+                    }
+                }
+                buffer
+            }
+            SourceDistExtension::Zip => {
+                let mut archive = zip::ZipArchive::new(std::io::Cursor::new(contents))?;
+                let name = archive
+                    .file_names()
+                    .find(|name| is_pkg_info(name))
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let mut buffer = Vec::new();
+                if let Ok(mut file) = archive.by_name(&name) {
+                    file.read_to_end(&mut buffer)?;
+                }
+                buffer
+            }
+        },
+    };
+    if metadata.is_empty() {
+        bail!("Missing `PKG-INFO` in source distribution: {filename}");
+    }
+    Ok(Metadata21::parse(&metadata)?)
+}
+
+/// Returns `true` if the archive entry is the `PKG-INFO` file in the top-level directory of a
+/// source distribution.
+fn is_pkg_info(entry: &str) -> bool {
+    entry
+        .trim_start_matches("./")
+        .split_once('/')
+        .is_some_and(|(_, file)| file == "PKG-INFO")
+}
@@ -0,0 +1,322 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+use owo_colors::OwoColorize;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use platform_host::{Arch, Os, Platform};
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, RegistryClientBuilder, TrustedHost};
+use uv_fs::Normalized;
+use uv_interpreter::{ToolchainRequest, ToolchainVersion, Toolchains};
+use uv_warnings::warn_user;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// The GitHub API endpoint for the latest release of `python-build-standalone`.
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/indygreg/python-build-standalone/releases/latest";
+
+/// List the installed toolchains or, with `available`, the toolchains that can be installed.
+pub(crate) async fn python_list(
+    available: bool,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let toolchains = toolchains()?;
+    let installed = toolchains.find_all()?;
+
+    if available {
+        let client = client(cache, trusted_hosts, connectivity, timeouts);
+        let downloads = available_downloads(&client).await?;
+        for download in downloads {
+            let marker = if installed
+                .iter()
+                .any(|toolchain| *toolchain.version() == download.version)
+            {
+                " (installed)"
+            } else {
+                ""
+            };
+            writeln!(printer, "{}{}", download.version, marker.dimmed())?;
+        }
+    } else {
+        for toolchain in installed {
+            writeln!(
+                printer,
+                "{} {}",
+                toolchain.version(),
+                toolchain.executable().normalized_display().cyan()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Download and install standalone CPython builds into the managed toolchain directory.
+///
+/// Each build is verified against its published SHA-256 digest. Builds without a published digest
+/// are rejected, unless `allow_unverified` is set.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn python_install(
+    requests: &[String],
+    force: bool,
+    allow_unverified: bool,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let requests = requests
+        .iter()
+        .map(|request| ToolchainRequest::from_str(request).map_err(|err| anyhow!(err)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let toolchains = toolchains()?;
+    let installed = toolchains.find_all()?;
+
+    let client = client(cache, trusted_hosts, connectivity, timeouts);
+    let downloads = available_downloads(&client).await?;
+
+    let mut num_installed = 0usize;
+    for request in &requests {
+        // Downloads are sorted newest-first, so take the first match.
+        let Some(download) = downloads
+            .iter()
+            .find(|download| request.matches(&download.version))
+        else {
+            bail!("No download found for Python {request} on the current platform");
+        };
+
+        if !force
+            && installed
+                .iter()
+                .any(|toolchain| *toolchain.version() == download.version)
+        {
+            writeln!(
+                printer,
+                "Python {} is already installed",
+                download.version.to_string().bold()
+            )?;
+            continue;
+        }
+
+        writeln!(
+            printer,
+            "Downloading Python {} from: {}",
+            download.version.to_string().bold(),
+            download.url.dimmed()
+        )?;
+        install(&client, &toolchains, download, allow_unverified)
+            .await
+            .with_context(|| format!("Failed to install Python {}", download.version))?;
+        num_installed += 1;
+    }
+
+    if num_installed > 0 {
+        let s = if num_installed == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Installed {} in {} to: {}",
+                format!("{num_installed} toolchain{s}").bold(),
+                elapsed(start.elapsed()),
+                toolchains.root().normalized_display().cyan()
+            )
+            .dimmed()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the managed toolchain directory.
+fn toolchains() -> Result<Toolchains> {
+    Toolchains::from_env().ok_or_else(|| {
+        anyhow!("Could not determine the toolchain directory; set `UV_TOOLCHAIN_DIR` explicitly")
+    })
+}
+
+/// Build a client for requests outside of the package indexes (e.g., release artifacts on GitHub).
+///
+/// Uses the same client as for package indexes, such that `--trusted-host`, `--offline`, the
+/// timeouts, and the retry policy all apply.
+pub(super) fn client(
+    cache: &Cache,
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+) -> ClientWithMiddleware {
+    RegistryClientBuilder::new(cache.clone())
+        .trusted_hosts(trusted_hosts)
+        .connectivity(connectivity)
+        .timeouts(timeouts)
+        .build()
+        .cached_client()
+        .uncached()
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// A standalone CPython build that can be installed on the current platform.
+#[derive(Debug)]
+struct Download {
+    version: ToolchainVersion,
+    url: String,
+    /// The URL of the file containing the SHA-256 digest of the archive.
+    sha256_url: Option<String>,
+}
+
+/// Return the builds in the latest `python-build-standalone` release that are compatible with the
+/// current platform, sorted by version, with the newest first.
+async fn available_downloads(client: &ClientWithMiddleware) -> Result<Vec<Download>> {
+    let platform = Platform::current()?;
+    let Some(triple) = target_triple(&platform) else {
+        bail!(
+            "Managed toolchains are not available for the current platform: {} {}",
+            platform.os(),
+            platform.arch()
+        );
+    };
+
+    debug!("Fetching available toolchains from: {LATEST_RELEASE_URL}");
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to fetch the available toolchains")?;
+
+    // e.g., `cpython-3.12.1+20240107-x86_64-unknown-linux-gnu-install_only.tar.gz`
+    let suffix = format!("-{triple}-install_only.tar.gz");
+    let mut downloads = release
+        .assets
+        .iter()
+        .filter_map(|asset| {
+            let (version, _date) = asset
+                .name
+                .strip_prefix("cpython-")?
+                .strip_suffix(&suffix)?
+                .split_once('+')?;
+            let version = ToolchainVersion::from_str(version).ok()?;
+            let sha256_name = format!("{}.sha256", asset.name);
+            let sha256_url = release
+                .assets
+                .iter()
+                .find(|asset| asset.name == sha256_name)
+                .map(|asset| asset.browser_download_url.clone());
+            Some(Download {
+                version,
+                url: asset.browser_download_url.clone(),
+                sha256_url,
+            })
+        })
+        .collect::<Vec<_>>();
+    downloads.sort_by(|a, b| b.version.cmp(&a.version));
+    downloads.dedup_by(|a, b| a.version == b.version);
+    Ok(downloads)
+}
+
+/// Return the `python-build-standalone` target triple for the given platform, if supported.
+fn target_triple(platform: &Platform) -> Option<&'static str> {
+    match (platform.os(), platform.arch()) {
+        (Os::Manylinux { .. }, Arch::X86_64) => Some("x86_64-unknown-linux-gnu"),
+        (Os::Manylinux { .. }, Arch::Aarch64) => Some("aarch64-unknown-linux-gnu"),
+        (Os::Musllinux { .. }, Arch::X86_64) => Some("x86_64-unknown-linux-musl"),
+        (Os::Macos { .. }, Arch::X86_64) => Some("x86_64-apple-darwin"),
+        (Os::Macos { .. }, Arch::Aarch64) => Some("aarch64-apple-darwin"),
+        (Os::Windows, Arch::X86_64) => Some("x86_64-pc-windows-msvc-shared"),
+        (Os::Windows, Arch::X86) => Some("i686-pc-windows-msvc-shared"),
+        _ => None,
+    }
+}
+
+/// Download, verify, and unpack a build into the toolchain directory.
+async fn install(
+    client: &ClientWithMiddleware,
+    toolchains: &Toolchains,
+    download: &Download,
+    allow_unverified: bool,
+) -> Result<()> {
+    let archive = client
+        .get(&download.url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    if let Some(sha256_url) = &download.sha256_url {
+        let expected = client
+            .get(sha256_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let expected = expected.split_whitespace().next().unwrap_or_default();
+        let actual = format!("{:x}", Sha256::digest(&archive));
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!(
+                "Hash mismatch for `{}`: expected {expected}, found {actual}",
+                download.url
+            );
+        }
+    } else if allow_unverified {
+        warn_user!(
+            "No SHA-256 digest is published for `{}`; installing it without verification",
+            download.url
+        );
+    } else {
+        bail!(
+            "No SHA-256 digest is published for `{}`; pass `--allow-unverified` to install it without verification",
+            download.url
+        );
+    }
+
+    let root = toolchains.root().to_path_buf();
+    let target = toolchains.install_dir(&download.version);
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        fs_err::create_dir_all(&root)?;
+
+        // Unpack into a temporary directory within the toolchain root, then move the `python`
+        // directory into place, such that an interrupted install can't leave a partial toolchain.
+        let temp_dir = tempfile::tempdir_in(&root)?;
+        tar::Archive::new(GzDecoder::new(archive.as_ref())).unpack(temp_dir.path())?;
+
+        if target.exists() {
+            fs_err::remove_dir_all(&target)?;
+        }
+        fs_err::rename(temp_dir.path().join("python"), &target)?;
+        Ok(())
+    })
+    .await??;
+
+    Ok(())
+}
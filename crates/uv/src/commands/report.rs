@@ -0,0 +1,396 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+use distribution_types::{
+    CachedDist, Dist, DistributionMetadata, InstalledDist, InstalledMetadata, InstalledVersion,
+    LocalDist, Name, VersionOrUrl,
+};
+use pypi_types::HashAlgorithm;
+use uv_resolver::ResolutionGraph;
+
+use crate::commands::clean::format_bytes;
+use crate::commands::{ChangeEvent, ChangeEventKind};
+
+/// The format in which to emit the results of a command.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Human-readable text (e.g., a `requirements.txt` file, for `pip compile`).
+    #[default]
+    Text,
+    /// Structured JSON, for consumption by other tools.
+    Json,
+}
+
+impl CacheKey for OutputFormat {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Text => 0u8.cache_key(state),
+            Self::Json => 1u8.cache_key(state),
+        }
+    }
+}
+
+/// The results of a `pip compile` invocation, in machine-readable form.
+///
+/// For example:
+/// ```json
+/// {
+///   "packages": [
+///     { "name": "black", "version": "23.10.1", "hashes": ["sha256:…"] },
+///     { "name": "flask", "url": "git+https://github.com/pallets/flask.git@…", "hashes": [] }
+///   ],
+///   "diagnostics": [],
+///   "elapsed_ms": 123
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+pub(crate) struct CompileReport {
+    /// The pinned packages, sorted by name.
+    packages: Vec<ReportPackage>,
+    /// Any diagnostics that were encountered during resolution.
+    diagnostics: Vec<String>,
+    /// The time taken to resolve, in milliseconds.
+    elapsed_ms: u128,
+}
+
+impl CompileReport {
+    pub(crate) fn new(
+        resolution: &ResolutionGraph,
+        hash_algorithms: &[HashAlgorithm],
+        elapsed: Duration,
+    ) -> Self {
+        let mut packages = resolution
+            .petgraph()
+            .node_weights()
+            .map(|dist| {
+                let (version, url) = match dist.version_or_url() {
+                    VersionOrUrl::Version(version) => (Some(version.to_string()), None),
+                    VersionOrUrl::Url(url) => (None, Some(url.to_string())),
+                };
+                ReportPackage {
+                    name: dist.name().to_string(),
+                    version,
+                    url,
+                    hashes: Some(
+                        resolution
+                            .hashes(dist.name())
+                            .iter()
+                            .flat_map(|hashes| {
+                                hash_algorithms
+                                    .iter()
+                                    .filter_map(|algorithm| hashes.format(*algorithm))
+                            })
+                            .collect(),
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            packages,
+            diagnostics: resolution
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| diagnostic.message())
+                .collect(),
+            elapsed_ms: elapsed.as_millis(),
+        }
+    }
+}
+
+/// The results of a `pip sync` invocation, in machine-readable form.
+///
+/// For example:
+/// ```json
+/// {
+///   "installed": [{ "name": "markupsafe", "version": "2.1.3" }],
+///   "uninstalled": [],
+///   "failed": [{ "name": "werkzeug", "error": "Failed to build: werkzeug==0.9.6: …" }],
+///   "elapsed_ms": 123
+/// }
+/// ```
+#[derive(Debug, Serialize)]
+pub(crate) struct SyncReport {
+    /// The packages that were installed (or reinstalled), sorted by name.
+    installed: Vec<ReportPackage>,
+    /// The packages that were uninstalled (or replaced), sorted by name.
+    uninstalled: Vec<ReportPackage>,
+    /// The packages that failed to download or build.
+    failed: Vec<ReportFailure>,
+    /// The time taken to sync, in milliseconds.
+    elapsed_ms: u128,
+}
+
+impl SyncReport {
+    pub(crate) fn new(
+        changes: &[ChangeEvent<LocalDist>],
+        failures: &[uv_installer::Error],
+        elapsed: Duration,
+    ) -> Self {
+        let mut installed = Vec::new();
+        let mut uninstalled = Vec::new();
+        for event in changes {
+            let (version, url) = match event.dist.installed_version() {
+                InstalledVersion::Version(version) => (version.to_string(), None),
+                InstalledVersion::Url(url, version) => (version.to_string(), Some(url.to_string())),
+            };
+            let package = ReportPackage {
+                name: event.dist.name().to_string(),
+                version: Some(version),
+                url,
+                hashes: None,
+            };
+            match event.kind {
+                ChangeEventKind::Added => installed.push(package),
+                ChangeEventKind::Removed => uninstalled.push(package),
+            }
+        }
+
+        let failed = failures
+            .iter()
+            .map(|err| {
+                let mut error = err.to_string();
+                let mut source = std::error::Error::source(err);
+                while let Some(err) = source {
+                    error.push_str(&format!(": {err}"));
+                    source = err.source();
+                }
+                ReportFailure {
+                    name: err.dist().map(|dist| dist.name().to_string()),
+                    error,
+                }
+            })
+            .collect();
+
+        Self {
+            installed,
+            uninstalled,
+            failed,
+            elapsed_ms: elapsed.as_millis(),
+        }
+    }
+}
+
+/// The installation plan of a `pip sync` invocation, as reported before it's executed.
+///
+/// For example:
+/// ```json
+/// {
+///   "install": [
+///     { "name": "markupsafe", "version": "2.1.3", "source": "cache" },
+///     { "name": "tomli", "version": "2.0.1", "source": "download", "size": 12757 },
+///     { "name": "werkzeug", "version": "0.9.6", "source": "build", "size": 1119281 }
+///   ],
+///   "uninstall": [{ "name": "flask", "version": "2.0.0" }],
+///   "download_bytes": 1132038
+/// }
+/// ```
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct PlanReport {
+    /// The packages to install (or reinstall), sorted by name.
+    install: Vec<PlannedPackage>,
+    /// The packages to uninstall (or replace), sorted by name.
+    uninstall: Vec<ReportPackage>,
+    /// The total size of the distributions to download, in bytes, excluding any distributions of
+    /// unknown size.
+    download_bytes: u64,
+}
+
+impl PlanReport {
+    pub(crate) fn new<'a>(
+        local: &[CachedDist],
+        remote: &[Dist],
+        uninstall: impl Iterator<Item = &'a InstalledDist>,
+    ) -> Self {
+        let mut install = local
+            .iter()
+            .map(|dist| {
+                let (version, url) = match dist.installed_version() {
+                    InstalledVersion::Version(version) => (version.to_string(), None),
+                    InstalledVersion::Url(url, version) => {
+                        (version.to_string(), Some(url.to_string()))
+                    }
+                };
+                PlannedPackage {
+                    name: dist.name().to_string(),
+                    version: Some(version),
+                    url,
+                    source: PlannedSource::Cache,
+                    size: None,
+                }
+            })
+            .chain(remote.iter().map(|dist| {
+                let (version, url) = match dist.version_or_url() {
+                    VersionOrUrl::Version(version) => (Some(version.to_string()), None),
+                    VersionOrUrl::Url(url) => (None, Some(url.to_string())),
+                };
+                PlannedPackage {
+                    name: dist.name().to_string(),
+                    version,
+                    url,
+                    source: match dist {
+                        Dist::Built(_) => PlannedSource::Download,
+                        Dist::Source(_) => PlannedSource::Build,
+                    },
+                    size: dist.file().and_then(|file| file.size),
+                }
+            }))
+            .collect::<Vec<_>>();
+        install.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut uninstall = uninstall
+            .map(|dist| {
+                let (version, url) = match dist.installed_version() {
+                    InstalledVersion::Version(version) => (version.to_string(), None),
+                    InstalledVersion::Url(url, version) => {
+                        (version.to_string(), Some(url.to_string()))
+                    }
+                };
+                ReportPackage {
+                    name: dist.name().to_string(),
+                    version: Some(version),
+                    url,
+                    hashes: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        uninstall.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let download_bytes = install.iter().filter_map(|package| package.size).sum();
+
+        Self {
+            install,
+            uninstall,
+            download_bytes,
+        }
+    }
+}
+
+impl Display for PlanReport {
+    /// Render the plan as human-readable text, with one line per package, followed by a summary.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for package in &self.install {
+            let detail = match (package.source, package.size) {
+                (PlannedSource::Cache, _) => "cached".to_string(),
+                (PlannedSource::Download, Some(size)) => {
+                    format!("download, {}", format_bytes(size))
+                }
+                (PlannedSource::Download, None) => "download".to_string(),
+                (PlannedSource::Build, Some(size)) => format!("build, {}", format_bytes(size)),
+                (PlannedSource::Build, None) => "build".to_string(),
+            };
+            writeln!(
+                f,
+                " {} {package} {}",
+                "+".green(),
+                format!("({detail})").dimmed()
+            )?;
+        }
+        for package in &self.uninstall {
+            writeln!(f, " {} {package}", "-".red())?;
+        }
+
+        let count = |source: PlannedSource| {
+            let n = self
+                .install
+                .iter()
+                .filter(|package| package.source == source)
+                .count();
+            format!("{n} package{}", if n == 1 { "" } else { "s" })
+        };
+        let n = self.uninstall.len();
+        write!(
+            f,
+            "{}",
+            format!(
+                "Would download {} ({}), build {}, link {} from the cache, and uninstall {}",
+                count(PlannedSource::Download),
+                format_bytes(self.download_bytes),
+                count(PlannedSource::Build),
+                count(PlannedSource::Cache),
+                format!("{n} package{}", if n == 1 { "" } else { "s" })
+            )
+            .dimmed()
+        )
+    }
+}
+
+/// A package to install, in a [`PlanReport`].
+#[derive(Debug, Serialize)]
+struct PlannedPackage {
+    /// The normalized name of the package.
+    name: String,
+    /// The version of the package, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The URL from which the package will be installed, if not from a registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    /// Where the package will be installed from.
+    source: PlannedSource,
+    /// The size of the distribution to download, in bytes, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+
+impl Display for PlannedPackage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.version, &self.url) {
+            (_, Some(url)) => write!(f, "{} @ {url}", self.name),
+            (Some(version), None) => write!(f, "{}=={version}", self.name),
+            (None, None) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Where a package in a [`PlanReport`] will be installed from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum PlannedSource {
+    /// A wheel that's already in the cache.
+    Cache,
+    /// A wheel that must be downloaded.
+    Download,
+    /// A source distribution that must be downloaded (if remote) and built.
+    Build,
+}
+
+impl Display for ReportPackage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.version, &self.url) {
+            (_, Some(url)) => write!(f, "{} @ {url}", self.name),
+            (Some(version), None) => write!(f, "{}=={version}", self.name),
+            (None, None) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// A package in a [`CompileReport`] or [`SyncReport`].
+#[derive(Debug, Serialize)]
+struct ReportPackage {
+    /// The normalized name of the package.
+    name: String,
+    /// The version of the package, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The URL from which the package was (or will be) installed, if not from a registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    /// The hashes of the package's distributions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hashes: Option<Vec<String>>,
+}
+
+/// A package that failed to download or build, in a [`SyncReport`].
+#[derive(Debug, Serialize)]
+struct ReportFailure {
+    /// The name of the package, if known.
+    name: Option<String>,
+    /// The error that was encountered, including its causes.
+    error: String,
+}
@@ -89,7 +89,12 @@ impl DownloadReporter {
             ProgressBar::with_draw_target(None, self.printer.target()),
         );
 
-        progress.set_style(ProgressStyle::with_template("{wide_msg}").unwrap());
+        progress.enable_steady_tick(Duration::from_millis(200));
+        progress.set_style(
+            ProgressStyle::with_template("{wide_msg} {spinner:.dim}")
+                .unwrap()
+                .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", ""]),
+        );
         progress.set_message(format!("{} {}", "Building".bold().cyan(), color_string));
 
         let mut bars = self.bars.lock().unwrap();
@@ -102,6 +107,35 @@ impl DownloadReporter {
         let progress = &bars[id];
         progress.finish_with_message(format!("   {} {}", "Built".bold().green(), color_string));
     }
+
+    fn on_any_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        let progress = self.multi_progress.insert_before(
+            &self.progress,
+            ProgressBar::with_draw_target(size, self.printer.target()),
+        );
+
+        // If the size is known, show a bar with an ETA; otherwise, show a spinner alongside the
+        // number of bytes received so far.
+        if size.is_some() {
+            progress.set_style(
+                ProgressStyle::with_template(
+                    "{msg:10.dim} {bar:30.green/dim} {binary_bytes:>7}/{binary_total_bytes:7} {eta}",
+                )
+                .unwrap()
+                .progress_chars("--"),
+            );
+        } else {
+            progress.set_style(
+                ProgressStyle::with_template("{msg:10.dim} {spinner:.green} {binary_bytes:>7}")
+                    .unwrap(),
+            );
+        }
+        progress.set_message(name.to_string());
+
+        let mut bars = self.bars.lock().unwrap();
+        bars.push(progress);
+        bars.len() - 1
+    }
 }
 
 impl uv_installer::DownloadReporter for DownloadReporter {
@@ -160,6 +194,20 @@ impl uv_installer::DownloadReporter for DownloadReporter {
             rev.dimmed()
         ));
     }
+
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        self.on_any_download_start(name, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].inc(bytes);
+    }
+
+    fn on_download_complete(&self, _name: &PackageName, id: usize) {
+        let bars = self.bars.lock().unwrap();
+        bars[id].finish_and_clear();
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,277 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::str::FromStr;
+
+use anstream::eprint;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use miette::{Diagnostic, IntoDiagnostic};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use pep440_rs::{Version, VersionSpecifiers, VersionSpecifiersParseError};
+use pep508_rs::{Pep508Error, Requirement};
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_traits::{Concurrency, NoBuild, SetupPyStrategy};
+
+use crate::commands::ephemeral::ephemeral_environment;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Run a Python script, installing any dependencies declared in its inline metadata.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    script: &Path,
+    args: &[OsString],
+    python_request: Option<&str>,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    connectivity: Connectivity,
+    upgrade: bool,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    match run_impl(
+        script,
+        args,
+        python_request,
+        index_locations,
+        trusted_hosts,
+        connectivity,
+        upgrade,
+        setup_py,
+        no_build,
+        no_binary,
+        exclude_newer,
+        timeouts,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await
+    {
+        Ok(status) => Ok(status),
+        Err(err) => {
+            eprint!("{err:?}");
+            Ok(ExitStatus::Failure)
+        }
+    }
+}
+
+#[derive(Error, Debug, Diagnostic)]
+enum RunError {
+    #[error("Failed to read script: `{0}`")]
+    #[diagnostic(code(uv::run::read))]
+    Read(String, #[source] std::io::Error),
+
+    #[error("Unterminated `script` metadata block (expected a closing `# ///` line)")]
+    #[diagnostic(
+        code(uv::run::unterminated),
+        help("Each line in the metadata block must either be `#` or start with `# `")
+    )]
+    Unterminated,
+
+    #[error("Multiple `script` metadata blocks found")]
+    #[diagnostic(
+        code(uv::run::multiple_blocks),
+        help("A script may contain at most one `# /// script` block")
+    )]
+    MultipleBlocks,
+
+    #[error("Failed to parse `script` metadata block")]
+    #[diagnostic(code(uv::run::toml))]
+    Toml(#[source] toml::de::Error),
+
+    #[error("Failed to parse dependency: `{0}`")]
+    #[diagnostic(code(uv::run::requirement))]
+    Requirement(String, #[source] Pep508Error),
+
+    #[error("Failed to parse `requires-python`: `{0}`")]
+    #[diagnostic(code(uv::run::requires_python))]
+    RequiresPython(String, #[source] VersionSpecifiersParseError),
+
+    #[error("The script requires Python {requires_python}, but {version} was found")]
+    #[diagnostic(
+        code(uv::run::incompatible_python),
+        help("Use `--python` to select a compatible interpreter")
+    )]
+    IncompatiblePython {
+        requires_python: VersionSpecifiers,
+        version: Version,
+    },
+
+    #[error("Failed to run `{0}`")]
+    #[diagnostic(code(uv::run::spawn))]
+    Spawn(String, #[source] std::io::Error),
+}
+
+/// The inline metadata of a Python script, as specified in PEP 723.
+///
+/// See: <https://peps.python.org/pep-0723/>
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ScriptMetadata {
+    dependencies: Option<Vec<String>>,
+    requires_python: Option<String>,
+}
+
+impl ScriptMetadata {
+    /// Extract the `script` metadata block from the contents of a Python script, if present.
+    ///
+    /// As in the reference regular expression, the block spans the consecutive comment lines (i.e.,
+    /// `#` or lines starting with `# `) that follow the opening `# /// script` line, and ends at the
+    /// last `# ///` line among them, such that the contents may themselves contain `# ///`. A script
+    /// with more than one `script` block is rejected.
+    fn parse(contents: &str) -> Result<Option<Self>, RunError> {
+        let lines = contents.lines().collect::<Vec<_>>();
+
+        // Find the start of the `script` block.
+        let Some(start) = lines
+            .iter()
+            .position(|line| line.trim_end() == "# /// script")
+        else {
+            return Ok(None);
+        };
+
+        // Collect the consecutive comment lines that follow.
+        let block = lines[start + 1..]
+            .iter()
+            .take_while(|line| **line == "#" || line.starts_with("# "))
+            .collect::<Vec<_>>();
+
+        // Find the end of the block.
+        let Some(end) = block.iter().rposition(|line| line.trim_end() == "# ///") else {
+            return Err(RunError::Unterminated);
+        };
+
+        // Reject any other `script` block after this one.
+        if lines[start + end + 2..]
+            .iter()
+            .any(|line| line.trim_end() == "# /// script")
+        {
+            return Err(RunError::MultipleBlocks);
+        }
+
+        // Strip the comment prefix from each line, preserving any trailing whitespace, which may be
+        // significant within multi-line strings.
+        let mut toml = String::new();
+        for line in &block[..end] {
+            toml.push_str(line.strip_prefix("# ").unwrap_or_default());
+            toml.push('\n');
+        }
+
+        let metadata = toml::from_str(&toml).map_err(RunError::Toml)?;
+        Ok(Some(metadata))
+    }
+}
+
+/// Run a Python script, installing any dependencies declared in its inline metadata.
+#[allow(clippy::too_many_arguments)]
+async fn run_impl(
+    script: &Path,
+    args: &[OsString],
+    python_request: Option<&str>,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    connectivity: Connectivity,
+    upgrade: bool,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> miette::Result<ExitStatus> {
+    // Read the inline metadata from the script.
+    let contents = fs_err::read_to_string(script)
+        .map_err(|err| RunError::Read(script.normalized_display().to_string(), err))?;
+    let metadata = ScriptMetadata::parse(&contents)?.unwrap_or_default();
+
+    let requirements = metadata
+        .dependencies
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dependency| {
+            Requirement::from_str(&dependency)
+                .map_err(|err| RunError::Requirement(dependency.clone(), err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let requires_python = metadata
+        .requires_python
+        .map(|requires_python| {
+            VersionSpecifiers::from_str(&requires_python)
+                .map_err(|err| RunError::RequiresPython(requires_python.clone(), err))
+        })
+        .transpose()?;
+
+    // Locate the Python interpreter.
+    let platform = Platform::current().into_diagnostic()?;
+    let interpreter = if let Some(python_request) = python_request {
+        find_requested_python(python_request, &platform, cache)
+            .into_diagnostic()?
+            .ok_or(Error::NoSuchPython(python_request.to_string()))
+            .into_diagnostic()?
+    } else {
+        find_default_python(&platform, cache).into_diagnostic()?
+    };
+
+    // Verify that the interpreter satisfies the script's `requires-python`.
+    if let Some(requires_python) = requires_python {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(RunError::IncompatiblePython {
+                requires_python,
+                version: interpreter.python_version().clone(),
+            }
+            .into());
+        }
+    }
+
+    // Create (or reuse) an environment in which the dependencies are installed.
+    let venv = ephemeral_environment(
+        &requirements,
+        interpreter,
+        index_locations,
+        trusted_hosts,
+        connectivity,
+        upgrade,
+        setup_py,
+        no_build,
+        no_binary,
+        exclude_newer,
+        timeouts,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Run the script, forwarding its exit code.
+    debug!(
+        "Running `{}` with: {}",
+        script.normalized_display(),
+        venv.python_executable().normalized_display()
+    );
+    let status = std::process::Command::new(venv.python_executable())
+        .arg(script)
+        .args(args)
+        .env("VIRTUAL_ENV", venv.root())
+        .status()
+        .map_err(|err| RunError::Spawn(script.normalized_display().to_string(), err))?;
+
+    Ok(ExitStatus::from(status))
+}
@@ -0,0 +1,326 @@
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use pep440_rs::Version;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
+use uv_fs::Normalized;
+
+use crate::commands::python::client;
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// The GitHub API endpoint for the latest release of uv.
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/astral-sh/uv/releases/latest";
+
+/// The hex-encoded Ed25519 public key with which release archives are signed, as provided by the
+/// release build.
+///
+/// Builds without a key (e.g., local development builds) can't verify a release, and so refuse to
+/// update themselves.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("UV_RELEASE_PUBLIC_KEY");
+
+/// Update the running uv executable to the latest release, as published by the standalone
+/// installer.
+///
+/// The release archive is verified against both its published SHA-256 digest and its Ed25519
+/// signature, the latter against the public key embedded in the running executable.
+pub(crate) async fn self_update(
+    trusted_hosts: Vec<TrustedHost>,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    if matches!(connectivity, Connectivity::Offline) {
+        bail!("Self-update requires network access, but `--offline` was provided");
+    }
+
+    let Some(public_key) = RELEASE_PUBLIC_KEY.filter(|key| !key.is_empty()) else {
+        bail!(
+            "Self-update is not available for this build of uv, which doesn't embed a key with \
+             which to verify the release"
+        );
+    };
+
+    let current_exe = dunce::canonicalize(std::env::current_exe()?)?;
+    if is_python_package(&current_exe) {
+        bail!(
+            "uv was installed into a Python environment (at: {}); use the tool that installed it \
+             to update it instead (e.g., `pip install --upgrade uv`)",
+            current_exe.normalized_display()
+        );
+    }
+
+    let Some(triple) = target_triple() else {
+        bail!(
+            "Self-update is not available for the current platform: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+    };
+
+    let client = client(cache, trusted_hosts, connectivity, timeouts);
+
+    debug!("Fetching the latest release from: {LATEST_RELEASE_URL}");
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to fetch the latest release")?;
+
+    let current = Version::from_str(env!("CARGO_PKG_VERSION")).map_err(|err| anyhow!(err))?;
+    let latest = Version::from_str(release.tag_name.trim_start_matches('v'))
+        .map_err(|err| anyhow!("Invalid release version `{}`: {err}", release.tag_name))?;
+    if latest <= current {
+        writeln!(
+            printer,
+            "uv is already up-to-date ({})",
+            format!("v{current}").bold()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // e.g., `uv-x86_64-unknown-linux-gnu.tar.gz`, with its digest in a sibling `.sha256` file and
+    // its signature in a sibling `.sig` file.
+    let archive_name = if cfg!(windows) {
+        format!("uv-{triple}.zip")
+    } else {
+        format!("uv-{triple}.tar.gz")
+    };
+    let archive_url = release
+        .asset_url(&archive_name)
+        .ok_or_else(|| anyhow!("The latest release (v{latest}) has no build for {triple}"))?;
+    let sha256_url = release
+        .asset_url(&format!("{archive_name}.sha256"))
+        .ok_or_else(|| anyhow!("The latest release (v{latest}) has no checksum for {triple}"))?;
+    let signature_url = release
+        .asset_url(&format!("{archive_name}.sig"))
+        .ok_or_else(|| anyhow!("The latest release (v{latest}) has no signature for {triple}"))?;
+
+    writeln!(
+        printer,
+        "Downloading uv {} from: {}",
+        format!("v{latest}").bold(),
+        archive_url.dimmed()
+    )?;
+    let archive = client
+        .get(archive_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    // Verify the archive against its published SHA-256 digest before unpacking anything.
+    let expected = client
+        .get(sha256_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let expected = expected.split_whitespace().next().unwrap_or_default();
+    let actual = format!("{:x}", Sha256::digest(&archive));
+    if !actual.eq_ignore_ascii_case(expected) {
+        bail!("Hash mismatch for `{archive_url}`: expected {expected}, found {actual}");
+    }
+
+    // Verify that the archive was signed by the release key, since the digest alone is served from
+    // the same place as the archive.
+    let signature = client
+        .get(signature_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    verify_signature(&archive, signature.trim(), public_key)
+        .with_context(|| format!("Failed to verify the signature of `{archive_url}`"))?;
+
+    let executable = extract_executable(&archive)
+        .with_context(|| format!("Failed to unpack `{archive_name}`"))?;
+    replace_executable(&current_exe, &executable).with_context(|| {
+        format!(
+            "Failed to replace the executable at: {}",
+            current_exe.normalized_display()
+        )
+    })?;
+
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Updated uv from {} to {} in {}",
+            format!("v{current}").bold(),
+            format!("v{latest}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+impl Release {
+    /// Return the download URL of the asset with the given name, if any.
+    fn asset_url(&self, name: &str) -> Option<&str> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .map(|asset| asset.browser_download_url.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Return `true` if the executable was installed as part of the `uv` Python package (e.g., via
+/// `pip install uv`), rather than via the standalone installer.
+///
+/// Python installers record the package in a `uv-{version}.dist-info` directory, within the
+/// `site-packages` directory of the prefix into which the executable was installed (e.g.,
+/// `~/.local/lib/python3.12/site-packages` for `~/.local/bin/uv`).
+fn is_python_package(executable: &Path) -> bool {
+    let Some(prefix) = executable.parent().and_then(Path::parent) else {
+        return false;
+    };
+    let prefix = glob::Pattern::escape(&prefix.to_string_lossy());
+    [
+        // Unix, e.g., `.venv/lib/python3.12/site-packages`.
+        format!("{prefix}/lib/python*/site-packages/uv-*.dist-info"),
+        // Windows, e.g., `.venv\Lib\site-packages`.
+        format!("{prefix}/Lib/site-packages/uv-*.dist-info"),
+        // Windows user installs, e.g., `%APPDATA%\Python\Python312\site-packages`.
+        format!("{prefix}/site-packages/uv-*.dist-info"),
+    ]
+    .iter()
+    .any(|pattern| glob::glob(pattern).is_ok_and(|mut paths| paths.next().is_some()))
+}
+
+/// Verify the hex-encoded Ed25519 signature of the given contents against the hex-encoded public
+/// key.
+fn verify_signature(contents: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let signature = hex::decode(signature).context("The signature is not valid hex")?;
+    let public_key = hex::decode(public_key).context("The public key is not valid hex")?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+        .verify(contents, &signature)
+        .map_err(|_| anyhow!("The signature doesn't match the release key"))
+}
+
+/// Return the target triple of the release build for the current platform, if one is published.
+fn target_triple() -> Option<&'static str> {
+    let musl = cfg!(target_env = "musl");
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") if musl => Some("x86_64-unknown-linux-musl"),
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") if musl => Some("aarch64-unknown-linux-musl"),
+        ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+        ("linux", "x86") if musl => Some("i686-unknown-linux-musl"),
+        ("linux", "x86") => Some("i686-unknown-linux-gnu"),
+        ("linux", "arm") => Some("armv7-unknown-linux-gnueabihf"),
+        ("linux", "powerpc64") if cfg!(target_endian = "little") => {
+            Some("powerpc64le-unknown-linux-gnu")
+        }
+        ("linux", "powerpc64") => Some("powerpc64-unknown-linux-gnu"),
+        ("linux", "s390x") => Some("s390x-unknown-linux-gnu"),
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        ("windows", "x86_64") => Some("x86_64-pc-windows-msvc"),
+        ("windows", "x86") => Some("i686-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+/// Read the uv executable out of a release archive.
+fn extract_executable(archive: &[u8]) -> Result<Vec<u8>> {
+    let name = OsStr::new(if cfg!(windows) { "uv.exe" } else { "uv" });
+    let mut contents = Vec::new();
+
+    if cfg!(windows) {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(archive))?;
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if Path::new(file.name()).file_name() == Some(name) {
+                file.read_to_end(&mut contents)?;
+                return Ok(contents);
+            }
+        }
+    } else {
+        let mut archive = tar::Archive::new(GzDecoder::new(archive));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()?.file_name() == Some(name) {
+                entry.read_to_end(&mut contents)?;
+                return Ok(contents);
+            }
+        }
+    }
+
+    bail!(
+        "The archive doesn't contain a `{}` executable",
+        name.to_string_lossy()
+    )
+}
+
+/// Atomically replace the executable at the given path.
+///
+/// The new executable is written to a temporary file in the same directory, then renamed into
+/// place, such that an interrupted update can't leave a partial executable behind.
+fn replace_executable(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow!("The executable has no parent directory"))?;
+    let mut temp_file = tempfile::NamedTempFile::new_in(dir)?;
+    std::io::Write::write_all(&mut temp_file, contents)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs_err::set_permissions(temp_file.path(), std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // A running executable can't be overwritten on Windows, but it can be renamed out of the way.
+    // If the new executable can't then be moved into place, restore the old one.
+    if cfg!(windows) {
+        let old = path.with_extension("exe.old");
+        if old.exists() {
+            fs_err::remove_file(&old)?;
+        }
+        fs_err::rename(path, &old)?;
+        if let Err(err) = temp_file.persist(path) {
+            fs_err::rename(&old, path)
+                .context("Failed to restore the previous executable after a failed update")?;
+            return Err(err.error.into());
+        }
+        return Ok(());
+    }
+
+    temp_file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
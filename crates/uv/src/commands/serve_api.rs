@@ -0,0 +1,442 @@
+//! A long-running JSON-RPC server, for editor and language-server integrations.
+//!
+//! Requests and responses are exchanged as [JSON-RPC 2.0](https://www.jsonrpc.org/specification)
+//! objects over stdin and stdout, one message per line. The server exits on `shutdown`, or when
+//! stdin is closed.
+//!
+//! The following methods are supported:
+//!
+//! - `resolve`: resolve a set of requirements (`{"requirements": [...], "constraints": [...]}`)
+//!   for the current environment, returning the pinned packages.
+//! - `environment`: describe the current environment, along with its installed packages.
+//! - `outdated`: list the installed packages (or the given `{"packages": [...]}`) for which a newer
+//!   version is available.
+//! - `shutdown`: stop the server.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::StreamExt;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::debug;
+
+use distribution_types::{DistributionMetadata, IndexLocations, Name, Verbatim, VersionOrUrl};
+use pep440_rs::Version;
+use pep508_rs::Requirement;
+use platform_host::Platform;
+use platform_tags::Tags;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, OwnedArchive, RegistryClient,
+    RegistryClientBuilder, TrustedHost,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, SitePackages};
+use uv_interpreter::Virtualenv;
+use uv_normalize::PackageName;
+use uv_resolver::{
+    InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver,
+};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::commands::ExitStatus;
+
+/// The message could not be parsed as JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The message is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The requested method does not exist.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The parameters for the requested method are invalid.
+const INVALID_PARAMS: i64 = -32602;
+/// The request failed with an unexpected error.
+const INTERNAL_ERROR: i64 = -32603;
+/// The requirements could not be resolved, as no solution exists.
+const NO_SOLUTION: i64 = -32000;
+
+/// Serve requests over stdin and stdout until the client sends `shutdown` or closes stdin.
+pub(crate) async fn serve_api(
+    index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    connectivity: Connectivity,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: Cache,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = Virtualenv::from_env(platform, &cache)?;
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    let tags = venv.interpreter().tags()?;
+
+    // Instantiate a client, shared across requests.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .timeouts(timeouts)
+        .trusted_hosts(trusted_hosts)
+        .build();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags)
+    };
+
+    let server = Server {
+        venv: &venv,
+        tags,
+        client: &client,
+        flat_index: &flat_index,
+        index_locations: &index_locations,
+        resolution_mode,
+        prerelease_mode,
+        concurrency,
+        cache: &cache,
+    };
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (response, shutdown) = server.handle(&line).await;
+        if let Some(response) = response {
+            let mut message = serde_json::to_vec(&response)?;
+            message.push(b'\n');
+            stdout.write_all(&message).await?;
+            stdout.flush().await?;
+        }
+
+        if shutdown {
+            debug!("Received shutdown request");
+            break;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The state shared across requests, such that each request avoids re-querying the interpreter and
+/// re-fetching the flat indexes.
+struct Server<'a> {
+    venv: &'a Virtualenv,
+    tags: &'a Tags,
+    client: &'a RegistryClient,
+    flat_index: &'a FlatIndex,
+    index_locations: &'a IndexLocations,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    concurrency: Concurrency,
+    cache: &'a Cache,
+}
+
+impl Server<'_> {
+    /// Handle a single message, returning the response (if any), and whether the server should
+    /// shut down.
+    async fn handle(&self, message: &str) -> (Option<Value>, bool) {
+        let request: Request = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(err) => {
+                let code = if err.is_data() {
+                    INVALID_REQUEST
+                } else {
+                    PARSE_ERROR
+                };
+                return (
+                    Some(RpcError::new(code, err).into_response(Value::Null)),
+                    false,
+                );
+            }
+        };
+
+        debug!("Received request: {}", request.method);
+        let shutdown = request.method == "shutdown";
+        let result = match request.method.as_str() {
+            "resolve" => self.resolve(request.params).await,
+            "environment" => self.environment(),
+            "outdated" => self.outdated(request.params).await,
+            "shutdown" => Ok(Value::Null),
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {method}"),
+            )),
+        };
+
+        // Notifications (i.e., requests without an `id`) don't receive a response.
+        let Some(id) = request.id else {
+            return (None, shutdown);
+        };
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(err) => err.into_response(id),
+        };
+        (Some(response), shutdown)
+    }
+
+    /// Resolve a set of requirements for the current environment.
+    async fn resolve(&self, params: Option<Value>) -> Result<Value, RpcError> {
+        let params: ResolveParams = parse_params(params)?;
+        let requirements = parse_requirements(&params.requirements)?;
+        let constraints = parse_requirements(&params.constraints)?;
+
+        // Use a fresh in-memory index for each request, as a failed resolution may leave it with
+        // incomplete entries.
+        let interpreter = self.venv.interpreter();
+        let index = InMemoryIndex::default();
+        let in_flight = InFlight::default();
+
+        let options = OptionsBuilder::new()
+            .resolution_mode(self.resolution_mode)
+            .prerelease_mode(self.prerelease_mode)
+            .build();
+
+        let build_dispatch = BuildDispatch::new(
+            self.client,
+            self.cache,
+            interpreter,
+            self.index_locations,
+            self.flat_index,
+            &index,
+            &in_flight,
+            interpreter.sys_executable().to_path_buf(),
+            SetupPyStrategy::default(),
+            &NoBuild::None,
+            &NoBinary::None,
+        )
+        .with_options(options)
+        .with_concurrency(self.concurrency);
+
+        let manifest = Manifest::new(
+            requirements,
+            constraints,
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+        );
+
+        let resolver = Resolver::new(
+            manifest,
+            options,
+            interpreter.markers(),
+            interpreter,
+            self.tags,
+            self.client,
+            self.flat_index,
+            &index,
+            &build_dispatch,
+        );
+
+        let resolution = match resolver.resolve().await {
+            Ok(resolution) => resolution,
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                return Err(RpcError::new(
+                    NO_SOLUTION,
+                    format!("No solution found when resolving dependencies: {err}"),
+                ));
+            }
+            Err(err) => return Err(anyhow::Error::from(err).into()),
+        };
+
+        let packages = resolution
+            .petgraph()
+            .node_weights()
+            .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+            .map(|dist| match dist.version_or_url() {
+                VersionOrUrl::Version(version) => json!({
+                    "name": dist.name().to_string(),
+                    "version": version.to_string(),
+                }),
+                VersionOrUrl::Url(url) => json!({
+                    "name": dist.name().to_string(),
+                    "url": url.verbatim(),
+                }),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "packages": packages }))
+    }
+
+    /// Describe the current environment, along with its installed packages.
+    fn environment(&self) -> Result<Value, RpcError> {
+        // Read the installed packages on each request, as the environment may have changed.
+        let site_packages = SitePackages::from_executable(self.venv)?;
+
+        let packages = site_packages
+            .iter()
+            .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+            .map(|dist| {
+                json!({
+                    "name": dist.name().to_string(),
+                    "version": dist.version().to_string(),
+                    "editable": dist.as_editable().is_some(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({
+            "root": self.venv.root(),
+            "python": {
+                "version": self.venv.interpreter().python_version().to_string(),
+                "executable": self.venv.python_executable(),
+            },
+            "packages": packages,
+        }))
+    }
+
+    /// List the installed packages for which a newer version is available in the index.
+    async fn outdated(&self, params: Option<Value>) -> Result<Value, RpcError> {
+        let params: OutdatedParams = parse_params(params)?;
+        let site_packages = SitePackages::from_executable(self.venv)?;
+
+        // Editable installs don't come from the index, so there's nothing to compare against.
+        let installed = site_packages
+            .iter()
+            .filter(|dist| dist.as_editable().is_none())
+            .filter(|dist| params.packages.is_empty() || params.packages.contains(dist.name()))
+            .map(|dist| (dist.name().clone(), dist.version().clone()))
+            .collect::<Vec<_>>();
+
+        let packages = futures::stream::iter(installed)
+            .map(|(name, installed)| async move {
+                let latest = self.latest_version(&name, &installed).await?;
+                (latest > installed).then_some((name, installed, latest))
+            })
+            .buffer_unordered(self.concurrency.downloads)
+            .filter_map(|outdated| async move { outdated })
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .sorted_unstable_by(|(a, ..), (b, ..)| a.cmp(b))
+            .map(|(name, installed, latest)| {
+                json!({
+                    "name": name.to_string(),
+                    "installed": installed.to_string(),
+                    "latest": latest.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "packages": packages }))
+    }
+
+    /// Find the latest version of a package in the index, excluding pre-releases unless the
+    /// installed version is itself a pre-release.
+    async fn latest_version(&self, name: &PackageName, installed: &Version) -> Option<Version> {
+        let results = match self.client.simple(name).await {
+            Ok(results) => results,
+            Err(err) => {
+                debug!("Failed to fetch available versions for {name}: {err}");
+                return None;
+            }
+        };
+        results
+            .iter()
+            .filter_map(|(_, raw_metadata)| {
+                let metadata = OwnedArchive::deserialize(raw_metadata);
+                metadata
+                    .into_iter()
+                    .rev()
+                    .map(|datum| datum.version)
+                    .find(|version| !version.any_prerelease() || installed.any_prerelease())
+            })
+            .max()
+    }
+}
+
+/// A JSON-RPC request (or notification, if the `id` is omitted).
+#[derive(Debug, Deserialize)]
+struct Request {
+    id: Option<Value>,
+    method: String,
+    params: Option<Value>,
+}
+
+/// The parameters to the `resolve` method.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResolveParams {
+    #[serde(default)]
+    requirements: Vec<String>,
+    #[serde(default)]
+    constraints: Vec<String>,
+}
+
+/// The parameters to the `outdated` method.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutdatedParams {
+    #[serde(default)]
+    packages: Vec<PackageName>,
+}
+
+/// A JSON-RPC error, returned in lieu of a result.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    fn into_response(self, id: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": self.code, "message": self.message },
+        })
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::new(INTERNAL_ERROR, format!("{err:#}"))
+    }
+}
+
+/// Parse the parameters to a method, which may be omitted entirely.
+fn parse_params<T: DeserializeOwned + Default>(params: Option<Value>) -> Result<T, RpcError> {
+    match params {
+        None | Some(Value::Null) => Ok(T::default()),
+        Some(params) => serde_json::from_value(params)
+            .map_err(|err| RpcError::new(INVALID_PARAMS, format!("Invalid params: {err}"))),
+    }
+}
+
+/// Parse a list of PEP 508 requirements.
+fn parse_requirements(requirements: &[String]) -> Result<Vec<Requirement>, RpcError> {
+    requirements
+        .iter()
+        .map(|requirement| {
+            Requirement::from_str(requirement).map_err(|err| {
+                RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Invalid requirement `{requirement}`: {err}"),
+                )
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use futures::future::join_all;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::Shebang;
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, Reinstall};
+use uv_interpreter::{find_default_python, find_requested_python, Virtualenv};
+use uv_traits::{Concurrency, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::commands::pip_sync::{report_changes, sync_environment};
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{RequirementsSource, RequirementsSpecification};
+use crate::workspace::Workspace;
+
+/// Sync every environment declared in the `pyproject.toml` of the current directory, concurrently.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn sync_all(
+    only: &[String],
+    link_mode: LinkMode,
+    index_locations: IndexLocations,
+    trusted_hosts: Vec<TrustedHost>,
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Discover the environments to sync.
+    let root = std::env::current_dir()?;
+    let mut environments = EnvironmentSpec::discover(&root)?;
+    if !only.is_empty() {
+        for name in only {
+            if !environments
+                .iter()
+                .any(|environment| environment.name == *name)
+            {
+                bail!("No environment named `{name}` was found");
+            }
+        }
+        environments.retain(|environment| only.contains(&environment.name));
+    }
+    if environments.is_empty() {
+        writeln!(printer, "No environments found")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Create (or locate) each virtual environment, and read its requirements.
+    let platform = Platform::current()?;
+    let mut prepared = Vec::with_capacity(environments.len());
+    for environment in environments {
+        let venv = if environment.venv.join("pyvenv.cfg").is_file() {
+            Virtualenv::from_path(&environment.venv, platform.clone(), &cache)?
+        } else {
+            let interpreter = if let Some(python) = environment.python.as_deref() {
+                find_requested_python(python, &platform, &cache)?
+                    .with_context(|| format!("No Python interpreter found for `{python}`"))?
+            } else {
+                find_default_python(&platform, &cache)?
+            };
+            debug!(
+                "Creating environment `{}` at: {}",
+                environment.name,
+                environment.venv.normalized_display()
+            );
+            gourgeist::create_venv(&environment.venv, interpreter, None, false, false)
+                .with_context(|| format!("Failed to create environment `{}`", environment.name))?
+        };
+
+        let sources = environment
+            .requirements
+            .iter()
+            .cloned()
+            .map(RequirementsSource::from_path)
+            .collect::<Vec<_>>();
+        let specification = RequirementsSpecification::from_simple_sources(&sources)?;
+
+        prepared.push((environment.name, venv, specification));
+    }
+
+    // Share in-flight downloads and builds across environments with the same base interpreter.
+    let mut in_flight: FxHashMap<PathBuf, InFlight> = FxHashMap::default();
+    for (_, venv, _) in &prepared {
+        in_flight
+            .entry(venv.interpreter().base_exec_prefix().to_path_buf())
+            .or_default();
+    }
+
+    // Render a single progress bar per environment.
+    let multi_progress = MultiProgress::with_draw_target(printer.target());
+    let style =
+        ProgressStyle::with_template("{spinner:.dim} {prefix:.bold} {wide_msg:.dim}").unwrap();
+
+    let cache = &cache;
+    let trusted_hosts = &trusted_hosts;
+    let results = join_all(prepared.iter().map(|(name, venv, specification)| {
+        let progress = multi_progress.add(ProgressBar::with_draw_target(None, printer.target()));
+        progress.set_style(style.clone());
+        progress.set_prefix(name.clone());
+        progress.set_message("Syncing...");
+        progress.enable_steady_tick(Duration::from_millis(100));
+
+        let in_flight = &in_flight[venv.interpreter().base_exec_prefix()];
+        let index_locations = index_locations.clone().combine(
+            specification.index_url.clone(),
+            specification.extra_index_urls.clone(),
+            specification.find_links.clone(),
+            specification.no_index,
+        );
+        async move {
+            let result = async {
+                let _lock = venv.lock()?;
+                sync_environment(
+                    venv,
+                    &specification.requirements,
+                    specification.editables.clone(),
+                    &Reinstall::None,
+                    true,
+                    false,
+                    link_mode,
+                    &Shebang::default(),
+                    &index_locations,
+                    trusted_hosts,
+                    setup_py,
+                    connectivity,
+                    no_build,
+                    no_binary,
+                    false,
+                    false,
+                    in_flight,
+                    None,
+                    false,
+                    None,
+                    timeouts,
+                    concurrency,
+                    cache,
+                    Printer::Quiet,
+                )
+                .await
+                .map(|outcome| outcome.changes)
+            }
+            .await
+            .with_context(|| format!("Failed to sync environment `{name}`"));
+            progress.finish_and_clear();
+            result
+        }
+    }))
+    .await;
+
+    // Report on the changes in each environment.
+    let mut failed = false;
+    for ((name, _, _), result) in prepared.iter().zip(results) {
+        match result {
+            Ok(changes) => {
+                writeln!(printer, "{}", name.bold())?;
+                report_changes(changes, printer)?;
+            }
+            Err(err) => {
+                failed = true;
+                writeln!(printer, "{}{} {err:#}", "error".red().bold(), ":".bold())?;
+            }
+        }
+    }
+
+    let s = if prepared.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Synced {} in {}",
+            format!("{} environment{s}", prepared.len()).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if failed {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// A single environment to sync, along with the requirements files that define it.
+#[derive(Debug)]
+struct EnvironmentSpec {
+    /// The name of the environment, as shown to the user.
+    name: String,
+    /// The root of the virtual environment.
+    venv: PathBuf,
+    /// The `requirements.txt` files to sync into the environment.
+    requirements: Vec<PathBuf>,
+    /// The Python interpreter to use when creating the environment.
+    python: Option<String>,
+}
+
+impl EnvironmentSpec {
+    /// Discover the environments declared in the `pyproject.toml` at the given root.
+    ///
+    /// Environments are read from the `[tool.uv.envs]` table, along with any members of the
+    /// `[tool.uv.workspace]` table that contain a `requirements.txt` file.
+    fn discover(root: &Path) -> Result<Vec<Self>> {
+        let path = root.join("pyproject.toml");
+        let contents = fs_err::read_to_string(&path)?;
+        let pyproject: PyProjectToml = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+
+        let mut environments = Vec::new();
+
+        // Read the explicitly declared environments.
+        for (name, environment) in pyproject
+            .tool
+            .and_then(|tool| tool.uv)
+            .and_then(|uv| uv.envs)
+            .unwrap_or_default()
+        {
+            environments.push(Self {
+                venv: root.join(
+                    environment
+                        .venv
+                        .unwrap_or_else(|| PathBuf::from(".venvs").join(&name)),
+                ),
+                requirements: environment
+                    .requirements
+                    .into_iter()
+                    .map(|requirements| root.join(requirements))
+                    .collect(),
+                python: environment.python,
+                name,
+            });
+        }
+
+        // Read the workspace members, each of which is synced into its own `.venv`.
+        if let Some(workspace) = Workspace::from_pyproject_toml(&path, &contents)? {
+            for member in workspace.members() {
+                let requirements = member.join("requirements.txt");
+                if !requirements.is_file() {
+                    debug!(
+                        "Skipping workspace member without a `requirements.txt`: {}",
+                        member.normalized_display()
+                    );
+                    continue;
+                }
+                let name = member
+                    .strip_prefix(workspace.root())
+                    .unwrap_or(member)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                environments.push(Self {
+                    name,
+                    venv: member.join(".venv"),
+                    requirements: vec![requirements],
+                    python: None,
+                });
+            }
+        }
+
+        // Reject duplicate environment names.
+        let mut seen = FxHashSet::default();
+        for environment in &environments {
+            if !seen.insert(&environment.name) {
+                bail!(
+                    "Environment `{}` is declared more than once",
+                    environment.name
+                );
+            }
+        }
+
+        Ok(environments)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PyProjectToml {
+    tool: Option<Tool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tool {
+    uv: Option<ToolUv>,
+}
+
+/// The `[tool.uv]` table.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ToolUv {
+    envs: Option<BTreeMap<String, EnvironmentOptions>>,
+}
+
+/// A single entry in the `[tool.uv.envs]` table.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EnvironmentOptions {
+    requirements: Vec<PathBuf>,
+    venv: Option<PathBuf>,
+    python: Option<String>,
+}
@@ -0,0 +1,211 @@
+use std::env::consts::EXE_SUFFIX;
+use std::ffi::OsString;
+use std::str::FromStr;
+
+use anstream::eprint;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use miette::{Diagnostic, IntoDiagnostic};
+use thiserror::Error;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::parse_scripts;
+use pep508_rs::Requirement;
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, SitePackages};
+use uv_interpreter::{find_default_python, find_requested_python, Error};
+use uv_traits::{Concurrency, NoBuild, SetupPyStrategy};
+
+use crate::commands::ephemeral::ephemeral_environment;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Run a command provided by a Python package in an ephemeral, cached environment.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn tool_run(
+    command: &str,
+    args: &[OsString],
+    from: Option<&str>,
+    python_request: Option<&str>,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    connectivity: Connectivity,
+    upgrade: bool,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    match tool_run_impl(
+        command,
+        args,
+        from,
+        python_request,
+        index_locations,
+        trusted_hosts,
+        connectivity,
+        upgrade,
+        setup_py,
+        no_build,
+        no_binary,
+        exclude_newer,
+        timeouts,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await
+    {
+        Ok(status) => Ok(status),
+        Err(err) => {
+            eprint!("{err:?}");
+            Ok(ExitStatus::Failure)
+        }
+    }
+}
+
+#[derive(Error, Debug, Diagnostic)]
+enum ToolError {
+    #[error("Failed to parse tool requirement: `{0}`")]
+    #[diagnostic(code(uv::tool::requirement))]
+    Requirement(String, #[source] pep508_rs::Pep508Error),
+
+    #[error("Failed to read the entrypoints of `{0}`")]
+    #[diagnostic(code(uv::tool::entrypoints))]
+    EntryPoints(String, #[source] install_wheel_rs::Error),
+
+    #[error("The package `{0}` does not provide any commands")]
+    #[diagnostic(code(uv::tool::no_commands))]
+    NoCommands(String),
+
+    #[error("The package `{package}` does not provide a `{command}` command")]
+    #[diagnostic(
+        code(uv::tool::missing_command),
+        help("The following commands are available: {available}")
+    )]
+    MissingCommand {
+        package: String,
+        command: String,
+        available: String,
+    },
+
+    #[error("Failed to read installed packages")]
+    #[diagnostic(code(uv::tool::site_packages))]
+    SitePackages(#[source] anyhow::Error),
+
+    #[error("Failed to run `{0}`")]
+    #[diagnostic(code(uv::tool::spawn))]
+    Spawn(String, #[source] std::io::Error),
+}
+
+/// Run a command provided by a Python package in an ephemeral, cached environment.
+#[allow(clippy::too_many_arguments)]
+async fn tool_run_impl(
+    command: &str,
+    args: &[OsString],
+    from: Option<&str>,
+    python_request: Option<&str>,
+    index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
+    connectivity: Connectivity,
+    upgrade: bool,
+    setup_py: SetupPyStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> miette::Result<ExitStatus> {
+    // Determine the package that provides the command. By default, the command and the package are
+    // assumed to share a name.
+    let source = from.unwrap_or(command);
+    let requirement = Requirement::from_str(source)
+        .map_err(|err| ToolError::Requirement(source.to_string(), err))?;
+
+    // Locate the Python interpreter.
+    let platform = Platform::current().into_diagnostic()?;
+    let interpreter = if let Some(python_request) = python_request {
+        find_requested_python(python_request, &platform, cache)
+            .into_diagnostic()?
+            .ok_or(Error::NoSuchPython(python_request.to_string()))
+            .into_diagnostic()?
+    } else {
+        find_default_python(&platform, cache).into_diagnostic()?
+    };
+
+    // Create (or reuse) an environment in which the tool is installed.
+    let venv = ephemeral_environment(
+        &[requirement.clone()],
+        interpreter,
+        index_locations,
+        trusted_hosts,
+        connectivity,
+        upgrade,
+        setup_py,
+        no_build,
+        no_binary,
+        exclude_newer,
+        timeouts,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Discover the console scripts provided by the tool.
+    let site_packages = SitePackages::from_executable(&venv).map_err(ToolError::SitePackages)?;
+    let dist = site_packages
+        .get(&requirement.name)
+        .ok_or_else(|| ToolError::NoCommands(requirement.name.to_string()))?;
+    let dist_info_prefix = dist
+        .path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (console_scripts, _gui_scripts) =
+        parse_scripts(venv.site_packages(), &dist_info_prefix, None)
+            .map_err(|err| ToolError::EntryPoints(requirement.name.to_string(), err))?;
+
+    if console_scripts.is_empty() {
+        return Err(ToolError::NoCommands(requirement.name.to_string()).into());
+    }
+    let Some(script) = console_scripts
+        .iter()
+        .find(|script| script.script_name == command)
+    else {
+        return Err(ToolError::MissingCommand {
+            package: requirement.name.to_string(),
+            command: command.to_string(),
+            available: console_scripts
+                .iter()
+                .map(|script| format!("`{}`", script.script_name))
+                .sorted()
+                .join(", "),
+        }
+        .into());
+    };
+
+    // Run the command, forwarding its exit code.
+    let executable = venv
+        .bin_dir()
+        .join(format!("{}{EXE_SUFFIX}", script.script_name));
+    debug!("Running: {}", executable.normalized_display());
+    let status = std::process::Command::new(&executable)
+        .args(args)
+        .env("VIRTUAL_ENV", venv.root())
+        .status()
+        .map_err(|err| ToolError::Spawn(command.to_string(), err))?;
+
+    Ok(ExitStatus::from(status))
+}
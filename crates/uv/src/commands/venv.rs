@@ -1,8 +1,9 @@
 use std::fmt::Write;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anstream::eprint;
+use anstream::{eprint, println};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use miette::{Diagnostic, IntoDiagnostic};
@@ -12,14 +13,16 @@ use thiserror::Error;
 use distribution_types::{DistributionMetadata, IndexLocations, Name};
 use pep508_rs::Requirement;
 use platform_host::Platform;
-use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_cache::{Cache, CacheBucket};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, HttpTimeouts, RegistryClientBuilder, TrustedHost,
+};
 use uv_dispatch::BuildDispatch;
-use uv_fs::Normalized;
+use uv_fs::{LockedFile, Normalized};
 use uv_installer::NoBinary;
 use uv_interpreter::{find_default_python, find_requested_python, Error};
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
-use uv_traits::{BuildContext, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, InFlight, NoBuild, SetupPyStrategy};
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
@@ -30,9 +33,15 @@ pub(crate) async fn venv(
     path: &Path,
     python_request: Option<&str>,
     index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
     connectivity: Connectivity,
     seed: bool,
+    prompt: Option<&str>,
+    system_site_packages: bool,
+    relocatable: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -40,9 +49,15 @@ pub(crate) async fn venv(
         path,
         python_request,
         index_locations,
+        trusted_hosts,
         connectivity,
         seed,
+        prompt,
+        system_site_packages,
+        relocatable,
         exclude_newer,
+        timeouts,
+        concurrency,
         cache,
         printer,
     )
@@ -73,6 +88,10 @@ enum VenvError {
     #[error("Failed to resolve `--find-links` entry")]
     #[diagnostic(code(uv::venv::flat_index))]
     FlatIndex(#[source] uv_client::FlatIndexError),
+
+    #[error("Failed to lock virtualenv")]
+    #[diagnostic(code(uv::venv::lock))]
+    Lock(#[source] std::io::Error),
 }
 
 /// Create a virtual environment.
@@ -81,9 +100,15 @@ async fn venv_impl(
     path: &Path,
     python_request: Option<&str>,
     index_locations: &IndexLocations,
+    trusted_hosts: &[TrustedHost],
     connectivity: Connectivity,
     seed: bool,
+    prompt: Option<&str>,
+    system_site_packages: bool,
+    relocatable: bool,
     exclude_newer: Option<DateTime<Utc>>,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
     cache: &Cache,
     mut printer: Printer,
 ) -> miette::Result<ExitStatus> {
@@ -113,17 +138,41 @@ async fn venv_impl(
     )
     .into_diagnostic()?;
 
+    // Prevent concurrent `uv venv` invocations from replacing or seeding the virtualenv at the
+    // same path. The lock lives in the cache, keyed by the path, rather than within the
+    // virtualenv, such that it can be held while the virtualenv is removed and recreated.
+    let key = venv_key(path).map_err(VenvError::Lock)?;
+    let _lock = cache
+        .lock_entry(&cache.entry(CacheBucket::Environments, "venvs", cache_key::digest(&key)))
+        .map_err(VenvError::Lock)?;
+
+    // Wait for any in-progress operation on an existing virtualenv at the same path (e.g., a
+    // concurrent `pip sync`) to finish before replacing it. The lock is released before the
+    // virtualenv is removed, since locked files can't be removed on all platforms.
+    if path.join("pyvenv.cfg").is_file() {
+        drop(
+            LockedFile::acquire(path.join(".lock"), path.normalized_display())
+                .map_err(VenvError::Lock)?,
+        );
+    }
+
     // Create the virtual environment.
-    let venv = gourgeist::create_venv(path, interpreter).map_err(VenvError::Creation)?;
+    let venv = gourgeist::create_venv(path, interpreter, prompt, system_site_packages, relocatable)
+        .map_err(VenvError::Creation)?;
 
     // Install seed packages.
     if seed {
+        // Prevent concurrent writes to the virtualenv while seeding it.
+        let _venv_lock = venv.lock().map_err(VenvError::Lock)?;
+
         // Extract the interpreter.
         let interpreter = venv.interpreter();
 
         // Instantiate a client.
         let client = RegistryClientBuilder::new(cache.clone())
             .connectivity(connectivity)
+            .timeouts(timeouts)
+            .trusted_hosts(trusted_hosts.to_vec())
             .build();
 
         // Resolve the flat indexes from `--find-links`.
@@ -158,7 +207,8 @@ async fn venv_impl(
             &NoBuild::All,
             &NoBinary::None,
         )
-        .with_options(options);
+        .with_options(options)
+        .with_concurrency(concurrency);
 
         // Resolve the seed packages.
         let resolution = build_dispatch
@@ -188,5 +238,22 @@ async fn venv_impl(
         }
     }
 
+    // In quiet mode, print the path to the environment, such that it can be consumed by scripts.
+    if printer == Printer::Quiet {
+        println!("{}", path.normalized_display());
+    }
+
     Ok(ExitStatus::Success)
 }
+
+/// Return the absolute path to the virtualenv at the given path, with any symlinks in its parent
+/// directory resolved, such that every spelling of the path maps to the same lock.
+fn venv_key(path: &Path) -> io::Result<PathBuf> {
+    let absolute = std::env::current_dir()?.join(path);
+    if let (Some(parent), Some(name)) = (absolute.parent(), absolute.file_name()) {
+        if let Ok(parent) = fs_err::canonicalize(parent) {
+            return Ok(parent.join(name));
+        }
+    }
+    Ok(absolute)
+}
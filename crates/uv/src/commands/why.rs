@@ -0,0 +1,203 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use distribution_types::Name;
+use pep508_rs::{MarkerEnvironment, Requirement};
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_installer::SitePackages;
+use uv_interpreter::Virtualenv;
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Explain why a package is installed in the current environment, by printing each chain of
+/// requirements from a top-level package down to it.
+///
+/// The dependency graph is reconstructed from the `METADATA` of the installed distributions, so
+/// no resolution (or network access) is required.
+pub(crate) fn why(
+    package: &PackageName,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = Virtualenv::from_env(platform, cache)?;
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let Some(target) = site_packages.get(package) else {
+        bail!("`{package}` is not installed");
+    };
+
+    // Read the declared requirements of each installed package.
+    let requires_dist = site_packages
+        .iter()
+        .filter_map(|dist| match dist.metadata() {
+            Ok(metadata) => Some((dist.name().clone(), metadata.requires_dist)),
+            Err(err) => {
+                debug!("Failed to read metadata for {}: {err}", dist.name());
+                None
+            }
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    let dependents = dependents(&requires_dist, &site_packages, venv.interpreter().markers());
+
+    let mut chains = Vec::new();
+    collect_chains(
+        &dependents,
+        target.name(),
+        &mut vec![target.name()],
+        &mut Vec::new(),
+        &mut chains,
+    );
+
+    if chains.is_empty() {
+        writeln!(
+            printer,
+            "{} is not required by any installed package",
+            format!("{}=={}", target.name(), target.version()).bold()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Print each chain from the top-level package down, e.g.:
+    // `flask==3.0.0 -> jinja2==3.1.2 [jinja2>=3.1.2] -> markupsafe==2.1.3 [markupsafe>=2.0]`
+    let mut lines = chains
+        .into_iter()
+        .map(|chain| {
+            let mut line = String::new();
+            for (index, (dependent, requirement)) in chain.iter().rev().enumerate() {
+                if index == 0 {
+                    let dist = site_packages
+                        .get(dependent)
+                        .expect("Dependent to be installed");
+                    write!(line, "{}=={}", dist.name(), dist.version())?;
+                }
+                let dist = site_packages
+                    .get(&requirement.name)
+                    .expect("Dependency to be installed");
+                write!(
+                    line,
+                    " -> {}=={} {}",
+                    dist.name(),
+                    dist.version(),
+                    format!("[{requirement}]").dimmed()
+                )?;
+            }
+            Ok(line)
+        })
+        .collect::<Result<Vec<_>, std::fmt::Error>>()?;
+    lines.sort();
+    for line in lines {
+        println!("{line}");
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// An edge in the installed dependency graph: a dependent, along with its requirement on the
+/// dependency.
+type Edge<'a> = (&'a PackageName, &'a Requirement);
+
+/// Build the reverse dependency graph of the environment, mapping each installed package to the
+/// requirements on it.
+fn dependents<'a>(
+    requires_dist: &'a FxHashMap<PackageName, Vec<Requirement>>,
+    site_packages: &SitePackages,
+    markers: &MarkerEnvironment,
+) -> FxHashMap<&'a PackageName, Vec<Edge<'a>>> {
+    // Determine the extras that are requested of each package, iterating to a fixed point, as
+    // enabling an extra can request extras of other packages.
+    let mut extras: FxHashMap<&PackageName, FxHashSet<ExtraName>> = FxHashMap::default();
+    loop {
+        let mut changed = false;
+        for (name, requirements) in requires_dist {
+            let enabled = enabled_extras(&extras, name);
+            for requirement in requirements {
+                if !requirement.evaluate_markers(markers, &enabled) {
+                    continue;
+                }
+                let requested = extras.entry(&requirement.name).or_default();
+                for extra in &requirement.extras {
+                    changed |= requested.insert(extra.clone());
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut dependents: FxHashMap<&PackageName, Vec<Edge>> = FxHashMap::default();
+    for (name, requirements) in requires_dist {
+        let enabled = enabled_extras(&extras, name);
+        for requirement in requirements {
+            if !requirement.evaluate_markers(markers, &enabled) {
+                continue;
+            }
+            if site_packages.get(&requirement.name).is_none() {
+                continue;
+            }
+            dependents
+                .entry(&requirement.name)
+                .or_default()
+                .push((name, requirement));
+        }
+    }
+    dependents
+}
+
+/// Return the extras that are requested of the given package.
+fn enabled_extras(
+    extras: &FxHashMap<&PackageName, FxHashSet<ExtraName>>,
+    name: &PackageName,
+) -> Vec<ExtraName> {
+    extras
+        .get(name)
+        .map(|extras| extras.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Walk up from `package` to each of its dependents, collecting every chain of edges (in reverse)
+/// that ends at a top-level package. Cycles are skipped.
+fn collect_chains<'a>(
+    dependents: &FxHashMap<&'a PackageName, Vec<Edge<'a>>>,
+    package: &'a PackageName,
+    path: &mut Vec<&'a PackageName>,
+    edges: &mut Vec<Edge<'a>>,
+    chains: &mut Vec<Vec<Edge<'a>>>,
+) {
+    let parents = dependents
+        .get(package)
+        .into_iter()
+        .flatten()
+        .filter(|(dependent, _)| !path.contains(dependent))
+        .collect::<Vec<_>>();
+    if parents.is_empty() {
+        if !edges.is_empty() {
+            chains.push(edges.clone());
+        }
+        return;
+    }
+    for &(dependent, requirement) in parents {
+        path.push(dependent);
+        edges.push((dependent, requirement));
+        collect_chains(dependents, dependent, path, edges, chains);
+        edges.pop();
+        path.pop();
+    }
+}
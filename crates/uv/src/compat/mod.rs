@@ -10,17 +10,11 @@ pub(crate) trait CompatArgs {
 /// Arguments for `pip-compile` compatibility.
 ///
 /// These represent a subset of the `pip-compile` interface that uv supports by default.
-/// For example, users often pass `--allow-unsafe`, which is unnecessary with uv. But it's a
-/// nice user experience to warn, rather than fail, when users pass `--allow-unsafe`.
+/// For example, users often pass `--build-isolation`, which is unnecessary with uv. But it's a
+/// nice user experience to warn, rather than fail, when users pass `--build-isolation`.
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipCompileCompatArgs {
-    #[clap(long, hide = true)]
-    allow_unsafe: bool,
-
-    #[clap(long, hide = true)]
-    no_allow_unsafe: bool,
-
     #[clap(long, hide = true)]
     reuse_hashes: bool,
 
@@ -48,18 +42,12 @@ pub(crate) struct PipCompileCompatArgs {
     #[clap(long, hide = true)]
     client_cert: Option<String>,
 
-    #[clap(long, hide = true)]
-    trusted_host: Option<String>,
-
     #[clap(long, hide = true)]
     emit_trusted_host: bool,
 
     #[clap(long, hide = true)]
     no_emit_trusted_host: bool,
 
-    #[clap(long, hide = true)]
-    unsafe_package: Vec<String>,
-
     #[clap(long, hide = true)]
     config: Option<String>,
 
@@ -95,16 +83,6 @@ impl CompatArgs for PipCompileCompatArgs {
     /// behavior. If an argument is passed that does _not_ match uv's behavior (e.g.,
     /// `--no-build-isolation`), this method will return an error.
     fn validate(&self) -> Result<()> {
-        if self.allow_unsafe {
-            warn_user!(
-                "pip-compile's `--allow-unsafe` has no effect (uv can safely pin `pip` and other packages)."
-            );
-        }
-
-        if self.no_allow_unsafe {
-            warn_user!("pip-compile's `--no-allow-unsafe` has no effect (uv can safely pin `pip` and other packages).");
-        }
-
         if self.reuse_hashes {
             return Err(anyhow!(
                 "pip-compile's `--reuse-hashes` is unsupported (uv doesn't reuse hashes)."
@@ -171,15 +149,9 @@ impl CompatArgs for PipCompileCompatArgs {
             ));
         }
 
-        if self.trusted_host.is_some() {
-            return Err(anyhow!(
-                "pip-compile's `--trusted-host` is unsupported (uv always requires HTTPS)."
-            ));
-        }
-
         if self.emit_trusted_host {
             return Err(anyhow!(
-                "pip-compile's `--emit-trusted-host` is unsupported (uv always requires HTTPS)."
+                "pip-compile's `--emit-trusted-host` is unsupported (uv never emits trusted hosts)."
             ));
         }
 
@@ -189,12 +161,6 @@ impl CompatArgs for PipCompileCompatArgs {
             );
         }
 
-        if !self.unsafe_package.is_empty() {
-            return Err(anyhow!(
-                "pip-compile's `--unsafe-package` is not supported."
-            ));
-        }
-
         if self.config.is_some() {
             return Err(anyhow!(
                 "pip-compile's `--config` is unsupported (uv does not use a configuration file)."
@@ -258,9 +224,6 @@ pub(crate) struct PipSyncCompatArgs {
     #[clap(short, long, hide = true)]
     ask: bool,
 
-    #[clap(long, hide = true)]
-    trusted_host: Option<String>,
-
     #[clap(long, hide = true)]
     python_executable: Option<String>,
 
@@ -312,12 +275,6 @@ impl CompatArgs for PipSyncCompatArgs {
             ));
         }
 
-        if self.trusted_host.is_some() {
-            return Err(anyhow!(
-                "pip-sync's `--trusted-host` is unsupported (uv always requires HTTPS)."
-            ));
-        }
-
         if self.config.is_some() {
             return Err(anyhow!(
                 "pip-sync's `--config` is unsupported (uv does not use a configuration file)."
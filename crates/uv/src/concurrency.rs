@@ -0,0 +1,208 @@
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use tracing::debug;
+
+use uv_traits::Concurrency;
+
+/// The amount of memory to budget for each concurrent source distribution build.
+const MEMORY_PER_BUILD: u64 = 1024 * 1024 * 1024;
+
+/// Below this amount of available memory, downloads are throttled to avoid buffering too many
+/// responses at once.
+const LOW_MEMORY: u64 = 2 * 1024 * 1024 * 1024;
+
+/// The size of the file written when probing the disk throughput of the cache directory.
+const PROBE_SIZE: usize = 1024 * 1024;
+
+/// If writing the probe takes longer than this, the disk is considered slow, and installs are
+/// throttled to avoid thrashing it.
+const SLOW_PROBE: Duration = Duration::from_millis(100);
+
+/// The number of threads in the global install pool, once it's been sized.
+static INSTALL_POOL: OnceLock<usize> = OnceLock::new();
+
+#[derive(Args, Debug, Clone, Default)]
+pub(crate) struct ConcurrencyArgs {
+    /// The maximum number of concurrent downloads.
+    ///
+    /// Defaults to a value derived from the number of available CPUs and the available memory.
+    #[arg(global = true, long, env = "UV_CONCURRENT_DOWNLOADS")]
+    concurrent_downloads: Option<NonZeroUsize>,
+
+    /// The maximum number of source distributions to build concurrently.
+    ///
+    /// Defaults to a value derived from the number of available CPUs and the available memory.
+    #[arg(global = true, long, env = "UV_CONCURRENT_BUILDS")]
+    concurrent_builds: Option<NonZeroUsize>,
+
+    /// The maximum number of threads to use when unzipping and installing wheels.
+    ///
+    /// Defaults to a value derived from the number of available CPUs and the throughput of the
+    /// cache directory's disk.
+    #[arg(global = true, long, env = "UV_CONCURRENT_INSTALLS")]
+    concurrent_installs: Option<NonZeroUsize>,
+}
+
+impl ConcurrencyArgs {
+    /// Determine the concurrency limits for the current system, preferring any explicit limits
+    /// provided by the user.
+    ///
+    /// An explicit install limit sizes the global install pool immediately. Otherwise, the pool
+    /// is sized by [`size_install_pool`] once an install is actually planned, such that the disk
+    /// is only probed when wheels are about to be unzipped and installed.
+    pub(crate) fn detect(&self) -> Result<Concurrency, rayon::ThreadPoolBuildError> {
+        let threads = Concurrency::threads();
+        let memory = available_memory();
+        debug!(
+            "Detected {threads} available thread(s) and {} of available memory",
+            memory.map_or_else(
+                || "an unknown amount".to_string(),
+                |memory| format!("{} MiB", memory / 1024 / 1024)
+            )
+        );
+
+        let downloads = self.concurrent_downloads.map_or_else(
+            || {
+                let downloads = (threads * 8).clamp(8, Concurrency::DEFAULT_DOWNLOADS);
+                if memory.is_some_and(|memory| memory < LOW_MEMORY) {
+                    downloads.min(16)
+                } else {
+                    downloads
+                }
+            },
+            NonZeroUsize::get,
+        );
+
+        let builds = self.concurrent_builds.map_or_else(
+            || {
+                memory.map_or(threads, |memory| {
+                    let builds = usize::try_from(memory / MEMORY_PER_BUILD).unwrap_or(usize::MAX);
+                    threads.min(builds.max(1))
+                })
+            },
+            NonZeroUsize::get,
+        );
+
+        let installs = self.concurrent_installs.map_or(threads, NonZeroUsize::get);
+
+        let concurrency = Concurrency {
+            downloads,
+            builds,
+            installs,
+        };
+        debug!(
+            "Using up to {} concurrent download(s), {} concurrent build(s), and {} install thread(s)",
+            concurrency.downloads, concurrency.builds, concurrency.installs
+        );
+
+        if self.concurrent_installs.is_some() {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(concurrency.installs)
+                .build_global()?;
+            INSTALL_POOL.get_or_init(|| concurrency.installs);
+        }
+
+        Ok(concurrency)
+    }
+}
+
+/// Size the global install pool, throttling it if the disk backing the given directory is slow.
+///
+/// The disk is probed at most once per process, and never if the user provided an explicit
+/// install limit.
+pub(crate) fn size_install_pool(dir: &Path) {
+    INSTALL_POOL.get_or_init(|| {
+        let threads = Concurrency::threads();
+        let installs = if is_slow_disk(dir) {
+            threads.min(4)
+        } else {
+            threads
+        };
+
+        // Building and installing any source distributions during resolution may have already
+        // initialized the pool with its default size.
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(installs)
+            .build_global()
+        {
+            Ok(()) => debug!("Using up to {installs} install thread(s)"),
+            Err(err) => debug!("Failed to size the install pool: {err}"),
+        }
+
+        installs
+    });
+}
+
+/// Return the memory available to the process, in bytes, if it can be determined.
+///
+/// Respects any cgroup (v2) memory limit, such that containers report their own limit rather
+/// than that of the host.
+#[cfg(target_os = "linux")]
+fn available_memory() -> Option<u64> {
+    let system = fs_err::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| {
+            meminfo.lines().find_map(|line| {
+                let kilobytes = line.strip_prefix("MemAvailable:")?;
+                let kilobytes = kilobytes.trim().trim_end_matches("kB").trim();
+                kilobytes
+                    .parse::<u64>()
+                    .ok()
+                    .map(|kilobytes| kilobytes * 1024)
+            })
+        });
+
+    let cgroup = fs_err::read_to_string("/sys/fs/cgroup/memory.max")
+        .ok()
+        .and_then(|max| max.trim().parse::<u64>().ok())
+        .map(|max| {
+            let current = fs_err::read_to_string("/sys/fs/cgroup/memory.current")
+                .ok()
+                .and_then(|current| current.trim().parse::<u64>().ok())
+                .unwrap_or(0);
+            max.saturating_sub(current)
+        });
+
+    match (system, cgroup) {
+        (Some(system), Some(cgroup)) => Some(system.min(cgroup)),
+        (system, cgroup) => system.or(cgroup),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn available_memory() -> Option<u64> {
+    None
+}
+
+/// Returns `true` if a small write to the given directory is slow enough to suggest that
+/// installing wheels in parallel would thrash the disk.
+fn is_slow_disk(dir: &Path) -> bool {
+    let Ok(()) = fs_err::create_dir_all(dir) else {
+        return false;
+    };
+    let Ok(mut file) = tempfile::tempfile_in(dir) else {
+        return false;
+    };
+
+    let start = Instant::now();
+    let result = file
+        .write_all(&vec![0; PROBE_SIZE])
+        .and_then(|()| file.sync_all());
+    let elapsed = start.elapsed();
+
+    if let Err(err) = result {
+        debug!("Failed to probe disk throughput: {err}");
+        return false;
+    }
+
+    debug!(
+        "Wrote a {} MiB probe in {elapsed:?}",
+        PROBE_SIZE / 1024 / 1024
+    );
+    elapsed > SLOW_PROBE
+}
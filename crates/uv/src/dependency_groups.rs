@@ -0,0 +1,93 @@
+//! Support for dependency groups, i.e., named sets of requirements declared via
+//! `[dependency-groups]` (PEP 735).
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use serde::Deserialize;
+
+use pep508_rs::Requirement;
+use uv_fs::Normalized;
+use uv_normalize::GroupName;
+
+/// The dependency groups declared in the `[dependency-groups]` table of a `pyproject.toml`.
+#[derive(Debug, Default)]
+pub(crate) struct DependencyGroups(BTreeMap<GroupName, Vec<DependencyGroupSpecifier>>);
+
+impl DependencyGroups {
+    /// Read the dependency groups declared in the given `pyproject.toml`, if any.
+    pub(crate) fn from_pyproject_toml(path: &Path, contents: &str) -> Result<Self> {
+        let pyproject: PyProjectToml = toml::from_str(contents)
+            .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+        Ok(Self(pyproject.dependency_groups.unwrap_or_default()))
+    }
+
+    /// Returns `true` if the given group is declared.
+    pub(crate) fn contains(&self, group: &GroupName) -> bool {
+        self.0.contains_key(group)
+    }
+
+    /// Return the requirements for the given group, expanding any `include-group` entries.
+    pub(crate) fn requirements(&self, group: &GroupName) -> Result<Vec<Requirement>> {
+        let mut requirements = Vec::new();
+        self.expand(group, &mut Vec::new(), &mut requirements)?;
+        Ok(requirements)
+    }
+
+    fn expand<'a>(
+        &'a self,
+        group: &'a GroupName,
+        parents: &mut Vec<&'a GroupName>,
+        requirements: &mut Vec<Requirement>,
+    ) -> Result<()> {
+        if parents.contains(&group) {
+            bail!(
+                "Detected a cycle in `dependency-groups`: {} -> {group}",
+                parents.iter().join(" -> ")
+            );
+        }
+        let Some(specifiers) = self.0.get(group) else {
+            match parents.last() {
+                Some(parent) => bail!("Failed to find group `{group}` included by `{parent}`"),
+                None => bail!("Failed to find group `{group}`"),
+            }
+        };
+
+        parents.push(group);
+        for specifier in specifiers {
+            match specifier {
+                DependencyGroupSpecifier::Requirement(requirement) => {
+                    requirements.push(Requirement::from_str(requirement).with_context(|| {
+                        format!("Failed to parse `{requirement}` in group `{group}`")
+                    })?);
+                }
+                DependencyGroupSpecifier::IncludeGroup { include_group } => {
+                    self.expand(include_group, parents, requirements)?;
+                }
+            }
+        }
+        parents.pop();
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    dependency_groups: Option<BTreeMap<GroupName, Vec<DependencyGroupSpecifier>>>,
+}
+
+/// An entry in a dependency group: either a PEP 508 requirement, or a reference to another group.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DependencyGroupSpecifier {
+    Requirement(String),
+    #[serde(rename_all = "kebab-case")]
+    IncludeGroup {
+        include_group: GroupName,
+    },
+}
@@ -1,14 +1,37 @@
+use std::io::Write;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use tracing::field::{Field, Visit};
 use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
 #[cfg(feature = "tracing-durations-export")]
 use tracing_durations_export::{
     plot::PlotConfig, DurationsLayer, DurationsLayerBuilder, DurationsLayerDropGuard,
 };
-use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer, Registry};
 use tracing_tree::time::Uptime;
 use tracing_tree::HierarchicalLayer;
 
+use crate::chrome_trace::ChromeTraceLayer;
+use crate::timings::TimingsLayer;
+
+/// The format in which to emit log messages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum LogFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON object per line, including the level, target, fields, enclosing spans, and
+    /// timestamp of each message, for ingestion into log pipelines.
+    Json,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Level {
     /// Suppress all tracing output by default (overrideable by `RUST_LOG`).
@@ -16,53 +39,218 @@ pub(crate) enum Level {
     Default,
     /// Show debug messages by default (overrideable by `RUST_LOG`).
     Verbose,
+    /// Show trace messages from uv's own crates by default (overrideable by `RUST_LOG`).
+    ExtraVerbose,
+    /// Show trace messages from all crates, including dependencies (overrideable by `RUST_LOG`).
+    Trace,
 }
 
-/// Configure `tracing` based on the given [`Level`], taking into account the `RUST_LOG` environment
-/// variable.
+impl Level {
+    /// Determine the [`Level`] from the number of `-v` flags passed on the command line.
+    pub(crate) fn from_verbosity(verbose: u8) -> Self {
+        match verbose {
+            0 => Self::Default,
+            1 => Self::Verbose,
+            2 => Self::ExtraVerbose,
+            _ => Self::Trace,
+        }
+    }
+
+    /// The default filter directives for the [`Level`].
+    fn directives(self) -> &'static str {
+        match self {
+            Self::Default => "off",
+            Self::Verbose => "uv=debug",
+            Self::ExtraVerbose => "uv=trace",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Configure `tracing` based on the given [`Level`], taking into account the `UV_LOG` and
+/// `RUST_LOG` environment variables.
+///
+/// The [`Level`] is used to dictate the default filters along with the formatting of the output.
+/// For example, [`Level::Verbose`] includes targets and timestamps, along with all `uv=debug`
+/// messages by default.
 ///
-/// The [`Level`] is used to dictate the default filters (which can be overridden by the `RUST_LOG`
-/// environment variable) along with the formatting of the output. For example, [`Level::Verbose`]
-/// includes targets and timestamps, along with all `uv=debug` messages by default.
-pub(crate) fn setup_logging(level: Level, duration: impl Layer<Registry> + Send + Sync) {
-    match level {
-        Level::Default => {
-            // Show nothing, but allow `RUST_LOG` to override.
-            let filter = EnvFilter::builder()
+/// ANSI escape codes are only emitted if `ansi` is `true`, such that log collectors that don't
+/// interpret them (e.g., in CI) receive plain text.
+///
+/// The `UV_LOG` environment variable accepts additional, per-module directives that are layered
+/// on top of the defaults (e.g., `UV_LOG=uv_resolver=trace,uv_client=debug`), such that a single
+/// subsystem can be inspected in detail without enabling verbose output for the rest. The
+/// `RUST_LOG` environment variable, if set, replaces the filters entirely.
+///
+/// With [`LogFormat::Json`], each message is written to stderr as a single-line JSON object, with
+/// the same filters.
+///
+/// If `timings` or `trace` are provided, they receive every `info`-level span regardless of the
+/// filters, such that `--timings` and `--trace-output` work without enabling verbose output.
+pub(crate) fn setup_logging(
+    level: Level,
+    format: LogFormat,
+    ansi: bool,
+    duration: impl Layer<Registry> + Send + Sync,
+    timings: Option<TimingsLayer>,
+    trace: Option<ChromeTraceLayer>,
+) {
+    let uv_log = std::env::var("UV_LOG")
+        .ok()
+        .filter(|directives| !directives.is_empty());
+
+    let filter = || {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+            let directives = if let Some(uv_log) = uv_log.as_deref() {
+                format!("{},{uv_log}", level.directives())
+            } else {
+                level.directives().to_string()
+            };
+            EnvFilter::builder()
                 .with_default_directive(LevelFilter::OFF.into())
-                .from_env_lossy();
-
-            // Regardless of the tracing level, show messages without any adornment.
-            tracing_subscriber::registry()
-                .with(duration)
-                .with(filter)
-                .with(
-                    tracing_subscriber::fmt::layer()
-                        .without_time()
-                        .with_target(false)
-                        .with_writer(std::io::sink),
-                )
-                .init();
-        }
-        Level::Verbose => {
-            // Show `DEBUG` messages from the CLI crate, but allow `RUST_LOG` to override.
-            let filter = EnvFilter::try_from_default_env()
-                .or_else(|_| EnvFilter::try_new("uv=debug"))
-                .unwrap();
-
-            // Regardless of the tracing level, include the uptime and target for each message.
-            tracing_subscriber::registry()
-                .with(duration)
-                .with(filter)
-                .with(
-                    HierarchicalLayer::default()
-                        .with_targets(true)
-                        .with_timer(Uptime::default())
-                        .with_writer(std::io::stderr),
-                )
-                .init();
+                .parse_lossy(directives)
+        })
+    };
+
+    // The filters are applied per-layer, such that the timings and trace layers see spans that
+    // are otherwise filtered out.
+    let timings = timings.map(|timings| timings.with_filter(filter_fn(is_info_span)));
+    let trace = trace.map(|trace| trace.with_filter(filter_fn(is_info_span)));
+
+    if format == LogFormat::Json {
+        tracing_subscriber::registry()
+            .with(duration.with_filter(filter()))
+            .with(timings)
+            .with(trace)
+            .with(JsonLayer.with_filter(filter()))
+            .init();
+    } else if level == Level::Default && uv_log.is_none() {
+        // Regardless of the tracing level, show messages without any adornment.
+        tracing_subscriber::registry()
+            .with(duration.with_filter(filter()))
+            .with(timings)
+            .with(trace)
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .without_time()
+                    .with_target(false)
+                    .with_ansi(ansi)
+                    .with_writer(std::io::sink)
+                    .with_filter(filter()),
+            )
+            .init();
+    } else {
+        // Regardless of the tracing level, include the uptime and target for each message.
+        tracing_subscriber::registry()
+            .with(duration.with_filter(filter()))
+            .with(timings)
+            .with(trace)
+            .with(
+                HierarchicalLayer::default()
+                    .with_targets(true)
+                    .with_timer(Uptime::default())
+                    .with_ansi(ansi)
+                    .with_writer(std::io::stderr)
+                    .with_filter(filter()),
+            )
+            .init();
+    }
+}
+
+/// A [`Layer`] that writes each event to stderr as a single-line JSON object.
+///
+/// For example:
+/// ```json
+/// {"timestamp":"2024-01-01T00:00:00.000000Z","level":"DEBUG","target":"uv_resolver::resolver","fields":{"message":"Searching for a compatible version of flask (*)"},"spans":[{"name":"solve"}]}
+/// ```
+struct JsonLayer;
+
+impl<S> Layer<S> for JsonLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = JsonFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(fields) = span.extensions_mut().get_mut::<JsonFields>() {
+            values.record(fields);
         }
     }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+
+        // Include the enclosing spans, from the outermost to the innermost.
+        let spans = ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(tracing_subscriber::registry::Scope::from_root)
+            .map(|span| {
+                let mut object = Map::new();
+                object.insert("name".to_string(), Value::from(span.name()));
+                if let Some(fields) = span.extensions().get::<JsonFields>() {
+                    object.extend(fields.0.clone());
+                }
+                Value::Object(object)
+            })
+            .collect::<Vec<_>>();
+
+        let metadata = event.metadata();
+        let line = json!({
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": fields.0,
+            "spans": spans,
+        });
+
+        let mut stderr = std::io::stderr().lock();
+        // Ignore errors from writing to stderr, as there's nowhere left to report them.
+        let _ = writeln!(stderr, "{line}");
+    }
+}
+
+/// The fields recorded on a span or event, as JSON values.
+#[derive(Debug, Default)]
+pub(crate) struct JsonFields(pub(crate) Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+/// Returns `true` if the [`Metadata`] describes a span at the `info` level or above.
+fn is_info_span(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span() && *metadata.level() <= tracing::Level::INFO
 }
 
 /// Setup the `TRACING_DURATIONS_FILE` environment variable to enable tracing durations.
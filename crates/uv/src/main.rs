@@ -1,29 +1,41 @@
 use std::env;
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anstream::eprintln;
 use anyhow::Result;
 use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
 use clap::error::{ContextKind, ContextValue};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use owo_colors::OwoColorize;
 use tracing::instrument;
+use url::Url;
 
-use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use distribution_types::{FlatIndexLocation, IndexLocations, IndexStrategy, IndexUrl};
+use pep440_rs::VersionSpecifiers;
+use pypi_types::HashAlgorithm;
 use requirements::ExtrasSpecification;
 use uv_cache::{Cache, CacheArgs, Refresh};
-use uv_client::Connectivity;
+use uv_client::{Connectivity, HttpTimeouts, TrustedHost};
 use uv_installer::{NoBinary, Reinstall};
 use uv_interpreter::PythonVersion;
-use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{DependencyMode, PreReleaseMode, ResolutionMode};
-use uv_traits::{NoBuild, PackageNameSpecifier, SetupPyStrategy};
-
-use crate::commands::{extra_name_with_clap_error, ExitStatus, Upgrade};
+use uv_normalize::{ExtraName, GroupName, PackageName};
+use uv_resolver::{Alias, DependencyMode, PreReleaseMode, ResolutionMode};
+use uv_traits::{Concurrency, NoBuild, PackageNameSpecifier, SetupPyStrategy};
+
+use crate::chrome_trace::ChromeTrace;
+use crate::commands::{
+    extra_name_with_clap_error, group_name_with_clap_error, ExitStatus, GraphFormat,
+    LockfileFormat, OutputFormat, TargetPlatform, Upgrade,
+};
 use crate::compat::CompatArgs;
+use crate::concurrency::ConcurrencyArgs;
 use crate::requirements::RequirementsSource;
+use crate::settings::Options;
+use crate::timings::Timings;
 
 #[cfg(target_os = "windows")]
 #[global_allocator]
@@ -41,12 +53,19 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 #[global_allocator]
 static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
+mod chrome_trace;
 mod commands;
 mod compat;
+mod concurrency;
 mod confirm;
+mod dependency_groups;
 mod logging;
+mod policy;
 mod printer;
 mod requirements;
+mod settings;
+mod timings;
+mod workspace;
 
 #[derive(Parser)]
 #[command(author, version, about)]
@@ -56,19 +75,26 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Do not print any output.
+    /// Do not print any output, apart from errors and the result of the command (e.g., the path to
+    /// a newly-created virtual environment).
     #[arg(global = true, long, short, conflicts_with = "verbose")]
     quiet: bool,
 
     /// Use verbose output.
-    #[arg(global = true, long, short, conflicts_with = "quiet")]
-    verbose: bool,
+    ///
+    /// Repeat to increase verbosity: `-v` shows debug messages, `-vv` shows trace messages, and
+    /// `-vvv` additionally shows trace messages from uv's dependencies.
+    ///
+    /// To enable detailed output for specific modules, set `UV_LOG` to a list of filter
+    /// directives (e.g., `UV_LOG=uv_resolver=trace,uv_client=debug`).
+    #[arg(global = true, long, short, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
 
     /// Disable colors; provided for compatibility with `pip`.
     #[arg(global = true, long, hide = true, conflicts_with = "color")]
     no_color: bool,
 
-    /// Control colors in output.
+    /// Control colors in output, including in verbose logs.
     #[arg(
         global = true,
         long,
@@ -78,8 +104,67 @@ struct Cli {
     )]
     color: ColorChoice,
 
+    /// The format in which to emit log messages (e.g., with `--verbose`).
+    ///
+    /// With `json`, each message is written to stderr as a single-line JSON object, including its
+    /// level, target, fields, enclosing spans, and timestamp.
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value_t,
+        env = "UV_LOG_FORMAT"
+    )]
+    log_format: logging::LogFormat,
+
     #[command(flatten)]
     cache_args: CacheArgs,
+
+    /// The path to a `uv.toml` file to use for configuration, in lieu of discovering one.
+    #[arg(global = true, long, env = "UV_CONFIG_FILE")]
+    config_file: Option<PathBuf>,
+
+    /// Avoid discovering a `uv.toml` or `pyproject.toml` configuration file in the current
+    /// directory, its ancestors, or the user configuration directory.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_NO_CONFIG",
+        conflicts_with = "config_file"
+    )]
+    no_config: bool,
+
+    #[command(flatten)]
+    concurrency_args: ConcurrencyArgs,
+
+    /// The maximum time for an HTTP request to complete, in seconds, including reading the
+    /// response body (default: 300).
+    ///
+    /// Increase this when downloading large wheels over slow connections.
+    #[arg(global = true, long, env = "UV_HTTP_TIMEOUT", value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// The maximum time to wait for an HTTP connection to be established, in seconds.
+    ///
+    /// Decrease this to fail fast when an index or mirror is unresponsive.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_HTTP_CONNECT_TIMEOUT",
+        value_name = "SECONDS"
+    )]
+    connect_timeout: Option<u64>,
+
+    /// Print a summary of the time spent on index fetches, metadata reads, resolution,
+    /// downloads, builds, and installation after the command completes, along with the slowest
+    /// packages.
+    #[arg(global = true, long)]
+    timings: bool,
+
+    /// Write the spans recorded during the command to the given file in the Chrome trace event
+    /// format, for viewing in `chrome://tracing` or <https://ui.perfetto.dev>.
+    #[arg(global = true, long, value_name = "FILE")]
+    trace_output: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -114,6 +199,51 @@ enum Commands {
     Venv(VenvArgs),
     /// Clear the cache.
     Clean(CleanArgs),
+    /// Manage the cache.
+    Cache(CacheNamespace),
+    /// Run and manage command-line tools provided by Python packages.
+    Tool(ToolArgs),
+    /// Run a Python script, installing any dependencies declared in its inline metadata.
+    Run(RunArgs),
+    /// Manage standalone Python toolchains.
+    ///
+    /// Installed toolchains are used by `--python` (e.g., `--python 3.12`) when no matching
+    /// interpreter is found on the system.
+    Python(PythonNamespace),
+    /// Manage the uv executable.
+    #[clap(name = "self")]
+    Self_(SelfNamespace),
+    /// Inspect the environment, e.g., to diagnose why a wheel isn't selected.
+    Debug(DebugNamespace),
+    /// Sync all environments declared in the `pyproject.toml` of the current directory,
+    /// concurrently.
+    ///
+    /// Environments are read from the `[tool.uv.envs]` table, along with any members of the
+    /// `[tool.uv.workspace]` table that contain a `requirements.txt` file.
+    SyncAll(SyncAllArgs),
+    /// Upload distributions to a package index (e.g., PyPI).
+    Publish(PublishArgs),
+    /// Check the pinned requirements or installed packages for known vulnerabilities.
+    ///
+    /// Exits with a non-zero status if any vulnerabilities are found.
+    Audit(AuditArgs),
+    /// Explain why a package is installed in the current environment, by showing each chain of
+    /// requirements that pulled it in, along with the version constraint on each edge.
+    Why(WhyArgs),
+    /// Resolve the given requirements, like `pip compile`, and write the resolution graph (including
+    /// the markers and extras on each edge) as a Graphviz DOT file or as JSON.
+    ExportGraph(ExportGraphArgs),
+    /// Convert a `poetry.lock` or `Pipfile.lock` into a pinned `requirements.txt` file, without
+    /// re-resolving.
+    Import(ImportArgs),
+    /// Serve resolution and environment queries as JSON-RPC over stdin and stdout, for editor and
+    /// language-server integrations.
+    ///
+    /// Each message is a JSON-RPC 2.0 object on a single line. Supported methods are `resolve`,
+    /// `environment`, `outdated`, and `shutdown`.
+    ServeApi(ServeApiArgs),
+    /// Generate a shell completion script (e.g., `uv generate-shell-completion bash`).
+    GenerateShellCompletion(GenerateShellCompletionArgs),
 }
 
 #[derive(Args)]
@@ -134,6 +264,12 @@ enum PipCommand {
     Uninstall(PipUninstallArgs),
     /// Enumerate the installed packages in the current environment.
     Freeze(PipFreezeArgs),
+    /// List the installed packages in the current environment, along with their provenance (e.g.,
+    /// whether each package was installed from a wheel or built locally from a source
+    /// distribution).
+    List(PipListArgs),
+    /// Download packages into a directory, without installing them.
+    Download(PipDownloadArgs),
 }
 
 /// Clap parser for the union of date and datetime
@@ -154,6 +290,81 @@ fn date_or_datetime(input: &str) -> Result<DateTime<Utc>, String> {
     ))
 }
 
+/// Arguments that configure the package indexes to query, shared by every command that resolves
+/// or installs packages.
+#[derive(Args)]
+struct IndexArgs {
+    /// The URL of the Python Package Index (default: <https://pypi.org/simple>).
+    #[clap(long, short, env = "UV_INDEX_URL")]
+    index_url: Option<IndexUrl>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ')]
+    extra_index_url: Vec<IndexUrl>,
+
+    /// The strategy to use when a package is available on multiple indexes.
+    ///
+    /// By default, only the first index that contains a package is used for that package, such
+    /// that a package on an extra index can't shadow the same package on the primary index.
+    #[clap(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    index_strategy: Option<IndexStrategy>,
+
+    /// Fall back to the remaining indexes when an index is unreachable for a package.
+    ///
+    /// An index is unreachable if the request times out, fails to connect, or returns a server
+    /// error (5xx). By default, such failures are fatal, since falling back would allow an extra
+    /// index to serve a package that should've come from the unreachable index.
+    #[clap(long, env = "UV_INDEX_FALLBACK")]
+    index_fallback: bool,
+
+    /// Skip malformed links on HTML index pages, rather than failing on the entire page.
+    ///
+    /// Intended for non-compliant private indexes. Links that can't be parsed (e.g., those with
+    /// a missing `href`) are skipped, and unsupported or malformed hashes (e.g., `#md5=...`) are
+    /// ignored, with a warning naming the offending page.
+    #[clap(long, env = "UV_LENIENT_INDEX")]
+    lenient_index: bool,
+
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
+    no_index: bool,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file with a flat
+    /// list of links to package files.
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[clap(long)]
+    find_links: Vec<FlatIndexLocation>,
+}
+
+impl IndexArgs {
+    /// Resolve the index locations and trusted hosts, falling back to the persistent
+    /// configuration for any that weren't provided.
+    fn resolve(self, options: &Options) -> (IndexLocations, Vec<TrustedHost>) {
+        let index_locations = options.index_locations(
+            self.index_url,
+            self.extra_index_url,
+            self.find_links,
+            self.no_index,
+            self.index_strategy,
+            self.index_fallback,
+            self.lenient_index,
+        );
+        let trusted_hosts = options.trusted_hosts(self.trusted_host);
+        (index_locations, trusted_hosts)
+    }
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct PipCompileArgs {
@@ -185,6 +396,15 @@ struct PipCompileArgs {
     #[clap(long)]
     r#override: Vec<PathBuf>,
 
+    /// Satisfy requirements on a package with another package, in the form
+    /// `<requested>=<provider>`; may be provided more than once.
+    ///
+    /// For example, `--alias opencv-python=opencv-python-headless` resolves any requirement on
+    /// `opencv-python` to `opencv-python-headless` instead, retaining its version specifiers,
+    /// extras, and markers.
+    #[clap(long)]
+    alias: Vec<Alias>,
+
     /// Include optional dependencies in the given extra group name; may be provided more than once.
     #[clap(long, conflicts_with = "all_extras", value_parser = extra_name_with_clap_error)]
     extra: Vec<ExtraName>,
@@ -193,21 +413,50 @@ struct PipCompileArgs {
     #[clap(long, conflicts_with = "extra")]
     all_extras: bool,
 
+    /// Include dependencies from the given dependency group, as declared in the
+    /// `[dependency-groups]` table of a `pyproject.toml`; may be provided more than once.
+    ///
+    /// To keep development dependencies out of a production lockfile, compile separate lockfiles
+    /// with and without the group.
+    #[clap(long, value_parser = group_name_with_clap_error)]
+    group: Vec<GroupName>,
+
     /// Ignore package dependencies, instead only add those packages explicitly listed
     /// on the command line to the resulting the requirements file.
     #[clap(long)]
     no_deps: bool,
 
-    #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
-    resolution: ResolutionMode,
+    /// Prefer the newest version with a compatible wheel over a newer version that only provides
+    /// a source distribution, to avoid building packages from source where possible.
+    #[clap(long)]
+    prefer_binary: bool,
+
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
 
-    #[clap(long, value_enum, default_value_t = PreReleaseMode::default())]
-    prerelease: PreReleaseMode,
+    #[clap(long, value_enum)]
+    prerelease: Option<PreReleaseMode>,
 
     /// Write the compiled requirements to the given `requirements.txt` file.
+    ///
+    /// Use `-` to write to stdout (the default).
     #[clap(short, long)]
     output_file: Option<PathBuf>,
 
+    /// Check that the output file is up-to-date, rather than writing it.
+    ///
+    /// Exits with a non-zero status if the output file is missing or would change, e.g., for use
+    /// in a pre-commit hook. The output is ordered deterministically, so an output file that
+    /// contains the expected requirements in a different order (or with different trailing
+    /// whitespace) is reported as not canonical.
+    #[clap(long, requires = "output_file")]
+    check: bool,
+
+    /// Write a Markdown summary of the changes to the pinned packages, relative to the existing
+    /// output file, to the given file (e.g., for use in a pull request description).
+    #[clap(long, requires = "output_file")]
+    changes_file: Option<PathBuf>,
+
     /// Exclude comment annotations indicating the source of each package.
     #[clap(long)]
     no_annotate: bool,
@@ -216,10 +465,37 @@ struct PipCompileArgs {
     #[clap(long)]
     no_header: bool,
 
+    /// Pin packages that are considered unsafe in a requirements file (`pip`, `setuptools`, and
+    /// `wheel`), as with pip-compile's `--allow-unsafe`.
+    ///
+    /// This is the default, unless `--unsafe-package` is provided.
+    #[clap(long, conflicts_with = "no_allow_unsafe")]
+    allow_unsafe: bool,
+
+    /// Omit packages that are considered unsafe in a requirements file from the output, listing
+    /// them in a comment at the end of the output instead, as with pip-compile's default.
+    #[clap(long)]
+    no_allow_unsafe: bool,
+
+    /// Consider the given package unsafe in a requirements file, in place of `pip`, `setuptools`,
+    /// and `wheel`.
+    ///
+    /// Unsafe packages are omitted from the output, unless `--allow-unsafe` is provided.
+    #[clap(long)]
+    unsafe_package: Vec<PackageName>,
+
+    /// The format in which to write the resolution.
+    ///
+    /// With `json`, the pinned packages (along with their hashes) are written as a JSON object,
+    /// rather than as a `requirements.txt` file.
+    #[clap(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
     /// Run offline, i.e., without accessing the network.
     #[arg(
         global = true,
         long,
+        env = "UV_OFFLINE",
         conflicts_with = "refresh",
         conflicts_with = "refresh_package"
     )]
@@ -233,27 +509,8 @@ struct PipCompileArgs {
     #[clap(long)]
     refresh_package: Vec<PackageName>,
 
-    /// The URL of the Python Package Index.
-    #[clap(long, short, default_value = IndexUrl::Pypi.as_str(), env = "UV_INDEX_URL")]
-    index_url: IndexUrl,
-
-    /// Extra URLs of package indexes to use, in addition to `--index-url`.
-    #[clap(long)]
-    extra_index_url: Vec<IndexUrl>,
-
-    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
-    /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
-    no_index: bool,
-
-    /// Locations to search for candidate distributions, beyond those found in the indexes.
-    ///
-    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
-    ///
-    /// If a URL, the page must contain a flat list of links to package files.
-    #[clap(long)]
-    find_links: Vec<FlatIndexLocation>,
+    #[command(flatten)]
+    index_args: IndexArgs,
 
     /// Allow package upgrades, ignoring pinned versions in the existing output file.
     #[clap(long, short = 'U')]
@@ -268,6 +525,14 @@ struct PipCompileArgs {
     #[clap(long)]
     generate_hashes: bool,
 
+    /// The algorithm to use for distribution hashes in the output file; may be provided more than
+    /// once to include a hash for each algorithm.
+    ///
+    /// Hashes are taken from the index, so an algorithm is omitted for any distribution for which
+    /// the index didn't provide it. Defaults to `sha256`.
+    #[clap(long, value_enum, requires = "generate_hashes")]
+    hash_algorithm: Vec<HashAlgorithm>,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[clap(long)]
@@ -302,6 +567,18 @@ struct PipCompileArgs {
     #[arg(long, short)]
     python_version: Option<PythonVersion>,
 
+    /// The range of Python versions that should be supported by the compiled requirements (e.g.,
+    /// `>=3.8`), as for a library's `requires-python`.
+    ///
+    /// Resolves for the lowest Python version in the range, but rejects any package version whose
+    /// `Requires-Python` doesn't cover every Python version in the range.
+    ///
+    /// The resolution does not yet fork on Python-version markers, so resolution fails if any
+    /// dependency only applies to newer Python versions in the range (e.g.,
+    /// `foo; python_version >= "3.11"`).
+    #[arg(long, conflicts_with = "python_version")]
+    requires_python: Option<VersionSpecifiers>,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
@@ -310,11 +587,14 @@ struct PipCompileArgs {
     exclude_newer: Option<DateTime<Utc>>,
 
     /// Include `--index-url` and `--extra-index-url` entries in the generated output file.
-    #[clap(long, hide = true)]
+    ///
+    /// If the index is disabled (e.g., via `--no-index`), includes a `--no-index` entry instead,
+    /// such that installing from the output file uses the same sources.
+    #[clap(long)]
     emit_index_url: bool,
 
     /// Include `--find-links` entries in the generated output file.
-    #[clap(long, hide = true)]
+    #[clap(long)]
     emit_find_links: bool,
 
     #[command(flatten)]
@@ -336,10 +616,27 @@ struct PipSyncArgs {
     #[clap(long)]
     reinstall_package: Vec<PackageName>,
 
+    /// Only install or upgrade the listed packages, rather than also uninstalling any installed
+    /// packages that aren't listed.
+    ///
+    /// By default, `pip sync` makes the environment exactly match the requirements.
+    #[clap(long)]
+    inexact: bool,
+
+    /// The Python interpreter into which packages should be installed.
+    ///
+    /// Accepts the path to a virtual environment, or any request supported by `uv venv --python`
+    /// (e.g., `3.12` or `.venv/bin/python`), which must resolve to an interpreter in a virtual
+    /// environment. By default, the environment is detected from `VIRTUAL_ENV`, then
+    /// `CONDA_PREFIX`, then the nearest `.venv` directory.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
     /// Run offline, i.e., without accessing the network.
     #[arg(
         global = true,
         long,
+        env = "UV_OFFLINE",
         conflicts_with = "refresh",
         conflicts_with = "refresh_package"
     )]
@@ -354,30 +651,20 @@ struct PipSyncArgs {
     refresh_package: Vec<PackageName>,
 
     /// The method to use when installing packages from the global cache.
-    #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
-    link_mode: install_wheel_rs::linker::LinkMode,
+    #[clap(long, value_enum)]
+    link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
-    /// The URL of the Python Package Index.
-    #[clap(long, short, default_value = IndexUrl::Pypi.as_str(), env = "UV_INDEX_URL")]
-    index_url: IndexUrl,
-
-    /// Extra URLs of package indexes to use, in addition to `--index-url`.
-    #[clap(long)]
-    extra_index_url: Vec<IndexUrl>,
+    /// The style of shebang to write to the scripts of installed packages.
+    #[clap(long, value_enum)]
+    shebang: Option<install_wheel_rs::ShebangStyle>,
 
-    /// Locations to search for candidate distributions, beyond those found in the indexes.
-    ///
-    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
-    ///
-    /// If a URL, the page must contain a flat list of links to package files.
-    #[clap(long)]
-    find_links: Vec<FlatIndexLocation>,
+    /// The interpreter to reference in the shebangs of installed scripts, in place of the
+    /// environment's own (e.g., the path at which the environment will live once deployed).
+    #[clap(long, value_name = "PATH")]
+    shebang_interpreter: Option<PathBuf>,
 
-    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
-    /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
-    no_index: bool,
+    #[command(flatten)]
+    index_args: IndexArgs,
 
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
@@ -420,6 +707,45 @@ struct PipSyncArgs {
     #[clap(long)]
     strict: bool,
 
+    /// Continue installing the remaining packages if any package fails to download or build,
+    /// reporting the failures once the sync is complete.
+    ///
+    /// Any existing installation of a package that fails is left in place.
+    #[clap(long)]
+    keep_going: bool,
+
+    /// Verify the files of any cached distributions against the hashes in their `RECORD` before
+    /// installing them (e.g., to detect a corrupted cache on a shared volume).
+    ///
+    /// Cached distributions that fail verification are removed from the cache and downloaded (or
+    /// built) again.
+    #[clap(long, env = "UV_VERIFY_HASHES")]
+    verify_hashes: bool,
+
+    /// The format in which to report the changes to the environment.
+    ///
+    /// With `json`, the packages that were installed, uninstalled, or failed are written to stdout
+    /// as a JSON object.
+    #[clap(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Write a JSON manifest of any wheels built from source distributions to the given path,
+    /// including each wheel's filename and SHA-256 digest, along with the source distribution from
+    /// which it was built.
+    #[clap(long, value_name = "PATH")]
+    build_manifest: Option<PathBuf>,
+
+    /// Print the installation plan before executing it, including the packages to install (and
+    /// whether each is linked from the cache, downloaded, or built from source), the packages to
+    /// uninstall, and the total download size.
+    #[clap(long)]
+    report: bool,
+
+    /// Write the installation plan to the given path as JSON, before executing it (e.g., to
+    /// archive as a CI artifact).
+    #[clap(long, value_name = "PATH")]
+    report_json: Option<PathBuf>,
+
     #[command(flatten)]
     compat_args: compat::PipSyncCompatArgs,
 }
@@ -432,6 +758,15 @@ struct PipInstallArgs {
     #[clap(group = "sources")]
     package: Vec<String>,
 
+    /// The Python interpreter into which packages should be installed.
+    ///
+    /// Accepts the path to a virtual environment, or any request supported by `uv venv --python`
+    /// (e.g., `3.12` or `.venv/bin/python`), which must resolve to an interpreter in a virtual
+    /// environment. By default, the environment is detected from `VIRTUAL_ENV`, then
+    /// `CONDA_PREFIX`, then the nearest `.venv` directory.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
     /// Install all packages listed in the given requirements files.
     #[clap(short, long, group = "sources")]
     requirement: Vec<PathBuf>,
@@ -462,6 +797,15 @@ struct PipInstallArgs {
     #[clap(long)]
     r#override: Vec<PathBuf>,
 
+    /// Satisfy requirements on a package with another package, in the form
+    /// `<requested>=<provider>`; may be provided more than once.
+    ///
+    /// For example, `--alias opencv-python=opencv-python-headless` resolves any requirement on
+    /// `opencv-python` to `opencv-python-headless` instead, retaining its version specifiers,
+    /// extras, and markers.
+    #[clap(long)]
+    alias: Vec<Alias>,
+
     /// Include optional dependencies in the given extra group name; may be provided more than once.
     #[clap(long, conflicts_with = "all_extras", value_parser = extra_name_with_clap_error)]
     extra: Vec<ExtraName>,
@@ -470,6 +814,11 @@ struct PipInstallArgs {
     #[clap(long, conflicts_with = "extra")]
     all_extras: bool,
 
+    /// Include dependencies from the given dependency group, as declared in the
+    /// `[dependency-groups]` table of a `pyproject.toml`; may be provided more than once.
+    #[clap(long, value_parser = group_name_with_clap_error)]
+    group: Vec<GroupName>,
+
     /// Allow package upgrades.
     #[clap(long, short = 'U')]
     upgrade: bool,
@@ -490,6 +839,7 @@ struct PipInstallArgs {
     #[arg(
         global = true,
         long,
+        env = "UV_OFFLINE",
         conflicts_with = "refresh",
         conflicts_with = "refresh_package"
     )]
@@ -508,41 +858,36 @@ struct PipInstallArgs {
     #[clap(long)]
     no_deps: bool,
 
+    /// Prefer the newest version with a compatible wheel over a newer version that only provides
+    /// a source distribution, to avoid building packages from source where possible.
+    #[clap(long)]
+    prefer_binary: bool,
+
     /// The method to use when installing packages from the global cache.
-    #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
-    link_mode: install_wheel_rs::linker::LinkMode,
+    #[clap(long, value_enum)]
+    link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
-    #[clap(long, value_enum, default_value_t = ResolutionMode::default())]
-    resolution: ResolutionMode,
+    /// The style of shebang to write to the scripts of installed packages.
+    #[clap(long, value_enum)]
+    shebang: Option<install_wheel_rs::ShebangStyle>,
 
-    #[clap(long, value_enum, default_value_t = PreReleaseMode::default())]
-    prerelease: PreReleaseMode,
+    /// The interpreter to reference in the shebangs of installed scripts, in place of the
+    /// environment's own (e.g., the path at which the environment will live once deployed).
+    #[clap(long, value_name = "PATH")]
+    shebang_interpreter: Option<PathBuf>,
+
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
+
+    #[clap(long, value_enum)]
+    prerelease: Option<PreReleaseMode>,
 
     /// Write the compiled requirements to the given `requirements.txt` file.
     #[clap(short, long)]
     output_file: Option<PathBuf>,
 
-    /// The URL of the Python Package Index.
-    #[clap(long, short, default_value = IndexUrl::Pypi.as_str(), env = "UV_INDEX_URL")]
-    index_url: IndexUrl,
-
-    /// Extra URLs of package indexes to use, in addition to `--index-url`.
-    #[clap(long)]
-    extra_index_url: Vec<IndexUrl>,
-
-    /// Locations to search for candidate distributions, beyond those found in the indexes.
-    ///
-    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
-    ///
-    /// If a URL, the page must contain a flat list of links to package files.
-    #[clap(long)]
-    find_links: Vec<FlatIndexLocation>,
-
-    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
-    /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
-    no_index: bool,
+    #[command(flatten)]
+    index_args: IndexArgs,
 
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
@@ -585,6 +930,20 @@ struct PipInstallArgs {
     #[clap(long)]
     strict: bool,
 
+    /// Verify the files of any cached distributions against the hashes in their `RECORD` before
+    /// installing them (e.g., to detect a corrupted cache on a shared volume).
+    ///
+    /// Cached distributions that fail verification are removed from the cache and downloaded (or
+    /// built) again.
+    #[clap(long, env = "UV_VERIFY_HASHES")]
+    verify_hashes: bool,
+
+    /// Write a JSON manifest of any wheels built from source distributions to the given path,
+    /// including each wheel's filename and SHA-256 digest, along with the source distribution from
+    /// which it was built.
+    #[clap(long, value_name = "PATH")]
+    build_manifest: Option<PathBuf>,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
@@ -596,92 +955,918 @@ struct PipInstallArgs {
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 #[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
-struct PipUninstallArgs {
-    /// Uninstall all listed packages.
+struct PipDownloadArgs {
+    /// Download all listed packages.
     #[clap(group = "sources")]
     package: Vec<String>,
 
-    /// Uninstall all packages listed in the given requirements files.
+    /// Download all packages listed in the given requirements files.
     #[clap(short, long, group = "sources")]
     requirement: Vec<PathBuf>,
 
-    /// Uninstall the editable package based on the provided local file path.
-    #[clap(short, long, group = "sources")]
-    editable: Vec<String>,
-}
-
-#[derive(Args)]
-#[allow(clippy::struct_excessive_bools)]
-struct PipFreezeArgs {
-    /// Validate the virtual environment, to detect packages with missing dependencies or other
-    /// issues.
-    #[clap(long)]
-    strict: bool,
-}
-
-#[derive(Args)]
-#[allow(clippy::struct_excessive_bools)]
-struct CleanArgs {
-    /// The packages to remove from the cache.
-    package: Vec<PackageName>,
-}
-
-#[derive(Args)]
-#[allow(clippy::struct_excessive_bools)]
-struct VenvArgs {
-    /// The Python interpreter to use for the virtual environment.
+    /// Constrain versions using the given requirements files.
     ///
-    /// Supported formats:
-    /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on Linux/Mac).
-    ///   Specifying a patch version is not supported.
-    /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
-    /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's downloaded. However, including a package in a constraints file will
+    /// _not_ trigger the download of that package.
     ///
-    /// Note that this is different from `--python-version` in `pip compile`, which takes `3.10` or `3.10.13` and
-    /// doesn't look for a Python interpreter on disk.
-    // Short `-p` to match `virtualenv`
+    /// This is equivalent to pip's `--constraint` option.
     #[clap(short, long)]
-    python: Option<String>,
+    constraint: Vec<PathBuf>,
 
-    /// Install seed packages (`pip`, `setuptools`, and `wheel`) into the virtual environment.
+    /// Override versions using the given requirements files.
+    ///
+    /// Overrides files are `requirements.txt`-like files that force a specific version of a
+    /// requirement to be downloaded, regardless of the requirements declared by any constituent
+    /// package, and regardless of whether this would be considered an invalid resolution.
     #[clap(long)]
-    seed: bool,
-
-    /// The path to the virtual environment to create.
-    #[clap(default_value = ".venv")]
-    name: PathBuf,
+    r#override: Vec<PathBuf>,
 
-    /// The URL of the Python Package Index.
-    #[clap(long, short, default_value = IndexUrl::Pypi.as_str(), env = "UV_INDEX_URL")]
-    index_url: IndexUrl,
+    /// The directory into which the distributions should be downloaded.
+    #[clap(short, long, default_value = ".")]
+    dest: PathBuf,
 
-    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    /// Ignore package dependencies, instead only downloading those packages explicitly listed on
+    /// the command line or in the requirements files.
     #[clap(long)]
-    extra_index_url: Vec<IndexUrl>,
+    no_deps: bool,
 
-    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
-    /// discovered via `--find-links`.
-    #[clap(long, conflicts_with = "index_url", conflicts_with = "extra_index_url")]
-    no_index: bool,
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
+
+    #[clap(long, value_enum)]
+    prerelease: Option<PreReleaseMode>,
 
     /// Run offline, i.e., without accessing the network.
-    #[arg(global = true, long)]
+    #[arg(
+        global = true,
+        long,
+        env = "UV_OFFLINE",
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
     offline: bool,
 
-    /// Limit candidate packages to those that were uploaded prior to the given date.
-    ///
-    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
-    /// format (e.g., `2006-12-02`).
-    #[arg(long, value_parser = date_or_datetime, hide = true)]
-    exclude_newer: Option<DateTime<Utc>>,
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
 
     #[command(flatten)]
-    compat_args: compat::VenvCompatArgs,
-}
+    index_args: IndexArgs,
 
-#[derive(Args)]
-#[allow(clippy::struct_excessive_bools)]
-struct AddArgs {
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long)]
+    legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+
+    /// The Python version for which distributions should be downloaded (e.g., `3.7` or `3.7.9`).
+    ///
+    /// If a patch version is omitted, the most recent known patch version for that minor version
+    /// is assumed. For example, `3.7` is mapped to `3.7.17`.
+    ///
+    /// May be provided multiple times, in which case the requirements are resolved for each
+    /// version (and each `--platform`), and the union of the distributions is downloaded.
+    #[arg(long)]
+    python_version: Vec<PythonVersion>,
+
+    /// The platform for which distributions should be downloaded, if other than the current
+    /// platform.
+    ///
+    /// Source distributions are still built (if necessary) with the current interpreter, in order
+    /// to determine their metadata.
+    ///
+    /// May be provided multiple times, in which case the requirements are resolved for each
+    /// platform (and each `--python-version`), and the union of the distributions is downloaded.
+    #[arg(long, alias = "python-platform", value_enum)]
+    platform: Vec<TargetPlatform>,
+
+    /// Write a JSON manifest to the given path, listing the files required by each combination of
+    /// `--python-version` and `--platform`.
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime, hide = true)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
+struct PipUninstallArgs {
+    /// Uninstall all listed packages.
+    #[clap(group = "sources")]
+    package: Vec<String>,
+
+    /// Uninstall all packages listed in the given requirements files.
+    #[clap(short, long, group = "sources")]
+    requirement: Vec<PathBuf>,
+
+    /// Uninstall the editable package based on the provided local file path.
+    #[clap(short, long, group = "sources")]
+    editable: Vec<String>,
+}
+
+#[derive(Args)]
+struct PipListArgs {
+    /// The format in which to list the installed packages.
+    #[clap(long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct PipFreezeArgs {
+    /// Validate the virtual environment, to detect packages with missing dependencies or other
+    /// issues.
+    #[clap(long)]
+    strict: bool,
+
+    /// Write the installed packages as a fully pinned requirements file, annotated with the hashes
+    /// of the matching distributions on the index.
+    ///
+    /// Packages installed from a URL are pinned to that URL; packages that can't be found on the
+    /// index are pinned without hashes.
+    #[clap(long)]
+    lock: bool,
+
+    /// The algorithm to use for distribution hashes in the output; may be provided more than once
+    /// to include a hash for each algorithm.
+    ///
+    /// Hashes are taken from the index, so an algorithm is omitted for any distribution for which
+    /// the index didn't provide it. Defaults to `sha256`.
+    #[clap(long, value_enum, requires = "lock")]
+    hash_algorithm: Vec<HashAlgorithm>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Run offline, i.e., without accessing the network.
+    ///
+    /// Hashes are then taken from the cached index metadata only.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct CleanArgs {
+    /// The packages to remove from the cache.
+    ///
+    /// Any deduplicated wheel files that are no longer used by a cached wheel or an environment
+    /// are removed too.
+    package: Vec<PackageName>,
+}
+
+#[derive(Args)]
+struct CacheNamespace {
+    #[clap(subcommand)]
+    command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Clear the cache.
+    Clean(CleanArgs),
+    /// Prune the cache, removing any entries left behind by older versions of uv, along with any
+    /// deduplicated files that are no longer used by any cached wheel.
+    ///
+    /// Cache entries in an outdated format are never read, but aren't removed automatically, as
+    /// they may still be in use by an older version of uv that's running concurrently.
+    Prune,
+    /// Revalidate the cached index metadata for a set of pinned requirements, and prefetch their
+    /// distributions into the cache.
+    ///
+    /// Intended to be run periodically (e.g., via `cron` or in CI), such that subsequent
+    /// resolutions and installations can be served from a warm cache.
+    Warm(CacheWarmArgs),
+    /// Export the cached wheels and index metadata to a `.tar.gz` archive.
+    ///
+    /// The archive can be restored on another machine (or at another cache path) with
+    /// `uv cache import`, e.g., to persist the cache across CI runs.
+    Export(CacheExportArgs),
+    /// Import a `.tar.gz` archive created by `uv cache export` into the cache.
+    Import(CacheImportArgs),
+    /// Show the cache directory.
+    ///
+    /// Respects `--cache-dir` and `UV_CACHE_DIR`, e.g., to verify that a mounted cache volume is
+    /// picked up in a containerized build.
+    Dir,
+    /// Show what's cached for a package: its index metadata, downloaded wheels, and the wheels
+    /// built from its source distributions (by tag), along with their sizes and paths.
+    ///
+    /// Useful to understand why a package is (or isn't) being re-downloaded or rebuilt.
+    Info(CacheInfoArgs),
+}
+
+#[derive(Args)]
+struct CacheInfoArgs {
+    /// The package to inspect.
+    package: PackageName,
+}
+
+#[derive(Args)]
+struct PythonNamespace {
+    #[clap(subcommand)]
+    command: PythonCommand,
+}
+
+#[derive(Subcommand)]
+enum PythonCommand {
+    /// List the installed toolchains.
+    List(PythonListArgs),
+    /// Download and install standalone CPython builds (from `python-build-standalone`).
+    ///
+    /// Toolchains are installed into `UV_TOOLCHAIN_DIR`, if set, or into the user data directory
+    /// (e.g., `~/.local/share/uv/toolchains` on Linux).
+    Install(PythonInstallArgs),
+}
+
+#[derive(Args)]
+struct SelfNamespace {
+    #[clap(subcommand)]
+    command: SelfCommand,
+}
+
+#[derive(Subcommand)]
+enum SelfCommand {
+    /// Update uv to the latest release.
+    ///
+    /// Only supported for installations via the standalone installer: the release archive for the
+    /// current platform is downloaded, verified against its published SHA-256 checksum and its
+    /// signature, and atomically swapped in for the running executable.
+    Update(SelfUpdateArgs),
+}
+
+#[derive(Args)]
+struct SelfUpdateArgs {
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct DebugNamespace {
+    #[clap(subcommand)]
+    command: DebugCommand,
+}
+
+#[derive(Subcommand)]
+enum DebugCommand {
+    /// Show the wheel tags supported by the target interpreter, from the most to the least
+    /// preferred.
+    ///
+    /// If any wheels are provided, explain whether (and why) each is compatible instead, exiting
+    /// with a non-zero status if any are incompatible.
+    Tags(DebugTagsArgs),
+}
+
+#[derive(Args)]
+struct DebugTagsArgs {
+    /// The wheel filenames (or paths, or URLs) to check for compatibility.
+    wheels: Vec<String>,
+
+    /// The Python interpreter whose tags should be shown.
+    ///
+    /// Supported formats:
+    /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on Linux/Mac).
+    ///   Specifying a patch version is not supported.
+    /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
+    /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+}
+
+#[derive(Args)]
+struct PythonListArgs {
+    /// List the toolchains available for download on the current platform, rather than those
+    /// already installed.
+    #[clap(long)]
+    available: bool,
+
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct PythonInstallArgs {
+    /// The Python versions to install (e.g., `3.12` or `3.11.7`).
+    ///
+    /// If a patch version is omitted, the newest available patch version is installed.
+    #[clap(required(true))]
+    versions: Vec<String>,
+
+    /// Reinstall the toolchain, even if it's already installed.
+    #[clap(long)]
+    force: bool,
+
+    /// Install toolchains that don't have a published SHA-256 digest, without verifying them.
+    ///
+    /// By default, such toolchains are rejected.
+    #[clap(long)]
+    allow_unverified: bool,
+
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct CacheExportArgs {
+    /// The path to which the archive should be written.
+    path: PathBuf,
+}
+
+#[derive(Args)]
+struct CacheImportArgs {
+    /// The path to the archive to import.
+    path: PathBuf,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct CacheWarmArgs {
+    /// Warm the cache for all packages listed in the given requirements files.
+    #[clap(short, long, required(true))]
+    requirement: Vec<PathBuf>,
+
+    /// The Python interpreter for which distributions should be fetched.
+    ///
+    /// Supported formats:
+    /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on Linux/Mac).
+    ///   Specifying a patch version is not supported.
+    /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
+    /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
+    /// Re-warm the cache every given number of seconds, until interrupted.
+    ///
+    /// By default, the cache is warmed once.
+    #[clap(long, value_name = "SECONDS")]
+    interval: Option<u64>,
+
+    /// Stop warming the cache after the given number of seconds, on each pass.
+    #[clap(long, value_name = "SECONDS")]
+    time_limit: Option<u64>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long)]
+    legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, source distributions will not be built, and so will not be cached.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Don't use pre-built wheels.
+    ///
+    /// The given packages will be built from a source distribution, and the resulting wheels
+    /// cached.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct VenvArgs {
+    /// The Python interpreter to use for the virtual environment.
+    ///
+    /// Supported formats:
+    /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on Linux/Mac).
+    ///   Specifying a patch version is not supported.
+    /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
+    /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
+    ///
+    /// Note that this is different from `--python-version` in `pip compile`, which takes `3.10` or `3.10.13` and
+    /// doesn't look for a Python interpreter on disk.
+    // Short `-p` to match `virtualenv`
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
+    /// Install seed packages (`pip`, `setuptools`, and `wheel`) into the virtual environment.
+    #[clap(long)]
+    seed: bool,
+
+    /// Give the virtual environment access to the system site packages directory.
+    ///
+    /// Packages installed into the virtual environment take precedence over those in the system
+    /// site packages.
+    #[clap(long)]
+    system_site_packages: bool,
+
+    /// Provide an alternative prompt prefix for the virtual environment.
+    ///
+    /// By default, the prompt prefix is the name of the virtual environment's directory.
+    #[clap(long)]
+    prompt: Option<String>,
+
+    /// Make the virtual environment relocatable.
+    ///
+    /// The activation scripts locate the environment relative to themselves, and scripts
+    /// installed into the environment use relative shebangs (unless `--shebang` is provided), such
+    /// that the environment can be moved (e.g., copied into a container image) after creation.
+    #[clap(long)]
+    relocatable: bool,
+
+    /// The path to the virtual environment to create.
+    #[clap(default_value = ".venv")]
+    name: PathBuf,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(global = true, long, env = "UV_OFFLINE")]
+    offline: bool,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime, hide = true)]
+    exclude_newer: Option<DateTime<Utc>>,
+
+    #[command(flatten)]
+    compat_args: compat::VenvCompatArgs,
+}
+
+#[derive(Args)]
+struct ToolArgs {
+    #[clap(subcommand)]
+    command: ToolCommand,
+}
+
+#[derive(Subcommand)]
+enum ToolCommand {
+    /// Run a command provided by a Python package in an ephemeral environment.
+    Run(ToolRunArgs),
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct ToolRunArgs {
+    /// The command to run (e.g., `ruff`).
+    command: String,
+
+    /// The arguments to pass to the command.
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<OsString>,
+
+    /// The requirement that provides the command (e.g., `ruff==0.2.0`).
+    ///
+    /// By default, the command is assumed to be provided by a package of the same name.
+    #[clap(long)]
+    from: Option<String>,
+
+    /// The Python interpreter to use for the tool environment.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
+    /// Re-resolve the requirements, upgrading the cached environment to the latest compatible
+    /// versions.
+    ///
+    /// By default, a cached environment that already satisfies the requirements is reused as-is.
+    #[clap(long, short = 'U')]
+    upgrade: bool,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(
+        long,
+        env = "UV_OFFLINE",
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
+    offline: bool,
+
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long)]
+    legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Don't install pre-built wheels.
+    ///
+    /// The given packages will be installed from a source distribution. The resolver
+    /// will still use pre-built wheels for metadata.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime, hide = true)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct RunArgs {
+    /// The Python script to run.
+    ///
+    /// Dependencies and Python version requirements are read from the script's inline metadata
+    /// (i.e., a `# /// script` block), as specified in PEP 723.
+    script: PathBuf,
+
+    /// The arguments to pass to the script.
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<OsString>,
+
+    /// The Python interpreter to use to run the script.
+    #[clap(short, long, env = "UV_PYTHON")]
+    python: Option<String>,
+
+    /// Re-resolve the requirements, upgrading the cached environment to the latest compatible
+    /// versions.
+    ///
+    /// By default, a cached environment that already satisfies the requirements is reused as-is.
+    #[clap(long, short = 'U')]
+    upgrade: bool,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(
+        long,
+        env = "UV_OFFLINE",
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
+    offline: bool,
+
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long)]
+    legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Don't install pre-built wheels.
+    ///
+    /// The given packages will be installed from a source distribution. The resolver
+    /// will still use pre-built wheels for metadata.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long, value_parser = date_or_datetime, hide = true)]
+    exclude_newer: Option<DateTime<Utc>>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct SyncAllArgs {
+    /// Only sync the environment with the given name.
+    #[clap(long = "env")]
+    envs: Vec<String>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(
+        long,
+        env = "UV_OFFLINE",
+        conflicts_with = "refresh",
+        conflicts_with = "refresh_package"
+    )]
+    offline: bool,
+
+    /// Refresh all cached data.
+    #[clap(long)]
+    refresh: bool,
+
+    /// Refresh cached data for a specific package.
+    #[clap(long)]
+    refresh_package: Vec<PackageName>,
+
+    /// The method to use when installing packages from the global cache.
+    #[clap(long, value_enum)]
+    link_mode: Option<install_wheel_rs::linker::LinkMode>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Use legacy `setuptools` behavior when building source distributions without a
+    /// `pyproject.toml`.
+    #[clap(long)]
+    legacy_setup_py: bool,
+
+    /// Don't build source distributions.
+    ///
+    /// When enabled, resolving will not run arbitrary code. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Alias for `--only-binary :all:`.
+    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
+    no_build: bool,
+
+    /// Don't install pre-built wheels.
+    ///
+    /// The given packages will be installed from a source distribution. The resolver
+    /// will still use pre-built wheels for metadata.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    no_binary: Vec<PackageNameSpecifier>,
+
+    /// Only use pre-built wheels; don't build source distributions.
+    ///
+    /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
+    /// source distributions will be reused, but operations that require building distributions will
+    /// exit with an error.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    only_binary: Vec<PackageNameSpecifier>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct PublishArgs {
+    /// The wheels and source distributions to upload.
+    ///
+    /// Defaults to the wheels and source distributions in the `dist` directory.
+    files: Vec<PathBuf>,
+
+    /// The URL of the upload endpoint, which must implement the legacy upload API.
+    #[clap(
+        long,
+        default_value = "https://upload.pypi.org/legacy/",
+        env = "UV_PUBLISH_URL"
+    )]
+    publish_url: Url,
+
+    /// Upload to TestPyPI, rather than PyPI.
+    #[clap(long, conflicts_with = "publish_url")]
+    test_pypi: bool,
+
+    /// The username with which to authenticate.
+    #[clap(short, long, env = "UV_PUBLISH_USERNAME")]
+    username: Option<String>,
+
+    /// The password with which to authenticate.
+    #[clap(short, long, env = "UV_PUBLISH_PASSWORD", hide_env_values = true)]
+    password: Option<String>,
+
+    /// An API token with which to authenticate, in lieu of a username and password.
+    #[clap(
+        short,
+        long,
+        env = "UV_PUBLISH_TOKEN",
+        hide_env_values = true,
+        conflicts_with = "username",
+        conflicts_with = "password"
+    )]
+    token: Option<String>,
+
+    /// Retrieve the password for `--username` from the `keyring` CLI, if no password is provided.
+    #[clap(long, requires = "username")]
+    keyring: bool,
+
+    /// Authenticate via trusted publishing, by exchanging the OpenID Connect token of the current
+    /// GitHub Actions workflow for a short-lived API token.
+    #[clap(
+        long,
+        conflicts_with = "username",
+        conflicts_with = "password",
+        conflicts_with = "token"
+    )]
+    trusted_publishing: bool,
+
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct WhyArgs {
+    /// The installed package to explain.
+    package: PackageName,
+}
+
+#[derive(Args)]
+struct ExportGraphArgs {
+    #[command(flatten)]
+    compile: PipCompileArgs,
+
+    /// The format in which to write the resolution graph.
+    #[clap(long, value_enum, default_value_t)]
+    graph_format: GraphFormat,
+}
+
+#[derive(Args)]
+struct AuditArgs {
+    /// Audit the packages pinned in the given requirements files.
+    ///
+    /// Requirements that aren't pinned to an exact version (e.g., `flask==3.0.0`) are skipped. If
+    /// no requirements files are provided, the packages installed in the current environment are
+    /// audited instead.
+    #[clap(short, long)]
+    requirement: Vec<PathBuf>,
+
+    /// Read advisories from a local directory of OSV records (`.json` files), rather than querying
+    /// the OSV API.
+    #[clap(long, value_name = "PATH")]
+    advisory_db: Option<PathBuf>,
+
+    /// Hosts to trust, disabling TLS certificate verification for them and permitting plain HTTP.
+    ///
+    /// Accepts a hostname, optionally followed by a port (e.g., `internal.example.com:8080`).
+    #[clap(long, env = "UV_TRUSTED_HOST", value_delimiter = ' ')]
+    trusted_host: Vec<TrustedHost>,
+
+    /// Run offline, i.e., without accessing the network.
+    #[clap(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct ServeApiArgs {
+    #[clap(long, value_enum)]
+    resolution: Option<ResolutionMode>,
+
+    #[clap(long, value_enum)]
+    prerelease: Option<PreReleaseMode>,
+
+    #[command(flatten)]
+    index_args: IndexArgs,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(long, env = "UV_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Args)]
+struct GenerateShellCompletionArgs {
+    /// The shell for which to generate the completion script.
+    #[clap(value_enum)]
+    shell: clap_complete::Shell,
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// The lockfile to import (e.g., `poetry.lock` or `Pipfile.lock`).
+    lockfile: PathBuf,
+
+    /// The format of the lockfile.
+    ///
+    /// Inferred from the filename by default.
+    #[clap(long, value_enum)]
+    format: Option<LockfileFormat>,
+
+    /// Include development dependencies (e.g., Poetry's non-`main` groups, or Pipenv's `develop`
+    /// section).
+    #[clap(long)]
+    dev: bool,
+
+    /// Include optional dependencies that are only required by an extra.
+    #[clap(long)]
+    all_extras: bool,
+
+    /// Write the requirements to the given `requirements.txt` file, rather than to stdout.
+    #[clap(short, long)]
+    output_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct AddArgs {
     /// The name of the package to add (e.g., `Django==4.2.6`).
     name: String,
 }
@@ -708,6 +1893,7 @@ async fn run() -> Result<ExitStatus> {
                         );
                     }
                     "sync" => {
+                        // Replaces clap's own suggestion, which would otherwise be `sync-all`.
                         err.insert(
                             ContextKind::SuggestedSubcommand,
                             ContextValue::String("uv pip sync".to_string()),
@@ -734,28 +1920,43 @@ async fn run() -> Result<ExitStatus> {
                     _ => {}
                 }
             }
+            // Usage errors exit with a dedicated code; `--help` and `--version` exit successfully.
+            if err.use_stderr() {
+                err.print()?;
+                return Ok(ExitStatus::Usage);
+            }
             err.exit()
         }
     };
 
+    // Configure the color choice before any output is written, such that it applies to both
+    // user-facing output and internal logging.
+    if cli.no_color {
+        anstream::ColorChoice::write_global(anstream::ColorChoice::Never);
+    } else {
+        anstream::ColorChoice::write_global(cli.color.into());
+    }
+
     // Configure the `tracing` crate, which controls internal logging.
     #[cfg(feature = "tracing-durations-export")]
     let (duration_layer, _duration_guard) = logging::setup_duration();
     #[cfg(not(feature = "tracing-durations-export"))]
     let duration_layer = None::<tracing_subscriber::layer::Identity>;
+    let timings = cli.timings.then(Timings::default);
+    let trace = cli.trace_output.is_some().then(ChromeTrace::default);
     logging::setup_logging(
-        if cli.verbose {
-            logging::Level::Verbose
-        } else {
-            logging::Level::Default
-        },
+        logging::Level::from_verbosity(cli.verbose),
+        cli.log_format,
+        anstream::AutoStream::choice(&std::io::stderr()) != anstream::ColorChoice::Never,
         duration_layer,
+        timings.as_ref().map(Timings::layer),
+        trace.as_ref().map(ChromeTrace::layer),
     );
 
     // Configure the `Printer`, which controls user-facing output in the CLI.
     let printer = if cli.quiet {
         printer::Printer::Quiet
-    } else if cli.verbose {
+    } else if cli.verbose > 0 {
         printer::Printer::Verbose
     } else {
         printer::Printer::Default
@@ -766,12 +1967,6 @@ async fn run() -> Result<ExitStatus> {
         uv_warnings::enable();
     }
 
-    if cli.no_color {
-        anstream::ColorChoice::write_global(anstream::ColorChoice::Never);
-    } else {
-        anstream::ColorChoice::write_global(cli.color.into());
-    }
-
     miette::set_hook(Box::new(|_| {
         Box::new(
             miette::MietteHandlerOpts::new()
@@ -783,19 +1978,239 @@ async fn run() -> Result<ExitStatus> {
         )
     }))?;
 
-    let cache = Cache::try_from(cli.cache_args)?;
+    // Load the persistent configuration, if any.
+    let options = if let Some(config_file) = cli.config_file.as_deref() {
+        Options::from_file(config_file)?
+    } else if cli.no_config {
+        Options::default()
+    } else {
+        let project = Options::find_project(&env::current_dir()?)?;
+        let user = Options::find_user()?;
+        project
+            .unwrap_or_default()
+            .combine(user.unwrap_or_default())
+    };
+
+    let cache = Cache::try_from(
+        cli.cache_args
+            .with_default_cache_dir(options.cache_dir.clone()),
+    )?;
 
-    match cli.command {
+    // Determine the concurrency limits. Unless the install limit is explicit, the thread pool used
+    // for installs is sized once an install is planned.
+    let concurrency = cli.concurrency_args.detect()?;
+
+    let timeouts = HttpTimeouts {
+        connect: cli.connect_timeout.map(Duration::from_secs),
+        request: cli
+            .timeout
+            .map_or(HttpTimeouts::DEFAULT_REQUEST, Duration::from_secs),
+    };
+
+    let start = std::time::Instant::now();
+
+    let result = match cli.command {
+        Commands::Pip(PipArgs {
+            command: PipCommand::Compile(args),
+        }) => pip_compile(args, None, &options, timeouts, concurrency, cache, printer).await,
+        Commands::Pip(PipArgs {
+            command: PipCommand::Sync(args),
+        }) => {
+            args.compat_args.validate()?;
+
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
+            let sources = args
+                .src_file
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
+            let no_binary = NoBinary::from_args(args.no_binary);
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            commands::pip_sync(
+                &sources,
+                &reinstall,
+                !args.inexact,
+                args.link_mode.or(options.link_mode).unwrap_or_default(),
+                &install_wheel_rs::Shebang {
+                    style: args.shebang.unwrap_or_default(),
+                    interpreter: args.shebang_interpreter,
+                },
+                index_urls,
+                trusted_hosts,
+                if args.legacy_setup_py {
+                    SetupPyStrategy::Setuptools
+                } else {
+                    SetupPyStrategy::Pep517
+                },
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                &no_build,
+                &no_binary,
+                args.strict,
+                args.keep_going,
+                args.verify_hashes,
+                args.format,
+                args.build_manifest.as_deref(),
+                args.report,
+                args.report_json.as_deref(),
+                args.python.as_deref(),
+                timeouts,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipArgs {
+            command: PipCommand::Install(args),
+        }) => {
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let requirements = args
+                .package
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .chain(args.editable.into_iter().map(RequirementsSource::Editable))
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
+                .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let overrides = args
+                .r#override
+                .into_iter()
+                .map(RequirementsSource::from_path)
+                .collect::<Vec<_>>();
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
+            let extras = if args.all_extras {
+                ExtrasSpecification::All
+            } else if args.extra.is_empty() {
+                ExtrasSpecification::None
+            } else {
+                ExtrasSpecification::Some(&args.extra)
+            };
+            let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
+            let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+            let no_binary = NoBinary::from_args(args.no_binary);
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let dependency_mode = if args.no_deps {
+                DependencyMode::Direct
+            } else {
+                DependencyMode::Transitive
+            };
+            commands::pip_install(
+                &requirements,
+                &constraints,
+                &overrides,
+                args.alias,
+                &extras,
+                &args.group,
+                args.resolution.or(options.resolution).unwrap_or_default(),
+                args.prerelease.or(options.prerelease).unwrap_or_default(),
+                dependency_mode,
+                args.prefer_binary,
+                upgrade,
+                index_urls,
+                trusted_hosts,
+                &reinstall,
+                args.link_mode.or(options.link_mode).unwrap_or_default(),
+                &install_wheel_rs::Shebang {
+                    style: args.shebang.unwrap_or_default(),
+                    interpreter: args.shebang_interpreter,
+                },
+                if args.legacy_setup_py {
+                    SetupPyStrategy::Setuptools
+                } else {
+                    SetupPyStrategy::Pep517
+                },
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                &no_build,
+                &no_binary,
+                &options.policy.clone().unwrap_or_default(),
+                args.strict,
+                args.verify_hashes,
+                args.exclude_newer,
+                args.build_manifest.as_deref(),
+                args.python.as_deref(),
+                timeouts,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipArgs {
+            command: PipCommand::Uninstall(args),
+        }) => {
+            let sources = args
+                .package
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .chain(args.editable.into_iter().map(RequirementsSource::Editable))
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
+                .collect::<Vec<_>>();
+            commands::pip_uninstall(&sources, cache, printer).await
+        }
+        Commands::Pip(PipArgs {
+            command: PipCommand::Freeze(args),
+        }) => {
+            let (index_locations, trusted_hosts) = args.index_args.resolve(&options);
+            commands::freeze(
+                args.strict,
+                args.lock,
+                if args.hash_algorithm.is_empty() {
+                    &[HashAlgorithm::Sha256]
+                } else {
+                    &args.hash_algorithm
+                },
+                index_locations,
+                trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
         Commands::Pip(PipArgs {
-            command: PipCommand::Compile(args),
+            command: PipCommand::List(args),
+        }) => commands::pip_list(args.format, &cache, printer),
+        Commands::Pip(PipArgs {
+            command: PipCommand::Download(args),
         }) => {
-            args.compat_args.validate()?;
-
             let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
             let requirements = args
-                .src_file
+                .package
                 .into_iter()
-                .map(RequirementsSource::from_path)
+                .map(RequirementsSource::from_package)
+                .chain(
+                    args.requirement
+                        .into_iter()
+                        .map(RequirementsSource::from_path),
+                )
                 .collect::<Vec<_>>();
             let constraints = args
                 .constraint
@@ -807,42 +2222,23 @@ async fn run() -> Result<ExitStatus> {
                 .into_iter()
                 .map(RequirementsSource::from_path)
                 .collect::<Vec<_>>();
-            let index_urls = IndexLocations::from_args(
-                args.index_url,
-                args.extra_index_url,
-                args.find_links,
-                args.no_index,
-            );
-            let extras = if args.all_extras {
-                ExtrasSpecification::All
-            } else if args.extra.is_empty() {
-                ExtrasSpecification::None
-            } else {
-                ExtrasSpecification::Some(&args.extra)
-            };
-            let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
             let dependency_mode = if args.no_deps {
                 DependencyMode::Direct
             } else {
                 DependencyMode::Transitive
             };
-            commands::pip_compile(
+            commands::pip_download(
                 &requirements,
                 &constraints,
                 &overrides,
-                extras,
-                args.output_file.as_deref(),
-                args.resolution,
-                args.prerelease,
+                &args.dest,
+                args.resolution.or(options.resolution).unwrap_or_default(),
+                args.prerelease.or(options.prerelease).unwrap_or_default(),
                 dependency_mode,
-                upgrade,
-                args.generate_hashes,
-                !args.no_annotate,
-                !args.no_header,
-                args.emit_index_url,
-                args.emit_find_links,
                 index_urls,
+                trusted_hosts,
                 if args.legacy_setup_py {
                     SetupPyStrategy::Setuptools
                 } else {
@@ -855,114 +2251,173 @@ async fn run() -> Result<ExitStatus> {
                 },
                 &no_build,
                 args.python_version,
+                args.platform,
+                args.manifest.as_deref(),
                 args.exclude_newer,
+                timeouts,
+                concurrency,
                 cache,
                 printer,
             )
             .await
         }
-        Commands::Pip(PipArgs {
-            command: PipCommand::Sync(args),
+        Commands::Clean(args)
+        | Commands::Cache(CacheNamespace {
+            command: CacheCommand::Clean(args),
+        }) => commands::clean(&cache, &args.package, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Prune,
+        }) => commands::cache_prune(&cache, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Dir,
+        }) => {
+            commands::cache_dir(&cache);
+            Ok(ExitStatus::Success)
+        }
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Info(args),
+        }) => commands::cache_info(&cache, &args.package, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Export(args),
+        }) => commands::cache_export(&cache, &args.path, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Import(args),
+        }) => commands::cache_import(&cache, &args.path, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Warm(args),
         }) => {
-            args.compat_args.validate()?;
-
-            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
-            let index_urls = IndexLocations::from_args(
-                args.index_url,
-                args.extra_index_url,
-                args.find_links,
-                args.no_index,
-            );
             let sources = args
-                .src_file
+                .requirement
                 .into_iter()
                 .map(RequirementsSource::from_path)
                 .collect::<Vec<_>>();
-            let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
             let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
-            commands::pip_sync(
+            commands::cache_warm(
                 &sources,
-                &reinstall,
-                args.link_mode,
+                args.python.as_deref(),
                 index_urls,
+                trusted_hosts,
                 if args.legacy_setup_py {
                     SetupPyStrategy::Setuptools
                 } else {
                     SetupPyStrategy::Pep517
                 },
+                &no_build,
+                &no_binary,
+                args.interval.map(Duration::from_secs),
+                args.time_limit.map(Duration::from_secs),
+                timeouts,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Venv(args) => {
+            args.compat_args.validate()?;
+
+            let (index_locations, trusted_hosts) = args.index_args.resolve(&options);
+            commands::venv(
+                &args.name,
+                args.python.as_deref(),
+                &index_locations,
+                &trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                args.seed,
+                args.prompt.as_deref(),
+                args.system_site_packages,
+                args.relocatable,
+                args.exclude_newer,
+                timeouts,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Tool(ToolArgs {
+            command: ToolCommand::Run(args),
+        }) => {
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let (index_locations, trusted_hosts) = args.index_args.resolve(&options);
+            let no_binary = NoBinary::from_args(args.no_binary);
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            commands::tool_run(
+                &args.command,
+                &args.args,
+                args.from.as_deref(),
+                args.python.as_deref(),
+                &index_locations,
+                &trusted_hosts,
                 if args.offline {
                     Connectivity::Offline
                 } else {
                     Connectivity::Online
                 },
+                args.upgrade,
+                if args.legacy_setup_py {
+                    SetupPyStrategy::Setuptools
+                } else {
+                    SetupPyStrategy::Pep517
+                },
                 &no_build,
                 &no_binary,
-                args.strict,
-                cache,
+                args.exclude_newer,
+                timeouts,
+                concurrency,
+                &cache,
                 printer,
             )
             .await
         }
-        Commands::Pip(PipArgs {
-            command: PipCommand::Install(args),
-        }) => {
+        Commands::Run(args) => {
             let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
-            let requirements = args
-                .package
-                .into_iter()
-                .map(RequirementsSource::from_package)
-                .chain(args.editable.into_iter().map(RequirementsSource::Editable))
-                .chain(
-                    args.requirement
-                        .into_iter()
-                        .map(RequirementsSource::from_path),
-                )
-                .collect::<Vec<_>>();
-            let constraints = args
-                .constraint
-                .into_iter()
-                .map(RequirementsSource::from_path)
-                .collect::<Vec<_>>();
-            let overrides = args
-                .r#override
-                .into_iter()
-                .map(RequirementsSource::from_path)
-                .collect::<Vec<_>>();
-            let index_urls = IndexLocations::from_args(
-                args.index_url,
-                args.extra_index_url,
-                args.find_links,
-                args.no_index,
-            );
-            let extras = if args.all_extras {
-                ExtrasSpecification::All
-            } else if args.extra.is_empty() {
-                ExtrasSpecification::None
-            } else {
-                ExtrasSpecification::Some(&args.extra)
-            };
-            let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
-            let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+            let (index_locations, trusted_hosts) = args.index_args.resolve(&options);
             let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
-            let dependency_mode = if args.no_deps {
-                DependencyMode::Direct
-            } else {
-                DependencyMode::Transitive
-            };
-            commands::pip_install(
-                &requirements,
-                &constraints,
-                &overrides,
-                &extras,
-                args.resolution,
-                args.prerelease,
-                dependency_mode,
-                upgrade,
+            commands::run(
+                &args.script,
+                &args.args,
+                args.python.as_deref(),
+                &index_locations,
+                &trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                args.upgrade,
+                if args.legacy_setup_py {
+                    SetupPyStrategy::Setuptools
+                } else {
+                    SetupPyStrategy::Pep517
+                },
+                &no_build,
+                &no_binary,
+                args.exclude_newer,
+                timeouts,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::SyncAll(args) => {
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
+            let no_binary = NoBinary::from_args(args.no_binary);
+            let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            commands::sync_all(
+                &args.envs,
+                args.link_mode.or(options.link_mode).unwrap_or_default(),
                 index_urls,
-                &reinstall,
-                args.link_mode,
+                trusted_hosts,
                 if args.legacy_setup_py {
                     SetupPyStrategy::Setuptools
                 } else {
@@ -975,60 +2430,288 @@ async fn run() -> Result<ExitStatus> {
                 },
                 &no_build,
                 &no_binary,
-                args.strict,
-                args.exclude_newer,
+                timeouts,
+                concurrency,
                 cache,
                 printer,
             )
             .await
         }
-        Commands::Pip(PipArgs {
-            command: PipCommand::Uninstall(args),
-        }) => {
+        Commands::Publish(args) => {
+            let publish_url = if args.test_pypi {
+                Url::parse("https://test.pypi.org/legacy/")?
+            } else {
+                args.publish_url
+            };
+            commands::publish(
+                &args.files,
+                &publish_url,
+                commands::PublishCredentials {
+                    username: args.username,
+                    password: args.password,
+                    token: args.token,
+                    keyring: args.keyring,
+                    trusted_publishing: args.trusted_publishing,
+                },
+                options.trusted_hosts(args.trusted_host),
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Audit(args) => {
             let sources = args
-                .package
+                .requirement
                 .into_iter()
-                .map(RequirementsSource::from_package)
-                .chain(args.editable.into_iter().map(RequirementsSource::Editable))
-                .chain(
-                    args.requirement
-                        .into_iter()
-                        .map(RequirementsSource::from_path),
-                )
+                .map(RequirementsSource::from_path)
                 .collect::<Vec<_>>();
-            commands::pip_uninstall(&sources, cache, printer).await
+            commands::audit(
+                &sources,
+                args.advisory_db.as_deref(),
+                options.trusted_hosts(args.trusted_host),
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
         }
-        Commands::Pip(PipArgs {
-            command: PipCommand::Freeze(args),
-        }) => commands::freeze(&cache, args.strict, printer),
-        Commands::Clean(args) => commands::clean(&cache, &args.package, printer),
-        Commands::Venv(args) => {
-            args.compat_args.validate()?;
-
-            let index_locations = IndexLocations::from_args(
-                args.index_url,
-                args.extra_index_url,
-                // No find links for the venv subcommand, to keep things simple
-                Vec::new(),
-                args.no_index,
-            );
-            commands::venv(
-                &args.name,
-                args.python.as_deref(),
-                &index_locations,
+        Commands::Why(args) => commands::why(&args.package, &cache, printer),
+        Commands::ExportGraph(args) => {
+            pip_compile(
+                args.compile,
+                Some(args.graph_format),
+                &options,
+                timeouts,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Import(args) => commands::import_lockfile(
+            &args.lockfile,
+            args.format,
+            args.dev,
+            args.all_extras,
+            args.output_file.as_deref(),
+            printer,
+        ),
+        Commands::ServeApi(args) => {
+            let (index_urls, trusted_hosts) = args.index_args.resolve(&options);
+            commands::serve_api(
+                index_urls,
+                trusted_hosts,
+                args.resolution.or(options.resolution).unwrap_or_default(),
+                args.prerelease.or(options.prerelease).unwrap_or_default(),
                 if args.offline {
                     Connectivity::Offline
                 } else {
                     Connectivity::Online
                 },
-                args.seed,
-                args.exclude_newer,
+                timeouts,
+                concurrency,
+                cache,
+            )
+            .await
+        }
+        Commands::Python(PythonNamespace {
+            command: PythonCommand::List(args),
+        }) => {
+            let trusted_hosts = options.trusted_hosts(args.trusted_host);
+            commands::python_list(
+                args.available,
+                trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Python(PythonNamespace {
+            command: PythonCommand::Install(args),
+        }) => {
+            let trusted_hosts = options.trusted_hosts(args.trusted_host);
+            commands::python_install(
+                &args.versions,
+                args.force,
+                args.allow_unverified,
+                trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Self_(SelfNamespace {
+            command: SelfCommand::Update(args),
+        }) => {
+            let trusted_hosts = options.trusted_hosts(args.trusted_host);
+            commands::self_update(
+                trusted_hosts,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                timeouts,
                 &cache,
                 printer,
             )
             .await
         }
+        Commands::Debug(DebugNamespace {
+            command: DebugCommand::Tags(args),
+        }) => commands::debug_tags(args.python.as_deref(), &args.wheels, &cache, printer),
+        Commands::GenerateShellCompletion(args) => {
+            commands::generate_shell_completion(args.shell, &mut Cli::command())
+        }
+    };
+
+    if let Some(timings) = timings {
+        timings.report(start.elapsed(), printer)?;
+    }
+    if let (Some(trace), Some(trace_output)) = (trace, cli.trace_output.as_deref()) {
+        trace.write(trace_output)?;
     }
+
+    result
+}
+
+/// Run `pip compile`, writing the resolution graph in the given format instead of a requirements
+/// file if one is provided (as for `export-graph`).
+async fn pip_compile(
+    args: PipCompileArgs,
+    graph_format: Option<GraphFormat>,
+    options: &Options,
+    timeouts: HttpTimeouts,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: printer::Printer,
+) -> Result<ExitStatus> {
+    args.compat_args.validate()?;
+
+    let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+    let requirements = args
+        .src_file
+        .into_iter()
+        .map(RequirementsSource::from_path)
+        .collect::<Vec<_>>();
+    let constraints = args
+        .constraint
+        .into_iter()
+        .map(RequirementsSource::from_path)
+        .collect::<Vec<_>>();
+    let overrides = args
+        .r#override
+        .into_iter()
+        .map(RequirementsSource::from_path)
+        .collect::<Vec<_>>();
+    let (index_urls, trusted_hosts) = args.index_args.resolve(options);
+    // Unsafe packages are pinned by default, unless `--no-allow-unsafe` or
+    // `--unsafe-package` is provided.
+    let unsafe_packages =
+        if args.no_allow_unsafe || (!args.allow_unsafe && !args.unsafe_package.is_empty()) {
+            if args.unsafe_package.is_empty() {
+                vec![
+                    PackageName::from_str("pip")?,
+                    PackageName::from_str("setuptools")?,
+                    PackageName::from_str("wheel")?,
+                ]
+            } else {
+                args.unsafe_package
+            }
+        } else {
+            Vec::new()
+        };
+    let extras = if args.all_extras {
+        ExtrasSpecification::All
+    } else if args.extra.is_empty() {
+        ExtrasSpecification::None
+    } else {
+        ExtrasSpecification::Some(&args.extra)
+    };
+    let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+    let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+    let dependency_mode = if args.no_deps {
+        DependencyMode::Direct
+    } else {
+        DependencyMode::Transitive
+    };
+    commands::pip_compile(
+        &requirements,
+        &constraints,
+        &overrides,
+        args.alias,
+        extras,
+        &args.group,
+        args.output_file
+            .as_deref()
+            .filter(|output_file| *output_file != Path::new("-")),
+        args.check,
+        args.changes_file.as_deref(),
+        args.resolution.or(options.resolution).unwrap_or_default(),
+        args.prerelease.or(options.prerelease).unwrap_or_default(),
+        dependency_mode,
+        args.prefer_binary,
+        upgrade,
+        args.generate_hashes,
+        if args.hash_algorithm.is_empty() {
+            &[HashAlgorithm::Sha256]
+        } else {
+            &args.hash_algorithm
+        },
+        !args.no_annotate,
+        !args.no_header,
+        args.emit_index_url,
+        args.emit_find_links,
+        &unsafe_packages,
+        args.format,
+        graph_format,
+        index_urls,
+        trusted_hosts,
+        if args.legacy_setup_py {
+            SetupPyStrategy::Setuptools
+        } else {
+            SetupPyStrategy::Pep517
+        },
+        if args.offline {
+            Connectivity::Offline
+        } else {
+            Connectivity::Online
+        },
+        &no_build,
+        &options.policy.clone().unwrap_or_default(),
+        args.python_version,
+        args.requires_python,
+        args.exclude_newer,
+        timeouts,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await
 }
 
 fn main() -> ExitCode {
@@ -1067,7 +2750,7 @@ fn main() -> ExitCode {
             for err in causes {
                 eprintln!("  {}: {}", "Caused by".red().bold(), err);
             }
-            ExitStatus::Error.into()
+            ExitStatus::from_error(&err).into()
         }
     }
 }
@@ -0,0 +1,362 @@
+//! Enforcement of a package policy, read from the `policy` table of the persistent configuration
+//! (e.g., `[tool.uv.policy]` in a `pyproject.toml`).
+//!
+//! The policy is evaluated against the resolution, after the fact, such that CI can reject any
+//! resolution that includes a denied package, version, license, or source.
+
+use std::fmt::{Display, Formatter, Write};
+use std::iter::Peekable;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+use distribution_types::VersionOrUrl;
+use pep508_rs::{Requirement, VerbatimUrl};
+use pypi_types::DeclaredLicense;
+use uv_resolver::ResolutionGraph;
+
+use crate::printer::Printer;
+
+/// The packages, versions, licenses, and sources that are denied in a resolution.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Policy {
+    /// The denied packages, as requirements. A bare name (e.g., `pycrypto`) denies every version of
+    /// the package, while a requirement with a specifier (e.g., `urllib3<2`) denies only the
+    /// matching versions.
+    #[serde(default)]
+    deny: Vec<Requirement>,
+    /// The denied licenses, as SPDX expressions (e.g., `GPL-3.0-only`), which are matched against
+    /// the declared license of each package (see [`license_denied`]).
+    #[serde(default)]
+    deny_licenses: Vec<String>,
+    /// The denied sources (e.g., `git`, to forbid any Git dependencies).
+    #[serde(default)]
+    deny_sources: Vec<Source>,
+}
+
+impl CacheKey for Policy {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.deny.len().cache_key(state);
+        for requirement in &self.deny {
+            requirement.to_string().cache_key(state);
+        }
+        self.deny_licenses.cache_key(state);
+        self.deny_sources.cache_key(state);
+    }
+}
+
+impl Policy {
+    /// Evaluate the policy against a resolution, returning any violations, sorted by package name.
+    pub(crate) fn evaluate(&self, resolution: &ResolutionGraph) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (name, version_or_url) in resolution.pins() {
+            let package = format!("{name}{version_or_url}");
+
+            for requirement in &self.deny {
+                if requirement.name != *name {
+                    continue;
+                }
+                let denied = match (&requirement.version_or_url, &version_or_url) {
+                    (None, _) => true,
+                    (
+                        Some(pep508_rs::VersionOrUrl::VersionSpecifier(specifiers)),
+                        VersionOrUrl::Version(version),
+                    ) => specifiers.contains(version),
+                    (Some(pep508_rs::VersionOrUrl::Url(denied)), VersionOrUrl::Url(url)) => {
+                        denied.raw() == url.raw()
+                    }
+                    _ => false,
+                };
+                if denied {
+                    violations.push(Violation {
+                        package: package.clone(),
+                        reason: Reason::Package(requirement.clone()),
+                    });
+                }
+            }
+
+            if let Some(license) = resolution.license(name) {
+                for denied in &self.deny_licenses {
+                    if license_denied(license, denied) {
+                        violations.push(Violation {
+                            package: package.clone(),
+                            reason: Reason::License(denied.clone()),
+                        });
+                    }
+                }
+            }
+
+            let source = match version_or_url {
+                VersionOrUrl::Version(_) => Source::Registry,
+                VersionOrUrl::Url(url) => Source::from_url(url),
+            };
+            if self.deny_sources.contains(&source) {
+                violations.push(Violation {
+                    package,
+                    reason: Reason::Source(source),
+                });
+            }
+        }
+        violations.sort_by(|a, b| a.package.cmp(&b.package));
+        violations
+    }
+}
+
+/// Returns `true` if the declared license is denied by the given SPDX expression.
+///
+/// If the package declares a `License-Expression`, it's authoritative: the package is denied if
+/// the expression matches the denied expression as a whole, or if every license the expression
+/// offers a choice between requires a denied term (e.g., `GPL-3.0-only` denies
+/// `GPL-3.0-only AND MIT`, but not `GPL-3.0-only OR MIT`, nor
+/// `GPL-3.0-only WITH GCC-exception-3.1`).
+///
+/// Otherwise, the free-form `License` field is matched in the same way (if it's a valid SPDX
+/// expression) or verbatim, and the license classifiers are mapped to SPDX identifiers where
+/// possible, with several classifiers offering a choice between them.
+fn license_denied(license: &DeclaredLicense, denied: &str) -> bool {
+    let denied_expression = SpdxExpression::parse(denied);
+    let matches = |candidate: &str| match (SpdxExpression::parse(candidate), &denied_expression) {
+        (Some(candidate), Some(denied)) => candidate.is_denied_by(denied),
+        _ => candidate.trim().eq_ignore_ascii_case(denied.trim()),
+    };
+
+    if let Some(expression) = &license.expression {
+        return matches(expression);
+    }
+
+    if license.text.as_deref().is_some_and(matches) {
+        return true;
+    }
+
+    !license.classifiers.is_empty()
+        && license.classifiers.iter().all(|classifier| {
+            classifier.eq_ignore_ascii_case(denied.trim())
+                || classifier_identifier(classifier).is_some_and(matches)
+        })
+}
+
+/// Map a license classifier to the equivalent SPDX identifier, if it's unambiguous.
+fn classifier_identifier(classifier: &str) -> Option<&'static str> {
+    let name = classifier.rsplit(" :: ").next()?;
+    let identifier = match name {
+        "Apache Software License" => "Apache-2.0",
+        "GNU Affero General Public License v3" => "AGPL-3.0-only",
+        "GNU Affero General Public License v3 or later (AGPLv3+)" => "AGPL-3.0-or-later",
+        "GNU General Public License v2 (GPLv2)" => "GPL-2.0-only",
+        "GNU General Public License v2 or later (GPLv2+)" => "GPL-2.0-or-later",
+        "GNU General Public License v3 (GPLv3)" => "GPL-3.0-only",
+        "GNU General Public License v3 or later (GPLv3+)" => "GPL-3.0-or-later",
+        "GNU Lesser General Public License v2 (LGPLv2)" => "LGPL-2.0-only",
+        "GNU Lesser General Public License v2 or later (LGPLv2+)" => "LGPL-2.0-or-later",
+        "GNU Lesser General Public License v3 (LGPLv3)" => "LGPL-3.0-only",
+        "GNU Lesser General Public License v3 or later (LGPLv3+)" => "LGPL-3.0-or-later",
+        "ISC License (ISCL)" => "ISC",
+        "MIT License" => "MIT",
+        "MIT No Attribution License (MIT-0)" => "MIT-0",
+        "Mozilla Public License 2.0 (MPL 2.0)" => "MPL-2.0",
+        "Python Software Foundation License" => "PSF-2.0",
+        "The Unlicense (Unlicense)" => "Unlicense",
+        _ => return None,
+    };
+    Some(identifier)
+}
+
+/// A parsed SPDX license expression (e.g., `(MIT OR Apache-2.0) AND BSD-3-Clause`).
+///
+/// See: <https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/>
+#[derive(Debug, PartialEq, Eq)]
+enum SpdxExpression {
+    /// A license identifier, with an optional exception (e.g., `GPL-3.0-only WITH
+    /// GCC-exception-3.1`), normalized to lowercase.
+    License(String),
+    /// A conjunction, all of which apply.
+    And(Vec<SpdxExpression>),
+    /// A disjunction, any of which may be chosen.
+    Or(Vec<SpdxExpression>),
+}
+
+impl SpdxExpression {
+    /// Parse an SPDX expression, returning `None` if it's invalid.
+    fn parse(expression: &str) -> Option<Self> {
+        let expression = expression.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = expression.split_whitespace().peekable();
+        let parsed = Self::parse_or(&mut tokens)?;
+        tokens.next().is_none().then_some(parsed)
+    }
+
+    fn parse_or<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut terms = vec![Self::parse_and(tokens)?];
+        while next_keyword(tokens, "OR") {
+            terms.push(Self::parse_and(tokens)?);
+        }
+        Some(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Self::Or(terms)
+        })
+    }
+
+    fn parse_and<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut terms = vec![Self::parse_term(tokens)?];
+        while next_keyword(tokens, "AND") {
+            terms.push(Self::parse_term(tokens)?);
+        }
+        Some(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Self::And(terms)
+        })
+    }
+
+    fn parse_term<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let token = tokens.next()?;
+        if token == "(" {
+            let expression = Self::parse_or(tokens)?;
+            return (tokens.next()? == ")").then_some(expression);
+        }
+        if !is_identifier(token) {
+            return None;
+        }
+        let mut license = token.to_ascii_lowercase();
+        if next_keyword(tokens, "WITH") {
+            let exception = tokens.next().filter(|token| is_identifier(token))?;
+            license.push_str(" with ");
+            license.push_str(&exception.to_ascii_lowercase());
+        }
+        Some(Self::License(license))
+    }
+
+    /// Returns `true` if the expression is denied by the given expression: either it matches as
+    /// a whole, or every choice it offers requires a denied term.
+    fn is_denied_by(&self, denied: &Self) -> bool {
+        if self == denied {
+            return true;
+        }
+        match self {
+            Self::License(_) => false,
+            Self::And(terms) => terms.iter().any(|term| term.is_denied_by(denied)),
+            Self::Or(terms) => terms.iter().all(|term| term.is_denied_by(denied)),
+        }
+    }
+}
+
+/// Consume the next token if it's the given keyword (e.g., `AND`), ignoring case.
+fn next_keyword<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>, keyword: &str) -> bool {
+    tokens
+        .next_if(|token| token.eq_ignore_ascii_case(keyword))
+        .is_some()
+}
+
+/// Returns `true` if the token is a license or exception identifier, rather than an operator or
+/// parenthesis.
+fn is_identifier(token: &str) -> bool {
+    !matches!(token, "(" | ")")
+        && !["AND", "OR", "WITH"]
+            .iter()
+            .any(|keyword| token.eq_ignore_ascii_case(keyword))
+}
+
+/// The source from which a package is installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Source {
+    /// A package index (e.g., PyPI).
+    Registry,
+    /// A Git repository.
+    Git,
+    /// A remote archive, referenced by URL.
+    Url,
+    /// A local file or directory.
+    Path,
+}
+
+impl CacheKey for Source {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Registry => 0u8.cache_key(state),
+            Self::Git => 1u8.cache_key(state),
+            Self::Url => 2u8.cache_key(state),
+            Self::Path => 3u8.cache_key(state),
+        }
+    }
+}
+
+impl Source {
+    fn from_url(url: &VerbatimUrl) -> Self {
+        match url.scheme() {
+            "file" => Self::Path,
+            scheme if scheme.starts_with("git+") => Self::Git,
+            _ => Self::Url,
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Registry => write!(f, "registry"),
+            Self::Git => write!(f, "Git"),
+            Self::Url => write!(f, "URL"),
+            Self::Path => write!(f, "path"),
+        }
+    }
+}
+
+/// A package in a resolution that violates the policy.
+#[derive(Debug)]
+pub(crate) struct Violation {
+    /// The pinned package (e.g., `pycrypto==2.6.1`).
+    package: String,
+    reason: Reason,
+}
+
+#[derive(Debug)]
+enum Reason {
+    /// The package (or version) is denied by the given requirement.
+    Package(Requirement),
+    /// The package declares a denied license.
+    License(String),
+    /// The package is installed from a denied source.
+    Source(Source),
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Package(requirement) => write!(f, "is denied by `{requirement}`"),
+            Self::License(license) => write!(f, "has a denied license (`{license}`)"),
+            Self::Source(source) => write!(f, "is from a denied {source} source"),
+        }
+    }
+}
+
+/// Report the policy violations in a resolution.
+pub(crate) fn report_violations(violations: &[Violation], mut printer: Printer) -> Result<()> {
+    let s = if violations.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}{} The resolution violates the package policy ({})",
+        "error".red().bold(),
+        ":".bold(),
+        format!("{} violation{s}", violations.len()).bold()
+    )?;
+    for violation in violations {
+        writeln!(
+            printer,
+            " {} {} {}",
+            "x".red(),
+            violation.package.white().bold(),
+            violation.reason
+        )?;
+    }
+    writeln!(
+        printer,
+        "{}{} Add a constraint to exclude the denied packages, or update the configured policy",
+        "hint".cyan().bold(),
+        ":".bold()
+    )?;
+    Ok(())
+}
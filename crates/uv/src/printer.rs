@@ -15,6 +15,8 @@ impl Printer {
     /// Return the [`ProgressDrawTarget`] for this printer.
     pub(crate) fn target(self) -> ProgressDrawTarget {
         match self {
+            // If stderr isn't a terminal, `indicatif` draws nothing, leaving only the plain
+            // summary lines (e.g., "Downloaded 3 packages in 1.2s").
             Self::Default => ProgressDrawTarget::stderr(),
             Self::Quiet => ProgressDrawTarget::hidden(),
             // Confusingly, hide the progress bar when in verbose mode.
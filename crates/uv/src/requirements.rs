@@ -8,11 +8,13 @@ use rustc_hash::FxHashSet;
 
 use distribution_types::{FlatIndexLocation, IndexUrl};
 use pep508_rs::Requirement;
-use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
+use requirements_txt::{parse_wheel_path, EditableRequirement, FindLink, RequirementsTxt};
 use uv_fs::Normalized;
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName, PackageNameSpecifier};
 
 use crate::confirm;
+use crate::dependency_groups::DependencyGroups;
+use crate::workspace::Workspace;
 
 #[derive(Debug)]
 pub(crate) enum RequirementsSource {
@@ -112,6 +114,8 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) editables: Vec<EditableRequirement>,
     /// The extras used to collect requirements.
     pub(crate) extras: FxHashSet<ExtraName>,
+    /// The dependency groups used to collect requirements.
+    pub(crate) groups: FxHashSet<GroupName>,
     /// The index URL to use for fetching packages.
     pub(crate) index_url: Option<IndexUrl>,
     /// The extra index URLs to use for fetching packages.
@@ -120,6 +124,10 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The packages for which wheels are disallowed, via `--no-binary`.
+    pub(crate) no_binary: Vec<PackageNameSpecifier>,
+    /// The packages for which source distributions are disallowed, via `--only-binary`.
+    pub(crate) only_binary: Vec<PackageNameSpecifier>,
 }
 
 impl RequirementsSpecification {
@@ -127,11 +135,21 @@ impl RequirementsSpecification {
     pub(crate) fn from_source(
         source: &RequirementsSource,
         extras: &ExtrasSpecification,
+        groups: &[GroupName],
     ) -> Result<Self> {
         Ok(match source {
             RequirementsSource::Package(name) => {
-                let requirement = Requirement::parse(name, std::env::current_dir()?)
-                    .with_context(|| format!("Failed to parse `{name}`"))?;
+                // Accept a path to a local wheel (as in `uv pip install ./foo-1.0-py3-none-any.whl`),
+                // in addition to a PEP 508 requirement.
+                let requirement = if let Some(requirement) =
+                    parse_wheel_path(name, std::env::current_dir()?)
+                        .with_context(|| format!("Failed to parse `{name}`"))?
+                {
+                    requirement
+                } else {
+                    Requirement::parse(name, std::env::current_dir()?)
+                        .with_context(|| format!("Failed to parse `{name}`"))?
+                };
                 Self {
                     project: None,
                     requirements: vec![requirement],
@@ -139,10 +157,13 @@ impl RequirementsSpecification {
                     overrides: vec![],
                     editables: vec![],
                     extras: FxHashSet::default(),
+                    groups: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
                 }
             }
             RequirementsSource::Editable(name) => {
@@ -155,10 +176,13 @@ impl RequirementsSpecification {
                     overrides: vec![],
                     editables: vec![requirement],
                     extras: FxHashSet::default(),
+                    groups: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
@@ -174,6 +198,7 @@ impl RequirementsSpecification {
                     editables: requirements_txt.editables,
                     overrides: vec![],
                     extras: FxHashSet::default(),
+                    groups: FxHashSet::default(),
                     index_url: requirements_txt.index_url.map(IndexUrl::from),
                     extra_index_urls: requirements_txt
                         .extra_index_urls
@@ -189,6 +214,8 @@ impl RequirementsSpecification {
                             FindLink::Path(path) => FlatIndexLocation::Path(path),
                         })
                         .collect(),
+                    no_binary: requirements_txt.no_binary,
+                    only_binary: requirements_txt.only_binary,
                 }
             }
             RequirementsSource::PyprojectToml(path) => {
@@ -220,17 +247,36 @@ impl RequirementsSpecification {
                     })?);
                 }
 
+                // Include any requested dependency groups.
+                let dependency_groups = DependencyGroups::from_pyproject_toml(path, &contents)?;
+                let mut used_groups = FxHashSet::default();
+                for group in groups {
+                    if dependency_groups.contains(group) {
+                        requirements.extend(dependency_groups.requirements(group)?);
+                        used_groups.insert(group.clone());
+                    }
+                }
+
+                // Include any workspace members as editables, such that they're resolved together.
+                let editables = Workspace::from_pyproject_toml(path, &contents)?
+                    .map(|workspace| workspace.editables())
+                    .transpose()?
+                    .unwrap_or_default();
+
                 Self {
                     project: project_name,
                     requirements,
                     constraints: vec![],
                     overrides: vec![],
-                    editables: vec![],
+                    editables,
                     extras: used_extras,
+                    groups: used_groups,
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    no_binary: vec![],
+                    only_binary: vec![],
                 }
             }
         })
@@ -242,6 +288,7 @@ impl RequirementsSpecification {
         constraints: &[RequirementsSource],
         overrides: &[RequirementsSource],
         extras: &ExtrasSpecification,
+        groups: &[GroupName],
     ) -> Result<Self> {
         let mut spec = Self::default();
 
@@ -249,11 +296,12 @@ impl RequirementsSpecification {
         // A `requirements.txt` can contain a `-c constraints.txt` directive within it, so reading
         // a requirements file can also add constraints.
         for source in requirements {
-            let source = Self::from_source(source, extras)?;
+            let source = Self::from_source(source, extras, groups)?;
             spec.requirements.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
+            spec.groups.extend(source.groups);
             spec.editables.extend(source.editables);
 
             // Use the first project name discovered.
@@ -272,11 +320,13 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         // Read all constraints, treating _everything_ as a constraint.
         for source in constraints {
-            let source = Self::from_source(source, extras)?;
+            let source = Self::from_source(source, extras, groups)?;
             spec.constraints.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.constraints.extend(source.overrides);
@@ -292,11 +342,13 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         // Read all overrides, treating both requirements _and_ constraints as overrides.
         for source in overrides {
-            let source = Self::from_source(source, extras)?;
+            let source = Self::from_source(source, extras, groups)?;
             spec.overrides.extend(source.requirements);
             spec.overrides.extend(source.constraints);
             spec.overrides.extend(source.overrides);
@@ -312,6 +364,8 @@ impl RequirementsSpecification {
             spec.no_index |= source.no_index;
             spec.extra_index_urls.extend(source.extra_index_urls);
             spec.find_links.extend(source.find_links);
+            spec.no_binary.extend(source.no_binary);
+            spec.only_binary.extend(source.only_binary);
         }
 
         Ok(spec)
@@ -319,6 +373,6 @@ impl RequirementsSpecification {
 
     /// Read the requirements from a set of sources.
     pub(crate) fn from_simple_sources(requirements: &[RequirementsSource]) -> Result<Self> {
-        Self::from_sources(requirements, &[], &[], &ExtrasSpecification::None)
+        Self::from_sources(requirements, &[], &[], &ExtrasSpecification::None, &[])
     }
 }
@@ -0,0 +1,271 @@
+//! Support for persistent configuration, read from a `uv.toml` file or the `[tool.uv]` table of a
+//! `pyproject.toml`.
+//!
+//! Settings are layered, in order of precedence: command-line arguments, environment variables,
+//! the project configuration (discovered in the current directory or its ancestors), and the user
+//! configuration (e.g., `~/.config/uv/uv.toml`).
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use directories::ProjectDirs;
+use serde::{Deserialize, Deserializer};
+use tracing::debug;
+
+use distribution_types::{
+    FlatIndexLocation, IndexLocations, IndexOverride, IndexStrategy, IndexUrl,
+};
+use install_wheel_rs::linker::LinkMode;
+use uv_client::TrustedHost;
+use uv_fs::Normalized;
+use uv_resolver::{PreReleaseMode, ResolutionMode};
+
+use crate::policy::Policy;
+
+/// The name of a standalone configuration file.
+const CONFIG_FILE: &str = "uv.toml";
+
+/// The settings that can be provided in a configuration file.
+///
+/// Each setting mirrors the command-line argument of the same name.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Options {
+    #[serde(default, deserialize_with = "from_str")]
+    pub(crate) index_url: Option<IndexUrl>,
+    #[serde(default, deserialize_with = "from_str_seq")]
+    pub(crate) extra_index_url: Option<Vec<IndexUrl>>,
+    #[serde(default, deserialize_with = "from_str_seq")]
+    pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
+    pub(crate) no_index: Option<bool>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub(crate) index_strategy: Option<IndexStrategy>,
+    pub(crate) index_fallback: Option<bool>,
+    pub(crate) lenient_index: Option<bool>,
+    pub(crate) index_override: Option<Vec<IndexOverride>>,
+    #[serde(default, deserialize_with = "from_str_seq")]
+    pub(crate) trusted_host: Option<Vec<TrustedHost>>,
+    pub(crate) cache_dir: Option<PathBuf>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub(crate) resolution: Option<ResolutionMode>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub(crate) prerelease: Option<PreReleaseMode>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub(crate) link_mode: Option<LinkMode>,
+    /// The packages, versions, licenses, and sources to deny in a resolution (e.g.,
+    /// `[tool.uv.policy]`). Unlike the other settings, there's no command-line equivalent.
+    pub(crate) policy: Option<Policy>,
+}
+
+impl Options {
+    /// Load the settings from the given `uv.toml` or `pyproject.toml` file.
+    ///
+    /// Relative paths are resolved against the directory containing the file.
+    pub(crate) fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs_err::read_to_string(path)?;
+        let options = if path
+            .file_name()
+            .is_some_and(|name| name == "pyproject.toml")
+        {
+            let pyproject: PyProjectToml = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+            pyproject.tool.and_then(|tool| tool.uv).unwrap_or_default()
+        } else {
+            toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?
+        };
+        debug!("Read configuration from: {}", path.normalized_display());
+
+        let root = path.parent().unwrap_or(Path::new(""));
+        Ok(options.relative_to(root))
+    }
+
+    /// Discover the project settings, searching upwards from the given directory for a `uv.toml`,
+    /// or a `pyproject.toml` with a `[tool.uv]` table.
+    pub(crate) fn find_project(dir: &Path) -> Result<Option<Self>> {
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(CONFIG_FILE);
+            if path.is_file() {
+                return Self::from_file(&path).map(Some);
+            }
+
+            let path = ancestor.join("pyproject.toml");
+            if path.is_file() {
+                let contents = fs_err::read_to_string(&path)?;
+                let pyproject: PyProjectToml = toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+                if pyproject.tool.and_then(|tool| tool.uv).is_some() {
+                    return Self::from_file(&path).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Discover the user settings, in the `uv.toml` file of the user configuration directory
+    /// (e.g., `~/.config/uv/uv.toml` on Linux).
+    pub(crate) fn find_user() -> Result<Option<Self>> {
+        let Some(project_dirs) = ProjectDirs::from("", "", "uv") else {
+            return Ok(None);
+        };
+        let path = project_dirs.config_dir().join(CONFIG_FILE);
+        if path.is_file() {
+            Self::from_file(&path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Combine two sets of settings, preferring those in `self`.
+    #[must_use]
+    pub(crate) fn combine(self, other: Self) -> Self {
+        Self {
+            index_url: self.index_url.or(other.index_url),
+            extra_index_url: self.extra_index_url.or(other.extra_index_url),
+            find_links: self.find_links.or(other.find_links),
+            no_index: self.no_index.or(other.no_index),
+            index_strategy: self.index_strategy.or(other.index_strategy),
+            index_fallback: self.index_fallback.or(other.index_fallback),
+            lenient_index: self.lenient_index.or(other.lenient_index),
+            index_override: self.index_override.or(other.index_override),
+            trusted_host: self.trusted_host.or(other.trusted_host),
+            cache_dir: self.cache_dir.or(other.cache_dir),
+            resolution: self.resolution.or(other.resolution),
+            prerelease: self.prerelease.or(other.prerelease),
+            link_mode: self.link_mode.or(other.link_mode),
+            policy: self.policy.or(other.policy),
+        }
+    }
+
+    /// Determine the index locations to use, preferring those provided on the command line.
+    pub(crate) fn index_locations(
+        &self,
+        index_url: Option<IndexUrl>,
+        extra_index_url: Vec<IndexUrl>,
+        find_links: Vec<FlatIndexLocation>,
+        no_index: bool,
+        index_strategy: Option<IndexStrategy>,
+        index_fallback: bool,
+        lenient_index: bool,
+    ) -> IndexLocations {
+        let extra_index_url = if extra_index_url.is_empty() {
+            self.extra_index_url.clone().unwrap_or_default()
+        } else {
+            extra_index_url
+        };
+        let find_links = if find_links.is_empty() {
+            self.find_links.clone().unwrap_or_default()
+        } else {
+            find_links
+        };
+        IndexLocations::from_args(
+            index_url
+                .or_else(|| self.index_url.clone())
+                .unwrap_or(IndexUrl::Pypi),
+            extra_index_url,
+            find_links,
+            no_index || self.no_index.unwrap_or(false),
+        )
+        .with_strategy(index_strategy.or(self.index_strategy).unwrap_or_default())
+        .with_fallback(index_fallback || self.index_fallback.unwrap_or(false))
+        .with_lenient(lenient_index || self.lenient_index.unwrap_or(false))
+        .with_overrides(self.index_override.clone().unwrap_or_default())
+    }
+
+    /// Determine the trusted hosts to use, preferring those provided on the command line.
+    pub(crate) fn trusted_hosts(&self, trusted_host: Vec<TrustedHost>) -> Vec<TrustedHost> {
+        if trusted_host.is_empty() {
+            self.trusted_host.clone().unwrap_or_default()
+        } else {
+            trusted_host
+        }
+    }
+
+    /// Resolve any relative paths against the given directory.
+    #[must_use]
+    fn relative_to(self, root: &Path) -> Self {
+        Self {
+            cache_dir: self.cache_dir.map(|cache_dir| root.join(cache_dir)),
+            find_links: self.find_links.map(|find_links| {
+                find_links
+                    .into_iter()
+                    .map(|location| match location {
+                        FlatIndexLocation::Path(path) => FlatIndexLocation::Path(root.join(path)),
+                        FlatIndexLocation::Url(url) => FlatIndexLocation::Url(url),
+                    })
+                    .collect()
+            }),
+            ..self
+        }
+    }
+}
+
+/// A `pyproject.toml`, with only the `[tool.uv]` table.
+#[derive(Debug, Deserialize)]
+struct PyProjectToml {
+    tool: Option<Tool>,
+}
+
+/// The `[tool]` table of a `pyproject.toml`.
+#[derive(Debug, Deserialize)]
+struct Tool {
+    uv: Option<Options>,
+}
+
+/// Deserialize a value via its [`FromStr`] implementation.
+fn from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    T::from_str(&value)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Deserialize a sequence of values via their [`FromStr`] implementation.
+fn from_str_seq<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(values) = Option::<Vec<String>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    values
+        .iter()
+        .map(|value| T::from_str(value).map_err(serde::de::Error::custom))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Deserialize a value using the same spelling as its command-line argument (e.g.,
+/// `lowest-direct`).
+fn value_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ValueEnum,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    T::from_str(&value, false).map(Some).map_err(|_| {
+        let variants = T::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .map(|value| format!("`{}`", value.get_name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        serde::de::Error::custom(format!(
+            "invalid value `{value}`, expected one of: {variants}"
+        ))
+    })
+}
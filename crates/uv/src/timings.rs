@@ -0,0 +1,263 @@
+//! Support for `--timings`, which summarizes where the time in a command was spent.
+//!
+//! Timings are collected from the `tracing` spans that the various crates already emit (e.g.,
+//! `simple_api` in `uv-client`, or `solve` in `uv-resolver`), so no additional instrumentation is
+//! required.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use owo_colors::OwoColorize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::commands::elapsed;
+use crate::printer::Printer;
+
+/// The number of slowest packages to include in the report.
+const OUTLIERS: usize = 5;
+
+/// A phase of a command, to which spans are attributed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Phase {
+    Index,
+    Metadata,
+    Resolution,
+    Download,
+    Build,
+    Install,
+}
+
+impl Phase {
+    /// Determine the [`Phase`] for a span, given its target and name.
+    fn from_span(target: &str, name: &str) -> Option<Self> {
+        if !(target.starts_with("uv") || target.starts_with("install_wheel_rs")) {
+            return None;
+        }
+        match name {
+            "simple_api" | "parse_flat_index_html" => Some(Self::Index),
+            "wheel_metadata" | "get_or_build_wheel_metadata" => Some(Self::Metadata),
+            "solve" => Some(Self::Resolution),
+            "download" | "download_source_dist" => Some(Self::Download),
+            "build_source_dist" | "build_source_dist_metadata" => Some(Self::Build),
+            "install_wheel" => Some(Self::Install),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Index => "Index fetches",
+            Self::Metadata => "Metadata reads",
+            Self::Resolution => "Resolution",
+            Self::Download => "Downloads",
+            Self::Build => "Builds",
+            Self::Install => "Install",
+        }
+    }
+
+    fn all() -> [Self; 6] {
+        [
+            Self::Index,
+            Self::Metadata,
+            Self::Resolution,
+            Self::Download,
+            Self::Build,
+            Self::Install,
+        ]
+    }
+}
+
+/// A completed span, attributed to a [`Phase`].
+#[derive(Debug)]
+struct Timing {
+    phase: Phase,
+    /// The package (or distribution) that the span relates to, if any.
+    package: Option<String>,
+    start: Instant,
+    end: Instant,
+}
+
+impl Timing {
+    fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// The state attached to an open span.
+#[derive(Debug)]
+struct OpenSpan {
+    phase: Phase,
+    package: Option<String>,
+    start: Instant,
+}
+
+/// Collects the timings of the spans in a command, for `--timings`.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Timings(Arc<Mutex<Vec<Timing>>>);
+
+impl Timings {
+    /// Return a [`Layer`] that records span timings into this collector.
+    pub(crate) fn layer(&self) -> TimingsLayer {
+        TimingsLayer(self.clone())
+    }
+
+    /// Write a summary of the collected timings.
+    pub(crate) fn report(&self, total: Duration, mut printer: Printer) -> std::fmt::Result {
+        let timings = self.0.lock().unwrap();
+
+        writeln!(
+            printer,
+            "{}",
+            format!("Timings ({} total; phases may overlap):", elapsed(total)).bold()
+        )?;
+        for phase in Phase::all() {
+            let spans = timings
+                .iter()
+                .filter(|timing| timing.phase == phase)
+                .collect::<Vec<_>>();
+            if spans.is_empty() {
+                continue;
+            }
+            writeln!(
+                printer,
+                "  {:<16} {:>8} ({} span{})",
+                phase.label(),
+                elapsed(wall_time(&spans)),
+                spans.len(),
+                if spans.len() == 1 { "" } else { "s" }
+            )?;
+        }
+
+        let mut outliers = timings
+            .iter()
+            .filter_map(|timing| {
+                timing
+                    .package
+                    .as_deref()
+                    .map(|package| (timing.phase, package, timing.duration()))
+            })
+            .collect::<Vec<_>>();
+        outliers.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(b.1)));
+        if !outliers.is_empty() {
+            writeln!(printer, "{}", "Slowest packages:".bold())?;
+            for (phase, package, duration) in outliers.into_iter().take(OUTLIERS) {
+                writeln!(
+                    printer,
+                    "  {:<16} {:>8} {}",
+                    phase.label(),
+                    elapsed(duration),
+                    package.cyan()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the wall-clock time covered by the given spans, counting concurrent spans once.
+fn wall_time(spans: &[&Timing]) -> Duration {
+    let mut intervals = spans
+        .iter()
+        .map(|timing| (timing.start, timing.end))
+        .collect::<Vec<_>>();
+    intervals.sort();
+
+    let mut total = Duration::ZERO;
+    let mut current: Option<(Instant, Instant)> = None;
+    for (start, end) in intervals {
+        match current {
+            Some((current_start, current_end)) if start <= current_end => {
+                current = Some((current_start, current_end.max(end)));
+            }
+            _ => {
+                if let Some((current_start, current_end)) = current {
+                    total += current_end - current_start;
+                }
+                current = Some((start, end));
+            }
+        }
+    }
+    if let Some((current_start, current_end)) = current {
+        total += current_end - current_start;
+    }
+    total
+}
+
+/// A [`Layer`] that records the duration of each span that belongs to a [`Phase`].
+#[derive(Debug)]
+pub(crate) struct TimingsLayer(Timings);
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let metadata = attrs.metadata();
+        let Some(phase) = Phase::from_span(metadata.target(), metadata.name()) else {
+            return;
+        };
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut visitor = PackageVisitor::default();
+        attrs.record(&mut visitor);
+
+        span.extensions_mut().insert(OpenSpan {
+            phase,
+            package: visitor.package,
+            start: Instant::now(),
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(open) = span.extensions_mut().remove::<OpenSpan>() else {
+            return;
+        };
+        self.0 .0.lock().unwrap().push(Timing {
+            phase: open.phase,
+            package: open.package,
+            start: open.start,
+            end: Instant::now(),
+        });
+    }
+}
+
+/// Extract the package (or distribution) that a span relates to from its fields.
+#[derive(Debug, Default)]
+struct PackageVisitor {
+    package: Option<String>,
+}
+
+impl Visit for PackageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if self.package.is_some() {
+            return;
+        }
+        if !matches!(
+            field.name(),
+            "package" | "package_id" | "built_dist" | "source_dist" | "dist" | "wheel"
+        ) {
+            return;
+        }
+        let value = format!("{value:?}");
+        let value = value.trim_matches('"');
+        // Some spans (e.g., `install_wheel`) record the path to the wheel, rather than its name.
+        let value = Path::new(value)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(value);
+        self.package = Some(value.to_string());
+    }
+}
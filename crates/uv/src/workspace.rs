@@ -0,0 +1,115 @@
+//! Support for workspaces, i.e., sets of packages declared via `[tool.uv.workspace]`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::debug;
+
+use requirements_txt::EditableRequirement;
+use uv_fs::Normalized;
+
+/// A workspace, as declared in the `[tool.uv.workspace]` table of a `pyproject.toml`.
+#[derive(Debug)]
+pub(crate) struct Workspace {
+    /// The directory containing the workspace `pyproject.toml`.
+    root: PathBuf,
+    /// The member directories, in sorted order.
+    members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Read the workspace declared in the given `pyproject.toml`, if any.
+    ///
+    /// Each entry in `members` is a glob pattern relative to the directory containing the
+    /// `pyproject.toml`; any matching directories are included as members.
+    pub(crate) fn from_pyproject_toml(path: &Path, contents: &str) -> Result<Option<Self>> {
+        let pyproject: PyProjectToml = toml::from_str(contents)
+            .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+        let Some(workspace) = pyproject
+            .tool
+            .and_then(|tool| tool.uv)
+            .and_then(|uv| uv.workspace)
+        else {
+            return Ok(None);
+        };
+
+        let root = std::env::current_dir()?.join(path.parent().unwrap_or(Path::new("")));
+
+        let mut members = Vec::new();
+        for pattern in &workspace.members {
+            let absolute = root.join(pattern);
+            let absolute = absolute.to_string_lossy();
+            for member in glob::glob(&absolute)
+                .with_context(|| format!("Invalid workspace member pattern: `{pattern}`"))?
+            {
+                let member = member?;
+                if member.is_dir() {
+                    members.push(member);
+                }
+            }
+        }
+        members.sort();
+        members.dedup();
+
+        Ok(Some(Self { root, members }))
+    }
+
+    /// Return the directory containing the workspace `pyproject.toml`.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the member directories of the workspace.
+    pub(crate) fn members(&self) -> &[PathBuf] {
+        &self.members
+    }
+
+    /// Return an editable requirement for each member that defines a Python package.
+    ///
+    /// Members are referenced relative to the workspace root, such that the resulting requirements
+    /// can be written to (and read back from) a lockfile alongside the `pyproject.toml`.
+    pub(crate) fn editables(&self) -> Result<Vec<EditableRequirement>> {
+        let mut editables = Vec::with_capacity(self.members.len());
+        for member in &self.members {
+            if !member.join("pyproject.toml").is_file()
+                && !member.join("setup.py").is_file()
+                && !member.join("setup.cfg").is_file()
+            {
+                debug!(
+                    "Skipping workspace member without a package definition: {}",
+                    member.normalized_display()
+                );
+                continue;
+            }
+
+            let relative = member.strip_prefix(&self.root).unwrap_or(member);
+            let given = format!("./{}", relative.to_string_lossy().replace('\\', "/"));
+            let editable = EditableRequirement::parse(&given, &self.root)
+                .with_context(|| format!("Failed to parse workspace member `{given}`"))?;
+            editables.push(editable);
+        }
+        Ok(editables)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PyProjectToml {
+    tool: Option<Tool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tool {
+    uv: Option<ToolUv>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolUv {
+    workspace: Option<WorkspaceOptions>,
+}
+
+/// The `[tool.uv.workspace]` table.
+#[derive(Debug, Deserialize)]
+struct WorkspaceOptions {
+    members: Vec<String>,
+}
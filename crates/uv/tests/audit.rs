@@ -0,0 +1,127 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `uv audit` command against a local advisory database.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("audit")
+        .arg("--advisory-db")
+        .arg("advisories")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Write an advisory affecting `example>=1.0,<1.2`, fixed in `1.2`.
+fn write_advisory(context: &TestContext) -> Result<()> {
+    context
+        .temp_dir
+        .child("advisories")
+        .child("PYSEC-0000-1.json")
+        .write_str(indoc! {r#"
+            {
+              "id": "PYSEC-0000-1",
+              "summary": "Remote code execution in example",
+              "aliases": ["CVE-0000-0001"],
+              "affected": [
+                {
+                  "package": { "ecosystem": "PyPI", "name": "Example" },
+                  "ranges": [
+                    {
+                      "type": "ECOSYSTEM",
+                      "events": [{ "introduced": "1.0" }, { "fixed": "1.2" }]
+                    }
+                  ]
+                }
+              ]
+            }
+        "#})?;
+    Ok(())
+}
+
+/// Report a vulnerable pin, with the version that fixes it.
+#[test]
+fn audit_vulnerable() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_advisory(&context)?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("example==1.1.0\nother==2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    example==1.1.0
+      PYSEC-0000-1 (CVE-0000-0001): Remote code execution in example
+        Fixed in: example==1.2
+
+    ----- stderr -----
+    Found 1 vulnerability in 1 package (audited 2 packages in [TIME])
+    "###
+    );
+
+    Ok(())
+}
+
+/// Versions outside the affected range, and unpinned requirements, aren't reported.
+#[test]
+fn audit_clean() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_advisory(&context)?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("example==1.2.0\nother>=2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Skipping requirement that isn't pinned to an exact version: other>=2.0.0
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Querying the OSV API requires network access.
+#[test]
+fn audit_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("example==1.1.0")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("audit")
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--offline")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Querying OSV requires network access, but `--offline` was provided (hint: use `--advisory-db`)
+    "###
+    );
+
+    Ok(())
+}
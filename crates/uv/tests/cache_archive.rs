@@ -0,0 +1,111 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+
+mod common;
+
+/// Export a warm cache, import it at a different path, then install from it offline.
+#[test]
+fn export_then_import() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // Populate the cache.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    let filters = [(r"\d+ cache entries", "[N] cache entries")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("export")
+        .arg("cache.tar.gz")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Exported [N] cache entries to: cache.tar.gz
+    "###
+    );
+
+    // Import the archive into a fresh cache.
+    let cache_dir = assert_fs::TempDir::new()?;
+    let filters = [
+        (r"\d+ cache entries", "[N] cache entries"),
+        (r"into: .*", "into: [CACHE_DIR]"),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("import")
+        .arg("cache.tar.gz")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Imported [N] cache entries into: [CACHE_DIR]
+    "###
+    );
+
+    // Installing from the imported cache shouldn't require network access.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("markupsafe")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--offline")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+use uv_fs::Normalized;
+
+mod common;
+
+/// Show the cache directory, as provided via `UV_CACHE_DIR`.
+#[test]
+fn cache_dir_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let cache_dir = context.temp_dir.path().join("mounted-cache");
+
+    let filter_path = regex::escape(
+        &fs_err::canonicalize(context.temp_dir.path())?
+            .normalized_display()
+            .to_string(),
+    );
+    let filters = [(filter_path.as_str(), "[TEMP_DIR]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("dir")
+        .env("UV_CACHE_DIR", &cache_dir)
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/mounted-cache
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
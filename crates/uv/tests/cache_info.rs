@@ -0,0 +1,91 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+use uv_fs::Normalized;
+
+mod common;
+
+/// Show the cache entries for a package, after installing it.
+#[test]
+fn cache_info_installed() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    let filter_path = regex::escape(
+        &fs_err::canonicalize(context.cache_dir.path())?
+            .normalized_display()
+            .to_string(),
+    );
+    let filters = [(filter_path.as_str(), "[CACHE_DIR]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("info")
+        .arg("tomli")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Index metadata:
+      simple-v1/pypi/tomli.rkyv ([SIZE], updated just now)
+    Wheels:
+      wheels-v0/pypi/tomli ([SIZE], updated just now)
+        tomli-2.0.1-py3-none-any: unzipped
+
+    ----- stderr -----
+    Cache entries for tomli at: [CACHE_DIR]
+    Found 2 cache entries ([SIZE])
+    "###
+    );
+
+    // Packages that aren't cached are reported as such.
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("info")
+        .arg("flask")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No cache entries found for flask at: [CACHE_DIR]
+    "###
+    );
+
+    Ok(())
+}
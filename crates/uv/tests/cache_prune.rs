@@ -0,0 +1,56 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+use uv_fs::Normalized;
+
+mod common;
+
+/// Prune a bucket left behind by an older version of uv, leaving the current buckets untouched.
+#[test]
+fn cache_prune_outdated_bucket() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let outdated = context.cache_dir.child("simple-v0");
+    outdated.child("pypi").child("flask.rkyv").write_str("")?;
+    let current = context.cache_dir.child("simple-v1");
+    current.child("pypi").child("flask.rkyv").write_str("")?;
+
+    let filter_path = regex::escape(
+        &fs_err::canonicalize(context.cache_dir.path())?
+            .normalized_display()
+            .to_string(),
+    );
+    let filters = [(filter_path.as_str(), "[CACHE_DIR]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("prune")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]
+    Removed 1 file
+    "###
+    );
+
+    outdated.assert(predicates::path::missing());
+    current
+        .child("pypi")
+        .child("flask.rkyv")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
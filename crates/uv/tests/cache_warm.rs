@@ -0,0 +1,62 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Warm the cache for a set of pinned requirements, then install them offline.
+#[test]
+fn warm_then_sync_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let bin = create_bin_with_executables(&context.temp_dir, &["3.12"])
+        .expect("Failed to create bin dir");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("cache")
+        .arg("warm")
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Warmed 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--offline")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
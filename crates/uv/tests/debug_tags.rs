@@ -0,0 +1,50 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::{create_bin_with_executables, get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+
+mod common;
+
+/// Explain whether each wheel is compatible with the target interpreter.
+#[test]
+fn explain_compatibility() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let bin = create_bin_with_executables(&context.temp_dir, &["3.12"])
+        .expect("Failed to create bin dir");
+
+    let filters = [
+        (r"Using .*", "Using [PYTHON]"),
+        (r"tag \d+ of \d+", "tag [N] of [N]"),
+        (r"expected one of: .*\)", "expected one of: [TAGS])"),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("debug")
+        .arg("tags")
+        .arg("tqdm-4.66.1-py3-none-any.whl")
+        .arg("https://example.com/files/numpy-1.16.6-cp27-cp27m-win32.whl")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    tqdm-4.66.1-py3-none-any.whl: compatible (matches `py3-none-any`, tag [N] of [N])
+    numpy-1.16.6-cp27-cp27m-win32.whl: incompatible (the Python tag `cp27` is not supported; expected one of: [TAGS])
+
+    ----- stderr -----
+    Using [PYTHON]
+    "###
+    );
+
+    Ok(())
+}
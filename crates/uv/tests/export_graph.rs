@@ -0,0 +1,146 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `uv export-graph` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("export-graph")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(context.temp_dir.path());
+    command
+}
+
+/// Export the resolution graph as a Graphviz DOT file, including the markers on each edge.
+#[test]
+fn export_graph_dot() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("requests[socks]==2.31.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    digraph {
+        "certifi" [label="certifi==2023.11.17"]
+        "charset-normalizer" [label="charset-normalizer==3.3.2"]
+        "idna" [label="idna==3.4"]
+        "pysocks" [label="pysocks==1.7.1"]
+        "requests" [label="requests==2.31.0"]
+        "urllib3" [label="urllib3==2.1.0"]
+        "requests" -> "certifi" [label=">=2017.4.17"]
+        "requests" -> "charset-normalizer" [label=">=2, <4"]
+        "requests" -> "idna" [label=">=2.5, <4"]
+        "requests" -> "pysocks" [label=">=1.5.6, <1.5.7 | >1.5.7; extra == 'socks'"]
+        "requests" -> "urllib3" [label=">=1.21.1, <3"]
+    }
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Export the resolution graph as JSON nodes and edges.
+#[test]
+fn export_graph_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("requests[socks]==2.31.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.in")
+        .arg("--graph-format")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "nodes": [
+        {
+          "name": "certifi",
+          "version": "2023.11.17"
+        },
+        {
+          "name": "charset-normalizer",
+          "version": "3.3.2"
+        },
+        {
+          "name": "idna",
+          "version": "3.4"
+        },
+        {
+          "name": "pysocks",
+          "version": "1.7.1"
+        },
+        {
+          "name": "requests",
+          "version": "2.31.0"
+        },
+        {
+          "name": "urllib3",
+          "version": "2.1.0"
+        }
+      ],
+      "edges": [
+        {
+          "from": "requests",
+          "to": "certifi",
+          "specifier": ">=2017.4.17",
+          "extras": [],
+          "markers": []
+        },
+        {
+          "from": "requests",
+          "to": "charset-normalizer",
+          "specifier": ">=2, <4",
+          "extras": [],
+          "markers": []
+        },
+        {
+          "from": "requests",
+          "to": "idna",
+          "specifier": ">=2.5, <4",
+          "extras": [],
+          "markers": []
+        },
+        {
+          "from": "requests",
+          "to": "pysocks",
+          "specifier": ">=1.5.6, <1.5.7 | >1.5.7",
+          "extras": [],
+          "markers": [
+            "extra == 'socks'"
+          ]
+        },
+        {
+          "from": "requests",
+          "to": "urllib3",
+          "specifier": ">=1.21.1, <3",
+          "extras": [],
+          "markers": []
+        }
+      ]
+    }
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###);
+
+    Ok(())
+}
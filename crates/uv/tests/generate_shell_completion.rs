@@ -0,0 +1,46 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Generate completions for each supported shell.
+#[test]
+fn generate_shell_completion() -> Result<()> {
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        Command::new(get_bin())
+            .arg("generate-shell-completion")
+            .arg(shell)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("uninstall"));
+    }
+
+    Ok(())
+}
+
+/// The Bash, Zsh, and Fish scripts complete installed package names for `uv pip uninstall`.
+#[test]
+fn generate_shell_completion_dynamic() -> Result<()> {
+    for shell in ["bash", "zsh", "fish"] {
+        Command::new(get_bin())
+            .arg("generate-shell-completion")
+            .arg(shell)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("uv pip freeze 2>/dev/null"));
+    }
+
+    Command::new(get_bin())
+        .arg("generate-shell-completion")
+        .arg("powershell")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("uv pip freeze").not());
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+use url::Url;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
+
+mod common;
+
+/// Create a `uv import` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command.arg("import").current_dir(&context.temp_dir);
+    command
+}
+
+/// Import a `poetry.lock`, preserving hashes and markers, and omitting development dependencies.
+#[test]
+fn import_poetry() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let poetry_lock = context.temp_dir.child("poetry.lock");
+    poetry_lock.write_str(indoc! {r#"
+        [[package]]
+        name = "MarkupSafe"
+        version = "2.1.3"
+        description = "Safely add untrusted strings to HTML/XML markup."
+        optional = false
+        python-versions = ">=3.7"
+        files = [
+            {file = "MarkupSafe-2.1.3.tar.gz", hash = "sha256:af598ed32d6ae86f1b747b82783958b1a4ab8f617b06fe68795c7f026abbdcad"},
+        ]
+
+        [[package]]
+        name = "tomli"
+        version = "2.0.1"
+        description = "A lil' TOML parser"
+        optional = false
+        python-versions = ">=3.7"
+        markers = "python_version < \"3.11\""
+        files = [
+            {file = "tomli-2.0.1.tar.gz", hash = "sha256:de526c12914f0c550d15924c62d72abc48d6fe7364aa87328337a31007fe8a4f"},
+        ]
+
+        [[package]]
+        name = "pytest"
+        version = "8.0.0"
+        description = "pytest: simple powerful testing with Python"
+        optional = false
+        python-versions = ">=3.8"
+        groups = ["dev"]
+        files = []
+
+        [metadata]
+        lock-version = "2.1"
+        python-versions = "^3.8"
+        content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("poetry.lock"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv import poetry.lock
+    markupsafe==2.1.3 \
+        --hash=sha256:af598ed32d6ae86f1b747b82783958b1a4ab8f617b06fe68795c7f026abbdcad
+    tomli==2.0.1 ; python_version < '3.11' \
+        --hash=sha256:de526c12914f0c550d15924c62d72abc48d6fe7364aa87328337a31007fe8a4f
+
+    ----- stderr -----
+    Imported 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Import a `Pipfile.lock`, including development dependencies and Git sources.
+#[test]
+fn import_pipfile_dev() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pipfile_lock = context.temp_dir.child("Pipfile.lock");
+    pipfile_lock.write_str(indoc! {r#"
+        {
+            "_meta": {
+                "hash": {"sha256": "0000"},
+                "pipfile-spec": 6,
+                "requires": {"python_version": "3.12"},
+                "sources": [
+                    {"name": "pypi", "url": "https://pypi.org/simple", "verify_ssl": true},
+                    {"name": "internal", "url": "https://example.com/simple/", "verify_ssl": true}
+                ]
+            },
+            "default": {
+                "requests": {
+                    "extras": ["socks"],
+                    "hashes": ["sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003f"],
+                    "index": "pypi",
+                    "markers": "python_version >= '3.7'",
+                    "version": "==2.31.0"
+                },
+                "flask": {
+                    "git": "https://github.com/pallets/flask.git",
+                    "ref": "735a4701d6d5e848241e7d7535db898efb62d400"
+                }
+            },
+            "develop": {
+                "iniconfig": {
+                    "hashes": ["sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374"],
+                    "index": "internal",
+                    "version": "==2.0.0"
+                }
+            }
+        }
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("Pipfile.lock")
+        .arg("--dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv import Pipfile.lock --dev
+    --extra-index-url https://example.com/simple
+
+    flask @ git+https://github.com/pallets/flask.git@735a4701d6d5e848241e7d7535db898efb62d400
+    iniconfig==2.0.0 \
+        --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+    requests[socks]==2.31.0 ; python_version >= '3.7' \
+        --hash=sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003f
+
+    ----- stderr -----
+    Imported 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Editable paths are resolved relative to the directory containing the lockfile, rather than the
+/// current directory.
+#[test]
+fn import_pipfile_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.child("lib").create_dir_all()?;
+    project.child("Pipfile.lock").write_str(indoc! {r#"
+        {
+            "_meta": {
+                "hash": {"sha256": "0000"},
+                "pipfile-spec": 6,
+                "requires": {"python_version": "3.12"},
+                "sources": [
+                    {"name": "pypi", "url": "https://pypi.org/simple", "verify_ssl": true}
+                ]
+            },
+            "default": {
+                "lib": {
+                    "editable": true,
+                    "path": "./lib"
+                }
+            },
+            "develop": {}
+        }
+    "#})?;
+
+    let project_dir = regex::escape(
+        Url::from_directory_path(project.path().canonicalize()?)
+            .unwrap()
+            .as_str(),
+    );
+    let filters = [(project_dir.as_str(), "file://[PROJECT_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("project/Pipfile.lock"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv import project/Pipfile.lock
+    -e file://[PROJECT_DIR]/lib
+
+    ----- stderr -----
+    Imported 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lockfiles with an unrecognized filename require an explicit format.
+#[test]
+fn import_unknown_format() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.temp_dir.child("requirements.lock").touch()?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.lock"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unable to determine the format of `requirements.lock`; pass `--format poetry` or `--format pipenv`
+    "###
+    );
+
+    Ok(())
+}
@@ -5,11 +5,13 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
+use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 use insta::assert_snapshot;
 use itertools::Itertools;
+use predicates::prelude::*;
 use url::Url;
 
 use common::{uv_snapshot, TestContext, INSTA_FILTERS};
@@ -47,6 +49,128 @@ fn compile_requirements_in() -> Result<()> {
     Ok(())
 }
 
+/// Write the resolution as JSON, including the hashes of each package.
+#[test]
+fn compile_format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tomli==2.0.1")?;
+
+    let filters: Vec<_> = [(r#""elapsed_ms": \d+"#, r#""elapsed_ms": [TIME]"#)]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, context
+        .compile()
+        .arg("requirements.in")
+        .arg("--format")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "packages": [
+        {
+          "name": "tomli",
+          "version": "2.0.1",
+          "hashes": [
+            "sha256:939de3e7a6161af0c887ef91b7d41a53e7c5a1ca976325f429cb46ea9bc30ecc",
+            "sha256:de526c12914f0c550d15924c62d72abc48d6fe7364aa87328337a31007fe8a4f"
+          ]
+        }
+      ],
+      "diagnostics": [],
+      "elapsed_ms": [TIME]
+    }
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Print a breakdown of where the time was spent with `--timings`.
+#[test]
+fn compile_timings() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tomli==2.0.1")?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--timings")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tomli==2.0.1"))
+        .stderr(predicate::str::contains("Timings ("))
+        .stderr(predicate::str::contains("Resolution"))
+        .stderr(predicate::str::contains("Index fetches"));
+
+    Ok(())
+}
+
+/// Write the spans recorded during resolution to a Chrome trace file with `--trace-output`.
+#[test]
+fn compile_trace_output() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tomli==2.0.1")?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--trace-output")
+        .arg("trace.json")
+        .assert()
+        .success();
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(context.temp_dir.child("trace.json"))?)?;
+    let events = trace["traceEvents"]
+        .as_array()
+        .context("Expected `traceEvents` to be an array")?;
+    assert!(events
+        .iter()
+        .any(|event| event["name"] == "solve" && event["ph"] == "X"));
+
+    Ok(())
+}
+
+/// Emit log messages as JSON objects with `--log-format json`.
+#[test]
+fn compile_log_format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tomli==2.0.1")?;
+
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--verbose")
+        .arg("--log-format")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success());
+
+    let messages = String::from_utf8(output.stderr)?
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(!messages.is_empty());
+    for message in &messages {
+        assert!(message["timestamp"].is_string());
+        assert!(message["level"].is_string());
+        assert!(message["target"].is_string());
+        assert!(message["spans"].is_array());
+    }
+
+    Ok(())
+}
+
 /// Resolve a specific version of Django from a `requirements.in` file on stdin
 /// when passed a path of `-`.
 #[test]
@@ -164,6 +288,71 @@ dependencies = [
     Ok(())
 }
 
+/// Resolve a workspace, in which the members are included as editables and one member depends on
+/// another by name.
+#[test]
+fn compile_workspace() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! {r#"
+        [tool.uv.workspace]
+        members = ["packages/*"]
+        "#
+        })?;
+
+    let app = context.temp_dir.child("packages").child("app");
+    app.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "app"
+        version = "0.1.0"
+        description = "An application that depends on another workspace member"
+        dependencies = ["lib"]
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+        "#
+    })?;
+    app.child("app").child("__init__.py").touch()?;
+
+    let lib = context.temp_dir.child("packages").child("lib");
+    lib.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "lib"
+        version = "0.1.0"
+        description = "A library that's only available in the workspace"
+        dependencies = ["iniconfig"]
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+        "#
+    })?;
+    lib.child("lib").child("__init__.py").touch()?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
+    -e ./packages/app
+    -e ./packages/lib
+    iniconfig==2.0.0
+        # via lib
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with a `constraints.txt` file.
 #[test]
 fn compile_constraints_txt() -> Result<()> {
@@ -197,6 +386,61 @@ fn compile_constraints_txt() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package from a `requirements.in` file, with an alias that satisfies the requirement
+/// with another package.
+#[test]
+fn compile_alias() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("opencv-python==4.8.1.78")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--alias")
+            .arg("opencv-python=opencv-python-headless"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --alias opencv-python=opencv-python-headless
+    numpy==1.26.2
+        # via opencv-python-headless
+    opencv-python-headless==4.8.1.78
+        # provides opencv-python
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject an alias that isn't of the form `<requested>=<provider>`.
+#[test]
+fn compile_alias_invalid() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("opencv-python==4.8.1.78")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--alias")
+            .arg("opencv-python"), @r###"
+    success: false
+    exit_code: 6
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'opencv-python' for '--alias <ALIAS>': Expected an alias of the form `<requested>=<provider>`, but found: `opencv-python`
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with an inline constraint.
 #[test]
 fn compile_constraints_inline() -> Result<()> {
@@ -302,6 +546,67 @@ optional-dependencies.foo = [
     Ok(())
 }
 
+/// Resolve packages from a dependency group (including an included group) in a `pyproject.toml`
+/// file.
+#[test]
+fn compile_pyproject_toml_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+dependencies = ["anyio==3.7.0"]
+
+[dependency-groups]
+Test = ["iniconfig==2.0.0"]
+dev = [
+    {include-group = "test"},
+    "tomli==2.0.1",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--group")
+            .arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --group dev
+    anyio==3.7.0
+    idna==3.4
+        # via anyio
+    iniconfig==2.0.0
+    sniffio==1.3.0
+        # via anyio
+    tomli==2.0.1
+
+    ----- stderr -----
+    Resolved 5 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--group")
+            .arg("lint"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requested dependency group not found: lint
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from an extra with unnormalized names in a `pyproject.toml` file.
 #[test]
 fn compile_pyproject_toml_extra_name_normalization() -> Result<()> {
@@ -461,7 +766,7 @@ optional-dependencies.foo = [
             .arg("--extra")
             .arg("invalid name!"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -560,7 +865,7 @@ fn compile_python_37() -> Result<()> {
             .arg("--python-version")
             .arg("3.7"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -575,6 +880,79 @@ fn compile_python_37() -> Result<()> {
     Ok(())
 }
 
+/// Fail to resolve a version of SciPy whose `Requires-Python` doesn't cover the entire
+/// `requires-python` range, even though it supports the lowest version in the range.
+#[test]
+fn compile_requires_python_range() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("scipy==1.10.1")?;
+
+    let filters: Vec<_> = [
+        // 3.8 may not be installed
+        (
+            "warning: The requested Python version 3.8 is not available; .* will be used to build dependencies instead.\n",
+            "",
+        ),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--requires-python")
+            .arg(">=3.8"), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because scipy==1.10.1 is unusable because it requires Python <3.12,
+          >=3.8, which does not cover the requires-python range (>=3.8) and you
+          require scipy==1.10.1, we can conclude that the requirements are
+          unsatisfiable.
+    "###);
+
+    Ok(())
+}
+
+/// Fail when a requirement would be omitted because its markers don't apply to the lowest version in
+/// the `requires-python` range, even though they apply to a newer version in the range.
+#[test]
+fn compile_requires_python_range_omitted_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0\nexceptiongroup ; python_version >= '3.11'")?;
+
+    let filters: Vec<_> = [
+        // 3.8 may not be installed
+        (
+            "warning: The requested Python version 3.8 is not available; .* will be used to build dependencies instead.\n",
+            "",
+        ),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--requires-python")
+            .arg(">=3.8"), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The resolution doesn't fork on Python-version markers, so it can't satisfy every version in the requires-python range:
+      - The requirement `exceptiongroup ; python_version >= '3.11'` applies to Python 3.11
+    "###);
+
+    Ok(())
+}
+
 /// Resolve a specific version of Black against an invalid Python version.
 #[test]
 fn compile_python_invalid_version() -> Result<()> {
@@ -587,7 +965,7 @@ fn compile_python_invalid_version() -> Result<()> {
             .arg("--python-version")
             .arg("3.7.x"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -612,7 +990,7 @@ fn compile_python_dev_version() -> Result<()> {
             .arg("--python-version")
             .arg("3.7-dev"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -1092,7 +1470,7 @@ fn conflicting_direct_url_dependency() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1131,6 +1509,28 @@ fn compatible_direct_url_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Request Flask via a URL to a Werkzeug wheel, which should fail since the requested name doesn't
+/// match the name in the wheel filename.
+#[test]
+fn mismatched_direct_url_name() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requested package name `flask` does not match `werkzeug` in the distribution filename: https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl
+    "###
+    );
+
+    Ok(())
+}
+
 /// Request Werkzeug via two different URLs at different versions, which should result in a conflict.
 #[test]
 fn conflicting_repeated_url_dependency_version_mismatch() -> Result<()> {
@@ -1141,7 +1541,7 @@ fn conflicting_repeated_url_dependency_version_mismatch() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1168,7 +1568,7 @@ fn conflicting_repeated_url_dependency_version_match() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1193,7 +1593,7 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1382,7 +1782,7 @@ optional-dependencies.bar = [
             .arg("foo"),
             @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -1415,7 +1815,7 @@ dependencies = ["django==5.0b1", "django==5.0a1"]
     uv_snapshot!(context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1447,7 +1847,7 @@ dependencies = ["django==300.1.4"]
     uv_snapshot!(context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1528,7 +1928,7 @@ fn compile_exclude_newer() -> Result<()> {
             .env("VIRTUAL_ENV", context.venv.as_os_str())
             .current_dir(context.temp_dir.path()), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -1689,20 +2089,29 @@ fn compile_wheel_path_dependency() -> Result<()> {
     Ok(())
 }
 
-/// Resolve a local path dependency on a specific source distribution.
+/// Resolve a local path dependency whose `pyproject.toml` declares its metadata statically, which
+/// should be read directly rather than invoking the (here, uninstallable) build backend.
 #[test]
-fn compile_source_distribution_path_dependency() -> Result<()> {
+fn compile_static_pyproject_metadata() -> Result<()> {
     let context = TestContext::new("3.12");
-    // Download a source distribution.
-    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
-    let flask_wheel = context.temp_dir.child("flask-3.0.0.tar.gz");
-    let mut flask_wheel_file = std::fs::File::create(&flask_wheel)?;
-    std::io::copy(&mut response.bytes()?.as_ref(), &mut flask_wheel_file)?;
+
+    let pyproject_toml = context.temp_dir.child("project").child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [build-system]
+        requires = ["this-build-backend-does-not-exist"]
+        build-backend = "this_build_backend_does_not_exist"
+
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+    })?;
 
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str(&format!(
-        "flask @ {}",
-        Url::from_file_path(flask_wheel.path()).unwrap()
+        "project @ {}",
+        Url::from_directory_path(context.temp_dir.child("project").path()).unwrap()
     ))?;
 
     // In addition to the standard filters, remove the temporary directory from the snapshot.
@@ -1718,12 +2127,52 @@ fn compile_source_distribution_path_dependency() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
-    blinker==1.7.0
-        # via flask
-    click==8.1.7
-        # via flask
-    flask @ file://[TEMP_DIR]/flask-3.0.0.tar.gz
-    itsdangerous==2.1.2
+    iniconfig==2.0.0
+        # via project
+    project @ file://[TEMP_DIR]/
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a local path dependency on a specific source distribution.
+#[test]
+fn compile_source_distribution_path_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    // Download a source distribution.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/d8/09/c1a7354d3925a3c6c8cfdebf4245bae67d633ffda1ba415add06ffc839c5/flask-3.0.0.tar.gz")?;
+    let flask_wheel = context.temp_dir.child("flask-3.0.0.tar.gz");
+    let mut flask_wheel_file = std::fs::File::create(&flask_wheel)?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut flask_wheel_file)?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!(
+        "flask @ {}",
+        Url::from_file_path(flask_wheel.path()).unwrap()
+    ))?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ file://[TEMP_DIR]/flask-3.0.0.tar.gz
+    itsdangerous==2.1.2
         # via flask
     jinja2==3.1.2
         # via flask
@@ -1801,7 +2250,7 @@ fn compile_yanked_version_indirect() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2159,7 +2608,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
         .context("Expected a python interpreter cache file")??
         .path();
     let cache_files = [
-        PathBuf::from("simple-v0/pypi/numpy.msgpack"),
+        PathBuf::from("simple-v1/pypi/numpy.msgpack"),
         PathBuf::from(
             "wheels-v0/pypi/python-dateutil/python_dateutil-2.8.2-py2.py3-none-any.msgpack",
         ),
@@ -2304,6 +2753,38 @@ fn trailing_slash() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package available on multiple indexes, pooling the versions across all indexes.
+#[test]
+fn index_strategy_best_match() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("jinja2")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--index-url")
+            .arg("https://test.pypi.org/simple")
+            .arg("--extra-index-url")
+            .arg("https://pypi.org/simple")
+            .arg("--index-strategy")
+            .arg("best-match"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --index-url https://test.pypi.org/simple --extra-index-url https://pypi.org/simple --index-strategy best-match
+    jinja2==3.1.2
+    markupsafe==2.1.3
+        # via jinja2
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a project without a `pyproject.toml`, using the PEP 517 build backend (default).
 #[test]
 fn compile_legacy_sdist_pep_517() -> Result<()> {
@@ -2542,183 +3023,690 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+/// Compile using `--find-links` with a local HTML file, which links to a wheel alongside it.
 #[test]
-fn find_links_url() -> Result<()> {
+fn find_links_html_file() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("tqdm")?;
 
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    wheelhouse.child("index.html").write_str(indoc! {r#"
+        <html>
+          <body>
+            <a href="tqdm-1000.0.0-py3-none-any.whl">tqdm-1000.0.0-py3-none-any.whl</a>
+          </body>
+        </html>
+    "#})?;
+
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--no-index")
             .arg("--find-links")
-            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+            .arg("wheelhouse/index.html"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv v[VERSION] via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links https://download.pytorch.org/whl/torch_stable.html
-    tqdm==4.64.1
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links wheelhouse/index.html
+    tqdm==1000.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    "###
-    );
+    "###);
 
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
-/// with the URL itself provided in a `requirements.txt` file.
+/// Prefer an older version with a compatible wheel over a newer, source-only version with
+/// `--prefer-binary`.
 #[test]
-fn find_links_requirements_txt() -> Result<()> {
+fn prefer_binary() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    // The source distribution is never built, so it needn't be valid.
+    wheelhouse.child("tqdm-1001.0.0.tar.gz").touch()?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .arg("--emit-find-links"), @r###"
+            .arg("--find-links")
+            .arg("wheelhouse")
+            .arg("--prefer-binary"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv v[VERSION] via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-find-links
-    --find-links https://download.pytorch.org/whl/torch_stable.html
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links wheelhouse --prefer-binary
+    tqdm==1000.0.0
 
-    tqdm==4.64.1
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Reuse a cached resolution when re-running with unchanged inputs, but not once the contents of
+/// the `--find-links` directory change.
+#[test]
+fn cached_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links wheelhouse
+    tqdm==1000.0.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    "###
-    );
+    "###);
+
+    // Re-run with unchanged inputs, which should reuse the cached resolution.
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg("wheelhouse")
+        .env("RUST_LOG", "uv=debug")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tqdm==1000.0.0"))
+        .stderr(predicate::str::contains("Using cached resolution from"));
+
+    // Remove the wheel, which should invalidate the cached resolution.
+    fs_err::remove_file(wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"))?;
+
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg("wheelhouse")
+        .env("RUST_LOG", "uv=debug")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Using cached resolution from").not());
 
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Nothing should change.
+/// Never reuse a cached resolution when `--exclude-newer` is in the future, since new uploads could
+/// still change the result.
 #[test]
-fn upgrade_none() -> Result<()> {
+fn cached_resolution_exclude_newer_future() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str("tqdm")?;
 
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc! {r"
-        black==23.10.1
-        click==8.1.2
-            # via black
-        mypy-extensions==1.0.0
-            # via black
-        packaging==23.2
-            # via black
-        pathspec==0.11.0
-            # via black
-        platformdirs==4.0.0
-            # via black
-    "})?;
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheelhouse.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
 
-    let filters = if cfg!(windows) {
-        [("Resolved 7 packages", "Resolved 6 packages")]
-            .into_iter()
-            .chain(INSTA_FILTERS.to_vec())
-            .collect()
-    } else {
-        INSTA_FILTERS.to_vec()
-    };
+    for _ in 0..2 {
+        Command::new(get_bin())
+            .arg("pip")
+            .arg("compile")
+            .arg("requirements.in")
+            .arg("--cache-dir")
+            .arg(context.cache_dir.path())
+            .arg("--exclude-newer")
+            .arg("2999-01-01T00:00:00Z")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("wheelhouse")
+            .env("VIRTUAL_ENV", context.venv.as_os_str())
+            .env("RUST_LOG", "uv=debug")
+            .current_dir(&context.temp_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("tqdm==1000.0.0"))
+            .stderr(predicate::str::contains("Using cached resolution from").not());
+    }
 
-    uv_snapshot!(filters, context.compile()
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+#[test]
+fn find_links_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--output-file")
-            .arg("requirements.txt"), @r###"
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --find-links https://download.pytorch.org/whl/torch_stable.html
+    tqdm==4.64.1
 
     ----- stderr -----
-    Resolved 6 packages in [TIME]
+    Resolved 1 package in [TIME]
     "###
     );
 
-    // Read the output requirements, but skip the header.
-    let resolution = fs::read_to_string(requirements_txt.path())?
-        .lines()
-        .skip_while(|line| line.trim_start().starts_with('#'))
-        .join("\n");
-    if cfg!(unix) {
-        assert_snapshot!(resolution, @r###"
-        black==23.10.1
-        click==8.1.2
-            # via black
-        mypy-extensions==1.0.0
-            # via black
-        packaging==23.2
-            # via black
-        pathspec==0.11.0
-            # via black
-        platformdirs==4.0.0
-            # via black
-        "###);
-    }
-
     Ok(())
 }
 
-/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
-/// Both packages should be upgraded.
+/// Compile using index settings from a `uv.toml` file in the current directory.
 #[test]
-fn upgrade_all() -> Result<()> {
+fn find_links_uv_toml() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str("tqdm")?;
 
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str(indoc! {r"
-        # This file was autogenerated by uv v0.0.1 via the following command:
-        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
-        black==23.10.1
-        click==8.1.2
-            # via black
-        mypy-extensions==1.0.0
-            # via black
-        packaging==23.2
-            # via black
-        pathspec==0.11.0
-            # via black
-        platformdirs==4.0.0
-            # via black
-    "})?;
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str(indoc! {r#"
+        no-index = true
+        find-links = ["https://download.pytorch.org/whl/torch_stable.html"]
+    "#})?;
 
-    let filters = if cfg!(windows) {
-        [("Resolved 7 packages", "Resolved 6 packages")]
-            .into_iter()
-            .chain(INSTA_FILTERS.to_vec())
-            .collect()
-    } else {
-        INSTA_FILTERS.to_vec()
-    };
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==4.64.1
 
-    uv_snapshot!(filters, context.compile()
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // With `--no-config`, the `uv.toml` file should be ignored.
+    uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--output-file")
-            .arg("requirements.txt")
-            .arg("--upgrade"), @r###"
+            .arg("--no-config"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-config
+    tqdm==4.66.1
 
     ----- stderr -----
-    Resolved 6 packages in [TIME]
+    Resolved 1 package in [TIME]
     "###
     );
 
-    // Read the output requirements, but skip the header.
-    let resolution = fs::read_to_string(requirements_txt.path())?
-        .lines()
-        .skip_while(|line| line.trim_start().starts_with('#'))
+    Ok(())
+}
+
+/// Compile using resolution settings from the `[tool.uv]` table of a `pyproject.toml`, with
+/// command-line arguments taking precedence.
+#[test]
+fn resolution_pyproject_toml() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm>=4.64")?;
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+
+        [tool.uv]
+        resolution = "lowest-direct"
+    "#})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==4.64.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--resolution")
+            .arg("highest"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --resolution highest
+    tqdm==4.66.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a resolution that includes packages denied by the `policy` table of a `uv.toml` file.
+#[test]
+fn policy_deny() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str(indoc! {r#"
+        [policy]
+        deny = ["sniffio", "idna<3.5"]
+        deny-sources = ["git"]
+    "#})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    error: The resolution violates the package policy (2 violations)
+     x idna==3.4 is denied by `idna <3.5`
+     x sniffio==1.3.0 is denied by `sniffio`
+    hint: Add a constraint to exclude the denied packages, or update the configured policy
+    "###
+    );
+
+    Ok(())
+}
+
+/// Deny packages by license, matching SPDX expressions as a whole: `sniffio` is licensed under
+/// `MIT OR Apache-2.0`, so denying `MIT` alone doesn't deny it.
+#[test]
+fn policy_deny_licenses() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let uv_toml = context.temp_dir.child("uv.toml");
+    uv_toml.write_str(indoc! {r#"
+        [policy]
+        deny-licenses = ["MIT"]
+    "#})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    error: The resolution violates the package policy (1 violation)
+     x anyio==3.7.0 has a denied license (`MIT`)
+    hint: Add a constraint to exclude the denied packages, or update the configured policy
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
+/// with the URL itself provided in a `requirements.txt` file.
+#[test]
+fn find_links_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-find-links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-find-links
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Nothing should change.
+#[test]
+fn upgrade_none() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    let filters = if cfg!(windows) {
+        [
+            ("Resolved 7 packages", "Resolved 6 packages"),
+            (r" \+ colorama==0\.4\.6\n", ""),
+        ]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect()
+    } else {
+        INSTA_FILTERS.to_vec()
+    };
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    // Read the output requirements, but skip the header.
+    let resolution = fs::read_to_string(requirements_txt.path())?
+        .lines()
+        .skip_while(|line| line.trim_start().starts_with('#'))
+        .join("\n");
+    if cfg!(unix) {
+        assert_snapshot!(resolution, @r###"
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+        "###);
+    }
+
+    Ok(())
+}
+
+/// Check whether an output file is up-to-date with `--check`, without modifying it.
+#[test]
+fn check_output_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    // A missing output file is out-of-date.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    requirements.txt is out-of-date; re-run without `--check` to update it
+    "###
+    );
+    assert!(!context.temp_dir.child("requirements.txt").exists());
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // The freshly-written output file is up-to-date.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    requirements.txt is up-to-date
+    "###
+    );
+
+    // Adding a requirement makes the output file out-of-date.
+    requirements_in.write_str("anyio==3.7.0\niniconfig==2.0.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+     + iniconfig==2.0.0
+    requirements.txt is out-of-date; re-run without `--check` to update it
+    "###
+    );
+
+    Ok(())
+}
+
+/// Report an output file whose requirements are out of order as not canonical with `--check`.
+#[test]
+fn check_output_file_order() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // Reverse the order of the lines in the output file.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    let contents = fs::read_to_string(requirements_txt.path())?;
+    requirements_txt.write_str(&(contents.lines().rev().join("\n") + "\n"))?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    requirements.txt is not in canonical order; re-run without `--check` to update it
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write the output to stdout with `-o -`.
+#[test]
+fn output_file_stdout() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("-o")
+            .arg("-"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in -o -
+    iniconfig==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    assert!(!context.temp_dir.child("-").exists());
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Both packages should be upgraded.
+#[test]
+fn upgrade_all() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv v0.0.1 via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    let filters = if cfg!(windows) {
+        [
+            ("Resolved 7 packages", "Resolved 6 packages"),
+            (r" \+ colorama==0\.4\.6\n", ""),
+        ]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect()
+    } else {
+        INSTA_FILTERS.to_vec()
+    };
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+     ~ click 8.1.2 → 8.1.7
+     ~ pathspec 0.11.0 → 0.11.2
+    "###
+    );
+
+    // Read the output requirements, but skip the header.
+    let resolution = fs::read_to_string(requirements_txt.path())?
+        .lines()
+        .skip_while(|line| line.trim_start().starts_with('#'))
         .join("\n");
     if cfg!(unix) {
         assert_snapshot!(resolution, @r###"
@@ -2783,10 +3771,13 @@ fn upgrade_package() -> Result<()> {
     "})?;
 
     let filters = if cfg!(windows) {
-        [("Resolved 7 packages", "Resolved 6 packages")]
-            .into_iter()
-            .chain(INSTA_FILTERS.to_vec())
-            .collect()
+        [
+            ("Resolved 7 packages", "Resolved 6 packages"),
+            (r" \+ colorama==0\.4\.6\n", ""),
+        ]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect()
     } else {
         INSTA_FILTERS.to_vec()
     };
@@ -2803,6 +3794,7 @@ fn upgrade_package() -> Result<()> {
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
+     ~ click 8.1.2 → 8.1.7
     "###
     );
 
@@ -2831,6 +3823,50 @@ fn upgrade_package() -> Result<()> {
     Ok(())
 }
 
+/// Upgrade a single package, and write a summary of the changes to the pinned packages with
+/// `--changes-file`.
+#[test]
+fn upgrade_package_changes_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        iniconfig==1.1.1
+        tomli==2.0.1
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("iniconfig")
+            .arg("--changes-file")
+            .arg("changes.md"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+     ~ iniconfig 1.1.1 → 2.0.0
+     - tomli==2.0.1
+    "###
+    );
+
+    let changes = fs::read_to_string(context.temp_dir.child("changes.md").path())?;
+    assert_snapshot!(changes, @r###"
+    | Package | Old | New |
+    | --- | --- | --- |
+    | iniconfig | 1.1.1 | 2.0.0 |
+    | tomli | 2.0.1 |  |
+    "###);
+
+    Ok(())
+}
+
 /// Attempt to resolve a requirement at a path that doesn't exist.
 #[test]
 fn missing_path_requirement() -> Result<()> {
@@ -2880,7 +3916,7 @@ fn missing_editable_requirement() -> Result<()> {
     uv_snapshot!(filters, context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -2979,12 +4015,52 @@ fn no_header() -> Result<()> {
     Ok(())
 }
 
-/// Emit warnings when users pass redundant options from `pip-compile`.
+/// Combine the `pip-compile` output controls, e.g., to match an existing `requirements.txt` when
+/// migrating. Extras are never included in the pinned output, so `--strip-extras` is accepted
+/// with a warning.
+#[test]
+fn strip_extras_no_annotate_no_header() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black[d]==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--strip-extras")
+            .arg("--no-annotate")
+            .arg("--no-header"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    aiohttp==3.9.0
+    aiosignal==1.3.1
+    attrs==23.1.0
+    black==23.10.1
+    click==8.1.7
+    frozenlist==1.4.0
+    idna==3.4
+    multidict==6.0.4
+    mypy-extensions==1.0.0
+    packaging==23.2
+    pathspec==0.11.2
+    platformdirs==4.0.0
+    yarl==1.9.3
+
+    ----- stderr -----
+    warning: pip-compile's `--strip-extras` has no effect (uv always strips extras).
+    Resolved 13 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Pin unsafe packages with `--allow-unsafe`, which is the default.
 #[test]
 fn allow_unsafe() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("werkzeug==3.0.1")?;
+    requirements_in.write_str("werkzeug==3.0.1\nsetuptools==68.2.2")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
@@ -2996,11 +4072,62 @@ fn allow_unsafe() -> Result<()> {
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-unsafe
     markupsafe==2.1.3
         # via werkzeug
+    setuptools==68.2.2
     werkzeug==3.0.1
 
     ----- stderr -----
-    warning: pip-compile's `--allow-unsafe` has no effect (uv can safely pin `pip` and other packages).
-    Resolved 2 packages in [TIME]
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Omit unsafe packages with `--no-allow-unsafe`, listing them in a trailing comment.
+#[test]
+fn no_allow_unsafe() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("werkzeug==3.0.1\nsetuptools==68.2.2")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-allow-unsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-allow-unsafe
+    markupsafe==2.1.3
+        # via werkzeug
+    werkzeug==3.0.1
+
+    # The following packages are considered to be unsafe in a requirements file:
+    # setuptools
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // `--unsafe-package` replaces the default unsafe packages.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--unsafe-package")
+            .arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --unsafe-package markupsafe
+    setuptools==68.2.2
+    werkzeug==3.0.1
+
+    # The following packages are considered to be unsafe in a requirements file:
+    # markupsafe
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
     "###
     );
 
@@ -3108,6 +4235,36 @@ fn emit_find_links() -> Result<()> {
     Ok(())
 }
 
+/// Emit `--no-index` alongside the `--find-links` locations when the index is disabled.
+#[test]
+fn emit_no_index() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-index-url")
+            .arg("--emit-find-links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-index-url --emit-find-links
+    --no-index
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Respect the `--no-index` flag in a `requirements.txt` file.
 #[test]
 fn no_index_requirements_txt() -> Result<()> {
@@ -3118,7 +4275,7 @@ fn no_index_requirements_txt() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3200,7 +4357,7 @@ fn offline() -> Result<()> {
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3266,6 +4423,58 @@ fn offline() -> Result<()> {
     Ok(())
 }
 
+/// Resolve without network access via the `UV_OFFLINE` environment variable.
+#[test]
+fn offline_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_OFFLINE", "1"), @r###"
+    success: false
+    exit_code: 5
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because black==23.10.1 was not found in the cache and you require
+          black==23.10.1, we can conclude that the requirements are unsatisfiable.
+
+          hint: Packages were unavailable because the network was disabled
+    "###
+    );
+
+    // A falsey value leaves the network enabled.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_OFFLINE", "false"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve without network access via the `--offline` flag, using `--find-links` for an HTML
 /// registry.
 #[test]
@@ -3283,7 +4492,7 @@ fn offline_find_links() -> Result<()> {
             .arg("https://download.pytorch.org/whl/torch_stable.html")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3303,7 +4512,7 @@ fn offline_find_links() -> Result<()> {
             .arg("--no-index")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
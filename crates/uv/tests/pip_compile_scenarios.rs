@@ -124,7 +124,7 @@ fn requires_compatible_python_version_incompatible_override() -> Result<()> {
         .arg("--python-version=3.9")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -177,7 +177,7 @@ fn requires_incompatible_python_version_compatible_override_no_wheels() -> Resul
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -288,7 +288,7 @@ fn requires_incompatible_python_version_compatible_override_no_compatible_wheels
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -346,7 +346,7 @@ fn requires_incompatible_python_version_compatible_override_other_wheel() -> Res
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -404,7 +404,7 @@ fn requires_python_patch_version_override_no_patch() -> Result<()> {
         .arg("--python-version=3.8")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -0,0 +1,199 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use insta::assert_snapshot;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip download` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("download")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Download a wheel for a foreign platform into a wheelhouse.
+#[test]
+fn download_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("MarkupSafe==2.1.3")
+        .arg("--dest")
+        .arg("wheelhouse")
+        .arg("--python-version")
+        .arg("3.12")
+        .arg("--platform")
+        .arg("x86_64-pc-windows-msvc"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 file to wheelhouse in [TIME]
+     + MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("wheelhouse")
+        .child("MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Download the transitive dependencies of a package, respecting the target platform's markers.
+#[test]
+fn download_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("flask==3.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--dest")
+        .arg("wheelhouse")
+        .arg("--platform")
+        .arg("x86_64-pc-windows-msvc"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 8 packages in [TIME]
+    Downloaded 8 files to wheelhouse in [TIME]
+     + Jinja2-3.1.2-py3-none-any.whl
+     + MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl
+     + blinker-1.7.0-py3-none-any.whl
+     + click-8.1.7-py3-none-any.whl
+     + colorama-0.4.6-py2.py3-none-any.whl
+     + flask-3.0.0-py3-none-any.whl
+     + itsdangerous-2.1.2-py3-none-any.whl
+     + werkzeug-3.0.1-py3-none-any.whl
+    "###
+    );
+
+    Ok(())
+}
+
+/// Download a wheelhouse for multiple platforms at once, along with a manifest of the files that
+/// each platform requires.
+#[test]
+fn download_multiple_platforms() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("MarkupSafe==2.1.3")
+        .arg("--dest")
+        .arg("wheelhouse")
+        .arg("--python-version")
+        .arg("3.12")
+        .arg("--platform")
+        .arg("x86_64-pc-windows-msvc")
+        .arg("--python-platform")
+        .arg("x86_64-unknown-linux-gnu")
+        .arg("--manifest")
+        .arg("manifest.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package for Python 3.12 on x86_64-pc-windows-msvc in [TIME]
+    Resolved 1 package for Python 3.12 on x86_64-unknown-linux-gnu in [TIME]
+    Downloaded 2 files to wheelhouse in [TIME]
+     + MarkupSafe-2.1.3-cp312-cp312-manylinux_2_17_x86_64.manylinux2014_x86_64.whl
+     + MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl
+    "###
+    );
+
+    let manifest = fs::read_to_string(context.temp_dir.child("manifest.json").path())?;
+    assert_snapshot!(manifest, @r###"
+    {
+      "targets": [
+        {
+          "python-version": "3.12",
+          "platform": "x86_64-pc-windows-msvc",
+          "files": [
+            "MarkupSafe-2.1.3-cp312-cp312-win_amd64.whl"
+          ]
+        },
+        {
+          "python-version": "3.12",
+          "platform": "x86_64-unknown-linux-gnu",
+          "files": [
+            "MarkupSafe-2.1.3-cp312-cp312-manylinux_2_17_x86_64.manylinux2014_x86_64.whl"
+          ]
+        }
+      ]
+    }
+    "###);
+
+    Ok(())
+}
+
+/// Download a wheel from a URL that's pinned to the wrong hash. The download is rejected, and
+/// nothing is written to the destination.
+#[test]
+fn download_url_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")?;
+
+    let filters: Vec<_> = [(
+        r"Computed:\n  sha256:[a-f0-9]+",
+        "Computed:\n  sha256:[HASH]",
+    )]
+    .into_iter()
+    .chain(common::INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--dest")
+        .arg("wheelhouse"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Hash mismatch for werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+
+    Expected:
+      sha256:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+
+    Computed:
+      sha256:[HASH]
+    "###
+    );
+
+    assert!(fs::read_dir(context.temp_dir.child("wheelhouse"))?
+        .next()
+        .is_none());
+
+    Ok(())
+}
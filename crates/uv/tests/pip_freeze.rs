@@ -0,0 +1,76 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use url::Url;
+
+use common::{uv_snapshot, INSTA_FILTERS};
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Export the packages in an environment as a pinned requirements file, with the hashes of the
+/// matching distributions on the index.
+#[test]
+fn freeze_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheel = project_root
+        .join("scripts")
+        .join("wheels")
+        .join("tqdm-1000.0.0-py3-none-any.whl");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&format!(
+        "tomli==2.0.1\ntqdm @ {}",
+        Url::from_file_path(wheel).unwrap()
+    ))?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    // In addition to the standard filters, remove the project root and the hashes from the
+    // snapshot.
+    let filters: Vec<_> = [
+        (r"file://.*/", "file://[PROJECT_ROOT]/"),
+        (r"sha256:[a-f0-9]{64}", "sha256:[HASH]"),
+    ]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("pip")
+        .arg("freeze")
+        .arg("--lock")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    tomli==2.0.1 \
+        --hash=sha256:[HASH] \
+        --hash=sha256:[HASH]
+    tqdm @ file://[PROJECT_ROOT]/tqdm-1000.0.0-py3-none-any.whl
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
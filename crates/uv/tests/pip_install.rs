@@ -9,6 +9,7 @@ use indoc::indoc;
 use url::Url;
 
 use common::{uv_snapshot, TestContext, EXCLUDE_NEWER, INSTA_FILTERS};
+use uv_fs::Normalized;
 
 use crate::common::get_bin;
 
@@ -60,7 +61,7 @@ fn no_solution() {
         .arg("WerkZeug<1.0.0")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -640,7 +641,7 @@ fn install_no_index() {
         .arg("Flask")
         .arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -667,7 +668,7 @@ fn install_no_index_version() {
         .arg("Flask==3.0.0")
         .arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1012,3 +1013,301 @@ fn install_upgrade() {
     "###
     );
 }
+
+/// Install a package that provides the same console script as an installed package.
+#[test]
+#[cfg(unix)]
+fn install_script_conflict() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create two packages that both provide a `hello` script.
+    for name in ["foo", "bar"] {
+        let project = context.temp_dir.child(name);
+        project
+            .child("pyproject.toml")
+            .write_str(&indoc::formatdoc! {r#"
+            [build-system]
+            requires = ["setuptools>=42"]
+            build-backend = "setuptools.build_meta"
+
+            [project]
+            name = "{name}"
+            version = "0.1.0"
+
+            [project.scripts]
+            hello = "{name}:main"
+            "#
+            })?;
+        project
+            .child(format!("{name}.py"))
+            .write_str(&format!("def main():\n    print(\"{name}\")\n"))?;
+    }
+
+    let temp_dir = regex::escape(
+        Url::from_directory_path(context.temp_dir.path())
+            .unwrap()
+            .as_str()
+            .trim_end_matches('/'),
+    );
+    let filters = [(temp_dir.as_str(), "file://[TEMP_DIR]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    let foo = format!(
+        "foo @ {}",
+        Url::from_directory_path(context.temp_dir.child("foo").path()).unwrap()
+    );
+    let bar = format!(
+        "bar @ {}",
+        Url::from_directory_path(context.temp_dir.child("bar").path()).unwrap()
+    );
+
+    command(&context).arg(&foo).assert().success();
+
+    // With `--strict`, refuse to overwrite the existing script.
+    uv_snapshot!(filters, command(&context)
+        .arg(&bar)
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error: The script `hello` is provided by multiple packages (`bar`, `foo`), and would be overwritten (remove `--strict` to install anyway)
+    "###
+    );
+
+    // Otherwise, warn and overwrite it.
+    uv_snapshot!(filters, command(&context)
+        .arg(&bar), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: The script `hello` is provided by multiple packages (`bar`, `foo`), and would be overwritten
+    Installed 1 package in [TIME]
+     + bar==0.1.0 (from file://[TEMP_DIR]/bar/)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a local project that uses the built-in build backend, which requires neither a build
+/// environment nor network access.
+#[test]
+fn install_builtin_build_backend() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.1.0"
+
+        [project.scripts]
+        example = "example:main"
+
+        [build-system]
+        requires = []
+        build-backend = "uv_build_backend"
+        "#
+    })?;
+    project
+        .child("src")
+        .child("example")
+        .child("__init__.py")
+        .write_str("def main():\n    print('Hello, world!')\n")?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg(format!(
+            "example @ {}",
+            Url::from_directory_path(project.path()).unwrap()
+        ))
+        .arg("--offline")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/)
+    "###
+    );
+
+    context.assert_command("import example").success();
+
+    Ok(())
+}
+
+/// Install a local wheel by its path, without a package name, both on the command line and in a
+/// requirements file.
+#[test]
+fn install_local_wheel_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheels = context.temp_dir.child("wheels");
+    wheels.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheels.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("./wheels/tqdm-1000.0.0-py3-none-any.whl")
+        .arg("--offline")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0 (from file://[TEMP_DIR]/tqdm-1000.0.0-py3-none-any.whl)
+    "###
+    );
+
+    context.assert_command("import tqdm").success();
+
+    // Reinstall from a requirements file, which resolves the path relative to the file.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("./wheels/tqdm-1000.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .arg("--offline")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - tqdm==1000.0.0 (from file://[TEMP_DIR]/tqdm-1000.0.0-py3-none-any.whl)
+     + tqdm==1000.0.0 (from file://[TEMP_DIR]/tqdm-1000.0.0-py3-none-any.whl)
+    "###
+    );
+
+    context.assert_command("import tqdm").success();
+
+    Ok(())
+}
+
+/// Reject a local wheel whose tags are incompatible with the environment, prior to resolution.
+#[test]
+fn install_local_wheel_path_incompatible() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let wheels = context.temp_dir.child("wheels");
+    wheels.create_dir_all()?;
+    wheels.child("foo-1.2.3-not-compatible-wheel.whl").touch()?;
+
+    let temp_dir = regex::escape(
+        &context
+            .temp_dir
+            .path()
+            .canonicalize()?
+            .normalized_display()
+            .to_string(),
+    );
+    let filters: Vec<_> = [(temp_dir.as_str(), "[TEMP_DIR]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("./wheels/foo-1.2.3-not-compatible-wheel.whl")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: A path dependency is incompatible with the current platform: [TEMP_DIR]/wheels/foo-1.2.3-not-compatible-wheel.whl
+    "###
+    );
+
+    Ok(())
+}
+
+/// Skip local wheels whose markers don't apply to the environment, including in directories with
+/// whitespace in their names.
+#[test]
+fn install_local_wheel_path_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheels = context.temp_dir.child("my wheels");
+    wheels.create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheels.join("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    wheels.child("foo-1.2.3-not-compatible-wheel.whl").touch()?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        ./my wheels/tqdm-1000.0.0-py3-none-any.whl ; python_version >= '3.12'
+        ./my wheels/foo-1.2.3-not-compatible-wheel.whl ; python_version < '3.0'
+        "
+    })?;
+
+    uv_snapshot!(filters, command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--offline")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==1000.0.0 (from file://[TEMP_DIR]/tqdm-1000.0.0-py3-none-any.whl)
+    "###
+    );
+
+    context.assert_command("import tqdm").success();
+
+    Ok(())
+}
@@ -79,7 +79,7 @@ fn requires_package_does_not_exist() {
         .arg("a-5a1a4a35")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -117,7 +117,7 @@ fn requires_exact_version_does_not_exist() {
         .arg("a-7cff23d9==2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -157,7 +157,7 @@ fn requires_greater_version_does_not_exist() {
         .arg("a-63569c9e>1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -198,7 +198,7 @@ fn requires_less_version_does_not_exist() {
         .arg("a-2af6fa02<2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -238,7 +238,7 @@ fn transitive_requires_package_does_not_exist() {
         .arg("a-64b04b2b")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -278,7 +278,7 @@ fn excluded_only_version() {
         .arg("a-72f0d052!=1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -338,7 +338,7 @@ fn excluded_only_compatible_version() {
                 .arg("b-d6ce69da<3.0.0,>=2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -438,7 +438,7 @@ fn dependency_excludes_range_of_compatible_versions() {
                 .arg("c-5824fb81")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -553,7 +553,7 @@ fn dependency_excludes_non_contiguous_range_of_compatible_versions() {
                 .arg("c-119f929b")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -775,7 +775,7 @@ fn extra_incompatible_with_extra() {
         .arg("a-a5547b80[extra_b,extra_c]")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -885,7 +885,7 @@ fn extra_incompatible_with_root() {
                 .arg("b-aca6971b==2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -985,7 +985,7 @@ fn direct_incompatible_versions() {
                 .arg("a-c0e7adfa==2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1034,7 +1034,7 @@ fn transitive_incompatible_with_root_version() {
                 .arg("b-a13da883==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1089,7 +1089,7 @@ fn transitive_incompatible_with_transitive() {
                 .arg("b-ec82e315")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1175,7 +1175,7 @@ fn package_only_prereleases_in_range() {
         .arg("a-1017748b>0.1.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1594,7 +1594,7 @@ fn transitive_package_only_prereleases_in_range() {
         .arg("a-20238f1b")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1708,7 +1708,7 @@ fn transitive_prerelease_and_stable_dependency() {
                 .arg("b-d62255d0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1856,7 +1856,7 @@ fn transitive_prerelease_and_stable_dependency_many_versions() {
                 .arg("b-cc6a6eac")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1934,7 +1934,7 @@ fn transitive_prerelease_and_stable_dependency_many_versions_holes() {
                 .arg("b-041e36bc")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1991,7 +1991,7 @@ fn requires_python_version_does_not_exist() {
         .arg("a-4486c0e5==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2032,7 +2032,7 @@ fn requires_python_version_less_than_current() {
         .arg("a-d4ea58de==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2073,7 +2073,7 @@ fn requires_python_version_greater_than_current() {
         .arg("a-741c8854==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2114,7 +2114,7 @@ fn requires_python_version_greater_than_current_patch() {
         .arg("a-0044ac94==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2177,7 +2177,7 @@ fn requires_python_version_greater_than_current_many() {
         .arg("a-da5bd150==1.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2274,7 +2274,7 @@ fn requires_python_version_greater_than_current_excluded() {
         .arg("a-874cae6d>=2.0.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2482,7 +2482,7 @@ fn no_sdist_no_wheels_with_matching_platform() {
         .arg("a-94e293e5")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2522,7 +2522,7 @@ fn no_sdist_no_wheels_with_matching_python() {
         .arg("a-40fe677d")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2562,7 +2562,7 @@ fn no_sdist_no_wheels_with_matching_abi() {
         .arg("a-8727a9b9")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2645,7 +2645,7 @@ fn only_wheels_no_binary() {
         .arg("a-dd137625")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2771,7 +2771,7 @@ fn package_only_yanked() {
         .arg("a-e3de7eb4")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2813,7 +2813,7 @@ fn package_only_yanked_in_range() {
         .arg("a-84b3720e>0.1.0")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2952,7 +2952,7 @@ fn transitive_package_only_yanked() {
         .arg("a-9ec30fe2")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3001,7 +3001,7 @@ fn transitive_package_only_yanked_in_range() {
         .arg("a-872d714e")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3117,7 +3117,7 @@ fn transitive_yanked_and_unyanked_dependency() {
                 .arg("b-eb1ba5f5")
         , @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
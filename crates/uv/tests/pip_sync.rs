@@ -1,8 +1,10 @@
 #![cfg(all(feature = "python", feature = "pypi"))]
 
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
@@ -61,7 +63,7 @@ fn uninstall_command(context: &TestContext) -> Command {
 fn missing_pip() {
     uv_snapshot!(Command::new(get_bin()).arg("sync"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -160,6 +162,252 @@ fn install() -> Result<()> {
     Ok(())
 }
 
+/// Install into the environment given by `--python`, rather than the detected environment.
+#[test]
+fn install_python_override() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project = assert_fs::TempDir::new()?;
+
+    let requirements_txt = project.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg(context.venv.as_os_str())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env_remove("VIRTUAL_ENV")
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    // Refuse to install into an interpreter outside of a virtual environment.
+    let pyvenv_cfg = fs::read_to_string(context.venv.join("pyvenv.cfg"))?;
+    let base_executable = pyvenv_cfg
+        .lines()
+        .find_map(|line| line.strip_prefix("base-executable = "))
+        .expect("pyvenv.cfg to contain the base executable");
+    let filter_python = regex::escape(base_executable);
+    let filters = [(filter_python.as_str(), "[PYTHON]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--python")
+        .arg(base_executable)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env_remove("VIRTUAL_ENV")
+        .current_dir(&project), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The Python interpreter at `[PYTHON]` is not in a virtualenv. Refusing to modify a system interpreter; run `uv venv` to create a virtualenv.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install into the environment given by `UV_PYTHON`, rather than the detected environment.
+#[test]
+fn install_python_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project = assert_fs::TempDir::new()?;
+
+    let requirements_txt = project.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_PYTHON", context.venv.as_os_str())
+        .env_remove("VIRTUAL_ENV")
+        .current_dir(&project), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Report the changes to the environment as JSON.
+#[test]
+fn install_format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let filters: Vec<_> = [(r#""elapsed_ms": \d+"#, r#""elapsed_ms": [TIME]"#)]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--format")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "installed": [
+        {
+          "name": "markupsafe",
+          "version": "2.1.3"
+        }
+      ],
+      "uninstalled": [],
+      "failed": [],
+      "elapsed_ms": [TIME]
+    }
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
+
+/// Report the installation plan before executing it, as text and as JSON.
+#[test]
+fn install_report_plan() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--report")
+        .arg("--report-json")
+        .arg("plan.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+     + tomli==2.0.1 (download, [SIZE])
+    Would download 1 package ([SIZE]), build 0 packages, link 0 packages from the cache, and uninstall 0 packages
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    let plan: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        context.temp_dir.child("plan.json").path(),
+    )?)?;
+    assert_eq!(plan["install"][0]["name"], "tomli");
+    assert_eq!(plan["install"][0]["version"], "2.0.1");
+    assert_eq!(plan["install"][0]["source"], "download");
+    assert_eq!(plan["install"][0]["size"], plan["download_bytes"]);
+    assert_eq!(plan["uninstall"].as_array().map(Vec::len), Some(0));
+
+    Ok(())
+}
+
+/// Re-download a cached wheel whose files no longer match its `RECORD`, with `--verify-hashes`.
+#[test]
+fn install_verify_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    // Corrupt a file in the unzipped wheel.
+    let wheel = fs::canonicalize(
+        context
+            .cache_dir
+            .child("wheels-v0")
+            .child("pypi")
+            .child("tomli")
+            .child("tomli-2.0.1-py3-none-any"),
+    )?;
+    fs::write(wheel.join("tomli").join("__init__.py"), "raise ImportError")?;
+
+    let filters: Vec<_> = [(r"sha256=[\w-]+", "sha256=[HASH]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .arg("--verify-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Cached distribution is corrupt, downloading it again: tomli==2.0.1 (RECORD file doesn't match wheel contents: Hash mismatch for tomli/__init__.py. Recorded: sha256=[HASH], Actual: sha256=[HASH])
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - tomli==2.0.1
+     + tomli==2.0.1
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment using copy semantics.
 #[test]
 fn install_copy() -> Result<()> {
@@ -264,6 +512,43 @@ fn install_many() -> Result<()> {
     Ok(())
 }
 
+/// Install multiple packages with explicit concurrency limits, which take precedence over the
+/// limits detected from the system.
+#[test]
+fn install_many_sequential() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .arg("--concurrent-downloads")
+        .arg("1")
+        .env("UV_CONCURRENT_BUILDS", "1")
+        .env("UV_CONCURRENT_INSTALLS", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
+    "###
+    );
+
+    context
+        .assert_command("import markupsafe; import tomli")
+        .success();
+
+    Ok(())
+}
+
 /// Attempt to install an already-installed package into a virtual environment.
 #[test]
 fn noop() -> Result<()> {
@@ -402,6 +687,48 @@ fn add_remove() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment, then sync the virtual environment with a
+/// different requirements file using `--inexact`, which should leave the first package in place.
+#[test]
+fn add_inexact() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--inexact"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    context
+        .assert_command("import markupsafe; import tomli")
+        .success();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment, then install a second package into the same
 /// virtual environment.
 #[test]
@@ -515,6 +842,48 @@ fn install_url() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment from a URL that's pinned to a hash.
+#[test]
+fn install_url_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")?;
+
+    let filters: Vec<_> = [(
+        r"Computed:\n  sha256:[a-f0-9]+",
+        "Computed:\n  sha256:[HASH]",
+    )]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    // The download doesn't match the pinned hash.
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+      Caused by: Hash mismatch for werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+
+    Expected:
+      sha256:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+
+    Computed:
+      sha256:[HASH]
+    "###
+    );
+
+    context.assert_command("import werkzeug").failure();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment from a Git repository.
 #[test]
 #[cfg(feature = "git")]
@@ -646,7 +1015,369 @@ fn install_sdist_url() -> Result<()> {
     requirements_txt.touch()?;
     requirements_txt.write_str("Werkzeug @ https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz")?;
 
-    uv_snapshot!(command(&context)
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + werkzeug==0.9.6 (from https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz)
+    "###
+    );
+
+    context.assert_command("import werkzeug").success();
+
+    Ok(())
+}
+
+/// Return the entries in a directory tree, along with their sizes and modification times.
+fn cache_tree(root: &Path) -> Result<BTreeMap<PathBuf, (u64, SystemTime)>> {
+    let mut tree = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry?;
+        let metadata = entry.path().symlink_metadata()?;
+        tree.insert(
+            entry.path().strip_prefix(root)?.to_path_buf(),
+            (metadata.len(), metadata.modified()?),
+        );
+    }
+    Ok(tree)
+}
+
+/// Remove the wheels built from source distributions from a cache, leaving the unzipped source
+/// distributions in place, such that they're built again.
+fn remove_built_wheels(cache_dir: &Path) -> Result<()> {
+    for bucket in fs_err::read_dir(cache_dir)? {
+        let bucket = bucket?.path();
+        if !bucket
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("built-wheels-"))
+        {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&bucket) {
+            let entry = entry?;
+            let is_built = entry.file_type().is_symlink()
+                || entry.path().extension().is_some_and(|ext| ext == "whl")
+                || entry.file_name() == "metadata.msgpack";
+            if is_built {
+                fs_err::remove_file(entry.path())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build a source distribution whose shard already exists in a read-only cache (via
+/// `--cache-readonly`). The build should happen in the writable scratch cache, leaving the
+/// read-only cache untouched.
+#[test]
+fn install_sdist_url_readonly_cache() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("Werkzeug @ https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz")?;
+
+    // Populate the cache, then drop the built wheel, such that only the source distribution
+    // remains.
+    command(&context).arg("requirements.txt").assert().success();
+    remove_built_wheels(context.cache_dir.path())?;
+    let before = cache_tree(context.cache_dir.path())?;
+
+    // Install into a fresh environment, treating the populated cache as read-only.
+    let fresh = TestContext::new("3.12");
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--cache-readonly")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", fresh.venv.as_os_str())
+        .current_dir(&fresh.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + werkzeug==0.9.6 (from https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz)
+    "###
+    );
+
+    fresh.assert_command("import werkzeug").success();
+
+    // The read-only cache is left untouched.
+    assert_eq!(before, cache_tree(context.cache_dir.path())?);
+
+    Ok(())
+}
+
+/// Build a source distribution whose shard already exists in a read-only cache overlaid on a
+/// writable cache (via `--cache-readonly-dir`). The build should be persisted to the writable
+/// cache, leaving the read-only cache untouched.
+#[test]
+fn install_sdist_url_readonly_cache_dir() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("Werkzeug @ https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz")?;
+
+    // Populate the cache, then drop the built wheel, such that only the source distribution
+    // remains.
+    command(&context).arg("requirements.txt").assert().success();
+    remove_built_wheels(context.cache_dir.path())?;
+    let before = cache_tree(context.cache_dir.path())?;
+
+    // Install into a fresh environment, overlaying the populated cache on an empty cache.
+    let fresh = TestContext::new("3.12");
+    uv_snapshot!(command(&fresh)
+        .arg(requirements_txt.path())
+        .arg("--cache-readonly-dir")
+        .arg(context.cache_dir.path())
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + werkzeug==0.9.6 (from https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz)
+    "###
+    );
+
+    fresh.assert_command("import werkzeug").success();
+
+    // The read-only cache is left untouched, and the build is persisted to the writable cache.
+    assert_eq!(before, cache_tree(context.cache_dir.path())?);
+    assert!(cache_tree(fresh.cache_dir.path())?
+        .keys()
+        .any(|path| path.extension().is_some_and(|ext| ext == "whl")));
+
+    Ok(())
+}
+
+/// Install a source distribution, recording the built wheel in a build manifest.
+#[test]
+fn install_sdist_build_manifest() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("Werkzeug==0.9.6")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--build-manifest")
+        .arg("manifest.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + werkzeug==0.9.6
+    "###
+    );
+
+    // The digest of the built wheel isn't reproducible, so check its shape rather than its value.
+    let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        context.temp_dir.child("manifest.json"),
+    )?)?;
+    let wheels = manifest["wheels"].as_array().unwrap();
+    assert_eq!(wheels.len(), 1);
+    let filename = wheels[0]["filename"].as_str().unwrap();
+    assert!(filename.to_lowercase().starts_with("werkzeug-0.9.6-"));
+    assert!(filename.ends_with(".whl"));
+    assert_eq!(wheels[0]["sha256"].as_str().unwrap().len(), 64);
+    assert_eq!(wheels[0]["source"]["name"], "werkzeug");
+    assert_eq!(wheels[0]["source"]["version"], "0.9.6");
+    assert!(wheels[0]["source"]["url"]
+        .as_str()
+        .unwrap()
+        .ends_with("/Werkzeug-0.9.6.tar.gz"));
+
+    Ok(())
+}
+
+/// Attempt to re-install a package into a virtual environment from a URL. The second install
+/// should be a no-op.
+#[test]
+fn install_url_then_install_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    context.assert_command("import werkzeug").success();
+
+    Ok(())
+}
+
+/// Re-sync an environment that already satisfies the requirements. The second sync should be a
+/// no-op, even when offline.
+#[test]
+fn install_then_sync_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("markupsafe==2.1.3")?;
+
+    command(&context).arg("requirements.txt").assert().success();
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Sync over a distribution installed by a legacy installer (i.e., `setup.py install`), which is
+/// represented by an `.egg-info` directory rather than a `.dist-info` directory.
+#[test]
+#[cfg(unix)]
+fn sync_over_egg_info() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = context
+        .venv
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages");
+
+    // Create an `.egg-info` install, as `setup.py install` would.
+    let egg_info = site_packages.join("legacy_pkg-1.0.0-py3.12.egg-info");
+    fs::create_dir_all(&egg_info)?;
+    fs::write(
+        egg_info.join("PKG-INFO"),
+        "Metadata-Version: 1.0\nName: legacy-pkg\nVersion: 1.0.0\n",
+    )?;
+    fs::write(egg_info.join("top_level.txt"), "legacy_pkg\n")?;
+    fs::create_dir_all(site_packages.join("legacy_pkg"))?;
+    fs::write(site_packages.join("legacy_pkg").join("__init__.py"), "")?;
+
+    context.assert_command("import legacy_pkg").success();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - legacy-pkg==1.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    context.assert_command("import tomli").success();
+    context.assert_command("import legacy_pkg").failure();
+    assert!(!egg_info.exists());
+
+    Ok(())
+}
+
+/// Sync over a distribution installed in development mode (i.e., `setup.py develop`), which is
+/// represented by an `.egg-link` file and an entry in `easy-install.pth`.
+#[test]
+#[cfg(unix)]
+fn sync_over_egg_link() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = context
+        .venv
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages");
+
+    // Create a source tree with an `.egg-info` directory, as `setup.py develop` would.
+    let project = context.temp_dir.child("project");
+    project
+        .child("legacy_editable")
+        .child("__init__.py")
+        .touch()?;
+    project
+        .child("legacy_editable.egg-info")
+        .child("PKG-INFO")
+        .write_str("Metadata-Version: 1.0\nName: legacy-editable\nVersion: 0.1.0\n")?;
+
+    // Link the source tree into the environment.
+    fs::write(
+        site_packages.join("legacy-editable.egg-link"),
+        format!("{}\n.", project.path().display()),
+    )?;
+    fs::write(
+        site_packages.join("easy-install.pth"),
+        format!("{}\n", project.path().display()),
+    )?;
+
+    context.assert_command("import legacy_editable").success();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    let project_url = regex::escape(
+        Url::from_directory_path(project.path())
+            .unwrap()
+            .as_str()
+            .trim_end_matches('/'),
+    );
+    let filters = INSTA_FILTERS
+        .iter()
+        .chain(&[(project_url.as_str(), "file://[PROJECT_DIR]")])
+        .copied()
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: true
@@ -656,45 +1387,73 @@ fn install_sdist_url() -> Result<()> {
     ----- stderr -----
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
     Installed 1 package in [TIME]
-     + werkzeug==0.9.6 (from https://files.pythonhosted.org/packages/63/69/5702e5eb897d1a144001e21d676676bcb87b88c0862f947509ea95ea54fc/Werkzeug-0.9.6.tar.gz)
+     - legacy-editable==0.1.0 (from file://[PROJECT_DIR]/)
+     + tomli==2.0.1
     "###
     );
 
-    context.assert_command("import werkzeug").success();
+    context.assert_command("import tomli").success();
+    context.assert_command("import legacy_editable").failure();
+    assert!(!site_packages.join("legacy-editable.egg-link").exists());
+
+    // The source tree should be left untouched.
+    project
+        .child("legacy_editable")
+        .child("__init__.py")
+        .assert(predicates::path::exists());
 
     Ok(())
 }
 
-/// Attempt to re-install a package into a virtual environment from a URL. The second install
-/// should be a no-op.
+/// Install a package with a console script, using an `env`-based shebang.
 #[test]
-fn install_url_then_install_url() -> Result<()> {
+#[cfg(unix)]
+fn install_shebang_env() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.touch()?;
-    requirements_txt.write_str("werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+    requirements_txt.write_str("tqdm==4.66.1")?;
 
     command(&context)
         .arg("requirements.txt")
-        .arg("--strict")
+        .arg("--shebang")
+        .arg("env")
         .assert()
         .success();
 
-    uv_snapshot!(command(&context)
+    let script = fs::read_to_string(context.venv.join("bin").join("tqdm"))?;
+    assert_eq!(script.lines().next(), Some("#!/usr/bin/env python"));
+
+    Ok(())
+}
+
+/// Install a package with a console script, using a relative shebang, then move the environment.
+/// The script should continue to run against the moved environment.
+#[test]
+#[cfg(unix)]
+fn install_shebang_relative() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm==4.66.1")?;
+
+    command(&context)
         .arg("requirements.txt")
-        .arg("--strict"), @r###"
-    success: true
-    exit_code: 0
-    ----- stdout -----
+        .arg("--shebang")
+        .arg("relative")
+        .assert()
+        .success();
 
-    ----- stderr -----
-    Audited 1 package in [TIME]
-    "###
-    );
+    let moved = context.temp_dir.child("moved");
+    fs::rename(&context.venv, &moved)?;
 
-    context.assert_command("import werkzeug").success();
+    Command::new(moved.join("bin").join("tqdm"))
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout("4.66.1\n");
 
     Ok(())
 }
@@ -1740,6 +2499,110 @@ fn install_path_built_dist_cached() -> Result<()> {
     Ok(())
 }
 
+/// Check that wheels built from a local source tree are invalidated when any source file changes,
+/// and not just the `pyproject.toml`.
+#[test]
+fn install_path_source_tree_modified() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local source tree.
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.1.0"
+
+[build-system]
+requires = ["setuptools>=42"]
+build-backend = "setuptools.build_meta"
+"#,
+    )?;
+    project
+        .child("example")
+        .child("__init__.py")
+        .write_str("VALUE = 1\n")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(&format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    ))?;
+
+    // In addition to the standard filters, remove the temporary directory from the snapshot.
+    let filters: Vec<_> = [(r"file://.*/", "file://[TEMP_DIR]/")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/)
+    "###
+    );
+
+    // Re-run the installation in a new virtual environment. The build artifacts that setuptools
+    // writes into the source tree shouldn't invalidate the cached wheel.
+    let parent = assert_fs::TempDir::new()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/)
+    "###
+    );
+
+    // Modify a module, then re-run the installation in a new virtual environment. The wheel
+    // should be rebuilt.
+    project
+        .child("example")
+        .child("__init__.py")
+        .write_str("VALUE = 2\n")?;
+
+    let parent = assert_fs::TempDir::new()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.1.0 (from file://[TEMP_DIR]/)
+    "###
+    );
+
+    check_command(
+        &venv,
+        "import example; assert example.VALUE == 2",
+        &context.temp_dir,
+    );
+
+    Ok(())
+}
+
 /// Check that we show the right messages on cached, direct URL built distribution installs.
 #[test]
 fn install_url_built_dist_cached() -> Result<()> {
@@ -1852,8 +2715,60 @@ fn duplicate_package_overlap() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Failed to determine installation plan
-      Caused by: Detected duplicate package in requirements: markupsafe
+    error: Conflicting requirements for `markupsafe`: `markupsafe==2.1.3` and `markupsafe==2.1.2`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Sync from multiple requirements files, deduplicating any packages that appear in both.
+#[test]
+fn multiple_requirements_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let dev_requirements_txt = context.temp_dir.child("dev-requirements.txt");
+    dev_requirements_txt.touch()?;
+    dev_requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("dev-requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tomli==2.0.1
+    "###
+    );
+
+    context
+        .assert_command("import markupsafe; import tomli")
+        .success();
+
+    // Conflicting pins across files should be rejected.
+    dev_requirements_txt.write_str("MarkupSafe==2.1.2")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("dev-requirements.txt")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Conflicting requirements for `markupsafe`: `markupsafe==2.1.3` and `markupsafe==2.1.2`
     "###
     );
 
@@ -2610,6 +3525,76 @@ fn find_links() -> Result<()> {
     Ok(())
 }
 
+/// Sync from a `--find-links` index that advertises the wrong hash for a wheel. The download is
+/// rejected, and nothing is persisted to the cache.
+#[test]
+fn find_links_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let index_html = context.temp_dir.child("index.html");
+    index_html.write_str(indoc! {r#"
+        <!DOCTYPE html>
+        <html>
+          <body>
+            <a href="https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl#sha256=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa">Werkzeug-2.0.0-py3-none-any.whl</a>
+          </body>
+        </html>
+    "#})?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("werkzeug==2.0.0")?;
+
+    let filters: Vec<_> = [(
+        r"Computed:\n  sha256:[a-f0-9]+",
+        "Computed:\n  sha256:[HASH]",
+    )]
+    .into_iter()
+    .chain(INSTA_FILTERS.to_vec())
+    .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg("index.html"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: werkzeug==2.0.0
+      Caused by: Hash mismatch for werkzeug==2.0.0
+
+    Expected:
+      sha256:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+
+    Computed:
+      sha256:[HASH]
+    "###
+    );
+
+    context.assert_command("import werkzeug").failure();
+
+    // Neither the unzipped wheel nor its HTTP cache entry was persisted.
+    let archives = context.cache_dir.child("archive-v0");
+    assert!(!archives.exists() || fs_err::read_dir(&archives)?.next().is_none());
+    for entry in walkdir::WalkDir::new(context.cache_dir.path()) {
+        let entry = entry?;
+        assert!(
+            !entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("Werkzeug-2.0.0-py3-none-any"),
+            "Unexpected cache entry: {}",
+            entry.path().display()
+        );
+    }
+
+    Ok(())
+}
+
 /// Install without network access via the `--offline` flag.
 #[test]
 fn offline() -> Result<()> {
@@ -2622,7 +3607,7 @@ fn offline() -> Result<()> {
         .arg("requirements.in")
         .arg("--offline"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -2664,3 +3649,107 @@ fn offline() -> Result<()> {
 
     Ok(())
 }
+
+/// Continue past a package that fails to build, installing the remaining packages and reporting
+/// the failure.
+#[test]
+fn install_keep_going() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\nWerkzeug==0.9.6")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--only-binary")
+        .arg("werkzeug")
+        .arg("--keep-going"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    error: Failed to install 1 package
+     x werkzeug==0.9.6
+       Caused by: Failed to download and build: werkzeug==0.9.6
+       Caused by: Building source distributions is disabled
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// With builds disabled, report every package that would need to be built, rather than failing on
+/// the first.
+#[test]
+fn only_binary_reports_all() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\nWerkzeug==0.9.6\nfuture==0.18.3")?;
+
+    let filters: Vec<_> = [(r"environment is `[^`]+`", "environment is `[TAG]`")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("requirements.txt")
+        .arg("--only-binary")
+        .arg(":all:"), @r###"
+    success: false
+    exit_code: 6
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    error: Building source distributions is disabled, but 2 packages would need to be built:
+      future==0.18.3 has no wheels
+      werkzeug==0.9.6 has no wheels
+
+    The most specific wheel tag supported by the current environment is `[TAG]`.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Respect `--only-binary` when provided in a `requirements.txt` file.
+#[test]
+fn install_only_binary_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        --only-binary werkzeug
+        MarkupSafe==2.1.3
+        Werkzeug==0.9.6
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--keep-going"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    error: Failed to install 1 package
+     x werkzeug==0.9.6
+       Caused by: Failed to download and build: werkzeug==0.9.6
+       Caused by: Building source distributions is disabled
+    "###
+    );
+
+    Ok(())
+}
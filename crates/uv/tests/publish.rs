@@ -0,0 +1,251 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// A request, as received by the test server.
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Respond to every request with the given status and body, returning the address of the server
+/// and the requests it received.
+fn serve(
+    status: &'static str,
+    body: &'static str,
+) -> Result<(SocketAddr, Arc<Mutex<Vec<Request>>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                return;
+            };
+            let mut reader = BufReader::new(&mut stream);
+
+            // Read the request line and headers.
+            let mut line = String::new();
+            if reader.read_line(&mut line).is_err() {
+                continue;
+            }
+            let mut parts = line.split(' ');
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+            let mut authorization = None;
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+                    break;
+                }
+                let Some((name, value)) = line.split_once(':') else {
+                    continue;
+                };
+                if name.eq_ignore_ascii_case("authorization") {
+                    authorization = Some(value.trim().to_string());
+                } else if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or_default();
+                }
+            }
+
+            // Read the body in full, such that the client sees the response.
+            let mut request_body = vec![0; content_length];
+            if reader.read_exact(&mut request_body).is_err() {
+                continue;
+            }
+            received.lock().unwrap().push(Request {
+                method,
+                path,
+                authorization,
+                body: request_body,
+            });
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    Ok((addr, requests))
+}
+
+/// Copy a wheel with valid metadata into the test directory.
+fn wheel(context: &TestContext) -> Result<&'static str> {
+    let filename = "tqdm-1000.0.0-py3-none-any.whl";
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("../.."))?;
+    fs_err::copy(
+        project_root.join("scripts/wheels").join(filename),
+        context.temp_dir.child(filename),
+    )?;
+    Ok(filename)
+}
+
+/// Without any credentials, the upload should fail before contacting the index.
+#[test]
+fn publish_missing_credentials() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context
+        .temp_dir
+        .child("dist")
+        .child("example-0.1.0-py3-none-any.whl")
+        .touch()?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("publish")
+        .env_remove("UV_PUBLISH_USERNAME")
+        .env_remove("UV_PUBLISH_PASSWORD")
+        .env_remove("UV_PUBLISH_TOKEN")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 6
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No credentials provided; pass `--token`, `--username` and `--password`, or `--trusted-publishing`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Files that aren't wheels or source distributions should be rejected.
+#[test]
+fn publish_invalid_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.temp_dir.child("README.md").touch()?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("publish")
+        .arg("README.md")
+        .arg("--token")
+        .arg("pypi-token")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 6
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Expected a wheel (`.whl`) or source distribution (`.tar.gz` or `.zip`), but found: README.md
+    "###
+    );
+
+    Ok(())
+}
+
+/// Upload a wheel to a local server, checking the request that was sent.
+#[test]
+fn publish_upload() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let filename = wheel(&context)?;
+    let (addr, requests) = serve("200 OK", "")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("publish")
+        .arg(filename)
+        .arg("--publish-url")
+        .arg(format!("http://{addr}/legacy/"))
+        .arg("--token")
+        .arg("pypi-token")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uploading tqdm-1000.0.0-py3-none-any.whl
+    Uploaded 1 file in [TIME]
+    "###
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/legacy/");
+    // `__token__:pypi-token`, base64-encoded.
+    assert_eq!(
+        request.authorization.as_deref(),
+        Some("Basic X190b2tlbl9fOnB5cGktdG9rZW4=")
+    );
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains("name=\":action\"\r\n\r\nfile_upload\r\n"));
+    assert!(body.contains("name=\"name\"\r\n\r\ntqdm\r\n"));
+    assert!(body.contains("name=\"version\"\r\n\r\n1000.0.0\r\n"));
+    assert!(body.contains("name=\"requires_python\"\r\n\r\n>=3.10, <4.0\r\n"));
+    assert!(body.contains(&format!("filename=\"{filename}\"")));
+
+    Ok(())
+}
+
+/// Uploads aren't idempotent, so a failed upload must not be retried.
+#[test]
+fn publish_upload_no_retry() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let filename = wheel(&context)?;
+    let (addr, requests) = serve("503 Service Unavailable", "Try again later")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("publish")
+        .arg(filename)
+        .arg("--publish-url")
+        .arg(format!("http://{addr}/legacy/"))
+        .arg("--token")
+        .arg("pypi-token")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Uploading tqdm-1000.0.0-py3-none-any.whl
+    error: Failed to upload: tqdm-1000.0.0-py3-none-any.whl
+      Caused by: Upload was rejected by the server (503 Service Unavailable): Try again later
+    "###
+    );
+
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    Ok(())
+}
+
+/// Publishing requires network access.
+#[test]
+fn publish_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let filename = wheel(&context)?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("publish")
+        .arg(filename)
+        .arg("--token")
+        .arg("pypi-token")
+        .arg("--offline")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Publishing requires network access, but `--offline` was provided
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use crate::common::get_bin;
+
+mod common;
+
+/// Create a toolchain directory entry with a Python executable at the expected location.
+fn create_toolchain(toolchains: &assert_fs::TempDir, name: &str) -> Result<()> {
+    let executable = if cfg!(windows) {
+        "python.exe".to_string()
+    } else {
+        "bin/python3".to_string()
+    };
+    toolchains.child(name).child(executable).touch()?;
+    Ok(())
+}
+
+/// List the installed toolchains, newest first, skipping partial installations and unrelated
+/// directories.
+#[test]
+fn python_list_installed() -> Result<()> {
+    let toolchains = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+
+    create_toolchain(&toolchains, "cpython-3.11.7")?;
+    create_toolchain(&toolchains, "cpython-3.12.1")?;
+    // A partial installation, without an executable.
+    toolchains.child("cpython-3.10.13").create_dir_all()?;
+    // A directory that isn't a toolchain.
+    create_toolchain(&toolchains, "pypy-3.10.13")?;
+
+    Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TOOLCHAIN_DIR", toolchains.path())
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::is_match(r"(?s)^3\.12\.1 .*\n3\.11\.7 [^\n]*\n$")?
+                .and(predicate::str::contains("3.10.13").not()),
+        );
+
+    Ok(())
+}
+
+/// Listing the available toolchains with `--offline` fails rather than reaching GitHub.
+#[test]
+fn python_list_available_offline() -> Result<()> {
+    let toolchains = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+
+    Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--available")
+        .arg("--offline")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TOOLCHAIN_DIR", toolchains.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Network connectivity is disabled, but the requested data wasn't found in the cache for: `https://api.github.com/repos/indygreg/python-build-standalone/releases/latest`",
+        ));
+
+    Ok(())
+}
+
+/// Installing a toolchain with `--offline` fails without installing anything.
+#[test]
+fn python_install_offline() -> Result<()> {
+    let toolchains = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+
+    Command::new(get_bin())
+        .arg("python")
+        .arg("install")
+        .arg("3.12")
+        .arg("--offline")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TOOLCHAIN_DIR", toolchains.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Network connectivity is disabled, but the requested data wasn't found in the cache for: `https://api.github.com/repos/indygreg/python-build-standalone/releases/latest`",
+        ));
+
+    // Nothing was written to the toolchain directory.
+    assert!(std::fs::read_dir(toolchains.path())?.next().is_none());
+
+    Ok(())
+}
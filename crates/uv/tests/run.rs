@@ -0,0 +1,189 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot, EXCLUDE_NEWER};
+
+mod common;
+
+/// Run a script that declares its dependencies in an inline `script` metadata block.
+#[test]
+fn run_script_dependencies() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let script = temp_dir.child("script.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # requires-python = ">=3.8"
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        # ///
+
+        import iniconfig
+
+        print("Hello, world!")
+    "#})?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("run")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("script.py")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hello, world!
+
+    ----- stderr -----
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// A `# ///` line within the metadata block doesn't end it; the block ends at the last `# ///`
+/// line before the next non-comment line.
+#[test]
+fn run_script_closing_marker() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let script = temp_dir.child("script.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        #
+        # [tool.example]
+        # banner = """
+        # ///
+        # """
+        # ///
+
+        import iniconfig
+
+        print("Hello, world!")
+    "#})?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("run")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("script.py")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hello, world!
+
+    ----- stderr -----
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a `script` metadata block that is interrupted by a non-comment line before its closing
+/// `# ///` line.
+#[test]
+fn run_script_unterminated() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let script = temp_dir.child("script.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # dependencies = ["iniconfig"]
+        print("Hello, world!")
+        # ///
+    "#})?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("run")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("script.py")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    uv::run::unterminated
+
+      × Unterminated `script` metadata block (expected a closing `# ///` line)
+      help: Each line in the metadata block must either be `#` or start with `# `
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a script with more than one `script` metadata block.
+#[test]
+fn run_script_multiple_blocks() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let script = temp_dir.child("script.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # dependencies = ["iniconfig"]
+        # ///
+
+        # /// script
+        # dependencies = ["anyio"]
+        # ///
+        print("Hello, world!")
+    "#})?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("run")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("script.py")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    uv::run::multiple_blocks
+
+      × Multiple `script` metadata blocks found
+      help: A script may contain at most one `# /// script` block
+    "###
+    );
+
+    Ok(())
+}
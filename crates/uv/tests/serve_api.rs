@@ -0,0 +1,83 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::fs;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `uv serve-api` command that reads its requests from the given file.
+fn command(context: &TestContext, requests: &str) -> Result<Command> {
+    let requests_jsonl = context.temp_dir.child("requests.jsonl");
+    requests_jsonl.write_str(requests)?;
+
+    let mut command = Command::new(get_bin());
+    command
+        .arg("serve-api")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .stdin(fs::File::open(requests_jsonl)?)
+        .current_dir(&context.temp_dir);
+    Ok(command)
+}
+
+/// Resolve a set of requirements, then shut down.
+#[test]
+fn serve_api_resolve() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requests = indoc! {r#"
+        {"jsonrpc": "2.0", "id": 1, "method": "resolve", "params": {"requirements": ["tomli==2.0.1"]}}
+        {"jsonrpc": "2.0", "id": 2, "method": "shutdown"}
+        {"jsonrpc": "2.0", "id": 3, "method": "environment"}
+    "#};
+
+    uv_snapshot!(command(&context, requests)?, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"id":1,"jsonrpc":"2.0","result":{"packages":[{"name":"tomli","version":"2.0.1"}]}}
+    {"id":2,"jsonrpc":"2.0","result":null}
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Malformed messages, unknown methods, and invalid parameters are reported as JSON-RPC errors,
+/// without stopping the server.
+#[test]
+fn serve_api_errors() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requests = indoc! {r#"
+        not json
+        {"jsonrpc": "2.0", "id": 1, "method": "install"}
+        {"jsonrpc": "2.0", "id": 2, "method": "resolve", "params": {"requirements": "flask"}}
+        {"jsonrpc": "2.0", "method": "install"}
+        {"jsonrpc": "2.0", "id": 3, "method": "outdated"}
+    "#};
+
+    uv_snapshot!(command(&context, requests)?, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"error":{"code":-32700,"message":"expected ident at line 1 column 2"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32601,"message":"Unknown method: install"},"id":1,"jsonrpc":"2.0"}
+    {"error":{"code":-32602,"message":"Invalid params: invalid type: string \"flask\", expected a sequence"},"id":2,"jsonrpc":"2.0"}
+    {"id":3,"jsonrpc":"2.0","result":{"packages":[]}}
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot};
+
+mod common;
+
+/// Sync the environments declared in `[tool.uv.envs]` and the workspace members, concurrently.
+#[test]
+fn sync_all() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    temp_dir.child("pyproject.toml").write_str(indoc! {r#"
+        [tool.uv.envs.docs]
+        requirements = ["docs.txt"]
+
+        [tool.uv.workspace]
+        members = ["services/*"]
+    "#})?;
+    temp_dir.child("docs.txt").write_str("tomli==2.0.1")?;
+    temp_dir
+        .child("services")
+        .child("api")
+        .child("requirements.txt")
+        .write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("sync-all")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    docs
+     + tomli==2.0.1
+    services/api
+     + markupsafe==2.1.3
+     + tomli==2.0.1
+    Synced 2 environments in [TIME]
+    "###
+    );
+
+    temp_dir
+        .child(".venvs")
+        .child("docs")
+        .assert(predicates::path::is_dir());
+    temp_dir
+        .child("services")
+        .child("api")
+        .child(".venv")
+        .assert(predicates::path::is_dir());
+
+    // Re-syncing a single environment should be a no-op.
+    uv_snapshot!(Command::new(get_bin())
+        .arg("sync-all")
+        .arg("--env")
+        .arg("docs")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    docs
+    Synced 1 environment in [TIME]
+    "###
+    );
+
+    Ok(())
+}
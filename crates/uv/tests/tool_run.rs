@@ -0,0 +1,153 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+use crate::common::{create_bin_with_executables, get_bin, uv_snapshot, EXCLUDE_NEWER};
+
+mod common;
+
+/// Run a package that doesn't provide any console scripts.
+#[test]
+fn tool_run_no_commands() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("tool")
+        .arg("run")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("iniconfig")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+     + iniconfig==2.0.0
+    uv::tool::no_commands
+
+      × The package `iniconfig` does not provide any commands
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request a command that isn't provided by the given package.
+#[test]
+fn tool_run_missing_command() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("tool")
+        .arg("run")
+        .arg("--from")
+        .arg("flask==3.0.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("flask-cli")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.0
+     + itsdangerous==2.1.2
+     + jinja2==3.1.2
+     + markupsafe==2.1.3
+     + werkzeug==3.0.1
+    uv::tool::missing_command
+
+      × The package `flask` does not provide a `flask-cli` command
+      help: The following commands are available: `flask`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requested package from `--index-url`, rather than from PyPI.
+#[test]
+fn tool_run_index_url() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    // Nothing is listening on the index, so the resolution must fail, rather than falling back to
+    // PyPI.
+    Command::new(get_bin())
+        .arg("tool")
+        .arg("run")
+        .arg("--index-url")
+        .arg("http://localhost:1/simple")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("iniconfig")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .failure()
+        .stderr(
+            predicate::str::contains("http://localhost:1/simple/iniconfig/")
+                .and(predicate::str::contains("+ iniconfig").not()),
+        );
+
+    Ok(())
+}
+
+/// Create a separate environment when `--exclude-newer` changes, rather than reusing the
+/// environment resolved for another date.
+#[test]
+fn tool_run_exclude_newer_environment() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    for (exclude_newer, version) in [
+        ("2022-01-01T00:00:00Z", "iniconfig==1.1.1"),
+        (EXCLUDE_NEWER, "iniconfig==2.0.0"),
+    ] {
+        Command::new(get_bin())
+            .arg("tool")
+            .arg("run")
+            .arg("--python")
+            .arg("3.12")
+            .arg("--cache-dir")
+            .arg(cache_dir.path())
+            .arg("--exclude-newer")
+            .arg(exclude_newer)
+            .arg("iniconfig")
+            .env("UV_TEST_PYTHON_PATH", &bin)
+            .current_dir(&temp_dir)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!("+ {version}")));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "python")]
 
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 use anyhow::Result;
 use assert_fs::prelude::*;
@@ -127,6 +127,69 @@ fn create_venv_defaults_to_cwd() -> Result<()> {
     Ok(())
 }
 
+/// In quiet mode, only the path to the virtual environment is printed.
+#[test]
+fn create_venv_quiet() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("venv")
+        .arg("--quiet")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    .venv
+
+    ----- stderr -----
+    "###
+    );
+
+    venv.assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+/// With `--color never`, verbose logs are written without ANSI escape codes.
+#[test]
+fn create_venv_verbose_no_color() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let output = Command::new(get_bin())
+        .arg("venv")
+        .arg("--verbose")
+        .arg("--color")
+        .arg("never")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("DEBUG"));
+    assert!(!stderr.contains('\u{1b}'));
+
+    Ok(())
+}
+
 #[test]
 fn seed() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -173,6 +236,111 @@ fn seed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn prompt_and_system_site_packages() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--prompt")
+        .arg("ferris")
+        .arg("--system-site-packages")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    "###
+    );
+
+    let pyvenv_cfg = fs_err::read_to_string(venv.child("pyvenv.cfg"))?;
+    assert!(pyvenv_cfg.contains("include-system-site-packages = true"));
+    assert!(pyvenv_cfg.contains("prompt = ferris"));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn relocatable() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--relocatable")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    "###
+    );
+
+    let pyvenv_cfg = fs_err::read_to_string(venv.child("pyvenv.cfg"))?;
+    assert!(pyvenv_cfg.contains("relocatable = true"));
+
+    // The activate scripts shouldn't reference the absolute path to the environment.
+    let root = fs_err::canonicalize(&venv)?;
+    for name in [
+        "activate",
+        "activate.csh",
+        "activate.fish",
+        "activate.nu",
+        "activate.ps1",
+    ] {
+        let activate = fs_err::read_to_string(venv.child("bin").child(name))?;
+        assert!(!activate.contains(&*root.to_string_lossy()), "{name}");
+    }
+
+    Ok(())
+}
+
 #[test]
 fn create_venv_unknown_python_minor() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -396,6 +564,48 @@ fn empty_dir_exists() -> Result<()> {
     Ok(())
 }
 
+/// Create virtual environments at the same path from several concurrent invocations, which should
+/// be serialized rather than racing to remove and recreate the environment.
+#[test]
+fn concurrent_venvs() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let children = (0..4)
+        .map(|_| {
+            Command::new(get_bin())
+                .arg("venv")
+                .arg(venv.as_os_str())
+                .arg("--python")
+                .arg("3.12")
+                .arg("--cache-dir")
+                .arg(cache_dir.path())
+                .arg("--exclude-newer")
+                .arg(EXCLUDE_NEWER)
+                .env("UV_TEST_PYTHON_PATH", bin.clone())
+                .current_dir(&temp_dir)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    for child in children {
+        let output = child.wait_with_output()?;
+        assert!(
+            output.status.success(),
+            "`uv venv` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    venv.child("pyvenv.cfg").assert(predicates::path::is_file());
+
+    Ok(())
+}
+
 #[test]
 fn non_empty_dir_exists() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `uv why` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("why")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Explain a package that's pulled in by multiple chains of requirements.
+#[test]
+fn why_transitive() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "blinker==1.7.0\nclick==8.1.7\nflask==3.0.0\nitsdangerous==2.1.2\njinja2==3.1.2\nmarkupsafe==2.1.3\nwerkzeug==3.0.1",
+    )?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    uv_snapshot!(command(&context).arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask==3.0.0 -> jinja2==3.1.2 [jinja2>=3.1.2] -> markupsafe==2.1.3 [markupsafe>=2.0]
+    flask==3.0.0 -> werkzeug==3.0.1 [werkzeug>=3.0.0] -> markupsafe==2.1.3 [markupsafe>=2.1.1]
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(command(&context).arg("flask"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    flask==3.0.0 is not required by any installed package
+    "###
+    );
+
+    Ok(())
+}
+
+/// Explain a package that isn't installed.
+#[test]
+fn why_missing() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context).arg("flask"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `flask` is not installed
+    "###
+    );
+}